@@ -39,6 +39,8 @@ async fn main() {
         port: 3210,
         db_path: "/tmp/routa-test.db".to_string(),
         static_dir: static_dir.clone(),
+        workflows_dir: None,
+        workspace_tokens: None,
     };
 
     println!("Starting standalone Routa Rust backend on 127.0.0.1:3210...");
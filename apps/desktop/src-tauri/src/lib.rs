@@ -818,11 +818,20 @@ fn start_rust_server(
         port,
         db_path,
         static_dir,
+        workflows_dir: None,
+        workspace_tokens: None,
+        tls_cert_path: None,
+        tls_key_path: None,
+        max_sessions_per_workspace: None,
+        db_encryption_key: None,
     };
 
     // Block startup until the backend is definitely ready so we don't
     // redirect the webview to a stale process that merely happens to own 3210.
-    let app_state = tauri::async_runtime::block_on(server::create_app_state(&config.db_path))
+    let app_state = tauri::async_runtime::block_on(server::create_app_state_with_key(
+        &config.db_path,
+        config.db_encryption_key.as_deref(),
+    ))
         .map_err(|e| format!("Failed to create app state: {e}"))?;
 
     tauri::async_runtime::block_on(rpc_state.set(app_state.clone()));
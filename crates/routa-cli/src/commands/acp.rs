@@ -3,6 +3,8 @@
 //! Provides:
 //!   - `routa acp install <agent_id>` — install an agent (download runtime if needed)
 //!   - `routa acp uninstall <agent_id>` — remove an installed agent
+//!   - `routa acp update <agent_id>` / `routa acp update --all` — move installed
+//!     agents to the registry's latest version
 //!   - `routa acp list` — list agents from the registry with installation status
 //!   - `routa acp installed` — list locally installed agents
 //!   - `routa acp runtime status` — show Node.js / uv runtime health
@@ -41,6 +43,15 @@ pub enum AcpAction {
         /// Agent ID to remove
         agent_id: String,
     },
+    /// Update installed ACP agents to the registry's latest version.
+    Update {
+        /// Installed agent ID to update
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        agent_id: Option<String>,
+        /// Update every installed agent
+        #[arg(long)]
+        all: bool,
+    },
     /// List agents from the ACP registry with their install status.
     List,
     /// List locally-installed ACP agents.
@@ -168,6 +179,129 @@ pub async fn uninstall(state: &AppState, agent_id: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub async fn update(state: &AppState, agent_id: Option<&str>, all: bool) -> Result<(), String> {
+    let _ = state.acp_installation_state.load().await;
+
+    let targets = match agent_id {
+        Some(id) if !all => vec![state
+            .acp_installation_state
+            .get_installed_info(id)
+            .await
+            .ok_or_else(|| format!("Agent '{id}' is not installed"))?],
+        _ => state.acp_installation_state.get_all_installed().await,
+    };
+    if targets.is_empty() {
+        print_json(&serde_json::json!({ "success": true, "updates": [], "total": 0 }));
+        return Ok(());
+    }
+
+    println!("[acp update] Fetching registry…");
+    let registry_json = fetch_registry_json().await?;
+
+    let mut rows = Vec::new();
+    let mut failed = 0;
+    for info in &targets {
+        match update_installed_agent(state, &registry_json, info).await {
+            Ok(row) => rows.push(row),
+            Err(e) => {
+                failed += 1;
+                rows.push(serde_json::json!({
+                    "agentId": info.agent_id,
+                    "oldVersion": info.version,
+                    "updated": false,
+                    "error": e,
+                }));
+            }
+        }
+    }
+
+    print_json(&serde_json::json!({
+        "success": failed == 0,
+        "updates": rows,
+        "total": rows.len(),
+    }));
+    if failed > 0 {
+        return Err(format!("{failed} agent update(s) failed"));
+    }
+    Ok(())
+}
+
+/// Move one installed agent to the version the registry lists. Binary agents
+/// are re-downloaded; npx/uvx agents only record the new version since they
+/// fetch the package on run.
+async fn update_installed_agent(
+    state: &AppState,
+    registry_json: &serde_json::Value,
+    info: &InstalledAgentInfo,
+) -> Result<serde_json::Value, String> {
+    let agent_id = info.agent_id.as_str();
+    let agent = find_agent(registry_json, agent_id)?;
+    let name = agent
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or(agent_id);
+    let latest = agent
+        .get("version")
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty())
+        .unwrap_or("latest")
+        .to_string();
+    let dist = agent
+        .get("distribution")
+        .cloned()
+        .unwrap_or(serde_json::Value::Object(Default::default()));
+
+    let check = state
+        .acp_installation_state
+        .check_update(agent_id, &latest)
+        .await
+        .ok_or_else(|| format!("Agent '{agent_id}' is not installed"))?;
+
+    if check.update_available {
+        println!(
+            "[acp update] Updating '{name}' v{} → v{latest}",
+            info.version
+        );
+        match info.dist_type {
+            DistributionType::Binary => {
+                install_binary(state, agent_id, name, &latest, &dist).await?;
+                state
+                    .acp_binary_manager
+                    .remove_version(agent_id, &info.version)
+                    .await?;
+            }
+            DistributionType::Npx | DistributionType::Uvx => {
+                let key = if info.dist_type == DistributionType::Npx {
+                    "npx"
+                } else {
+                    "uvx"
+                };
+                let package = dist
+                    .get(key)
+                    .and_then(|v| v.get("package"))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .or_else(|| info.package.clone());
+                state
+                    .acp_installation_state
+                    .mark_installed(agent_id, &latest, info.dist_type.clone(), None, package)
+                    .await
+                    .map_err(|e| format!("State update failed: {e}"))?;
+            }
+        }
+    } else {
+        println!("[acp update] '{name}' is up to date (v{})", info.version);
+    }
+
+    Ok(serde_json::json!({
+        "agentId": agent_id,
+        "distributionType": info.dist_type,
+        "oldVersion": check.installed_version,
+        "newVersion": if check.update_available { latest } else { check.installed_version.clone() },
+        "updated": check.update_available,
+    }))
+}
+
 pub async fn install_top_level(
     state: &AppState,
     agent_id: Option<&str>,
@@ -718,10 +852,16 @@ fn canonical_provider_key(id: &str) -> &str {
 #[cfg(test)]
 mod tests {
     use super::{
-        build_inventory_entry, canonical_provider_key, find_inventory_entry, ProviderInventoryEntry,
+        build_inventory_entry, canonical_provider_key, find_inventory_entry,
+        update_installed_agent, ProviderInventoryEntry,
     };
     use routa_core::acp::registry_types::{DistributionType, InstalledAgentInfo};
-    use routa_core::acp::AcpPreset;
+    use routa_core::acp::{AcpBinaryManager, AcpInstallationState, AcpPaths, AcpPreset};
+    use sha2::{Digest, Sha256};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread;
 
     #[test]
     fn canonical_key_maps_aliases() {
@@ -779,4 +919,92 @@ mod tests {
 
         assert!(find_inventory_entry(&entries, "opencode").is_some());
     }
+
+    /// Serve `body` once over HTTP and return the URL it is reachable at.
+    fn serve_once(path: &str, body: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let addr = listener.local_addr().expect("local addr");
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept should succeed");
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(header.as_bytes()).expect("write header");
+            stream.write_all(body).expect("write body");
+        });
+        format!("http://{addr}/{path}")
+    }
+
+    #[tokio::test]
+    async fn update_redownloads_binary_agent_at_registry_version() {
+        const BINARY: &[u8] = b"#!/bin/sh\necho agent 1.10.0\n";
+        let dir = tempfile::tempdir().unwrap();
+        let paths = AcpPaths::with_base_dir(dir.path());
+        let mut inner =
+            routa_core::AppStateInner::new(routa_core::Database::open_in_memory().unwrap());
+        inner.acp_binary_manager = AcpBinaryManager::new(paths.clone());
+        inner.acp_installation_state = AcpInstallationState::new(paths.clone());
+        let state = Arc::new(inner);
+
+        let old_dir = paths.agent_version_dir("fake-agent", "1.9.0");
+        std::fs::create_dir_all(&old_dir).unwrap();
+        state
+            .acp_installation_state
+            .mark_installed(
+                "fake-agent",
+                "1.9.0",
+                DistributionType::Binary,
+                Some(old_dir.join("fake-agent").to_string_lossy().to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let checksum: String = Sha256::digest(BINARY)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let registry = serde_json::json!({
+            "agents": [{
+                "id": "fake-agent",
+                "name": "Fake Agent",
+                "version": "1.10.0",
+                "distribution": {
+                    "binary": {
+                        (AcpPaths::current_platform()): {
+                            "archive": serve_once("fake-agent", BINARY),
+                            "cmd": "./fake-agent",
+                            "sha256": checksum,
+                        }
+                    }
+                }
+            }]
+        });
+        let info = state
+            .acp_installation_state
+            .get_installed_info("fake-agent")
+            .await
+            .unwrap();
+
+        let row = update_installed_agent(&state, &registry, &info)
+            .await
+            .expect("update should succeed");
+
+        assert_eq!(row["oldVersion"], "1.9.0");
+        assert_eq!(row["newVersion"], "1.10.0");
+        assert_eq!(row["updated"], true);
+        let updated = state
+            .acp_installation_state
+            .get_installed_info("fake-agent")
+            .await
+            .unwrap();
+        assert_eq!(updated.version, "1.10.0");
+        let binary_path = updated.binary_path.expect("binary path recorded");
+        assert_eq!(std::fs::read(&binary_path).unwrap(), BINARY);
+        assert!(binary_path.contains("1.10.0"));
+        assert!(!old_dir.exists());
+    }
 }
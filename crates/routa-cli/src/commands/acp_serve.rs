@@ -16,7 +16,7 @@
 use std::sync::Arc;
 
 use routa_core::models::agent::AgentRole;
-use routa_core::orchestration::{OrchestratorConfig, RoutaOrchestrator, SpecialistConfig};
+use routa_core::orchestration::{RoutaOrchestrator, SpecialistConfig};
 use routa_core::rpc::RpcRouter;
 use routa_core::state::AppState;
 use serde_json::Value;
@@ -226,14 +226,7 @@ async fn handle_session_new(
     }
 
     // Register with orchestrator
-    let acp = Arc::new(server.state.acp_manager.clone());
-    let orchestrator = RoutaOrchestrator::new(
-        OrchestratorConfig::default(),
-        acp,
-        server.state.agent_store.clone(),
-        server.state.task_store.clone(),
-        server.state.event_bus.clone(),
-    );
+    let orchestrator = server.state.orchestrator.clone();
     orchestrator
         .register_agent_session(&agent_id, &routa_session_id)
         .await;
@@ -251,7 +244,7 @@ async fn handle_session_new(
     );
     server
         .orchestrators
-        .insert(acp_session_id.clone(), Arc::new(orchestrator));
+        .insert(acp_session_id.clone(), orchestrator);
 
     serde_json::json!({
         "jsonrpc": "2.0",
@@ -361,10 +354,15 @@ async fn handle_session_prompt(
                     }
                     write_response(&mut stdout_clone, &notification).await;
                 }
-                Ok(Err(_)) => {
-                    // Channel closed
-                    break;
+                Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped))) => {
+                    // stdout belongs to the ACP client; just note the gap in the log.
+                    tracing::warn!(
+                        "[acp-serve] Skipped {} updates for {}",
+                        skipped,
+                        routa_session_id
+                    );
                 }
+                Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => break,
                 Err(_) => {
                     // Timeout — check if still alive
                     if !acp_manager.is_alive(&routa_session_id).await {
@@ -1,11 +1,13 @@
 //! `routa agent` — Agent management commands.
 
-use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use clap::ValueEnum;
 use dialoguer::{theme::ColorfulTheme, Input, Select};
 use routa_core::acp::SessionLaunchOptions;
-use routa_core::orchestration::{OrchestratorConfig, RoutaOrchestrator, SpecialistConfig};
+use routa_core::models::agent::Agent;
+use routa_core::models::message::{Message, MessageRole};
+use routa_core::orchestration::SpecialistConfig;
 use routa_core::rpc::RpcRouter;
 use routa_core::state::AppState;
 use routa_core::workflow::specialist::{SpecialistDef, SpecialistLoader};
@@ -413,6 +415,139 @@ pub async fn summary(state: &AppState, agent_id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Report completion to the parent agent via `agents.reportToParent`, which
+/// parses an incomplete report the same way the MCP tool does.
+pub async fn report(
+    state: &AppState,
+    agent_id: &str,
+    task_id: Option<&str>,
+    summary: &str,
+    success: Option<bool>,
+    files_modified: &[String],
+) -> Result<(), String> {
+    let mut params = serde_json::json!({ "agentId": agent_id, "summary": summary });
+    if let Some(task_id) = task_id {
+        params["taskId"] = serde_json::json!(task_id);
+    }
+    if let Some(success) = success {
+        params["success"] = serde_json::json!(success);
+    }
+    if !files_modified.is_empty() {
+        params["filesModified"] = serde_json::json!(files_modified);
+    }
+    let router = RpcRouter::new(state.clone());
+    let response = router
+        .handle_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "agents.reportToParent",
+            "params": params
+        }))
+        .await;
+    print_json(&response);
+    Ok(())
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum ConversationFormat {
+    Md,
+    Json,
+}
+
+/// Print an agent's stored conversation, oldest message first.
+pub async fn conversation(
+    state: &AppState,
+    agent_id: &str,
+    last_n: Option<usize>,
+    format: ConversationFormat,
+    include_tool_calls: bool,
+) -> Result<(), String> {
+    let output = render_conversation(state, agent_id, last_n, format, include_tool_calls).await?;
+    print!("{output}");
+    Ok(())
+}
+
+async fn render_conversation(
+    state: &AppState,
+    agent_id: &str,
+    last_n: Option<usize>,
+    format: ConversationFormat,
+    include_tool_calls: bool,
+) -> Result<String, String> {
+    let agent = state
+        .agent_store
+        .get(agent_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Agent not found: {agent_id}"))?;
+    let mut messages = match last_n {
+        Some(n) => state.conversation_store.get_last_n(agent_id, n).await,
+        None => state.conversation_store.get_conversation(agent_id).await,
+    }
+    .map_err(|e| e.to_string())?;
+    if !include_tool_calls {
+        messages.retain(|message| message.role != MessageRole::Tool);
+    }
+
+    Ok(match format {
+        ConversationFormat::Json => {
+            let value = serde_json::json!({
+                "agentId": agent.id,
+                "agentName": agent.name,
+                "messageCount": messages.len(),
+                "messages": messages,
+            });
+            format!(
+                "{}\n",
+                serde_json::to_string_pretty(&value).unwrap_or_default()
+            )
+        }
+        ConversationFormat::Md => conversation_markdown(&agent, &messages),
+    })
+}
+
+fn conversation_markdown(agent: &Agent, messages: &[Message]) -> String {
+    let mut out = format!(
+        "# {} ({})\n\n{} · {} message(s)\n",
+        agent.name,
+        agent.role.as_str(),
+        agent.id,
+        messages.len()
+    );
+    for message in messages {
+        let mut heading = match (&message.role, &message.tool_name) {
+            (MessageRole::Tool, Some(tool)) => format!("Tool `{tool}`"),
+            (role, _) => {
+                let role = role.as_str().to_lowercase();
+                let mut chars = role.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        };
+        if let Some(turn) = message.turn {
+            heading.push_str(&format!(" · turn {turn}"));
+        }
+        heading.push_str(&format!(
+            " · {}",
+            message.timestamp.format("%Y-%m-%d %H:%M:%S")
+        ));
+        out.push_str(&format!("\n## {heading}\n\n"));
+
+        if message.role == MessageRole::Tool {
+            if let Some(args) = &message.tool_args {
+                out.push_str(&format!("```json\n{args}\n```\n\n"));
+            }
+            out.push_str(&format!("```\n{}\n```\n", message.content.trim_end()));
+        } else {
+            out.push_str(message.content.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
 pub async fn run(state: &AppState, args: RunArgs<'_>) -> Result<(), String> {
     run_internal(state, args, false).await.map(|_| ())
 }
@@ -740,14 +875,7 @@ async fn execute_specialist_run(
         eprintln!("Failed to mark agent {agent_id} ACTIVE: {err}");
     }
 
-    let acp = Arc::new(state.acp_manager.clone());
-    let orchestrator = RoutaOrchestrator::new(
-        OrchestratorConfig::default(),
-        acp,
-        state.agent_store.clone(),
-        state.task_store.clone(),
-        state.event_bus.clone(),
-    );
+    let orchestrator = state.orchestrator.clone();
     orchestrator
         .register_agent_session(&agent_id, &session_id)
         .await;
@@ -813,7 +941,7 @@ async fn execute_specialist_run(
     let mut prompt_error: Option<String> = None;
     let mut prompt_finished = false;
     metrics.prompt_status = Some("pending".to_string());
-    let prompt_future = state.acp_manager.prompt(&session_id, &initial_prompt);
+    let prompt_future = state.acp_manager.prompt_raw(&session_id, &initial_prompt);
     tokio::pin!(prompt_future);
 
     loop {
@@ -925,7 +1053,13 @@ async fn execute_specialist_run(
                             break;
                         }
                     }
-                    Err(_) => {
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        idle_count = 0;
+                        if !output_json {
+                            eprintln!("[routa] Fell behind the agent; skipped {skipped} updates");
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => {
                         if let Some(renderer) = renderer.as_mut() {
                             renderer.finish();
                         }
@@ -1508,8 +1642,9 @@ async fn ensure_workspace(router: &RpcRouter, workspace_id: &str) -> Result<Stri
 #[cfg(test)]
 mod tests {
     use super::{
-        parse_prompt_mention, parse_specialist_json_output, resolve_specialist_output,
-        should_finish_non_journey_run, SpecialistOutputSnapshot,
+        parse_prompt_mention, parse_specialist_json_output, render_conversation,
+        resolve_specialist_output, should_finish_non_journey_run, ConversationFormat,
+        SpecialistOutputSnapshot,
     };
     use routa_core::orchestration::SpecialistConfig;
 
@@ -1726,4 +1861,80 @@ mod tests {
             "{\"summary\":{\"mode\":\"dry-run\"},\"verificationPlan\":[{\"label\":\"verify\"}],\"warnings\":[]}"
         );
     }
+
+    #[tokio::test]
+    async fn renders_seeded_conversation_as_markdown() {
+        use routa_core::models::agent::{Agent, AgentRole};
+        use routa_core::models::message::{Message, MessageRole};
+        use routa_core::{AppState, AppStateInner, Database};
+        use std::sync::Arc;
+
+        let state: AppState = Arc::new(AppStateInner::new(
+            Database::open_in_memory().expect("in-memory db should open"),
+        ));
+        state.workspace_store.ensure_default().await.unwrap();
+        state
+            .agent_store
+            .save(&Agent::new(
+                "crafter-1".to_string(),
+                "fix-login".to_string(),
+                AgentRole::Crafter,
+                "default".to_string(),
+                None,
+                None,
+                None,
+            ))
+            .await
+            .unwrap();
+        let seed = [
+            (MessageRole::User, "Fix the login bug", None),
+            (MessageRole::Tool, "fn login() {}", Some("read_file")),
+            (MessageRole::Assistant, "Patched the session refresh.", None),
+        ];
+        for (index, (role, content, tool)) in seed.into_iter().enumerate() {
+            let mut message = Message::new(
+                format!("m{index}"),
+                "crafter-1".to_string(),
+                role,
+                content.to_string(),
+                tool.map(str::to_string),
+                None,
+                Some(1),
+            );
+            message.timestamp += chrono::Duration::seconds(index as i64);
+            state.conversation_store.append(&message).await.unwrap();
+        }
+
+        let md = render_conversation(&state, "crafter-1", None, ConversationFormat::Md, false)
+            .await
+            .unwrap();
+        assert!(md.starts_with("# fix-login (CRAFTER)\n\ncrafter-1 · 2 message(s)\n"));
+        assert!(md.contains("## User · turn 1 · "));
+        assert!(md.contains("Fix the login bug\n"));
+        assert!(md.contains("## Assistant · turn 1 · "));
+        assert!(!md.contains("read_file"));
+
+        let md = render_conversation(&state, "crafter-1", None, ConversationFormat::Md, true)
+            .await
+            .unwrap();
+        assert!(md.contains("## Tool `read_file` · turn 1 · "));
+        assert!(md.contains("```\nfn login() {}\n```\n"));
+
+        let json =
+            render_conversation(&state, "crafter-1", Some(1), ConversationFormat::Json, true)
+                .await
+                .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["messageCount"], 1);
+        assert_eq!(
+            json["messages"][0]["content"],
+            "Patched the session refresh."
+        );
+
+        assert!(
+            render_conversation(&state, "ghost", None, ConversationFormat::Md, false)
+                .await
+                .is_err()
+        );
+    }
 }
@@ -8,12 +8,11 @@
 //! picker so you can switch context without leaving the chat.
 
 use std::io::{self, BufRead, Write};
-use std::sync::Arc;
 
 use console::style;
 use dialoguer::{theme::ColorfulTheme, Select};
 use routa_core::models::agent::AgentRole;
-use routa_core::orchestration::{OrchestratorConfig, RoutaOrchestrator, SpecialistConfig};
+use routa_core::orchestration::SpecialistConfig;
 use routa_core::rpc::RpcRouter;
 use routa_core::state::AppState;
 use routa_core::store::acp_session_store::CreateAcpSessionParams;
@@ -145,6 +144,7 @@ pub async fn run(
                             custom_command: None,
                             custom_args: None,
                             parent_session_id: None,
+                            metadata: None,
                         })
                         .await
                         .map_err(|e| format!("Failed to persist session {session_id}: {e}"))?;
@@ -166,14 +166,7 @@ pub async fn run(
         println!("  {} Session: {}", style("●").green(), session_id);
     }
 
-    let acp = Arc::new(state.acp_manager.clone());
-    let orchestrator = RoutaOrchestrator::new(
-        OrchestratorConfig::default(),
-        acp,
-        state.agent_store.clone(),
-        state.task_store.clone(),
-        state.event_bus.clone(),
-    );
+    let orchestrator = state.orchestrator.clone();
     orchestrator
         .register_agent_session(&agent_id, &session_id)
         .await;
@@ -426,7 +419,10 @@ async fn prompt_and_stream_until_idle(
                             break;
                         }
                     }
-                    Err(_) => break,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        eprintln!("[routa] Fell behind the agent; skipped {skipped} updates");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
                 }
             }
             _ = &mut tick => {
@@ -0,0 +1,73 @@
+//! `routa db` — Check the database file for corruption and repair it.
+
+use routa_core::Database;
+use serde_json::json;
+
+use super::{exit_code, print_json, CliError};
+
+/// Run `PRAGMA integrity_check` on `db_path` without creating or migrating
+/// it. A file that cannot be read is reported as not ok.
+pub fn check_status(db_path: &str) -> Result<serde_json::Value, CliError> {
+    let status = Database::inspect(db_path)?;
+    Ok(json!({
+        "path": db_path,
+        "ok": status.ok,
+        "problems": status.problems,
+        "schemaVersion": status.schema_version,
+    }))
+}
+
+pub fn check(db_path: &str) -> Result<(), CliError> {
+    let status = check_status(db_path)?;
+    print_json(&status);
+    if status["ok"] != true {
+        return Err(CliError::new(
+            exit_code::DATABASE,
+            format!("{db_path} failed its integrity check; run `routa db repair --db {db_path}`"),
+        ));
+    }
+    Ok(())
+}
+
+pub fn repair(db_path: &str) -> Result<(), CliError> {
+    let (_, report) = Database::open_with_recovery(db_path)?;
+    match report {
+        Some(report) => {
+            println!("Rebuilt {db_path}; the damaged file was kept as a backup");
+            print_json(&serde_json::to_value(&report).unwrap_or_default());
+        }
+        None => println!("{db_path} is healthy; nothing to repair"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_status_reports_healthy_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        drop(Database::open(&path.to_string_lossy()).unwrap());
+
+        let status = check_status(&path.to_string_lossy()).unwrap();
+
+        assert_eq!(status["ok"], true);
+        assert_eq!(status["problems"], json!([]));
+        assert!(status["schemaVersion"].as_i64().unwrap() > 0);
+    }
+
+    #[test]
+    fn check_status_reports_unreadable_file_instead_of_failing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        std::fs::write(&path, vec![0x5a; 8192]).unwrap();
+
+        let status = check_status(&path.to_string_lossy()).unwrap();
+
+        assert_eq!(status["ok"], false);
+        assert_eq!(status["schemaVersion"], serde_json::Value::Null);
+        assert!(check(&path.to_string_lossy()).is_err());
+    }
+}
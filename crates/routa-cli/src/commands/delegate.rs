@@ -1,11 +1,10 @@
 //! `routa delegate` — Delegate a task to a specialist agent with ACP spawning.
 
-use std::sync::Arc;
-
-use routa_core::orchestration::{DelegateWithSpawnParams, OrchestratorConfig, RoutaOrchestrator};
+use routa_core::models::agent::ModelTier;
+use routa_core::orchestration::DelegateWithSpawnParams;
 use routa_core::state::AppState;
 
-use super::print_json;
+use super::{exit_code, print_json, CliError};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn run(
@@ -16,17 +15,22 @@ pub async fn run(
     workspace_id: &str,
     specialist: &str,
     provider: Option<&str>,
+    model_tier: Option<&str>,
     cwd: Option<&str>,
     wait_mode: &str,
-) -> Result<(), String> {
-    let acp = Arc::new(state.acp_manager.clone());
-    let orchestrator = RoutaOrchestrator::new(
-        OrchestratorConfig::default(),
-        acp,
-        state.agent_store.clone(),
-        state.task_store.clone(),
-        state.event_bus.clone(),
-    );
+    isolate: bool,
+) -> Result<(), CliError> {
+    let model_tier = match model_tier {
+        None => None,
+        Some(tier) => Some(
+            ModelTier::from_str(&tier.to_ascii_uppercase()).ok_or_else(|| {
+                CliError::new(
+                    exit_code::BAD_ARGS,
+                    format!("Unknown model tier: {tier}. Use FAST, BALANCED, or SMART."),
+                )
+            })?,
+        ),
+    };
 
     let params = DelegateWithSpawnParams {
         task_id: task_id.to_string(),
@@ -35,16 +39,24 @@ pub async fn run(
         workspace_id: workspace_id.to_string(),
         specialist: specialist.to_string(),
         provider: provider.map(|s| s.to_string()),
+        model_tier,
         cwd: cwd.map(|s| s.to_string()),
         additional_instructions: None,
         wait_mode: wait_mode.to_string(),
+        isolate,
+        caller_depth: None,
     };
 
-    let result = orchestrator
-        .delegate_task_with_spawn(params)
-        .await
-        .map_err(|e| e.to_string())?;
+    let result = state.orchestrator.delegate_task_with_spawn(params).await?;
 
     print_json(&serde_json::to_value(&result).unwrap());
+    if !result.success {
+        return Err(CliError::new(
+            exit_code::PROVIDER,
+            result
+                .error
+                .unwrap_or_else(|| "Delegation failed".to_string()),
+        ));
+    }
     Ok(())
 }
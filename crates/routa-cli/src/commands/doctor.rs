@@ -0,0 +1,213 @@
+//! `routa doctor` — Environment diagnostics.
+//!
+//! Checks the things new installs most often trip over (agent CLIs missing
+//! from PATH, an unwritable database or ACP data directory, no network access
+//! to the ACP registry) and prints a checklist with remediation hints.
+
+use std::time::Duration;
+
+use console::style;
+use routa_core::acp::{get_presets, registry_fetch, AcpPaths};
+use routa_core::Database;
+
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Something is off, but Routa can still run.
+    Warn,
+    /// Routa cannot work until this is fixed.
+    Fail,
+}
+
+#[derive(Debug, Clone)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn problem(
+        name: &'static str,
+        status: CheckStatus,
+        detail: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Run every check, print the checklist, and fail if a critical check failed.
+pub async fn run(db_path: &str) -> Result<(), String> {
+    let checks = vec![
+        check_path(),
+        check_database_path(db_path),
+        check_providers(),
+        check_acp_paths(&AcpPaths::new()),
+        check_registry().await,
+    ];
+    print_checks(&checks);
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(format!("{failed} critical check(s) failed"));
+    }
+    Ok(())
+}
+
+fn print_checks(checks: &[DoctorCheck]) {
+    println!("Routa doctor\n");
+    for check in checks {
+        let mark = match check.status {
+            CheckStatus::Pass => style("✔").green(),
+            CheckStatus::Warn => style("!").yellow(),
+            CheckStatus::Fail => style("✘").red(),
+        };
+        println!("  {} {:<10} {}", mark, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("    {} {}", style("→").dim(), style(hint).dim());
+        }
+    }
+    println!();
+}
+
+/// The login-shell PATH resolved by `shell_env`, which agent spawns use.
+pub fn check_path() -> DoctorCheck {
+    let entries = routa_core::shell_env::full_path()
+        .split(if cfg!(windows) { ';' } else { ':' })
+        .filter(|p| !p.is_empty())
+        .count();
+    if entries == 0 {
+        return DoctorCheck::problem(
+            "PATH",
+            CheckStatus::Fail,
+            "could not resolve a PATH",
+            "Set PATH in your shell profile so agent CLIs can be found",
+        );
+    }
+    DoctorCheck::pass("PATH", format!("{entries} directories resolved"))
+}
+
+pub fn check_database_path(db_path: &str) -> DoctorCheck {
+    match Database::open(db_path) {
+        Ok(db) => check_database(&db),
+        Err(e) => DoctorCheck::problem(
+            "database",
+            CheckStatus::Fail,
+            format!("cannot open {db_path}: {e}"),
+            "Pass a writable location with --db or ROUTA_DB_PATH",
+        ),
+    }
+}
+
+/// Verify the database accepts a write lock; the probe is rolled back.
+pub fn check_database(db: &Database) -> DoctorCheck {
+    match db.with_conn(|conn| conn.execute_batch("BEGIN IMMEDIATE; ROLLBACK;")) {
+        Ok(()) => DoctorCheck::pass("database", "open and writable"),
+        Err(e) => DoctorCheck::problem(
+            "database",
+            CheckStatus::Fail,
+            format!("not writable: {e}"),
+            "Check file permissions, or that no other process holds the database lock",
+        ),
+    }
+}
+
+/// At least one provider CLI from the ACP presets must be on PATH.
+pub fn check_providers() -> DoctorCheck {
+    let found: Vec<String> = get_presets()
+        .into_iter()
+        .filter(|preset| routa_core::shell_env::which(&preset.command).is_some())
+        .map(|preset| preset.name)
+        .collect();
+    if found.is_empty() {
+        return DoctorCheck::problem(
+            "providers",
+            CheckStatus::Fail,
+            "no agent CLI found on PATH",
+            "Install one, e.g. `routa install opencode` or `npm i -g opencode-ai`",
+        );
+    }
+    DoctorCheck::pass("providers", format!("found {}", found.join(", ")))
+}
+
+/// The ACP data directory must exist and accept new files.
+pub fn check_acp_paths(paths: &AcpPaths) -> DoctorCheck {
+    let base = paths.base_dir();
+    let probe = base.join(".routa-doctor");
+    let result = paths
+        .ensure_directories()
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => DoctorCheck::pass("acp dir", format!("{} is writable", base.display())),
+        Err(e) => DoctorCheck::problem(
+            "acp dir",
+            CheckStatus::Fail,
+            format!("{} is not writable: {e}", base.display()),
+            "Fix permissions on the directory; agent installs are stored there",
+        ),
+    }
+}
+
+/// The registry is only needed to install agents, so failures are warnings.
+pub async fn check_registry() -> DoctorCheck {
+    let hint = "Check network/proxy settings; installed agents keep working offline";
+    match tokio::time::timeout(REGISTRY_TIMEOUT, registry_fetch::fetch_live_registry_json()).await {
+        Ok(Ok(_)) => DoctorCheck::pass("registry", "reachable"),
+        Ok(Err(e)) => DoctorCheck::problem("registry", CheckStatus::Warn, e, hint),
+        Err(_) => DoctorCheck::problem(
+            "registry",
+            CheckStatus::Warn,
+            format!("timed out after {}s", REGISTRY_TIMEOUT.as_secs()),
+            hint,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_in_memory_database_as_ok() {
+        let db = Database::open_in_memory().unwrap();
+
+        let check = check_database(&db);
+
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert!(check.hint.is_none());
+    }
+
+    #[test]
+    fn reports_unwritable_acp_dir_as_failure() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("not-a-dir");
+        std::fs::write(&file, "").unwrap();
+
+        let check = check_acp_paths(&AcpPaths::with_base_dir(file));
+
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.hint.is_some());
+    }
+}
@@ -0,0 +1,122 @@
+//! `routa events` — Watch coordination events from a running Routa server.
+//!
+//! `routa events tail` subscribes to `/api/events/stream` and prints each
+//! `AgentEvent` as it arrives, similar to `kubectl get events -w`.
+
+use std::future::Future;
+use std::io::Write;
+
+use console::style;
+use reqwest::Client;
+use routa_core::events::{AgentEvent, AgentEventType};
+
+use super::truncate_text;
+
+pub async fn tail(server_url: &str, workspace_id: &str, types: &[String]) -> Result<(), String> {
+    eprintln!("Tailing events in workspace {workspace_id} from {server_url} (Ctrl-C to stop)");
+    let mut stdout = std::io::stdout();
+    tail_to(server_url, workspace_id, types, &mut stdout, async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await
+}
+
+/// Stream matching events into `out` until `stop` resolves or the server closes
+/// the stream. Dropping the response on return unsubscribes on the server side.
+pub async fn tail_to<W, F>(
+    server_url: &str,
+    workspace_id: &str,
+    types: &[String],
+    out: &mut W,
+    stop: F,
+) -> Result<(), String>
+where
+    W: Write,
+    F: Future<Output = ()>,
+{
+    let mut event_types = Vec::with_capacity(types.len());
+    for raw in types {
+        let event_type = AgentEventType::from_str(raw).ok_or_else(|| {
+            format!(
+                "Invalid event type: {raw}. Expected one of: {}",
+                routa_core::events::EventBus::all_event_types().join(", ")
+            )
+        })?;
+        event_types.push(event_type.as_str());
+    }
+
+    let url = format!("{}/api/events/stream", server_url.trim_end_matches('/'));
+    let mut query = vec![("workspaceId", workspace_id.to_string())];
+    if !event_types.is_empty() {
+        query.push(("types", event_types.join(",")));
+    }
+
+    let mut response = Client::new()
+        .get(&url)
+        .query(&query)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to {url}: {e}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Event stream request failed ({status}): {body}"));
+    }
+
+    tokio::pin!(stop);
+    let mut buffer: Vec<u8> = Vec::new();
+    loop {
+        tokio::select! {
+            _ = &mut stop => break,
+            chunk = response.chunk() => {
+                let Some(bytes) = chunk.map_err(|e| format!("Event stream error: {e}"))? else {
+                    break;
+                };
+                buffer.extend_from_slice(&bytes);
+                while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=pos).collect();
+                    let line = String::from_utf8_lossy(&line);
+                    let Some(data) = line.trim_end().strip_prefix("data:") else {
+                        continue;
+                    };
+                    match serde_json::from_str::<AgentEvent>(data.trim()) {
+                        Ok(event) => {
+                            writeln!(out, "{}", format_event_line(&event))
+                                .and_then(|_| out.flush())
+                                .map_err(|e| format!("Failed to write event: {e}"))?;
+                        }
+                        Err(e) => tracing::debug!("[events tail] Skipping frame: {e}"),
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Render one event as `time  TYPE  agent  data`, colored by event type.
+pub fn format_event_line(event: &AgentEvent) -> String {
+    let label = format!("{:<20}", event.event_type.as_str());
+    let label = match event.event_type {
+        AgentEventType::AgentError
+        | AgentEventType::TaskFailed
+        | AgentEventType::DelegationCancelled
+        | AgentEventType::SessionLimitReached => style(label).red(),
+        AgentEventType::AgentCompleted
+        | AgentEventType::TaskCompleted
+        | AgentEventType::ReportSubmitted => style(label).green(),
+        AgentEventType::TaskAssigned | AgentEventType::AgentCreated => style(label).cyan(),
+        AgentEventType::TaskStatusChanged
+        | AgentEventType::AgentActivated
+        | AgentEventType::DelegationQueued => style(label).yellow(),
+        AgentEventType::MessageSent | AgentEventType::WorkspaceUpdated => style(label).blue(),
+    };
+    format!(
+        "{}  {}  {:<12} {}",
+        style(event.timestamp.format("%H:%M:%S")).dim(),
+        label,
+        truncate_text(&event.agent_id, 12),
+        truncate_text(&event.data.to_string(), 96)
+    )
+}
@@ -7,7 +7,10 @@ pub mod acp;
 pub mod acp_serve;
 pub mod agent;
 pub mod chat;
+pub mod db;
 pub mod delegate;
+pub mod doctor;
+pub mod events;
 pub mod feature_tree;
 pub mod fitness;
 pub mod graph;
@@ -22,6 +25,7 @@ pub mod server;
 pub mod session;
 pub mod skill;
 pub mod specialist;
+pub mod state;
 pub mod task;
 pub mod team;
 pub mod tui;
@@ -29,9 +33,87 @@ pub mod workflow;
 pub mod workspace;
 
 use chrono::TimeZone;
+use routa_core::rpc::types as rpc_types;
 use routa_core::state::AppState;
+use routa_core::ServerError;
+use std::fmt;
 use std::sync::Arc;
 
+/// Process exit codes returned by `routa` (documented in `main`).
+pub mod exit_code {
+    /// Any failure without a more specific category.
+    pub const FAILURE: i32 = 1;
+    /// Invalid arguments or parameters (clap usage errors also exit with 2).
+    pub const BAD_ARGS: i32 = 2;
+    /// The requested task, agent, workspace, … does not exist.
+    pub const NOT_FOUND: i32 = 3;
+    /// An agent provider could not be spawned or reached.
+    pub const PROVIDER: i32 = 4;
+    /// The database could not be opened or written.
+    pub const DATABASE: i32 = 5;
+    /// The run was stopped with Ctrl-C (128 + SIGINT).
+    pub const INTERRUPTED: i32 = 130;
+}
+
+/// A command failure carrying the exit code it should produce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Map a JSON-RPC `error` object to an exit code.
+    pub fn from_rpc_error(error: &serde_json::Value) -> Self {
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("RPC request failed");
+        let code = match error.get("code").and_then(|c| c.as_i64()) {
+            Some(rpc_types::NOT_FOUND) => exit_code::NOT_FOUND,
+            Some(
+                rpc_types::BAD_REQUEST | rpc_types::INVALID_PARAMS | rpc_types::METHOD_NOT_FOUND,
+            ) => exit_code::BAD_ARGS,
+            _ => exit_code::FAILURE,
+        };
+        Self::new(code, message)
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        Self::new(exit_code::FAILURE, message)
+    }
+}
+
+impl From<ServerError> for CliError {
+    fn from(err: ServerError) -> Self {
+        let code = match &err {
+            ServerError::NotFound(_) => exit_code::NOT_FOUND,
+            ServerError::BadRequest(_) | ServerError::Conflict(_) => exit_code::BAD_ARGS,
+            ServerError::Database(_) => exit_code::DATABASE,
+            ServerError::Unauthorized(_)
+            | ServerError::Forbidden(_)
+            | ServerError::Internal(_)
+            | ServerError::NotImplemented(_) => exit_code::FAILURE,
+        };
+        Self::new(code, err.to_string())
+    }
+}
+
 /// Initialize a shared `AppState` from the given SQLite database path.
 ///
 /// This mirrors `routa_server::create_app_state` but avoids pulling in
@@ -39,15 +121,16 @@ use std::sync::Arc;
 pub async fn init_state(db_path: &str) -> AppState {
     let db = routa_core::Database::open(db_path).unwrap_or_else(|e| {
         eprintln!("Failed to open database '{db_path}': {e}");
-        std::process::exit(1);
+        std::process::exit(exit_code::DATABASE);
     });
+    db.startup_integrity_check(db_path);
 
     let state: AppState = Arc::new(routa_core::AppStateInner::new(db));
 
     // Ensure the default workspace exists
     if let Err(e) = state.workspace_store.ensure_default().await {
         eprintln!("Failed to initialize default workspace: {e}");
-        std::process::exit(1);
+        std::process::exit(exit_code::DATABASE);
     }
 
     // Discover skills from cwd
@@ -67,6 +150,15 @@ pub fn print_json(value: &serde_json::Value) {
     );
 }
 
+/// Print a JSON-RPC response, failing with the mapped exit code if it is an error.
+pub fn print_rpc_response(response: &serde_json::Value) -> Result<(), CliError> {
+    print_json(response);
+    match response.get("error") {
+        Some(error) if !error.is_null() => Err(CliError::from_rpc_error(error)),
+        _ => Ok(()),
+    }
+}
+
 pub fn truncate_text(value: &str, max_len: usize) -> String {
     let char_count = value.chars().count();
     if char_count <= max_len {
@@ -6,14 +6,26 @@
 //! 3. Sends the user's prompt as-is
 //! 4. Streams session updates (agent messages, tool calls, process output)
 //! 5. Prints a run-scoped summary
+//!
+//! Ctrl-C stops the run cooperatively: every spawned session is killed and
+//! the run's agents and in-progress tasks are marked CANCELLED.
+//!
+//! With `--plan-only`, a ROUTA coordinator is spawned instead under an MCP
+//! profile without `create_agent` / `delegate_task_to_agent`: it writes the
+//! spec note and creates tasks, and the run stops before any delegation.
 
 use std::collections::HashSet;
 
+use routa_core::acp::SessionLaunchOptions;
+use routa_core::models::agent::AgentRole;
+use routa_core::orchestration::SpecialistConfig;
 use routa_core::rpc::RpcRouter;
 use routa_core::state::AppState;
 
 use super::review::stream_parser::{extract_update_text, update_contains_turn_complete};
+use super::team::prompt_and_stream_until_idle;
 use super::tui::TuiRenderer;
+use super::{exit_code, CliError};
 
 /// Run a single DEVELOPER prompt flow for a user prompt.
 pub async fn run(
@@ -21,62 +33,14 @@ pub async fn run(
     prompt: &str,
     workspace_id: &str,
     provider: &str,
-) -> Result<(), String> {
+) -> Result<(), CliError> {
     let router = RpcRouter::new(state.clone());
     let cwd = std::env::current_dir()
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|_| ".".to_string());
 
     // ── 1. Use default workspace (always exists) ────────────────────────
-    let workspace_id = if workspace_id == "default" {
-        "default".to_string()
-    } else {
-        // For non-default workspaces, try to get or create
-        let ws_response = router
-            .handle_value(serde_json::json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "workspaces.get",
-                "params": { "id": workspace_id }
-            }))
-            .await;
-
-        if ws_response.get("error").is_some() {
-            // Create workspace if it doesn't exist
-            let create_resp = router
-                .handle_value(serde_json::json!({
-                    "jsonrpc": "2.0",
-                    "id": 2,
-                    "method": "workspaces.create",
-                    "params": {
-                        "title": workspace_id
-                    }
-                }))
-                .await;
-
-            if let Some(err) = create_resp.get("error") {
-                let err_msg = err
-                    .get("message")
-                    .and_then(|m| m.as_str())
-                    .unwrap_or("Unknown error");
-                return Err(format!("Failed to create workspace: {err_msg}"));
-            }
-
-            // Get the created workspace ID
-            let created_ws_id = create_resp
-                .get("result")
-                .and_then(|r| r.get("workspace"))
-                .and_then(|w| w.get("id"))
-                .and_then(|id| id.as_str())
-                .ok_or("Failed to get created workspace ID")?
-                .to_string();
-
-            println!("Created workspace: {created_ws_id}");
-            created_ws_id
-        } else {
-            workspace_id.to_string()
-        }
-    };
+    let workspace_id = resolve_workspace(&router, workspace_id).await?;
 
     // ── 2. Create DEVELOPER agent ───────────────────────────────────────
     let agent_name = "cli-developer";
@@ -151,7 +115,10 @@ pub async fn run(
             if let Err(err) = update_agent_status(&router, &agent_id, "ERROR").await {
                 eprintln!("Failed to mark agent {agent_id} ERROR: {err}");
             }
-            return Err(format!("Failed to create ACP session: {e}"));
+            return Err(CliError::new(
+                exit_code::PROVIDER,
+                format!("Failed to create ACP session: {e}"),
+            ));
         }
     }
 
@@ -163,7 +130,10 @@ pub async fn run(
                 eprintln!("Failed to mark agent {agent_id} ERROR: {err}");
             }
             state.acp_manager.kill_session(&session_id).await;
-            return Err("Failed to subscribe to session updates".to_string());
+            return Err(CliError::new(
+                exit_code::PROVIDER,
+                "Failed to subscribe to session updates",
+            ));
         }
     };
 
@@ -181,6 +151,8 @@ pub async fn run(
     let mut saw_output = false;
     let mut waiting_notice_shown = false;
     let mut final_status = "COMPLETED";
+    let mut interrupted = false;
+    let mut interrupt = tokio::spawn(tokio::signal::ctrl_c());
     let prompt_future = state.acp_manager.prompt(&session_id, prompt_text);
     tokio::pin!(prompt_future);
 
@@ -189,6 +161,15 @@ pub async fn run(
         tokio::pin!(tick);
 
         tokio::select! {
+            signal = &mut interrupt => {
+                if matches!(signal, Ok(Ok(()))) {
+                    renderer.finish();
+                    interrupted = true;
+                    break;
+                }
+                // No Ctrl-C handler could be installed; keep waiting without one.
+                interrupt = tokio::spawn(std::future::pending());
+            }
             prompt_result = &mut prompt_future, if !prompt_finished => {
                 prompt_finished = true;
                 if let Err(err) = prompt_result {
@@ -226,7 +207,11 @@ pub async fn run(
                             break;
                         }
                     }
-                    Err(_) => {
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        idle_count = 0;
+                        eprintln!("[routa] Fell behind the agent; skipped {skipped} updates");
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => {
                         renderer.finish();
                         final_status = "ERROR";
                         println!("═══ Agent session ended ═══");
@@ -275,12 +260,21 @@ pub async fn run(
         }
     }
 
+    interrupt.abort();
+
+    if interrupted {
+        println!();
+        println!("⏹ Interrupted — stopping agents...");
+        cancel_run(state, &router, &workspace_id, &agent_id, &session_id).await;
+        return Err(CliError::new(exit_code::INTERRUPTED, "Interrupted"));
+    }
+
     if let Some(error) = prompt_error {
         if let Err(err) = update_agent_status(&router, &agent_id, "ERROR").await {
             eprintln!("Failed to mark agent {agent_id} ERROR: {err}");
         }
         state.acp_manager.kill_session(&session_id).await;
-        return Err(error);
+        return Err(CliError::new(exit_code::PROVIDER, error));
     }
 
     if let Err(err) = update_agent_status(&router, &agent_id, final_status).await {
@@ -297,6 +291,299 @@ pub async fn run(
     Ok(())
 }
 
+/// Tear down a run stopped by Ctrl-C: kill every ACP session this process
+/// spawned, mark the run's live agents and in-progress tasks CANCELLED, and
+/// signal the state's background tasks to stop.
+pub(crate) async fn cancel_run(
+    state: &AppState,
+    router: &RpcRouter,
+    workspace_id: &str,
+    root_agent_id: &str,
+    session_id: &str,
+) {
+    for session in state.acp_manager.list_sessions().await {
+        state.acp_manager.kill_session(&session.session_id).await;
+    }
+
+    let agents_resp = router
+        .handle_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 110,
+            "method": "agents.list",
+            "params": { "workspaceId": workspace_id }
+        }))
+        .await;
+    let agents = agents_resp
+        .get("result")
+        .and_then(|r| r.get("agents"))
+        .and_then(|a| a.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let related_agent_ids = collect_related_agent_ids(&agents, Some(root_agent_id));
+    for agent in &agents {
+        let Some(id) = agent_id(agent).filter(|id| related_agent_ids.contains(*id)) else {
+            continue;
+        };
+        let status = agent.get("status").and_then(|v| v.as_str());
+        if matches!(status, Some("ACTIVE" | "PENDING")) {
+            if let Err(err) = update_agent_status(router, id, "CANCELLED").await {
+                eprintln!("Failed to mark agent {id} CANCELLED: {err}");
+            }
+        }
+    }
+
+    let tasks_resp = router
+        .handle_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 111,
+            "method": "tasks.list",
+            "params": { "workspaceId": workspace_id, "status": "IN_PROGRESS" }
+        }))
+        .await;
+    let tasks = tasks_resp
+        .get("result")
+        .and_then(|r| r.get("tasks"))
+        .and_then(|t| t.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for task in tasks
+        .iter()
+        .filter(|task| is_run_related_task(task, &related_agent_ids, Some(session_id)))
+    {
+        let Some(id) = task.get("id").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let response = router
+            .handle_value(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 112,
+                "method": "tasks.updateStatus",
+                "params": { "id": id, "status": "CANCELLED" }
+            }))
+            .await;
+        if let Some(error) = response.get("error") {
+            eprintln!("Failed to mark task {id} CANCELLED: {error}");
+        }
+    }
+
+    state.shutdown();
+}
+
+/// MCP profile for plan-only coordinator sessions; the server withholds
+/// `create_agent` and `delegate_task_to_agent` under it.
+const PLANNING_MCP_PROFILE: &str = "routa-planning";
+
+/// Plan a prompt without executing it: run the ROUTA coordinator on the
+/// requirement with the delegation tools withheld, so it writes the spec note
+/// and creates tasks, then stops before delegating. The plan is printed.
+pub async fn plan(
+    state: &AppState,
+    prompt: &str,
+    workspace_id: &str,
+    provider: &str,
+) -> Result<(), CliError> {
+    let router = RpcRouter::new(state.clone());
+    let cwd = std::env::current_dir()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| ".".to_string());
+    let workspace_id = resolve_workspace(&router, workspace_id).await?;
+
+    let specialist = SpecialistConfig::list_available()
+        .into_iter()
+        .find(|specialist| specialist.id == "routa" && specialist.role == AgentRole::Routa)
+        .ok_or_else(|| CliError::from("No ROUTA specialist is installed".to_string()))?;
+
+    // ── 1. Create the coordinator agent ──────────────────────────────────
+    let create_response = router
+        .handle_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "agents.create",
+            "params": {
+                "name": "cli-planner",
+                "role": specialist.role.as_str(),
+                "workspaceId": &workspace_id
+            }
+        }))
+        .await;
+    let agent_id = create_response
+        .get("result")
+        .and_then(|r| r.get("agentId"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            let error_msg = create_response
+                .get("error")
+                .and_then(|e| e.get("message"))
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown error");
+            CliError::from(format!("Failed to create coordinator agent: {error_msg}"))
+        })?
+        .to_string();
+
+    println!("📋 Requirement: {}", prompt.trim());
+    println!();
+
+    // ── 2. Spawn the coordinator without delegation tools ────────────────
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let launch_options = if provider == "claude" {
+        SessionLaunchOptions {
+            specialist_id: Some(specialist.id.clone()),
+            specialist_system_prompt: specialist.system_prompt_with_reminder(),
+            allowed_native_tools: Some(Vec::new()),
+            ..SessionLaunchOptions::default()
+        }
+    } else {
+        SessionLaunchOptions::default()
+    };
+    if let Err(e) = state
+        .acp_manager
+        .create_session_with_options(
+            session_id.clone(),
+            cwd,
+            workspace_id.clone(),
+            Some(provider.to_string()),
+            Some(specialist.role.as_str().to_string()),
+            None,
+            None,
+            None,
+            Some(PLANNING_MCP_PROFILE.to_string()),
+            launch_options,
+        )
+        .await
+    {
+        if let Err(err) = update_agent_status(&router, &agent_id, "ERROR").await {
+            eprintln!("Failed to mark agent {agent_id} ERROR: {err}");
+        }
+        return Err(CliError::new(
+            exit_code::PROVIDER,
+            format!("Failed to create ACP session: {e}"),
+        ));
+    }
+    if let Err(err) = update_agent_status(&router, &agent_id, "ACTIVE").await {
+        eprintln!("Failed to mark agent {agent_id} ACTIVE: {err}");
+    }
+    let orchestrator = state.orchestrator.clone();
+    orchestrator
+        .register_agent_session(&agent_id, &session_id)
+        .await;
+
+    // ── 3. Let the coordinator plan, then stop before delegation ─────────
+    let result = match state.acp_manager.subscribe(&session_id).await {
+        Some(mut rx) => {
+            let plan_prompt = build_plan_prompt(
+                provider,
+                &specialist,
+                &agent_id,
+                &workspace_id,
+                prompt.trim(),
+            );
+            prompt_and_stream_until_idle(&mut rx, state, &session_id, &plan_prompt)
+                .await
+                .map_err(|e| CliError::new(exit_code::PROVIDER, format!("Planning failed: {e}")))
+        }
+        None => Err(CliError::from(
+            "Failed to subscribe to session updates".to_string(),
+        )),
+    };
+    state.acp_manager.kill_session(&session_id).await;
+    orchestrator.cleanup(&session_id).await;
+
+    let final_status = if result.is_ok() { "COMPLETED" } else { "ERROR" };
+    if let Err(err) = update_agent_status(&router, &agent_id, final_status).await {
+        eprintln!("Failed to mark agent {agent_id} {final_status}: {err}");
+    }
+    result?;
+
+    println!();
+    print_session_summary(&router, &workspace_id, Some(&agent_id), Some(&session_id)).await;
+    println!();
+    println!("Nothing was delegated; run without --plan-only to execute.");
+    Ok(())
+}
+
+/// Build the coordinator prompt for a plan-only run. For Claude the
+/// specialist prompt is passed as the system prompt instead.
+fn build_plan_prompt(
+    provider: &str,
+    specialist: &SpecialistConfig,
+    agent_id: &str,
+    workspace_id: &str,
+    user_requirement: &str,
+) -> String {
+    let request = format!(
+        "**Your Agent ID:** {agent_id}\n\
+         **Workspace ID:** {workspace_id}\n\n\
+         ## Plan Only\n\n\
+         Write the spec note and create one task per work item, then stop. \
+         Do not create agents or delegate tasks; the delegation tools are not \
+         available in this session and the user will review the plan first.\n\n\
+         ## User Requirement\n\n{user_requirement}\n"
+    );
+    if provider == "claude" {
+        return request;
+    }
+
+    match specialist.system_prompt_with_reminder() {
+        Some(system_prompt) => format!("{system_prompt}\n---\n\n{request}"),
+        None => request,
+    }
+}
+
+/// Return `workspace_id`, creating a workspace titled after it when it does
+/// not exist yet. The default workspace always exists.
+async fn resolve_workspace(router: &RpcRouter, workspace_id: &str) -> Result<String, CliError> {
+    let workspace_id = if workspace_id == "default" {
+        "default".to_string()
+    } else {
+        // For non-default workspaces, try to get or create
+        let ws_response = router
+            .handle_value(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "workspaces.get",
+                "params": { "id": workspace_id }
+            }))
+            .await;
+
+        if ws_response.get("error").is_some() {
+            // Create workspace if it doesn't exist
+            let create_resp = router
+                .handle_value(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "workspaces.create",
+                    "params": {
+                        "title": workspace_id
+                    }
+                }))
+                .await;
+
+            if let Some(err) = create_resp.get("error") {
+                let err_msg = err
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("Unknown error");
+                return Err(format!("Failed to create workspace: {err_msg}").into());
+            }
+
+            // Get the created workspace ID
+            let created_ws_id = create_resp
+                .get("result")
+                .and_then(|r| r.get("workspace"))
+                .and_then(|w| w.get("id"))
+                .and_then(|id| id.as_str())
+                .ok_or_else(|| CliError::from("Failed to get created workspace ID".to_string()))?
+                .to_string();
+
+            println!("Created workspace: {created_ws_id}");
+            created_ws_id
+        } else {
+            workspace_id.to_string()
+        }
+    };
+    Ok(workspace_id)
+}
+
 pub(crate) async fn update_agent_status(
     router: &RpcRouter,
     agent_id: &str,
@@ -555,3 +842,138 @@ fn lane_session_agent_matches(
 fn agent_id(agent: &serde_json::Value) -> Option<&str> {
     agent.get("id").and_then(|value| value.as_str())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use routa_core::{AppState, AppStateInner, Database};
+
+    use super::*;
+
+    fn routa_specialist() -> SpecialistConfig {
+        SpecialistConfig {
+            id: "routa".to_string(),
+            role: AgentRole::Routa,
+            ..SpecialistConfig::crafter()
+        }
+    }
+
+    #[test]
+    fn plan_prompt_forbids_delegation_and_carries_the_requirement() {
+        let specialist = routa_specialist();
+        let prompt = build_plan_prompt("opencode", &specialist, "agent-1", "ws-1", "Add login");
+
+        assert!(prompt.starts_with(&specialist.system_prompt_body().unwrap()));
+        assert!(prompt.contains("**Your Agent ID:** agent-1"));
+        assert!(prompt.contains("**Workspace ID:** ws-1"));
+        assert!(prompt.contains("Do not create agents or delegate tasks"));
+        assert!(prompt.ends_with("## User Requirement\n\nAdd login\n"));
+    }
+
+    #[test]
+    fn claude_plan_prompt_leaves_the_specialist_prompt_to_the_system_prompt() {
+        let specialist = routa_specialist();
+        let prompt = build_plan_prompt("claude", &specialist, "agent-1", "ws-1", "Add login");
+
+        assert!(prompt.starts_with("**Your Agent ID:** agent-1"));
+        assert!(!prompt.contains(&specialist.system_prompt_body().unwrap()));
+        assert!(prompt.contains("Do not create agents or delegate tasks"));
+    }
+
+    async fn rpc(router: &RpcRouter, method: &str, params: serde_json::Value) -> serde_json::Value {
+        let response = router
+            .handle_value(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params
+            }))
+            .await;
+        response
+            .get("result")
+            .cloned()
+            .unwrap_or_else(|| panic!("{method} failed: {response}"))
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cancel_run_tears_down_spawned_session() {
+        let state: AppState = Arc::new(AppStateInner::new(
+            Database::open_in_memory().expect("in-memory db should open"),
+        ));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("default workspace");
+        let router = RpcRouter::new(state.clone());
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+
+        let agent_id = rpc(
+            &router,
+            "agents.create",
+            serde_json::json!({ "name": "cli-developer", "role": "DEVELOPER" }),
+        )
+        .await["agentId"]
+            .as_str()
+            .expect("agent id")
+            .to_string();
+        update_agent_status(&router, &agent_id, "ACTIVE")
+            .await
+            .expect("mark agent active");
+
+        let stub_agent = r#"read _
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1}}'
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session"}}'
+cat > /dev/null"#;
+        state
+            .acp_manager
+            .create_session_from_inline(
+                "session-1".to_string(),
+                temp.path().to_string_lossy().to_string(),
+                "default".to_string(),
+                "stub".to_string(),
+                Some("DEVELOPER".to_string()),
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                routa_core::acp::SessionLaunchOptions::default(),
+            )
+            .await
+            .expect("stub session should start");
+        assert!(state.acp_manager.is_alive("session-1").await);
+
+        let task_id = rpc(
+            &router,
+            "tasks.create",
+            serde_json::json!({
+                "title": "Add login",
+                "objective": "Users can sign in",
+                "sessionId": "session-1"
+            }),
+        )
+        .await["task"]["id"]
+            .as_str()
+            .expect("task id")
+            .to_string();
+        rpc(
+            &router,
+            "tasks.updateStatus",
+            serde_json::json!({ "id": task_id, "status": "IN_PROGRESS" }),
+        )
+        .await;
+
+        cancel_run(&state, &router, "default", &agent_id, "session-1").await;
+
+        assert!(!state.acp_manager.is_alive("session-1").await);
+        assert!(state.acp_manager.list_sessions().await.is_empty());
+        let task = rpc(&router, "tasks.get", serde_json::json!({ "id": task_id })).await;
+        assert_eq!(task["status"], "CANCELLED");
+        let agent = rpc(&router, "agents.get", serde_json::json!({ "id": agent_id })).await;
+        assert_eq!(agent["status"], "CANCELLED");
+        assert!(state.shutdown_token.is_cancelled());
+    }
+}
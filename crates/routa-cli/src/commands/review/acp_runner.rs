@@ -9,7 +9,7 @@ use super::output::{print_security_acp_runtime_diagnostics, truncate};
 use super::shared::{find_command_in_path, provider_runtime_binary};
 use super::stream_parser::{
     extract_agent_output_from_history, extract_agent_output_from_process_output,
-    extract_text_from_prompt_result, extract_update_text, update_contains_turn_complete,
+    extract_update_text, update_contains_turn_complete,
 };
 
 pub(crate) fn resolve_security_provider(specialist: &SpecialistDef) -> String {
@@ -95,7 +95,7 @@ pub(crate) async fn call_security_specialist_via_acp(
         streamed_output
     };
     let output = if output.trim().is_empty() {
-        extract_text_from_prompt_result(&prompt_response).unwrap_or_default()
+        prompt_response.text.clone()
     } else {
         output
     };
@@ -108,10 +108,10 @@ pub(crate) async fn call_security_specialist_via_acp(
     state.acp_manager.kill_session(&session_id).await;
 
     if output.trim().is_empty() {
-        let response_preview = truncate(&prompt_response.to_string(), 600);
         return Err(format!(
-            "Security specialist completed without producing an output. prompt_response={}, history_entries={}",
-            response_preview,
+            "Security specialist completed without producing an output. stop_reason={}, tool_calls={}, history_entries={}",
+            prompt_response.stop_reason.as_str(),
+            prompt_response.tool_calls.len(),
             history.len()
         ));
     }
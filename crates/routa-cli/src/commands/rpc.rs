@@ -1,14 +1,44 @@
 //! `routa rpc` — Raw JSON-RPC invocation.
 
+use std::io::Read;
+
 use routa_core::rpc::RpcRouter;
 use routa_core::state::AppState;
 
-use super::print_json;
+use super::{exit_code, print_rpc_response, CliError};
+
+/// Where `routa rpc` reads its params from.
+pub enum ParamsSource<'a> {
+    Inline(&'a str),
+    File(&'a str),
+    Stdin,
+}
 
-pub async fn call(state: &AppState, method: &str, params_str: &str) -> Result<(), String> {
-    let params: serde_json::Value =
-        serde_json::from_str(params_str).map_err(|e| format!("Invalid JSON params: {e}"))?;
+/// Read and parse the params before anything is sent.
+pub fn read_params(source: ParamsSource<'_>) -> Result<serde_json::Value, CliError> {
+    let bad_args = |message: String| CliError::new(exit_code::BAD_ARGS, message);
+    let raw = match source {
+        ParamsSource::Inline(raw) => raw.to_string(),
+        ParamsSource::File(path) => std::fs::read_to_string(path)
+            .map_err(|e| bad_args(format!("Failed to read params file {path}: {e}")))?,
+        ParamsSource::Stdin => {
+            let mut raw = String::new();
+            std::io::stdin()
+                .read_to_string(&mut raw)
+                .map_err(|e| bad_args(format!("Failed to read params from stdin: {e}")))?;
+            raw
+        }
+    };
+    serde_json::from_str(&raw).map_err(|e| bad_args(format!("Invalid JSON params: {e}")))
+}
 
+/// Send one request and pretty-print the response; an `error` response
+/// fails with the matching exit code.
+pub async fn call(
+    state: &AppState,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<(), CliError> {
     let router = RpcRouter::new(state.clone());
     let response = router
         .handle_value(serde_json::json!({
@@ -19,6 +49,5 @@ pub async fn call(state: &AppState, method: &str, params_str: &str) -> Result<()
         }))
         .await;
 
-    print_json(&response);
-    Ok(())
+    print_rpc_response(&response)
 }
@@ -5,22 +5,36 @@ pub async fn run(
     port: u16,
     db_path: String,
     static_dir: Option<String>,
+    workflows_dir: Option<String>,
+    tls_cert_path: Option<String>,
+    tls_key_path: Option<String>,
 ) -> Result<(), String> {
     // Resolve full shell PATH so child processes can be found
     let full_path = routa_core::shell_env::full_path();
     std::env::set_var("PATH", full_path);
 
+    let scheme = if tls_cert_path.is_some() {
+        "https"
+    } else {
+        "http"
+    };
     let config = routa_server::ServerConfig {
         host: host.clone(),
         port,
         db_path,
         static_dir,
+        workflows_dir,
+        workspace_tokens: None,
+        tls_cert_path,
+        tls_key_path,
+        max_sessions_per_workspace: None,
+        db_encryption_key: None,
     };
 
     println!("Starting Routa server on {host}:{port}...");
 
     let addr = routa_server::start_server(config).await?;
-    println!("Routa server listening on http://{addr}");
+    println!("Routa server listening on {scheme}://{addr}");
 
     // Keep the process running until interrupted
     tokio::signal::ctrl_c()
@@ -18,13 +18,14 @@ pub async fn list(state: &AppState) -> Result<(), String> {
     Ok(())
 }
 
-pub async fn reload(state: &AppState) -> Result<(), String> {
+pub async fn reload(state: &AppState, dirs: &[String]) -> Result<(), String> {
     let router = RpcRouter::new(state.clone());
     let response = router
         .handle_value(serde_json::json!({
             "jsonrpc": "2.0",
             "id": 1,
-            "method": "skills.reload"
+            "method": "skills.reload",
+            "params": { "paths": dirs }
         }))
         .await;
     print_json(&response);
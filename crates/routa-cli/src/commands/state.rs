@@ -0,0 +1,21 @@
+//! `routa state` — Export and import support bundles.
+
+use std::path::Path;
+
+use routa_core::state::AppState;
+
+use super::{print_json, CliError};
+
+pub async fn export(state: &AppState, path: &str) -> Result<(), CliError> {
+    let summary = state.export_bundle(Path::new(path)).await?;
+    println!("Exported state to {path}");
+    print_json(&serde_json::to_value(&summary).unwrap_or_default());
+    Ok(())
+}
+
+pub async fn import(state: &AppState, path: &str) -> Result<(), CliError> {
+    let imported = state.import_bundle(Path::new(path)).await?;
+    println!("Imported state from {path}");
+    print_json(&serde_json::to_value(&imported).unwrap_or_default());
+    Ok(())
+}
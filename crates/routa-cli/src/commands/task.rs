@@ -3,9 +3,9 @@
 use routa_core::rpc::RpcRouter;
 use routa_core::state::AppState;
 
-use super::{format_rfc3339_timestamp, print_json, truncate_text};
+use super::{format_rfc3339_timestamp, print_rpc_response, truncate_text, CliError};
 
-pub async fn list(state: &AppState, workspace_id: &str, limit: usize) -> Result<(), String> {
+pub async fn list(state: &AppState, workspace_id: &str, limit: usize) -> Result<(), CliError> {
     let router = RpcRouter::new(state.clone());
     let response = router
         .handle_value(serde_json::json!({
@@ -58,7 +58,7 @@ pub async fn list(state: &AppState, workspace_id: &str, limit: usize) -> Result<
             );
         }
     } else {
-        print_json(&response);
+        print_rpc_response(&response)?;
     }
 
     Ok(())
@@ -75,7 +75,7 @@ pub async fn create(
     workspace_id: &str,
     scope: Option<&str>,
     acceptance_criteria: Option<Vec<String>>,
-) -> Result<(), String> {
+) -> Result<(), CliError> {
     let router = RpcRouter::new(state.clone());
     let mut params = serde_json::json!({
         "title": title,
@@ -96,11 +96,10 @@ pub async fn create(
             "params": params
         }))
         .await;
-    print_json(&response);
-    Ok(())
+    print_rpc_response(&response)
 }
 
-pub async fn get(state: &AppState, task_id: &str) -> Result<(), String> {
+pub async fn get(state: &AppState, task_id: &str) -> Result<(), CliError> {
     let router = RpcRouter::new(state.clone());
     let response = router
         .handle_value(serde_json::json!({
@@ -110,8 +109,7 @@ pub async fn get(state: &AppState, task_id: &str) -> Result<(), String> {
             "params": { "id": task_id }
         }))
         .await;
-    print_json(&response);
-    Ok(())
+    print_rpc_response(&response)
 }
 
 pub async fn update_status(
@@ -120,7 +118,7 @@ pub async fn update_status(
     status: &str,
     _agent_id: &str,
     _summary: Option<&str>,
-) -> Result<(), String> {
+) -> Result<(), CliError> {
     let router = RpcRouter::new(state.clone());
     let response = router
         .handle_value(serde_json::json!({
@@ -133,15 +131,30 @@ pub async fn update_status(
             }
         }))
         .await;
-    print_json(&response);
-    Ok(())
+    print_rpc_response(&response)
+}
+
+pub async fn assign(state: &AppState, task_id: &str, agent_id: &str) -> Result<(), CliError> {
+    let router = RpcRouter::new(state.clone());
+    let response = router
+        .handle_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tasks.assign",
+            "params": {
+                "id": task_id,
+                "agentId": agent_id
+            }
+        }))
+        .await;
+    print_rpc_response(&response)
 }
 
 pub async fn list_artifacts(
     state: &AppState,
     task_id: &str,
     artifact_type: Option<&str>,
-) -> Result<(), String> {
+) -> Result<(), CliError> {
     let router = RpcRouter::new(state.clone());
     let mut params = serde_json::json!({
         "taskId": task_id
@@ -157,8 +170,7 @@ pub async fn list_artifacts(
             "params": params
         }))
         .await;
-    print_json(&response);
-    Ok(())
+    print_rpc_response(&response)
 }
 
 pub async fn provide_artifact(
@@ -168,7 +180,7 @@ pub async fn provide_artifact(
     artifact_type: &str,
     content: &str,
     context: Option<&str>,
-) -> Result<(), String> {
+) -> Result<(), CliError> {
     let router = RpcRouter::new(state.clone());
     let mut params = serde_json::json!({
         "taskId": task_id,
@@ -187,6 +199,5 @@ pub async fn provide_artifact(
             "params": params
         }))
         .await;
-    print_json(&response);
-    Ok(())
+    print_rpc_response(&response)
 }
@@ -5,12 +5,11 @@
 //! team members (CRAFTER, GATE, DEVELOPER agents).
 
 use std::io::{self, BufRead, Write};
-use std::sync::Arc;
 
 use dialoguer::{theme::ColorfulTheme, Input};
 use routa_core::acp::SessionLaunchOptions;
 use routa_core::models::agent::AgentRole;
-use routa_core::orchestration::{OrchestratorConfig, RoutaOrchestrator, SpecialistConfig};
+use routa_core::orchestration::SpecialistConfig;
 use routa_core::rpc::RpcRouter;
 use routa_core::state::AppState;
 use routa_core::store::acp_session_store::CreateAcpSessionParams;
@@ -136,6 +135,7 @@ pub async fn run(
                     custom_command: None,
                     custom_args: None,
                     parent_session_id: None,
+                    metadata: None,
                 })
                 .await
             {
@@ -151,14 +151,7 @@ pub async fn run(
     }
 
     // ── 7. Register with orchestrator ────────────────────────────────────
-    let acp = Arc::new(state.acp_manager.clone());
-    let orchestrator = RoutaOrchestrator::new(
-        OrchestratorConfig::default(),
-        acp,
-        state.agent_store.clone(),
-        state.task_store.clone(),
-        state.event_bus.clone(),
-    );
+    let orchestrator = state.orchestrator.clone();
     orchestrator
         .register_agent_session(&agent_id, &session_id)
         .await;
@@ -331,7 +324,7 @@ async fn run_interactive_repl(
 }
 
 /// Stream updates until idle or turn_complete.
-async fn prompt_and_stream_until_idle(
+pub(super) async fn prompt_and_stream_until_idle(
     rx: &mut broadcast::Receiver<serde_json::Value>,
     state: &AppState,
     session_id: &str,
@@ -373,7 +366,10 @@ async fn prompt_and_stream_until_idle(
                             break;
                         }
                     }
-                    Err(_) => break,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        eprintln!("[routa] Fell behind the agent; skipped {skipped} updates");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
                 }
             }
             _ = &mut tick => {
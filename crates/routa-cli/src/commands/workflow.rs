@@ -1,9 +1,17 @@
 //! `routa workflow` — Run YAML-defined agent workflows.
 
+use clap::ValueEnum;
 use routa_core::state::AppState;
 use routa_core::workflow::executor::WorkflowExecutor;
+use routa_core::workflow::graph::WorkflowGraph;
 use routa_core::workflow::schema::WorkflowDefinition;
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum WorkflowGraphFormat {
+    Ascii,
+    Dot,
+}
+
 /// Run a workflow from a YAML file.
 pub async fn run(
     _state: &AppState,
@@ -11,6 +19,7 @@ pub async fn run(
     verbose: bool,
     specialist_dir: Option<&str>,
     trigger_payload: Option<&str>,
+    output_file: Option<&str>,
 ) -> Result<(), String> {
     // Load .env / .env.local if present (for API keys, etc.)
     load_dotenv();
@@ -39,8 +48,17 @@ pub async fn run(
         executor.set_trigger_payload(payload.to_string());
     }
 
-    // Execute the workflow
-    let result = executor.execute(&workflow).await?;
+    // Execute the workflow, recording the run even when it fails
+    let result = executor.execute(&workflow).await;
+    if let Some(path) = output_file {
+        let output = match &result {
+            Ok(result) => executor.run_output(result),
+            Err(e) => executor.error_output(&workflow, e),
+        };
+        output.write_to(std::path::Path::new(path))?;
+        println!("📝 Wrote workflow output to {path}");
+    }
+    let result = result?;
 
     // Exit with appropriate code
     if result.success {
@@ -111,6 +129,7 @@ pub async fn list_specialists(specialist_dir: Option<&str>) -> Result<(), String
 /// Validate a workflow YAML file without executing it.
 pub async fn validate(workflow_file: &str) -> Result<(), String> {
     let workflow = WorkflowDefinition::from_file(workflow_file)?;
+    workflow.validate()?;
 
     println!("✅ Workflow '{}' is valid", workflow.name);
     println!("   Version: {}", workflow.version);
@@ -130,6 +149,27 @@ pub async fn validate(workflow_file: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Render a workflow's step graph, flagging steps that can never run.
+pub fn graph(workflow_file: &str, format: WorkflowGraphFormat) -> Result<(), String> {
+    let workflow = WorkflowDefinition::from_file(workflow_file)?;
+    let graph = WorkflowGraph::from_definition(&workflow)?;
+
+    match format {
+        WorkflowGraphFormat::Ascii => print!("{}", graph.to_ascii()),
+        WorkflowGraphFormat::Dot => print!("{}", graph.to_dot()),
+    }
+
+    let unreachable = graph
+        .nodes
+        .iter()
+        .filter(|node| node.unreachable.is_some())
+        .count();
+    if unreachable > 0 {
+        eprintln!("⚠️  {unreachable} step(s) can never run as written");
+    }
+    Ok(())
+}
+
 /// Load .env and .env.local files for environment variables.
 fn load_dotenv() {
     // Try .env.local first (higher priority), then .env
@@ -54,14 +54,14 @@ pub async fn list(state: &AppState, limit: usize) -> Result<(), String> {
     Ok(())
 }
 
-pub async fn create(state: &AppState, name: &str) -> Result<(), String> {
+pub async fn create(state: &AppState, name: &str, template: Option<&str>) -> Result<(), String> {
     let router = RpcRouter::new(state.clone());
     let response = router
         .handle_value(serde_json::json!({
             "jsonrpc": "2.0",
             "id": 1,
             "method": "workspaces.create",
-            "params": { "title": name }
+            "params": { "title": name, "template": template }
         }))
         .await;
     print_json(&response);
@@ -2,14 +2,25 @@
 //!
 //! Reuses the same core domain logic (routa-core) and server bootstrap
 //! (routa-server) that power the Next.js web UI and Tauri desktop app.
+//!
+//! Exit codes (see `commands::exit_code`):
+//! - `0` success
+//! - `1` other failure
+//! - `2` bad arguments or parameters
+//! - `3` requested entity not found
+//! - `4` agent provider could not be spawned or reached
+//! - `5` database could not be opened or written
 
 mod commands;
 mod kanban_cli;
 
 use crate::commands::acp::AcpAction;
+use crate::commands::agent::ConversationFormat;
 use crate::commands::fitness::FitnessAction;
 use crate::commands::graph::GraphAction;
 use crate::commands::harness::HarnessAction;
+use crate::commands::workflow::WorkflowGraphFormat;
+use crate::commands::CliError;
 use crate::kanban_cli::{handle_kanban_action, KanbanAction};
 use clap::{Parser, Subcommand};
 
@@ -30,6 +41,11 @@ pub struct Cli {
     #[arg(short = 'p', long = "prompt")]
     prompt: Option<String>,
 
+    /// With -p: run the ROUTA coordinator to write the spec note and create
+    /// tasks, then stop and print the plan without delegating to agents
+    #[arg(long, requires = "prompt")]
+    plan_only: bool,
+
     /// Workspace ID (used with -p prompt mode)
     #[arg(long, default_value = "default")]
     workspace_id: String,
@@ -38,6 +54,14 @@ pub struct Cli {
     #[arg(long, default_value = "opencode")]
     provider: String,
 
+    /// More log output: -v for info, -vv for debug (ignored when RUST_LOG is set)
+    #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Only log errors (ignored when RUST_LOG is set)
+    #[arg(short = 'q', long = "quiet", conflicts_with = "verbose")]
+    quiet: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -55,6 +79,16 @@ enum Commands {
         /// Path to static frontend directory (Next.js export)
         #[arg(long)]
         static_dir: Option<String>,
+        /// Directory of workflow YAML files whose cron and file_watch
+        /// triggers should run
+        #[arg(long)]
+        workflows_dir: Option<String>,
+        /// PEM certificate to serve HTTPS with (requires --tls-key)
+        #[arg(long, requires = "tls_key")]
+        tls_cert: Option<String>,
+        /// PEM private key for --tls-cert
+        #[arg(long, requires = "tls_cert")]
+        tls_key: Option<String>,
     },
 
     /// Run Routa as an ACP (Agent Client Protocol) server over stdio.
@@ -87,6 +121,12 @@ enum Commands {
         action: TaskAction,
     },
 
+    /// Watch agent coordination events
+    Events {
+        #[command(subcommand)]
+        action: EventsAction,
+    },
+
     /// Manage Kanban boards, cards, and columns
     Kanban {
         /// Prefer this Routa server for Kanban RPC calls.
@@ -129,6 +169,12 @@ enum Commands {
         /// JSON-RPC params as a JSON string
         #[arg(long, default_value = "{}")]
         params: String,
+        /// Read the JSON params from a file instead
+        #[arg(long, conflicts_with_all = ["params", "params_stdin"])]
+        params_file: Option<String>,
+        /// Read the JSON params from stdin instead
+        #[arg(long, conflicts_with = "params")]
+        params_stdin: bool,
     },
 
     /// Delegate a task to a specialist agent with ACP process spawning
@@ -151,12 +197,18 @@ enum Commands {
         /// ACP provider (e.g. "opencode")
         #[arg(long)]
         provider: Option<String>,
+        /// Model tier: FAST, BALANCED, or SMART (default: the specialist's tier)
+        #[arg(long)]
+        model_tier: Option<String>,
         /// Working directory for the child agent
         #[arg(long)]
         cwd: Option<String>,
         /// Wait mode: "immediate" or "after_all"
         #[arg(long, default_value = "immediate")]
         wait_mode: String,
+        /// Run the child in its own git worktree of the working directory
+        #[arg(long)]
+        isolate: bool,
     },
 
     /// Interactive chat session with an agent
@@ -175,6 +227,13 @@ enum Commands {
         session_id: Option<String>,
     },
 
+    /// Export or import a support bundle of workspaces, agents, tasks,
+    /// notes and sessions
+    State {
+        #[command(subcommand)]
+        action: StateAction,
+    },
+
     /// Run repository static/security scans (TypeScript, Rust, Docker)
     Scan {
         /// Optional project directory to scan
@@ -229,6 +288,15 @@ enum Commands {
         #[command(subcommand)]
         action: FeatureTreeAction,
     },
+
+    /// Check PATH, database, provider CLIs and ACP setup for common problems
+    Doctor,
+
+    /// Check the database file for corruption and repair it
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
 }
 
 #[derive(Subcommand)]
@@ -289,6 +357,39 @@ enum AgentAction {
         #[arg(long)]
         id: String,
     },
+    /// Submit a completion report to the agent's parent
+    Report {
+        /// Reporting agent ID
+        #[arg(long)]
+        agent_id: String,
+        /// Task ID being reported
+        #[arg(long)]
+        task_id: Option<String>,
+        /// Summary of the work; may be the agent's full free-text report
+        #[arg(long)]
+        summary: String,
+        /// Whether the task succeeded (read from the summary when omitted)
+        #[arg(long)]
+        success: Option<bool>,
+        /// Files changed, comma-separated
+        #[arg(long, value_delimiter = ',')]
+        files_modified: Vec<String>,
+    },
+    /// Dump an agent's conversation
+    Conversation {
+        /// Agent ID
+        #[arg(long)]
+        id: String,
+        /// Only the last N messages
+        #[arg(long)]
+        last_n: Option<usize>,
+        /// Output format
+        #[arg(long, short = 'f', value_enum, default_value_t = ConversationFormat::Md)]
+        format: ConversationFormat,
+        /// Include tool call messages
+        #[arg(long)]
+        tool_calls: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -321,6 +422,26 @@ enum SpecialistAction {
     },
 }
 
+#[derive(Subcommand)]
+enum EventsAction {
+    /// Stream events from a running server as they occur
+    Tail {
+        /// Routa server to subscribe to
+        #[arg(
+            long,
+            env = "ROUTA_SERVER_URL",
+            default_value = "http://127.0.0.1:3210"
+        )]
+        server_url: String,
+        /// Workspace ID ("*" for all workspaces)
+        #[arg(long, default_value = "default")]
+        workspace_id: String,
+        /// Only show these event types (comma-separated, e.g. TASK_ASSIGNED,AGENT_ERROR)
+        #[arg(long, value_delimiter = ',')]
+        types: Vec<String>,
+    },
+}
+
 #[derive(Subcommand)]
 enum TaskAction {
     /// List tasks in a workspace
@@ -370,6 +491,15 @@ enum TaskAction {
         #[arg(long)]
         summary: Option<String>,
     },
+    /// Assign or reassign a task to an agent
+    Assign {
+        /// Task ID
+        #[arg(long)]
+        id: String,
+        /// Agent ID to assign the task to
+        #[arg(long)]
+        agent_id: String,
+    },
     /// List artifacts attached to a task
     ArtifactList {
         /// Task ID
@@ -446,6 +576,34 @@ enum WorkspaceAction {
         /// Workspace name
         #[arg(long)]
         name: String,
+        /// Seed from a built-in template (feature, research)
+        #[arg(long)]
+        template: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbAction {
+    /// Run an integrity check and print any problems
+    Check,
+    /// Rebuild a damaged database from its readable rows, keeping the
+    /// original as a backup
+    Repair,
+}
+
+#[derive(Subcommand)]
+enum StateAction {
+    /// Write all state to a JSON bundle, with credentials redacted
+    Export {
+        /// Output file
+        #[arg(long)]
+        path: String,
+    },
+    /// Load a bundle under fresh IDs
+    Import {
+        /// Bundle file written by `routa state export`
+        #[arg(long)]
+        path: String,
     },
 }
 
@@ -454,7 +612,11 @@ enum SkillAction {
     /// List discovered skills
     List,
     /// Reload skills from the current directory
-    Reload,
+    Reload {
+        /// Extra directory to scan (repeatable), e.g. another repo's root
+        #[arg(long = "dir")]
+        dirs: Vec<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -472,12 +634,23 @@ enum WorkflowAction {
         /// Trigger payload (JSON string for webhook-triggered workflows)
         #[arg(long)]
         trigger_payload: Option<String>,
+        /// Write variables, step outputs and status as JSON to this file
+        #[arg(long, short = 'o')]
+        output: Option<String>,
     },
     /// Validate a workflow YAML file without executing it
     Validate {
         /// Path to the workflow YAML file
         file: String,
     },
+    /// Render the step dependency graph of a workflow YAML file
+    Graph {
+        /// Path to the workflow YAML file
+        file: String,
+        /// Output format
+        #[arg(long, short = 'f', value_enum, default_value_t = WorkflowGraphFormat::Ascii)]
+        format: WorkflowGraphFormat,
+    },
     /// List available specialist definitions
     Specialists {
         /// Custom specialist definitions directory
@@ -621,19 +794,40 @@ enum FeatureTreeAction {
     },
 }
 
+/// Default tracing filter for the `-v`/`-q` flags.
+fn log_filter(verbose: u8, quiet: bool) -> &'static str {
+    if quiet {
+        return "error";
+    }
+    match verbose {
+        0 => "routa_core=warn,routa_server=warn,routa_cli=info",
+        1 => "routa_core=info,routa_server=info,routa_cli=info",
+        _ => "routa_core=debug,routa_server=debug,routa_cli=debug",
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
     std::env::set_var("ROUTA_DB_PATH", &cli.db);
 
-    // Initialize tracing
+    // Initialize tracing; an explicit RUST_LOG wins over -v/-q
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "routa_core=warn,routa_server=warn,routa_cli=info".into()),
+                .unwrap_or_else(|_| log_filter(cli.verbose, cli.quiet).into()),
         )
         .init();
 
+    if let Err(e) = run(cli).await {
+        eprintln!("Error: {e}");
+        std::process::exit(e.code);
+    }
+}
+
+/// Dispatch the parsed command line. Commands that still report plain string
+/// errors exit with [`commands::exit_code::FAILURE`].
+async fn run(cli: Cli) -> Result<(), CliError> {
     let result = if let Some(prompt_text) = cli.prompt {
         // ── Quick prompt mode: routa -p "requirement" ───────────────
         // Resolve full shell PATH so child processes can be found
@@ -641,14 +835,32 @@ async fn main() {
         std::env::set_var("PATH", full_path);
 
         let state = commands::init_state(&cli.db).await;
-        commands::prompt::run(&state, &prompt_text, &cli.workspace_id, &cli.provider).await
+        if cli.plan_only {
+            return commands::prompt::plan(&state, &prompt_text, &cli.workspace_id, &cli.provider)
+                .await;
+        }
+        return commands::prompt::run(&state, &prompt_text, &cli.workspace_id, &cli.provider).await;
     } else if let Some(command) = cli.command {
         match command {
             Commands::Server {
                 host,
                 port,
                 static_dir,
-            } => commands::server::run(host, port, cli.db, static_dir).await,
+                workflows_dir,
+                tls_cert,
+                tls_key,
+            } => {
+                commands::server::run(
+                    host,
+                    port,
+                    cli.db,
+                    static_dir,
+                    workflows_dir,
+                    tls_cert,
+                    tls_key,
+                )
+                .await
+            }
 
             Commands::Acp { action } => {
                 match action {
@@ -670,6 +882,10 @@ async fn main() {
                         let state = commands::init_state(&cli.db).await;
                         commands::acp::uninstall(&state, &agent_id).await
                     }
+                    AcpAction::Update { agent_id, all } => {
+                        let state = commands::init_state(&cli.db).await;
+                        commands::acp::update(&state, agent_id.as_deref(), all).await
+                    }
                     AcpAction::List => {
                         let state = commands::init_state(&cli.db).await;
                         commands::acp::list(&state).await
@@ -758,6 +974,31 @@ async fn main() {
                     }
                     AgentAction::Status { id } => commands::agent::status(&state, &id).await,
                     AgentAction::Summary { id } => commands::agent::summary(&state, &id).await,
+                    AgentAction::Report {
+                        agent_id,
+                        task_id,
+                        summary,
+                        success,
+                        files_modified,
+                    } => {
+                        commands::agent::report(
+                            &state,
+                            &agent_id,
+                            task_id.as_deref(),
+                            &summary,
+                            success,
+                            &files_modified,
+                        )
+                        .await
+                    }
+                    AgentAction::Conversation {
+                        id,
+                        last_n,
+                        format,
+                        tool_calls,
+                    } => {
+                        commands::agent::conversation(&state, &id, last_n, format, tool_calls).await
+                    }
                 }
             }
 
@@ -795,7 +1036,7 @@ async fn main() {
 
             Commands::Task { action } => {
                 let state = commands::init_state(&cli.db).await;
-                match action {
+                return match action {
                     TaskAction::List {
                         workspace_id,
                         limit,
@@ -833,6 +1074,9 @@ async fn main() {
                         )
                         .await
                     }
+                    TaskAction::Assign { id, agent_id } => {
+                        commands::task::assign(&state, &id, &agent_id).await
+                    }
                     TaskAction::ArtifactList {
                         task_id,
                         artifact_type,
@@ -857,7 +1101,7 @@ async fn main() {
                         )
                         .await
                     }
-                }
+                };
             }
 
             Commands::Session { action } => {
@@ -886,6 +1130,14 @@ async fn main() {
                 }
             }
 
+            Commands::Events { action } => match action {
+                EventsAction::Tail {
+                    server_url,
+                    workspace_id,
+                    types,
+                } => commands::events::tail(&server_url, &workspace_id, &types).await,
+            },
+
             Commands::Kanban {
                 server_url,
                 json,
@@ -907,23 +1159,42 @@ async fn main() {
                     WorkspaceAction::List { limit } => {
                         commands::workspace::list(&state, limit).await
                     }
-                    WorkspaceAction::Create { name } => {
-                        commands::workspace::create(&state, &name).await
+                    WorkspaceAction::Create { name, template } => {
+                        commands::workspace::create(&state, &name, template.as_deref()).await
                     }
                 }
             }
 
+            Commands::State { action } => {
+                let state = commands::init_state(&cli.db).await;
+                return match action {
+                    StateAction::Export { path } => commands::state::export(&state, &path).await,
+                    StateAction::Import { path } => commands::state::import(&state, &path).await,
+                };
+            }
+
             Commands::Skill { action } => {
                 let state = commands::init_state(&cli.db).await;
                 match action {
                     SkillAction::List => commands::skill::list(&state).await,
-                    SkillAction::Reload => commands::skill::reload(&state).await,
+                    SkillAction::Reload { dirs } => commands::skill::reload(&state, &dirs).await,
                 }
             }
 
-            Commands::Rpc { method, params } => {
+            Commands::Rpc {
+                method,
+                params,
+                params_file,
+                params_stdin,
+            } => {
+                let source = match params_file.as_deref() {
+                    Some(path) => commands::rpc::ParamsSource::File(path),
+                    None if params_stdin => commands::rpc::ParamsSource::Stdin,
+                    None => commands::rpc::ParamsSource::Inline(&params),
+                };
+                let params = commands::rpc::read_params(source)?;
                 let state = commands::init_state(&cli.db).await;
-                commands::rpc::call(&state, &method, &params).await
+                return commands::rpc::call(&state, &method, params).await;
             }
 
             Commands::Delegate {
@@ -933,11 +1204,13 @@ async fn main() {
                 workspace_id,
                 specialist,
                 provider,
+                model_tier,
                 cwd,
                 wait_mode,
+                isolate,
             } => {
                 let state = commands::init_state(&cli.db).await;
-                commands::delegate::run(
+                return commands::delegate::run(
                     &state,
                     &task_id,
                     &caller_agent_id,
@@ -945,10 +1218,12 @@ async fn main() {
                     &workspace_id,
                     &specialist,
                     provider.as_deref(),
+                    model_tier.as_deref(),
                     cwd.as_deref(),
                     &wait_mode,
+                    isolate,
                 )
-                .await
+                .await;
             }
 
             Commands::Chat {
@@ -976,6 +1251,14 @@ async fn main() {
 
             Commands::Graph { action } => commands::graph::run(action),
 
+            Commands::Doctor => commands::doctor::run(&cli.db).await,
+            Commands::Db { action } => {
+                return match action {
+                    DbAction::Check => commands::db::check(&cli.db),
+                    DbAction::Repair => commands::db::repair(&cli.db),
+                };
+            }
+
             Commands::Fitness { action } => commands::fitness::run(action),
             Commands::Harness { action } => commands::harness::run(&cli.db, action).await,
 
@@ -987,6 +1270,7 @@ async fn main() {
                         verbose,
                         specialist_dir,
                         trigger_payload,
+                        output,
                     } => {
                         commands::workflow::run(
                             &state,
@@ -994,10 +1278,14 @@ async fn main() {
                             verbose,
                             specialist_dir.as_deref(),
                             trigger_payload.as_deref(),
+                            output.as_deref(),
                         )
                         .await
                     }
                     WorkflowAction::Validate { file } => commands::workflow::validate(&file).await,
+                    WorkflowAction::Graph { file, format } => {
+                        commands::workflow::graph(&file, format)
+                    }
                     WorkflowAction::Specialists { specialist_dir } => {
                         commands::workflow::list_specialists(specialist_dir.as_deref()).await
                     }
@@ -1142,8 +1430,47 @@ async fn main() {
         Ok(())
     };
 
-    if let Err(e) = result {
-        eprintln!("Error: {e}");
-        std::process::exit(1);
+    result.map_err(CliError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_for(args: &[&str]) -> &'static str {
+        let cli = Cli::try_parse_from(args).unwrap();
+        log_filter(cli.verbose, cli.quiet)
+    }
+
+    #[test]
+    fn verbosity_flags_select_log_filter() {
+        assert_eq!(
+            filter_for(&["routa"]),
+            "routa_core=warn,routa_server=warn,routa_cli=info"
+        );
+        assert_eq!(
+            filter_for(&["routa", "-v"]),
+            "routa_core=info,routa_server=info,routa_cli=info"
+        );
+        assert_eq!(
+            filter_for(&["routa", "-vv", "doctor"]),
+            "routa_core=debug,routa_server=debug,routa_cli=debug"
+        );
+        assert_eq!(filter_for(&["routa", "-q"]), "error");
+        assert!(Cli::try_parse_from(["routa", "-q", "-v"]).is_err());
+    }
+
+    #[test]
+    fn subcommand_verbose_flag_is_independent() {
+        let cli =
+            Cli::try_parse_from(["routa", "-v", "workflow", "run", "flow.yaml", "-v"]).unwrap();
+        assert_eq!(cli.verbose, 1);
+    }
+
+    #[test]
+    fn plan_only_requires_prompt() {
+        let cli = Cli::try_parse_from(["routa", "-p", "Add login", "--plan-only"]).unwrap();
+        assert!(cli.plan_only);
+        assert!(Cli::try_parse_from(["routa", "--plan-only"]).is_err());
     }
 }
@@ -6,7 +6,9 @@
 
 use std::sync::Arc;
 
+use routa_cli::commands::exit_code;
 use routa_cli::commands::kanban as kanban_cmd;
+use routa_cli::commands::rpc as rpc_cmd;
 use routa_core::models::kanban_config::KanbanConfig;
 use routa_core::rpc::RpcRouter;
 use routa_core::state::{AppState, AppStateInner};
@@ -299,6 +301,33 @@ async fn test_workspace_create() {
     assert!(workspaces.len() >= 2);
 }
 
+#[tokio::test]
+async fn test_rpc_params_from_file() {
+    let state = test_state().await;
+    let dir = tempfile::tempdir().unwrap();
+    let params_path = dir.path().join("params.json");
+    std::fs::write(&params_path, r#"{ "title": "from-file" }"#).unwrap();
+
+    let params =
+        rpc_cmd::read_params(rpc_cmd::ParamsSource::File(params_path.to_str().unwrap())).unwrap();
+    rpc_cmd::call(&state, "workspaces.create", params)
+        .await
+        .expect("workspaces.create should succeed");
+
+    let workspaces = state.workspace_store.list().await.unwrap();
+    assert!(workspaces.iter().any(|ws| ws.title == "from-file"));
+
+    std::fs::write(&params_path, "{ not json").unwrap();
+    let err = rpc_cmd::read_params(rpc_cmd::ParamsSource::File(params_path.to_str().unwrap()))
+        .unwrap_err();
+    assert_eq!(err.code, exit_code::BAD_ARGS);
+
+    let err = rpc_cmd::call(&state, "nonexistent.method", serde_json::json!({}))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code, exit_code::BAD_ARGS);
+}
+
 // ── Kanban YAML config integration tests ──────────────────────────────────────
 
 const SAMPLE_YAML: &str = r#"
@@ -507,3 +536,52 @@ async fn test_kanban_export_roundtrip() {
     assert_eq!(automation.role.as_deref(), Some("CRAFTER"));
     assert_eq!(automation.transition_type.as_deref(), Some("entry"));
 }
+
+/// A missing entity maps to the documented "not found" exit code.
+#[test]
+fn test_task_get_missing_exits_with_not_found_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("routa.db");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_routa"))
+        .args([
+            "--db",
+            db_path.to_str().unwrap(),
+            "task",
+            "get",
+            "--id",
+            "missing-task",
+        ])
+        .output()
+        .expect("routa binary should run");
+
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+}
+
+/// A delegation the orchestrator refuses exits with the provider code.
+#[test]
+fn test_delegate_failure_exits_with_provider_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("routa.db");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_routa"))
+        .args([
+            "--db",
+            db_path.to_str().unwrap(),
+            "delegate",
+            "--task-id",
+            "missing-task",
+            "--caller-agent-id",
+            "routa-1",
+            "--caller-session-id",
+            "session-1",
+            "--specialist",
+            "CRAFTER",
+        ])
+        .output()
+        .expect("routa binary should run");
+
+    assert_eq!(output.status.code(), Some(4), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Task not found"), "{output:?}");
+}
@@ -0,0 +1,72 @@
+//! Integration test for `routa events tail` against an in-process server.
+//!
+//! Kept in its own test binary because starting the server sets
+//! `ROUTA_SERVER_URL` for the whole process.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use routa_cli::commands::events;
+use routa_core::events::{AgentEvent, AgentEventType};
+use routa_core::state::{AppState, AppStateInner};
+use routa_core::Database;
+use routa_server::ServerConfig;
+
+#[tokio::test]
+async fn test_events_tail_prints_emitted_event() {
+    let db = Database::open(":memory:").expect("Failed to open in-memory database");
+    let state: AppState = Arc::new(AppStateInner::new(db));
+    let addr = routa_server::start_server_with_state(
+        ServerConfig {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            db_path: ":memory:".to_string(),
+            static_dir: None,
+            workflows_dir: None,
+            workspace_tokens: None,
+            tls_cert_path: None,
+            tls_key_path: None,
+            max_sessions_per_workspace: None,
+            db_encryption_key: None,
+        },
+        state.clone(),
+    )
+    .await
+    .expect("server should start");
+
+    let event_bus = state.event_bus.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        for (event_type, agent_id) in [
+            (AgentEventType::MessageSent, "filtered-agent"),
+            (AgentEventType::TaskAssigned, "crafter-tail"),
+        ] {
+            event_bus
+                .emit(AgentEvent {
+                    event_type,
+                    agent_id: agent_id.to_string(),
+                    workspace_id: "default".to_string(),
+                    data: serde_json::json!({ "taskId": "task-1" }),
+                    timestamp: Utc::now(),
+                })
+                .await;
+        }
+    });
+
+    let mut output: Vec<u8> = Vec::new();
+    events::tail_to(
+        &format!("http://{addr}"),
+        "default",
+        &["TASK_ASSIGNED".to_string()],
+        &mut output,
+        tokio::time::sleep(Duration::from_millis(1500)),
+    )
+    .await
+    .expect("tail should succeed");
+
+    let output = String::from_utf8(output).expect("output should be UTF-8");
+    assert!(output.contains("TASK_ASSIGNED"), "output: {output}");
+    assert!(output.contains("crafter-tail"), "output: {output}");
+    assert!(!output.contains("filtered-agent"), "output: {output}");
+}
@@ -2,6 +2,7 @@
 //!
 //! Handles:
 //! - Downloading agent archives from URLs
+//! - Verifying archives against the registry's SHA-256 checksum
 //! - Extracting ZIP, TAR.GZ, TAR.BZ2 formats
 //! - Setting executable permissions on Unix
 //! - Removing macOS quarantine attributes
@@ -9,6 +10,8 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
 use tokio::sync::Mutex;
 
 use super::paths::AcpPaths;
@@ -75,7 +78,11 @@ impl AcpBinaryManager {
 
         // Download the archive
         let archive_path = self
-            .download_archive(&binary_info.archive, &download_dir)
+            .download_archive(
+                &binary_info.archive,
+                binary_info.sha256.as_deref(),
+                &download_dir,
+            )
             .await?;
 
         // Extract the archive
@@ -102,8 +109,14 @@ impl AcpBinaryManager {
         Ok(exe_path)
     }
 
-    /// Download an archive from a URL.
-    async fn download_archive(&self, url: &str, download_dir: &Path) -> Result<PathBuf, String> {
+    /// Download an archive from a URL, rejecting it when it does not match
+    /// `expected_sha256`.
+    async fn download_archive(
+        &self,
+        url: &str,
+        expected_sha256: Option<&str>,
+        download_dir: &Path,
+    ) -> Result<PathBuf, String> {
         tracing::info!("[AcpBinaryManager] Downloading from {}", url);
 
         let response = reqwest::get(url)
@@ -133,6 +146,18 @@ impl AcpBinaryManager {
             .await
             .map_err(|e| format!("Failed to read response: {e}"))?;
 
+        if let Some(expected) = expected_sha256 {
+            let actual: String = Sha256::digest(&bytes)
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect();
+            if !actual.eq_ignore_ascii_case(expected.trim()) {
+                return Err(format!(
+                    "Checksum mismatch for {url}: expected {expected}, got {actual}"
+                ));
+            }
+        }
+
         tokio::fs::write(&archive_path, &bytes)
             .await
             .map_err(|e| format!("Failed to write archive: {e}"))?;
@@ -320,6 +345,17 @@ impl AcpBinaryManager {
         Ok(())
     }
 
+    /// Remove one installed version of a binary agent, keeping the others.
+    pub async fn remove_version(&self, agent_id: &str, version: &str) -> Result<(), String> {
+        let version_dir = self.paths.agent_version_dir(agent_id, version);
+        if version_dir.exists() {
+            tokio::fs::remove_dir_all(&version_dir)
+                .await
+                .map_err(|e| format!("Failed to remove agent version directory: {e}"))?;
+        }
+        Ok(())
+    }
+
     /// Uninstall a binary agent.
     pub async fn uninstall(&self, agent_id: &str) -> Result<(), String> {
         let agent_dir = self.paths.agent_dir(agent_id);
@@ -119,6 +119,8 @@ pub struct ClaudeCodeConfig {
     pub append_system_prompt: Option<String>,
     /// Optional allowlist for Claude built-in tools. Empty disables all built-ins.
     pub allowed_tools: Option<Vec<String>>,
+    /// Extra environment variables (e.g. stored provider credentials).
+    pub env: HashMap<String, String>,
 }
 
 impl Default for ClaudeCodeConfig {
@@ -131,6 +133,7 @@ impl Default for ClaudeCodeConfig {
             mcp_configs: Vec::new(),
             append_system_prompt: None,
             allowed_tools: None,
+            env: HashMap::new(),
         }
     }
 }
@@ -247,6 +250,7 @@ impl ClaudeCodeProcess {
         cmd.current_dir(&self.config.cwd);
         cmd.env("PATH", crate::shell_env::full_path());
         cmd.env("NODE_NO_READLINE", "1");
+        cmd.envs(&self.config.env);
         cmd.stdin(Stdio::piped());
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
@@ -2,12 +2,28 @@
 //!
 //! Tracks which agents are installed locally and persists state to JSON file.
 
+use std::cmp::Ordering;
 use std::sync::Arc;
+
+use serde::Serialize;
 use tokio::sync::RwLock;
 
 use super::paths::AcpPaths;
 use super::registry_types::{DistributionType, InstalledAgentInfo, InstalledAgentsState};
 
+/// Pseudo-version the registry reports when an agent has no pinned release.
+pub const LATEST_PSEUDO_VERSION: &str = "latest";
+
+/// Installed vs registry version of one agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentUpdateCheck {
+    pub agent_id: String,
+    pub installed_version: String,
+    pub available_version: String,
+    pub update_available: bool,
+}
+
 /// Manages the installation state of ACP agents.
 pub struct AcpInstallationState {
     paths: AcpPaths,
@@ -116,12 +132,98 @@ impl AcpInstallationState {
         self.save().await
     }
 
+    /// Compare an installed agent against the registry's version.
+    /// Returns `None` when the agent is not installed.
+    pub async fn check_update(
+        &self,
+        agent_id: &str,
+        latest_version: &str,
+    ) -> Option<AgentUpdateCheck> {
+        let state = self.state.read().await;
+        let installed = state.agents.get(agent_id)?;
+        Some(AgentUpdateCheck {
+            agent_id: agent_id.to_string(),
+            installed_version: installed.version.clone(),
+            available_version: latest_version.to_string(),
+            update_available: is_newer_version(&installed.version, latest_version),
+        })
+    }
+
     /// Check if an agent has an update available.
     pub async fn has_update(&self, agent_id: &str, latest_version: &str) -> bool {
-        if let Some(installed) = self.state.read().await.agents.get(agent_id) {
-            installed.version != latest_version
-        } else {
-            false
-        }
+        self.check_update(agent_id, latest_version)
+            .await
+            .is_some_and(|check| check.update_available)
+    }
+}
+
+/// Whether `available` is a newer release than `installed`.
+///
+/// Versions are compared as semver (a leading `v` is ignored). The `latest`
+/// pseudo-version never signals an update since nothing pins what it
+/// resolves to; other non-semver tags count as an update when they differ.
+pub fn is_newer_version(installed: &str, available: &str) -> bool {
+    let available = available.trim();
+    if available.is_empty() || available.eq_ignore_ascii_case(LATEST_PSEUDO_VERSION) {
+        return false;
+    }
+    match (parse_version(installed), parse_version(available)) {
+        (Some(installed), Some(available)) => available.cmp(&installed) == Ordering::Greater,
+        _ => installed.trim() != available,
+    }
+}
+
+fn parse_version(version: &str) -> Option<semver::Version> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    semver::Version::parse(version).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn semver_ordering_is_numeric_not_lexical() {
+        assert!(is_newer_version("1.9.0", "1.10.0"));
+        assert!(!is_newer_version("1.10.0", "1.9.0"));
+        assert!(is_newer_version("0.9.9", "0.10.0"));
+        assert!(!is_newer_version("2.0.0", "2.0.0"));
+        assert!(!is_newer_version("v2.0.0", "2.0.0"));
+        assert!(is_newer_version("2.0.0-beta.2", "2.0.0"));
+        assert!(is_newer_version("2.0.0-beta.2", "2.0.0-beta.10"));
+        assert!(!is_newer_version("2.0.0", "2.0.0-rc.1"));
+    }
+
+    #[test]
+    fn latest_and_other_tags_fall_back_to_string_compare() {
+        assert!(!is_newer_version("1.4.0", "latest"));
+        assert!(!is_newer_version("latest", "latest"));
+        assert!(!is_newer_version("1.4.0", ""));
+        assert!(is_newer_version("latest", "1.4.0"));
+        assert!(is_newer_version("nightly-0412", "nightly-0413"));
+        assert!(!is_newer_version("nightly-0413", "nightly-0413"));
+    }
+
+    #[tokio::test]
+    async fn check_update_reports_installed_and_available_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = AcpInstallationState::new(AcpPaths::with_base_dir(dir.path()));
+        state
+            .mark_installed("codex", "1.9.0", DistributionType::Binary, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            state.check_update("codex", "1.10.0").await,
+            Some(AgentUpdateCheck {
+                agent_id: "codex".to_string(),
+                installed_version: "1.9.0".to_string(),
+                available_version: "1.10.0".to_string(),
+                update_available: true,
+            })
+        );
+        assert!(!state.has_update("codex", "1.9.0").await);
+        assert_eq!(state.check_update("missing", "1.0.0").await, None);
     }
 }
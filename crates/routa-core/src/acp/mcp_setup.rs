@@ -44,9 +44,12 @@ fn build_mcp_endpoint(
         params.push(format!("toolMode={mode}"));
     }
 
-    if let Some(profile) =
-        mcp_profile.filter(|value| *value == "kanban-planning" || *value == "team-coordination")
-    {
+    if let Some(profile) = mcp_profile.filter(|value| {
+        matches!(
+            *value,
+            "kanban-planning" | "team-coordination" | "routa-planning"
+        )
+    }) {
         params.push(format!("mcpProfile={profile}"));
     }
 
@@ -449,6 +452,16 @@ mod tests {
         assert!(endpoint.contains("mcpProfile=team-coordination"));
     }
 
+    #[test]
+    fn routa_planning_profile_is_forwarded_in_mcp_endpoint() {
+        let endpoint = build_mcp_endpoint("default", "session-123", None, Some("routa-planning"));
+        assert!(endpoint.contains("mcpProfile=routa-planning"));
+        assert!(
+            !build_mcp_endpoint("default", "session-123", None, Some("bogus"))
+                .contains("mcpProfile")
+        );
+    }
+
     #[test]
     fn claude_inline_config_uses_routa_coordination_server() {
         let config = build_claude_mcp_config(
@@ -24,6 +24,7 @@ pub mod mcp_setup;
 pub mod paths;
 pub mod process;
 pub mod provider_adapter;
+pub mod provider_models;
 pub mod registry_fetch;
 pub mod registry_types;
 pub mod runtime_manager;
@@ -32,22 +33,30 @@ pub mod warmup;
 
 pub use binary_manager::AcpBinaryManager;
 pub use claude_code_process::{ClaudeCodeConfig, ClaudeCodeProcess};
-pub use installation_state::AcpInstallationState;
+pub use installation_state::{AcpInstallationState, AgentUpdateCheck};
 pub use paths::AcpPaths;
+pub use provider_models::{ProviderModels, ProviderModelsCache};
 pub use registry_fetch::{fetch_registry, fetch_registry_json};
 pub use registry_types::*;
 pub use runtime_manager::{current_platform, AcpRuntimeManager, RuntimeInfo, RuntimeType};
 pub use warmup::{AcpWarmupService, WarmupState, WarmupStatus};
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, RwLock};
 
+use crate::events::{AgentEvent, EventBus, SessionLimitReachedData};
+use crate::store::ProviderCredentialStore;
+use crate::tools::{LiveSessionDelivery, SessionLiveness};
 use crate::trace::{Contributor, TraceConversation, TraceEventType, TraceRecord, TraceWriter};
 use process::AcpProcess;
+use provider_adapter::{normalize_prompt_result, PromptResult};
 
 #[cfg(windows)]
 pub(crate) const CREATE_NO_WINDOW: u32 = 0x0800_0000;
@@ -67,6 +76,51 @@ fn validate_session_cwd(cwd: &str) -> Result<(), String> {
     Ok(())
 }
 
+fn duplicate_session_error(session_id: &str) -> String {
+    format!("Session {session_id} already exists and its agent is still running")
+}
+
+/// Live sessions one workspace may hold unless configured otherwise.
+pub const DEFAULT_MAX_SESSIONS_PER_WORKSPACE: usize = 16;
+/// Overrides [`DEFAULT_MAX_SESSIONS_PER_WORKSPACE`]; `0` disables the limit.
+pub const MAX_SESSIONS_PER_WORKSPACE_ENV: &str = "ROUTA_MAX_SESSIONS_PER_WORKSPACE";
+
+fn session_limit_error(workspace_id: &str, limit: usize) -> String {
+    format!(
+        "Workspace {workspace_id} already has {limit} live sessions, the maximum allowed; \
+         stop one before starting another"
+    )
+}
+
+/// Files and directories that mark a repo as set up for a specific agent,
+/// checked in order by [`AcpManager::detect_provider`].
+const PROVIDER_MARKERS: &[(&str, &str)] = &[
+    (".opencode", "opencode"),
+    ("opencode.json", "opencode"),
+    (".claude", "claude"),
+    ("CLAUDE.md", "claude"),
+    (".codex", "codex-acp"),
+    (".gemini", "gemini"),
+    ("GEMINI.md", "gemini"),
+    (".kiro", "kiro"),
+    (".qoder", "qoder"),
+];
+
+/// How many `session/update` notifications a session buffers per subscriber
+/// before slow subscribers start missing them (`RecvError::Lagged`).
+/// Override with [`NOTIFICATION_BUFFER_ENV`].
+pub const DEFAULT_NOTIFICATION_BUFFER: usize = 256;
+pub const NOTIFICATION_BUFFER_ENV: &str = "ROUTA_NOTIFICATION_BUFFER";
+
+fn notification_channel() -> broadcast::Sender<serde_json::Value> {
+    let capacity = std::env::var(NOTIFICATION_BUFFER_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|capacity| *capacity > 0)
+        .unwrap_or(DEFAULT_NOTIFICATION_BUFFER);
+    broadcast::channel(capacity).0
+}
+
 // ─── Session Record ─────────────────────────────────────────────────────
 
 /// Record of an active ACP session persisted for UI listing.
@@ -95,6 +149,52 @@ pub struct AcpSessionRecord {
     pub specialist_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub specialist_system_prompt: Option<String>,
+    /// User-assigned context, e.g. a label or a linked PR.
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+}
+
+/// What an ACP agent advertised in its `initialize` response.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcpCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<u64>,
+    /// The agent's `agentCapabilities` object, as sent.
+    #[serde(default)]
+    pub agent_capabilities: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_info: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auth_methods: Vec<serde_json::Value>,
+    /// The `modes` object returned by `session/new` or `session/load`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modes: Option<serde_json::Value>,
+}
+
+impl AcpCapabilities {
+    /// Parse an `initialize` result. Unexpected shapes yield empty capabilities.
+    pub fn from_initialize(result: &serde_json::Value) -> Self {
+        serde_json::from_value(result.clone()).unwrap_or_default()
+    }
+
+    /// Whether the agent supports `feature`. Agents rarely list everything
+    /// they accept, so only an explicit `false` counts as unsupported.
+    pub fn supports(&self, feature: &str) -> bool {
+        self.agent_capabilities.get(feature) != Some(&serde_json::Value::Bool(false))
+    }
+
+    /// Record the `modes` an agent returned when creating or loading a session.
+    pub fn with_modes(mut self, modes: Option<serde_json::Value>) -> Self {
+        self.modes = modes;
+        self
+    }
+
+    /// Whether `session/set_mode` may be sent to the agent. ACP agents opt in
+    /// to session modes by returning `modes` from `session/new`.
+    pub fn supports_set_mode(&self) -> bool {
+        self.modes.is_some()
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -105,6 +205,10 @@ pub struct SessionLaunchOptions {
     pub initialize_timeout_ms: Option<u64>,
     pub provider_args: Option<Vec<String>>,
     pub acp_mcp_servers: Option<Vec<serde_json::Value>>,
+    pub metadata: Option<serde_json::Value>,
+    /// When the session id already has a live agent process, return that
+    /// session instead of failing the create.
+    pub reuse_existing: bool,
 }
 
 // ─── Managed Process ────────────────────────────────────────────────────
@@ -126,6 +230,25 @@ impl AgentProcessType {
             AgentProcessType::Claude(process) => process.kill().await,
         }
     }
+
+    fn is_alive(&self) -> bool {
+        match self {
+            AgentProcessType::Acp(process) => process.is_alive(),
+            AgentProcessType::Claude(process) => process.is_alive(),
+        }
+    }
+
+    /// Capabilities negotiated during `initialize` and session setup.
+    /// Claude's stream-json protocol has no such handshake.
+    fn capabilities(&self) -> Option<AcpCapabilities> {
+        match self {
+            AgentProcessType::Acp(process) => process.initialize_result().map(|result| {
+                AcpCapabilities::from_initialize(result)
+                    .with_modes(process.session_modes().cloned())
+            }),
+            AgentProcessType::Claude(_) => None,
+        }
+    }
 }
 
 /// A managed agent process with its metadata.
@@ -141,8 +264,12 @@ struct ManagedProcess {
     /// Working directory (for contributor context)
     #[allow(dead_code)]
     cwd: String,
+    /// Workspace the session counts against for the live session limit.
+    workspace_id: String,
     /// Provider-specific MCP teardown to run when the session exits.
     mcp_cleanup: Option<mcp_setup::McpCleanupAction>,
+    /// Captured from the agent's `initialize` response.
+    capabilities: Option<AcpCapabilities>,
 }
 
 // ─── ACP Manager ────────────────────────────────────────────────────────
@@ -161,6 +288,12 @@ pub struct AcpManager {
     notification_channels: Arc<RwLock<HashMap<String, broadcast::Sender<serde_json::Value>>>>,
     /// Our sessionId → message history (session/update notifications)
     history: Arc<RwLock<HashMap<String, Vec<serde_json::Value>>>>,
+    /// Stored provider credentials, injected into each agent's environment
+    credential_store: Option<ProviderCredentialStore>,
+    /// Live sessions allowed per workspace; `0` means unlimited
+    max_sessions_per_workspace: Arc<AtomicUsize>,
+    /// Receives `SESSION_LIMIT_REACHED` when a create is refused
+    event_bus: Option<EventBus>,
 }
 
 impl Default for AcpManager {
@@ -169,6 +302,42 @@ impl Default for AcpManager {
     }
 }
 
+impl SessionLiveness for AcpManager {
+    fn session_alive<'a>(
+        &'a self,
+        agent_id: &'a str,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        Box::pin(async move { self.live_session_for_agent(agent_id).await.is_some() })
+    }
+}
+
+impl LiveSessionDelivery for AcpManager {
+    fn deliver<'a>(
+        &'a self,
+        agent_id: &'a str,
+        text: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Option<String>> + Send + 'a>> {
+        Box::pin(async move {
+            let session_id = self.live_session_for_agent(agent_id).await?;
+            // Don't hold the sender's tool call for the recipient's whole turn.
+            let manager = self.clone();
+            let prompt_session_id = session_id.clone();
+            let agent_id = agent_id.to_string();
+            let text = text.to_string();
+            tokio::spawn(async move {
+                if let Err(e) = manager.prompt(&prompt_session_id, &text).await {
+                    tracing::error!(
+                        "[AcpManager] Failed to deliver message to agent {}: {}",
+                        agent_id,
+                        e
+                    );
+                }
+            });
+            Some(session_id)
+        })
+    }
+}
+
 impl AcpManager {
     pub fn rewrite_notification_session_id(
         session_id: &str,
@@ -189,6 +358,96 @@ impl AcpManager {
             processes: Arc::new(RwLock::new(HashMap::new())),
             notification_channels: Arc::new(RwLock::new(HashMap::new())),
             history: Arc::new(RwLock::new(HashMap::new())),
+            credential_store: None,
+            max_sessions_per_workspace: Arc::new(AtomicUsize::new(
+                std::env::var(MAX_SESSIONS_PER_WORKSPACE_ENV)
+                    .ok()
+                    .and_then(|raw| raw.trim().parse().ok())
+                    .unwrap_or(DEFAULT_MAX_SESSIONS_PER_WORKSPACE),
+            )),
+            event_bus: None,
+        }
+    }
+
+    /// Inject credentials from `store` into every agent process spawned later.
+    pub fn with_credential_store(mut self, store: ProviderCredentialStore) -> Self {
+        self.credential_store = Some(store);
+        self
+    }
+
+    /// Emit an event on `bus` whenever a session create hits the workspace limit.
+    pub fn with_event_bus(mut self, bus: EventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
+    /// Cap live sessions per workspace at `limit` (`0` for no cap). Applies to
+    /// every clone of this manager.
+    pub fn set_max_sessions_per_workspace(&self, limit: usize) {
+        self.max_sessions_per_workspace
+            .store(limit, Ordering::Relaxed);
+    }
+
+    /// The per-workspace live session cap, or `None` when unlimited.
+    pub fn max_sessions_per_workspace(&self) -> Option<usize> {
+        Some(self.max_sessions_per_workspace.load(Ordering::Relaxed)).filter(|limit| *limit > 0)
+    }
+
+    /// Live agent processes in `workspace_id`.
+    async fn live_session_count(&self, workspace_id: &str) -> usize {
+        self.processes
+            .read()
+            .await
+            .values()
+            .filter(|managed| managed.workspace_id == workspace_id && managed.process.is_alive())
+            .count()
+    }
+
+    /// Whether `workspace_id` has reached
+    /// [`max_sessions_per_workspace`](Self::max_sessions_per_workspace), so a
+    /// new session there would be refused.
+    pub async fn at_session_limit(&self, workspace_id: &str) -> bool {
+        match self.max_sessions_per_workspace() {
+            Some(limit) => self.live_session_count(workspace_id).await >= limit,
+            None => false,
+        }
+    }
+
+    /// Refuse a create in `workspace_id`, announcing it on the event bus.
+    async fn reject_over_limit(
+        &self,
+        session_id: &str,
+        workspace_id: &str,
+        limit: usize,
+    ) -> String {
+        tracing::warn!(
+            "[AcpManager] Refusing session {} in workspace {}: {} live sessions",
+            session_id,
+            workspace_id,
+            limit
+        );
+        if let Some(bus) = &self.event_bus {
+            bus.emit(AgentEvent::session_limit_reached(
+                "system",
+                workspace_id,
+                SessionLimitReachedData {
+                    session_id: session_id.to_string(),
+                    limit,
+                },
+            ))
+            .await;
+        }
+        session_limit_error(workspace_id, limit)
+    }
+
+    /// Environment variables holding the stored credentials for `provider`.
+    async fn provider_env(&self, provider: &str) -> Result<HashMap<String, String>, String> {
+        match &self.credential_store {
+            Some(store) => store
+                .env_for(provider)
+                .await
+                .map_err(|e| format!("Failed to load credentials for '{provider}': {e}")),
+            None => Ok(HashMap::new()),
         }
     }
 
@@ -204,6 +463,19 @@ impl AcpManager {
         sessions.get(session_id).cloned()
     }
 
+    /// Replace a session's metadata.
+    /// Returns `Some(())` if the session was found, `None` if not found.
+    pub async fn set_session_metadata(
+        &self,
+        session_id: &str,
+        metadata: serde_json::Value,
+    ) -> Option<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(session_id)?;
+        session.metadata = metadata;
+        Some(())
+    }
+
     /// Rename a session.
     /// Returns `Some(())` if the session was found and renamed, `None` if not found.
     pub async fn rename_session(&self, session_id: &str, name: &str) -> Option<()> {
@@ -318,6 +590,16 @@ impl AcpManager {
         }
     }
 
+    /// Suggest a provider for `cwd` from agent-specific config it contains,
+    /// e.g. `.opencode/` → `opencode`. `None` when nothing matches.
+    pub fn detect_provider(cwd: &str) -> Option<&'static str> {
+        let root = Path::new(cwd);
+        PROVIDER_MARKERS
+            .iter()
+            .find(|(marker, _)| root.join(marker).exists())
+            .map(|(_, provider)| *provider)
+    }
+
     /// Create a new ACP session: spawn agent process, initialize, create session.
     /// Supports both static presets and registry-based agents.
     /// **Claude** uses stream-json protocol instead of ACP.
@@ -370,6 +652,12 @@ impl AcpManager {
         options: SessionLaunchOptions,
     ) -> Result<(String, String), String> {
         validate_session_cwd(&cwd)?;
+        if let Some(existing) = self
+            .claim_session_id(&session_id, &workspace_id, &options)
+            .await?
+        {
+            return Ok(existing);
+        }
         let provider_name = provider.as_deref().unwrap_or("opencode");
         let acp_mcp_servers = if matches!(provider_name, "codex" | "codex-acp") {
             options.acp_mcp_servers.clone().unwrap_or_else(|| {
@@ -388,7 +676,7 @@ impl AcpManager {
             return Err("Native session/load is not supported for Claude".to_string());
         }
 
-        let (ntx, _) = broadcast::channel::<serde_json::Value>(256);
+        let ntx = notification_channel();
         let preset = get_preset_by_id_with_registry(provider_name).await?;
 
         let mcp_setup = mcp_setup::ensure_mcp_for_provider(
@@ -424,10 +712,12 @@ impl AcpManager {
 
         let preset_command = resolve_preset_command(&preset);
         let launch_result = async {
+            let env = self.provider_env(provider_name).await?;
             let process = AcpProcess::spawn(
                 &preset_command,
                 &extra_args.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
                 &cwd,
+                &env,
                 ntx.clone(),
                 &preset.name,
                 &session_id,
@@ -473,7 +763,7 @@ impl AcpManager {
             ntx.clone(),
             mcp_cleanup,
         )
-        .await;
+        .await?;
 
         tracing::info!(
             "[AcpManager] Session {} loaded (provider: {}, agent session: {})",
@@ -562,7 +852,7 @@ impl AcpManager {
         acp_session_id: String,
         ntx: broadcast::Sender<serde_json::Value>,
         mcp_cleanup: Option<mcp_setup::McpCleanupAction>,
-    ) {
+    ) -> Result<(), String> {
         let created_at = chrono::Utc::now().to_rfc3339();
         let trace_writer = TraceWriter::new(&cwd);
         let record = AcpSessionRecord {
@@ -580,24 +870,73 @@ impl AcpManager {
             parent_session_id: parent_session_id.clone(),
             specialist_id: options.specialist_id.clone(),
             specialist_system_prompt: options.specialist_system_prompt.clone(),
+            metadata: options
+                .metadata
+                .clone()
+                .unwrap_or_else(|| serde_json::json!({})),
         };
 
+        // Re-check under the write lock: a concurrent create may have claimed
+        // the id, or filled the workspace, while this process was starting.
+        let replaced = {
+            let mut processes = self.processes.write().await;
+            let duplicate = processes
+                .get(&session_id)
+                .is_some_and(|existing| existing.process.is_alive());
+            let over_limit = self.max_sessions_per_workspace().filter(|limit| {
+                !duplicate
+                    && processes
+                        .iter()
+                        .filter(|(id, managed)| {
+                            **id != session_id
+                                && managed.workspace_id == workspace_id
+                                && managed.process.is_alive()
+                        })
+                        .count()
+                        >= *limit
+            });
+            if duplicate || over_limit.is_some() {
+                drop(processes);
+                let error = match over_limit {
+                    Some(limit) => {
+                        self.reject_over_limit(&session_id, &workspace_id, limit)
+                            .await
+                    }
+                    None => duplicate_session_error(&session_id),
+                };
+                process_type.kill().await;
+                if let Some(cleanup) = mcp_cleanup.as_ref() {
+                    let summary = mcp_setup::cleanup_mcp_for_provider(cleanup).await;
+                    tracing::warn!("[AcpManager] {}", summary);
+                }
+                return Err(error);
+            }
+            processes.insert(
+                session_id.clone(),
+                ManagedProcess {
+                    capabilities: process_type.capabilities(),
+                    process: process_type,
+                    acp_session_id: acp_session_id.clone(),
+                    preset_id: provider_name.clone(),
+                    created_at,
+                    trace_writer: trace_writer.clone(),
+                    cwd: cwd.clone(),
+                    workspace_id: workspace_id.clone(),
+                    mcp_cleanup,
+                },
+            )
+        };
+        if let Some(stale) = replaced {
+            stale.process.kill().await;
+            if let Some(cleanup) = stale.mcp_cleanup.as_ref() {
+                let summary = mcp_setup::cleanup_mcp_for_provider(cleanup).await;
+                tracing::info!("[AcpManager] {}", summary);
+            }
+        }
         self.sessions
             .write()
             .await
             .insert(session_id.clone(), record);
-        self.processes.write().await.insert(
-            session_id.clone(),
-            ManagedProcess {
-                process: process_type,
-                acp_session_id: acp_session_id.clone(),
-                preset_id: provider_name.clone(),
-                created_at,
-                trace_writer: trace_writer.clone(),
-                cwd: cwd.clone(),
-                mcp_cleanup,
-            },
-        );
         self.notification_channels
             .write()
             .await
@@ -617,6 +956,38 @@ impl AcpManager {
         .with_metadata("cwd", serde_json::json!(cwd));
 
         trace_writer.append_safe(&trace).await;
+        Ok(())
+    }
+
+    /// Refuse to start a second agent for a session id whose process is still
+    /// alive. With [`SessionLaunchOptions::reuse_existing`] the live session's
+    /// ids are returned instead, and the caller should hand those back as-is.
+    /// A new session is also refused once `workspace_id` has reached
+    /// [`max_sessions_per_workspace`](Self::max_sessions_per_workspace).
+    async fn claim_session_id(
+        &self,
+        session_id: &str,
+        workspace_id: &str,
+        options: &SessionLaunchOptions,
+    ) -> Result<Option<(String, String)>, String> {
+        if !self.is_alive(session_id).await {
+            if let Some(limit) = self.max_sessions_per_workspace() {
+                if self.live_session_count(workspace_id).await >= limit {
+                    return Err(self
+                        .reject_over_limit(session_id, workspace_id, limit)
+                        .await);
+                }
+            }
+            return Ok(None);
+        }
+        if !options.reuse_existing {
+            return Err(duplicate_session_error(session_id));
+        }
+        let acp_session_id = self
+            .get_acp_session_id(session_id)
+            .await
+            .ok_or_else(|| duplicate_session_error(session_id))?;
+        Ok(Some((session_id.to_string(), acp_session_id)))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -634,12 +1005,20 @@ impl AcpManager {
         options: SessionLaunchOptions,
     ) -> Result<(String, String), String> {
         validate_session_cwd(&cwd)?;
-        let (ntx, _) = broadcast::channel::<serde_json::Value>(256);
+        if let Some(existing) = self
+            .claim_session_id(&session_id, &workspace_id, &options)
+            .await?
+        {
+            return Ok(existing);
+        }
+        let ntx = notification_channel();
+        let env = self.provider_env(&provider_name).await?;
 
         let process = AcpProcess::spawn(
             &command,
             &args.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
             &cwd,
+            &env,
             ntx.clone(),
             &provider_name,
             &session_id,
@@ -667,7 +1046,7 @@ impl AcpManager {
             ntx.clone(),
             None,
         )
-        .await;
+        .await?;
 
         tracing::info!(
             "[AcpManager] Session {} created from inline command (provider: {}, agent session: {})",
@@ -695,12 +1074,20 @@ impl AcpManager {
         options: SessionLaunchOptions,
     ) -> Result<(String, String), String> {
         validate_session_cwd(&cwd)?;
-        let (ntx, _) = broadcast::channel::<serde_json::Value>(256);
+        if let Some(existing) = self
+            .claim_session_id(&session_id, &workspace_id, &options)
+            .await?
+        {
+            return Ok(existing);
+        }
+        let ntx = notification_channel();
+        let env = self.provider_env(&provider_name).await?;
 
         let process = AcpProcess::spawn(
             &command,
             &args.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
             &cwd,
+            &env,
             ntx.clone(),
             &provider_name,
             &session_id,
@@ -735,7 +1122,7 @@ impl AcpManager {
             ntx.clone(),
             None,
         )
-        .await;
+        .await?;
 
         tracing::info!(
             "[AcpManager] Session {} loaded from inline command (provider: {}, agent session: {})",
@@ -762,7 +1149,16 @@ impl AcpManager {
         options: SessionLaunchOptions,
     ) -> Result<(String, String), String> {
         validate_session_cwd(&cwd)?;
-        let provider_name = provider.as_deref().unwrap_or("opencode");
+        if let Some(existing) = self
+            .claim_session_id(&session_id, &workspace_id, &options)
+            .await?
+        {
+            return Ok(existing);
+        }
+        let provider_name = provider
+            .as_deref()
+            .or_else(|| Self::detect_provider(&cwd))
+            .unwrap_or("opencode");
         let acp_mcp_servers = if matches!(provider_name, "codex" | "codex-acp") {
             options.acp_mcp_servers.clone().unwrap_or_else(|| {
                 mcp_setup::build_acp_http_mcp_servers(
@@ -777,7 +1173,7 @@ impl AcpManager {
         };
 
         // Create the notification broadcast channel for this session
-        let (ntx, _) = broadcast::channel::<serde_json::Value>(256);
+        let ntx = notification_channel();
         let claude_mcp_config = if provider_name == "claude" {
             Some(mcp_setup::build_claude_mcp_config(
                 &workspace_id,
@@ -800,6 +1196,7 @@ impl AcpManager {
                 mcp_configs: claude_mcp_config.into_iter().collect(),
                 append_system_prompt: options.specialist_system_prompt.clone(),
                 allowed_tools: options.allowed_native_tools.clone(),
+                env: self.provider_env(provider_name).await?,
             };
 
             let claude_process = ClaudeCodeProcess::spawn(config, ntx.clone()).await?;
@@ -852,10 +1249,12 @@ impl AcpManager {
 
             let preset_command = resolve_preset_command(&preset);
             let launch_result = async {
+                let env = self.provider_env(provider_name).await?;
                 let process = AcpProcess::spawn(
                     &preset_command,
                     &extra_args.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
                     &cwd,
+                    &env,
                     ntx.clone(),
                     &preset.name,
                     &session_id,
@@ -904,7 +1303,7 @@ impl AcpManager {
             ntx.clone(),
             mcp_cleanup,
         )
-        .await;
+        .await?;
 
         tracing::info!(
             "[AcpManager] Session {} created (provider: {}, agent session: {})",
@@ -916,8 +1315,60 @@ impl AcpManager {
         Ok((session_id, acp_session_id))
     }
 
-    /// Send a prompt to an existing session's agent process.
-    pub async fn prompt(&self, session_id: &str, text: &str) -> Result<serde_json::Value, String> {
+    /// Send a prompt to an existing session's agent process and return a
+    /// [`PromptResult`] built from the response and the session updates
+    /// streamed during the turn, in the same shape for every provider.
+    pub async fn prompt(&self, session_id: &str, text: &str) -> Result<PromptResult, String> {
+        let provider = {
+            let processes = self.processes.read().await;
+            processes
+                .get(session_id)
+                .map(|managed| match &managed.process {
+                    AgentProcessType::Acp(_) => managed.preset_id.clone(),
+                    AgentProcessType::Claude(_) => "claude".to_string(),
+                })
+                .unwrap_or_default()
+        };
+        let mut updates = Vec::new();
+        let raw = match self.subscribe(session_id).await {
+            Some(mut rx) => {
+                let raw_future = self.prompt_raw(session_id, text);
+                tokio::pin!(raw_future);
+                let raw = loop {
+                    tokio::select! {
+                        result = &mut raw_future => break result?,
+                        update = rx.recv() => match update {
+                            Ok(update) => updates.push(update),
+                            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                                tracing::warn!(
+                                    "[AcpManager] Prompt result for {} missed {} updates",
+                                    session_id,
+                                    skipped
+                                );
+                            }
+                            Err(broadcast::error::RecvError::Closed) => {
+                                break (&mut raw_future).await?;
+                            }
+                        },
+                    }
+                };
+                while let Ok(update) = rx.try_recv() {
+                    updates.push(update);
+                }
+                raw
+            }
+            None => self.prompt_raw(session_id, text).await?,
+        };
+        Ok(normalize_prompt_result(&provider, &raw, &updates))
+    }
+
+    /// Send a prompt and return the provider's response as-is: the ACP
+    /// `session/prompt` result, or `{ "stopReason": <subtype> }` for Claude Code.
+    pub async fn prompt_raw(
+        &self,
+        session_id: &str,
+        text: &str,
+    ) -> Result<serde_json::Value, String> {
         self.mark_first_prompt_sent(session_id).await;
 
         let (process, acp_session_id, preset_id, trace_writer) = {
@@ -1047,11 +1498,36 @@ impl AcpManager {
         let processes = self.processes.read().await;
         processes
             .get(session_id)
-            .map(|m| match &m.process {
-                AgentProcessType::Acp(p) => p.is_alive(),
-                AgentProcessType::Claude(p) => p.is_alive(),
-            })
-            .unwrap_or(false)
+            .is_some_and(|m| m.process.is_alive())
+    }
+
+    /// A session attached to `routa_agent_id` whose agent process is still
+    /// running.
+    pub async fn live_session_for_agent(&self, routa_agent_id: &str) -> Option<String> {
+        let candidates: Vec<String> = {
+            let sessions = self.sessions.read().await;
+            sessions
+                .values()
+                .filter(|s| s.routa_agent_id.as_deref() == Some(routa_agent_id))
+                .map(|s| s.session_id.clone())
+                .collect()
+        };
+        let processes = self.processes.read().await;
+        candidates.into_iter().find(|session_id| {
+            processes
+                .get(session_id)
+                .is_some_and(|m| m.process.is_alive())
+        })
+    }
+
+    /// The trace file a live session is appending to. `None` if the session
+    /// isn't live or no trace could be written for it yet.
+    pub async fn trace_path(&self, session_id: &str) -> Option<PathBuf> {
+        let trace_writer = {
+            let processes = self.processes.read().await;
+            processes.get(session_id)?.trace_writer.clone()
+        };
+        trace_writer.current_path().await
     }
 
     /// Get the managed ACP session id for a live session.
@@ -1062,6 +1538,15 @@ impl AcpManager {
             .map(|managed| managed.acp_session_id.clone())
     }
 
+    /// Get the capabilities the session's agent advertised in `initialize`.
+    /// `None` if the session isn't live or its protocol has no handshake.
+    pub async fn get_capabilities(&self, session_id: &str) -> Option<AcpCapabilities> {
+        let processes = self.processes.read().await;
+        processes
+            .get(session_id)
+            .and_then(|managed| managed.capabilities.clone())
+    }
+
     /// Get the preset ID for a session.
     pub async fn get_preset_id(&self, session_id: &str) -> Option<String> {
         let processes = self.processes.read().await;
@@ -1282,7 +1767,22 @@ pub fn get_resume_capability(provider: &str) -> Option<ResumeCapability> {
 ///
 /// Supports suffixed IDs like "auggie-registry" to explicitly request
 /// the registry version when both built-in and registry versions exist.
+///
+/// Agents installed locally as a binary resolve to the installed executable
+/// without fetching the registry, so they work offline.
 pub async fn get_preset_by_id_with_registry(id: &str) -> Result<AcpPreset, String> {
+    let installation = AcpInstallationState::new(AcpPaths::new());
+    if let Err(e) = installation.load().await {
+        tracing::warn!("[AcpManager] Ignoring installation state: {}", e);
+    }
+    get_preset_by_id_with_installation(id, &installation).await
+}
+
+/// [`get_preset_by_id_with_registry`] against an explicit installation state.
+pub async fn get_preset_by_id_with_installation(
+    id: &str,
+    installation: &AcpInstallationState,
+) -> Result<AcpPreset, String> {
     let normalized_id = match id {
         "codex" => "codex-acp",
         "qodercli" => "qoder",
@@ -1293,7 +1793,10 @@ pub async fn get_preset_by_id_with_registry(id: &str) -> Result<AcpPreset, Strin
     // This allows explicit selection of registry version when both exist
     const REGISTRY_SUFFIX: &str = "-registry";
     if let Some(base_id) = normalized_id.strip_suffix(REGISTRY_SUFFIX) {
-        let mut preset = get_registry_preset(base_id).await?;
+        let mut preset = match get_installed_binary_preset(base_id, installation).await {
+            Some(preset) => preset,
+            None => get_registry_preset(base_id).await?,
+        };
         // Keep the suffixed ID in the returned preset for consistency
         preset.id = id.to_string();
         return Ok(preset);
@@ -1307,14 +1810,39 @@ pub async fn get_preset_by_id_with_registry(id: &str) -> Result<AcpPreset, Strin
         return Ok(preset);
     }
 
-    // Fall back to registry
-    let mut preset = get_registry_preset(normalized_id).await?;
+    // Fall back to a local binary install, then the registry
+    let mut preset = match get_installed_binary_preset(normalized_id, installation).await {
+        Some(preset) => preset,
+        None => get_registry_preset(normalized_id).await?,
+    };
     if preset.id != id {
         preset.id = id.to_string();
     }
     Ok(preset)
 }
 
+/// Build a preset for an agent installed as a binary whose executable is
+/// still on disk.
+async fn get_installed_binary_preset(
+    id: &str,
+    installation: &AcpInstallationState,
+) -> Option<AcpPreset> {
+    let info = installation.get_installed_info(id).await?;
+    if info.dist_type != DistributionType::Binary {
+        return None;
+    }
+    let binary_path = info.binary_path.filter(|path| Path::new(path).is_file())?;
+    Some(AcpPreset {
+        id: info.agent_id.clone(),
+        name: info.agent_id,
+        command: binary_path,
+        args: Vec::new(),
+        description: format!("Installed binary (v{})", info.version),
+        env_bin_override: None,
+        resume: None,
+    })
+}
+
 /// Get a preset from the ACP registry by ID.
 async fn get_registry_preset(id: &str) -> Result<AcpPreset, String> {
     let registry: AcpRegistry = fetch_registry().await?;
@@ -1382,11 +1910,13 @@ fn truncate_content(text: &str, max_len: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::{
-        get_preset_by_id_with_registry, get_presets, truncate_content, validate_session_cwd,
-        AcpManager, AcpSessionRecord,
+        get_preset_by_id_with_installation, get_preset_by_id_with_registry, get_presets,
+        truncate_content, validate_session_cwd, AcpCapabilities, AcpInstallationState, AcpManager,
+        AcpPaths, AcpSessionRecord, DistributionType, SessionLaunchOptions,
     };
     use std::collections::HashMap;
     use std::fs;
+    use std::sync::atomic::AtomicUsize;
     use std::sync::Arc;
     use tokio::sync::RwLock;
 
@@ -1422,6 +1952,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn installed_binary_agent_resolves_without_registry() {
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let binary = temp.path().join("offline-agent");
+        fs::write(&binary, "#!/bin/sh\n").expect("binary should be written");
+        let installation =
+            AcpInstallationState::new(AcpPaths::with_base_dir(temp.path().join("acp")));
+        installation
+            .mark_installed(
+                "offline-agent",
+                "1.2.0",
+                DistributionType::Binary,
+                Some(binary.to_string_lossy().to_string()),
+                None,
+            )
+            .await
+            .expect("install should be recorded");
+
+        // Not a builtin preset, and not in any registry: resolving it through
+        // the registry would fail.
+        for id in ["offline-agent", "offline-agent-registry"] {
+            let preset = get_preset_by_id_with_installation(id, &installation)
+                .await
+                .expect("installed binary should resolve offline");
+            assert_eq!(preset.id, id);
+            assert_eq!(preset.command, binary.to_string_lossy());
+        }
+    }
+
+    #[test]
+    fn detect_provider_recognizes_agent_config_dirs() {
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let cwd = temp.path().to_string_lossy().to_string();
+        assert_eq!(AcpManager::detect_provider(&cwd), None);
+
+        fs::create_dir(temp.path().join(".opencode")).expect("config dir should be created");
+        assert_eq!(AcpManager::detect_provider(&cwd), Some("opencode"));
+    }
+
     #[test]
     fn validate_session_cwd_rejects_missing_or_non_directory_paths() {
         let temp = tempfile::tempdir().expect("tempdir should create");
@@ -1462,6 +2031,7 @@ mod tests {
                 parent_session_id: None,
                 specialist_id: None,
                 specialist_system_prompt: None,
+                metadata: serde_json::json!({}),
             },
         );
 
@@ -1478,6 +2048,9 @@ mod tests {
             processes: Arc::new(RwLock::new(HashMap::new())),
             notification_channels: Arc::new(RwLock::new(HashMap::new())),
             history: Arc::new(RwLock::new(HashMap::new())),
+            credential_store: None,
+            max_sessions_per_workspace: Arc::new(AtomicUsize::new(0)),
+            event_bus: None,
         };
 
         manager
@@ -1509,6 +2082,9 @@ mod tests {
                 tx,
             )]))),
             history: Arc::new(RwLock::new(HashMap::new())),
+            credential_store: None,
+            max_sessions_per_workspace: Arc::new(AtomicUsize::new(0)),
+            event_bus: None,
         };
 
         manager
@@ -1540,6 +2116,9 @@ mod tests {
             processes: Arc::new(RwLock::new(HashMap::new())),
             notification_channels: Arc::new(RwLock::new(HashMap::new())),
             history: Arc::new(RwLock::new(HashMap::new())),
+            credential_store: None,
+            max_sessions_per_workspace: Arc::new(AtomicUsize::new(0)),
+            event_bus: None,
         };
 
         manager
@@ -1577,6 +2156,327 @@ mod tests {
         assert_eq!(rewritten["sessionId"].as_str(), Some("child-session"));
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn capabilities_from_initialize_are_retrievable() {
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        // Answers `initialize` (id 1) and `session/new` (id 2), then idles.
+        let stub_agent = r#"read _
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1,"agentCapabilities":{"loadSession":true,"sessionModes":true},"agentInfo":{"name":"stub","version":"0.0.1"}}}'
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session"}}'
+cat > /dev/null"#;
+        let manager = AcpManager::new();
+        manager
+            .create_session_from_inline(
+                "session-1".to_string(),
+                temp.path().to_string_lossy().to_string(),
+                "default".to_string(),
+                "stub".to_string(),
+                None,
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                SessionLaunchOptions::default(),
+            )
+            .await
+            .expect("stub session should start");
+
+        let capabilities = manager
+            .get_capabilities("session-1")
+            .await
+            .expect("capabilities should be stored");
+        assert_eq!(capabilities.protocol_version, Some(1));
+        assert!(capabilities.supports("loadSession"));
+        // Modes come from `session/new`, not from `agentCapabilities`.
+        assert!(!capabilities.supports_set_mode());
+        assert_eq!(
+            capabilities.agent_info,
+            Some(serde_json::json!({ "name": "stub", "version": "0.0.1" }))
+        );
+        assert!(manager.get_capabilities("missing").await.is_none());
+
+        manager.delete_session("session-1").await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn session_modes_come_from_the_session_new_response() {
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let stub_agent = r#"read _
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1,"agentCapabilities":{}}}'
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session","modes":{"currentModeId":"ask","availableModes":[{"id":"ask","name":"Ask"},{"id":"code","name":"Code"}]}}}'
+cat > /dev/null"#;
+        let manager = AcpManager::new();
+        manager
+            .create_session_from_inline(
+                "session-1".to_string(),
+                temp.path().to_string_lossy().to_string(),
+                "default".to_string(),
+                "stub".to_string(),
+                None,
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                SessionLaunchOptions::default(),
+            )
+            .await
+            .expect("stub session should start");
+
+        let capabilities = manager
+            .get_capabilities("session-1")
+            .await
+            .expect("capabilities should be stored");
+        assert!(capabilities.supports_set_mode());
+        assert_eq!(
+            capabilities.modes.as_ref().unwrap()["currentModeId"],
+            serde_json::json!("ask")
+        );
+
+        manager.delete_session("session-1").await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn trace_path_points_at_the_session_trace_file() {
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let cwd = temp.path().to_string_lossy().to_string();
+        let stub_agent = r#"read _
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1}}'
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session"}}'
+cat > /dev/null"#;
+        let manager = AcpManager::new();
+        assert!(manager.trace_path("session-1").await.is_none());
+        manager
+            .create_session_from_inline(
+                "session-1".to_string(),
+                cwd.clone(),
+                "default".to_string(),
+                "stub".to_string(),
+                None,
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                SessionLaunchOptions::default(),
+            )
+            .await
+            .expect("stub session should start");
+
+        let path = manager
+            .trace_path("session-1")
+            .await
+            .expect("session start trace should be written");
+        assert!(path.starts_with(crate::storage::get_traces_dir(&cwd)));
+        let written = fs::read_to_string(&path).expect("trace file should be readable");
+        let start: serde_json::Value =
+            serde_json::from_str(written.lines().next().expect("one trace line"))
+                .expect("trace line should be json");
+        assert_eq!(start["sessionId"], "session-1");
+
+        manager.delete_session("session-1").await;
+        let _ = fs::remove_dir_all(crate::storage::get_project_storage_dir(&cwd));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn duplicate_session_id_is_rejected_while_agent_is_alive() {
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let stub_agent = r#"read _
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1}}'
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session"}}'
+cat > /dev/null"#;
+        let manager = AcpManager::new();
+        let create = |options: SessionLaunchOptions| {
+            manager.create_session_from_inline(
+                "session-1".to_string(),
+                temp.path().to_string_lossy().to_string(),
+                "default".to_string(),
+                "stub".to_string(),
+                None,
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                options,
+            )
+        };
+
+        create(SessionLaunchOptions::default())
+            .await
+            .expect("first session should start");
+        let error = create(SessionLaunchOptions::default())
+            .await
+            .expect_err("duplicate id should be rejected");
+        assert!(error.contains("session-1 already exists"), "{error}");
+        assert!(manager.is_alive("session-1").await);
+
+        let reused = create(SessionLaunchOptions {
+            reuse_existing: true,
+            ..Default::default()
+        })
+        .await
+        .expect("live session should be reused");
+        assert_eq!(
+            reused,
+            ("session-1".to_string(), "stub-session".to_string())
+        );
+        assert!(manager.is_alive("session-1").await);
+
+        manager.delete_session("session-1").await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn sessions_beyond_the_workspace_limit_are_rejected() {
+        use crate::events::{AgentEventType, EventBus, EventSubscription};
+
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let stub_agent = r#"read _
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1}}'
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session"}}'
+cat > /dev/null"#;
+        let bus = EventBus::new();
+        bus.subscribe(EventSubscription {
+            id: "sub-observer".to_string(),
+            agent_id: "observer".to_string(),
+            agent_name: "observer".to_string(),
+            event_types: vec![AgentEventType::SessionLimitReached],
+            exclude_self: false,
+            one_shot: false,
+            wait_group_id: None,
+            priority: 0,
+            workspace_id: "ws-1".to_string(),
+            cross_workspace: false,
+            ttl_secs: None,
+        })
+        .await;
+        let manager = AcpManager::new().with_event_bus(bus.clone());
+        manager.set_max_sessions_per_workspace(2);
+        let create = |session_id: &str, workspace_id: &str| {
+            manager.create_session_from_inline(
+                session_id.to_string(),
+                temp.path().to_string_lossy().to_string(),
+                workspace_id.to_string(),
+                "stub".to_string(),
+                None,
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                SessionLaunchOptions::default(),
+            )
+        };
+
+        create("session-1", "ws-1").await.expect("first session");
+        create("session-2", "ws-1").await.expect("second session");
+        let error = create("session-3", "ws-1")
+            .await
+            .expect_err("third session should exceed the limit");
+        assert!(
+            error.contains("Workspace ws-1 already has 2 live sessions"),
+            "{error}"
+        );
+        assert!(!manager.is_alive("session-3").await);
+        create("session-4", "ws-2")
+            .await
+            .expect("other workspaces have their own budget");
+
+        let events = bus.drain_pending_events("observer").await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, AgentEventType::SessionLimitReached);
+        assert_eq!(events[0].data["sessionId"], "session-3");
+        assert_eq!(events[0].data["limit"], 2);
+
+        manager.kill_session("session-1").await;
+        create("session-3", "ws-1")
+            .await
+            .expect("a freed slot admits a new session");
+
+        for id in ["session-2", "session-3", "session-4"] {
+            manager.kill_session(id).await;
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stored_credentials_are_injected_into_agent_env() {
+        use crate::store::{MachineKeyCipher, ProviderCredentialStore};
+
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let db = crate::db::Database::open_in_memory().expect("in-memory db should open");
+        let store = ProviderCredentialStore::with_cipher(
+            db,
+            Arc::new(MachineKeyCipher::from_secret(b"test-secret")),
+        );
+        let masked = store
+            .set("stub", "STUB_API_KEY", "sk-stub-0123456789wxyz")
+            .await
+            .expect("credential should be stored");
+        assert_eq!(masked.masked_value, "****wxyz");
+
+        // Reports the injected key back as its agent name.
+        let stub_agent = r#"read _
+echo "{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"protocolVersion\":1,\"agentInfo\":{\"name\":\"$STUB_API_KEY\"}}}"
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session"}}'
+cat > /dev/null"#;
+        let manager = AcpManager::new().with_credential_store(store.clone());
+        manager
+            .create_session_from_inline(
+                "session-1".to_string(),
+                temp.path().to_string_lossy().to_string(),
+                "default".to_string(),
+                "stub".to_string(),
+                None,
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                SessionLaunchOptions::default(),
+            )
+            .await
+            .expect("stub session should start");
+
+        let capabilities = manager
+            .get_capabilities("session-1")
+            .await
+            .expect("capabilities should be stored");
+        assert_eq!(
+            capabilities.agent_info,
+            Some(serde_json::json!({ "name": "sk-stub-0123456789wxyz" }))
+        );
+
+        let listed = store.list_masked("stub").await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert!(!serde_json::to_string(&listed)
+            .unwrap()
+            .contains("sk-stub-0123456789wxyz"));
+
+        manager.delete_session("session-1").await;
+    }
+
+    #[test]
+    fn capabilities_treat_unlisted_features_as_supported() {
+        let capabilities = AcpCapabilities::from_initialize(&serde_json::json!({
+            "protocolVersion": 1
+        }));
+        assert!(capabilities.supports("loadSession"));
+        assert!(!capabilities.supports_set_mode());
+        assert!(
+            AcpCapabilities::from_initialize(&serde_json::json!("garbage")).supports("loadSession")
+        );
+        assert!(capabilities
+            .with_modes(Some(serde_json::json!({ "availableModes": [] })))
+            .supports_set_mode());
+    }
+
     #[test]
     fn truncate_content_handles_unicode_boundaries() {
         assert_eq!(truncate_content("你好世界ABC", 5), "你好...");
@@ -1,16 +1,20 @@
 //! ACP installation paths management.
 //!
 //! Manages directory structure for locally installed ACP agents:
-//!   - Base directory: `{data_dir}/acp-agents/`
+//!   - Base directory: `$ROUTA_ACP_HOME`, or `{data_dir}/acp-agents/`
 //!   - Agent binaries: `{base}/{agentId}/{version}/`
 //!   - Downloads: `{base}/.downloads/{agentId}/{version}/`
 //!   - Runtimes: `{base}/.runtimes/{runtime}/{version}/`
 //!   - Icons: `{base}/.icons/`
 //!   - Registry cache: `{base}/registry.json`
 //!   - Installed state: `{base}/installed.json`
+//!   - Provider model lists: `{base}/provider-models.json`
 
 use std::path::PathBuf;
 
+/// Overrides the base directory used by [`AcpPaths::new`].
+pub const ACP_HOME_ENV: &str = "ROUTA_ACP_HOME";
+
 /// ACP paths manager for local agent installation.
 #[derive(Debug, Clone)]
 pub struct AcpPaths {
@@ -18,17 +22,27 @@ pub struct AcpPaths {
 }
 
 impl AcpPaths {
-    /// Create a new AcpPaths instance using the system data directory.
+    /// Create a new AcpPaths instance rooted at `$ROUTA_ACP_HOME`, falling
+    /// back to the system data directory.
     pub fn new() -> Self {
-        let base_dir = dirs::data_local_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("acp-agents");
-        Self { base_dir }
+        Self::with_base_dir(Self::resolve_base_dir(std::env::var_os(ACP_HOME_ENV)))
+    }
+
+    /// Create AcpPaths with a custom base directory (for isolated tests and
+    /// portable installs).
+    pub fn with_base_dir(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+        }
     }
 
-    /// Create AcpPaths with a custom base directory (for testing).
-    pub fn with_base_dir(base_dir: PathBuf) -> Self {
-        Self { base_dir }
+    fn resolve_base_dir(override_dir: Option<std::ffi::OsString>) -> PathBuf {
+        match override_dir {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => dirs::data_local_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("acp-agents"),
+        }
     }
 
     /// Get the base directory for all ACP agent data.
@@ -76,6 +90,11 @@ impl AcpPaths {
         self.base_dir.join("registry.json")
     }
 
+    /// Get the path to the cached provider model lists.
+    pub fn provider_models_cache_path(&self) -> PathBuf {
+        self.base_dir.join("provider-models.json")
+    }
+
     /// Get the path to the installed agents state file.
     pub fn installed_state_path(&self) -> PathBuf {
         self.base_dir.join("installed.json")
@@ -112,3 +131,37 @@ impl Default for AcpPaths {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_base_dir_routes_all_paths_under_base() {
+        let base = tempfile::tempdir().unwrap();
+        let paths = AcpPaths::with_base_dir(base.path());
+
+        for path in [
+            paths.agent_version_dir("opencode", "1.0.0"),
+            paths.agent_download_dir("opencode", "1.0.0"),
+            paths.runtime_dir("node", "22"),
+            paths.icons_dir(),
+            paths.registry_cache_path(),
+            paths.installed_state_path(),
+        ] {
+            assert!(path.starts_with(base.path()), "{}", path.display());
+        }
+        paths.ensure_directories().unwrap();
+        assert!(paths.downloads_dir().is_dir());
+    }
+
+    #[test]
+    fn acp_home_override_replaces_data_dir() {
+        assert_eq!(
+            AcpPaths::resolve_base_dir(Some("/opt/routa/acp".into())),
+            PathBuf::from("/opt/routa/acp")
+        );
+        assert!(AcpPaths::resolve_base_dir(Some("".into())).ends_with("acp-agents"));
+        assert!(AcpPaths::resolve_base_dir(None).ends_with("acp-agents"));
+    }
+}
@@ -9,6 +9,12 @@
 //!
 //! Agent→client requests (permissions, fs, terminal) are handled in the background reader.
 //! Agent message notifications are traced to JSONL files for attribution tracking.
+//!
+//! Stdout lines longer than [`DEFAULT_MAX_LINE_BYTES`] (or the value of
+//! [`MAX_LINE_BYTES_ENV`]) are discarded as they stream in rather than
+//! buffered, and a `process_output` diagnostic is sent in their place. When
+//! the skipped line is a response, the pending request with its `id` fails
+//! with a size error.
 
 use std::collections::HashMap;
 use std::io::ErrorKind;
@@ -16,10 +22,10 @@ use std::io::ErrorKind;
 use std::os::windows::process::CommandExt;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin};
 use tokio::sync::{broadcast, oneshot, Mutex};
 
@@ -33,6 +39,12 @@ use crate::trace::{
 /// Callback type for session/update notifications from the agent.
 pub type NotificationSender = broadcast::Sender<serde_json::Value>;
 
+/// Longest stdout line the reader will buffer (32 MiB).
+pub const DEFAULT_MAX_LINE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Environment variable overriding [`DEFAULT_MAX_LINE_BYTES`], in bytes.
+pub const MAX_LINE_BYTES_ENV: &str = "ROUTA_ACP_MAX_LINE_BYTES";
+
 /// Type alias for the pending request map to avoid complex type repetition.
 type PendingMap = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<serde_json::Value, String>>>>>;
 
@@ -47,6 +59,11 @@ pub struct AcpProcess {
     display_name: String,
     /// The command used to spawn this process (e.g., "npx", "uvx", "opencode")
     command: String,
+    /// The agent's `initialize` response, once the handshake has completed.
+    initialize_result: OnceLock<serde_json::Value>,
+    /// The `modes` object from the `session/new` or `session/load` response,
+    /// present only when the agent supports session modes.
+    session_modes: OnceLock<serde_json::Value>,
     _reader_handle: tokio::task::JoinHandle<()>,
 }
 
@@ -54,11 +71,13 @@ impl AcpProcess {
     /// Spawn the agent process and start the background reader.
     ///
     /// `our_session_id` is used to rewrite the agent's session ID in notifications
-    /// so the frontend SSE stream matches on the correct session.
+    /// so the frontend SSE stream matches on the correct session. `env` is
+    /// layered over the inherited environment (provider credentials and such).
     pub async fn spawn(
         command: &str,
         args: &[&str],
         cwd: &str,
+        env: &HashMap<String, String>,
         notification_tx: NotificationSender,
         display_name: &str,
         our_session_id: &str,
@@ -94,6 +113,7 @@ impl AcpProcess {
             .current_dir(cwd)
             .env("PATH", crate::shell_env::full_path())
             .env("NODE_NO_READLINE", "1")
+            .envs(env)
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped());
@@ -196,9 +216,11 @@ impl AcpProcess {
         let cwd_clone = cwd.to_string();
         let provider_clone = display_name.to_string();
 
+        let max_line_bytes = max_line_bytes_from_env();
+
         let reader_handle = tokio::spawn(async move {
-            let reader = BufReader::new(stdout);
-            let mut lines = reader.lines();
+            let mut reader = BufReader::new(stdout);
+            let mut line_buf = Vec::new();
             // Buffer for accumulating agent message chunks
             let mut agent_msg_buffer = String::new();
             // Buffer for accumulating agent thought chunks
@@ -207,8 +229,36 @@ impl AcpProcess {
             let mut pending_tool_calls: std::collections::HashMap<String, (String, bool)> =
                 std::collections::HashMap::new();
 
-            while let Ok(Some(line)) = lines.next_line().await {
-                let line = line.trim().to_string();
+            while let Ok(Some(line)) =
+                read_bounded_line(&mut reader, &mut line_buf, max_line_bytes).await
+            {
+                let line = match line {
+                    StdoutLine::Line(line) => line.trim().to_string(),
+                    StdoutLine::Oversized { len, response_id } => {
+                        tracing::warn!(
+                            "[AcpProcess:{}] Skipped {}-byte stdout line (limit {} bytes)",
+                            name_clone,
+                            len,
+                            max_line_bytes
+                        );
+                        // Fail the request the oversized response answers
+                        // instead of leaving it to time out.
+                        if let Some(id) = response_id {
+                            if let Some(tx) = pending_clone.lock().await.remove(&id) {
+                                let _ = tx.send(Err(format!(
+                                    "Response to request {id} was {len} bytes, over the {max_line_bytes}-byte limit (raise {MAX_LINE_BYTES_ENV} to allow it)"
+                                )));
+                            }
+                        }
+                        let _ = ntx.send(oversized_line_notification(
+                            &our_sid,
+                            &name_clone,
+                            len,
+                            max_line_bytes,
+                        ));
+                        continue;
+                    }
+                };
                 if line.is_empty() {
                     continue;
                 }
@@ -562,6 +612,8 @@ impl AcpProcess {
             notification_tx,
             display_name: display_name.to_string(),
             command: command.to_string(),
+            initialize_result: OnceLock::new(),
+            session_modes: OnceLock::new(),
             _reader_handle: reader_handle,
         })
     }
@@ -666,9 +718,27 @@ impl AcpProcess {
             self.display_name,
             serde_json::to_string(&result).unwrap_or_default()
         );
+        let _ = self.initialize_result.set(result.clone());
         Ok(result)
     }
 
+    /// The agent's `initialize` response, if the process has been initialized.
+    pub fn initialize_result(&self) -> Option<&serde_json::Value> {
+        self.initialize_result.get()
+    }
+
+    /// The session modes the agent offered when its session was created or
+    /// loaded, if any.
+    pub fn session_modes(&self) -> Option<&serde_json::Value> {
+        self.session_modes.get()
+    }
+
+    fn record_session_modes(&self, result: &serde_json::Value) {
+        if let Some(modes) = result.get("modes").filter(|modes| !modes.is_null()) {
+            let _ = self.session_modes.set(modes.clone());
+        }
+    }
+
     /// Create a new ACP session. Returns the agent's session ID.
     pub async fn new_session(
         &self,
@@ -690,6 +760,7 @@ impl AcpProcess {
             .as_str()
             .ok_or_else(|| "No sessionId in session/new response".to_string())?
             .to_string();
+        self.record_session_modes(&result);
 
         tracing::info!(
             "[AcpProcess:{}] Session created: {}",
@@ -722,6 +793,7 @@ impl AcpProcess {
             .as_str()
             .unwrap_or(session_id)
             .to_string();
+        self.record_session_modes(&result);
 
         tracing::info!(
             "[AcpProcess:{}] Session loaded: {}",
@@ -976,6 +1048,175 @@ fn resolve_permission_option_id(params: &serde_json::Value, scope: &str) -> Opti
     })
 }
 
+/// One line read by [`read_bounded_line`].
+#[derive(Debug, PartialEq, Eq)]
+enum StdoutLine {
+    Line(String),
+    /// A line over the limit; its content was discarded. Holds its length
+    /// and, when the line looks like a JSON-RPC response, its numeric `id`.
+    Oversized {
+        len: usize,
+        response_id: Option<u64>,
+    },
+}
+
+/// Bytes kept from the end of an oversized line so an `id` serialized after
+/// the `result` can still be recovered.
+const OVERSIZED_TAIL_BYTES: usize = 64;
+
+fn max_line_bytes_from_env() -> usize {
+    std::env::var(MAX_LINE_BYTES_ENV)
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .filter(|max: &usize| *max > 0)
+        .unwrap_or(DEFAULT_MAX_LINE_BYTES)
+}
+
+/// Read the next newline-terminated line, keeping at most `max_bytes` of it
+/// in `buf`. Bytes past the limit are consumed and dropped, so memory stays
+/// bounded however long the line is. Returns `None` at EOF.
+async fn read_bounded_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    max_bytes: usize,
+) -> std::io::Result<Option<StdoutLine>> {
+    buf.clear();
+    let mut tail = Vec::new();
+    let mut len = 0usize;
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            if len == 0 {
+                return Ok(None);
+            }
+            break;
+        }
+        let newline = available.iter().position(|byte| *byte == b'\n');
+        let chunk = &available[..newline.unwrap_or(available.len())];
+        len += chunk.len();
+        let room = max_bytes.saturating_sub(buf.len());
+        buf.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        if len > max_bytes {
+            tail.extend_from_slice(chunk);
+            let excess = tail.len().saturating_sub(OVERSIZED_TAIL_BYTES);
+            tail.drain(..excess);
+        }
+        let consumed = newline.map_or(available.len(), |index| index + 1);
+        reader.consume(consumed);
+        if newline.is_some() {
+            break;
+        }
+    }
+
+    if len > max_bytes {
+        let response_id = oversized_response_id(buf, &tail);
+        buf.clear();
+        return Ok(Some(StdoutLine::Oversized { len, response_id }));
+    }
+    Ok(Some(StdoutLine::Line(
+        String::from_utf8_lossy(buf).into_owned(),
+    )))
+}
+
+/// Recover the numeric `id` of a JSON-RPC response from the retained start
+/// (`head`) and end (`tail`) of a line too long to parse. Lines carrying a
+/// top-level `method` are agent requests or notifications and yield `None`.
+fn oversized_response_id(head: &[u8], tail: &[u8]) -> Option<u64> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut key_start = None;
+    let mut head_id = None;
+    for (index, byte) in head.iter().enumerate() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    in_string = false;
+                    if let Some(start) = key_start.take() {
+                        let rest = &head[index + 1..];
+                        let after_key = rest.iter().position(|b| !b.is_ascii_whitespace());
+                        if after_key.is_some_and(|offset| rest[offset] == b':') {
+                            match &head[start..index] {
+                                b"method" => return None,
+                                b"id" => {
+                                    let value = &rest[after_key.unwrap_or(0) + 1..];
+                                    head_id = head_id.or_else(|| leading_u64(value));
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => {
+                in_string = true;
+                if depth == 1 {
+                    key_start = Some(index + 1);
+                }
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    head_id.or_else(|| trailing_id(tail))
+}
+
+/// Parse the unsigned integer at the start of `value`, skipping whitespace.
+fn leading_u64(value: &[u8]) -> Option<u64> {
+    let value = std::str::from_utf8(value).ok()?.trim_start();
+    // A number running into the end of `value` may have been cut short.
+    let digits = value.find(|c: char| !c.is_ascii_digit())?;
+    value[..digits].parse().ok()
+}
+
+/// Match an `"id": <n>` that is the last member of the top-level object,
+/// i.e. the line ends with `"id":<n>}`.
+fn trailing_id(tail: &[u8]) -> Option<u64> {
+    let tail = std::str::from_utf8(tail).ok()?.trim_end();
+    let body = tail.strip_suffix('}')?.trim_end();
+    let digits_start = body
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |index| index + 1);
+    let id = body[digits_start..].parse().ok()?;
+    body[..digits_start]
+        .trim_end()
+        .strip_suffix(':')?
+        .trim_end()
+        .ends_with("\"id\"")
+        .then_some(id)
+}
+
+/// `process_output` notification standing in for a skipped oversized line.
+fn oversized_line_notification(
+    session_id: &str,
+    display_name: &str,
+    len: usize,
+    max_bytes: usize,
+) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "session/update",
+        "params": {
+            "sessionId": session_id,
+            "update": {
+                "sessionUpdate": "process_output",
+                "source": "stdout",
+                "data": format!(
+                    "[routa] Skipped a {len}-byte message from the agent (limit {max_bytes} bytes; raise {MAX_LINE_BYTES_ENV} to allow it)\n"
+                ),
+                "displayName": display_name,
+            }
+        }
+    })
+}
+
 /// Safely truncate a string at a UTF-8 character boundary.
 /// Returns a substring of at most `max_bytes` bytes, but ensures it doesn't
 /// cut in the middle of a multi-byte UTF-8 character.
@@ -1011,8 +1252,90 @@ fn is_codex_otel_stderr(line: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_codex_otel_stderr, resolve_permission_option_id, should_ignore_process_stderr};
+    use super::{
+        is_codex_otel_stderr, oversized_line_notification, oversized_response_id,
+        read_bounded_line, resolve_permission_option_id, should_ignore_process_stderr, StdoutLine,
+    };
     use serde_json::json;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn oversized_stdout_line_is_skipped_without_losing_the_next_one() {
+        let huge = format!("{{\"jsonrpc\":\"2.0\",\"result\":\"{}\"}}", "x".repeat(200));
+        let input = format!("{huge}\n{{\"jsonrpc\":\"2.0\",\"id\":1}}\ntail");
+        // A tiny buffer forces the long line to arrive over many reads.
+        let mut reader = BufReader::with_capacity(16, input.as_bytes());
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            read_bounded_line(&mut reader, &mut buf, 64).await.unwrap(),
+            Some(StdoutLine::Oversized {
+                len: huge.len(),
+                response_id: None
+            })
+        );
+        assert!(buf.len() <= 64);
+        assert_eq!(
+            read_bounded_line(&mut reader, &mut buf, 64).await.unwrap(),
+            Some(StdoutLine::Line(
+                "{\"jsonrpc\":\"2.0\",\"id\":1}".to_string()
+            ))
+        );
+        assert_eq!(
+            read_bounded_line(&mut reader, &mut buf, 64).await.unwrap(),
+            Some(StdoutLine::Line("tail".to_string()))
+        );
+        assert_eq!(
+            read_bounded_line(&mut reader, &mut buf, 64).await.unwrap(),
+            None
+        );
+
+        let notification = oversized_line_notification("session-1", "opencode", huge.len(), 64);
+        assert_eq!(notification["params"]["sessionId"], "session-1");
+        assert_eq!(
+            notification["params"]["update"]["sessionUpdate"],
+            "process_output"
+        );
+    }
+
+    #[tokio::test]
+    async fn oversized_response_reports_its_id() {
+        let payload = "x".repeat(200);
+        let input = format!(
+            "{{\"jsonrpc\":\"2.0\",\"id\":7,\"result\":\"{payload}\"}}\n{{\"jsonrpc\":\"2.0\",\"result\":{{\"id\":1,\"text\":\"{payload}\"}},\"id\": 9 }}\n"
+        );
+        let mut reader = BufReader::with_capacity(16, input.as_bytes());
+        let mut buf = Vec::new();
+
+        let Some(StdoutLine::Oversized { response_id, .. }) =
+            read_bounded_line(&mut reader, &mut buf, 64).await.unwrap()
+        else {
+            panic!("expected an oversized line");
+        };
+        assert_eq!(response_id, Some(7));
+        let Some(StdoutLine::Oversized { response_id, .. }) =
+            read_bounded_line(&mut reader, &mut buf, 64).await.unwrap()
+        else {
+            panic!("expected an oversized line");
+        };
+        assert_eq!(response_id, Some(9));
+    }
+
+    #[test]
+    fn oversized_agent_requests_have_no_response_id() {
+        assert_eq!(
+            oversized_response_id(
+                br#"{"jsonrpc":"2.0","id":3,"method":"fs/write_text_file","params":{"#,
+                b"}}"
+            ),
+            None
+        );
+        assert_eq!(
+            oversized_response_id(br#"{"jsonrpc":"2.0","result":{"id":4,"#, br#"xx"}}"#),
+            None
+        );
+        assert_eq!(oversized_response_id(br#"{"id":12"#, b"xx"), None);
+    }
 
     #[test]
     fn ignores_codex_otel_stderr_noise() {
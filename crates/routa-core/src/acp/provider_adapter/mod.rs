@@ -3,9 +3,11 @@
 //! Normalizes messages from different ACP providers (Claude Code, OpenCode, Kimi, etc.)
 //! to a unified internal format for consistent trace recording.
 
+mod prompt_result;
 mod trace_recorder;
 mod types;
 
+pub use prompt_result::{normalize_prompt_result, PromptResult, StopReason};
 pub use trace_recorder::TraceRecorder;
 pub use types::*;
 
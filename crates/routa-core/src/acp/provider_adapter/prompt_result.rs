@@ -0,0 +1,253 @@
+//! Prompt Result Normalization
+//!
+//! Folds a provider's raw prompt response and the session updates streamed
+//! during the turn into a single [`PromptResult`], so callers never need to
+//! branch on the provider.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::get_provider_behavior;
+use super::types::{has_input, NormalizedToolCall, ProviderType, ToolStatus};
+
+/// Why the agent ended its turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    EndTurn,
+    MaxTokens,
+    MaxTurnRequests,
+    Refusal,
+    Cancelled,
+    Error,
+}
+
+impl StopReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::EndTurn => "end_turn",
+            Self::MaxTokens => "max_tokens",
+            Self::MaxTurnRequests => "max_turn_requests",
+            Self::Refusal => "refusal",
+            Self::Cancelled => "cancelled",
+            Self::Error => "error",
+        }
+    }
+
+    /// Map an ACP `stopReason` or a Claude Code `result` subtype.
+    pub fn from_provider(raw: &str) -> Self {
+        match raw {
+            "end_turn" | "end_of_turn" | "success" => Self::EndTurn,
+            "max_tokens" => Self::MaxTokens,
+            "max_turn_requests" | "error_max_turns" => Self::MaxTurnRequests,
+            "refusal" => Self::Refusal,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Error,
+        }
+    }
+}
+
+/// Provider-independent outcome of one prompt turn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptResult {
+    pub stop_reason: StopReason,
+    /// Agent message text streamed during the turn, concatenated.
+    pub text: String,
+    /// Tool calls in the order they started, with their final status.
+    pub tool_calls: Vec<NormalizedToolCall>,
+}
+
+/// Build a [`PromptResult`] from a raw prompt response and the `session/update`
+/// notifications (or bare update objects) received while it ran.
+pub fn normalize_prompt_result(provider: &str, raw: &Value, updates: &[Value]) -> PromptResult {
+    let behavior = get_provider_behavior(provider);
+    let stop_reason = ["stopReason", "stop_reason", "subtype"]
+        .iter()
+        .find_map(|key| raw.get(*key).and_then(Value::as_str))
+        .map(StopReason::from_provider)
+        .unwrap_or(StopReason::EndTurn);
+
+    let mut text = String::new();
+    let mut tool_calls: Vec<NormalizedToolCall> = Vec::new();
+    for update in updates {
+        let update = update
+            .get("params")
+            .and_then(|params| params.get("update"))
+            .unwrap_or(update);
+        match update.get("sessionUpdate").and_then(Value::as_str) {
+            Some("agent_message_chunk" | "agent_message") => {
+                if let Some(chunk) = update.pointer("/content/text").and_then(Value::as_str) {
+                    text.push_str(chunk);
+                }
+            }
+            Some("tool_call") => {
+                let tool_call = tool_call_from_update(update, behavior.immediate_tool_input);
+                match tool_calls
+                    .iter_mut()
+                    .find(|existing| existing.tool_call_id == tool_call.tool_call_id)
+                {
+                    Some(existing) => *existing = tool_call,
+                    None => tool_calls.push(tool_call),
+                }
+            }
+            Some("tool_call_update") => {
+                let id = update
+                    .get("toolCallId")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                match tool_calls.iter_mut().find(|call| call.tool_call_id == id) {
+                    Some(existing) => apply_tool_call_update(existing, update),
+                    None => tool_calls.push(tool_call_from_update(update, false)),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Claude Code's stream-json `result` carries the final text when nothing
+    // was streamed as message chunks.
+    if text.is_empty() && behavior.provider_type == ProviderType::Claude {
+        if let Some(result) = raw.get("result").and_then(Value::as_str) {
+            text = result.to_string();
+        }
+    }
+
+    PromptResult {
+        stop_reason,
+        text,
+        tool_calls,
+    }
+}
+
+fn tool_call_from_update(update: &Value, immediate_tool_input: bool) -> NormalizedToolCall {
+    let str_field = |key: &str| update.get(key).and_then(Value::as_str).map(str::to_string);
+    let input = update.get("rawInput").cloned();
+    NormalizedToolCall {
+        tool_call_id: str_field("toolCallId").unwrap_or_default(),
+        name: str_field("kind")
+            .or_else(|| str_field("title"))
+            .unwrap_or_else(|| "unknown".to_string()),
+        title: str_field("title"),
+        status: update
+            .get("status")
+            .and_then(Value::as_str)
+            .map(ToolStatus::from_str)
+            .unwrap_or(ToolStatus::Pending),
+        input_finalized: immediate_tool_input || has_input(&input),
+        input,
+        output: update.get("rawOutput").cloned(),
+    }
+}
+
+fn apply_tool_call_update(tool_call: &mut NormalizedToolCall, update: &Value) {
+    if let Some(status) = update.get("status").and_then(Value::as_str) {
+        tool_call.status = ToolStatus::from_str(status);
+    }
+    if let Some(title) = update.get("title").and_then(Value::as_str) {
+        tool_call.title = Some(title.to_string());
+    }
+    let input = update.get("rawInput").cloned();
+    if has_input(&input) {
+        tool_call.input = input;
+        tool_call.input_finalized = true;
+    }
+    if let Some(output) = update.get("rawOutput") {
+        tool_call.output = Some(output.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn session_update(update: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "session/update",
+            "params": { "sessionId": "s1", "update": update }
+        })
+    }
+
+    fn expected() -> PromptResult {
+        PromptResult {
+            stop_reason: StopReason::EndTurn,
+            text: "Done.".to_string(),
+            tool_calls: vec![NormalizedToolCall {
+                tool_call_id: "tool-1".to_string(),
+                name: "read".to_string(),
+                title: Some("Read README.md".to_string()),
+                status: ToolStatus::Completed,
+                input: Some(json!({ "file_path": "README.md" })),
+                output: Some(json!("hello")),
+                input_finalized: true,
+            }],
+        }
+    }
+
+    #[test]
+    fn normalizes_claude_stream_json_result() {
+        let raw = json!({ "type": "result", "subtype": "success", "result": "Done." });
+        let updates = vec![
+            session_update(json!({
+                "sessionUpdate": "tool_call",
+                "toolCallId": "tool-1",
+                "title": "Read README.md",
+                "status": "running",
+                "kind": "read",
+                "rawInput": { "file_path": "README.md" }
+            })),
+            session_update(json!({
+                "sessionUpdate": "tool_call_update",
+                "toolCallId": "tool-1",
+                "status": "completed",
+                "kind": "read",
+                "rawOutput": "hello"
+            })),
+        ];
+
+        assert_eq!(
+            normalize_prompt_result("claude", &raw, &updates),
+            expected()
+        );
+    }
+
+    #[test]
+    fn normalizes_acp_result() {
+        let raw = json!({ "stopReason": "end_turn" });
+        let updates = vec![
+            json!({
+                "sessionUpdate": "tool_call",
+                "toolCallId": "tool-1",
+                "title": "Read README.md",
+                "status": "pending",
+                "kind": "read"
+            }),
+            json!({
+                "sessionUpdate": "tool_call_update",
+                "toolCallId": "tool-1",
+                "status": "in_progress",
+                "rawInput": { "file_path": "README.md" }
+            }),
+            json!({
+                "sessionUpdate": "tool_call_update",
+                "toolCallId": "tool-1",
+                "status": "completed",
+                "rawOutput": "hello"
+            }),
+            json!({
+                "sessionUpdate": "agent_message_chunk",
+                "content": { "type": "text", "text": "Do" }
+            }),
+            json!({
+                "sessionUpdate": "agent_message_chunk",
+                "content": { "type": "text", "text": "ne." }
+            }),
+        ];
+
+        assert_eq!(
+            normalize_prompt_result("opencode", &raw, &updates),
+            expected()
+        );
+    }
+}
@@ -60,7 +60,7 @@ pub enum NormalizedEventType {
 }
 
 /// Normalized tool call information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct NormalizedToolCall {
     pub tool_call_id: String,
     pub name: String,
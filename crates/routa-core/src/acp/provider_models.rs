@@ -0,0 +1,281 @@
+//! Provider model listing with an in-memory and on-disk cache.
+//!
+//! Listing models runs the provider's CLI (e.g. `opencode models`), which can
+//! take seconds. Results are kept per provider for [`MODELS_CACHE_TTL`] and
+//! persisted to [`AcpPaths::provider_models_cache_path`] so a restart does not
+//! pay for the listing again.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::paths::AcpPaths;
+use crate::shell_env;
+
+/// How long a provider's model list is served from the cache.
+pub const MODELS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// How long the provider's listing command may run.
+const LIST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Describes how to list models for a provider.
+struct ProviderModelConfig {
+    /// The CLI command to run (e.g., "opencode")
+    command: &'static str,
+    /// Arguments to pass (e.g., ["models"])
+    args: &'static [&'static str],
+    /// Whether a trimmed line of output is a model ID.
+    filter_fn: fn(&str) -> bool,
+}
+
+fn default_filter(line: &str) -> bool {
+    !line.is_empty() && line.contains('/')
+}
+
+/// Model listing config for `provider`, if it supports listing.
+fn provider_model_config(provider: &str) -> Option<ProviderModelConfig> {
+    match provider {
+        "opencode" => Some(ProviderModelConfig {
+            command: "opencode",
+            args: &["models"],
+            filter_fn: default_filter,
+        }),
+        // Future providers can be added here, e.g.:
+        // "gemini" => Some(ProviderModelConfig { command: "gemini", args: &["models", "--list"], filter_fn: ... }),
+        _ => None,
+    }
+}
+
+/// Whether Routa knows how to list `provider`'s models.
+pub fn supports_model_listing(provider: &str) -> bool {
+    provider_model_config(provider).is_some()
+}
+
+/// Run `provider`'s model listing command.
+pub async fn list_provider_models(provider: &str) -> Result<Vec<String>, String> {
+    let config = provider_model_config(provider)
+        .ok_or_else(|| "Provider does not support model listing".to_string())?;
+    let resolved = shell_env::which(config.command)
+        .ok_or_else(|| format!("'{}' not found in PATH", config.command))?;
+
+    let output = tokio::time::timeout(
+        LIST_TIMEOUT,
+        tokio::process::Command::new(&resolved)
+            .args(config.args)
+            .env("PATH", shell_env::full_path())
+            .output(),
+    )
+    .await
+    .map_err(|_| {
+        tracing::warn!(
+            "[provider_models] Timeout listing models for '{}'",
+            provider
+        );
+        "Timeout".to_string()
+    })?
+    .map_err(|e| {
+        tracing::warn!(
+            "[provider_models] Failed to run '{}': {}",
+            config.command,
+            e
+        );
+        e.to_string()
+    })?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| (config.filter_fn)(line))
+        .map(str::to_string)
+        .collect())
+}
+
+/// A provider's model list and when it was fetched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderModels {
+    pub models: Vec<String>,
+    pub cached_at: DateTime<Utc>,
+}
+
+impl ProviderModels {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        let age = Utc::now().signed_duration_since(self.cached_at);
+        age.to_std().map_or(true, |age| age < ttl)
+    }
+}
+
+/// Per-provider model lists, persisted as JSON. The file is read on first
+/// use; `None` means it has not been read yet.
+#[derive(Clone)]
+pub struct ProviderModelsCache {
+    path: PathBuf,
+    ttl: Duration,
+    entries: Arc<Mutex<Option<HashMap<String, ProviderModels>>>>,
+}
+
+impl ProviderModelsCache {
+    pub fn new(paths: &AcpPaths) -> Self {
+        Self::with_path(paths.provider_models_cache_path(), MODELS_CACHE_TTL)
+    }
+
+    pub fn with_path(path: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            path: path.into(),
+            ttl,
+            entries: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// `provider`'s models, listed by running its CLI unless a fresh entry
+    /// is cached. `refresh` skips the cache. The flag is `true` when the
+    /// result came from the cache.
+    pub async fn models(
+        &self,
+        provider: &str,
+        refresh: bool,
+    ) -> Result<(ProviderModels, bool), String> {
+        self.models_with(provider, refresh, || list_provider_models(provider))
+            .await
+    }
+
+    /// [`models`](Self::models) with the listing supplied by `fetch`.
+    pub async fn models_with<F, Fut>(
+        &self,
+        provider: &str,
+        refresh: bool,
+        fetch: F,
+    ) -> Result<(ProviderModels, bool), String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<String>, String>>,
+    {
+        if !refresh {
+            let mut entries = self.entries.lock().await;
+            let cached = self.loaded(&mut entries).await.get(provider);
+            if let Some(cached) = cached.filter(|cached| cached.is_fresh(self.ttl)) {
+                return Ok((cached.clone(), true));
+            }
+        }
+
+        let fetched = ProviderModels {
+            models: fetch().await?,
+            cached_at: Utc::now(),
+        };
+        let mut entries = self.entries.lock().await;
+        let loaded = self.loaded(&mut entries).await;
+        loaded.insert(provider.to_string(), fetched.clone());
+        if let Err(e) = self.save(loaded).await {
+            tracing::warn!("[provider_models] Cache not persisted: {}", e);
+        }
+        Ok((fetched, false))
+    }
+
+    async fn loaded<'a>(
+        &self,
+        entries: &'a mut Option<HashMap<String, ProviderModels>>,
+    ) -> &'a mut HashMap<String, ProviderModels> {
+        if entries.is_none() {
+            *entries = Some(self.load().await);
+        }
+        entries.get_or_insert_with(HashMap::new)
+    }
+
+    async fn load(&self) -> HashMap<String, ProviderModels> {
+        let Ok(content) = tokio::fs::read_to_string(&self.path).await else {
+            return HashMap::new();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!(
+                "[provider_models] Ignoring unreadable cache {}: {}",
+                self.path.display(),
+                e
+            );
+            HashMap::new()
+        })
+    }
+
+    async fn save(&self, entries: &HashMap<String, ProviderModels>) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(entries)
+            .map_err(|e| format!("Failed to serialize model cache: {e}"))?;
+        tokio::fs::write(&self.path, content)
+            .await
+            .map_err(|e| format!("Failed to write {}: {e}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn cached_within_ttl_and_refresh_bypasses_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("provider-models.json");
+        let cache = ProviderModelsCache::with_path(&path, MODELS_CACHE_TTL);
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async {
+            let n = fetches.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(vec![format!("acme/model-{n}")])
+        };
+
+        let (first, cached) = cache.models_with("opencode", false, fetch).await.unwrap();
+        assert!(!cached);
+        assert_eq!(first.models, vec!["acme/model-1"]);
+
+        let (second, cached) = cache.models_with("opencode", false, fetch).await.unwrap();
+        assert!(cached);
+        assert_eq!(second, first);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        let (refreshed, cached) = cache.models_with("opencode", true, fetch).await.unwrap();
+        assert!(!cached);
+        assert_eq!(refreshed.models, vec!["acme/model-2"]);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        // A fresh cache over the same file serves the persisted entry.
+        let reopened = ProviderModelsCache::with_path(&path, MODELS_CACHE_TTL);
+        let (from_disk, cached) = reopened
+            .models_with("opencode", false, fetch)
+            .await
+            .unwrap();
+        assert!(cached);
+        assert_eq!(from_disk, refreshed);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_fetched_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ProviderModelsCache::with_path(dir.path().join("m.json"), Duration::ZERO);
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            Ok(vec!["acme/model".to_string()])
+        };
+
+        cache.models_with("opencode", false, fetch).await.unwrap();
+        let (_, cached) = cache.models_with("opencode", false, fetch).await.unwrap();
+        assert!(!cached);
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn unsupported_provider_is_an_error() {
+        let err = list_provider_models("no-such-provider").await.unwrap_err();
+        assert_eq!(err, "Provider does not support model listing");
+    }
+}
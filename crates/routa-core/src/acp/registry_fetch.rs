@@ -39,7 +39,8 @@ async fn save_cached_registry_json(value: &serde_json::Value) -> Result<(), Stri
         .map_err(|e| format!("Failed to write ACP registry cache: {e}"))
 }
 
-async fn fetch_live_registry_json() -> Result<serde_json::Value, String> {
+/// Fetch the registry from the CDN without falling back to the local cache.
+pub async fn fetch_live_registry_json() -> Result<serde_json::Value, String> {
     let resp = reqwest::get(REGISTRY_URL)
         .await
         .map_err(|e| format!("Failed to fetch ACP registry: {e}"))?;
@@ -2,8 +2,13 @@
 //!
 //! These types match the ACP registry JSON schema from:
 //! https://cdn.agentclientprotocol.com/registry/v1/latest/registry.json
+//!
+//! Parsing is tolerant of schema drift: unknown fields are ignored, optional
+//! fields default, and an agent that fails to parse is skipped with a warning
+//! instead of failing the whole registry.
 
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
 /// The root registry containing all available agents.
@@ -12,9 +17,53 @@ use std::collections::HashMap;
 pub struct AcpRegistry {
     #[serde(default)]
     pub version: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_agents_lenient")]
     pub agents: Vec<AcpAgentEntry>,
 }
 
+/// Deserialize a registry `agents` array one entry at a time, dropping the
+/// entries that fail to parse so the remaining agents stay installable.
+pub fn deserialize_agents_lenient<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let entries = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(entries
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, entry)| match parse_registry_agent(index, entry) {
+            Ok(agent) => Some(agent),
+            Err(error) => {
+                tracing::warn!("[ACP Registry] Skipping agent: {}", error);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Parse one registry agent, naming it (by `id`, or by position when the id
+/// itself is missing) in the error.
+pub fn parse_registry_agent<T: DeserializeOwned>(
+    index: usize,
+    entry: serde_json::Value,
+) -> Result<T, String> {
+    let label = match entry.get("id").and_then(|id| id.as_str()) {
+        Some(id) if !id.trim().is_empty() => format!("'{id}'"),
+        _ => format!("at index {index}"),
+    };
+    for field in ["id", "name"] {
+        let present = entry
+            .get(field)
+            .and_then(|value| value.as_str())
+            .is_some_and(|value| !value.trim().is_empty());
+        if !present {
+            return Err(format!("agent {label} is missing required field `{field}`"));
+        }
+    }
+    serde_json::from_value(entry).map_err(|e| format!("agent {label} is invalid: {e}"))
+}
+
 /// An agent entry in the registry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -187,3 +236,54 @@ impl AcpAgentEntry {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_skips_malformed_agents_and_ignores_unknown_fields() {
+        let registry: AcpRegistry = serde_json::from_value(serde_json::json!({
+            "version": "1.0.0",
+            "schemaRevision": 2,
+            "agents": [
+                {
+                    "id": "opencode",
+                    "name": "OpenCode",
+                    "capabilities": ["terminal"],
+                    "distribution": { "npx": { "package": "opencode-ai" }, "oci": {} }
+                },
+                { "id": "broken", "name": "Broken", "distribution": { "npx": { "args": [] } } },
+                { "name": "Anonymous", "distribution": {} },
+                {
+                    "id": "gemini",
+                    "name": "Gemini",
+                    "distribution": { "uvx": { "package": "gemini-acp" } }
+                }
+            ]
+        }))
+        .expect("registry should parse");
+
+        let ids: Vec<&str> = registry.agents.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["opencode", "gemini"]);
+        assert_eq!(registry.agents[0].dist_type(), Some(DistributionType::Npx));
+    }
+
+    #[test]
+    fn agent_errors_name_the_offending_agent() {
+        let error = parse_registry_agent::<AcpAgentEntry>(
+            0,
+            serde_json::json!({ "id": "broken", "name": "Broken" }),
+        )
+        .unwrap_err();
+        assert!(error.contains("'broken'"), "{error}");
+        assert!(error.contains("distribution"), "{error}");
+
+        let error = parse_registry_agent::<AcpAgentEntry>(
+            3,
+            serde_json::json!({ "name": "Anonymous", "distribution": {} }),
+        )
+        .unwrap_err();
+        assert_eq!(error, "agent at index 3 is missing required field `id`");
+    }
+}
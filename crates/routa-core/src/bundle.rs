@@ -0,0 +1,452 @@
+//! Portable snapshots of application state for support bundles.
+//!
+//! [`AppStateInner::export_bundle`] writes every workspace with its agents,
+//! tasks, notes and ACP sessions to one JSON file. Metadata entries (at any
+//! depth, for sessions) and custom command arguments that look like
+//! credentials are redacted on the way out. [`AppStateInner::import_bundle`] loads such a file into another
+//! database, giving every workspace, agent, task and session a fresh ID and
+//! rewriting the references between them, so a bundle never collides with
+//! existing rows — not even with a second import of itself.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ServerError;
+use crate::models::agent::Agent;
+use crate::models::note::Note;
+use crate::models::task::Task;
+use crate::models::workspace::Workspace;
+use crate::state::AppStateInner;
+use crate::store::acp_session_store::AcpSessionRow;
+use crate::store::{AcpSessionStore, AgentStore, NoteStore, TaskStore, WorkspaceStore};
+
+/// Bumped whenever the bundle layout changes incompatibly.
+pub const BUNDLE_VERSION: u32 = 1;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// On-disk layout of a support bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub workspaces: Vec<Workspace>,
+    pub agents: Vec<Agent>,
+    pub tasks: Vec<Task>,
+    pub notes: Vec<Note>,
+    pub sessions: Vec<AcpSessionRow>,
+}
+
+/// Number of records written or loaded, per kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSummary {
+    pub workspaces: usize,
+    pub agents: usize,
+    pub tasks: usize,
+    pub notes: usize,
+    pub sessions: usize,
+}
+
+impl StateBundle {
+    fn summary(&self) -> BundleSummary {
+        BundleSummary {
+            workspaces: self.workspaces.len(),
+            agents: self.agents.len(),
+            tasks: self.tasks.len(),
+            notes: self.notes.len(),
+            sessions: self.sessions.len(),
+        }
+    }
+}
+
+/// Result of [`AppStateInner::import_bundle`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleImport {
+    pub summary: BundleSummary,
+    /// Bundle ID → newly assigned ID, for workspaces, agents, tasks and sessions.
+    pub id_map: HashMap<String, String>,
+}
+
+impl AppStateInner {
+    /// Write all workspaces, agents, tasks, notes and sessions to `path` as JSON.
+    pub async fn export_bundle(&self, path: &Path) -> Result<BundleSummary, ServerError> {
+        let mut bundle = StateBundle {
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            workspaces: self.workspace_store.list().await?,
+            agents: Vec::new(),
+            tasks: Vec::new(),
+            notes: Vec::new(),
+            sessions: Vec::new(),
+        };
+        for workspace in &bundle.workspaces {
+            bundle
+                .agents
+                .extend(self.agent_store.list_by_workspace(&workspace.id).await?);
+            bundle
+                .tasks
+                .extend(self.task_store.list_by_workspace(&workspace.id).await?);
+            bundle
+                .notes
+                .extend(self.note_store.list_by_workspace(&workspace.id).await?);
+            bundle.sessions.extend(
+                self.acp_session_store
+                    .list(Some(&workspace.id), Some(i64::MAX as usize))
+                    .await?,
+            );
+        }
+        redact_secrets(&mut bundle);
+
+        let json = serde_json::to_vec_pretty(&bundle)
+            .map_err(|e| ServerError::Internal(format!("Failed to serialize bundle: {e}")))?;
+        std::fs::write(path, json).map_err(|e| {
+            ServerError::Internal(format!("Failed to write {}: {e}", path.display()))
+        })?;
+        Ok(bundle.summary())
+    }
+
+    /// Load a bundle written by [`export_bundle`](Self::export_bundle) under
+    /// fresh IDs, in a single transaction.
+    pub async fn import_bundle(&self, path: &Path) -> Result<BundleImport, ServerError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ServerError::BadRequest(format!("Failed to read {}: {e}", path.display()))
+        })?;
+        let bundle: StateBundle = serde_json::from_str(&content)
+            .map_err(|e| ServerError::BadRequest(format!("Invalid bundle: {e}")))?;
+        if bundle.version != BUNDLE_VERSION {
+            return Err(ServerError::BadRequest(format!(
+                "Unsupported bundle version {} (expected {BUNDLE_VERSION})",
+                bundle.version
+            )));
+        }
+
+        let (bundle, id_map) = remap_ids(bundle);
+        let summary = bundle.summary();
+        let max_note_versions = self.note_store.max_versions();
+        self.db
+            .transaction(move |conn| {
+                for workspace in &bundle.workspaces {
+                    WorkspaceStore::save_in(conn, workspace)?;
+                }
+                for agent in &bundle.agents {
+                    AgentStore::save_in(conn, agent)?;
+                }
+                for task in &bundle.tasks {
+                    TaskStore::save_in(conn, task)?;
+                }
+                for note in &bundle.notes {
+                    NoteStore::save_in(conn, note, max_note_versions)?;
+                }
+                for session in &bundle.sessions {
+                    AcpSessionStore::save_in(conn, session)?;
+                }
+                Ok(())
+            })
+            .await?;
+        Ok(BundleImport { summary, id_map })
+    }
+}
+
+/// Whether a metadata key or command-line flag names a credential.
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    [
+        "token",
+        "secret",
+        "password",
+        "passwd",
+        "api_key",
+        "apikey",
+        "api-key",
+        "credential",
+        "authorization",
+    ]
+    .iter()
+    .any(|needle| key.contains(needle))
+}
+
+fn redact_map(map: &mut HashMap<String, String>) {
+    for (key, value) in map.iter_mut() {
+        if is_secret_key(key) {
+            *value = REDACTED.to_string();
+        }
+    }
+}
+
+/// Redact secret keys at any depth of a JSON metadata value.
+fn redact_value(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *value = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+/// Redact `KEY=value` pairs with a secret key, and the value after a secret flag.
+fn redact_args(args: &mut [String]) {
+    let mut redact_next = false;
+    for arg in args.iter_mut() {
+        if std::mem::take(&mut redact_next) {
+            *arg = REDACTED.to_string();
+        } else if let Some((key, _)) = arg.split_once('=') {
+            if is_secret_key(key) {
+                *arg = format!("{key}={REDACTED}");
+            }
+        } else if arg.starts_with('-') && is_secret_key(arg) {
+            redact_next = true;
+        }
+    }
+}
+
+fn redact_secrets(bundle: &mut StateBundle) {
+    for workspace in &mut bundle.workspaces {
+        redact_map(&mut workspace.metadata);
+    }
+    for agent in &mut bundle.agents {
+        redact_map(&mut agent.metadata);
+    }
+    for note in &mut bundle.notes {
+        if let Some(custom) = note.metadata.custom.as_mut() {
+            redact_map(custom);
+        }
+    }
+    for session in &mut bundle.sessions {
+        redact_args(&mut session.custom_args);
+        redact_value(&mut session.metadata);
+    }
+}
+
+/// Assign fresh IDs and rewrite references to them. References to records
+/// outside the bundle are left as they are. Note IDs are scoped to their
+/// workspace, so they are kept.
+fn remap_ids(mut bundle: StateBundle) -> (StateBundle, HashMap<String, String>) {
+    let mut ids = HashMap::new();
+    let kinds = [
+        bundle
+            .workspaces
+            .iter()
+            .map(|w| w.id.clone())
+            .collect::<Vec<_>>(),
+        bundle.agents.iter().map(|a| a.id.clone()).collect(),
+        bundle.tasks.iter().map(|t| t.id.clone()).collect(),
+        bundle.sessions.iter().map(|s| s.id.clone()).collect(),
+    ];
+    for id in kinds.into_iter().flatten() {
+        ids.insert(id, uuid::Uuid::new_v4().to_string());
+    }
+    let map = |id: &mut String| {
+        if let Some(new_id) = ids.get(id.as_str()) {
+            *id = new_id.clone();
+        }
+    };
+    let map_opt = |id: &mut Option<String>| {
+        if let Some(id) = id.as_mut() {
+            map(id);
+        }
+    };
+
+    for workspace in &mut bundle.workspaces {
+        map(&mut workspace.id);
+    }
+    for agent in &mut bundle.agents {
+        map(&mut agent.id);
+        map(&mut agent.workspace_id);
+        map_opt(&mut agent.parent_id);
+    }
+    for task in &mut bundle.tasks {
+        map(&mut task.id);
+        map(&mut task.workspace_id);
+        map_opt(&mut task.assigned_to);
+        map_opt(&mut task.session_id);
+        map_opt(&mut task.trigger_session_id);
+        task.dependencies.iter_mut().for_each(map);
+        task.session_ids.iter_mut().for_each(map);
+        for lane in &mut task.lane_sessions {
+            map(&mut lane.session_id);
+            map_opt(&mut lane.routa_agent_id);
+        }
+        for handoff in &mut task.lane_handoffs {
+            map(&mut handoff.from_session_id);
+            map(&mut handoff.to_session_id);
+        }
+        // Boards, codebases and worktrees are not part of the bundle.
+        task.board_id = None;
+        task.codebase_ids.clear();
+        task.worktree_id = None;
+    }
+    for note in &mut bundle.notes {
+        map(&mut note.workspace_id);
+        map_opt(&mut note.session_id);
+        map_opt(&mut note.metadata.linked_task_id);
+        if let Some(agent_ids) = note.metadata.assigned_agent_ids.as_mut() {
+            agent_ids.iter_mut().for_each(map);
+        }
+    }
+    for session in &mut bundle.sessions {
+        let old_id = session.id.clone();
+        map(&mut session.id);
+        map(&mut session.workspace_id);
+        map_opt(&mut session.routa_agent_id);
+        map_opt(&mut session.parent_session_id);
+        for message in &mut session.message_history {
+            if let Some(session_id) = message.get_mut("sessionId") {
+                if session_id.as_str() == Some(old_id.as_str()) {
+                    *session_id = serde_json::Value::String(session.id.clone());
+                }
+            }
+        }
+    }
+    (bundle, ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::agent::AgentRole;
+    use crate::store::acp_session_store::CreateAcpSessionParams;
+    use crate::Database;
+
+    #[tokio::test]
+    async fn bundle_round_trips_into_a_fresh_database_under_new_ids() {
+        let source = AppStateInner::new(Database::open_in_memory().unwrap());
+        let mut workspace = Workspace::new("ws-1".to_string(), "Support case".to_string(), None);
+        workspace
+            .metadata
+            .insert("githubToken".to_string(), "ghp_secret".to_string());
+        source.workspace_store.save(&workspace).await.unwrap();
+        let agent = Agent::new(
+            "agent-1".to_string(),
+            "crafter".to_string(),
+            AgentRole::Crafter,
+            "ws-1".to_string(),
+            None,
+            None,
+            None,
+        );
+        source.agent_store.save(&agent).await.unwrap();
+        let mut task = Task::new(
+            "task-1".to_string(),
+            "Fix login".to_string(),
+            "Make login work".to_string(),
+            "ws-1".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        task.assigned_to = Some("agent-1".to_string());
+        task.session_id = Some("session-1".to_string());
+        source.task_store.save(&task).await.unwrap();
+        let mut note = Note::new(
+            "n-1".to_string(),
+            "Findings".to_string(),
+            "Cookie expires early".to_string(),
+            "ws-1".to_string(),
+            None,
+        );
+        note.metadata.linked_task_id = Some("task-1".to_string());
+        source.note_store.save(&note).await.unwrap();
+        let args = vec!["--api-key".to_string(), "sk-live".to_string()];
+        let session_metadata = serde_json::json!({
+            "label": "repro",
+            "githubToken": "ghp_session",
+            "provider": { "env": [{ "OPENAI_API_KEY": "sk-nested" }] },
+        });
+        source
+            .acp_session_store
+            .create(CreateAcpSessionParams {
+                id: "session-1",
+                cwd: "/repo",
+                branch: None,
+                workspace_id: "ws-1",
+                provider: Some("opencode"),
+                role: Some("CRAFTER"),
+                custom_command: None,
+                custom_args: Some(&args),
+                parent_session_id: None,
+                metadata: Some(&session_metadata),
+            })
+            .await
+            .unwrap();
+        source
+            .acp_session_store
+            .set_routa_agent_id("session-1", Some("agent-1"))
+            .await
+            .unwrap();
+        source
+            .acp_session_store
+            .save_history(
+                "session-1",
+                &[serde_json::json!({ "sessionId": "session-1", "update": { "text": "hi" } })],
+            )
+            .await
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.json");
+        let exported = source.export_bundle(&path).await.unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        for secret in ["ghp_secret", "sk-live", "ghp_session", "sk-nested"] {
+            assert!(!written.contains(secret), "{secret} leaked into the bundle");
+        }
+
+        let target = AppStateInner::new(Database::open_in_memory().unwrap());
+        let imported = target.import_bundle(&path).await.unwrap();
+        assert_eq!(imported.summary, exported);
+        assert_eq!(exported.tasks, 1);
+        assert_eq!(exported.sessions, 1);
+
+        let ws_id = &imported.id_map["ws-1"];
+        let agent_id = &imported.id_map["agent-1"];
+        let task_id = &imported.id_map["task-1"];
+        let session_id = &imported.id_map["session-1"];
+        assert_ne!(ws_id, "ws-1");
+
+        let workspace = target.workspace_store.get(ws_id).await.unwrap().unwrap();
+        assert_eq!(workspace.title, "Support case");
+        assert_eq!(workspace.metadata["githubToken"], REDACTED);
+        let task = target.task_store.get(task_id).await.unwrap().unwrap();
+        assert_eq!(task.workspace_id, *ws_id);
+        assert_eq!(task.assigned_to.as_ref(), Some(agent_id));
+        assert_eq!(task.session_id.as_ref(), Some(session_id));
+        let note = target.note_store.get("n-1", ws_id).await.unwrap().unwrap();
+        assert_eq!(note.metadata.linked_task_id.as_ref(), Some(task_id));
+        let session = target
+            .acp_session_store
+            .get(session_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.routa_agent_id.as_ref(), Some(agent_id));
+        assert_eq!(session.custom_args, vec!["--api-key", REDACTED]);
+        assert_eq!(session.metadata["label"], "repro");
+        assert_eq!(session.metadata["githubToken"], REDACTED);
+        assert_eq!(
+            session.metadata["provider"]["env"][0]["OPENAI_API_KEY"],
+            REDACTED
+        );
+        assert_eq!(session.message_history.len(), 1);
+        assert_eq!(session.message_history[0]["sessionId"], session_id.as_str());
+
+        // A second import lands alongside the first instead of colliding.
+        let again = target.import_bundle(&path).await.unwrap();
+        assert_ne!(again.id_map["ws-1"], *ws_id);
+    }
+}
@@ -0,0 +1,56 @@
+//! Storage engines a store can run on.
+//!
+//! SQLite ([`Database`]) is the default and backs every store. With the
+//! `postgres` feature, [`WorkspaceStore`](crate::store::WorkspaceStore) and
+//! [`TaskStore`](crate::store::TaskStore) can also run on a
+//! [`PgDatabase`](super::postgres::PgDatabase).
+
+use super::Database;
+use crate::error::ServerError;
+
+#[cfg(feature = "postgres")]
+use super::postgres::PgDatabase;
+
+/// The engine behind a store.
+#[derive(Clone)]
+pub enum DbBackend {
+    Sqlite(Database),
+    #[cfg(feature = "postgres")]
+    Postgres(PgDatabase),
+}
+
+impl DbBackend {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sqlite(_) => "sqlite",
+            #[cfg(feature = "postgres")]
+            Self::Postgres(_) => "postgres",
+        }
+    }
+
+    /// The SQLite database, for work that spans several stores in one
+    /// [`Database::transaction`]. Errors on any other engine.
+    pub fn sqlite(&self) -> Result<&Database, ServerError> {
+        match self {
+            Self::Sqlite(db) => Ok(db),
+            #[cfg(feature = "postgres")]
+            other => Err(ServerError::Database(format!(
+                "Cross-store transactions need the sqlite backend, not {}",
+                other.name()
+            ))),
+        }
+    }
+}
+
+impl From<Database> for DbBackend {
+    fn from(db: Database) -> Self {
+        Self::Sqlite(db)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<PgDatabase> for DbBackend {
+    fn from(db: PgDatabase) -> Self {
+        Self::Postgres(db)
+    }
+}
@@ -0,0 +1,328 @@
+//! Online backup and restore of the SQLite database.
+//!
+//! Both directions use SQLite's backup API. A backup copies every page in
+//! one step: a stepped copy restarts whenever another pool connection
+//! writes, so it may never finish while the server is busy. In WAL mode the
+//! single step reads one snapshot without blocking writers; the cost is
+//! that the WAL cannot be checkpointed past that snapshot until the copy is
+//! done. Restoring copies into the open database in small steps, holding
+//! the write lock throughout, so every pooled connection sees the restored
+//! data without reopening. An encrypted database writes and reads backups
+//! with its own SQLCipher key.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Serialize;
+
+use super::{encryption, Database, LATEST_SCHEMA_VERSION};
+use crate::error::ServerError;
+
+/// Pages copied per restore step.
+const PAGES_PER_STEP: std::os::raw::c_int = 256;
+/// Pause between steps, leaving room for writers.
+const STEP_PAUSE: Duration = Duration::from_millis(5);
+
+/// A database file written by [`Database::backup_to`] or read by
+/// [`Database::restore_from`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub schema_version: i64,
+    pub size_bytes: u64,
+}
+
+/// Directory for backups made through the HTTP API: `~/.routa/backups`.
+pub fn backup_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".routa")
+        .join("backups")
+}
+
+/// The file `name` inside [`backup_dir`]. Only a plain file name is
+/// accepted, so callers cannot reach files anywhere else.
+pub fn backup_path(name: &str) -> Result<PathBuf, ServerError> {
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(std::path::Component::Normal(file)), None) => Ok(backup_dir().join(file)),
+        _ => Err(ServerError::BadRequest(format!(
+            "Backup name must be a plain file name inside {}: {name}",
+            backup_dir().display()
+        ))),
+    }
+}
+
+impl Database {
+    /// Copy the live database to `path` in a single backup step, so writes
+    /// from other connections can't restart it. Refuses to overwrite an
+    /// existing file.
+    pub async fn backup_to(&self, path: impl AsRef<Path>) -> Result<BackupInfo, ServerError> {
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            return Err(ServerError::Conflict(format!(
+                "Backup target already exists: {}",
+                path.display()
+            )));
+        }
+        let db = self.clone();
+        run_blocking(move || {
+            let mut dst = Connection::open(&path).map_err(backup_error)?;
+            if let Some(key) = &db.key {
+                encryption::apply_key(&dst, &path.to_string_lossy(), key)?;
+            }
+            db.with_conn(|src| copy_in_one_step(&Backup::new(src, &mut dst)?))?;
+            drop(dst);
+            let schema_version = db.schema_version()?;
+            tracing::info!("Database backed up to {}", path.display());
+            backup_info(path, schema_version)
+        })
+        .await
+    }
+
+    /// Replace the live database contents with the Routa database at `path`,
+    /// then bring it up to the current schema. The file is checked first: it
+    /// must pass `PRAGMA quick_check` and carry a `schema_migrations` version
+    /// this build knows.
+    pub async fn restore_from(&self, path: impl AsRef<Path>) -> Result<BackupInfo, ServerError> {
+        let path = path.as_ref().to_path_buf();
+        let db = self.clone();
+        run_blocking(move || {
+            let src = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .map_err(|e| {
+                    ServerError::BadRequest(format!("Cannot open {}: {e}", path.display()))
+                })?;
+            if let Some(key) = &db.key {
+                encryption::apply_key(&src, &path.to_string_lossy(), key)
+                    .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+            }
+            let schema_version = validate_backup(&src, &path)?;
+
+            let mut dst = db
+                .pool
+                .get()
+                .map_err(|e| ServerError::Database(format!("Connection pool error: {e}")))?;
+            Backup::new(&src, &mut dst)
+                .and_then(|backup| backup.run_to_completion(PAGES_PER_STEP, STEP_PAUSE, None))
+                .map_err(backup_error)?;
+            drop(dst);
+
+            db.initialize_tables()?;
+            tracing::info!(
+                "Database restored from {} (schema version {})",
+                path.display(),
+                schema_version
+            );
+            backup_info(path, schema_version)
+        })
+        .await
+    }
+}
+
+/// The backup's schema version, if `src` is a healthy Routa database this
+/// build can migrate.
+fn validate_backup(src: &Connection, path: &Path) -> Result<i64, ServerError> {
+    let invalid = |reason: String| {
+        ServerError::BadRequest(format!(
+            "{} is not a Routa database backup: {reason}",
+            path.display()
+        ))
+    };
+    let check: String = src
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| invalid(e.to_string()))?;
+    if check != "ok" {
+        return Err(invalid(format!("integrity check failed ({check})")));
+    }
+    let has_migrations = src
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()
+        .map_err(|e| invalid(e.to_string()))?
+        .is_some();
+    if !has_migrations {
+        return Err(invalid("no schema_migrations table".to_string()));
+    }
+    let version: i64 = src
+        .query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| invalid(e.to_string()))?;
+    if version < 1 {
+        return Err(invalid("no schema migrations recorded".to_string()));
+    }
+    if version > LATEST_SCHEMA_VERSION {
+        return Err(invalid(format!(
+            "schema version {version} is newer than this build supports ({LATEST_SCHEMA_VERSION})"
+        )));
+    }
+    Ok(version)
+}
+
+/// Copy every remaining page with a single `sqlite3_backup_step(-1)`,
+/// retrying only while the source is busy or locked.
+fn copy_in_one_step(backup: &Backup<'_, '_>) -> rusqlite::Result<()> {
+    loop {
+        match backup.step(-1)? {
+            StepResult::Done => return Ok(()),
+            _ => std::thread::sleep(STEP_PAUSE),
+        }
+    }
+}
+
+fn backup_info(path: PathBuf, schema_version: i64) -> Result<BackupInfo, ServerError> {
+    let size_bytes = std::fs::metadata(&path)
+        .map(|meta| meta.len())
+        .map_err(|e| ServerError::Internal(format!("Cannot stat {}: {e}", path.display())))?;
+    Ok(BackupInfo {
+        path,
+        schema_version,
+        size_bytes,
+    })
+}
+
+fn backup_error(e: rusqlite::Error) -> ServerError {
+    ServerError::Database(format!("Database backup failed: {e}"))
+}
+
+async fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, ServerError> + Send + 'static,
+) -> Result<T, ServerError> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ServerError::Database(format!("Task join error: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::workspace::Workspace;
+    use crate::store::WorkspaceStore;
+
+    #[tokio::test]
+    async fn backup_can_be_opened_as_a_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("routa.db").to_string_lossy()).unwrap();
+        WorkspaceStore::new(db.clone())
+            .save(&Workspace::new(
+                "ws-1".to_string(),
+                "Backed up".to_string(),
+                None,
+            ))
+            .await
+            .unwrap();
+
+        let backup_path = dir.path().join("backup.db");
+        let info = db.backup_to(&backup_path).await.unwrap();
+        assert_eq!(info.schema_version, LATEST_SCHEMA_VERSION);
+        assert!(info.size_bytes > 0);
+
+        let restored = Database::open(&backup_path.to_string_lossy()).unwrap();
+        let ws = WorkspaceStore::new(restored)
+            .get("ws-1")
+            .await
+            .unwrap()
+            .expect("workspace should be in the backup");
+        assert_eq!(ws.title, "Backed up");
+    }
+
+    #[tokio::test]
+    async fn restore_replaces_live_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("routa.db").to_string_lossy()).unwrap();
+        let store = WorkspaceStore::new(db.clone());
+        store
+            .save(&Workspace::new(
+                "ws-1".to_string(),
+                "Kept".to_string(),
+                None,
+            ))
+            .await
+            .unwrap();
+        let backup_path = dir.path().join("backup.db");
+        db.backup_to(&backup_path).await.unwrap();
+        store.delete("ws-1").await.unwrap();
+
+        db.restore_from(&backup_path).await.unwrap();
+
+        assert!(store.get("ws-1").await.unwrap().is_some());
+    }
+
+    #[test]
+    fn backup_path_only_accepts_plain_file_names() {
+        assert_eq!(
+            backup_path("nightly.db").unwrap(),
+            backup_dir().join("nightly.db")
+        );
+        for name in ["", "..", "../routa.db", "/etc/passwd", "sub/nightly.db"] {
+            assert!(
+                matches!(backup_path(name), Err(ServerError::BadRequest(_))),
+                "{name}"
+            );
+        }
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[tokio::test]
+    async fn encrypted_backup_round_trips_with_the_database_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_with_key(
+            &dir.path().join("routa.db").to_string_lossy(),
+            Some("s3cret"),
+        )
+        .unwrap();
+        let store = WorkspaceStore::new(db.clone());
+        store
+            .save(&Workspace::new(
+                "ws-1".to_string(),
+                "Secret".to_string(),
+                None,
+            ))
+            .await
+            .unwrap();
+        let backup_path = dir.path().join("backup.db");
+        db.backup_to(&backup_path).await.unwrap();
+
+        let plain = Connection::open(&backup_path).unwrap();
+        assert!(plain
+            .query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row
+                .get::<_, i64>(0))
+            .is_err());
+        drop(plain);
+        let reopened =
+            Database::open_with_key(&backup_path.to_string_lossy(), Some("s3cret")).unwrap();
+        assert!(WorkspaceStore::new(reopened)
+            .get("ws-1")
+            .await
+            .unwrap()
+            .is_some());
+
+        store.delete("ws-1").await.unwrap();
+        db.restore_from(&backup_path).await.unwrap();
+        assert!(store.get("ws-1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn restore_rejects_files_without_routa_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = dir.path().join("other.db");
+        Connection::open(&other)
+            .unwrap()
+            .execute_batch("CREATE TABLE notes (body TEXT);")
+            .unwrap();
+        let db = Database::open_in_memory().unwrap();
+
+        let err = db.restore_from(&other).await.unwrap_err();
+
+        assert!(matches!(err, ServerError::BadRequest(_)), "{err}");
+        assert!(err.to_string().contains("schema_migrations"));
+    }
+}
@@ -0,0 +1,149 @@
+//! Optional SQLCipher encryption of the database file.
+//!
+//! When a key is given (`ROUTA_DB_KEY` or `ServerConfig::db_encryption_key`)
+//! every connection runs `PRAGMA key` before anything else touches the file.
+//! That needs a build with the `sqlcipher` feature; plain SQLite would
+//! silently ignore the pragma, so opening fails instead.
+//!
+//! Changing the key of an existing file is done with [`Database::rekey`]
+//! while nothing has it open. An unencrypted file cannot be keyed in place;
+//! export it with `routa state export` and import into a fresh, keyed one.
+
+use rusqlite::{Connection, OptionalExtension};
+
+use super::Database;
+use crate::error::ServerError;
+
+/// Encryption key for the database file. Unset or empty means unencrypted.
+pub const DB_KEY_ENV: &str = "ROUTA_DB_KEY";
+
+impl Database {
+    /// The key from `ROUTA_DB_KEY`, if set and non-empty.
+    pub fn encryption_key_from_env() -> Option<String> {
+        std::env::var(DB_KEY_ENV)
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+    }
+
+    /// Re-encrypt the SQLCipher database at `db_path` from `old_key` to
+    /// `new_key`. Close every [`Database`] on the file first: pooled
+    /// connections keep using the key they were opened with.
+    pub fn rekey(db_path: &str, old_key: &str, new_key: &str) -> Result<(), ServerError> {
+        if new_key.trim().is_empty() {
+            return Err(ServerError::BadRequest(
+                "New database key must not be empty".to_string(),
+            ));
+        }
+        let conn = Connection::open(db_path)
+            .map_err(|e| ServerError::Database(format!("Failed to open database: {e}")))?;
+        apply_key(&conn, db_path, old_key)?;
+        conn.pragma_update(None, "rekey", new_key)
+            .map_err(|e| ServerError::Database(format!("Failed to rekey database: {e}")))?;
+        tracing::info!("Database re-encrypted: {}", db_path);
+        Ok(())
+    }
+}
+
+/// Unlock `conn` with `key` and confirm the key actually decrypts the file.
+pub(super) fn apply_key(conn: &Connection, db_path: &str, key: &str) -> Result<(), ServerError> {
+    let cipher_version: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+        .optional()
+        .map_err(|e| ServerError::Database(format!("Failed to query SQLCipher: {e}")))?;
+    if cipher_version.is_none() {
+        return Err(ServerError::Database(
+            "A database encryption key is set, but this build has no SQLCipher support; \
+             rebuild with the `sqlcipher` feature or unset ROUTA_DB_KEY"
+                .to_string(),
+        ));
+    }
+
+    conn.pragma_update(None, "key", key)
+        .map_err(|e| ServerError::Database(format!("Failed to set database key: {e}")))?;
+    // The key is only checked when the first page is read.
+    match conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    }) {
+        Ok(_) => Ok(()),
+        Err(rusqlite::Error::SqliteFailure(err, _))
+            if err.code == rusqlite::ErrorCode::NotADatabase =>
+        {
+            Err(ServerError::Database(format!(
+                "Cannot decrypt {db_path}: the encryption key is wrong, or the file was \
+                 created without one"
+            )))
+        }
+        Err(e) => Err(ServerError::Database(format!(
+            "Failed to read encrypted database: {e}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "sqlcipher"))]
+    #[test]
+    fn key_without_sqlcipher_support_fails_clearly() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+
+        let err = Database::open_with_key(&path.to_string_lossy(), Some("secret"))
+            .err()
+            .expect("a key must not be ignored");
+
+        assert!(err.to_string().contains("no SQLCipher support"), "{err}");
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn reopening_requires_the_right_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        let path = path.to_string_lossy().to_string();
+        {
+            let db = Database::open_with_key(&path, Some("correct horse")).unwrap();
+            db.with_conn(|conn| {
+                conn.execute(
+                    "INSERT INTO workspaces (id, title, status, metadata, created_at, updated_at)
+                     VALUES ('ws-1', 'Secret', 'active', '{}', 0, 0)",
+                    [],
+                )
+            })
+            .unwrap();
+        }
+
+        let err = Database::open_with_key(&path, Some("battery staple"))
+            .err()
+            .expect("wrong key should fail");
+        assert!(err.to_string().contains("encryption key is wrong"), "{err}");
+        assert!(Database::open_with_key(&path, None).is_err());
+
+        let db = Database::open_with_key(&path, Some("correct horse")).unwrap();
+        let title: String = db
+            .with_conn(|conn| {
+                conn.query_row(
+                    "SELECT title FROM workspaces WHERE id = 'ws-1'",
+                    [],
+                    |row| row.get(0),
+                )
+            })
+            .unwrap();
+        assert_eq!(title, "Secret");
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn rekey_switches_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        let path = path.to_string_lossy().to_string();
+        drop(Database::open_with_key(&path, Some("old")).unwrap());
+
+        Database::rekey(&path, "old", "new").unwrap();
+
+        assert!(Database::open_with_key(&path, Some("old")).is_err());
+        assert!(Database::open_with_key(&path, Some("new")).is_ok());
+    }
+}
@@ -1,92 +1,173 @@
 //! SQLite database layer for the Routa desktop backend.
 //!
-//! Uses rusqlite with WAL mode for concurrent read performance.
-//! All database operations are executed via `tokio::task::spawn_blocking`
-//! to avoid blocking the async runtime.
+//! Uses an r2d2 pool of rusqlite connections in WAL mode, so reads run in
+//! parallel while SQLite serializes writers (each connection waits on
+//! `busy_timeout` rather than failing). All database operations are executed
+//! via `tokio::task::spawn_blocking` to avoid blocking the async runtime.
 
-use rusqlite::Connection;
+pub mod backend;
+pub mod backup;
+pub mod encryption;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod recovery;
+
+pub use backend::DbBackend;
+pub use backup::BackupInfo;
+#[cfg(feature = "postgres")]
+pub use postgres::PgDatabase;
+pub use recovery::{IntegrityStatus, RecoveryReport};
+
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Connection, Transaction, TransactionBehavior};
 use std::path::Path;
-use std::sync::{Arc, Mutex};
 
 use crate::error::ServerError;
 
+/// Per-connection setup; runs for every connection the pool opens.
+const CONNECTION_PRAGMAS: &str = "PRAGMA foreign_keys=ON; PRAGMA busy_timeout=5000;";
+
+/// One pooled connection per available CPU.
+fn default_pool_size() -> u32 {
+    std::thread::available_parallelism()
+        .map(|n| n.get() as u32)
+        .unwrap_or(4)
+}
+
+/// Whether `db_path` names an in-memory SQLite database rather than a file.
+fn is_in_memory_path(db_path: &str) -> bool {
+    db_path == ":memory:"
+        || db_path.starts_with("file::memory:")
+        || (db_path.starts_with("file:") && db_path.contains("mode=memory"))
+}
+
 /// Thread-safe handle to the SQLite database.
 #[derive(Clone)]
 pub struct Database {
-    conn: Arc<Mutex<Connection>>,
+    pool: Pool<SqliteConnectionManager>,
+    /// SQLCipher key the pool's connections were opened with; backups are
+    /// written and read with the same key.
+    key: Option<String>,
 }
 
 impl Database {
-    fn ignore_duplicate_column(
-        result: Result<usize, rusqlite::Error>,
-    ) -> Result<(), rusqlite::Error> {
-        match result {
-            Ok(_) => Ok(()),
-            Err(error)
-                if error
-                    .to_string()
-                    .to_ascii_lowercase()
-                    .contains("duplicate column name") =>
-            {
-                Ok(())
-            }
-            Err(error) => Err(error),
-        }
+    /// Open (or create) a SQLite database at the given path with one pooled
+    /// connection per available CPU, encrypted with `ROUTA_DB_KEY` if set.
+    ///
+    /// Errors that look like file corruption point at `routa db repair`.
+    pub fn open(db_path: &str) -> Result<Self, ServerError> {
+        Self::open_with_key(db_path, Self::encryption_key_from_env().as_deref())
     }
 
-    /// Open (or create) a SQLite database at the given path.
-    pub fn open(db_path: &str) -> Result<Self, ServerError> {
+    /// Like [`open`](Self::open), with an explicit SQLCipher key instead of
+    /// `ROUTA_DB_KEY`. `None` opens the file unencrypted.
+    pub fn open_with_key(db_path: &str, key: Option<&str>) -> Result<Self, ServerError> {
+        Self::open_with_options(db_path, default_pool_size(), key)
+            .map_err(|e| recovery::with_recovery_hint(db_path, e))
+    }
+
+    /// Open (or create) an unencrypted SQLite database at the given path,
+    /// pooling up to `pool_size` connections.
+    pub fn open_with_pool_size(db_path: &str, pool_size: u32) -> Result<Self, ServerError> {
+        Self::open_with_options(db_path, pool_size, None)
+    }
+
+    fn open_with_options(
+        db_path: &str,
+        pool_size: u32,
+        key: Option<&str>,
+    ) -> Result<Self, ServerError> {
+        // A pool of connections to `:memory:` would be a pool of separate
+        // empty databases.
+        if is_in_memory_path(db_path) {
+            return Self::open_in_memory();
+        }
+
         let path = Path::new(db_path);
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).ok();
         }
 
+        // The journal mode is persistent, so set it once before the pool
+        // opens connections concurrently. The key goes first: nothing can
+        // be read from an encrypted file before it.
         let conn = Connection::open(db_path)
             .map_err(|e| ServerError::Database(format!("Failed to open database: {e}")))?;
-
-        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")
+        if let Some(key) = key {
+            encryption::apply_key(&conn, db_path, key)?;
+        }
+        conn.execute_batch("PRAGMA journal_mode=WAL;")
             .map_err(|e| ServerError::Database(format!("Failed to set pragmas: {e}")))?;
+        drop(conn);
 
-        let db = Self {
-            conn: Arc::new(Mutex::new(conn)),
-        };
+        let encrypted = key.is_some();
+        let key = key.map(str::to_string);
+        let init_key = key.clone();
+        let manager = SqliteConnectionManager::file(db_path).with_init(move |conn| {
+            if let Some(key) = &init_key {
+                conn.pragma_update(None, "key", key)?;
+            }
+            conn.execute_batch(CONNECTION_PRAGMAS)
+        });
+        let db = Self::with_pool(manager, pool_size.max(1), key)?;
 
         db.initialize_tables()?;
 
-        tracing::info!("SQLite database opened at: {}", db_path);
+        tracing::info!(
+            "SQLite database opened at: {} (pool size {}{})",
+            db_path,
+            pool_size.max(1),
+            if encrypted { ", encrypted" } else { "" }
+        );
         Ok(db)
     }
 
     /// Open an in-memory database (for testing).
+    ///
+    /// Every in-memory connection is its own database, so the pool holds a
+    /// single connection that is never recycled.
     pub fn open_in_memory() -> Result<Self, ServerError> {
-        let conn = Connection::open_in_memory()
-            .map_err(|e| ServerError::Database(format!("Failed to open in-memory db: {e}")))?;
-
-        conn.execute_batch("PRAGMA foreign_keys=ON;")
-            .map_err(|e| ServerError::Database(format!("Failed to set pragmas: {e}")))?;
-
-        let db = Self {
-            conn: Arc::new(Mutex::new(conn)),
-        };
+        let manager = SqliteConnectionManager::memory()
+            .with_init(|conn| conn.execute_batch(CONNECTION_PRAGMAS));
+        let pool = Pool::builder()
+            .max_size(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .build(manager)
+            .map_err(|e| ServerError::Database(format!("Failed to open database: {e}")))?;
+        let db = Self { pool, key: None };
 
         db.initialize_tables()?;
         Ok(db)
     }
 
-    /// Execute a closure with access to the database connection.
-    /// Automatically handles locking and error conversion.
+    fn with_pool(
+        manager: SqliteConnectionManager,
+        pool_size: u32,
+        key: Option<String>,
+    ) -> Result<Self, ServerError> {
+        let pool = Pool::builder()
+            .max_size(pool_size)
+            .build(manager)
+            .map_err(|e| ServerError::Database(format!("Failed to open database: {e}")))?;
+        Ok(Self { pool, key })
+    }
+
+    /// Execute a closure with a pooled database connection.
+    /// Automatically handles checkout and error conversion.
     pub fn with_conn<F, T>(&self, f: F) -> Result<T, ServerError>
     where
         F: FnOnce(&Connection) -> Result<T, rusqlite::Error>,
     {
         let conn = self
-            .conn
-            .lock()
-            .map_err(|e| ServerError::Database(format!("Lock poisoned: {e}")))?;
+            .pool
+            .get()
+            .map_err(|e| ServerError::Database(format!("Connection pool error: {e}")))?;
         f(&conn).map_err(|e| ServerError::Database(e.to_string()))
     }
 
-    /// Execute a closure with access to the database connection (async-friendly).
+    /// Execute a closure with a pooled database connection (async-friendly).
     pub async fn with_conn_async<F, T>(&self, f: F) -> Result<T, ServerError>
     where
         F: FnOnce(&Connection) -> Result<T, rusqlite::Error> + Send + 'static,
@@ -98,6 +179,26 @@ impl Database {
             .map_err(|e| ServerError::Database(format!("Task join error: {e}")))?
     }
 
+    /// Run a closure inside a SQLite transaction (async-friendly).
+    ///
+    /// Commits when the closure returns `Ok`; any error rolls back every write
+    /// made through the connection. Use the stores' `save_in` helpers to group
+    /// writes that must land together. The transaction takes the write lock
+    /// up front so it never fails upgrading from a read under contention.
+    pub async fn transaction<F, T>(&self, f: F) -> Result<T, ServerError>
+    where
+        F: FnOnce(&Connection) -> Result<T, rusqlite::Error> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.with_conn_async(move |conn| {
+            let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+            let value = f(&tx)?;
+            tx.commit()?;
+            Ok(value)
+        })
+        .await
+    }
+
     /// Create all tables if they don't exist.
     fn initialize_tables(&self) -> Result<(), ServerError> {
         self.with_conn(|conn| {
@@ -146,6 +247,14 @@ impl Database {
                 );
                 CREATE INDEX IF NOT EXISTS idx_acp_sessions_workspace ON acp_sessions(workspace_id);
 
+                CREATE TABLE IF NOT EXISTS session_messages (
+                    session_id      TEXT NOT NULL REFERENCES acp_sessions(id) ON DELETE CASCADE,
+                    seq             INTEGER NOT NULL,
+                    message         TEXT NOT NULL,
+                    created_at      INTEGER NOT NULL,
+                    PRIMARY KEY (session_id, seq)
+                );
+
                 CREATE TABLE IF NOT EXISTS skills (
                     id              TEXT PRIMARY KEY,
                     name            TEXT NOT NULL,
@@ -156,6 +265,7 @@ impl Database {
                     license         TEXT,
                     metadata        TEXT NOT NULL DEFAULT '{}',
                     installs        INTEGER NOT NULL DEFAULT 0,
+                    uses            INTEGER NOT NULL DEFAULT 0,
                     created_at      INTEGER NOT NULL,
                     updated_at      INTEGER NOT NULL
                 );
@@ -223,6 +333,7 @@ impl Database {
                     codebase_ids            TEXT NOT NULL DEFAULT '[]',
                     context_search_spec     TEXT,
                     worktree_id             TEXT,
+                    acceptance_criteria_status TEXT NOT NULL DEFAULT '[]',
                     version                 INTEGER NOT NULL DEFAULT 1,
                     created_at              INTEGER NOT NULL,
                     updated_at              INTEGER NOT NULL
@@ -270,6 +381,17 @@ impl Database {
                     PRIMARY KEY (workspace_id, id)
                 );
 
+                CREATE TABLE IF NOT EXISTS note_versions (
+                    workspace_id    TEXT NOT NULL,
+                    note_id         TEXT NOT NULL,
+                    version         INTEGER NOT NULL,
+                    content         TEXT NOT NULL,
+                    author_agent_id TEXT,
+                    created_at      INTEGER NOT NULL,
+                    PRIMARY KEY (workspace_id, note_id, version),
+                    FOREIGN KEY (workspace_id, note_id) REFERENCES notes(workspace_id, id) ON DELETE CASCADE
+                );
+
                 CREATE TABLE IF NOT EXISTS messages (
                     id          TEXT PRIMARY KEY,
                     agent_id    TEXT NOT NULL,
@@ -281,6 +403,38 @@ impl Database {
                     turn        INTEGER
                 );
 
+                CREATE TABLE IF NOT EXISTS message_audit (
+                    id          TEXT PRIMARY KEY,
+                    message_id  TEXT NOT NULL,
+                    agent_id    TEXT NOT NULL,
+                    action      TEXT NOT NULL,
+                    actor       TEXT NOT NULL,
+                    reason      TEXT,
+                    created_at  INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS task_audit (
+                    id              TEXT PRIMARY KEY,
+                    task_id         TEXT NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+                    action          TEXT NOT NULL,
+                    actor           TEXT NOT NULL,
+                    reason          TEXT,
+                    previous_status TEXT NOT NULL,
+                    created_at      INTEGER NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS tool_audit (
+                    id           TEXT PRIMARY KEY,
+                    tool_name    TEXT NOT NULL,
+                    args         TEXT NOT NULL,
+                    agent_id     TEXT,
+                    session_id   TEXT,
+                    workspace_id TEXT,
+                    success      INTEGER NOT NULL,
+                    duration_ms  INTEGER NOT NULL,
+                    created_at   INTEGER NOT NULL
+                );
+
                 CREATE TABLE IF NOT EXISTS event_subscriptions (
                     id              TEXT PRIMARY KEY,
                     agent_id        TEXT NOT NULL,
@@ -290,9 +444,27 @@ impl Database {
                     one_shot        INTEGER NOT NULL DEFAULT 0,
                     wait_group_id   TEXT,
                     priority        INTEGER NOT NULL DEFAULT 0,
+                    workspace_id    TEXT NOT NULL DEFAULT '',
+                    cross_workspace INTEGER NOT NULL DEFAULT 0,
+                    ttl_secs        INTEGER,
                     created_at      INTEGER NOT NULL
                 );
 
+                CREATE TABLE IF NOT EXISTS delegations (
+                    child_agent_id    TEXT PRIMARY KEY,
+                    child_session_id  TEXT NOT NULL,
+                    parent_agent_id   TEXT NOT NULL,
+                    parent_session_id TEXT NOT NULL,
+                    task_id           TEXT NOT NULL,
+                    role              TEXT NOT NULL,
+                    provider          TEXT NOT NULL,
+                    group_id          TEXT,
+                    completed         INTEGER NOT NULL DEFAULT 0,
+                    created_at        INTEGER NOT NULL,
+                    repo_path         TEXT,
+                    worktree_path     TEXT
+                );
+
                 CREATE TABLE IF NOT EXISTS pending_events (
                     id              TEXT PRIMARY KEY,
                     agent_id        TEXT NOT NULL,
@@ -300,17 +472,28 @@ impl Database {
                     source_agent_id TEXT NOT NULL,
                     workspace_id    TEXT NOT NULL,
                     data            TEXT NOT NULL DEFAULT '{}',
-                    timestamp       INTEGER NOT NULL
+                    timestamp       INTEGER NOT NULL,
+                    delivered       INTEGER NOT NULL DEFAULT 0
                 );
 
                 CREATE INDEX IF NOT EXISTS idx_agents_workspace ON agents(workspace_id);
+                CREATE INDEX IF NOT EXISTS idx_agents_workspace_role ON agents(workspace_id, role);
                 CREATE INDEX IF NOT EXISTS idx_tasks_workspace ON tasks(workspace_id);
+                CREATE INDEX IF NOT EXISTS idx_tasks_workspace_updated ON tasks(workspace_id, updated_at);
+                CREATE INDEX IF NOT EXISTS idx_tasks_workspace_created ON tasks(workspace_id, created_at);
                 CREATE INDEX IF NOT EXISTS idx_artifacts_task ON artifacts(task_id);
                 CREATE INDEX IF NOT EXISTS idx_artifacts_workspace ON artifacts(workspace_id);
                 CREATE INDEX IF NOT EXISTS idx_kanban_boards_workspace ON kanban_boards(workspace_id);
                 CREATE UNIQUE INDEX IF NOT EXISTS uq_kanban_boards_default_workspace ON kanban_boards(workspace_id) WHERE is_default = 1;
                 CREATE INDEX IF NOT EXISTS idx_notes_workspace ON notes(workspace_id);
+                CREATE INDEX IF NOT EXISTS idx_notes_workspace_updated ON notes(workspace_id, updated_at);
+                CREATE INDEX IF NOT EXISTS idx_notes_workspace_created ON notes(workspace_id, created_at);
                 CREATE INDEX IF NOT EXISTS idx_messages_agent ON messages(agent_id);
+                CREATE INDEX IF NOT EXISTS idx_message_audit_agent ON message_audit(agent_id);
+                CREATE INDEX IF NOT EXISTS idx_task_audit_task ON task_audit(task_id);
+                CREATE INDEX IF NOT EXISTS idx_tool_audit_created ON tool_audit(created_at);
+                CREATE INDEX IF NOT EXISTS idx_pending_events_agent ON pending_events(agent_id);
+                CREATE INDEX IF NOT EXISTS idx_delegations_group ON delegations(group_id);
 
                 CREATE TABLE IF NOT EXISTS schedules (
                     id              TEXT PRIMARY KEY,
@@ -330,6 +513,14 @@ impl Database {
                 CREATE INDEX IF NOT EXISTS idx_schedules_workspace ON schedules(workspace_id);
                 CREATE INDEX IF NOT EXISTS idx_schedules_next_run ON schedules(next_run_at) WHERE enabled = 1;
 
+                CREATE TABLE IF NOT EXISTS provider_credentials (
+                    provider        TEXT NOT NULL,
+                    key             TEXT NOT NULL,
+                    ciphertext      BLOB NOT NULL,
+                    updated_at      INTEGER NOT NULL,
+                    PRIMARY KEY (provider, key)
+                );
+
                 CREATE TABLE IF NOT EXISTS worktrees (
                     id              TEXT PRIMARY KEY,
                     codebase_id     TEXT NOT NULL REFERENCES codebases(id) ON DELETE CASCADE,
@@ -356,93 +547,403 @@ impl Database {
         self.run_migrations()
     }
 
-    /// Apply incremental migrations for schema changes on existing databases.
+    /// Apply every [`MIGRATIONS`] step newer than [`schema_version`](Self::schema_version),
+    /// each in its own transaction that also records it in `schema_migrations`.
+    /// A failing step rolls back and aborts startup.
     fn run_migrations(&self) -> Result<(), ServerError> {
         self.with_conn(|conn| {
-            // Add session_id to tasks if it doesn't exist yet (ignore error if already present)
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN comment TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN session_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN board_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN column_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN position INTEGER NOT NULL DEFAULT 0", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN priority TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN labels TEXT NOT NULL DEFAULT '[]'", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN assignee TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN assigned_provider TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN assigned_role TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN assigned_specialist_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN assigned_specialist_name TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN trigger_session_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN github_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN github_number INTEGER", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN github_url TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN github_repo TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN github_state TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN github_synced_at INTEGER", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN last_sync_error TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN test_cases TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN codebase_ids TEXT NOT NULL DEFAULT '[]'", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN context_search_spec TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN worktree_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN creation_source TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN session_ids TEXT NOT NULL DEFAULT '[]'", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN lane_sessions TEXT NOT NULL DEFAULT '[]'", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE tasks ADD COLUMN lane_handoffs TEXT NOT NULL DEFAULT '[]'", []))?;
-            // Add session_id to notes if it doesn't exist yet (ignore error if already present)
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE notes ADD COLUMN session_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE acp_sessions ADD COLUMN branch TEXT", []))?;
-            // Add parent_session_id to acp_sessions for CRAFTER child session tracking
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE acp_sessions ADD COLUMN parent_session_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE acp_sessions ADD COLUMN provider_session_id TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE acp_sessions ADD COLUMN custom_command TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE acp_sessions ADD COLUMN custom_args TEXT NOT NULL DEFAULT '[]'", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE codebases ADD COLUMN source_type TEXT", []))?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE codebases ADD COLUMN source_url TEXT", []))?;
             conn.execute_batch(
-                "CREATE TABLE IF NOT EXISTS kanban_boards (
-                    id TEXT PRIMARY KEY,
-                    workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
-                    name TEXT NOT NULL,
-                    is_default INTEGER NOT NULL DEFAULT 0,
-                    github_token TEXT,
-                    columns TEXT NOT NULL DEFAULT '[]',
-                    created_at INTEGER NOT NULL,
-                    updated_at INTEGER NOT NULL
-                );
-                CREATE INDEX IF NOT EXISTS idx_kanban_boards_workspace ON kanban_boards(workspace_id);
-                CREATE UNIQUE INDEX IF NOT EXISTS uq_kanban_boards_default_workspace ON kanban_boards(workspace_id) WHERE is_default = 1;"
+                "CREATE TABLE IF NOT EXISTS schema_migrations (
+                    version     INTEGER PRIMARY KEY,
+                    name        TEXT NOT NULL,
+                    applied_at  INTEGER NOT NULL
+                );",
+            )
+        })?;
+
+        let applied = self.schema_version()?;
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied as usize) {
+            let version = index as i64 + 1;
+            self.with_conn(|conn| {
+                let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+                // Another process may have applied it while we waited for the lock.
+                let done: bool = tx.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM schema_migrations WHERE version = ?1)",
+                    [version],
+                    |row| row.get(0),
+                )?;
+                if !done {
+                    (migration.apply)(&tx)?;
+                    tx.execute(
+                        "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+                        rusqlite::params![
+                            version,
+                            migration.name,
+                            chrono::Utc::now().timestamp_millis()
+                        ],
+                    )?;
+                }
+                tx.commit()
+            })
+            .map_err(|e| {
+                let reason = match e {
+                    ServerError::Database(reason) => reason,
+                    other => other.to_string(),
+                };
+                ServerError::Database(format!(
+                    "Schema migration {version} ({}) failed: {reason}",
+                    migration.name
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Highest applied schema migration; [`LATEST_SCHEMA_VERSION`] once the
+    /// database is open.
+    pub fn schema_version(&self) -> Result<i64, ServerError> {
+        self.with_conn(|conn| {
+            conn.query_row(
+                "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+                [],
+                |row| row.get(0),
+            )
+        })
+    }
+}
+
+/// One step of the schema history. Steps must be idempotent: databases
+/// created before `schema_migrations` existed replay them from version 1.
+struct Migration {
+    name: &'static str,
+    apply: fn(&Connection) -> Result<(), rusqlite::Error>,
+}
+
+/// Ordered schema migrations; version N is `MIGRATIONS[N - 1]`. Append new
+/// steps at the end and never edit or reorder applied ones.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        name: "tasks_kanban_and_tracking_columns",
+        apply: |conn| {
+            for (column, definition) in [
+                ("comment", "TEXT"),
+                ("session_id", "TEXT"),
+                ("board_id", "TEXT"),
+                ("column_id", "TEXT"),
+                ("position", "INTEGER NOT NULL DEFAULT 0"),
+                ("priority", "TEXT"),
+                ("labels", "TEXT NOT NULL DEFAULT '[]'"),
+                ("assignee", "TEXT"),
+                ("assigned_provider", "TEXT"),
+                ("assigned_role", "TEXT"),
+                ("assigned_specialist_id", "TEXT"),
+                ("assigned_specialist_name", "TEXT"),
+                ("trigger_session_id", "TEXT"),
+                ("github_id", "TEXT"),
+                ("github_number", "INTEGER"),
+                ("github_url", "TEXT"),
+                ("github_repo", "TEXT"),
+                ("github_state", "TEXT"),
+                ("github_synced_at", "INTEGER"),
+                ("last_sync_error", "TEXT"),
+                ("test_cases", "TEXT"),
+                ("codebase_ids", "TEXT NOT NULL DEFAULT '[]'"),
+                ("context_search_spec", "TEXT"),
+                ("worktree_id", "TEXT"),
+                ("creation_source", "TEXT"),
+                ("session_ids", "TEXT NOT NULL DEFAULT '[]'"),
+                ("lane_sessions", "TEXT NOT NULL DEFAULT '[]'"),
+                ("lane_handoffs", "TEXT NOT NULL DEFAULT '[]'"),
+                ("acceptance_criteria_status", "TEXT NOT NULL DEFAULT '[]'"),
+            ] {
+                add_column(conn, "tasks", column, definition)?;
+            }
+            Ok(())
+        },
+    },
+    Migration {
+        name: "notes_session_id",
+        apply: |conn| add_column(conn, "notes", "session_id", "TEXT"),
+    },
+    Migration {
+        name: "acp_sessions_lineage_and_launch_columns",
+        apply: |conn| {
+            add_column(conn, "acp_sessions", "branch", "TEXT")?;
+            // Parent session for CRAFTER child session tracking
+            add_column(conn, "acp_sessions", "parent_session_id", "TEXT")?;
+            add_column(conn, "acp_sessions", "provider_session_id", "TEXT")?;
+            add_column(conn, "acp_sessions", "custom_command", "TEXT")?;
+            add_column(
+                conn,
+                "acp_sessions",
+                "custom_args",
+                "TEXT NOT NULL DEFAULT '[]'",
             )?;
-            conn.execute_batch(
-                "CREATE TABLE IF NOT EXISTS artifacts (
-                    id TEXT PRIMARY KEY,
-                    type TEXT NOT NULL,
-                    task_id TEXT NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
-                    workspace_id TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
-                    provided_by_agent_id TEXT,
-                    requested_by_agent_id TEXT,
-                    request_id TEXT,
-                    content TEXT,
-                    context TEXT,
-                    status TEXT NOT NULL DEFAULT 'provided',
-                    expires_at INTEGER,
-                    metadata TEXT,
-                    created_at INTEGER NOT NULL,
-                    updated_at INTEGER NOT NULL
-                );
-                CREATE INDEX IF NOT EXISTS idx_artifacts_task ON artifacts(task_id);
-                CREATE INDEX IF NOT EXISTS idx_artifacts_workspace ON artifacts(workspace_id);"
+            add_column(
+                conn,
+                "acp_sessions",
+                "metadata",
+                "TEXT NOT NULL DEFAULT '{}'",
+            )
+        },
+    },
+    Migration {
+        name: "codebases_source_columns",
+        apply: |conn| {
+            add_column(conn, "codebases", "source_type", "TEXT")?;
+            add_column(conn, "codebases", "source_url", "TEXT")
+        },
+    },
+    Migration {
+        name: "pending_events_delivered",
+        apply: |conn| {
+            add_column(
+                conn,
+                "pending_events",
+                "delivered",
+                "INTEGER NOT NULL DEFAULT 0",
+            )
+        },
+    },
+    Migration {
+        name: "delegations_worktree_columns",
+        apply: |conn| {
+            add_column(conn, "delegations", "repo_path", "TEXT")?;
+            add_column(conn, "delegations", "worktree_path", "TEXT")
+        },
+    },
+    Migration {
+        name: "event_subscriptions_workspace_scope",
+        apply: |conn| {
+            // Subscriptions persisted before workspace scoping keep receiving every workspace's events.
+            add_column(
+                conn,
+                "event_subscriptions",
+                "workspace_id",
+                "TEXT NOT NULL DEFAULT ''",
+            )?;
+            add_column(
+                conn,
+                "event_subscriptions",
+                "cross_workspace",
+                "INTEGER NOT NULL DEFAULT 1",
             )?;
-            Self::ignore_duplicate_column(
-                conn.execute("ALTER TABLE kanban_boards ADD COLUMN github_token TEXT", []),
+            add_column(conn, "event_subscriptions", "ttl_secs", "INTEGER")
+        },
+    },
+    Migration {
+        name: "skills_uses",
+        apply: |conn| add_column(conn, "skills", "uses", "INTEGER NOT NULL DEFAULT 0"),
+    },
+    Migration {
+        name: "kanban_board_columns",
+        apply: |conn| {
+            add_column(conn, "kanban_boards", "github_token", "TEXT")?;
+            add_column(
+                conn,
+                "kanban_boards",
+                "columns",
+                "TEXT NOT NULL DEFAULT '[]'",
             )?;
-            Self::ignore_duplicate_column(conn.execute("ALTER TABLE kanban_boards ADD COLUMN columns TEXT NOT NULL DEFAULT '[]'", []))?;
-            let _ = conn.execute("UPDATE kanban_boards SET columns = columns_json WHERE (columns IS NULL OR columns = '[]') AND columns_json IS NOT NULL", []);
-            // Create indexes for session_id columns
+            if has_column(conn, "kanban_boards", "columns_json")? {
+                conn.execute(
+                    "UPDATE kanban_boards SET columns = columns_json
+                     WHERE (columns IS NULL OR columns = '[]') AND columns_json IS NOT NULL",
+                    [],
+                )?;
+            }
+            Ok(())
+        },
+    },
+    Migration {
+        name: "session_message_history_to_rows",
+        apply: |conn| {
+            // Move legacy message_history blobs into session_messages
+            conn.execute_batch(
+                "INSERT OR IGNORE INTO session_messages (session_id, seq, message, created_at)
+                     SELECT s.id, CAST(j.key AS INTEGER), j.value, s.updated_at
+                     FROM acp_sessions s, json_each(s.message_history) j
+                     WHERE s.message_history <> '[]' AND json_valid(s.message_history);
+                 UPDATE acp_sessions SET message_history = '[]' WHERE message_history <> '[]';",
+            )
+        },
+    },
+    Migration {
+        name: "session_id_indexes",
+        apply: |conn| {
             conn.execute_batch(
                 "CREATE INDEX IF NOT EXISTS idx_tasks_session ON tasks(session_id);
                  CREATE INDEX IF NOT EXISTS idx_notes_session ON notes(session_id);
-                 CREATE INDEX IF NOT EXISTS idx_acp_sessions_parent ON acp_sessions(parent_session_id);"
+                 CREATE INDEX IF NOT EXISTS idx_acp_sessions_parent ON acp_sessions(parent_session_id);",
+            )
+        },
+    },
+];
+
+/// Schema version of a database with every migration applied.
+pub const LATEST_SCHEMA_VERSION: i64 = MIGRATIONS.len() as i64;
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool, rusqlite::Error> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)",
+        [table, column],
+        |row| row.get(0),
+    )
+}
+
+/// `ALTER TABLE … ADD COLUMN` unless `table` already has `column`, which
+/// tables created from the current schema do.
+fn add_column(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), rusqlite::Error> {
+    if has_column(conn, table, column)? {
+        return Ok(());
+    }
+    conn.execute(
+        &format!("ALTER TABLE {table} ADD COLUMN {column} {definition}"),
+        [],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::Task;
+    use crate::models::workspace::Workspace;
+    use crate::store::{TaskStore, WorkspaceStore};
+
+    #[tokio::test]
+    async fn transaction_rolls_back_every_write_on_error() {
+        let db = Database::open_in_memory().unwrap();
+        let first = Workspace::new("ws-1".to_string(), "First".to_string(), None);
+        let second = Workspace::new("ws-2".to_string(), "Second".to_string(), None);
+
+        let result = db
+            .transaction(move |conn| {
+                WorkspaceStore::save_in(conn, &first)?;
+                WorkspaceStore::save_in(conn, &second)?;
+                conn.execute("INSERT INTO no_such_table VALUES (1)", [])?;
+                Ok(())
+            })
+            .await;
+
+        assert!(result.is_err());
+        let count: i64 = db
+            .with_conn(|conn| conn.query_row("SELECT COUNT(*) FROM workspaces", [], |r| r.get(0)))
+            .unwrap();
+        assert_eq!(count, 0);
+
+        let third = Workspace::new("ws-3".to_string(), "Third".to_string(), None);
+        db.transaction(move |conn| WorkspaceStore::save_in(conn, &third))
+            .await
+            .unwrap();
+        let store = WorkspaceStore::new(db.clone());
+        assert!(store.get("ws-3").await.unwrap().is_some());
+    }
+
+    #[test]
+    fn fresh_database_is_at_latest_schema_version() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(db.schema_version().unwrap(), LATEST_SCHEMA_VERSION);
+
+        // Reopening an up-to-date database applies nothing new.
+        db.run_migrations().unwrap();
+        let recorded: i64 = db
+            .with_conn(|conn| {
+                conn.query_row("SELECT COUNT(*) FROM schema_migrations", [], |r| r.get(0))
+            })
+            .unwrap();
+        assert_eq!(recorded, LATEST_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn migrations_bring_a_pre_migration_database_current() {
+        let db = Database::open_in_memory().unwrap();
+        // Simulate a database created before tasks.session_id and before
+        // migrations were tracked.
+        db.with_conn(|conn| {
+            conn.execute_batch(
+                "DROP INDEX idx_tasks_session;
+                 ALTER TABLE tasks DROP COLUMN session_id;
+                 DROP TABLE schema_migrations;",
             )
         })
+        .unwrap();
+        assert!(!db
+            .with_conn(|conn| has_column(conn, "tasks", "session_id"))
+            .unwrap());
+
+        db.run_migrations().unwrap();
+
+        assert!(db
+            .with_conn(|conn| has_column(conn, "tasks", "session_id"))
+            .unwrap());
+        assert_eq!(db.schema_version().unwrap(), LATEST_SCHEMA_VERSION);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn pooled_reads_run_concurrently_without_deadlock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        let db = Database::open_with_pool_size(&path.to_string_lossy(), 4).unwrap();
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .unwrap();
+        let store = TaskStore::new(db);
+        for i in 0..10 {
+            let task = Task::new(
+                format!("task-{i}"),
+                format!("Task {i}"),
+                "Objective".to_string(),
+                "default".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            store.save(&task).await.unwrap();
+        }
+
+        let mut reads = tokio::task::JoinSet::new();
+        for _ in 0..50 {
+            let store = store.clone();
+            reads.spawn(async move { store.list_by_workspace("default").await });
+        }
+        let results = tokio::time::timeout(std::time::Duration::from_secs(30), reads.join_all())
+            .await
+            .expect("concurrent reads should not deadlock");
+
+        assert_eq!(results.len(), 50);
+        for tasks in results {
+            assert_eq!(tasks.unwrap().len(), 10);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn memory_path_shares_one_database_across_checkouts() {
+        let db = Database::open_with_pool_size(":memory:", 4).unwrap();
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .unwrap();
+
+        let mut reads = tokio::task::JoinSet::new();
+        for _ in 0..8 {
+            let store = WorkspaceStore::new(db.clone());
+            reads.spawn(async move { store.get("default").await });
+        }
+        for workspace in reads.join_all().await {
+            assert!(workspace.unwrap().is_some());
+        }
+        assert!(is_in_memory_path("file::memory:?cache=shared"));
+        assert!(!is_in_memory_path("/tmp/memory.db"));
     }
 }
@@ -0,0 +1,138 @@
+//! Postgres storage engine (`postgres` feature).
+//!
+//! Mirrors the SQLite columns for the tables the Postgres-capable stores use:
+//! timestamps are epoch millis in `BIGINT` and list/object fields are JSON in
+//! `TEXT`, so both engines round-trip the same values.
+//!
+//! Connections come from a deadpool pool. Unless the URL sets
+//! `sslmode=disable`, they are made through a native-tls connector that
+//! verifies the server certificate and host name; `sslmode=prefer` (the
+//! default) falls back to plaintext only when the server does not offer TLS.
+
+use deadpool_postgres::{Manager, ManagerConfig, Object, Pool, RecyclingMethod};
+use postgres_native_tls::MakeTlsConnector;
+use tokio_postgres::config::SslMode;
+use tokio_postgres::{Config, NoTls};
+
+use crate::error::ServerError;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS workspaces (
+        id              TEXT PRIMARY KEY,
+        title           TEXT NOT NULL,
+        status          TEXT NOT NULL DEFAULT 'active',
+        metadata        TEXT NOT NULL DEFAULT '{}',
+        created_at      BIGINT NOT NULL,
+        updated_at      BIGINT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS tasks (
+        id                      TEXT PRIMARY KEY,
+        title                   TEXT NOT NULL,
+        objective               TEXT NOT NULL,
+        comment                 TEXT,
+        scope                   TEXT,
+        acceptance_criteria     TEXT,
+        verification_commands   TEXT,
+        test_cases              TEXT,
+        assigned_to             TEXT,
+        status                  TEXT NOT NULL DEFAULT 'PENDING',
+        board_id                TEXT,
+        column_id               TEXT,
+        position                BIGINT NOT NULL DEFAULT 0,
+        priority                TEXT,
+        labels                  TEXT NOT NULL DEFAULT '[]',
+        assignee                TEXT,
+        assigned_provider       TEXT,
+        assigned_role           TEXT,
+        assigned_specialist_id  TEXT,
+        assigned_specialist_name TEXT,
+        trigger_session_id      TEXT,
+        github_id               TEXT,
+        github_number           BIGINT,
+        github_url              TEXT,
+        github_repo             TEXT,
+        github_state            TEXT,
+        github_synced_at        BIGINT,
+        last_sync_error         TEXT,
+        dependencies            TEXT NOT NULL DEFAULT '[]',
+        parallel_group          TEXT,
+        workspace_id            TEXT NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+        session_id              TEXT,
+        creation_source         TEXT,
+        session_ids             TEXT NOT NULL DEFAULT '[]',
+        lane_sessions           TEXT NOT NULL DEFAULT '[]',
+        lane_handoffs           TEXT NOT NULL DEFAULT '[]',
+        completion_summary      TEXT,
+        verification_verdict    TEXT,
+        verification_report     TEXT,
+        codebase_ids            TEXT NOT NULL DEFAULT '[]',
+        context_search_spec     TEXT,
+        worktree_id             TEXT,
+        acceptance_criteria_status TEXT NOT NULL DEFAULT '[]',
+        version                 BIGINT NOT NULL DEFAULT 1,
+        created_at              BIGINT NOT NULL,
+        updated_at              BIGINT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_tasks_workspace ON tasks(workspace_id);
+    CREATE INDEX IF NOT EXISTS idx_tasks_session ON tasks(session_id);
+
+    CREATE TABLE IF NOT EXISTS task_audit (
+        id              TEXT PRIMARY KEY,
+        task_id         TEXT NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+        action          TEXT NOT NULL,
+        actor           TEXT NOT NULL,
+        reason          TEXT,
+        previous_status TEXT NOT NULL,
+        created_at      BIGINT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_task_audit_task ON task_audit(task_id);
+";
+
+/// Shared handle to a pool of Postgres connections.
+#[derive(Clone)]
+pub struct PgDatabase {
+    pool: Pool,
+}
+
+impl PgDatabase {
+    /// Connect to `url` (a libpq connection string or `postgres://` URL) and
+    /// create any missing tables. TLS follows the URL's `sslmode`.
+    pub async fn connect(url: &str) -> Result<Self, ServerError> {
+        let config: Config = url
+            .parse()
+            .map_err(|e| ServerError::Database(format!("Invalid Postgres URL: {e}")))?;
+        let manager_config = ManagerConfig {
+            recycling_method: RecyclingMethod::Fast,
+        };
+        let manager = match config.get_ssl_mode() {
+            SslMode::Disable => Manager::from_config(config, NoTls, manager_config),
+            _ => Manager::from_config(config, tls_connector()?, manager_config),
+        };
+        let pool = Pool::builder(manager)
+            .build()
+            .map_err(|e| ServerError::Database(format!("Failed to create Postgres pool: {e}")))?;
+        let db = Self { pool };
+
+        db.client()
+            .await?
+            .batch_execute(SCHEMA)
+            .await
+            .map_err(|e| ServerError::Database(format!("Failed to create tables: {e}")))?;
+        Ok(db)
+    }
+
+    /// Check out a pooled connection; it returns to the pool when dropped.
+    pub async fn client(&self) -> Result<Object, ServerError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| ServerError::Database(format!("Failed to connect to Postgres: {e}")))
+    }
+}
+
+fn tls_connector() -> Result<MakeTlsConnector, ServerError> {
+    let connector = native_tls::TlsConnector::new()
+        .map_err(|e| ServerError::Database(format!("Failed to set up Postgres TLS: {e}")))?;
+    Ok(MakeTlsConnector::new(connector))
+}
@@ -0,0 +1,410 @@
+//! Integrity checking and recovery for corrupted database files.
+//!
+//! Recovery is a dump-and-reload: a fresh database is created next to the
+//! damaged one, every readable row is copied across table by table (stopping
+//! at the first unreadable row in a table), the original is kept as a
+//! `.corrupt-<timestamp>` backup and the fresh file takes its place.
+
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Serialize;
+
+use super::{default_pool_size, Database};
+use crate::error::ServerError;
+
+/// Set to `1`/`true` to run [`Database::integrity_check`] when the app
+/// state is created.
+pub const INTEGRITY_CHECK_ENV: &str = "ROUTA_DB_INTEGRITY_CHECK";
+
+/// What [`Database::open_with_recovery`] salvaged from a damaged file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryReport {
+    /// The damaged original, moved aside.
+    pub backup_path: PathBuf,
+    /// Why recovery ran: the open error or integrity problems.
+    pub reason: String,
+    pub rows_recovered: usize,
+    /// Tables whose rows could only be read in part.
+    pub incomplete_tables: Vec<String>,
+}
+
+/// What [`Database::inspect`] found in a database file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityStatus {
+    pub ok: bool,
+    /// Integrity problems, or why the file could not be read at all.
+    pub problems: Vec<String>,
+    /// `None` when the file has no `schema_migrations` table or could not
+    /// be read.
+    pub schema_version: Option<i64>,
+}
+
+impl Database {
+    /// Check the database file at `db_path` through a read-only connection,
+    /// keyed with `ROUTA_DB_KEY` if set: nothing is created or migrated, so
+    /// a damaged file is inspected as it is. A file that cannot be read is
+    /// reported as a problem; only a missing file is an error.
+    pub fn inspect(db_path: &str) -> Result<IntegrityStatus, ServerError> {
+        if !Path::new(db_path).is_file() {
+            return Err(ServerError::NotFound(format!(
+                "Database file not found: {db_path}"
+            )));
+        }
+        let unreadable = |reason: String| IntegrityStatus {
+            ok: false,
+            problems: vec![reason],
+            schema_version: None,
+        };
+
+        let conn = match Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY) {
+            Ok(conn) => conn,
+            Err(e) => return Ok(unreadable(format!("Failed to open database: {e}"))),
+        };
+        if let Some(key) = Self::encryption_key_from_env() {
+            if let Err(e) = super::encryption::apply_key(&conn, db_path, &key) {
+                return Ok(unreadable(e.to_string()));
+            }
+        }
+        let problems = match integrity_problems(&conn) {
+            Ok(problems) => problems,
+            Err(e) => return Ok(unreadable(format!("Integrity check could not run: {e}"))),
+        };
+        let schema_version = conn
+            .query_row("SELECT MAX(version) FROM schema_migrations", [], |row| {
+                row.get::<_, Option<i64>>(0)
+            })
+            .optional()
+            .ok()
+            .flatten()
+            .flatten();
+
+        Ok(IntegrityStatus {
+            ok: problems.is_empty(),
+            problems,
+            schema_version,
+        })
+    }
+
+    /// Problems reported by `PRAGMA integrity_check`; empty when the
+    /// database is healthy.
+    pub fn integrity_check(&self) -> Result<Vec<String>, ServerError> {
+        self.with_conn(integrity_problems)
+    }
+
+    /// Whether `ROUTA_DB_INTEGRITY_CHECK` asks for a check at startup.
+    pub fn integrity_check_enabled_from_env() -> bool {
+        std::env::var(INTEGRITY_CHECK_ENV)
+            .map(|value| matches!(value.trim(), "1" | "true" | "TRUE" | "yes"))
+            .unwrap_or(false)
+    }
+
+    /// Run [`integrity_check`](Self::integrity_check) when
+    /// `ROUTA_DB_INTEGRITY_CHECK` is set and log how to repair a damaged
+    /// file. Startup continues either way.
+    pub fn startup_integrity_check(&self, db_path: &str) {
+        if !Self::integrity_check_enabled_from_env() {
+            return;
+        }
+        match self.integrity_check() {
+            Ok(problems) if problems.is_empty() => {
+                tracing::info!("Database integrity check passed: {}", db_path);
+            }
+            Ok(problems) => tracing::error!(
+                "Database {} failed its integrity check ({} problem(s), first: {}). \
+                 Stop Routa and run `routa db repair --db {}` to rebuild it from the \
+                 readable rows; the original is kept as a backup.",
+                db_path,
+                problems.len(),
+                problems[0],
+                db_path
+            ),
+            Err(e) => tracing::error!("Database integrity check could not run: {}", e),
+        }
+    }
+
+    /// Open `db_path`, rebuilding it from whatever rows are still readable
+    /// when it cannot be opened or fails [`integrity_check`](Self::integrity_check).
+    /// The report is `None` when the database was healthy. Encrypted
+    /// databases are not supported: an unreadable one usually means a
+    /// wrong key, not damage.
+    pub fn open_with_recovery(
+        db_path: &str,
+    ) -> Result<(Self, Option<RecoveryReport>), ServerError> {
+        if Self::encryption_key_from_env().is_some() {
+            return Err(ServerError::BadRequest(format!(
+                "Repair does not support encrypted databases; unset {} to repair an \
+                 unencrypted file",
+                super::encryption::DB_KEY_ENV
+            )));
+        }
+        let reason = match Self::open_with_pool_size(db_path, default_pool_size()) {
+            Ok(db) => {
+                let problems = db.integrity_check()?;
+                if problems.is_empty() {
+                    return Ok((db, None));
+                }
+                problems.join("; ")
+            }
+            Err(e) => e.to_string(),
+        };
+        tracing::warn!("Recovering database {}: {}", db_path, reason);
+
+        let report = recover(Path::new(db_path), reason)?;
+        let db = Self::open_with_pool_size(db_path, default_pool_size())?;
+        Ok((db, Some(report)))
+    }
+}
+
+/// Guidance appended to open failures that look like file corruption.
+pub(super) fn with_recovery_hint(db_path: &str, err: ServerError) -> ServerError {
+    match err {
+        ServerError::Database(msg)
+            if msg.contains("malformed") || msg.contains("not a database") =>
+        {
+            let encrypted_hint = if cfg!(feature = "sqlcipher") {
+                format!(
+                    ". If it is encrypted, set {} instead",
+                    super::encryption::DB_KEY_ENV
+                )
+            } else {
+                String::new()
+            };
+            ServerError::Database(format!(
+                "{msg}. The database file looks corrupted; run `routa db repair --db {db_path}` \
+                 to rebuild it from the readable rows (the original is kept as a backup)\
+                 {encrypted_hint}"
+            ))
+        }
+        other => other,
+    }
+}
+
+fn integrity_problems(conn: &Connection) -> Result<Vec<String>, rusqlite::Error> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows.into_iter().filter(|row| row != "ok").collect())
+}
+
+fn recover(path: &Path, reason: String) -> Result<RecoveryReport, ServerError> {
+    let stamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
+    let rebuilt = sibling(path, &format!(".recovering-{stamp}"));
+    let backup_path = sibling(path, &format!(".corrupt-{stamp}"));
+    let io_err = |what: &str, e: std::io::Error| {
+        ServerError::Database(format!("Database recovery failed to {what}: {e}"))
+    };
+
+    // Create the current schema in the new file, then fill it without the
+    // pool so foreign keys can be off while rows arrive in any order.
+    drop(Database::open_with_pool_size(
+        &rebuilt.to_string_lossy(),
+        1,
+    )?);
+    let (rows_recovered, incomplete_tables) = copy_readable_rows(path, &rebuilt).map_err(|e| {
+        ServerError::Database(format!("Database recovery failed to copy rows: {e}"))
+    })?;
+
+    std::fs::rename(path, &backup_path).map_err(|e| io_err("back up the original", e))?;
+    for suffix in ["-wal", "-shm"] {
+        let side = sibling(path, suffix);
+        if side.exists() {
+            std::fs::rename(&side, sibling(&backup_path, suffix))
+                .map_err(|e| io_err("back up the original", e))?;
+        }
+    }
+    for suffix in ["-wal", "-shm"] {
+        let _ = std::fs::remove_file(sibling(&rebuilt, suffix));
+    }
+    std::fs::rename(&rebuilt, path).map_err(|e| io_err("replace the original", e))?;
+
+    Ok(RecoveryReport {
+        backup_path,
+        reason,
+        rows_recovered,
+        incomplete_tables,
+    })
+}
+
+/// Copy every readable row of `from` into the same-named tables of `to`,
+/// using the columns both sides have. Returns the row count and the tables
+/// that stopped at an unreadable row.
+fn copy_readable_rows(from: &Path, to: &Path) -> Result<(usize, Vec<String>), rusqlite::Error> {
+    let conn = Connection::open(to)?;
+    conn.execute_batch("PRAGMA foreign_keys=OFF;")?;
+    let attached = conn.execute(
+        "ATTACH DATABASE ?1 AS damaged",
+        [from.to_string_lossy().as_ref()],
+    );
+    if attached.is_err() || !is_readable(&conn) {
+        // Nothing salvageable, e.g. the header itself is garbage.
+        return Ok((0, Vec::new()));
+    }
+
+    let tables: Vec<String> = conn
+        .prepare(
+            "SELECT name FROM main.sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name != 'schema_migrations'",
+        )?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    let mut copied = 0;
+    let mut incomplete = Vec::new();
+    for table in tables {
+        let Ok(columns) = shared_columns(&conn, &table) else {
+            incomplete.push(table);
+            continue;
+        };
+        if columns.is_empty() {
+            continue;
+        }
+        let column_list = columns.join(", ");
+        let placeholders = (1..=columns.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut insert = conn.prepare(&format!(
+            "INSERT OR IGNORE INTO main.{table} ({column_list}) VALUES ({placeholders})"
+        ))?;
+        let select = conn.prepare(&format!("SELECT {column_list} FROM damaged.{table}"));
+        let Ok(mut select) = select else {
+            incomplete.push(table);
+            continue;
+        };
+        let Ok(mut rows) = select.query([]) else {
+            incomplete.push(table);
+            continue;
+        };
+        loop {
+            match rows.next() {
+                Ok(Some(row)) => {
+                    let values = (0..columns.len())
+                        .map(|i| row.get::<_, rusqlite::types::Value>(i))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    copied += insert.execute(rusqlite::params_from_iter(values))?;
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    incomplete.push(table.clone());
+                    break;
+                }
+            }
+        }
+    }
+    Ok((copied, incomplete))
+}
+
+fn is_readable(conn: &Connection) -> bool {
+    conn.query_row("SELECT COUNT(*) FROM damaged.sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })
+    .is_ok()
+}
+
+/// Columns of `table` present in both the new schema and the damaged file.
+fn shared_columns(conn: &Connection, table: &str) -> Result<Vec<String>, rusqlite::Error> {
+    let columns = |schema: &str| -> Result<Vec<String>, rusqlite::Error> {
+        conn.prepare(&format!(
+            "SELECT name FROM pragma_table_info(?1, '{schema}')"
+        ))?
+        .query_map([table], |row| row.get(0))?
+        .collect()
+    };
+    let damaged = columns("damaged")?;
+    Ok(columns("main")?
+        .into_iter()
+        .filter(|column| damaged.contains(column))
+        .collect())
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integrity_check_passes_on_fresh_database() {
+        let db = Database::open_in_memory().unwrap();
+
+        assert!(db.integrity_check().unwrap().is_empty());
+    }
+
+    #[test]
+    fn inspect_reads_without_creating_or_migrating() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        let path_str = path.to_string_lossy().to_string();
+
+        assert!(matches!(
+            Database::inspect(&path_str),
+            Err(ServerError::NotFound(_))
+        ));
+        assert!(!path.exists());
+
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch("CREATE TABLE legacy (id TEXT)").unwrap();
+        drop(conn);
+        let status = Database::inspect(&path_str).unwrap();
+        assert!(status.ok);
+        assert_eq!(status.schema_version, None);
+        let conn = Connection::open(&path).unwrap();
+        let tables: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sqlite_master", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(tables, 1);
+    }
+
+    #[test]
+    fn inspect_reports_unreadable_file_as_a_problem() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        std::fs::write(&path, vec![0x5a; 8192]).unwrap();
+
+        let status = Database::inspect(&path.to_string_lossy()).unwrap();
+
+        assert!(!status.ok);
+        assert_eq!(status.problems.len(), 1);
+        assert_eq!(std::fs::read(&path).unwrap(), vec![0x5a; 8192]);
+    }
+
+    #[test]
+    fn open_with_recovery_leaves_healthy_file_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        let path_str = path.to_string_lossy().to_string();
+        drop(Database::open(&path_str).unwrap());
+
+        let (_, report) = Database::open_with_recovery(&path_str).unwrap();
+
+        assert!(report.is_none());
+    }
+
+    #[test]
+    fn open_with_recovery_replaces_unreadable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        let path_str = path.to_string_lossy().to_string();
+        std::fs::write(&path, vec![0x5a; 8192]).unwrap();
+
+        let err = Database::open(&path_str)
+            .err()
+            .expect("garbage should not open");
+        assert!(err.to_string().contains("routa db repair"));
+
+        let (db, report) = Database::open_with_recovery(&path_str).unwrap();
+        let report = report.expect("recovery should run");
+
+        assert!(report.backup_path.exists());
+        assert_eq!(report.rows_recovered, 0);
+        assert!(db.integrity_check().unwrap().is_empty());
+    }
+}
@@ -18,6 +18,15 @@ pub enum ServerError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// The caller did not present valid credentials.
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// The caller is known but may not touch this resource, e.g. a path
+    /// outside the repository it is scoped to.
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 
@@ -25,6 +34,13 @@ pub enum ServerError {
     NotImplemented(String),
 }
 
+#[cfg(feature = "postgres")]
+impl From<tokio_postgres::Error> for ServerError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        ServerError::Database(e.to_string())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // axum integration (opt-in via feature flag)
 // ---------------------------------------------------------------------------
@@ -39,6 +55,8 @@ impl axum::response::IntoResponse for ServerError {
             ServerError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
             ServerError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
             ServerError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            ServerError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
+            ServerError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg.clone()),
             ServerError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             ServerError::NotImplemented(msg) => (StatusCode::NOT_IMPLEMENTED, msg.clone()),
         };
@@ -47,3 +65,27 @@ impl axum::response::IntoResponse for ServerError {
         (status, axum::Json(body)).into_response()
     }
 }
+
+#[cfg(all(test, feature = "axum"))]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    #[test]
+    fn auth_errors_map_to_401_and_403() {
+        let status = |error: ServerError| error.into_response().status();
+        assert_eq!(
+            status(ServerError::Unauthorized("missing token".into())),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(ServerError::Forbidden("outside repo".into())),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(ServerError::BadRequest("bad".into())),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -4,15 +4,49 @@
 //!
 //! Features:
 //!   - One-shot subscriptions: auto-remove after first matching event
-//!   - Priority ordering: higher priority subscribers get notified first
+//!   - Priority ordering: each agent's pending events drain highest priority
+//!     first (see [`EventSubscription::priority`])
 //!   - Wait-group support: group multiple subscriptions for after_all semantics
 //!   - Pre-subscribe: subscribe before the triggering action
+//!   - Workspace isolation: subscriptions only see events from their own
+//!     workspace unless they opt into `cross_workspace`
+//!   - Optional persistence: subscriptions and queued events survive restarts
+//!     when `ROUTA_PERSIST_EVENTS` is set (see [`EventBus::replay_pending`])
+//!   - Expiry: subscriptions lapse after a TTL and a background sweeper drops
+//!     them (see [`EventBus::spawn_subscription_sweeper`])
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use crate::error::ServerError;
+use crate::store::EventStore;
+
+mod payloads;
+
+pub use payloads::{
+    AgentCreatedData, AgentErrorData, DelegationCancelledData, DelegationQueuedData,
+    KanbanChangedData, MessageSentData, ReportSubmittedData, SessionLimitReachedData,
+    TaskAssignedData, TaskCompletedData, TaskFailedData, TaskStatusChangedData,
+    WorkspaceRenamedData,
+};
+
+/// Environment variable that enables SQLite persistence for the event bus.
+pub const PERSIST_EVENTS_ENV: &str = "ROUTA_PERSIST_EVENTS";
+
+/// Environment variable overriding the default subscription TTL, in seconds.
+/// `0` disables expiry.
+pub const SUBSCRIPTION_TTL_ENV: &str = "ROUTA_SUBSCRIPTION_TTL_SECS";
+
+/// Subscription TTL used when neither the subscription nor the environment
+/// sets one.
+pub const DEFAULT_SUBSCRIPTION_TTL_SECS: u64 = 60 * 60;
 
 /// Event types for agent coordination.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -29,6 +63,9 @@ pub enum AgentEventType {
     MessageSent,
     ReportSubmitted,
     WorkspaceUpdated,
+    DelegationQueued,
+    DelegationCancelled,
+    SessionLimitReached,
 }
 
 impl AgentEventType {
@@ -45,6 +82,9 @@ impl AgentEventType {
             Self::MessageSent => "MESSAGE_SENT",
             Self::ReportSubmitted => "REPORT_SUBMITTED",
             Self::WorkspaceUpdated => "WORKSPACE_UPDATED",
+            Self::DelegationQueued => "DELEGATION_QUEUED",
+            Self::DelegationCancelled => "DELEGATION_CANCELLED",
+            Self::SessionLimitReached => "SESSION_LIMIT_REACHED",
         }
     }
 
@@ -62,6 +102,9 @@ impl AgentEventType {
             "MESSAGE_SENT" => Some(Self::MessageSent),
             "REPORT_SUBMITTED" => Some(Self::ReportSubmitted),
             "WORKSPACE_UPDATED" => Some(Self::WorkspaceUpdated),
+            "DELEGATION_QUEUED" => Some(Self::DelegationQueued),
+            "DELEGATION_CANCELLED" => Some(Self::DelegationCancelled),
+            "SESSION_LIMIT_REACHED" => Some(Self::SessionLimitReached),
             _ => None,
         }
     }
@@ -91,8 +134,31 @@ pub struct EventSubscription {
     pub one_shot: bool,
     /// Group ID for wait-all semantics
     pub wait_group_id: Option<String>,
-    /// Higher priority subscriptions are notified first (default: 0)
+    /// Delivery priority (default: 0). An event is queued once per agent, at
+    /// the highest priority among that agent's matching subscriptions, and
+    /// [`EventBus::drain_pending_events`] returns higher priorities first,
+    /// oldest first within a priority. Priority never suppresses delivery:
+    /// every matching one-shot subscription is consumed by the event.
     pub priority: i32,
+    /// Workspace whose events this subscription receives
+    pub workspace_id: String,
+    /// If true, receive events from every workspace
+    pub cross_workspace: bool,
+    /// Seconds after subscribing until the bus drops this subscription.
+    /// `None` uses the bus default; `Some(0)` never expires.
+    pub ttl_secs: Option<u64>,
+}
+
+impl EventSubscription {
+    fn receives(&self, event: &AgentEvent) -> bool {
+        if self.exclude_self && event.agent_id == self.agent_id {
+            return false;
+        }
+        if !self.cross_workspace && event.workspace_id != self.workspace_id {
+            return false;
+        }
+        self.event_types.contains(&event.event_type)
+    }
 }
 
 /// Wait group tracks multiple agents completing a set of tasks.
@@ -106,18 +172,49 @@ pub struct WaitGroup {
 
 type EventHandler = Arc<dyn Fn(AgentEvent) + Send + Sync>;
 
+/// An event waiting in an agent's buffer, with the priority it was queued at.
+struct PendingEvent {
+    priority: i32,
+    event: AgentEvent,
+    /// Id of the persisted row, on buses with a store.
+    row_id: Option<String>,
+}
+
 /// Inner state for the EventBus.
 struct EventBusInner {
     handlers: HashMap<String, EventHandler>,
     subscriptions: HashMap<String, EventSubscription>,
-    pending_events: HashMap<String, Vec<AgentEvent>>,
+    /// Per-agent buffers, kept sorted by priority (descending), FIFO within
+    /// a priority.
+    pending_events: HashMap<String, Vec<PendingEvent>>,
     wait_groups: HashMap<String, WaitGroup>,
+    /// When each expiring subscription lapses, by subscription id.
+    expires_at: HashMap<String, Instant>,
+    /// Queued rows whose insert has not finished, by row id, with whether
+    /// they were drained meanwhile; `emit` marks those delivered once the
+    /// insert lands.
+    unsaved: HashMap<String, bool>,
+}
+
+impl EventBusInner {
+    fn is_expired(&self, subscription_id: &str, now: Instant) -> bool {
+        self.expires_at
+            .get(subscription_id)
+            .is_some_and(|expires_at| *expires_at <= now)
+    }
+
+    fn remove_subscription(&mut self, subscription_id: &str) -> bool {
+        self.expires_at.remove(subscription_id);
+        self.subscriptions.remove(subscription_id).is_some()
+    }
 }
 
 /// Thread-safe event bus for inter-agent communication.
 #[derive(Clone)]
 pub struct EventBus {
     inner: Arc<RwLock<EventBusInner>>,
+    store: Option<EventStore>,
+    default_ttl_secs: u64,
 }
 
 impl Default for EventBus {
@@ -134,10 +231,51 @@ impl EventBus {
                 subscriptions: HashMap::new(),
                 pending_events: HashMap::new(),
                 wait_groups: HashMap::new(),
+                expires_at: HashMap::new(),
+                unsaved: HashMap::new(),
             })),
+            store: None,
+            default_ttl_secs: DEFAULT_SUBSCRIPTION_TTL_SECS,
         }
     }
 
+    /// TTL applied to subscriptions that do not set their own; `0` disables
+    /// expiry.
+    pub fn with_subscription_ttl(mut self, ttl_secs: u64) -> Self {
+        self.default_ttl_secs = ttl_secs;
+        self
+    }
+
+    /// Default subscription TTL from `ROUTA_SUBSCRIPTION_TTL_SECS`.
+    pub fn subscription_ttl_from_env() -> u64 {
+        match std::env::var(SUBSCRIPTION_TTL_ENV) {
+            Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!(
+                    "[EventBus] Ignoring invalid {}: {}",
+                    SUBSCRIPTION_TTL_ENV,
+                    raw
+                );
+                DEFAULT_SUBSCRIPTION_TTL_SECS
+            }),
+            Err(_) => DEFAULT_SUBSCRIPTION_TTL_SECS,
+        }
+    }
+
+    /// Create an event bus that mirrors subscriptions and queued events to SQLite.
+    pub fn with_persistence(store: EventStore) -> Self {
+        Self {
+            store: Some(store),
+            ..Self::new()
+        }
+    }
+
+    /// Whether `ROUTA_PERSIST_EVENTS` asks for a persistent event bus.
+    pub fn persistence_enabled_from_env() -> bool {
+        std::env::var(PERSIST_EVENTS_ENV)
+            .map(|value| matches!(value.trim(), "1" | "true" | "TRUE" | "yes"))
+            .unwrap_or(false)
+    }
+
     // ─── Direct handlers ────────────────────────────────────────────────
 
     /// Subscribe to events with a handler function.
@@ -171,35 +309,43 @@ impl EventBus {
             });
         }
 
-        // 2. Buffer for agent subscriptions, sorted by priority (descending)
-        let mut sorted_subs: Vec<_> = inner.subscriptions.values().cloned().collect();
-        sorted_subs.sort_by(|a, b| b.priority.cmp(&a.priority));
-
+        // 2. Buffer once per subscribed agent, at the priority of its
+        //    highest-priority matching subscription
+        let mut matched: HashMap<String, i32> = HashMap::new();
         let mut one_shot_to_remove: Vec<String> = Vec::new();
+        let now = Instant::now();
 
-        for sub in &sorted_subs {
-            if sub.exclude_self && event.agent_id == sub.agent_id {
-                continue;
-            }
-            if !sub.event_types.contains(&event.event_type) {
+        for sub in inner.subscriptions.values() {
+            // Expired subscriptions wait for the sweeper but receive nothing
+            if !sub.receives(&event) || inner.is_expired(&sub.id, now) {
                 continue;
             }
-
-            let pending = inner
-                .pending_events
+            matched
                 .entry(sub.agent_id.clone())
-                .or_default();
-            pending.push(event.clone());
+                .and_modify(|priority| *priority = (*priority).max(sub.priority))
+                .or_insert(sub.priority);
 
-            // Track one-shot for removal
+            // Every matching one-shot fires, even when another subscription
+            // of the same agent outranks it
             if sub.one_shot {
                 one_shot_to_remove.push(sub.id.clone());
             }
         }
 
+        let mut rows: Vec<(String, String)> = Vec::new();
+        for (agent_id, priority) in matched {
+            let row_id = self.store.as_ref().map(|_| {
+                let row_id = uuid::Uuid::new_v4().to_string();
+                inner.unsaved.insert(row_id.clone(), false);
+                rows.push((row_id.clone(), agent_id.clone()));
+                row_id
+            });
+            Self::queue_pending(&mut inner, agent_id, priority, event.clone(), row_id);
+        }
+
         // Remove one-shot subscriptions that were triggered
-        for sub_id in one_shot_to_remove {
-            inner.subscriptions.remove(&sub_id);
+        for sub_id in &one_shot_to_remove {
+            inner.remove_subscription(sub_id);
         }
 
         // 3. Check wait groups
@@ -209,13 +355,40 @@ impl EventBus {
         ) {
             Self::check_wait_groups_inner(&mut inner, &event.agent_id);
         }
+        drop(inner);
+
+        // Persist after releasing the lock so a slow write doesn't stall
+        // every other publisher and subscriber. A row drained before its
+        // insert landed is marked delivered here instead of by the drain.
+        if let Some(store) = &self.store {
+            for (row_id, agent_id) in &rows {
+                if let Err(e) = store.save_pending(row_id, agent_id, &event).await {
+                    tracing::warn!("[EventBus] Failed to persist pending event: {}", e);
+                }
+                let drained = self.inner.write().await.unsaved.remove(row_id);
+                if drained == Some(true) {
+                    if let Err(e) = store.mark_delivered(std::slice::from_ref(row_id)).await {
+                        tracing::warn!("[EventBus] Failed to mark events delivered: {}", e);
+                    }
+                }
+            }
+        }
+        for sub_id in &one_shot_to_remove {
+            self.forget_subscription(sub_id).await;
+        }
     }
 
     // ─── Agent subscriptions ────────────────────────────────────────────
 
     /// Register an agent event subscription.
     pub async fn subscribe(&self, subscription: EventSubscription) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_subscription(&subscription).await {
+                tracing::warn!("[EventBus] Failed to persist subscription: {}", e);
+            }
+        }
         let mut inner = self.inner.write().await;
+        self.track_expiry(&mut inner, &subscription, Instant::now());
         inner
             .subscriptions
             .insert(subscription.id.clone(), subscription);
@@ -223,14 +396,185 @@ impl EventBus {
 
     /// Remove an agent event subscription.
     pub async fn unsubscribe(&self, subscription_id: &str) -> bool {
+        let removed = self
+            .inner
+            .write()
+            .await
+            .remove_subscription(subscription_id);
+        self.forget_subscription(subscription_id).await;
+        removed
+    }
+
+    fn track_expiry(&self, inner: &mut EventBusInner, sub: &EventSubscription, from: Instant) {
+        match sub.ttl_secs.unwrap_or(self.default_ttl_secs) {
+            0 => inner.expires_at.remove(&sub.id),
+            ttl => inner
+                .expires_at
+                .insert(sub.id.clone(), from + Duration::from_secs(ttl)),
+        };
+    }
+
+    /// Drop every subscription whose TTL has lapsed. Returns how many were
+    /// removed; no events are emitted for them.
+    pub async fn sweep_expired_subscriptions(&self) -> usize {
         let mut inner = self.inner.write().await;
-        inner.subscriptions.remove(subscription_id).is_some()
+        let now = Instant::now();
+        let expired: Vec<String> = inner
+            .expires_at
+            .iter()
+            .filter(|(_, expires_at)| **expires_at <= now)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for sub_id in &expired {
+            inner.remove_subscription(sub_id);
+        }
+        drop(inner);
+        for sub_id in &expired {
+            self.forget_subscription(sub_id).await;
+        }
+        if !expired.is_empty() {
+            tracing::debug!("[EventBus] Swept {} expired subscription(s)", expired.len());
+        }
+        expired.len()
+    }
+
+    /// Sweep expired subscriptions every `interval` until `shutdown` fires.
+    pub fn spawn_subscription_sweeper(
+        &self,
+        interval: Duration,
+        shutdown: CancellationToken,
+    ) -> JoinHandle<()> {
+        let bus = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                bus.sweep_expired_subscriptions().await;
+            }
+        })
+    }
+
+    async fn forget_subscription(&self, subscription_id: &str) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.delete_subscription(subscription_id).await {
+                tracing::warn!("[EventBus] Failed to delete persisted subscription: {}", e);
+            }
+        }
     }
 
-    /// Drain all pending events for an agent.
+    /// Drain all pending events for an agent, highest priority first.
+    ///
+    /// Only the drained rows are marked delivered; events queued after the
+    /// drain stay pending in the store.
     pub async fn drain_pending_events(&self, agent_id: &str) -> Vec<AgentEvent> {
+        let mut delivered_rows = Vec::new();
+        let events = {
+            let mut inner = self.inner.write().await;
+            let drained = inner.pending_events.remove(agent_id).unwrap_or_default();
+            drained
+                .into_iter()
+                .map(|pending| {
+                    if let Some(row_id) = pending.row_id {
+                        match inner.unsaved.get_mut(&row_id) {
+                            Some(drained) => *drained = true,
+                            None => delivered_rows.push(row_id),
+                        }
+                    }
+                    pending.event
+                })
+                .collect()
+        };
+        if let Some(store) = &self.store {
+            if let Err(e) = store.mark_delivered(&delivered_rows).await {
+                tracing::warn!("[EventBus] Failed to mark events delivered: {}", e);
+            }
+        }
+        events
+    }
+
+    /// Insert behind every queued event of equal or higher priority.
+    fn queue_pending(
+        inner: &mut EventBusInner,
+        agent_id: String,
+        priority: i32,
+        event: AgentEvent,
+        row_id: Option<String>,
+    ) {
+        let pending = inner.pending_events.entry(agent_id).or_default();
+        let index = pending.partition_point(|queued| queued.priority >= priority);
+        pending.insert(
+            index,
+            PendingEvent {
+                priority,
+                event,
+                row_id,
+            },
+        );
+    }
+
+    // ─── Persistence ────────────────────────────────────────────────────
+
+    /// Restore persisted subscriptions and re-queue undelivered events.
+    ///
+    /// Intended to run once at startup. Events are re-queued only for agents
+    /// that currently hold a subscription. Their rows stay undelivered until
+    /// [`drain_pending_events`](Self::drain_pending_events) hands them out, so
+    /// a crash before then replays them again. Returns the number of events
+    /// re-queued. A no-op for in-memory buses.
+    pub async fn replay_pending(&self) -> Result<usize, ServerError> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+
+        let subscriptions = store.list_subscriptions().await?;
+        let undelivered = store.list_undelivered().await?;
+
         let mut inner = self.inner.write().await;
-        inner.pending_events.remove(agent_id).unwrap_or_default()
+        // TTLs restart from the replay; time spent down does not count
+        let now = Instant::now();
+        for sub in subscriptions {
+            if !inner.subscriptions.contains_key(&sub.id) {
+                self.track_expiry(&mut inner, &sub, now);
+                inner.subscriptions.insert(sub.id.clone(), sub);
+            }
+        }
+
+        let subscribed: HashSet<String> = inner
+            .subscriptions
+            .values()
+            .map(|sub| sub.agent_id.clone())
+            .collect();
+        let mut replayed = 0;
+        for pending in undelivered {
+            if !subscribed.contains(&pending.agent_id) {
+                continue;
+            }
+            // Priorities are not persisted; recover them from the restored
+            // subscriptions that still match.
+            let priority = inner
+                .subscriptions
+                .values()
+                .filter(|sub| sub.agent_id == pending.agent_id && sub.receives(&pending.event))
+                .map(|sub| sub.priority)
+                .max()
+                .unwrap_or(0);
+            Self::queue_pending(
+                &mut inner,
+                pending.agent_id,
+                priority,
+                pending.event,
+                Some(pending.id),
+            );
+            replayed += 1;
+        }
+        drop(inner);
+
+        if replayed > 0 {
+            tracing::info!("[EventBus] Replayed {} persisted event(s)", replayed);
+        }
+        Ok(replayed)
     }
 
     // ─── Wait groups ────────────────────────────────────────────────────
@@ -323,6 +667,277 @@ impl EventBus {
             "MESSAGE_SENT",
             "REPORT_SUBMITTED",
             "WORKSPACE_UPDATED",
+            // A delegation waiting for a free slot before its agent spawns.
+            "DELEGATION_QUEUED",
+            // A delegated agent stopped before it reported back.
+            "DELEGATION_CANCELLED",
+            // A session create refused because its workspace is at the live session cap.
+            "SESSION_LIMIT_REACHED",
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn subscription(agent_id: &str) -> EventSubscription {
+        EventSubscription {
+            id: format!("sub-{agent_id}"),
+            agent_id: agent_id.to_string(),
+            agent_name: agent_id.to_string(),
+            event_types: vec![AgentEventType::AgentCompleted],
+            exclude_self: true,
+            one_shot: false,
+            wait_group_id: None,
+            priority: 0,
+            workspace_id: "default".to_string(),
+            cross_workspace: false,
+            ttl_secs: None,
+        }
+    }
+
+    fn completed_event(agent_id: &str) -> AgentEvent {
+        AgentEvent {
+            event_type: AgentEventType::AgentCompleted,
+            agent_id: agent_id.to_string(),
+            workspace_id: "default".to_string(),
+            data: serde_json::json!({ "summary": "done" }),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_undelivered_events_after_restart() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+
+        let before_crash = EventBus::with_persistence(EventStore::new(db.clone()));
+        before_crash.subscribe(subscription("routa")).await;
+        before_crash.emit(completed_event("crafter-1")).await;
+        drop(before_crash);
+
+        let restarted = EventBus::with_persistence(EventStore::new(db.clone()));
+        restarted.subscribe(subscription("routa")).await;
+        assert_eq!(restarted.replay_pending().await.unwrap(), 1);
+
+        let events = restarted.drain_pending_events("routa").await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].agent_id, "crafter-1");
+        assert_eq!(events[0].data["summary"], "done");
+
+        let restarted_again = EventBus::with_persistence(EventStore::new(db));
+        assert_eq!(restarted_again.replay_pending().await.unwrap(), 0);
+        assert!(restarted_again
+            .drain_pending_events("routa")
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn replayed_events_survive_a_second_crash_until_drained() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+
+        let before_crash = EventBus::with_persistence(EventStore::new(db.clone()));
+        before_crash.subscribe(subscription("routa")).await;
+        before_crash.emit(completed_event("crafter-1")).await;
+        drop(before_crash);
+
+        let crashed_again = EventBus::with_persistence(EventStore::new(db.clone()));
+        assert_eq!(crashed_again.replay_pending().await.unwrap(), 1);
+        drop(crashed_again);
+
+        let restarted = EventBus::with_persistence(EventStore::new(db));
+        assert_eq!(restarted.replay_pending().await.unwrap(), 1);
+        assert_eq!(restarted.drain_pending_events("routa").await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn drain_marks_only_the_rows_it_hands_out() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let bus = EventBus::with_persistence(EventStore::new(db.clone()));
+        bus.subscribe(subscription("routa")).await;
+
+        bus.emit(completed_event("crafter-1")).await;
+        assert_eq!(bus.drain_pending_events("routa").await.len(), 1);
+        bus.emit(completed_event("crafter-2")).await;
+        drop(bus);
+
+        let restarted = EventBus::with_persistence(EventStore::new(db));
+        assert_eq!(restarted.replay_pending().await.unwrap(), 1);
+        let events = restarted.drain_pending_events("routa").await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].agent_id, "crafter-2");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn interleaved_emit_and_drain_replay_exactly_the_undrained_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        let db = Database::open_with_pool_size(&path.to_string_lossy(), 4).unwrap();
+        let bus = EventBus::with_persistence(EventStore::new(db.clone()));
+        bus.subscribe(subscription("routa")).await;
+
+        let emitter = {
+            let bus = bus.clone();
+            tokio::spawn(async move {
+                for i in 0..50 {
+                    bus.emit(completed_event(&format!("crafter-{i}"))).await;
+                }
+            })
+        };
+        let mut drained = 0;
+        while !emitter.is_finished() {
+            drained += bus.drain_pending_events("routa").await.len();
+            tokio::task::yield_now().await;
+        }
+        emitter.await.unwrap();
+        let undrained = bus.drain_pending_events("routa").await.len();
+        drained += undrained;
+        assert_eq!(drained, 50);
+        // Leave the last few queued, as a crash would.
+        for i in 50..53 {
+            bus.emit(completed_event(&format!("crafter-{i}"))).await;
+        }
+        drop(bus);
+
+        let restarted = EventBus::with_persistence(EventStore::new(db));
+        assert_eq!(restarted.replay_pending().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_deliver_other_workspace_events() {
+        let bus = EventBus::new();
+        bus.subscribe(EventSubscription {
+            event_types: vec![AgentEventType::TaskAssigned],
+            workspace_id: "ws-a".to_string(),
+            ..subscription("routa-a")
+        })
+        .await;
+        bus.subscribe(EventSubscription {
+            event_types: vec![AgentEventType::TaskAssigned],
+            workspace_id: "ws-a".to_string(),
+            cross_workspace: true,
+            ..subscription("observer")
+        })
+        .await;
+
+        bus.emit(AgentEvent {
+            event_type: AgentEventType::TaskAssigned,
+            agent_id: "crafter-b".to_string(),
+            workspace_id: "ws-b".to_string(),
+            data: serde_json::json!({ "taskId": "task-b" }),
+            timestamp: Utc::now(),
+        })
+        .await;
+
+        assert!(bus.drain_pending_events("routa-a").await.is_empty());
+        assert_eq!(bus.drain_pending_events("observer").await.len(), 1);
+    }
+
+    fn event(event_type: AgentEventType, agent_id: &str) -> AgentEvent {
+        AgentEvent {
+            event_type,
+            ..completed_event(agent_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn drains_higher_priority_events_first() {
+        let bus = EventBus::new();
+        bus.subscribe(EventSubscription {
+            id: "sub-messages".to_string(),
+            event_types: vec![AgentEventType::MessageSent],
+            ..subscription("routa")
+        })
+        .await;
+        bus.subscribe(EventSubscription {
+            id: "sub-failures".to_string(),
+            event_types: vec![AgentEventType::TaskFailed],
+            priority: 10,
+            ..subscription("routa")
+        })
+        .await;
+
+        bus.emit(event(AgentEventType::MessageSent, "crafter-1"))
+            .await;
+        bus.emit(event(AgentEventType::TaskFailed, "crafter-1"))
+            .await;
+        bus.emit(event(AgentEventType::MessageSent, "crafter-2"))
+            .await;
+        bus.emit(event(AgentEventType::TaskFailed, "crafter-2"))
+            .await;
+
+        let drained: Vec<_> = bus
+            .drain_pending_events("routa")
+            .await
+            .into_iter()
+            .map(|e| (e.event_type, e.agent_id))
+            .collect();
+        assert_eq!(
+            drained,
+            vec![
+                (AgentEventType::TaskFailed, "crafter-1".to_string()),
+                (AgentEventType::TaskFailed, "crafter-2".to_string()),
+                (AgentEventType::MessageSent, "crafter-1".to_string()),
+                (AgentEventType::MessageSent, "crafter-2".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn outranked_one_shot_still_fires_and_event_is_queued_once() {
+        let bus = EventBus::new();
+        bus.subscribe(EventSubscription {
+            id: "sub-watch".to_string(),
+            priority: 5,
+            ..subscription("routa")
+        })
+        .await;
+        bus.subscribe(EventSubscription {
+            id: "sub-once".to_string(),
+            one_shot: true,
+            ..subscription("routa")
+        })
+        .await;
+        bus.subscribe(EventSubscription {
+            id: "sub-gate-once".to_string(),
+            one_shot: true,
+            priority: 100,
+            ..subscription("gate")
+        })
+        .await;
+
+        bus.emit(completed_event("crafter-1")).await;
+
+        assert_eq!(bus.drain_pending_events("routa").await.len(), 1);
+        assert_eq!(bus.drain_pending_events("gate").await.len(), 1);
+        assert!(!bus.unsubscribe("sub-once").await);
+        assert!(!bus.unsubscribe("sub-gate-once").await);
+        assert!(bus.unsubscribe("sub-watch").await);
+    }
+
+    #[tokio::test]
+    async fn expired_subscription_stops_receiving_events() {
+        let bus = EventBus::new().with_subscription_ttl(0);
+        bus.subscribe(EventSubscription {
+            id: "sub-short".to_string(),
+            ttl_secs: Some(1),
+            ..subscription("routa")
+        })
+        .await;
+        bus.subscribe(subscription("gate")).await;
+
+        bus.emit(completed_event("crafter-1")).await;
+        assert_eq!(bus.drain_pending_events("routa").await.len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        bus.emit(completed_event("crafter-2")).await;
+        assert!(bus.drain_pending_events("routa").await.is_empty());
+        assert_eq!(bus.drain_pending_events("gate").await.len(), 2);
+
+        assert_eq!(bus.sweep_expired_subscriptions().await, 1);
+        assert!(!bus.unsubscribe("sub-short").await);
+        assert!(bus.unsubscribe("sub-gate").await);
+    }
+}
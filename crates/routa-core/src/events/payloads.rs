@@ -0,0 +1,574 @@
+//! Typed `data` payloads for [`AgentEvent`]s.
+//!
+//! Each event type has one payload struct, serialized in `camelCase` so the
+//! wire format matches what subscribers already read. Build events through
+//! the `AgentEvent::*` constructors instead of hand-written `json!` objects.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::{AgentEvent, AgentEventType};
+use crate::models::agent::AgentRole;
+use crate::models::task::TaskStatus;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentCreatedData {
+    pub name: String,
+    pub role: AgentRole,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentErrorData {
+    pub parent_id: Option<String>,
+    pub task_id: Option<String>,
+    pub reason: String,
+    pub task_status: Option<TaskStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskAssignedData {
+    pub task_id: String,
+    pub task_title: String,
+    pub caller_agent_id: Option<String>,
+    /// Set when the task was reassigned away from another agent.
+    pub previous_agent_id: Option<String>,
+    pub provider: Option<String>,
+    pub specialist: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStatusChangedData {
+    pub task_id: String,
+    pub task_title: Option<String>,
+    pub old_status: TaskStatus,
+    pub new_status: TaskStatus,
+    pub reason: Option<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCompletedData {
+    pub task_id: String,
+    pub task_title: String,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskFailedData {
+    pub task_id: String,
+    pub error: String,
+    /// Delegation group whose integration failed.
+    pub group_id: Option<String>,
+    /// Integration strategy name, e.g. `git-merge`.
+    pub strategy: Option<String>,
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub conflicted_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSentData {
+    pub from_agent_id: String,
+    pub to_agent_id: String,
+    pub message_preview: String,
+    /// Live session the message was delivered to, if any.
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportSubmittedData {
+    pub parent_id: String,
+    pub task_id: Option<String>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationQueuedData {
+    pub task_id: String,
+    pub caller_agent_id: String,
+    pub specialist: String,
+    /// Delegations ahead of this one, including it.
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationCancelledData {
+    pub task_id: String,
+    pub parent_agent_id: String,
+    pub session_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLimitReachedData {
+    /// The session that was not started.
+    pub session_id: String,
+    pub limit: usize,
+}
+
+/// `WORKSPACE_UPDATED` payload for kanban board changes (`scope: "kanban"`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KanbanChangedData {
+    pub entity: String,
+    pub action: String,
+    pub resource_id: Option<String>,
+    pub source: String,
+}
+
+/// `WORKSPACE_UPDATED` payload for a workspace rename (`scope: "workspace"`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRenamedData {
+    pub title: String,
+    pub previous_title: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "scope", rename_all = "lowercase")]
+enum ScopedWorkspaceData {
+    Kanban(KanbanChangedData),
+    Workspace(WorkspaceRenamedData),
+}
+
+impl AgentEvent {
+    fn with_data(
+        event_type: AgentEventType,
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: impl Serialize,
+    ) -> Self {
+        Self {
+            event_type,
+            agent_id: agent_id.into(),
+            workspace_id: workspace_id.into(),
+            data: serde_json::to_value(data).unwrap_or_default(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn agent_created(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: AgentCreatedData,
+    ) -> Self {
+        Self::with_data(AgentEventType::AgentCreated, agent_id, workspace_id, data)
+    }
+
+    pub fn agent_error(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: AgentErrorData,
+    ) -> Self {
+        Self::with_data(AgentEventType::AgentError, agent_id, workspace_id, data)
+    }
+
+    pub fn task_assigned(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: TaskAssignedData,
+    ) -> Self {
+        Self::with_data(AgentEventType::TaskAssigned, agent_id, workspace_id, data)
+    }
+
+    pub fn task_status_changed(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: TaskStatusChangedData,
+    ) -> Self {
+        Self::with_data(
+            AgentEventType::TaskStatusChanged,
+            agent_id,
+            workspace_id,
+            data,
+        )
+    }
+
+    pub fn task_completed(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: TaskCompletedData,
+    ) -> Self {
+        Self::with_data(AgentEventType::TaskCompleted, agent_id, workspace_id, data)
+    }
+
+    pub fn task_failed(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: TaskFailedData,
+    ) -> Self {
+        Self::with_data(AgentEventType::TaskFailed, agent_id, workspace_id, data)
+    }
+
+    pub fn message_sent(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: MessageSentData,
+    ) -> Self {
+        Self::with_data(AgentEventType::MessageSent, agent_id, workspace_id, data)
+    }
+
+    pub fn report_submitted(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: ReportSubmittedData,
+    ) -> Self {
+        Self::with_data(
+            AgentEventType::ReportSubmitted,
+            agent_id,
+            workspace_id,
+            data,
+        )
+    }
+
+    pub fn delegation_queued(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: DelegationQueuedData,
+    ) -> Self {
+        Self::with_data(
+            AgentEventType::DelegationQueued,
+            agent_id,
+            workspace_id,
+            data,
+        )
+    }
+
+    pub fn delegation_cancelled(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: DelegationCancelledData,
+    ) -> Self {
+        Self::with_data(
+            AgentEventType::DelegationCancelled,
+            agent_id,
+            workspace_id,
+            data,
+        )
+    }
+
+    pub fn session_limit_reached(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: SessionLimitReachedData,
+    ) -> Self {
+        Self::with_data(
+            AgentEventType::SessionLimitReached,
+            agent_id,
+            workspace_id,
+            data,
+        )
+    }
+
+    pub fn kanban_changed(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: KanbanChangedData,
+    ) -> Self {
+        Self::with_data(
+            AgentEventType::WorkspaceUpdated,
+            agent_id,
+            workspace_id,
+            ScopedWorkspaceData::Kanban(data),
+        )
+    }
+
+    pub fn workspace_renamed(
+        agent_id: impl Into<String>,
+        workspace_id: impl Into<String>,
+        data: WorkspaceRenamedData,
+    ) -> Self {
+        Self::with_data(
+            AgentEventType::WorkspaceUpdated,
+            agent_id,
+            workspace_id,
+            ScopedWorkspaceData::Workspace(data),
+        )
+    }
+
+    /// Check that `data` has the shape of this event type's payload.
+    /// Workspace updates are only checked for scopes with a typed payload.
+    pub fn validate_data(&self) -> Result<(), String> {
+        fn check<T: serde::de::DeserializeOwned>(data: &serde_json::Value) -> Result<(), String> {
+            T::deserialize(data).map(|_| ()).map_err(|e| e.to_string())
+        }
+        let data = &self.data;
+        match self.event_type {
+            AgentEventType::AgentCreated => check::<AgentCreatedData>(data),
+            AgentEventType::AgentError => check::<AgentErrorData>(data),
+            AgentEventType::TaskAssigned => check::<TaskAssignedData>(data),
+            AgentEventType::TaskStatusChanged => check::<TaskStatusChangedData>(data),
+            AgentEventType::TaskCompleted => check::<TaskCompletedData>(data),
+            AgentEventType::TaskFailed => check::<TaskFailedData>(data),
+            AgentEventType::MessageSent => check::<MessageSentData>(data),
+            AgentEventType::ReportSubmitted => check::<ReportSubmittedData>(data),
+            AgentEventType::DelegationQueued => check::<DelegationQueuedData>(data),
+            AgentEventType::DelegationCancelled => check::<DelegationCancelledData>(data),
+            AgentEventType::SessionLimitReached => check::<SessionLimitReachedData>(data),
+            AgentEventType::WorkspaceUpdated
+                if matches!(
+                    data.get("scope").and_then(|scope| scope.as_str()),
+                    Some("kanban" | "workspace")
+                ) =>
+            {
+                check::<ScopedWorkspaceData>(data)
+            }
+            AgentEventType::AgentActivated
+            | AgentEventType::AgentCompleted
+            | AgentEventType::WorkspaceUpdated => Ok(()),
+        }
+        .map_err(|e| format!("Invalid {} data: {e}", self.event_type.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(event: &AgentEvent) -> Vec<&str> {
+        let mut keys: Vec<&str> = event
+            .data
+            .as_object()
+            .expect("data should be an object")
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn constructors_serialize_camel_case_keys() {
+        let events = [
+            (
+                AgentEvent::agent_created(
+                    "a1",
+                    "ws",
+                    AgentCreatedData {
+                        name: "crafter".into(),
+                        role: AgentRole::Crafter,
+                    },
+                ),
+                vec!["name", "role"],
+            ),
+            (
+                AgentEvent::agent_error(
+                    "a1",
+                    "ws",
+                    AgentErrorData {
+                        parent_id: Some("p1".into()),
+                        task_id: Some("t1".into()),
+                        reason: "lost".into(),
+                        task_status: Some(TaskStatus::Blocked),
+                    },
+                ),
+                vec!["parentId", "reason", "taskId", "taskStatus"],
+            ),
+            (
+                AgentEvent::task_assigned(
+                    "a1",
+                    "ws",
+                    TaskAssignedData {
+                        task_id: "t1".into(),
+                        task_title: "Fix".into(),
+                        caller_agent_id: Some("p1".into()),
+                        previous_agent_id: None,
+                        provider: None,
+                        specialist: None,
+                    },
+                ),
+                vec![
+                    "callerAgentId",
+                    "previousAgentId",
+                    "provider",
+                    "specialist",
+                    "taskId",
+                    "taskTitle",
+                ],
+            ),
+            (
+                AgentEvent::task_status_changed(
+                    "a1",
+                    "ws",
+                    TaskStatusChangedData {
+                        task_id: "t1".into(),
+                        task_title: None,
+                        old_status: TaskStatus::Pending,
+                        new_status: TaskStatus::InProgress,
+                        reason: None,
+                        summary: None,
+                    },
+                ),
+                vec![
+                    "newStatus",
+                    "oldStatus",
+                    "reason",
+                    "summary",
+                    "taskId",
+                    "taskTitle",
+                ],
+            ),
+            (
+                AgentEvent::task_completed(
+                    "a1",
+                    "ws",
+                    TaskCompletedData {
+                        task_id: "t1".into(),
+                        task_title: "Fix".into(),
+                        summary: Some("done".into()),
+                    },
+                ),
+                vec!["summary", "taskId", "taskTitle"],
+            ),
+            (
+                AgentEvent::task_failed(
+                    "a1",
+                    "ws",
+                    TaskFailedData {
+                        task_id: "t1".into(),
+                        error: "conflict".into(),
+                        group_id: Some("g1".into()),
+                        strategy: Some("git-merge".into()),
+                        branch: Some("b1".into()),
+                        conflicted_files: vec!["a.rs".into()],
+                    },
+                ),
+                vec![
+                    "branch",
+                    "conflictedFiles",
+                    "error",
+                    "groupId",
+                    "strategy",
+                    "taskId",
+                ],
+            ),
+            (
+                AgentEvent::message_sent(
+                    "a1",
+                    "ws",
+                    MessageSentData {
+                        from_agent_id: "a1".into(),
+                        to_agent_id: "a2".into(),
+                        message_preview: "hi".into(),
+                        session_id: None,
+                    },
+                ),
+                vec!["fromAgentId", "messagePreview", "sessionId", "toAgentId"],
+            ),
+            (
+                AgentEvent::report_submitted(
+                    "a1",
+                    "ws",
+                    ReportSubmittedData {
+                        parent_id: "p1".into(),
+                        task_id: Some("t1".into()),
+                        success: true,
+                    },
+                ),
+                vec!["parentId", "success", "taskId"],
+            ),
+            (
+                AgentEvent::kanban_changed(
+                    "kanban-user",
+                    "ws",
+                    KanbanChangedData {
+                        entity: "task".into(),
+                        action: "moved".into(),
+                        resource_id: Some("t1".into()),
+                        source: "user".into(),
+                    },
+                ),
+                vec!["action", "entity", "resourceId", "scope", "source"],
+            ),
+            (
+                AgentEvent::workspace_renamed(
+                    "rpc",
+                    "ws",
+                    WorkspaceRenamedData {
+                        title: "Checkout".into(),
+                        previous_title: "Untitled".into(),
+                    },
+                ),
+                vec!["previousTitle", "scope", "title"],
+            ),
+            (
+                AgentEvent::delegation_queued(
+                    "p1",
+                    "ws",
+                    DelegationQueuedData {
+                        task_id: "t1".into(),
+                        caller_agent_id: "p1".into(),
+                        specialist: "CRAFTER".into(),
+                        position: 2,
+                    },
+                ),
+                vec!["callerAgentId", "position", "specialist", "taskId"],
+            ),
+            (
+                AgentEvent::delegation_cancelled(
+                    "a1",
+                    "ws",
+                    DelegationCancelledData {
+                        task_id: "t1".into(),
+                        parent_agent_id: "p1".into(),
+                        session_id: "s1".into(),
+                        reason: "parent session closed".into(),
+                    },
+                ),
+                vec!["parentAgentId", "reason", "sessionId", "taskId"],
+            ),
+            (
+                AgentEvent::session_limit_reached(
+                    "system",
+                    "ws",
+                    SessionLimitReachedData {
+                        session_id: "s1".into(),
+                        limit: 16,
+                    },
+                ),
+                vec!["limit", "sessionId"],
+            ),
+        ];
+
+        for (event, expected) in &events {
+            assert_eq!(keys(event), *expected, "{:?}", event.event_type);
+            event.validate_data().unwrap();
+        }
+        assert_eq!(events[3].0.data["newStatus"], "IN_PROGRESS");
+        assert_eq!(events[8].0.data["scope"], "kanban");
+        assert_eq!(events[9].0.data["scope"], "workspace");
+    }
+
+    #[test]
+    fn validate_data_rejects_mismatched_payloads() {
+        let mut event = AgentEvent::task_assigned(
+            "a1",
+            "ws",
+            TaskAssignedData {
+                task_id: "t1".into(),
+                task_title: "Fix".into(),
+                caller_agent_id: None,
+                previous_agent_id: None,
+                provider: None,
+                specialist: None,
+            },
+        );
+        event.data = serde_json::json!({ "task_id": "t1" });
+        assert!(event.validate_data().is_err());
+
+        event.event_type = AgentEventType::WorkspaceUpdated;
+        event.data = serde_json::json!({ "scope": "fitness", "status": "running" });
+        assert!(event.validate_data().is_ok());
+    }
+}
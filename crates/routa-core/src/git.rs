@@ -2481,6 +2481,114 @@ pub fn worktree_remove(repo_path: &str, worktree_path: &str, force: bool) -> Res
     }
 }
 
+/// Create an isolated worktree of `repo_path` checked out on `branch`, under
+/// `~/.routa/worktrees/{repo}/{branch}`. The branch is created from HEAD if
+/// it does not exist yet.
+pub fn create_worktree(repo_path: &str, branch: &str) -> Result<PathBuf, String> {
+    create_worktree_in(&get_worktree_base_dir(), repo_path, branch)
+}
+
+/// [`create_worktree`] with an explicit base directory.
+pub fn create_worktree_in(
+    base_dir: &Path,
+    repo_path: &str,
+    branch: &str,
+) -> Result<PathBuf, String> {
+    let repo_name = Path::new(repo_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "repo".to_string());
+    let worktree_path = base_dir
+        .join(branch_to_safe_dir_name(&repo_name))
+        .join(branch_to_safe_dir_name(branch));
+    if worktree_path.exists() {
+        return Err(format!(
+            "Worktree path already exists: {}",
+            worktree_path.display()
+        ));
+    }
+
+    let create_branch = !branch_exists(repo_path, branch);
+    worktree_add(
+        repo_path,
+        &worktree_path.to_string_lossy(),
+        branch,
+        "HEAD",
+        create_branch,
+    )?;
+    Ok(worktree_path)
+}
+
+/// Remove a worktree created by [`create_worktree`]. Refuses to discard
+/// uncommitted changes; the branch itself is kept.
+pub fn remove_worktree(repo_path: &str, worktree_path: &Path) -> Result<(), String> {
+    worktree_remove(repo_path, &worktree_path.to_string_lossy(), false)?;
+    worktree_prune(repo_path)
+}
+
+/// Stage every change, untracked files included, and commit it. Returns
+/// `None` when there was nothing to commit.
+pub fn commit_all(repo_path: &str, message: &str) -> Result<Option<String>, String> {
+    if get_repo_status(repo_path).clean {
+        return Ok(None);
+    }
+    let output = git_command()
+        .args(["add", "-A"])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    create_commit(repo_path, message, None).map(Some)
+}
+
+/// A merge that stopped on conflicts and was aborted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub message: String,
+    pub conflicted_files: Vec<String>,
+}
+
+/// Merge `branch` into the branch checked out at `repo_path`, always creating a
+/// merge commit. On conflict the merge is aborted so the checkout stays clean.
+pub fn merge_branch(repo_path: &str, branch: &str) -> Result<(), MergeConflict> {
+    let message = format!("Merge branch '{branch}'");
+    let output = git_command()
+        .args(["merge", "--no-ff", "-m", &message, branch])
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| MergeConflict {
+            message: e.to_string(),
+            conflicted_files: Vec::new(),
+        })?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let conflicted_files: Vec<String> =
+        git_output_in_repo(repo_path, &["diff", "--name-only", "--diff-filter=U"])
+            .map(|out| out.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = if stderr.trim().is_empty() {
+        stdout.trim().to_string()
+    } else {
+        stderr.trim().to_string()
+    };
+
+    let _ = git_command()
+        .args(["merge", "--abort"])
+        .current_dir(repo_path)
+        .output();
+
+    Err(MergeConflict {
+        message,
+        conflicted_files,
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WorktreeListEntry {
@@ -2809,4 +2917,55 @@ mod tests {
             Some("feature/test")
         );
     }
+
+    #[test]
+    fn creates_and_removes_worktree_on_local_repo() {
+        let temp = tempdir().unwrap();
+        let repo = temp.path().join("project");
+        fs::create_dir_all(&repo).unwrap();
+        for args in [
+            &["init", "-b", "main"][..],
+            &["config", "user.name", "Test User"],
+            &["config", "user.email", "test@example.com"],
+        ] {
+            assert!(git_command()
+                .args(args)
+                .current_dir(&repo)
+                .output()
+                .unwrap()
+                .status
+                .success());
+        }
+        fs::write(repo.join("README.md"), "hello\n").unwrap();
+        for args in [&["add", "README.md"][..], &["commit", "-m", "init"]] {
+            assert!(git_command()
+                .args(args)
+                .current_dir(&repo)
+                .output()
+                .unwrap()
+                .status
+                .success());
+        }
+        let repo_path = repo.to_str().unwrap();
+        let base = temp.path().join("worktrees");
+
+        let worktree = create_worktree_in(&base, repo_path, "routa/crafter-1").unwrap();
+
+        assert_eq!(worktree, base.join("project").join("routa-crafter-1"));
+        assert!(worktree.join("README.md").is_file());
+        assert!(has_local_branch(repo_path, "routa/crafter-1"));
+        assert_eq!(
+            get_current_branch(worktree.to_str().unwrap()).as_deref(),
+            Some("routa/crafter-1")
+        );
+        assert!(create_worktree_in(&base, repo_path, "routa/crafter-1").is_err());
+
+        remove_worktree(repo_path, &worktree).unwrap();
+
+        assert!(!worktree.exists());
+        assert!(worktree_list(repo_path)
+            .iter()
+            .all(|entry| entry.branch != "routa/crafter-1"));
+        assert!(has_local_branch(repo_path, "routa/crafter-1"));
+    }
 }
@@ -2,6 +2,12 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Agent metadata key holding the comma-separated ACP session ids it ran in.
+pub const AGENT_SESSION_IDS_METADATA_KEY: &str = "sessionIds";
+
+/// Agent metadata key holding how many delegations deep the agent was spawned.
+pub const AGENT_DELEGATION_DEPTH_METADATA_KEY: &str = "delegationDepth";
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum AgentRole {
     #[serde(rename = "ROUTA")]
@@ -36,7 +42,7 @@ impl AgentRole {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ModelTier {
     #[serde(rename = "SMART")]
     Smart,
@@ -81,6 +87,18 @@ pub enum AgentStatus {
 }
 
 impl AgentStatus {
+    /// Every variant, in declaration order.
+    pub fn all() -> impl Iterator<Item = Self> {
+        [
+            Self::Pending,
+            Self::Active,
+            Self::Completed,
+            Self::Error,
+            Self::Cancelled,
+        ]
+        .into_iter()
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Pending => "PENDING",
@@ -104,6 +122,12 @@ impl AgentStatus {
     }
 }
 
+impl std::fmt::Display for AgentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Agent {
@@ -145,4 +169,70 @@ impl Agent {
             metadata: metadata.unwrap_or_default(),
         }
     }
+
+    /// ACP session ids this agent has run in, oldest first.
+    pub fn session_ids(&self) -> Vec<String> {
+        self.metadata
+            .get(AGENT_SESSION_IDS_METADATA_KEY)
+            .map(|ids| {
+                ids.split(',')
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Remember that the agent ran in `session_id`. Agents can be respawned
+    /// into new sessions over their lifetime, so ids accumulate.
+    pub fn record_session_id(&mut self, session_id: &str) {
+        let mut ids = self.session_ids();
+        if ids.iter().any(|id| id == session_id) {
+            return;
+        }
+        ids.push(session_id.to_string());
+        self.metadata
+            .insert(AGENT_SESSION_IDS_METADATA_KEY.to_string(), ids.join(","));
+    }
+
+    /// Delegations between the root agent and this one; 0 for agents that
+    /// were not spawned by delegation.
+    pub fn delegation_depth(&self) -> u32 {
+        self.metadata
+            .get(AGENT_DELEGATION_DEPTH_METADATA_KEY)
+            .and_then(|depth| depth.parse().ok())
+            .unwrap_or(0)
+    }
+
+    pub fn set_delegation_depth(&mut self, depth: u32) {
+        self.metadata.insert(
+            AGENT_DELEGATION_DEPTH_METADATA_KEY.to_string(),
+            depth.to_string(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agent_status_round_trips_through_str_and_serde() {
+        for variant in AgentStatus::all() {
+            assert_eq!(
+                AgentStatus::from_str(variant.as_str()),
+                Some(variant.clone())
+            );
+            assert_eq!(variant.to_string(), variant.as_str());
+            let json = serde_json::to_value(&variant).unwrap();
+            assert_eq!(
+                json,
+                serde_json::Value::String(variant.as_str().to_string())
+            );
+            assert_eq!(
+                serde_json::from_value::<AgentStatus>(json).unwrap(),
+                variant
+            );
+        }
+    }
 }
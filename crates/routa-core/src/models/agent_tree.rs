@@ -0,0 +1,214 @@
+//! Agent delegation hierarchy assembled from flat `parent_id` links.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use super::agent::{Agent, AgentRole, AgentStatus};
+use super::task::{Task, TaskStatus};
+
+/// Task summary attached to a tree node.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentTreeTask {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+}
+
+/// One agent in the delegation tree, with its delegated children.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentTreeNode {
+    pub id: String,
+    pub name: String,
+    pub role: AgentRole,
+    pub status: AgentStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_task: Option<AgentTreeTask>,
+    pub children: Vec<AgentTreeNode>,
+}
+
+/// Assemble the delegation forest for one workspace from its agents and tasks.
+///
+/// Roots are agents without a parent in `agents` (normally ROUTA coordinators,
+/// which sort first). A node's current task is its assigned IN_PROGRESS task,
+/// falling back to the most recently updated task assigned to it. Agents caught
+/// in a `parent_id` cycle are never dropped: the cycle is broken at the first
+/// revisited edge and the remainder is attached as an extra root.
+pub fn build_agent_tree(mut agents: Vec<Agent>, tasks: &[Task]) -> Vec<AgentTreeNode> {
+    agents.sort_by_key(|a| a.created_at);
+
+    let mut current_tasks: HashMap<&str, &Task> = HashMap::new();
+    for task in tasks {
+        let Some(agent_id) = task.assigned_to.as_deref() else {
+            continue;
+        };
+        let replace = match current_tasks.get(agent_id) {
+            None => true,
+            Some(existing) => {
+                let existing_active = existing.status == TaskStatus::InProgress;
+                let candidate_active = task.status == TaskStatus::InProgress;
+                (candidate_active && !existing_active)
+                    || (candidate_active == existing_active
+                        && task.updated_at > existing.updated_at)
+            }
+        };
+        if replace {
+            current_tasks.insert(agent_id, task);
+        }
+    }
+
+    let ids: HashSet<&str> = agents.iter().map(|a| a.id.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&Agent>> = HashMap::new();
+    let mut roots: Vec<&Agent> = Vec::new();
+    for agent in &agents {
+        match agent.parent_id.as_deref() {
+            Some(parent_id) if ids.contains(parent_id) && parent_id != agent.id => {
+                children.entry(parent_id).or_default().push(agent);
+            }
+            _ => roots.push(agent),
+        }
+    }
+    roots.sort_by_key(|a| a.role != AgentRole::Routa);
+
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut tree: Vec<AgentTreeNode> = roots
+        .into_iter()
+        .filter_map(|root| build_node(root, &children, &current_tasks, &mut visited))
+        .collect();
+
+    for agent in &agents {
+        if !visited.contains(agent.id.as_str()) {
+            tracing::warn!(
+                "[AgentTree] Breaking parent_id cycle at agent {} in workspace {}",
+                agent.id,
+                agent.workspace_id
+            );
+            if let Some(node) = build_node(agent, &children, &current_tasks, &mut visited) {
+                tree.push(node);
+            }
+        }
+    }
+
+    tree
+}
+
+fn build_node<'a>(
+    agent: &'a Agent,
+    children: &HashMap<&str, Vec<&'a Agent>>,
+    current_tasks: &HashMap<&str, &Task>,
+    visited: &mut HashSet<&'a str>,
+) -> Option<AgentTreeNode> {
+    if !visited.insert(agent.id.as_str()) {
+        return None;
+    }
+
+    let child_nodes = children
+        .get(agent.id.as_str())
+        .map(|kids| {
+            kids.iter()
+                .filter_map(|child| build_node(child, children, current_tasks, visited))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(AgentTreeNode {
+        id: agent.id.clone(),
+        name: agent.name.clone(),
+        role: agent.role.clone(),
+        status: agent.status.clone(),
+        current_task: current_tasks
+            .get(agent.id.as_str())
+            .map(|task| AgentTreeTask {
+                id: task.id.clone(),
+                title: task.title.clone(),
+                status: task.status.clone(),
+            }),
+        children: child_nodes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(id: &str, role: AgentRole, parent_id: Option<&str>) -> Agent {
+        Agent::new(
+            id.to_string(),
+            id.to_string(),
+            role,
+            "default".to_string(),
+            parent_id.map(str::to_string),
+            None,
+            None,
+        )
+    }
+
+    fn task(id: &str, assigned_to: &str, status: TaskStatus) -> Task {
+        let mut task = Task::new(
+            id.to_string(),
+            format!("Task {id}"),
+            "objective".to_string(),
+            "default".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        task.assigned_to = Some(assigned_to.to_string());
+        task.status = status;
+        task
+    }
+
+    #[test]
+    fn builds_two_level_delegation_tree() {
+        let agents = vec![
+            agent("crafter-1", AgentRole::Crafter, Some("routa")),
+            agent("gate-1", AgentRole::Gate, Some("routa")),
+            agent("routa", AgentRole::Routa, None),
+            agent("crafter-1a", AgentRole::Crafter, Some("crafter-1")),
+        ];
+        let tasks = vec![
+            task("t-done", "crafter-1", TaskStatus::Completed),
+            task("t-active", "crafter-1", TaskStatus::InProgress),
+        ];
+
+        let tree = build_agent_tree(agents, &tasks);
+
+        assert_eq!(tree.len(), 1);
+        let root = &tree[0];
+        assert_eq!(root.id, "routa");
+        assert!(root.current_task.is_none());
+        let child_ids: Vec<&str> = root.children.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(child_ids, vec!["crafter-1", "gate-1"]);
+
+        let crafter = &root.children[0];
+        assert_eq!(
+            crafter.current_task.as_ref().map(|t| t.id.as_str()),
+            Some("t-active")
+        );
+        assert_eq!(crafter.children.len(), 1);
+        assert_eq!(crafter.children[0].id, "crafter-1a");
+        assert!(crafter.children[0].children.is_empty());
+        assert!(root.children[1].children.is_empty());
+    }
+
+    #[test]
+    fn breaks_parent_cycles_without_dropping_agents() {
+        let agents = vec![
+            agent("a", AgentRole::Crafter, Some("b")),
+            agent("b", AgentRole::Crafter, Some("a")),
+        ];
+
+        let tree = build_agent_tree(agents, &[]);
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].id, "a");
+        assert_eq!(tree[0].children.len(), 1);
+        assert_eq!(tree[0].children[0].id, "b");
+        assert!(tree[0].children[0].children.is_empty());
+    }
+}
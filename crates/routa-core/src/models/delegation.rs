@@ -0,0 +1,31 @@
+//! Persisted parent → child delegation links owned by `RoutaOrchestrator`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::agent::AgentRole;
+
+/// A spawned child agent and the parent it must wake when it finishes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationRecord {
+    pub child_agent_id: String,
+    pub child_session_id: String,
+    pub parent_agent_id: String,
+    pub parent_session_id: String,
+    pub task_id: String,
+    pub role: AgentRole,
+    pub provider: String,
+    /// `after_all` delegation group the child belongs to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    /// The child reported back but its group is still waiting on siblings.
+    pub completed: bool,
+    /// Repository the child's isolated worktree was created from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo_path: Option<String>,
+    /// Isolated git worktree the child runs in (`isolate: true`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_path: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -73,3 +73,47 @@ impl Message {
         }
     }
 }
+
+/// Placeholder stored in place of redacted message content.
+pub const REDACTED_MESSAGE_PLACEHOLDER: &str = "[redacted]";
+
+/// Moderation action recorded against a conversation message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MessageAuditAction {
+    #[serde(rename = "REDACT")]
+    Redact,
+    #[serde(rename = "DELETE")]
+    Delete,
+}
+
+impl MessageAuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Redact => "REDACT",
+            Self::Delete => "DELETE",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "REDACT" => Some(Self::Redact),
+            "DELETE" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// Audit record for a redaction or deletion of a conversation message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageAuditEntry {
+    pub id: String,
+    pub message_id: String,
+    pub agent_id: String,
+    pub action: MessageAuditAction,
+    pub actor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,30 +1,38 @@
 pub mod agent;
+pub mod agent_tree;
 pub mod artifact;
 pub mod canvas_artifact;
 pub mod canvas_generation_contract;
 pub mod canvas_sdk_resource_contract;
 pub mod codebase;
+pub mod delegation;
 pub mod feature_tree_spec_resource_contract;
 pub mod kanban;
 pub mod kanban_config;
 pub mod message;
 pub mod note;
 pub mod schedule;
+pub mod skill;
 pub mod task;
+pub mod tool_audit;
 pub mod workspace;
 pub mod worktree;
 
 pub use agent::*;
+pub use agent_tree::*;
 pub use artifact::*;
 pub use canvas_artifact::*;
 pub use canvas_generation_contract::*;
 pub use canvas_sdk_resource_contract::*;
 pub use codebase::*;
+pub use delegation::*;
 pub use feature_tree_spec_resource_contract::*;
 pub use kanban::*;
 pub use message::*;
 pub use note::*;
 pub use schedule::*;
+pub use skill::*;
 pub use task::*;
+pub use tool_audit::*;
 pub use workspace::*;
 pub use worktree::*;
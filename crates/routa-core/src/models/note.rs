@@ -1,10 +1,20 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
 use super::task::TaskStatus;
 
 pub const SPEC_NOTE_ID: &str = "spec";
+pub const TASK_NOTE_ID: &str = "task";
+
+/// Starter content for a workspace spec created on first read.
+pub const DEFAULT_SPEC_CONTENT: &str = "# Goal\n\n\
+    _What should this workspace deliver, and why?_\n\n\
+    ## Acceptance Criteria\n\n\
+    - _Observable outcome that proves the goal is met_\n\n\
+    ## Tasks\n\n\
+    - _Break the goal into delegable tasks_\n";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -15,6 +25,11 @@ pub enum NoteType {
 }
 
 impl NoteType {
+    /// Every variant, in declaration order.
+    pub fn all() -> impl Iterator<Item = Self> {
+        [Self::Spec, Self::Task, Self::General].into_iter()
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Spec => "spec",
@@ -23,6 +38,16 @@ impl NoteType {
         }
     }
 
+    /// Fixed id of the workspace's only note of this type; `None` for types
+    /// a workspace may hold any number of.
+    pub fn singleton_id(&self) -> Option<&'static str> {
+        match self {
+            Self::Spec => Some(SPEC_NOTE_ID),
+            Self::Task => Some(TASK_NOTE_ID),
+            Self::General => None,
+        }
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s {
@@ -33,6 +58,12 @@ impl NoteType {
     }
 }
 
+impl std::fmt::Display for NoteType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NoteMetadata {
@@ -63,6 +94,17 @@ impl Default for NoteMetadata {
     }
 }
 
+/// A past revision of a note's content, recorded on each change.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteVersion {
+    pub version: i64,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_agent_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Note {
@@ -121,11 +163,21 @@ impl Note {
         }
     }
 
+    /// SHA-256 of `content` with whitespace runs collapsed and the ends
+    /// trimmed, so notes differing only in formatting hash the same.
+    pub fn content_hash(content: &str) -> String {
+        let normalized = content.split_whitespace().collect::<Vec<_>>().join(" ");
+        Sha256::digest(normalized.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
     pub fn new_spec(workspace_id: String) -> Self {
         Self::new(
             SPEC_NOTE_ID.to_string(),
             "Spec".to_string(),
-            String::new(),
+            DEFAULT_SPEC_CONTENT.to_string(),
             workspace_id,
             Some(NoteMetadata {
                 note_type: NoteType::Spec,
@@ -134,3 +186,22 @@ impl Note {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn note_type_round_trips_through_str_and_serde() {
+        for variant in NoteType::all() {
+            assert_eq!(NoteType::from_str(variant.as_str()), variant.clone());
+            assert_eq!(variant.to_string(), variant.as_str());
+            let json = serde_json::to_value(&variant).unwrap();
+            assert_eq!(
+                json,
+                serde_json::Value::String(variant.as_str().to_string())
+            );
+            assert_eq!(serde_json::from_value::<NoteType>(json).unwrap(), variant);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A skill installed through the catalog, clone, upload or workspace paths,
+/// with its install and agent-use counters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillRecord {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Where the skill came from, e.g. `owner/repo`, a clone URL or `upload`.
+    pub source: String,
+    pub catalog_type: String,
+    pub installs: i64,
+    /// Times an agent pulled the skill's instructions via `skills.use`.
+    pub uses: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SkillRecord {
+    /// Skills are keyed by name, matching the directory they install into.
+    pub fn new(name: String, description: String, source: String, catalog_type: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: name.clone(),
+            name,
+            description,
+            source,
+            catalog_type,
+            installs: 0,
+            uses: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
@@ -219,6 +219,20 @@ pub enum TaskStatus {
 }
 
 impl TaskStatus {
+    /// Every variant, in declaration order.
+    pub fn all() -> impl Iterator<Item = Self> {
+        [
+            Self::Pending,
+            Self::InProgress,
+            Self::ReviewRequired,
+            Self::Completed,
+            Self::NeedsFix,
+            Self::Blocked,
+            Self::Cancelled,
+        ]
+        .into_iter()
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Pending => "PENDING",
@@ -246,6 +260,12 @@ impl TaskStatus {
     }
 }
 
+impl std::fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskCreationSource {
@@ -277,6 +297,56 @@ impl TaskCreationSource {
     }
 }
 
+/// Checklist state of a single acceptance criterion.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CriterionStatus {
+    #[default]
+    Pending,
+    Verified,
+    Failed,
+}
+
+impl CriterionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Verified => "verified",
+            Self::Failed => "failed",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "verified" => Some(Self::Verified),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// An acceptance criterion paired with its checklist state.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AcceptanceCriterion {
+    pub text: String,
+    pub status: CriterionStatus,
+}
+
+/// Acceptance criterion as accepted on input: either a plain string or a
+/// `{ "text", "status" }` object.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AcceptanceCriterionInput {
+    Text(String),
+    Item {
+        text: String,
+        #[serde(default)]
+        status: CriterionStatus,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum VerificationVerdict {
     #[serde(rename = "APPROVED")]
@@ -426,6 +496,44 @@ pub struct TaskEvidenceSummary {
     pub runs: TaskRunSummary,
 }
 
+/// Lifecycle action recorded in a task's audit log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TaskAuditAction {
+    #[serde(rename = "REOPEN")]
+    Reopen,
+}
+
+impl TaskAuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Reopen => "REOPEN",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "REOPEN" => Some(Self::Reopen),
+            _ => None,
+        }
+    }
+}
+
+/// Audit record for a manual lifecycle change to a task, such as a reopen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskAuditEntry {
+    pub id: String,
+    pub task_id: String,
+    pub action: TaskAuditAction,
+    /// Agent (or user) that performed the action.
+    pub actor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub previous_status: TaskStatus,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Task {
@@ -438,6 +546,10 @@ pub struct Task {
     pub scope: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub acceptance_criteria: Option<Vec<String>>,
+    /// Checklist state parallel to `acceptance_criteria`. Missing entries
+    /// are pending.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub acceptance_criteria_status: Vec<CriterionStatus>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verification_commands: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -542,6 +654,7 @@ impl Task {
             comment: None,
             scope,
             acceptance_criteria,
+            acceptance_criteria_status: Vec::new(),
             verification_commands,
             test_cases,
             assigned_to: None,
@@ -582,6 +695,76 @@ impl Task {
             verification_report: None,
         }
     }
+
+    /// Whether tasks that depend on this one may start.
+    pub fn satisfies_dependency(&self) -> bool {
+        self.status == TaskStatus::Completed
+    }
+
+    /// Acceptance criteria with their checklist state.
+    pub fn acceptance_checklist(&self) -> Vec<AcceptanceCriterion> {
+        self.acceptance_criteria
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, text)| AcceptanceCriterion {
+                text: text.clone(),
+                status: self
+                    .acceptance_criteria_status
+                    .get(index)
+                    .copied()
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Replace the acceptance criteria. Checklist state is kept only when
+    /// the criteria are unchanged.
+    pub fn replace_acceptance_criteria(&mut self, criteria: Vec<String>) {
+        if self.acceptance_criteria.as_ref() != Some(&criteria) {
+            self.acceptance_criteria_status.clear();
+        }
+        self.acceptance_criteria = Some(criteria);
+    }
+
+    /// Replace the acceptance criteria from input that may carry a status
+    /// per item.
+    pub fn set_acceptance_criteria(&mut self, criteria: Vec<AcceptanceCriterionInput>) {
+        let (texts, statuses): (Vec<String>, Vec<CriterionStatus>) = criteria
+            .into_iter()
+            .map(|criterion| match criterion {
+                AcceptanceCriterionInput::Text(text) => (text, CriterionStatus::Pending),
+                AcceptanceCriterionInput::Item { text, status } => (text, status),
+            })
+            .unzip();
+        self.acceptance_criteria = Some(texts);
+        self.acceptance_criteria_status = if statuses
+            .iter()
+            .all(|status| *status == CriterionStatus::Pending)
+        {
+            Vec::new()
+        } else {
+            statuses
+        };
+    }
+
+    /// Set the checklist state of the criterion at `index`.
+    pub fn set_criterion_status(
+        &mut self,
+        index: usize,
+        status: CriterionStatus,
+    ) -> Result<(), String> {
+        let count = self.acceptance_criteria.as_ref().map_or(0, Vec::len);
+        if index >= count {
+            return Err(format!(
+                "Acceptance criterion index {index} is out of range ({count} criteria)"
+            ));
+        }
+        self.acceptance_criteria_status
+            .resize(count, CriterionStatus::Pending);
+        self.acceptance_criteria_status[index] = status;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -1104,4 +1287,21 @@ story:
 
         assert!(checks.dependencies_declared);
     }
+
+    #[test]
+    fn task_status_round_trips_through_str_and_serde() {
+        for variant in TaskStatus::all() {
+            assert_eq!(
+                TaskStatus::from_str(variant.as_str()),
+                Some(variant.clone())
+            );
+            assert_eq!(variant.to_string(), variant.as_str());
+            let json = serde_json::to_value(&variant).unwrap();
+            assert_eq!(
+                json,
+                serde_json::Value::String(variant.as_str().to_string())
+            );
+            assert_eq!(serde_json::from_value::<TaskStatus>(json).unwrap(), variant);
+        }
+    }
 }
@@ -0,0 +1,134 @@
+//! Audit trail of agent tool executions.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest serialized argument payload kept per audit row, in bytes.
+pub const TOOL_AUDIT_MAX_ARGS_BYTES: usize = 4096;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// One tool call made through Routa's coordination tools.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAuditEntry {
+    pub id: String,
+    pub tool_name: String,
+    /// JSON-encoded arguments with secrets redacted, cut to
+    /// [`TOOL_AUDIT_MAX_ARGS_BYTES`].
+    pub args: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+    pub success: bool,
+    pub duration_ms: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ToolAuditEntry {
+    /// Build an entry for a call to `tool_name`, taking the caller from the
+    /// `agentId`/`sessionId`/`workspaceId` arguments when present.
+    pub fn new(tool_name: &str, args: &serde_json::Value, success: bool, duration_ms: i64) -> Self {
+        let arg = |key: &str| {
+            args.get(key)
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_string())
+        };
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool_name: tool_name.to_string(),
+            args: sanitize_tool_args(args),
+            agent_id: arg("agentId").or_else(|| arg("callerAgentId")),
+            session_id: arg("sessionId"),
+            workspace_id: arg("workspaceId"),
+            success,
+            duration_ms,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Serialize `args` for the audit log with secret-looking values redacted and
+/// the result cut to [`TOOL_AUDIT_MAX_ARGS_BYTES`].
+pub fn sanitize_tool_args(args: &serde_json::Value) -> String {
+    let mut args = args.clone();
+    redact_secrets(&mut args);
+    let text = serde_json::to_string(&args).unwrap_or_default();
+    if text.len() <= TOOL_AUDIT_MAX_ARGS_BYTES {
+        return text;
+    }
+    let mut end = TOOL_AUDIT_MAX_ARGS_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &text[..end])
+}
+
+fn redact_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *value = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    [
+        "token",
+        "secret",
+        "password",
+        "apikey",
+        "api_key",
+        "credential",
+        "authorization",
+    ]
+    .iter()
+    .any(|needle| key.contains(needle))
+}
+
+/// Optional filters for listing tool audit rows. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct ToolAuditFilter {
+    pub tool_name: Option<String>,
+    pub agent_id: Option<String>,
+    pub session_id: Option<String>,
+    pub workspace_id: Option<String>,
+    pub success: Option<bool>,
+    pub limit: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_redacts_nested_secrets_and_truncates() {
+        let args = serde_json::json!({
+            "title": "Deploy",
+            "env": { "API_TOKEN": "abc", "region": "eu" },
+            "apiKey": "sk-1",
+        });
+        let text = sanitize_tool_args(&args);
+        assert!(!text.contains("abc"));
+        assert!(!text.contains("sk-1"));
+        assert!(text.contains("\"region\":\"eu\""));
+
+        let big = serde_json::json!({ "content": "é".repeat(TOOL_AUDIT_MAX_ARGS_BYTES) });
+        let text = sanitize_tool_args(&big);
+        assert!(text.len() <= TOOL_AUDIT_MAX_ARGS_BYTES + '…'.len_utf8());
+        assert!(text.ends_with('…'));
+    }
+}
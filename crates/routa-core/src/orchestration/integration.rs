@@ -0,0 +1,131 @@
+//! Integration of isolated `after_all` delegations.
+//!
+//! Children delegated with `isolate` work on their own branch in a separate
+//! worktree. Once every child of a group has succeeded, the orchestrator hands
+//! those worktrees to an [`IntegrationStrategy`] before waking the parent, so
+//! the parent reviews one integrated tree instead of N branches.
+
+use crate::git;
+
+/// A successful child whose work lives on its own branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IsolatedChild {
+    pub agent_id: String,
+    pub task_id: String,
+    pub repo_path: String,
+    pub worktree_path: String,
+    pub branch: String,
+}
+
+/// Why integrating one child's branch failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrationConflict {
+    pub agent_id: String,
+    pub task_id: String,
+    pub branch: String,
+    pub message: String,
+    pub conflicted_files: Vec<String>,
+}
+
+/// Step run between "all isolated children succeeded" and waking the parent.
+///
+/// Implementations may merge branches directly ([`GitMergeStrategy`]) or hand
+/// the branches to something else, such as an integrator agent.
+pub trait IntegrationStrategy: Send + Sync {
+    /// Short identifier used in logs and events.
+    fn name(&self) -> &str;
+
+    /// Integrate `children` in delegation order, returning the branches that
+    /// were integrated. Stops at the first conflict.
+    fn integrate(&self, children: &[IsolatedChild]) -> Result<Vec<String>, IntegrationConflict>;
+}
+
+/// Commit whatever each child left uncommitted in its worktree, then
+/// `git merge --no-ff` its branch into the branch checked out in the repo.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GitMergeStrategy;
+
+impl IntegrationStrategy for GitMergeStrategy {
+    fn name(&self) -> &str {
+        "git-merge"
+    }
+
+    fn integrate(&self, children: &[IsolatedChild]) -> Result<Vec<String>, IntegrationConflict> {
+        let mut merged = Vec::new();
+        for child in children {
+            let conflict = |message: String, conflicted_files: Vec<String>| IntegrationConflict {
+                agent_id: child.agent_id.clone(),
+                task_id: child.task_id.clone(),
+                branch: child.branch.clone(),
+                message,
+                conflicted_files,
+            };
+
+            git::commit_all(
+                &child.worktree_path,
+                &format!("routa: work from agent {}", child.agent_id),
+            )
+            .map_err(|e| conflict(e, Vec::new()))?;
+            git::merge_branch(&child.repo_path, &child.branch)
+                .map_err(|e| conflict(e.message, e.conflicted_files))?;
+            merged.push(child.branch.clone());
+        }
+        Ok(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = git::git_command()
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed: {output:?}");
+    }
+
+    #[test]
+    fn git_merge_strategy_merges_two_worktree_branches() {
+        let temp = tempdir().unwrap();
+        let repo = temp.path().join("project");
+        fs::create_dir_all(&repo).unwrap();
+        git(&repo, &["init", "-b", "main"]);
+        git(&repo, &["config", "user.name", "Test User"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        fs::write(repo.join("README.md"), "hello\n").unwrap();
+        git(&repo, &["add", "README.md"]);
+        git(&repo, &["commit", "-m", "init"]);
+        let repo_path = repo.to_str().unwrap();
+        let base = temp.path().join("worktrees");
+
+        let children: Vec<IsolatedChild> = ["a", "b"]
+            .iter()
+            .map(|name| {
+                let branch = format!("routa/crafter-{name}");
+                let worktree = git::create_worktree_in(&base, repo_path, &branch).unwrap();
+                fs::write(worktree.join(format!("{name}.txt")), name).unwrap();
+                IsolatedChild {
+                    agent_id: format!("crafter-{name}"),
+                    task_id: format!("task-{name}"),
+                    repo_path: repo_path.to_string(),
+                    worktree_path: worktree.to_string_lossy().to_string(),
+                    branch,
+                }
+            })
+            .collect();
+
+        let merged = GitMergeStrategy.integrate(&children).unwrap();
+
+        assert_eq!(merged, vec!["routa/crafter-a", "routa/crafter-b"]);
+        assert_eq!(fs::read_to_string(repo.join("a.txt")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(repo.join("b.txt")).unwrap(), "b");
+        assert_eq!(git::get_current_branch(repo_path).as_deref(), Some("main"));
+        assert!(git::get_repo_status(repo_path).clean);
+    }
+}
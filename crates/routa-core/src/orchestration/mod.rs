@@ -8,8 +8,25 @@
 //!   3. Sends the task as the initial prompt
 //!   4. Subscribes for completion events
 //!   5. When the child reports back, wakes the parent agent
+//!
+//! With a [`DelegationStore`] attached, delegations are mirrored to SQLite so a
+//! restarted process can reload them (see [`RoutaOrchestrator::restore_delegations`]).
+//!
+//! When an `after_all` group of isolated children all succeed, their branches
+//! are integrated (see [`IntegrationStrategy`]) before the parent is woken.
+//!
+//! A delegation into a workspace that is at its live session cap is queued
+//! (announced with `DELEGATION_QUEUED`) and started once a slot frees up.
+
+mod integration;
+mod specialist_resolver;
 
-use std::collections::{HashMap, HashSet};
+pub use integration::{GitMergeStrategy, IntegrationConflict, IntegrationStrategy, IsolatedChild};
+pub use specialist_resolver::SpecialistResolver;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 
 use chrono::Utc;
@@ -18,12 +35,17 @@ use tokio::sync::RwLock;
 
 use crate::acp::AcpManager;
 use crate::error::ServerError;
-use crate::events::{AgentEvent, AgentEventType, EventBus};
+use crate::events::{
+    AgentErrorData, AgentEvent, DelegationCancelledData, DelegationQueuedData, EventBus,
+    TaskAssignedData, TaskFailedData,
+};
 use crate::models::agent::{AgentRole, AgentStatus, ModelTier};
 use crate::models::build_feature_tree_spec_prompt_section;
+use crate::models::delegation::DelegationRecord;
 use crate::models::task::TaskStatus;
-use crate::store::{AgentStore, TaskStore};
-use crate::tools::{CompletionReport, ToolResult};
+use crate::store::{AgentStore, DelegationStore, TaskStore};
+use crate::tools::{CompletionReport, LiveSessionDelivery, SessionLiveness, ToolResult};
+use crate::trace::{Contributor, TraceEventType, TraceRecord, TraceWriter};
 use crate::workflow::specialist::{SpecialistDef, SpecialistLoader};
 
 // ─── Specialist Configuration ─────────────────────────────────────────────
@@ -204,20 +226,10 @@ impl SpecialistConfig {
         values
     }
 
+    /// Resolve a role name or specialist ID against the default specialist
+    /// directories. See [`SpecialistResolver`].
     pub fn resolve(input: &str) -> Option<Self> {
-        if let Some(role) = AgentRole::from_str(input) {
-            return Self::by_role(&role);
-        }
-
-        let target = input.to_lowercase();
-
-        if let Some(alias) = Self::by_id(&target) {
-            return Some(alias);
-        }
-
-        Self::list_available()
-            .into_iter()
-            .find(|specialist| specialist.id == target)
+        SpecialistResolver::default().resolve(input)
     }
 }
 
@@ -297,6 +309,9 @@ pub struct DelegateWithSpawnParams {
     /// ACP provider to use for the child
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider: Option<String>,
+    /// Model tier for the child; the specialist's default tier when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_tier: Option<ModelTier>,
     /// Working directory for the child agent
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cwd: Option<String>,
@@ -306,6 +321,24 @@ pub struct DelegateWithSpawnParams {
     /// Wait mode: "immediate" or "after_all"
     #[serde(default = "default_wait_mode")]
     pub wait_mode: String,
+    /// Run the child in its own git worktree of `cwd` so parallel agents
+    /// don't share a working tree.
+    #[serde(default)]
+    pub isolate: bool,
+    /// Delegation depth of the caller (root agents are 0). Read from the
+    /// caller's agent record when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caller_depth: Option<u32>,
+}
+
+impl DelegateWithSpawnParams {
+    /// The tier the child runs at: the delegation's own tier, else the
+    /// specialist's default.
+    fn model_tier_for(&self, specialist: &SpecialistConfig) -> ModelTier {
+        self.model_tier
+            .clone()
+            .unwrap_or_else(|| specialist.default_model_tier.clone())
+    }
 }
 
 fn default_wait_mode() -> String {
@@ -321,6 +354,12 @@ pub struct OrchestratorConfig {
     pub default_gate_provider: String,
     /// Default working directory
     pub default_cwd: String,
+    /// Provider per model tier (e.g. FAST → opencode, SMART → claude).
+    /// Consulted before the per-role defaults when no provider is given.
+    pub tier_providers: HashMap<ModelTier, String>,
+    /// Deepest a delegated agent may sit below the root agent. Delegations
+    /// that would spawn a child deeper than this are refused.
+    pub max_delegation_depth: u32,
 }
 
 impl Default for OrchestratorConfig {
@@ -329,10 +368,75 @@ impl Default for OrchestratorConfig {
             default_crafter_provider: "opencode".to_string(),
             default_gate_provider: "opencode".to_string(),
             default_cwd: ".".to_string(),
+            tier_providers: HashMap::new(),
+            max_delegation_depth: Self::DEFAULT_MAX_DELEGATION_DEPTH,
         }
     }
 }
 
+impl OrchestratorConfig {
+    /// Environment variable holding the tier → provider map,
+    /// formatted as `FAST=opencode,SMART=claude`.
+    pub const TIER_PROVIDERS_ENV: &'static str = "ROUTA_TIER_PROVIDERS";
+
+    /// Environment variable overriding [`Self::max_delegation_depth`].
+    pub const MAX_DELEGATION_DEPTH_ENV: &'static str = "ROUTA_MAX_DELEGATION_DEPTH";
+
+    /// ROUTA → CRAFTER → GATE, with one level to spare.
+    pub const DEFAULT_MAX_DELEGATION_DEPTH: u32 = 3;
+
+    /// Default config with `tier_providers` read from [`Self::TIER_PROVIDERS_ENV`]
+    /// and `max_delegation_depth` from [`Self::MAX_DELEGATION_DEPTH_ENV`].
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Ok(raw) = std::env::var(Self::TIER_PROVIDERS_ENV) {
+            config.tier_providers = parse_tier_providers(&raw);
+        }
+        if let Ok(raw) = std::env::var(Self::MAX_DELEGATION_DEPTH_ENV) {
+            match raw.trim().parse() {
+                Ok(depth) => config.max_delegation_depth = depth,
+                Err(_) => tracing::warn!(
+                    "[Orchestrator] Ignoring invalid {}: {}",
+                    Self::MAX_DELEGATION_DEPTH_ENV,
+                    raw
+                ),
+            }
+        }
+        config
+    }
+
+    /// Pick the provider for a delegated agent: tier mapping first, then the
+    /// per-role default.
+    pub fn provider_for(&self, role: &AgentRole, tier: &ModelTier) -> String {
+        if let Some(provider) = self.tier_providers.get(tier) {
+            return provider.clone();
+        }
+        if *role == AgentRole::Crafter {
+            self.default_crafter_provider.clone()
+        } else {
+            self.default_gate_provider.clone()
+        }
+    }
+}
+
+fn parse_tier_providers(raw: &str) -> HashMap<ModelTier, String> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (tier, provider) = entry.split_once('=')?;
+            let tier = ModelTier::from_str(&tier.trim().to_ascii_uppercase());
+            let provider = provider.trim();
+            if tier.is_none() || provider.is_empty() {
+                tracing::warn!(
+                    "[Orchestrator] Ignoring invalid tier provider entry: {}",
+                    entry
+                );
+                return None;
+            }
+            Some((tier?, provider.to_string()))
+        })
+        .collect()
+}
+
 // ─── Child Agent Record ───────────────────────────────────────────────────
 
 /// Tracks a spawned child agent and its relationship to a parent.
@@ -346,6 +450,112 @@ struct ChildAgentRecord {
     task_id: String,
     role: AgentRole,
     provider: String,
+    repo_path: Option<String>,
+    worktree_path: Option<String>,
+}
+
+impl ChildAgentRecord {
+    /// Trace linking the parent agent/session to the child it spawned.
+    fn to_trace(&self, workspace_id: &str) -> TraceRecord {
+        let mut trace = TraceRecord::new(
+            &self.parent_session_id,
+            TraceEventType::Delegation,
+            Contributor::new(&self.provider, None),
+        )
+        .with_workspace_id(workspace_id)
+        .with_metadata("parentAgentId", serde_json::json!(self.parent_agent_id))
+        .with_metadata("parentSessionId", serde_json::json!(self.parent_session_id))
+        .with_metadata("childAgentId", serde_json::json!(self.agent_id))
+        .with_metadata("childSessionId", serde_json::json!(self.session_id))
+        .with_metadata("taskId", serde_json::json!(self.task_id))
+        .with_metadata("role", serde_json::json!(self.role.as_str()));
+        if let Some(worktree_path) = &self.worktree_path {
+            trace = trace.with_metadata("worktreePath", serde_json::json!(worktree_path));
+        }
+        trace
+    }
+
+    fn to_delegation(&self, group_id: Option<String>, completed: bool) -> DelegationRecord {
+        DelegationRecord {
+            child_agent_id: self.agent_id.clone(),
+            child_session_id: self.session_id.clone(),
+            parent_agent_id: self.parent_agent_id.clone(),
+            parent_session_id: self.parent_session_id.clone(),
+            task_id: self.task_id.clone(),
+            role: self.role.clone(),
+            provider: self.provider.clone(),
+            group_id,
+            completed,
+            created_at: Utc::now(),
+            repo_path: self.repo_path.clone(),
+            worktree_path: self.worktree_path.clone(),
+        }
+    }
+
+    fn release_worktree(&self) {
+        if let (Some(repo), Some(path)) = (&self.repo_path, &self.worktree_path) {
+            release_worktree(&self.agent_id, repo, path);
+        }
+    }
+}
+
+impl From<&DelegationRecord> for ChildAgentRecord {
+    fn from(record: &DelegationRecord) -> Self {
+        Self {
+            agent_id: record.child_agent_id.clone(),
+            session_id: record.child_session_id.clone(),
+            parent_agent_id: record.parent_agent_id.clone(),
+            parent_session_id: record.parent_session_id.clone(),
+            task_id: record.task_id.clone(),
+            role: record.role.clone(),
+            provider: record.provider.clone(),
+            repo_path: record.repo_path.clone(),
+            worktree_path: record.worktree_path.clone(),
+        }
+    }
+}
+
+/// Outcome of [`RoutaOrchestrator::restore_delegations`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DelegationRestoreSummary {
+    /// Delegations reloaded into memory with a live child session.
+    pub restored: usize,
+    /// Child agents whose session died; their tasks were marked BLOCKED.
+    pub orphaned: Vec<String>,
+}
+
+/// A tracked child agent, as reported by [`RoutaOrchestrator::status`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildAgentStatus {
+    pub agent_id: String,
+    pub parent_agent_id: String,
+    pub task_id: String,
+    pub role: AgentRole,
+    pub provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    /// The child's ACP session is still running.
+    pub alive: bool,
+}
+
+/// An `after_all` group still waiting on some of its children.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationGroupStatus {
+    pub group_id: String,
+    pub parent_agent_id: String,
+    pub completed: usize,
+    pub total: usize,
+    pub pending_agent_ids: Vec<String>,
+}
+
+/// Live delegation state for one workspace.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrchestrationStatus {
+    pub children: Vec<ChildAgentStatus>,
+    pub groups: Vec<DelegationGroupStatus>,
 }
 
 /// Delegation group for wait_mode="after_all"
@@ -370,6 +580,8 @@ struct OrchestratorInner {
     delegation_groups: HashMap<String, DelegationGroup>,
     /// Map: callerAgentId → current groupId (for after_all mode)
     active_group_by_agent: HashMap<String, String>,
+    /// Delegations waiting for a free session slot in their workspace
+    queued_delegations: VecDeque<DelegateWithSpawnParams>,
 }
 
 // ─── Routa Orchestrator ───────────────────────────────────────────────────
@@ -382,6 +594,9 @@ pub struct RoutaOrchestrator {
     agent_store: AgentStore,
     task_store: TaskStore,
     event_bus: EventBus,
+    delegation_store: Option<DelegationStore>,
+    integration: Arc<dyn IntegrationStrategy>,
+    specialists: SpecialistResolver,
 }
 
 impl RoutaOrchestrator {
@@ -398,17 +613,44 @@ impl RoutaOrchestrator {
                 agent_session_map: HashMap::new(),
                 delegation_groups: HashMap::new(),
                 active_group_by_agent: HashMap::new(),
+                queued_delegations: VecDeque::new(),
             })),
             config,
             acp_manager,
             agent_store,
             task_store,
             event_bus,
+            delegation_store: None,
+            integration: Arc::new(GitMergeStrategy),
+            specialists: SpecialistResolver::default(),
         }
     }
 
+    /// Persist delegations so they survive a restart.
+    pub fn with_delegation_store(mut self, delegation_store: DelegationStore) -> Self {
+        self.delegation_store = Some(delegation_store);
+        self
+    }
+
+    /// Replace the default [`GitMergeStrategy`] used to integrate isolated
+    /// `after_all` groups.
+    pub fn with_integration_strategy(mut self, strategy: Arc<dyn IntegrationStrategy>) -> Self {
+        self.integration = strategy;
+        self
+    }
+
+    /// Resolve delegated specialists with `specialists` instead of the
+    /// default specialist directories.
+    pub fn with_specialist_resolver(mut self, specialists: SpecialistResolver) -> Self {
+        self.specialists = specialists;
+        self
+    }
+
     /// Register the mapping between an agent ID and its ACP session ID.
     pub async fn register_agent_session(&self, agent_id: &str, session_id: &str) {
+        self.acp_manager
+            .set_routa_agent_id(session_id, agent_id)
+            .await;
         let mut inner = self.inner.write().await;
         inner
             .agent_session_map
@@ -426,6 +668,312 @@ impl RoutaOrchestrator {
         inner.agent_session_map.get(agent_id).cloned()
     }
 
+    /// Get the parent agent a tracked child reports to.
+    pub async fn get_parent_for_child(&self, child_agent_id: &str) -> Option<String> {
+        let inner = self.inner.read().await;
+        inner
+            .child_agents
+            .get(child_agent_id)
+            .map(|record| record.parent_agent_id.clone())
+    }
+
+    /// In-flight children and waiting groups whose agents belong to
+    /// `workspace_id`. Children that already reported back to a waiting group
+    /// only count towards that group's progress.
+    pub async fn status(&self, workspace_id: &str) -> Result<OrchestrationStatus, ServerError> {
+        let (children, groups) = {
+            let inner = self.inner.read().await;
+            let group_of = |agent_id: &str| {
+                inner
+                    .delegation_groups
+                    .iter()
+                    .find(|(_, group)| group.child_agent_ids.iter().any(|id| id == agent_id))
+            };
+            let children: Vec<(ChildAgentRecord, Option<String>)> = inner
+                .child_agents
+                .values()
+                .filter_map(|record| match group_of(&record.agent_id) {
+                    Some((_, group)) if group.completed_agent_ids.contains(&record.agent_id) => {
+                        None
+                    }
+                    Some((group_id, _)) => Some((record.clone(), Some(group_id.clone()))),
+                    None => Some((record.clone(), None)),
+                })
+                .collect();
+            let groups: Vec<DelegationGroupStatus> = inner
+                .delegation_groups
+                .iter()
+                .map(|(group_id, group)| DelegationGroupStatus {
+                    group_id: group_id.clone(),
+                    parent_agent_id: group.parent_agent_id.clone(),
+                    completed: group.completed_agent_ids.len(),
+                    total: group.child_agent_ids.len(),
+                    pending_agent_ids: group
+                        .child_agent_ids
+                        .iter()
+                        .filter(|id| !group.completed_agent_ids.contains(*id))
+                        .cloned()
+                        .collect(),
+                })
+                .collect();
+            (children, groups)
+        };
+
+        let mut status = OrchestrationStatus::default();
+        let mut in_workspace = HashSet::new();
+        for (record, group_id) in children {
+            let agent = self.agent_store.get(&record.agent_id).await?;
+            if agent.is_none_or(|agent| agent.workspace_id != workspace_id) {
+                continue;
+            }
+            in_workspace.insert(record.agent_id.clone());
+            status.children.push(ChildAgentStatus {
+                alive: self.acp_manager.is_alive(&record.session_id).await,
+                agent_id: record.agent_id,
+                parent_agent_id: record.parent_agent_id,
+                task_id: record.task_id,
+                role: record.role,
+                provider: record.provider,
+                group_id,
+            });
+        }
+        status.groups = groups
+            .into_iter()
+            .filter(|group| {
+                group
+                    .pending_agent_ids
+                    .iter()
+                    .any(|id| in_workspace.contains(id))
+            })
+            .collect();
+        status.children.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        status.groups.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+        Ok(status)
+    }
+
+    /// Record a spawned child in memory, joining the caller's open group when
+    /// `after_all`, and mirror it to the delegation store.
+    async fn track_child(&self, record: ChildAgentRecord, after_all: bool) {
+        let group_id = {
+            let mut inner = self.inner.write().await;
+            inner
+                .agent_session_map
+                .insert(record.agent_id.clone(), record.session_id.clone());
+
+            let group_id = after_all.then(|| {
+                let group_id = inner
+                    .active_group_by_agent
+                    .entry(record.parent_agent_id.clone())
+                    .or_insert_with(|| format!("delegation-group-{}", uuid::Uuid::new_v4()))
+                    .clone();
+                inner
+                    .delegation_groups
+                    .entry(group_id.clone())
+                    .or_insert_with(|| DelegationGroup {
+                        group_id: group_id.clone(),
+                        parent_agent_id: record.parent_agent_id.clone(),
+                        parent_session_id: record.parent_session_id.clone(),
+                        child_agent_ids: Vec::new(),
+                        completed_agent_ids: HashSet::new(),
+                    })
+                    .child_agent_ids
+                    .push(record.agent_id.clone());
+                group_id
+            });
+            inner
+                .child_agents
+                .insert(record.agent_id.clone(), record.clone());
+            group_id
+        };
+
+        if let Some(store) = &self.delegation_store {
+            if let Err(e) = store.save(&record.to_delegation(group_id, false)).await {
+                tracing::warn!(
+                    "[Orchestrator] Failed to persist delegation for agent {}: {}",
+                    record.agent_id,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Reload persisted delegations into memory without checking liveness.
+    /// Returns the number of child agents loaded.
+    pub async fn load_delegations(&self) -> Result<usize, ServerError> {
+        let Some(store) = &self.delegation_store else {
+            return Ok(0);
+        };
+        let records = store.list().await?;
+
+        let mut inner = self.inner.write().await;
+        for record in &records {
+            inner.agent_session_map.insert(
+                record.child_agent_id.clone(),
+                record.child_session_id.clone(),
+            );
+            inner.child_agents.insert(
+                record.child_agent_id.clone(),
+                ChildAgentRecord::from(record),
+            );
+
+            if let Some(group_id) = &record.group_id {
+                inner
+                    .active_group_by_agent
+                    .insert(record.parent_agent_id.clone(), group_id.clone());
+                let group = inner
+                    .delegation_groups
+                    .entry(group_id.clone())
+                    .or_insert_with(|| DelegationGroup {
+                        group_id: group_id.clone(),
+                        parent_agent_id: record.parent_agent_id.clone(),
+                        parent_session_id: record.parent_session_id.clone(),
+                        child_agent_ids: Vec::new(),
+                        completed_agent_ids: HashSet::new(),
+                    });
+                group.child_agent_ids.push(record.child_agent_id.clone());
+                if record.completed {
+                    group
+                        .completed_agent_ids
+                        .insert(record.child_agent_id.clone());
+                }
+            }
+        }
+
+        Ok(records.len())
+    }
+
+    /// Reload persisted delegations and reconcile them against live ACP sessions.
+    ///
+    /// A child that has not reported back and whose session is gone can never
+    /// wake its parent, so its task is marked BLOCKED, the agent is set to
+    /// ERROR, the delegation is dropped and the parent is notified.
+    pub async fn restore_delegations(&self) -> Result<DelegationRestoreSummary, ServerError> {
+        let loaded = self.load_delegations().await?;
+        let candidates: Vec<(ChildAgentRecord, bool)> = {
+            let inner = self.inner.read().await;
+            inner
+                .child_agents
+                .values()
+                .map(|record| {
+                    let completed = inner
+                        .delegation_groups
+                        .values()
+                        .any(|group| group.completed_agent_ids.contains(&record.agent_id));
+                    (record.clone(), completed)
+                })
+                .collect()
+        };
+
+        let mut summary = DelegationRestoreSummary::default();
+        for (record, completed) in candidates {
+            if completed || self.acp_manager.is_alive(&record.session_id).await {
+                continue;
+            }
+            self.orphan_child(&record).await?;
+            summary.orphaned.push(record.agent_id);
+        }
+        summary.restored = loaded - summary.orphaned.len();
+
+        if loaded > 0 {
+            tracing::info!(
+                "[Orchestrator] Restored {} delegation(s), {} orphaned",
+                summary.restored,
+                summary.orphaned.len()
+            );
+        }
+        Ok(summary)
+    }
+
+    /// Drop a child whose session died before it reported back.
+    async fn orphan_child(&self, record: &ChildAgentRecord) -> Result<(), ServerError> {
+        if let Some(mut task) = self.task_store.get(&record.task_id).await? {
+            if task.status != TaskStatus::Completed {
+                task.status = TaskStatus::Blocked;
+                task.updated_at = Utc::now();
+                self.task_store.save(&task).await?;
+            }
+        }
+        self.agent_store
+            .update_status(&record.agent_id, &AgentStatus::Error)
+            .await?;
+        record.release_worktree();
+
+        let workspace_id = self
+            .agent_store
+            .get(&record.agent_id)
+            .await?
+            .map(|agent| agent.workspace_id)
+            .unwrap_or_default();
+
+        let group_finished = {
+            let mut inner = self.inner.write().await;
+            inner.child_agents.remove(&record.agent_id);
+            inner.agent_session_map.remove(&record.agent_id);
+
+            let mut finished = None;
+            for (group_id, group) in inner.delegation_groups.iter_mut() {
+                if let Some(pos) = group
+                    .child_agent_ids
+                    .iter()
+                    .position(|id| id == &record.agent_id)
+                {
+                    group.child_agent_ids.remove(pos);
+                    if group.completed_agent_ids.len() >= group.child_agent_ids.len() {
+                        finished = Some((group_id.clone(), group.parent_agent_id.clone()));
+                    }
+                    break;
+                }
+            }
+            if let Some((group_id, parent_agent_id)) = &finished {
+                inner.delegation_groups.remove(group_id);
+                inner.active_group_by_agent.remove(parent_agent_id);
+            }
+            finished.map(|(group_id, _)| group_id)
+        };
+
+        if let Some(store) = &self.delegation_store {
+            store.delete(&record.agent_id).await?;
+            if let Some(group_id) = &group_finished {
+                store.delete_group(group_id).await?;
+            }
+        }
+
+        self.event_bus
+            .emit(AgentEvent::agent_error(
+                record.agent_id.clone(),
+                workspace_id,
+                AgentErrorData {
+                    parent_id: Some(record.parent_agent_id.clone()),
+                    task_id: Some(record.task_id.clone()),
+                    reason: "child session lost after restart".to_string(),
+                    task_status: Some(TaskStatus::Blocked),
+                },
+            ))
+            .await;
+
+        if self.acp_manager.is_alive(&record.parent_session_id).await {
+            let message = format!(
+                "## Delegated Agent Lost\n\n\
+                 Agent {} stopped before reporting back (its session did not survive a restart).\n\
+                 Task {} is now BLOCKED. Re-delegate it or decide next steps.",
+                record.agent_id, record.task_id
+            );
+            if let Err(e) = self
+                .acp_manager
+                .prompt(&record.parent_session_id, &message)
+                .await
+            {
+                tracing::error!(
+                    "[Orchestrator] Failed to notify parent session {}: {}",
+                    record.parent_session_id,
+                    e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     /// Delegate a task to a new agent by spawning a real ACP process.
     pub async fn delegate_task_with_spawn(
         &self,
@@ -443,6 +991,23 @@ impl RoutaOrchestrator {
             }
         };
 
+        // 1b. Refuse to grow the agent tree past the configured depth
+        let caller_depth = match params.caller_depth {
+            Some(depth) => depth,
+            None => self
+                .agent_store
+                .get(&params.caller_agent_id)
+                .await?
+                .map_or(0, |caller| caller.delegation_depth()),
+        };
+        let depth = caller_depth + 1;
+        if depth > self.config.max_delegation_depth {
+            return Ok(ToolResult::error(format!(
+                "Delegation depth limit reached: agent {} is at depth {} and the maximum is {}. Complete the task yourself or report back to your parent.",
+                params.caller_agent_id, caller_depth, self.config.max_delegation_depth
+            )));
+        }
+
         // 2. Get the task
         let task = match self.task_store.get(&params.task_id).await? {
             Some(t) => t,
@@ -454,21 +1019,55 @@ impl RoutaOrchestrator {
             }
         };
 
-        // 3. Determine provider
+        // 2b. Defer the delegation while the workspace is at its session cap
+        if self
+            .acp_manager
+            .at_session_limit(&params.workspace_id)
+            .await
+        {
+            return Ok(self.queue_delegation(params).await);
+        }
+
+        // 3. Determine model tier and provider
+        let model_tier = params.model_tier_for(&specialist_config);
         let provider = params.provider.unwrap_or_else(|| {
-            if specialist_config.role == AgentRole::Crafter {
-                self.config.default_crafter_provider.clone()
-            } else {
-                self.config.default_gate_provider.clone()
-            }
+            self.config
+                .provider_for(&specialist_config.role, &model_tier)
         });
 
         let cwd = params
             .cwd
             .unwrap_or_else(|| self.config.default_cwd.clone());
+        let agent_id = uuid::Uuid::new_v4().to_string();
+
+        // 3b. Optionally isolate the child in its own git worktree
+        let (cwd, repo_path, worktree_path) = if params.isolate {
+            if !crate::git::is_git_repository(&cwd) {
+                return Ok(ToolResult::error(format!(
+                    "Cannot isolate agent: {cwd} is not a git repository"
+                )));
+            }
+            let branch = format!(
+                "routa/{}-{}",
+                specialist_config.id.to_lowercase(),
+                &agent_id[..8]
+            );
+            match crate::git::create_worktree(&cwd, &branch) {
+                Ok(path) => {
+                    let path = path.to_string_lossy().to_string();
+                    (path.clone(), Some(cwd), Some(path))
+                }
+                Err(e) => {
+                    return Ok(ToolResult::error(format!(
+                        "Failed to create worktree for agent: {e}"
+                    )));
+                }
+            }
+        } else {
+            (cwd, None, None)
+        };
 
         // 4. Create agent record
-        let agent_id = uuid::Uuid::new_v4().to_string();
         let agent_name = format!(
             "{}-{}",
             specialist_config.id,
@@ -480,16 +1079,19 @@ impl RoutaOrchestrator {
                 .to_lowercase()
         );
 
-        let agent = crate::models::agent::Agent::new(
+        let child_session_id = uuid::Uuid::new_v4().to_string();
+        let mut agent = crate::models::agent::Agent::new(
             agent_id.clone(),
             agent_name.clone(),
             specialist_config.role.clone(),
             params.workspace_id.clone(),
             Some(params.caller_agent_id.clone()),
-            Some(specialist_config.default_model_tier.clone()),
+            Some(model_tier),
             None,
         );
-        self.agent_store.save(&agent).await?;
+        agent.record_session_id(&child_session_id);
+        agent.set_delegation_depth(depth);
+        agent.status = AgentStatus::Active;
 
         // 5. Build the delegation prompt
         let delegation_prompt = build_delegation_prompt(
@@ -506,18 +1108,21 @@ impl RoutaOrchestrator {
             params.additional_instructions.as_deref(),
         );
 
-        // 6. Assign task to agent and update status
+        // 6. Assign task to agent; the agent and task rows land together
         let mut task = task;
         task.assigned_to = Some(agent_id.clone());
         task.status = TaskStatus::InProgress;
         task.updated_at = Utc::now();
-        self.task_store.save(&task).await?;
-        self.agent_store
-            .update_status(&agent_id, &AgentStatus::Active)
+        let saved_task = task.clone();
+        self.task_store
+            .db()?
+            .transaction(move |conn| {
+                AgentStore::save_in(conn, &agent)?;
+                TaskStore::save_in(conn, &saved_task)
+            })
             .await?;
 
         // 7. Spawn the ACP process
-        let child_session_id = uuid::Uuid::new_v4().to_string();
         let spawn_result = self
             .acp_manager
             .create_session(
@@ -537,6 +1142,9 @@ impl RoutaOrchestrator {
             Ok(ids) => ids,
             Err(e) => {
                 // Clean up on spawn failure
+                if let (Some(repo), Some(path)) = (&repo_path, &worktree_path) {
+                    release_worktree(&agent_id, repo, path);
+                }
                 self.agent_store
                     .update_status(&agent_id, &AgentStatus::Error)
                     .await?;
@@ -549,6 +1157,12 @@ impl RoutaOrchestrator {
             }
         };
 
+        // Attach the child agent so liveness checks and live message
+        // delivery find its session.
+        self.acp_manager
+            .set_routa_agent_id(&child_session_id, &agent_id)
+            .await;
+
         // Kick off the child prompt in the background. Waiting for the entire
         // child turn here blocks the parent MCP tool call long enough for
         // OpenCode to abort delegation before the child can report progress.
@@ -590,72 +1204,40 @@ impl RoutaOrchestrator {
             )
             .await;
 
-        // 8. Track the child agent
-        {
-            let mut inner = self.inner.write().await;
-            let record = ChildAgentRecord {
-                agent_id: agent_id.clone(),
-                session_id: child_session_id.clone(),
-                parent_agent_id: params.caller_agent_id.clone(),
-                parent_session_id: params.caller_session_id.clone(),
-                task_id: params.task_id.clone(),
-                role: specialist_config.role.clone(),
-                provider: provider.clone(),
-            };
-            inner.child_agents.insert(agent_id.clone(), record);
-            inner
-                .agent_session_map
-                .insert(agent_id.clone(), child_session_id.clone());
-
-            // 9. Handle wait mode
-            if params.wait_mode == "after_all" {
-                let group_id = inner
-                    .active_group_by_agent
-                    .get(&params.caller_agent_id)
-                    .cloned();
-
-                let group_id = match group_id {
-                    Some(gid) => gid,
-                    None => {
-                        let new_group_id = format!("delegation-group-{}", uuid::Uuid::new_v4());
-                        inner
-                            .active_group_by_agent
-                            .insert(params.caller_agent_id.clone(), new_group_id.clone());
-                        inner.delegation_groups.insert(
-                            new_group_id.clone(),
-                            DelegationGroup {
-                                group_id: new_group_id.clone(),
-                                parent_agent_id: params.caller_agent_id.clone(),
-                                parent_session_id: params.caller_session_id.clone(),
-                                child_agent_ids: Vec::new(),
-                                completed_agent_ids: HashSet::new(),
-                            },
-                        );
-                        new_group_id
-                    }
-                };
-
-                if let Some(group) = inner.delegation_groups.get_mut(&group_id) {
-                    group.child_agent_ids.push(agent_id.clone());
-                }
-            }
-        }
+        // 8-9. Track the child agent (and its after_all group)
+        let record = ChildAgentRecord {
+            agent_id: agent_id.clone(),
+            session_id: child_session_id.clone(),
+            parent_agent_id: params.caller_agent_id.clone(),
+            parent_session_id: params.caller_session_id.clone(),
+            task_id: params.task_id.clone(),
+            role: specialist_config.role.clone(),
+            provider: provider.clone(),
+            repo_path,
+            worktree_path: worktree_path.clone(),
+        };
+        // Traces live with the parent's checkout, not the child's worktree
+        let trace = record.to_trace(&params.workspace_id);
+        TraceWriter::new(record.repo_path.as_deref().unwrap_or(&cwd))
+            .append_safe(&trace)
+            .await;
+        self.track_child(record, params.wait_mode == "after_all")
+            .await;
 
         // 10. Emit event
         self.event_bus
-            .emit(AgentEvent {
-                event_type: AgentEventType::TaskAssigned,
-                agent_id: agent_id.clone(),
-                workspace_id: params.workspace_id.clone(),
-                data: serde_json::json!({
-                    "taskId": params.task_id,
-                    "callerAgentId": params.caller_agent_id,
-                    "taskTitle": task.title,
-                    "provider": provider,
-                    "specialist": specialist_config.id,
-                }),
-                timestamp: Utc::now(),
-            })
+            .emit(AgentEvent::task_assigned(
+                agent_id.clone(),
+                params.workspace_id.clone(),
+                TaskAssignedData {
+                    task_id: params.task_id.clone(),
+                    task_title: task.title.clone(),
+                    caller_agent_id: Some(params.caller_agent_id.clone()),
+                    previous_agent_id: None,
+                    provider: Some(provider.clone()),
+                    specialist: Some(specialist_config.id.clone()),
+                },
+            ))
             .await;
 
         let wait_message = if params.wait_mode == "after_all" {
@@ -680,6 +1262,7 @@ impl RoutaOrchestrator {
             "provider": provider,
             "sessionId": child_session_id,
             "waitMode": params.wait_mode,
+            "worktreePath": worktree_path,
             "message": format!("Task \"{}\" delegated to {} agent. {}", task.title, specialist_config.name, wait_message),
         })))
     }
@@ -698,8 +1281,8 @@ impl RoutaOrchestrator {
         let record = match record {
             Some(r) => r,
             None => {
-                tracing::warn!(
-                    "[Orchestrator] Report from unknown child agent {}, ignoring",
+                tracing::debug!(
+                    "[Orchestrator] Report from {} is not for a tracked delegation",
                     child_agent_id
                 );
                 return Ok(());
@@ -729,6 +1312,9 @@ impl RoutaOrchestrator {
         self.handle_child_completion(child_agent_id, &record)
             .await?;
 
+        // A finished child may have freed a slot for a queued delegation
+        self.start_queued_delegations().await;
+
         Ok(())
     }
 
@@ -742,8 +1328,10 @@ impl RoutaOrchestrator {
 
         // Check if this child is part of an after_all group
         let mut group_complete = None;
+        let mut in_group = false;
         for (group_id, group) in inner.delegation_groups.iter_mut() {
             if group.child_agent_ids.contains(&child_agent_id.to_string()) {
+                in_group = true;
                 group.completed_agent_ids.insert(child_agent_id.to_string());
                 tracing::info!(
                     "[Orchestrator] Agent {} completed in group {} ({}/{})",
@@ -758,24 +1346,45 @@ impl RoutaOrchestrator {
                         group_id.clone(),
                         group.parent_agent_id.clone(),
                         group.parent_session_id.clone(),
+                        group.child_agent_ids.clone(),
                     ));
                 }
                 break;
             }
         }
 
-        if let Some((group_id, parent_agent_id, parent_session_id)) = group_complete {
+        if let Some((group_id, parent_agent_id, parent_session_id, child_ids)) = group_complete {
             tracing::info!(
                 "[Orchestrator] All agents in group {} completed, waking parent",
                 group_id
             );
             inner.delegation_groups.remove(&group_id);
             inner.active_group_by_agent.remove(&parent_agent_id);
+            let children: Vec<ChildAgentRecord> = child_ids
+                .iter()
+                .filter_map(|id| inner.child_agents.get(id).cloned())
+                .collect();
 
             // Wake parent with group completion message
             drop(inner); // Release lock before async call
-            self.wake_parent_with_group_completion(&parent_session_id, &group_id)
-                .await?;
+            if let Some(store) = &self.delegation_store {
+                store.delete_group(&group_id).await?;
+            }
+            let integration = self.integrate_group(&group_id, &children).await?;
+            for child in &children {
+                child.release_worktree();
+            }
+            self.wake_parent_with_group_completion(
+                &parent_session_id,
+                &group_id,
+                integration.as_deref(),
+            )
+            .await?;
+        } else if in_group {
+            drop(inner);
+            if let Some(store) = &self.delegation_store {
+                store.mark_completed(child_agent_id).await?;
+            }
         } else {
             // Immediate mode: wake parent right away
             tracing::info!(
@@ -784,6 +1393,10 @@ impl RoutaOrchestrator {
                 record.parent_agent_id
             );
             drop(inner);
+            record.release_worktree();
+            if let Some(store) = &self.delegation_store {
+                store.delete(child_agent_id).await?;
+            }
             self.wake_parent(&record.parent_session_id, child_agent_id, &record.task_id)
                 .await?;
         }
@@ -837,19 +1450,130 @@ impl RoutaOrchestrator {
     }
 
     /// Wake parent with group completion message.
+    /// Run the integration strategy over a finished group's isolated children.
+    ///
+    /// Only runs when every isolated child's task is COMPLETED. Returns a note
+    /// for the parent's wake message, or `None` when nothing was integrated.
+    /// A conflict is reported as a `TASK_FAILED` event on the child's task.
+    async fn integrate_group(
+        &self,
+        group_id: &str,
+        children: &[ChildAgentRecord],
+    ) -> Result<Option<String>, ServerError> {
+        let mut isolated = Vec::new();
+        for child in children {
+            let (Some(repo_path), Some(worktree_path)) = (&child.repo_path, &child.worktree_path)
+            else {
+                continue;
+            };
+            let status = self.task_store.get(&child.task_id).await?.map(|t| t.status);
+            if status != Some(TaskStatus::Completed) {
+                tracing::info!(
+                    "[Orchestrator] Skipping integration of group {}: task {} is not completed",
+                    group_id,
+                    child.task_id
+                );
+                return Ok(None);
+            }
+            let Some(branch) = crate::git::get_current_branch(worktree_path) else {
+                tracing::warn!(
+                    "[Orchestrator] Worktree {} for agent {} has no branch, skipping",
+                    worktree_path,
+                    child.agent_id
+                );
+                continue;
+            };
+            isolated.push(IsolatedChild {
+                agent_id: child.agent_id.clone(),
+                task_id: child.task_id.clone(),
+                repo_path: repo_path.clone(),
+                worktree_path: worktree_path.clone(),
+                branch,
+            });
+        }
+        if isolated.is_empty() {
+            return Ok(None);
+        }
+
+        let strategy = self.integration.name().to_string();
+        match self.integration.integrate(&isolated) {
+            Ok(branches) => {
+                tracing::info!(
+                    "[Orchestrator] Integrated {} branch(es) for group {} via {}",
+                    branches.len(),
+                    group_id,
+                    strategy
+                );
+                Ok(Some(format!(
+                    "### Integration ({})\n\nMerged branches: {}",
+                    strategy,
+                    branches.join(", ")
+                )))
+            }
+            Err(conflict) => {
+                tracing::warn!(
+                    "[Orchestrator] Integration of group {} failed on {}: {}",
+                    group_id,
+                    conflict.branch,
+                    conflict.message
+                );
+                let workspace_id = self
+                    .task_store
+                    .get(&conflict.task_id)
+                    .await?
+                    .map(|t| t.workspace_id)
+                    .unwrap_or_default();
+                self.event_bus
+                    .emit(AgentEvent::task_failed(
+                        conflict.agent_id.clone(),
+                        workspace_id,
+                        TaskFailedData {
+                            task_id: conflict.task_id.clone(),
+                            error: conflict.message.clone(),
+                            group_id: Some(group_id.to_string()),
+                            strategy: Some(strategy.clone()),
+                            branch: Some(conflict.branch.clone()),
+                            conflicted_files: conflict.conflicted_files.clone(),
+                        },
+                    ))
+                    .await;
+                let files = if conflict.conflicted_files.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "\nConflicted files: {}",
+                        conflict.conflicted_files.join(", ")
+                    )
+                };
+                Ok(Some(format!(
+                    "### Integration Failed ({})\n\n\
+                     Branch `{}` from agent {} could not be integrated: {}{}\n\
+                     The worktree branches were kept for manual resolution.",
+                    strategy, conflict.branch, conflict.agent_id, conflict.message, files
+                )))
+            }
+        }
+    }
+
     async fn wake_parent_with_group_completion(
         &self,
         parent_session_id: &str,
         _group_id: &str,
+        integration: Option<&str>,
     ) -> Result<(), ServerError> {
-        let wake_message = "## Delegation Group Complete\n\n\
+        let mut wake_message = "## Delegation Group Complete\n\n\
             All delegated agents have completed their work.\n\
             Review the results and decide next steps.\n\
-            You may want to delegate a GATE (verifier) agent to validate the work.";
+            You may want to delegate a GATE (verifier) agent to validate the work."
+            .to_string();
+        if let Some(integration) = integration {
+            wake_message.push_str("\n\n");
+            wake_message.push_str(integration);
+        }
 
         if let Err(e) = self
             .acp_manager
-            .prompt(parent_session_id, wake_message)
+            .prompt(parent_session_id, &wake_message)
             .await
         {
             tracing::error!(
@@ -864,12 +1588,100 @@ impl RoutaOrchestrator {
 
     /// Resolve specialist config from a string (role name or specialist ID).
     fn resolve_specialist(&self, input: &str) -> Option<SpecialistConfig> {
-        SpecialistConfig::resolve(input)
+        self.specialists.resolve(input)
+    }
+
+    /// Hold `params` until its workspace has a free session slot, announcing
+    /// it with `DELEGATION_QUEUED`.
+    async fn queue_delegation(&self, params: DelegateWithSpawnParams) -> ToolResult {
+        let position = {
+            let mut inner = self.inner.write().await;
+            inner.queued_delegations.push_back(params.clone());
+            inner
+                .queued_delegations
+                .iter()
+                .filter(|queued| queued.workspace_id == params.workspace_id)
+                .count()
+        };
+        tracing::info!(
+            "[Orchestrator] Queued delegation of task {} in workspace {} (position {})",
+            params.task_id,
+            params.workspace_id,
+            position
+        );
+
+        self.event_bus
+            .emit(AgentEvent::delegation_queued(
+                params.caller_agent_id.clone(),
+                params.workspace_id.clone(),
+                DelegationQueuedData {
+                    task_id: params.task_id.clone(),
+                    caller_agent_id: params.caller_agent_id.clone(),
+                    specialist: params.specialist.clone(),
+                    position,
+                },
+            ))
+            .await;
+
+        ToolResult::success(serde_json::json!({
+            "queued": true,
+            "taskId": params.task_id,
+            "position": position,
+            "message": format!(
+                "Workspace {} is at its live session limit; the delegation will start when a session ends.",
+                params.workspace_id
+            ),
+        }))
     }
 
-    /// Clean up resources for a session.
+    /// Start queued delegations, in order, while their workspaces have free
+    /// session slots.
+    pub async fn start_queued_delegations(&self) {
+        loop {
+            let next = {
+                let mut inner = self.inner.write().await;
+                let mut ready = None;
+                for (index, queued) in inner.queued_delegations.iter().enumerate() {
+                    if !self
+                        .acp_manager
+                        .at_session_limit(&queued.workspace_id)
+                        .await
+                    {
+                        ready = Some(index);
+                        break;
+                    }
+                }
+                ready.and_then(|index| inner.queued_delegations.remove(index))
+            };
+            let Some(params) = next else {
+                return;
+            };
+
+            let task_id = params.task_id.clone();
+            match self.delegate_task_with_spawn(params).await {
+                Ok(result) if !result.success => tracing::warn!(
+                    "[Orchestrator] Queued delegation of task {} failed: {}",
+                    task_id,
+                    result.error.unwrap_or_default()
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    "[Orchestrator] Queued delegation of task {} failed: {}",
+                    task_id,
+                    e
+                ),
+            }
+        }
+    }
+
+    /// Clean up resources for a session, emitting `DELEGATION_CANCELLED` for
+    /// each child stopped before it reported back. Delegations the session
+    /// queued are dropped, and others waiting on the freed slots are started.
     pub async fn cleanup(&self, session_id: &str) {
         let mut inner = self.inner.write().await;
+        inner
+            .queued_delegations
+            .retain(|queued| queued.caller_session_id != session_id);
         let agents_to_remove: Vec<String> = inner
             .child_agents
             .iter()
@@ -877,17 +1689,126 @@ impl RoutaOrchestrator {
             .map(|(id, _)| id.clone())
             .collect();
 
+        let mut cancelled = Vec::new();
         for agent_id in agents_to_remove {
             if let Some(record) = inner.child_agents.remove(&agent_id) {
                 self.acp_manager.kill_session(&record.session_id).await;
+                record.release_worktree();
+                let reported = inner
+                    .delegation_groups
+                    .values()
+                    .any(|group| group.completed_agent_ids.contains(&agent_id));
+                if !reported {
+                    cancelled.push(record);
+                }
             }
             inner.agent_session_map.remove(&agent_id);
+            if let Some(store) = &self.delegation_store {
+                if let Err(e) = store.delete(&agent_id).await {
+                    tracing::warn!(
+                        "[Orchestrator] Failed to delete delegation for agent {}: {}",
+                        agent_id,
+                        e
+                    );
+                }
+            }
+        }
+        drop(inner);
+
+        for record in cancelled {
+            let workspace_id = match self.agent_store.get(&record.agent_id).await {
+                Ok(Some(agent)) => agent.workspace_id,
+                _ => "default".to_string(),
+            };
+            let reason = if record.parent_session_id == session_id {
+                "Parent session ended before the agent reported back"
+            } else {
+                "Agent session ended before it reported back"
+            };
+            self.event_bus
+                .emit(AgentEvent::delegation_cancelled(
+                    record.agent_id.clone(),
+                    workspace_id,
+                    DelegationCancelledData {
+                        task_id: record.task_id.clone(),
+                        parent_agent_id: record.parent_agent_id.clone(),
+                        session_id: record.session_id.clone(),
+                        reason: reason.to_string(),
+                    },
+                ))
+                .await;
         }
+
+        self.start_queued_delegations().await;
+    }
+}
+
+impl LiveSessionDelivery for RoutaOrchestrator {
+    fn deliver<'a>(
+        &'a self,
+        agent_id: &'a str,
+        text: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Option<String>> + Send + 'a>> {
+        Box::pin(async move {
+            let session_id = self.get_session_for_agent(agent_id).await?;
+            if !self.acp_manager.is_alive(&session_id).await {
+                return None;
+            }
+
+            // Like delegation, don't hold the sender's tool call for the
+            // recipient's whole turn.
+            let manager = Arc::clone(&self.acp_manager);
+            let prompt_session_id = session_id.clone();
+            let agent_id = agent_id.to_string();
+            let text = text.to_string();
+            tokio::spawn(async move {
+                if let Err(e) = manager.prompt(&prompt_session_id, &text).await {
+                    tracing::error!(
+                        "[Orchestrator] Failed to deliver message to agent {}: {}",
+                        agent_id,
+                        e
+                    );
+                }
+            });
+            Some(session_id)
+        })
+    }
+}
+
+impl SessionLiveness for RoutaOrchestrator {
+    fn session_alive<'a>(
+        &'a self,
+        agent_id: &'a str,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        Box::pin(async move {
+            match self.get_session_for_agent(agent_id).await {
+                Some(session_id) => self.acp_manager.is_alive(&session_id).await,
+                None => self.acp_manager.session_alive(agent_id).await,
+            }
+        })
     }
 }
 
 // ─── Helper Functions ─────────────────────────────────────────────────────
 
+/// Remove a child's worktree. Dirty worktrees are left in place (with a
+/// warning) so uncommitted work is never discarded.
+fn release_worktree(agent_id: &str, repo_path: &str, worktree_path: &str) {
+    match crate::git::remove_worktree(repo_path, std::path::Path::new(worktree_path)) {
+        Ok(()) => tracing::info!(
+            "[Orchestrator] Removed worktree {} for agent {}",
+            worktree_path,
+            agent_id
+        ),
+        Err(e) => tracing::warn!(
+            "[Orchestrator] Keeping worktree {} for agent {}: {}",
+            worktree_path,
+            agent_id,
+            e.trim()
+        ),
+    }
+}
+
 /// Build the initial prompt for a delegated agent.
 #[allow(clippy::too_many_arguments)]
 fn build_delegation_prompt(
@@ -953,3 +1874,647 @@ fn build_delegation_prompt(
 
     prompt
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_for_prefers_tier_mapping_over_role_default() {
+        let mut config = OrchestratorConfig {
+            default_crafter_provider: "codex".to_string(),
+            ..OrchestratorConfig::default()
+        };
+        config
+            .tier_providers
+            .insert(ModelTier::Smart, "claude".to_string());
+
+        let crafter = SpecialistConfig::crafter();
+        let gate = SpecialistConfig::gate();
+        assert_eq!(crafter.default_model_tier, ModelTier::Fast);
+        assert_eq!(
+            config.provider_for(&crafter.role, &crafter.default_model_tier),
+            "codex"
+        );
+        assert_eq!(
+            config.provider_for(&gate.role, &gate.default_model_tier),
+            "claude"
+        );
+
+        config
+            .tier_providers
+            .insert(ModelTier::Fast, "opencode".to_string());
+        assert_eq!(
+            config.provider_for(&crafter.role, &crafter.default_model_tier),
+            "opencode"
+        );
+    }
+
+    #[test]
+    fn delegation_tier_overrides_specialist_default_for_provider() {
+        let mut config = OrchestratorConfig::default();
+        config
+            .tier_providers
+            .insert(ModelTier::Fast, "opencode".to_string());
+        config
+            .tier_providers
+            .insert(ModelTier::Smart, "claude".to_string());
+        let gate = SpecialistConfig::gate();
+        let mut params: DelegateWithSpawnParams = serde_json::from_value(serde_json::json!({
+            "taskId": "task-1",
+            "callerAgentId": "routa",
+            "callerSessionId": "session-1",
+            "workspaceId": "default",
+            "specialist": "GATE",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            config.provider_for(&gate.role, &params.model_tier_for(&gate)),
+            "claude"
+        );
+
+        params.model_tier = Some(ModelTier::Fast);
+        assert_eq!(
+            config.provider_for(&gate.role, &params.model_tier_for(&gate)),
+            "opencode"
+        );
+    }
+
+    #[test]
+    fn parse_tier_providers_skips_invalid_entries() {
+        let parsed = parse_tier_providers("fast=opencode, SMART = claude,HUGE=x,BALANCED=");
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(
+            parsed.get(&ModelTier::Fast).map(String::as_str),
+            Some("opencode")
+        );
+        assert_eq!(
+            parsed.get(&ModelTier::Smart).map(String::as_str),
+            Some("claude")
+        );
+    }
+
+    async fn persistent_orchestrator(db: &crate::db::Database) -> RoutaOrchestrator {
+        RoutaOrchestrator::new(
+            OrchestratorConfig::default(),
+            Arc::new(AcpManager::new()),
+            AgentStore::new(db.clone()),
+            TaskStore::new(db.clone()),
+            EventBus::new(),
+        )
+        .with_delegation_store(DelegationStore::new(db.clone()))
+    }
+
+    fn child_record(agent_id: &str, task_id: &str) -> ChildAgentRecord {
+        ChildAgentRecord {
+            agent_id: agent_id.to_string(),
+            session_id: format!("session-{agent_id}"),
+            parent_agent_id: "routa".to_string(),
+            parent_session_id: "session-routa".to_string(),
+            task_id: task_id.to_string(),
+            role: AgentRole::Crafter,
+            provider: "opencode".to_string(),
+            repo_path: None,
+            worktree_path: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn delegation_trace_links_parent_and_child() {
+        use crate::trace::{TraceQuery, TraceReader};
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        TraceWriter::with_base_dir(dir.path())
+            .append(&child_record("crafter-1", "task-1").to_trace("default"))
+            .await
+            .expect("trace should write");
+
+        let reader = TraceReader::with_base_dir(dir.path());
+        for agent_id in ["routa", "crafter-1"] {
+            let traces = reader
+                .query(&TraceQuery {
+                    agent_id: Some(agent_id.to_string()),
+                    ..TraceQuery::default()
+                })
+                .await
+                .unwrap();
+            assert_eq!(traces.len(), 1, "{agent_id}");
+        }
+        assert!(reader
+            .query(&TraceQuery {
+                agent_id: Some("crafter-2".to_string()),
+                ..TraceQuery::default()
+            })
+            .await
+            .unwrap()
+            .is_empty());
+
+        let traces = reader
+            .query(&TraceQuery {
+                event_type: Some("delegation".to_string()),
+                ..TraceQuery::default()
+            })
+            .await
+            .unwrap();
+        let trace = &traces[0];
+        assert_eq!(trace.session_id, "session-routa");
+        assert_eq!(trace.workspace_id.as_deref(), Some("default"));
+        let meta = |key: &str| trace.metadata[key].as_str().unwrap().to_string();
+        assert_eq!(meta("parentAgentId"), "routa");
+        assert_eq!(meta("parentSessionId"), "session-routa");
+        assert_eq!(meta("childAgentId"), "crafter-1");
+        assert_eq!(meta("childSessionId"), "session-crafter-1");
+        assert_eq!(meta("taskId"), "task-1");
+    }
+
+    #[tokio::test]
+    async fn reloaded_orchestrator_still_knows_child_parent() {
+        let db = crate::db::Database::open_in_memory().expect("in-memory db should open");
+
+        let before_restart = persistent_orchestrator(&db).await;
+        before_restart
+            .track_child(child_record("crafter-1", "task-1"), true)
+            .await;
+        before_restart
+            .track_child(child_record("crafter-2", "task-2"), true)
+            .await;
+        drop(before_restart);
+
+        let reloaded = persistent_orchestrator(&db).await;
+        assert_eq!(reloaded.load_delegations().await.unwrap(), 2);
+        assert_eq!(
+            reloaded.get_parent_for_child("crafter-1").await.as_deref(),
+            Some("routa")
+        );
+        assert_eq!(
+            reloaded.get_session_for_agent("crafter-2").await.as_deref(),
+            Some("session-crafter-2")
+        );
+        let inner = reloaded.inner.read().await;
+        assert_eq!(inner.delegation_groups.len(), 1);
+        let group = inner.delegation_groups.values().next().unwrap();
+        assert_eq!(group.child_agent_ids, vec!["crafter-1", "crafter-2"]);
+    }
+
+    #[tokio::test]
+    async fn report_after_restart_completes_restored_delegation() {
+        use crate::models::agent::Agent;
+        use crate::models::task::Task;
+        use crate::state::AppStateInner;
+
+        let db = crate::db::Database::open_in_memory().expect("in-memory db should open");
+        let before_restart = AppStateInner::new(db.clone());
+        before_restart
+            .workspace_store
+            .ensure_default()
+            .await
+            .unwrap();
+        before_restart
+            .agent_store
+            .save(&Agent::new(
+                "crafter-1".to_string(),
+                "crafter-1".to_string(),
+                AgentRole::Crafter,
+                "default".to_string(),
+                Some("routa".to_string()),
+                None,
+                None,
+            ))
+            .await
+            .unwrap();
+        before_restart
+            .task_store
+            .save(&Task::new(
+                "task-1".to_string(),
+                "Implement".to_string(),
+                "objective".to_string(),
+                "default".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ))
+            .await
+            .unwrap();
+        before_restart
+            .orchestrator
+            .track_child(child_record("crafter-1", "task-1"), false)
+            .await;
+        drop(before_restart);
+
+        // A fresh state over the same database stands in for the restart. The
+        // child's ACP session can't be faked alive, so load without the
+        // liveness reconciliation `restore_delegations` adds.
+        let restarted = AppStateInner::new(db);
+        assert_eq!(restarted.orchestrator.load_delegations().await.unwrap(), 1);
+
+        let result = restarted
+            .report_to_parent(
+                "crafter-1",
+                CompletionReport {
+                    agent_id: "crafter-1".to_string(),
+                    task_id: Some("task-1".to_string()),
+                    summary: "Implemented".to_string(),
+                    success: true,
+                    files_modified: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(restarted.delegation_store.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cleanup_cancels_children_that_have_not_reported() {
+        use crate::events::{AgentEventType, EventSubscription};
+
+        let db = crate::db::Database::open_in_memory().expect("in-memory db should open");
+        let orchestrator = persistent_orchestrator(&db).await;
+        orchestrator
+            .event_bus
+            .subscribe(EventSubscription {
+                id: "sub-observer".to_string(),
+                agent_id: "observer".to_string(),
+                agent_name: "observer".to_string(),
+                event_types: vec![AgentEventType::DelegationCancelled],
+                exclude_self: false,
+                one_shot: false,
+                wait_group_id: None,
+                priority: 0,
+                workspace_id: "default".to_string(),
+                cross_workspace: false,
+                ttl_secs: None,
+            })
+            .await;
+        orchestrator
+            .track_child(child_record("crafter-1", "task-1"), true)
+            .await;
+        orchestrator
+            .track_child(child_record("crafter-2", "task-2"), true)
+            .await;
+        {
+            let mut inner = orchestrator.inner.write().await;
+            let group = inner.delegation_groups.values_mut().next().unwrap();
+            group.completed_agent_ids.insert("crafter-1".to_string());
+        }
+
+        orchestrator.cleanup("session-routa").await;
+
+        let events = orchestrator
+            .event_bus
+            .drain_pending_events("observer")
+            .await;
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.event_type.as_str(), "DELEGATION_CANCELLED");
+        assert_eq!(event.agent_id, "crafter-2");
+        assert_eq!(event.data["taskId"], "task-2");
+        assert_eq!(event.data["parentAgentId"], "routa");
+        event.validate_data().unwrap();
+        assert!(orchestrator
+            .get_parent_for_child("crafter-2")
+            .await
+            .is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn delegation_at_the_session_cap_is_queued() {
+        use crate::acp::SessionLaunchOptions;
+        use crate::events::{AgentEventType, EventSubscription};
+        use crate::models::task::Task;
+        use crate::store::WorkspaceStore;
+
+        let db = crate::db::Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let tasks = TaskStore::new(db.clone());
+        tasks
+            .save(&Task::new(
+                "task-1".to_string(),
+                "Queue me".to_string(),
+                "objective".to_string(),
+                "default".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        // One live stub session fills the workspace.
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let cwd = temp.path().to_string_lossy().to_string();
+        let stub_agent = r#"read _
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1}}'
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session"}}'
+cat > /dev/null"#;
+        let acp_manager = Arc::new(AcpManager::new());
+        acp_manager.set_max_sessions_per_workspace(1);
+        acp_manager
+            .create_session_from_inline(
+                "session-busy".to_string(),
+                cwd.clone(),
+                "default".to_string(),
+                "stub".to_string(),
+                None,
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                SessionLaunchOptions::default(),
+            )
+            .await
+            .expect("stub session should start");
+
+        let orchestrator = RoutaOrchestrator::new(
+            OrchestratorConfig::default(),
+            acp_manager.clone(),
+            AgentStore::new(db.clone()),
+            tasks.clone(),
+            EventBus::new(),
+        );
+        orchestrator
+            .event_bus
+            .subscribe(EventSubscription {
+                id: "sub-observer".to_string(),
+                agent_id: "observer".to_string(),
+                agent_name: "observer".to_string(),
+                event_types: vec![AgentEventType::DelegationQueued],
+                exclude_self: false,
+                one_shot: false,
+                wait_group_id: None,
+                priority: 0,
+                workspace_id: "default".to_string(),
+                cross_workspace: false,
+                ttl_secs: None,
+            })
+            .await;
+
+        let result = orchestrator
+            .delegate_task_with_spawn(DelegateWithSpawnParams {
+                task_id: "task-1".to_string(),
+                caller_agent_id: "routa".to_string(),
+                caller_session_id: "session-routa".to_string(),
+                workspace_id: "default".to_string(),
+                specialist: "CRAFTER".to_string(),
+                provider: None,
+                model_tier: None,
+                cwd: Some(cwd.clone()),
+                additional_instructions: None,
+                wait_mode: "immediate".to_string(),
+                isolate: false,
+                caller_depth: Some(0),
+            })
+            .await
+            .unwrap();
+        assert!(result.success);
+        let data = result.data.unwrap();
+        assert_eq!(data["queued"], true);
+        assert_eq!(data["position"], 1);
+
+        let events = orchestrator
+            .event_bus
+            .drain_pending_events("observer")
+            .await;
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.event_type.as_str(), "DELEGATION_QUEUED");
+        assert_eq!(event.agent_id, "routa");
+        assert_eq!(event.data["taskId"], "task-1");
+        assert_eq!(event.data["specialist"], "CRAFTER");
+        assert_eq!(event.data["position"], 1);
+        event.validate_data().unwrap();
+
+        // Nothing was spawned or assigned while queued.
+        let task = tasks.get("task-1").await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Pending);
+        assert!(task.assigned_to.is_none());
+
+        // Ending the calling session drops its queued delegation.
+        orchestrator.cleanup("session-routa").await;
+        assert!(orchestrator
+            .inner
+            .read()
+            .await
+            .queued_delegations
+            .is_empty());
+
+        acp_manager.delete_session("session-busy").await;
+        let _ = std::fs::remove_dir_all(crate::storage::get_project_storage_dir(&cwd));
+    }
+
+    #[tokio::test]
+    async fn status_lists_delegated_children_with_their_parent() {
+        use crate::models::agent::Agent;
+
+        let db = crate::db::Database::open_in_memory().expect("in-memory db should open");
+        crate::store::WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        for agent_id in ["crafter-1", "crafter-2"] {
+            AgentStore::new(db.clone())
+                .save(&Agent::new(
+                    agent_id.to_string(),
+                    agent_id.to_string(),
+                    AgentRole::Crafter,
+                    "default".to_string(),
+                    None,
+                    None,
+                    None,
+                ))
+                .await
+                .unwrap();
+        }
+
+        let orchestrator = persistent_orchestrator(&db).await;
+        orchestrator
+            .track_child(child_record("crafter-1", "task-1"), true)
+            .await;
+        orchestrator
+            .track_child(child_record("crafter-2", "task-2"), true)
+            .await;
+
+        let status = orchestrator.status("default").await.unwrap();
+        let children: Vec<(&str, &str)> = status
+            .children
+            .iter()
+            .map(|child| (child.agent_id.as_str(), child.parent_agent_id.as_str()))
+            .collect();
+        assert_eq!(
+            children,
+            vec![("crafter-1", "routa"), ("crafter-2", "routa")]
+        );
+        assert!(status.children.iter().all(|child| !child.alive));
+        assert_eq!(status.groups.len(), 1);
+        assert_eq!(status.groups[0].completed, 0);
+        assert_eq!(status.groups[0].total, 2);
+
+        assert!(orchestrator
+            .status("elsewhere")
+            .await
+            .unwrap()
+            .children
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn restore_blocks_tasks_of_children_with_dead_sessions() {
+        use crate::models::agent::Agent;
+        use crate::models::task::Task;
+        use crate::store::WorkspaceStore;
+
+        let db = crate::db::Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let mut task = Task::new(
+            "task-1".to_string(),
+            "Implement".to_string(),
+            "objective".to_string(),
+            "default".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        task.status = TaskStatus::InProgress;
+        TaskStore::new(db.clone()).save(&task).await.unwrap();
+        AgentStore::new(db.clone())
+            .save(&Agent::new(
+                "crafter-1".to_string(),
+                "crafter-1".to_string(),
+                AgentRole::Crafter,
+                "default".to_string(),
+                None,
+                None,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        persistent_orchestrator(&db)
+            .await
+            .track_child(child_record("crafter-1", "task-1"), false)
+            .await;
+
+        let restarted = persistent_orchestrator(&db).await;
+        let summary = restarted.restore_delegations().await.unwrap();
+
+        assert_eq!(summary.restored, 0);
+        assert_eq!(summary.orphaned, vec!["crafter-1".to_string()]);
+        assert_eq!(restarted.get_parent_for_child("crafter-1").await, None);
+        let task = TaskStore::new(db.clone())
+            .get("task-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(task.status, TaskStatus::Blocked);
+        assert!(DelegationStore::new(db).list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn delegation_beyond_max_depth_is_rejected() {
+        use crate::models::agent::Agent;
+        use crate::models::task::Task;
+        use crate::store::WorkspaceStore;
+
+        let db = crate::db::Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let task = Task::new(
+            "task-1".to_string(),
+            "Recurse".to_string(),
+            "objective".to_string(),
+            "default".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        TaskStore::new(db.clone()).save(&task).await.unwrap();
+        let mut caller = Agent::new(
+            "crafter-1".to_string(),
+            "crafter-1".to_string(),
+            AgentRole::Crafter,
+            "default".to_string(),
+            Some("routa".to_string()),
+            None,
+            None,
+        );
+        caller.set_delegation_depth(2);
+        let agents = AgentStore::new(db.clone());
+        agents.save(&caller).await.unwrap();
+
+        let orchestrator = RoutaOrchestrator::new(
+            OrchestratorConfig {
+                max_delegation_depth: 2,
+                ..OrchestratorConfig::default()
+            },
+            Arc::new(AcpManager::new()),
+            agents.clone(),
+            TaskStore::new(db.clone()),
+            EventBus::new(),
+        );
+        let params = |caller_depth| DelegateWithSpawnParams {
+            task_id: "task-1".to_string(),
+            caller_agent_id: "crafter-1".to_string(),
+            caller_session_id: "session-crafter-1".to_string(),
+            workspace_id: "default".to_string(),
+            specialist: "CRAFTER".to_string(),
+            provider: None,
+            model_tier: None,
+            cwd: None,
+            additional_instructions: None,
+            wait_mode: "immediate".to_string(),
+            isolate: false,
+            caller_depth,
+        };
+
+        // Depth comes from the caller's agent record when not given.
+        let result = orchestrator
+            .delegate_task_with_spawn(params(None))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Delegation depth limit reached"));
+
+        let result = orchestrator
+            .delegate_task_with_spawn(params(Some(5)))
+            .await
+            .unwrap();
+        assert!(!result.success);
+
+        assert_eq!(agents.list_by_workspace("default").await.unwrap().len(), 1);
+        let task = TaskStore::new(db).get("task-1").await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Pending);
+        assert!(task.assigned_to.is_none());
+    }
+}
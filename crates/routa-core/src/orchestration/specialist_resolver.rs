@@ -0,0 +1,152 @@
+//! One lookup for specialist ids, shared by delegation and workflows.
+//!
+//! Role names and the built-in aliases (`crafter`, `gate`, `developer`,
+//! `reviewer`, ...) resolve to the orchestrator's built-in specialists, so a
+//! workflow step and a delegated task with the same id get the same prompt.
+//! Other ids come from specialist files, then from
+//! [`SpecialistLoader::builtin_specialists`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::models::agent::AgentRole;
+use crate::workflow::specialist::{SpecialistDef, SpecialistExecutionDef, SpecialistLoader};
+
+use super::SpecialistConfig;
+
+/// Resolves specialist ids for [`RoutaOrchestrator`](super::RoutaOrchestrator)
+/// and [`WorkflowExecutor`](crate::workflow::executor::WorkflowExecutor).
+#[derive(Clone, Default)]
+pub struct SpecialistResolver {
+    /// `None` reads the default specialist directories on each lookup, so
+    /// files added while the server runs are picked up.
+    loader: Option<Arc<SpecialistLoader>>,
+}
+
+impl SpecialistResolver {
+    /// Resolve file specialists from `loader` instead of the default
+    /// directories.
+    pub fn with_loader(loader: SpecialistLoader) -> Self {
+        Self {
+            loader: Some(Arc::new(loader)),
+        }
+    }
+
+    /// Resolve file specialists from `dir` instead of the default directories.
+    pub fn with_dir(dir: &str) -> Result<Self, String> {
+        let mut loader = SpecialistLoader::new();
+        loader.load_dir(dir)?;
+        Ok(Self::with_loader(loader))
+    }
+
+    /// The specialist a delegation to `input` (a role name or specialist id)
+    /// runs as.
+    pub fn resolve(&self, input: &str) -> Option<SpecialistConfig> {
+        if let Some(role) = AgentRole::from_str(input) {
+            return SpecialistConfig::by_role(&role);
+        }
+        SpecialistConfig::by_id(input).or_else(|| {
+            self.find_def(input)
+                .and_then(SpecialistConfig::from_specialist_def)
+        })
+    }
+
+    /// Like [`resolve`](Self::resolve), as a full definition for workflow
+    /// steps. Specialists from files keep their execution settings; the
+    /// system prompt is always the one delegation would send.
+    pub fn resolve_def(&self, input: &str) -> Option<SpecialistDef> {
+        if let Some(role) = AgentRole::from_str(input) {
+            return SpecialistConfig::by_role(&role).map(to_specialist_def);
+        }
+        if let Some(builtin) = SpecialistConfig::by_id(input) {
+            return Some(to_specialist_def(builtin));
+        }
+        let mut def = self.find_def(input)?;
+        if let Some(config) = SpecialistConfig::from_specialist_def(def.clone()) {
+            def.system_prompt = config.system_prompt_body().unwrap_or(config.system_prompt);
+        }
+        Some(def)
+    }
+
+    /// Every id [`resolve_def`](Self::resolve_def) accepts, sorted; aliases
+    /// are not listed.
+    pub fn available_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.with_loader_ref(|loader| {
+            loader
+                .all()
+                .keys()
+                .cloned()
+                .chain(
+                    SpecialistLoader::builtin_specialists()
+                        .into_iter()
+                        .map(|def| def.id),
+                )
+                .chain(["crafter", "gate", "developer"].map(String::from))
+                .collect()
+        });
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    fn find_def(&self, input: &str) -> Option<SpecialistDef> {
+        let target = input.to_lowercase();
+        self.with_loader_ref(|loader| loader.get(input).or_else(|| loader.get(&target)).cloned())
+            .or_else(|| {
+                SpecialistLoader::builtin_specialists()
+                    .into_iter()
+                    .find(|def| def.id == target)
+            })
+    }
+
+    fn with_loader_ref<T>(&self, f: impl FnOnce(&SpecialistLoader) -> T) -> T {
+        match &self.loader {
+            Some(loader) => f(loader),
+            None => {
+                let mut loader = SpecialistLoader::new();
+                loader.load_default_dirs();
+                f(&loader)
+            }
+        }
+    }
+}
+
+fn to_specialist_def(config: SpecialistConfig) -> SpecialistDef {
+    SpecialistDef {
+        system_prompt: config
+            .system_prompt_body()
+            .unwrap_or_else(|| config.system_prompt.clone()),
+        id: config.id,
+        name: config.name,
+        description: config.description,
+        role: config.role.as_str().to_string(),
+        model_tier: config.default_model_tier.as_str().to_lowercase(),
+        role_reminder: Some(config.role_reminder).filter(|r| !r.is_empty()),
+        execution: SpecialistExecutionDef::default(),
+        default_provider: config.default_provider,
+        default_adapter: config.default_adapter,
+        default_model: config.default_model,
+        metadata: HashMap::new(),
+        capabilities: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_and_aliases_resolve_to_builtin_specialists() {
+        let resolver = SpecialistResolver::with_loader(SpecialistLoader::new());
+
+        assert_eq!(resolver.resolve("GATE").unwrap().id, "gate");
+        assert_eq!(resolver.resolve("reviewer").unwrap().id, "gate");
+        assert_eq!(resolver.resolve_def("reviewer").unwrap().role, "GATE");
+        assert_eq!(
+            resolver.resolve("issue-refiner").unwrap().role,
+            AgentRole::Developer
+        );
+        assert!(resolver.resolve("ROUTA").is_none());
+        assert!(resolver.resolve_def("no-such-specialist").is_none());
+    }
+}
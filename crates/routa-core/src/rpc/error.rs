@@ -1,8 +1,19 @@
 //! RPC error type that bridges `ServerError` to JSON-RPC errors.
 
+use serde::Serialize;
+
 use super::types;
 use crate::error::ServerError;
 
+/// A parameter that failed to parse or validate, reported to clients in the
+/// JSON-RPC error `data` so forms can point at the offending field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// Path to the parameter, e.g. `title` or `acceptanceCriteria[0]`.
+    pub field: String,
+    pub reason: String,
+}
+
 /// Unified RPC error that can be converted to a JSON-RPC error response.
 #[derive(Debug, thiserror::Error)]
 pub enum RpcError {
@@ -12,12 +23,25 @@ pub enum RpcError {
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 
     #[error("Invalid params: {0}")]
     InvalidParams(String),
 
+    /// Invalid params with a per-field breakdown in the response `data`.
+    #[error("Invalid params: {message}")]
+    InvalidFields {
+        message: String,
+        fields: Vec<FieldError>,
+    },
+
     #[error("Method not found: {0}")]
     MethodNotFound(String),
 }
@@ -28,15 +52,60 @@ impl RpcError {
         match self {
             RpcError::NotFound(_) => types::NOT_FOUND,
             RpcError::BadRequest(_) => types::BAD_REQUEST,
+            RpcError::Unauthorized(_) => types::UNAUTHORIZED,
+            RpcError::Forbidden(_) => types::FORBIDDEN,
             RpcError::Internal(_) => types::INTERNAL_ERROR,
-            RpcError::InvalidParams(_) => types::INVALID_PARAMS,
+            RpcError::InvalidParams(_) | RpcError::InvalidFields { .. } => types::INVALID_PARAMS,
             RpcError::MethodNotFound(_) => types::METHOD_NOT_FOUND,
         }
     }
 
     /// Convert to a JSON-RPC error response.
     pub fn to_response(&self, id: Option<serde_json::Value>) -> types::JsonRpcResponse {
-        types::JsonRpcResponse::error(id, self.code(), self.to_string())
+        match self {
+            RpcError::InvalidFields { fields, .. } => types::JsonRpcResponse::error_with_data(
+                id,
+                self.code(),
+                self.to_string(),
+                serde_json::to_value(fields).unwrap_or_default(),
+            ),
+            _ => types::JsonRpcResponse::error(id, self.code(), self.to_string()),
+        }
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for RpcError {
+    /// Name the failing parameter from the deserializer's path. serde reports
+    /// missing and unknown fields against the enclosing object, so the field
+    /// name is taken from the message in those cases.
+    fn from(err: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let path = err.path().to_string();
+        let inner = err.into_inner().to_string();
+        let named = |prefix: &str| {
+            inner
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.split('`').next())
+                .map(|name| match path.as_str() {
+                    "." => name.to_string(),
+                    parent => format!("{parent}.{name}"),
+                })
+        };
+        let (field, reason) = if let Some(field) = named("missing field `") {
+            (field, "is required".to_string())
+        } else if let Some(field) = named("unknown field `") {
+            (field, "is not a recognized parameter".to_string())
+        } else {
+            (path.clone(), inner.clone())
+        };
+        let message = if path == "." {
+            inner
+        } else {
+            format!("{path}: {inner}")
+        };
+        RpcError::InvalidFields {
+            message,
+            fields: vec![FieldError { field, reason }],
+        }
     }
 }
 
@@ -46,9 +115,28 @@ impl From<ServerError> for RpcError {
             ServerError::NotFound(msg) => RpcError::NotFound(msg),
             ServerError::BadRequest(msg) => RpcError::BadRequest(msg),
             ServerError::Conflict(msg) => RpcError::BadRequest(msg),
+            ServerError::Unauthorized(msg) => RpcError::Unauthorized(msg),
+            ServerError::Forbidden(msg) => RpcError::Forbidden(msg),
             ServerError::Database(msg) => RpcError::Internal(msg),
             ServerError::Internal(msg) => RpcError::Internal(msg),
             ServerError::NotImplemented(msg) => RpcError::Internal(msg),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auth_errors_keep_distinct_codes() {
+        let unauthorized = RpcError::from(ServerError::Unauthorized("missing token".into()));
+        assert_eq!(unauthorized.code(), types::UNAUTHORIZED);
+        let forbidden = RpcError::from(ServerError::Forbidden("outside repo".into()));
+        assert_eq!(forbidden.code(), types::FORBIDDEN);
+        assert_eq!(
+            forbidden.to_response(None).error.unwrap().message,
+            "Forbidden: outside repo"
+        );
+    }
+}
@@ -2,17 +2,23 @@
 //!
 //! Methods:
 //! - `agents.list`         — list agents with optional filters
+//! - `agents.tree`         — delegation hierarchy for a workspace
 //! - `agents.get`          — get a single agent by id
 //! - `agents.create`       — create a new agent
 //! - `agents.delete`       — delete an agent
 //! - `agents.updateStatus` — update an agent's status
+//! - `agents.redactMessage` — redact a message in an agent's conversation
+//! - `agents.reportToParent` — submit a completion report to the parent agent
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::models::agent::{Agent, AgentRole, AgentStatus, ModelTier};
+use crate::models::agent_tree::{build_agent_tree, AgentTreeNode};
 use crate::rpc::error::RpcError;
 use crate::state::AppState;
+use crate::store::{AgentListFilter, AgentOrderBy};
+use crate::tools::CompletionReport;
 
 // ---------------------------------------------------------------------------
 // agents.list
@@ -26,6 +32,8 @@ pub struct ListParams {
     pub role: Option<String>,
     pub status: Option<String>,
     pub parent_id: Option<String>,
+    /// `createdAt` (default) or `updatedAt`; results are newest first.
+    pub order_by: Option<String>,
 }
 
 fn default_workspace_id() -> String {
@@ -38,32 +46,79 @@ pub struct ListResult {
 }
 
 pub async fn list(state: &AppState, params: ListParams) -> Result<ListResult, RpcError> {
-    let agents = if let Some(parent_id) = &params.parent_id {
-        state.agent_store.list_by_parent(parent_id).await?
-    } else if let Some(role_str) = &params.role {
-        let role = AgentRole::from_str(role_str)
-            .ok_or_else(|| RpcError::BadRequest(format!("Invalid role: {role_str}")))?;
-        state
-            .agent_store
-            .list_by_role(&params.workspace_id, &role)
-            .await?
-    } else if let Some(status_str) = &params.status {
-        let status = AgentStatus::from_str(status_str)
-            .ok_or_else(|| RpcError::BadRequest(format!("Invalid status: {status_str}")))?;
-        state
-            .agent_store
-            .list_by_status(&params.workspace_id, &status)
-            .await?
-    } else {
-        state
-            .agent_store
-            .list_by_workspace(&params.workspace_id)
-            .await?
+    let role = params
+        .role
+        .as_deref()
+        .map(|role_str| {
+            AgentRole::from_str(role_str)
+                .ok_or_else(|| RpcError::BadRequest(format!("Invalid role: {role_str}")))
+        })
+        .transpose()?;
+    let status = params
+        .status
+        .as_deref()
+        .map(|status_str| {
+            AgentStatus::from_str(status_str)
+                .ok_or_else(|| RpcError::BadRequest(format!("Invalid status: {status_str}")))
+        })
+        .transpose()?;
+    let order_by = params
+        .order_by
+        .as_deref()
+        .map(|order_str| {
+            AgentOrderBy::from_str(order_str)
+                .ok_or_else(|| RpcError::BadRequest(format!("Invalid orderBy: {order_str}")))
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    // Children of a parent are looked up across the parent's workspace, so the
+    // defaulted workspace id is only applied when no parent is given.
+    let filter = AgentListFilter {
+        workspace_id: params
+            .parent_id
+            .is_none()
+            .then(|| params.workspace_id.clone()),
+        role,
+        status,
+        parent_id: params.parent_id,
+        order_by,
     };
+    let agents = state.agent_store.list_filtered(&filter).await?;
 
     Ok(ListResult { agents })
 }
 
+// ---------------------------------------------------------------------------
+// agents.tree
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeParams {
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TreeResult {
+    pub roots: Vec<AgentTreeNode>,
+}
+
+pub async fn tree(state: &AppState, params: TreeParams) -> Result<TreeResult, RpcError> {
+    let agents = state
+        .agent_store
+        .list_by_workspace(&params.workspace_id)
+        .await?;
+    let tasks = state
+        .task_store
+        .list_by_workspace(&params.workspace_id)
+        .await?;
+    Ok(TreeResult {
+        roots: build_agent_tree(agents, &tasks),
+    })
+}
+
 // ---------------------------------------------------------------------------
 // agents.get
 // ---------------------------------------------------------------------------
@@ -175,3 +230,77 @@ pub async fn update_status(
     state.agent_store.update_status(&params.id, &status).await?;
     Ok(UpdateStatusResult { updated: true })
 }
+
+// ---------------------------------------------------------------------------
+// agents.redactMessage
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedactMessageParams {
+    /// Owning agent; the message must belong to this agent's conversation.
+    pub agent_id: String,
+    pub message_id: String,
+    /// Who requested the redaction, recorded in the audit log.
+    pub actor: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RedactMessageResult {
+    pub redacted: bool,
+}
+
+pub async fn redact_message(
+    state: &AppState,
+    params: RedactMessageParams,
+) -> Result<RedactMessageResult, RpcError> {
+    if params.actor.trim().is_empty() {
+        return Err(RpcError::BadRequest(
+            "actor is required to redact a message".to_string(),
+        ));
+    }
+
+    let message = state
+        .conversation_store
+        .get_message(&params.message_id)
+        .await?
+        .filter(|message| message.agent_id == params.agent_id)
+        .ok_or_else(|| {
+            RpcError::NotFound(format!(
+                "Message {} not found for agent {}",
+                params.message_id, params.agent_id
+            ))
+        })?;
+
+    let redacted = state
+        .conversation_store
+        .redact_message(&message.id, &params.actor, params.reason.as_deref())
+        .await?;
+    Ok(RedactMessageResult { redacted })
+}
+
+// ---------------------------------------------------------------------------
+// agents.reportToParent
+// ---------------------------------------------------------------------------
+
+/// Takes the same arguments as the `report_to_parent` MCP tool: `agentId`,
+/// `taskId`, `summary`, `success`, `filesModified`, or a free-text `report`
+/// when the structured fields are incomplete.
+pub async fn report_to_parent(
+    state: &AppState,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, RpcError> {
+    let agent_id = params
+        .get("agentId")
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.trim().is_empty())
+        .ok_or_else(|| RpcError::BadRequest("agentId is required".to_string()))?;
+    let report = CompletionReport::from_tool_args(agent_id, &params);
+    let result = state.report_to_parent(agent_id, report).await?;
+    if result.success {
+        Ok(result.data.unwrap_or(serde_json::Value::Null))
+    } else {
+        Err(RpcError::BadRequest(result.error.unwrap_or_default()))
+    }
+}
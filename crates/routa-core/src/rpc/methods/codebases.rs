@@ -0,0 +1,192 @@
+//! RPC methods for codebase branch tracking.
+//!
+//! Methods:
+//! - `codebases.checkout`      — check out a branch in the repo and store it on the codebase
+//! - `codebases.currentBranch` — the branch actually checked out in the repo
+
+use serde::{Deserialize, Serialize};
+
+use crate::git;
+use crate::models::codebase::Codebase;
+use crate::rpc::error::RpcError;
+use crate::state::AppState;
+
+async fn load_codebase(state: &AppState, id: &str) -> Result<Codebase, RpcError> {
+    state
+        .codebase_store
+        .get(id)
+        .await?
+        .ok_or_else(|| RpcError::NotFound(format!("Codebase {id} not found")))
+}
+
+// ---------------------------------------------------------------------------
+// codebases.checkout
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutParams {
+    pub id: String,
+    pub branch: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckoutResult {
+    pub codebase: Codebase,
+}
+
+/// Run `git checkout <branch>` in the codebase's repo, then record the branch.
+/// Refuses to switch while tracked files have uncommitted changes.
+pub async fn checkout(
+    state: &AppState,
+    params: CheckoutParams,
+) -> Result<CheckoutResult, RpcError> {
+    let codebase = load_codebase(state, &params.id).await?;
+    let repo_path = codebase.repo_path.as_str();
+    let branch = params.branch.trim();
+    if branch.is_empty() {
+        return Err(RpcError::BadRequest("branch must not be empty".to_string()));
+    }
+
+    let dirty_error = || {
+        RpcError::BadRequest(format!(
+            "Cannot check out '{branch}': {repo_path} has uncommitted changes. Commit or stash them first."
+        ))
+    };
+    if git::get_repo_status(repo_path).modified > 0 {
+        return Err(dirty_error());
+    }
+    git::checkout_existing_branch(repo_path, branch).map_err(|error| {
+        if error.contains("would be overwritten") {
+            dirty_error()
+        } else {
+            RpcError::BadRequest(format!("Cannot check out '{branch}': {error}"))
+        }
+    })?;
+
+    state
+        .codebase_store
+        .update(&codebase.id, Some(branch), None, None, None, None)
+        .await?;
+    Ok(CheckoutResult {
+        codebase: load_codebase(state, &codebase.id).await?,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// codebases.currentBranch
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentBranchParams {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentBranchResult {
+    /// Branch checked out in the repo; `None` if it cannot be read.
+    pub branch: Option<String>,
+    /// Branch stored on the codebase record.
+    pub stored_branch: Option<String>,
+    pub in_sync: bool,
+}
+
+pub async fn current_branch(
+    state: &AppState,
+    params: CurrentBranchParams,
+) -> Result<CurrentBranchResult, RpcError> {
+    let codebase = load_codebase(state, &params.id).await?;
+    let branch = git::get_current_branch(&codebase.repo_path);
+    Ok(CurrentBranchResult {
+        in_sync: branch.is_some() && branch == codebase.branch,
+        branch,
+        stored_branch: codebase.branch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::workspace::Workspace;
+    use crate::{AppStateInner, Database};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = git::git_command()
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed: {output:?}");
+    }
+
+    #[tokio::test]
+    async fn checkout_switches_branch_and_records_it() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = temp.path();
+        git(repo, &["init", "-b", "main"]);
+        git(repo, &["config", "user.name", "Test User"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        std::fs::write(repo.join("README.md"), "hello\n").unwrap();
+        git(repo, &["add", "README.md"]);
+        git(repo, &["commit", "-m", "init"]);
+        git(repo, &["branch", "feature"]);
+
+        let state: AppState = Arc::new(AppStateInner::new(Database::open_in_memory().unwrap()));
+        state
+            .workspace_store
+            .save(&Workspace::new("ws".to_string(), "ws".to_string(), None))
+            .await
+            .unwrap();
+        let codebase = Codebase::new(
+            "cb".to_string(),
+            "ws".to_string(),
+            repo.to_string_lossy().to_string(),
+            Some("main".to_string()),
+            None,
+            true,
+            None,
+            None,
+        );
+        state.codebase_store.save(&codebase).await.unwrap();
+
+        let result = checkout(
+            &state,
+            CheckoutParams {
+                id: "cb".to_string(),
+                branch: "feature".to_string(),
+            },
+        )
+        .await
+        .expect("checkout should succeed");
+        assert_eq!(result.codebase.branch.as_deref(), Some("feature"));
+
+        let current = current_branch(
+            &state,
+            CurrentBranchParams {
+                id: "cb".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(current.branch.as_deref(), Some("feature"));
+        assert!(current.in_sync);
+
+        std::fs::write(repo.join("README.md"), "changed\n").unwrap();
+        let dirty = checkout(
+            &state,
+            CheckoutParams {
+                id: "cb".to_string(),
+                branch: "main".to_string(),
+            },
+        )
+        .await;
+        assert!(
+            matches!(dirty, Err(RpcError::BadRequest(ref message)) if message.contains("uncommitted changes")),
+            "{dirty:?}"
+        );
+    }
+}
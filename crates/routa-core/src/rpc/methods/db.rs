@@ -0,0 +1,63 @@
+//! RPC methods for the database file.
+//!
+//! Methods:
+//! - `db.backup` — snapshot the live database to a new file in
+//!   `~/.routa/backups`
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::{backup, BackupInfo};
+use crate::rpc::error::RpcError;
+use crate::state::AppState;
+
+// ---------------------------------------------------------------------------
+// db.backup
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupParams {
+    /// File name of the backup inside `~/.routa/backups`; must not exist
+    /// yet.
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupResult {
+    pub backup: BackupInfo,
+}
+
+pub async fn backup(state: &AppState, params: BackupParams) -> Result<BackupResult, RpcError> {
+    let path = backup::backup_path(params.name.trim())?;
+    std::fs::create_dir_all(backup::backup_dir())
+        .map_err(|e| RpcError::Internal(format!("Cannot create backup directory: {e}")))?;
+    let backup = state.db.backup_to(&path).await?;
+    Ok(BackupResult { backup })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AppStateInner, Database};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn backup_rejects_names_outside_the_backup_dir() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+
+        for name in ["", "../routa.db", "/tmp/routa.db", "nested/routa.db"] {
+            let result = backup(
+                &state,
+                BackupParams {
+                    name: name.to_string(),
+                },
+            )
+            .await;
+            assert!(
+                matches!(result, Err(RpcError::BadRequest(_))),
+                "{name:?} should be rejected"
+            );
+        }
+    }
+}
@@ -572,6 +572,7 @@ async fn trigger_assigned_task_acp_agent(
             custom_command: None,
             custom_args: None,
             parent_session_id: None,
+            metadata: None,
         })
         .await
         .map_err(|error| format!("Failed to persist ACP session: {error}"))?;
@@ -4,7 +4,7 @@ use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
-use crate::events::{AgentEvent, AgentEventType};
+use crate::events::{AgentEvent, KanbanChangedData};
 use crate::models::kanban::{KanbanAutomationStep, KanbanBoard, KanbanTransport};
 use crate::models::task::{
     build_task_evidence_summary, build_task_invest_validation, build_task_story_readiness, Task,
@@ -455,19 +455,16 @@ async fn wait_for_task_persistence(
 async fn emit_kanban_workspace_event(state: &AppState, workspace_id: &str, task_id: &str) {
     state
         .event_bus
-        .emit(AgentEvent {
-            event_type: AgentEventType::WorkspaceUpdated,
-            agent_id: "kanban-a2a".to_string(),
-            workspace_id: workspace_id.to_string(),
-            data: serde_json::json!({
-                "scope": "kanban",
-                "entity": "task",
-                "action": "updated",
-                "resourceId": task_id,
-                "source": "system",
-            }),
-            timestamp: Utc::now(),
-        })
+        .emit(AgentEvent::kanban_changed(
+            "kanban-a2a",
+            workspace_id,
+            KanbanChangedData {
+                entity: "task".to_string(),
+                action: "updated".to_string(),
+                resource_id: Some(task_id.to_string()),
+                source: "system".to_string(),
+            },
+        ))
         .await;
 }
 
@@ -1,8 +1,7 @@
-use chrono::Utc;
 use std::collections::HashSet;
 
 use crate::error::ServerError;
-use crate::events::{AgentEvent, AgentEventType};
+use crate::events::{AgentEvent, KanbanChangedData};
 use crate::models::kanban::{KanbanBoard, KanbanColumn};
 use crate::models::task::{Task, TaskCreationSource, TaskPriority};
 use crate::rpc::error::RpcError;
@@ -22,19 +21,16 @@ pub(super) async fn emit_kanban_workspace_event(
 ) {
     state
         .event_bus
-        .emit(AgentEvent {
-            event_type: AgentEventType::WorkspaceUpdated,
-            agent_id: format!("kanban-{source}"),
-            workspace_id: workspace_id.to_string(),
-            data: serde_json::json!({
-                "scope": "kanban",
-                "entity": entity,
-                "action": action,
-                "resourceId": resource_id,
-                "source": source,
-            }),
-            timestamp: Utc::now(),
-        })
+        .emit(AgentEvent::kanban_changed(
+            format!("kanban-{source}"),
+            workspace_id,
+            KanbanChangedData {
+                entity: entity.to_string(),
+                action: action.to_string(),
+                resource_id: resource_id.map(str::to_string),
+                source: source.to_string(),
+            },
+        ))
         .await;
 }
 
@@ -4,8 +4,13 @@
 //! function that takes `AppState` + params and returns a `serde_json::Value`.
 
 pub mod agents;
+pub mod codebases;
+pub mod db;
 pub mod kanban;
 pub mod notes;
+pub mod orchestration;
+pub mod providers;
+pub mod sessions;
 pub mod skills;
 pub mod tasks;
 pub mod workspaces;
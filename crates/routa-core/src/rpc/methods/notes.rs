@@ -3,14 +3,20 @@
 //! Methods:
 //! - `notes.list`   — list notes with optional filters
 //! - `notes.get`    — get a single note
+//! - `notes.assignedTo` — list notes assigned to an agent
 //! - `notes.create` — create or update a note
+//! - `notes.rename` — change a note's title
 //! - `notes.delete` — delete a note
+//! - `notes.history` — list a note's content versions
+//! - `notes.revert` — restore a note's content to an earlier version
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
-use crate::models::note::{Note, NoteMetadata, NoteType};
+use crate::models::note::{Note, NoteMetadata, NoteType, NoteVersion};
 use crate::rpc::error::RpcError;
 use crate::state::AppState;
+use crate::store::TimeRange;
 
 // ---------------------------------------------------------------------------
 // notes.list
@@ -23,6 +29,10 @@ pub struct ListParams {
     pub workspace_id: String,
     #[serde(rename = "type")]
     pub note_type: Option<String>,
+    /// `createdAfter`/`createdBefore`/`updatedAfter`/`updatedBefore` bounds.
+    /// When any is set, results are ordered by `updatedAt`, newest first.
+    #[serde(flatten)]
+    pub range: TimeRange,
 }
 
 fn default_workspace_id() -> String {
@@ -35,7 +45,20 @@ pub struct ListResult {
 }
 
 pub async fn list(state: &AppState, params: ListParams) -> Result<ListResult, RpcError> {
-    let notes = if let Some(type_str) = &params.note_type {
+    let notes = if !params.range.is_unbounded() {
+        let note_type = params.note_type.as_deref().map(NoteType::from_str);
+        state
+            .note_store
+            .list_in_range(&params.workspace_id, &params.range)
+            .await?
+            .into_iter()
+            .filter(|note| {
+                note_type
+                    .as_ref()
+                    .is_none_or(|note_type| &note.metadata.note_type == note_type)
+            })
+            .collect()
+    } else if let Some(type_str) = &params.note_type {
         let note_type = NoteType::from_str(type_str);
         state
             .note_store
@@ -71,6 +94,29 @@ pub async fn get(state: &AppState, params: GetParams) -> Result<Note, RpcError>
         .ok_or_else(|| RpcError::NotFound(format!("Note {} not found", params.note_id)))
 }
 
+// ---------------------------------------------------------------------------
+// notes.assignedTo
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignedToParams {
+    pub agent_id: String,
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+}
+
+pub async fn assigned_to(
+    state: &AppState,
+    params: AssignedToParams,
+) -> Result<ListResult, RpcError> {
+    let notes = state
+        .note_store
+        .list_assigned_to(&params.agent_id, &params.workspace_id)
+        .await?;
+    Ok(ListResult { notes })
+}
+
 // ---------------------------------------------------------------------------
 // notes.create
 // ---------------------------------------------------------------------------
@@ -86,11 +132,19 @@ pub struct CreateParams {
     #[serde(rename = "type")]
     pub note_type: Option<String>,
     pub metadata: Option<NoteMetadata>,
+    /// Return an existing general note with the same content instead of
+    /// creating a duplicate. Ignored for spec and task notes.
+    #[serde(default)]
+    pub dedup: bool,
+    /// Agent recorded as the author of this content version.
+    pub author_agent_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct CreateResult {
     pub note: Note,
+    /// True when `note` is an existing note returned by `dedup`.
+    pub deduplicated: bool,
 }
 
 pub async fn create(state: &AppState, params: CreateParams) -> Result<CreateResult, RpcError> {
@@ -115,8 +169,57 @@ pub async fn create(state: &AppState, params: CreateParams) -> Result<CreateResu
         Some(metadata),
     );
 
-    state.note_store.save(&note).await?;
-    Ok(CreateResult { note })
+    if params.dedup {
+        let stored = state.note_store.save_deduplicated(&note).await?;
+        let deduplicated = stored.id != note.id;
+        return Ok(CreateResult {
+            note: stored,
+            deduplicated,
+        });
+    }
+
+    let note = state
+        .note_store
+        .save_as(&note, params.author_agent_id.as_deref())
+        .await?;
+    Ok(CreateResult {
+        note,
+        deduplicated: false,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// notes.rename
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameParams {
+    pub note_id: String,
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+    pub title: String,
+}
+
+/// Change only the title; content and its version history are untouched.
+pub async fn rename(state: &AppState, params: RenameParams) -> Result<Note, RpcError> {
+    let title = params.title.trim();
+    if title.is_empty() {
+        return Err(RpcError::BadRequest(
+            "Note title cannot be empty".to_string(),
+        ));
+    }
+    let mut note = get(
+        state,
+        GetParams {
+            note_id: params.note_id,
+            workspace_id: params.workspace_id,
+        },
+    )
+    .await?;
+    note.title = title.to_string();
+    note.updated_at = Utc::now();
+    Ok(state.note_store.save(&note).await?)
 }
 
 // ---------------------------------------------------------------------------
@@ -148,3 +251,182 @@ pub async fn delete(state: &AppState, params: DeleteParams) -> Result<DeleteResu
         note_id: params.note_id,
     })
 }
+
+// ---------------------------------------------------------------------------
+// notes.history
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryParams {
+    pub note_id: String,
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryResult {
+    pub note_id: String,
+    pub versions: Vec<NoteVersion>,
+}
+
+pub async fn history(state: &AppState, params: HistoryParams) -> Result<HistoryResult, RpcError> {
+    let versions = state
+        .note_store
+        .history(&params.note_id, &params.workspace_id)
+        .await?;
+    Ok(HistoryResult {
+        note_id: params.note_id,
+        versions,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// notes.revert
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertParams {
+    pub note_id: String,
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+    pub version: i64,
+    pub author_agent_id: Option<String>,
+}
+
+pub async fn revert(state: &AppState, params: RevertParams) -> Result<Note, RpcError> {
+    state
+        .note_store
+        .revert(
+            &params.note_id,
+            &params.workspace_id,
+            params.version,
+            params.author_agent_id.as_deref(),
+        )
+        .await?
+        .ok_or_else(|| {
+            RpcError::NotFound(format!(
+                "Version {} of note {} not found",
+                params.version, params.note_id
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AppStateInner, Database};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn rename_keeps_content_and_history() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let mut note = Note::new(
+            "note-1".to_string(),
+            "Draft".to_string(),
+            "Decisions so far".to_string(),
+            "default".to_string(),
+            None,
+        );
+        note.updated_at -= chrono::Duration::seconds(60);
+        state
+            .note_store
+            .save(&note)
+            .await
+            .expect("note should save");
+
+        let renamed = rename(
+            &state,
+            RenameParams {
+                note_id: note.id.clone(),
+                workspace_id: "default".to_string(),
+                title: "Architecture decisions".to_string(),
+            },
+        )
+        .await
+        .expect("rename should succeed");
+        assert_eq!(renamed.title, "Architecture decisions");
+        assert!(renamed.updated_at > note.updated_at);
+
+        let stored = get(
+            &state,
+            GetParams {
+                note_id: note.id.clone(),
+                workspace_id: "default".to_string(),
+            },
+        )
+        .await
+        .expect("note should exist");
+        assert_eq!(stored.title, "Architecture decisions");
+        assert_eq!(stored.content, "Decisions so far");
+        let versions = state
+            .note_store
+            .history(&note.id, "default")
+            .await
+            .expect("history should load");
+        assert_eq!(versions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn second_spec_create_updates_the_existing_spec() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let create_spec = |content: &str| CreateParams {
+            note_id: None,
+            title: "Spec".to_string(),
+            content: Some(content.to_string()),
+            workspace_id: "default".to_string(),
+            note_type: Some("spec".to_string()),
+            metadata: None,
+            dedup: false,
+            author_agent_id: None,
+        };
+
+        let first = create(&state, create_spec("# Goal\n\nFirst"))
+            .await
+            .expect("first spec should be created");
+        let second = create(&state, create_spec("# Goal\n\nSecond"))
+            .await
+            .expect("second spec should update the first");
+        assert_eq!(first.note.id, crate::models::note::SPEC_NOTE_ID);
+        assert_eq!(second.note.id, first.note.id);
+        assert_eq!(second.note.created_at, first.note.created_at);
+        assert_eq!(second.note.content, "# Goal\n\nSecond");
+
+        for _ in 0..2 {
+            create(
+                &state,
+                CreateParams {
+                    note_type: Some("general".to_string()),
+                    ..create_spec("Scratch")
+                },
+            )
+            .await
+            .expect("general note should be created");
+        }
+        let notes = state
+            .note_store
+            .list_by_workspace("default")
+            .await
+            .expect("notes should list");
+        let specs = notes
+            .iter()
+            .filter(|note| note.metadata.note_type == NoteType::Spec)
+            .count();
+        assert_eq!(specs, 1);
+        assert_eq!(notes.len(), 3);
+    }
+}
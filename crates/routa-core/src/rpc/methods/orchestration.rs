@@ -0,0 +1,46 @@
+//! RPC methods for live delegation state.
+//!
+//! Methods:
+//! - `orchestration.status` — in-flight child agents and waiting `after_all`
+//!   groups for a workspace
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::orchestration::{OrchestrationStatus, RoutaOrchestrator};
+use crate::rpc::error::RpcError;
+use crate::state::AppState;
+
+// ---------------------------------------------------------------------------
+// orchestration.status
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusParams {
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+}
+
+fn default_workspace_id() -> String {
+    "default".into()
+}
+
+/// The delegation store also holds delegations made by other processes
+/// (the CLI, for one), so the view is rebuilt from it every call.
+pub async fn status(
+    state: &AppState,
+    params: StatusParams,
+) -> Result<OrchestrationStatus, RpcError> {
+    let orchestrator = RoutaOrchestrator::new(
+        state.orchestrator_config.clone(),
+        Arc::new(state.acp_manager.clone()),
+        state.agent_store.clone(),
+        state.task_store.clone(),
+        state.event_bus.clone(),
+    )
+    .with_delegation_store(state.delegation_store.clone());
+    orchestrator.load_delegations().await?;
+    Ok(orchestrator.status(&params.workspace_id).await?)
+}
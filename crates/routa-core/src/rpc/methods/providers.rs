@@ -0,0 +1,367 @@
+//! RPC methods for ACP providers.
+//!
+//! Methods:
+//! - `providers.list` — builtin presets, registry agents and other installed
+//!   agents in one list, with install and availability status
+//! - `providers.setCredential` — store an API key (or other env var) for a
+//!   provider, encrypted at rest
+//! - `providers.getMasked` — list a provider's stored credentials, masked
+//! - `providers.refreshModels` — re-list a provider's models, bypassing the
+//!   model cache
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::acp::provider_models::supports_model_listing;
+use crate::acp::{
+    current_platform, fetch_registry, get_presets, AcpPreset, AcpRegistry, DistributionType,
+    InstalledAgentInfo, ProviderModels,
+};
+use crate::rpc::error::RpcError;
+use crate::shell_env;
+use crate::state::AppState;
+use crate::store::MaskedCredential;
+
+/// How long a fetched registry is reused before fetching it again.
+const REGISTRY_CACHE_TTL: Duration = Duration::from_secs(300);
+
+static REGISTRY_CACHE: Mutex<Option<(Instant, AcpRegistry)>> = Mutex::new(None);
+
+// ---------------------------------------------------------------------------
+// providers.list
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderSource {
+    /// A static preset shipped with Routa.
+    Builtin,
+    /// An agent from the ACP registry.
+    Registry,
+    /// An installed agent that is neither builtin nor in the registry.
+    Custom,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderEntry {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub command: String,
+    pub source: ProviderSource,
+    /// Recorded as installed in the ACP installation state.
+    pub installed: bool,
+    /// The command needed to launch it is on `PATH` (or, for binaries, on disk).
+    pub available: bool,
+    pub dist_type: Option<DistributionType>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListResult {
+    pub providers: Vec<ProviderEntry>,
+}
+
+pub async fn list(state: &AppState) -> Result<ListResult, RpcError> {
+    if let Err(e) = state.acp_installation_state.load().await {
+        tracing::warn!("[providers.list] Ignoring installation state: {}", e);
+    }
+    let installed = state.acp_installation_state.get_all_installed().await;
+    let registry = cached_registry().await;
+    let providers = merge_providers(&get_presets(), registry.as_ref(), &installed, |command| {
+        shell_env::which(command).is_some()
+    });
+    Ok(ListResult { providers })
+}
+
+/// The ACP registry, refetched at most every [`REGISTRY_CACHE_TTL`].
+/// `None` when it cannot be fetched and nothing is cached.
+async fn cached_registry() -> Option<AcpRegistry> {
+    if let Some((fetched_at, registry)) = REGISTRY_CACHE.lock().unwrap().as_ref() {
+        if fetched_at.elapsed() < REGISTRY_CACHE_TTL {
+            return Some(registry.clone());
+        }
+    }
+    match fetch_registry().await {
+        Ok(registry) => {
+            *REGISTRY_CACHE.lock().unwrap() = Some((Instant::now(), registry.clone()));
+            Some(registry)
+        }
+        Err(e) => {
+            tracing::warn!("[providers.list] Registry unavailable: {}", e);
+            REGISTRY_CACHE
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|(_, registry)| registry.clone())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// providers.setCredential
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCredentialParams {
+    pub provider: String,
+    /// Environment variable name, e.g. `OPENAI_API_KEY`.
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetCredentialResult {
+    pub credential: MaskedCredential,
+}
+
+pub async fn set_credential(
+    state: &AppState,
+    params: SetCredentialParams,
+) -> Result<SetCredentialResult, RpcError> {
+    let credential = state
+        .provider_credential_store
+        .set(&params.provider, &params.key, &params.value)
+        .await?;
+    Ok(SetCredentialResult { credential })
+}
+
+// ---------------------------------------------------------------------------
+// providers.getMasked
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMaskedParams {
+    pub provider: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetMaskedResult {
+    pub credentials: Vec<MaskedCredential>,
+}
+
+pub async fn get_masked(
+    state: &AppState,
+    params: GetMaskedParams,
+) -> Result<GetMaskedResult, RpcError> {
+    let credentials = state
+        .provider_credential_store
+        .list_masked(&params.provider)
+        .await?;
+    Ok(GetMaskedResult { credentials })
+}
+
+// ---------------------------------------------------------------------------
+// providers.refreshModels
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshModelsParams {
+    pub provider: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshModelsResult {
+    pub provider: String,
+    #[serde(flatten)]
+    pub models: ProviderModels,
+}
+
+pub async fn refresh_models(
+    state: &AppState,
+    params: RefreshModelsParams,
+) -> Result<RefreshModelsResult, RpcError> {
+    if !supports_model_listing(&params.provider) {
+        return Err(RpcError::BadRequest(format!(
+            "Provider does not support model listing: {}",
+            params.provider
+        )));
+    }
+    let (models, _) = state
+        .provider_models_cache
+        .models(&params.provider, true)
+        .await
+        .map_err(RpcError::Internal)?;
+    Ok(RefreshModelsResult {
+        provider: params.provider,
+        models,
+    })
+}
+
+/// Merge the three provider sources. Registry agents sharing an ID with a
+/// builtin preset are listed as `{id}-registry`, matching
+/// [`get_preset_by_id_with_registry`](crate::acp::get_preset_by_id_with_registry).
+fn merge_providers(
+    presets: &[AcpPreset],
+    registry: Option<&AcpRegistry>,
+    installed: &[InstalledAgentInfo],
+    on_path: impl Fn(&str) -> bool,
+) -> Vec<ProviderEntry> {
+    let installed_info = |id: &str| installed.iter().find(|info| info.agent_id == id);
+    let dist_available =
+        |dist_type: &DistributionType, info: Option<&InstalledAgentInfo>| match dist_type {
+            DistributionType::Npx => on_path("npx"),
+            DistributionType::Uvx => on_path("uv"),
+            DistributionType::Binary => info
+                .and_then(|info| info.binary_path.as_deref())
+                .is_some_and(|path| std::path::Path::new(path).is_file()),
+        };
+
+    let mut providers: Vec<ProviderEntry> = presets
+        .iter()
+        .map(|preset| ProviderEntry {
+            id: preset.id.clone(),
+            name: preset.name.clone(),
+            description: preset.description.clone(),
+            command: preset.command.clone(),
+            source: ProviderSource::Builtin,
+            installed: installed_info(&preset.id).is_some(),
+            available: on_path(&preset.command),
+            dist_type: None,
+        })
+        .collect();
+    let builtin_ids: HashSet<String> = presets.iter().map(|preset| preset.id.clone()).collect();
+    let mut known_ids = builtin_ids.clone();
+
+    for agent in registry
+        .map(|registry| registry.agents.as_slice())
+        .unwrap_or_default()
+    {
+        known_ids.insert(agent.id.clone());
+        let info = installed_info(&agent.id);
+        let dist_type = agent.dist_type();
+        let command = agent
+            .get_command(info.and_then(|info| info.binary_path.as_deref()))
+            .map(|(command, args)| {
+                std::iter::once(command)
+                    .chain(args)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .or_else(|| {
+                agent
+                    .distribution
+                    .binary
+                    .as_ref()
+                    .and_then(|binaries| binaries.get(current_platform()))
+                    .and_then(|binary| binary.cmd.clone())
+            })
+            .unwrap_or_else(|| agent.id.clone());
+        let (id, name) = if builtin_ids.contains(&agent.id) {
+            (
+                format!("{}-registry", agent.id),
+                format!("{} (Registry)", agent.name),
+            )
+        } else {
+            (agent.id.clone(), agent.name.clone())
+        };
+        providers.push(ProviderEntry {
+            id,
+            name,
+            description: agent.description.clone(),
+            command,
+            source: ProviderSource::Registry,
+            installed: info.is_some(),
+            available: dist_type
+                .as_ref()
+                .is_some_and(|dist_type| dist_available(dist_type, info)),
+            dist_type,
+        });
+    }
+
+    for info in installed
+        .iter()
+        .filter(|info| !known_ids.contains(&info.agent_id))
+    {
+        providers.push(ProviderEntry {
+            id: info.agent_id.clone(),
+            name: info.agent_id.clone(),
+            description: String::new(),
+            command: info
+                .binary_path
+                .clone()
+                .or_else(|| info.package.clone())
+                .unwrap_or_else(|| info.agent_id.clone()),
+            source: ProviderSource::Custom,
+            installed: true,
+            available: dist_available(&info.dist_type, Some(info)),
+            dist_type: Some(info.dist_type.clone()),
+        });
+    }
+
+    providers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(agent_id: &str, dist_type: DistributionType) -> InstalledAgentInfo {
+        InstalledAgentInfo {
+            agent_id: agent_id.to_string(),
+            version: "1.0.0".to_string(),
+            dist_type,
+            installed_at: "2026-01-01T00:00:00Z".to_string(),
+            binary_path: None,
+            package: Some(format!("@acme/{agent_id}")),
+        }
+    }
+
+    #[test]
+    fn merges_builtin_registry_and_installed_providers() {
+        let registry: AcpRegistry = serde_json::from_value(serde_json::json!({
+            "agents": [
+                {
+                    "id": "acme",
+                    "name": "Acme Agent",
+                    "distribution": { "npx": { "package": "@acme/agent" } }
+                },
+                {
+                    "id": "opencode",
+                    "name": "OpenCode",
+                    "distribution": { "npx": { "package": "opencode-ai" } }
+                }
+            ]
+        }))
+        .unwrap();
+        let installed = vec![
+            installed("acme", DistributionType::Npx),
+            installed("homegrown", DistributionType::Uvx),
+        ];
+
+        let providers = merge_providers(&get_presets(), Some(&registry), &installed, |command| {
+            command == "npx"
+        });
+        let find = |id: &str| {
+            providers
+                .iter()
+                .find(|provider| provider.id == id)
+                .unwrap_or_else(|| panic!("{id} should be listed"))
+        };
+
+        let opencode = find("opencode");
+        assert_eq!(opencode.source, ProviderSource::Builtin);
+        assert!(!opencode.installed);
+        assert!(!opencode.available);
+        assert_eq!(find("opencode-registry").source, ProviderSource::Registry);
+
+        let acme = find("acme");
+        assert_eq!(acme.source, ProviderSource::Registry);
+        assert!(acme.installed);
+        assert!(acme.available);
+        assert_eq!(acme.dist_type, Some(DistributionType::Npx));
+        assert_eq!(acme.command, "npx -y @acme/agent");
+
+        let homegrown = find("homegrown");
+        assert_eq!(homegrown.source, ProviderSource::Custom);
+        assert!(homegrown.installed);
+        assert!(!homegrown.available);
+    }
+}
@@ -0,0 +1,122 @@
+//! RPC methods for ACP session management.
+//!
+//! Methods:
+//! - `sessions.setMetadata` — replace a session's user-assigned metadata
+
+use serde::{Deserialize, Serialize};
+
+use crate::rpc::error::RpcError;
+use crate::state::AppState;
+
+// ---------------------------------------------------------------------------
+// sessions.setMetadata
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMetadataParams {
+    pub session_id: String,
+    pub metadata: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMetadataResult {
+    pub session_id: String,
+    pub metadata: serde_json::Value,
+}
+
+pub async fn set_metadata(
+    state: &AppState,
+    params: SetMetadataParams,
+) -> Result<SetMetadataResult, RpcError> {
+    if !params.metadata.is_object() {
+        return Err(RpcError::InvalidParams(
+            "metadata must be a JSON object".to_string(),
+        ));
+    }
+
+    // A live session may not have been persisted (e.g. persistence failed).
+    let in_memory_found = state
+        .acp_manager
+        .set_session_metadata(&params.session_id, params.metadata.clone())
+        .await
+        .is_some();
+    let persisted = state
+        .acp_session_store
+        .set_metadata(&params.session_id, &params.metadata)
+        .await?;
+    if !in_memory_found && !persisted {
+        return Err(RpcError::NotFound(format!(
+            "Session {} not found",
+            params.session_id
+        )));
+    }
+
+    Ok(SetMetadataResult {
+        session_id: params.session_id,
+        metadata: params.metadata,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::db::Database;
+    use crate::state::AppStateInner;
+    use crate::store::acp_session_store::CreateAcpSessionParams;
+
+    #[tokio::test]
+    async fn set_metadata_round_trips_through_store() {
+        let state: AppState = Arc::new(AppStateInner::new(Database::open_in_memory().unwrap()));
+        state.workspace_store.ensure_default().await.unwrap();
+        let initial = serde_json::json!({ "label": "triage" });
+        state
+            .acp_session_store
+            .create(CreateAcpSessionParams {
+                id: "session-1",
+                cwd: "/tmp",
+                branch: None,
+                workspace_id: "default",
+                provider: Some("opencode"),
+                role: None,
+                custom_command: None,
+                custom_args: None,
+                parent_session_id: None,
+                metadata: Some(&initial),
+            })
+            .await
+            .unwrap();
+        let stored = state.acp_session_store.get("session-1").await.unwrap();
+        assert_eq!(stored.unwrap().metadata, initial);
+
+        let metadata = serde_json::json!({ "label": "review", "linkedPr": 42 });
+        set_metadata(
+            &state,
+            SetMetadataParams {
+                session_id: "session-1".to_string(),
+                metadata: metadata.clone(),
+            },
+        )
+        .await
+        .unwrap();
+        let listed = state
+            .acp_session_store
+            .list(Some("default"), None)
+            .await
+            .unwrap();
+        assert_eq!(listed[0].metadata, metadata);
+
+        let missing = set_metadata(
+            &state,
+            SetMetadataParams {
+                session_id: "missing".to_string(),
+                metadata: serde_json::json!({}),
+            },
+        )
+        .await;
+        assert!(matches!(missing, Err(RpcError::NotFound(_))));
+    }
+}
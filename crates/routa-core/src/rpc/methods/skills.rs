@@ -1,12 +1,17 @@
 //! RPC methods for skill management.
 //!
 //! Methods:
-//! - `skills.list`   — list all discovered skills
-//! - `skills.get`    — get a single skill by name
-//! - `skills.reload` — re-discover skills from the filesystem
+//! - `skills.list`         — list all discovered skills, most installed first
+//! - `skills.get`          — get a single skill by name
+//! - `skills.reload`       — re-discover skills from the filesystem, plus optional extra directories
+//! - `skills.topInstalled` — most installed skills from the install counter
+//! - `skills.use`          — a skill's instructions formatted for an agent's context
+
+use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::skill::SkillRecord;
 use crate::rpc::error::RpcError;
 use crate::skills::SkillDefinition;
 use crate::state::AppState;
@@ -15,13 +20,36 @@ use crate::state::AppState;
 // skills.list
 // ---------------------------------------------------------------------------
 
+#[derive(Debug, Serialize)]
+pub struct ListedSkill {
+    #[serde(flatten)]
+    pub skill: SkillDefinition,
+    pub installs: i64,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ListResult {
-    pub skills: Vec<SkillDefinition>,
+    pub skills: Vec<ListedSkill>,
 }
 
 pub async fn list(state: &AppState) -> Result<ListResult, RpcError> {
-    let skills = state.skill_registry.list_skills();
+    let installs: HashMap<String, i64> = state
+        .skill_store
+        .list_by_installs(None)
+        .await?
+        .into_iter()
+        .map(|record| (record.name, record.installs))
+        .collect();
+    let mut skills: Vec<ListedSkill> = state
+        .skill_registry
+        .list_skills()
+        .into_iter()
+        .map(|skill| ListedSkill {
+            installs: installs.get(&skill.name).copied().unwrap_or(0),
+            skill,
+        })
+        .collect();
+    skills.sort_by_key(|listed| std::cmp::Reverse(listed.installs));
     Ok(ListResult { skills })
 }
 
@@ -46,20 +74,127 @@ pub async fn get(state: &AppState, params: GetParams) -> Result<SkillDefinition,
 // skills.reload
 // ---------------------------------------------------------------------------
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadParams {
+    /// Extra directories to scan in addition to cwd and home.
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ReloadResult {
     pub reloaded: bool,
     pub skills: Vec<SkillDefinition>,
 }
 
-pub async fn reload(state: &AppState) -> Result<ReloadResult, RpcError> {
+pub async fn reload(state: &AppState, params: ReloadParams) -> Result<ReloadResult, RpcError> {
+    if let Some(missing) = params
+        .paths
+        .iter()
+        .find(|path| !std::path::Path::new(path).is_dir())
+    {
+        return Err(RpcError::BadRequest(format!(
+            "Skill directory not found: {missing}"
+        )));
+    }
     let cwd = std::env::current_dir()
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|_| ".".to_string());
-    state.skill_registry.reload(&cwd);
+    state.skill_registry.reload_from(&cwd, &params.paths);
     let skills = state.skill_registry.list_skills();
     Ok(ReloadResult {
         reloaded: true,
         skills,
     })
 }
+
+// ---------------------------------------------------------------------------
+// skills.topInstalled
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopInstalledParams {
+    #[serde(default = "default_top_installed_limit")]
+    pub limit: usize,
+}
+
+fn default_top_installed_limit() -> usize {
+    10
+}
+
+#[derive(Debug, Serialize)]
+pub struct TopInstalledResult {
+    pub skills: Vec<SkillRecord>,
+}
+
+pub async fn top_installed(
+    state: &AppState,
+    params: TopInstalledParams,
+) -> Result<TopInstalledResult, RpcError> {
+    let skills = state
+        .skill_store
+        .list_by_installs(Some(params.limit))
+        .await?;
+    Ok(TopInstalledResult { skills })
+}
+
+// ---------------------------------------------------------------------------
+// skills.use
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UseParams {
+    pub name: String,
+    /// Only return the markdown section under this heading.
+    #[serde(default)]
+    pub section: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UseResult {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// Skill instructions ready to inject into the agent's context.
+    pub content: String,
+    pub uses: i64,
+}
+
+pub async fn use_skill(state: &AppState, params: UseParams) -> Result<UseResult, RpcError> {
+    let skill = state
+        .skill_registry
+        .get_skill(&params.name)
+        .ok_or_else(|| RpcError::NotFound(format!("Skill {} not found", params.name)))?;
+    let body = match params.section.as_deref() {
+        Some(heading) => skill.section(heading).ok_or_else(|| {
+            RpcError::NotFound(format!(
+                "Section {heading} not found in skill {}",
+                skill.name
+            ))
+        })?,
+        None => skill.content.clone(),
+    };
+    let content = format!(
+        "# Skill: {}\n\n> {}\n\nFollow these instructions:\n\n{}",
+        skill.name, skill.description, body
+    );
+
+    let record = SkillRecord::new(
+        skill.name.clone(),
+        skill.description.clone(),
+        skill.source.clone(),
+        "local".to_string(),
+    );
+    let uses = state.skill_store.record_use(&record).await?;
+
+    Ok(UseResult {
+        name: skill.name,
+        section: params.section,
+        content,
+        uses,
+    })
+}
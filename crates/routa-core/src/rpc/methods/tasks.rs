@@ -2,26 +2,35 @@
 //!
 //! Methods:
 //! - `tasks.list`         — list tasks with optional filters
-//! - `tasks.get`          — get a single task by id
+//! - `tasks.get`          — get a single task by id, optionally with assignee and dependency status
 //! - `tasks.create`       — create a new task
+//! - `tasks.createBatch`  — create many tasks in one transaction, linking them by client ids
+//! - `tasks.update`       — edit a task's title, objective or scope
 //! - `tasks.delete`       — delete a task
 //! - `tasks.updateStatus` — update a task's status
+//! - `tasks.setCriterionStatus` — mark one acceptance criterion pending, verified or failed
+//! - `tasks.assign`       — assign or reassign a task to an agent
+//! - `tasks.reopen`       — send a finished task back to NEEDS_FIX with a reason
 //! - `tasks.findReady`    — find tasks ready for execution
+//! - `tasks.search`       — substring search over titles and objectives, with snippet and rank
 //! - `tasks.listArtifacts` — list artifacts attached to a task
 //! - `tasks.provideArtifact` — attach an artifact to a task
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
+use crate::events::{AgentEvent, KanbanChangedData, TaskAssignedData, TaskStatusChangedData};
+use crate::models::agent::{AgentRole, AgentStatus};
 use crate::models::artifact::{Artifact, ArtifactStatus, ArtifactType};
 use crate::models::kanban::KanbanBoard;
 use crate::models::task::{
-    build_task_invest_validation, build_task_story_readiness, Task, TaskLaneSessionStatus,
-    TaskStatus,
+    build_task_invest_validation, build_task_story_readiness, AcceptanceCriterionInput,
+    CriterionStatus, Task, TaskAuditAction, TaskAuditEntry, TaskLaneSessionStatus, TaskStatus,
 };
 use crate::rpc::error::RpcError;
 use crate::state::AppState;
+use crate::store::{TaskSearchHit, TaskStore, TimeRange};
 
 const KANBAN_HAPPY_PATH_COLUMN_ORDER: [&str; 5] = ["backlog", "todo", "dev", "review", "done"];
 
@@ -77,6 +86,10 @@ pub struct ListParams {
     pub session_id: Option<String>,
     pub status: Option<String>,
     pub assigned_to: Option<String>,
+    /// `createdAfter`/`createdBefore`/`updatedAfter`/`updatedBefore` bounds.
+    /// When any is set, results are ordered by `updatedAt`, newest first.
+    #[serde(flatten)]
+    pub range: TimeRange,
 }
 
 fn default_workspace_id() -> String {
@@ -89,7 +102,33 @@ pub struct ListResult {
 }
 
 pub async fn list(state: &AppState, params: ListParams) -> Result<ListResult, RpcError> {
-    let tasks = if let Some(session_id) = &params.session_id {
+    let tasks = if !params.range.is_unbounded() {
+        let status = params
+            .status
+            .as_deref()
+            .map(|status_str| {
+                TaskStatus::from_str(status_str)
+                    .ok_or_else(|| RpcError::BadRequest(format!("Invalid status: {status_str}")))
+            })
+            .transpose()?;
+        state
+            .task_store
+            .list_in_range(&params.workspace_id, &params.range)
+            .await?
+            .into_iter()
+            .filter(|task| {
+                params
+                    .session_id
+                    .as_ref()
+                    .is_none_or(|id| task.session_id.as_ref() == Some(id))
+                    && params
+                        .assigned_to
+                        .as_ref()
+                        .is_none_or(|id| task.assigned_to.as_ref() == Some(id))
+                    && status.as_ref().is_none_or(|status| &task.status == status)
+            })
+            .collect()
+    } else if let Some(session_id) = &params.session_id {
         // Filter by session_id takes priority
         state.task_store.list_by_session(session_id).await?
     } else if let Some(assignee) = &params.assigned_to {
@@ -121,6 +160,28 @@ pub async fn list(state: &AppState, params: ListParams) -> Result<ListResult, Rp
 #[serde(rename_all = "camelCase")]
 pub struct GetParams {
     pub id: String,
+    /// Also return the assigned agent and the status of each dependency.
+    #[serde(default)]
+    pub expand: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskAgentSummary {
+    pub id: String,
+    pub name: String,
+    pub role: AgentRole,
+    pub status: AgentStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDependencyStatus {
+    pub id: String,
+    /// `None` when the dependency no longer exists in the task's workspace.
+    pub title: Option<String>,
+    pub status: Option<TaskStatus>,
+    pub satisfied: bool,
 }
 
 pub async fn get(state: &AppState, params: GetParams) -> Result<serde_json::Value, RpcError> {
@@ -129,7 +190,64 @@ pub async fn get(state: &AppState, params: GetParams) -> Result<serde_json::Valu
         .get(&params.id)
         .await?
         .ok_or_else(|| RpcError::NotFound(format!("Task {} not found", params.id)))?;
-    serialize_task_with_evidence(state, &task).await
+    let mut task_value = serialize_task_with_checklist(state, &task).await?;
+    if params.expand {
+        let assigned_agent = match task.assigned_to.as_deref() {
+            Some(agent_id) => {
+                state
+                    .agent_store
+                    .get(agent_id)
+                    .await?
+                    .map(|agent| TaskAgentSummary {
+                        id: agent.id,
+                        name: agent.name,
+                        role: agent.role,
+                        status: agent.status,
+                    })
+            }
+            None => None,
+        };
+        let dependencies = build_task_dependency_statuses(state, &task).await?;
+        task_value["assignedAgent"] = serde_json::to_value(&assigned_agent).map_err(|error| {
+            RpcError::Internal(format!("Failed to serialize assigned agent: {error}"))
+        })?;
+        task_value["dependencies"] = serde_json::to_value(&dependencies).map_err(|error| {
+            RpcError::Internal(format!("Failed to serialize task dependencies: {error}"))
+        })?;
+    }
+    Ok(task_value)
+}
+
+/// Resolve each dependency id the same way `tasks.findReady` does: only a
+/// task in the same workspace that [satisfies](Task::satisfies_dependency)
+/// the dependency unblocks it.
+async fn build_task_dependency_statuses(
+    state: &AppState,
+    task: &Task,
+) -> Result<Vec<TaskDependencyStatus>, RpcError> {
+    let mut statuses = Vec::with_capacity(task.dependencies.len());
+    for dependency_id in &task.dependencies {
+        let dependency = state
+            .task_store
+            .get(dependency_id)
+            .await?
+            .filter(|dependency| dependency.workspace_id == task.workspace_id);
+        statuses.push(match dependency {
+            Some(dependency) => TaskDependencyStatus {
+                id: dependency.id.clone(),
+                satisfied: dependency.satisfies_dependency(),
+                title: Some(dependency.title),
+                status: Some(dependency.status),
+            },
+            None => TaskDependencyStatus {
+                id: dependency_id.clone(),
+                title: None,
+                status: None,
+                satisfied: false,
+            },
+        });
+    }
+    Ok(statuses)
 }
 
 // ---------------------------------------------------------------------------
@@ -145,7 +263,8 @@ pub struct CreateParams {
     pub workspace_id: String,
     pub session_id: Option<String>,
     pub scope: Option<String>,
-    pub acceptance_criteria: Option<Vec<String>>,
+    /// Plain strings or `{ text, status }` items.
+    pub acceptance_criteria: Option<Vec<AcceptanceCriterionInput>>,
     pub verification_commands: Option<Vec<String>>,
     pub test_cases: Option<Vec<String>>,
     pub dependencies: Option<Vec<String>>,
@@ -158,19 +277,22 @@ pub struct CreateResult {
 }
 
 pub async fn create(state: &AppState, params: CreateParams) -> Result<CreateResult, RpcError> {
-    let task = Task::new(
+    let mut task = Task::new(
         uuid::Uuid::new_v4().to_string(),
         params.title,
         params.objective,
         params.workspace_id,
         params.session_id,
         params.scope,
-        params.acceptance_criteria,
+        None,
         params.verification_commands,
         params.test_cases,
         params.dependencies,
         params.parallel_group,
     );
+    if let Some(criteria) = params.acceptance_criteria {
+        task.set_acceptance_criteria(criteria);
+    }
 
     state.task_store.save(&task).await?;
     Ok(CreateResult {
@@ -178,6 +300,261 @@ pub async fn create(state: &AppState, params: CreateParams) -> Result<CreateResu
     })
 }
 
+// ---------------------------------------------------------------------------
+// tasks.createBatch
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTaskInput {
+    /// Temporary id other entries in the same batch use to depend on this one.
+    pub client_id: String,
+    pub title: String,
+    pub objective: String,
+    pub session_id: Option<String>,
+    pub scope: Option<String>,
+    /// Plain strings or `{ text, status }` items.
+    pub acceptance_criteria: Option<Vec<AcceptanceCriterionInput>>,
+    pub verification_commands: Option<Vec<String>>,
+    pub test_cases: Option<Vec<String>>,
+    /// Client ids from this batch or ids of existing tasks.
+    pub dependencies: Option<Vec<String>>,
+    pub parallel_group: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBatchParams {
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+    pub tasks: Vec<BatchTaskInput>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBatchResult {
+    pub tasks: Vec<Task>,
+    /// Client id to generated task id.
+    pub id_map: BTreeMap<String, String>,
+}
+
+pub async fn create_batch(
+    state: &AppState,
+    params: CreateBatchParams,
+) -> Result<CreateBatchResult, RpcError> {
+    validate_batch(&params.tasks)?;
+
+    let id_map: HashMap<String, String> = params
+        .tasks
+        .iter()
+        .map(|input| (input.client_id.clone(), uuid::Uuid::new_v4().to_string()))
+        .collect();
+    let tasks: Vec<Task> = params
+        .tasks
+        .into_iter()
+        .map(|input| {
+            let dependencies = input.dependencies.map(|deps| {
+                deps.into_iter()
+                    .map(|dep| id_map.get(&dep).cloned().unwrap_or(dep))
+                    .collect()
+            });
+            let mut task = Task::new(
+                id_map[&input.client_id].clone(),
+                input.title,
+                input.objective,
+                params.workspace_id.clone(),
+                input.session_id,
+                input.scope,
+                None,
+                input.verification_commands,
+                input.test_cases,
+                dependencies,
+                input.parallel_group,
+            );
+            if let Some(criteria) = input.acceptance_criteria {
+                task.set_acceptance_criteria(criteria);
+            }
+            task
+        })
+        .collect();
+
+    let saved = tasks.clone();
+    state
+        .db
+        .transaction(move |conn| {
+            for task in &saved {
+                TaskStore::save_in(conn, task)?;
+            }
+            Ok(())
+        })
+        .await?;
+
+    Ok(CreateBatchResult {
+        tasks,
+        id_map: id_map.into_iter().collect(),
+    })
+}
+
+/// Reject duplicate client ids and dependency cycles among batch entries.
+/// Dependencies that are not client ids refer to existing tasks, which cannot
+/// depend on tasks that do not exist yet, so they never close a cycle.
+fn validate_batch(inputs: &[BatchTaskInput]) -> Result<(), RpcError> {
+    let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+    for input in inputs {
+        if edges.insert(input.client_id.as_str(), Vec::new()).is_some() {
+            return Err(RpcError::BadRequest(format!(
+                "Duplicate clientId in batch: {}",
+                input.client_id
+            )));
+        }
+    }
+    for input in inputs {
+        let deps: Vec<&str> = input
+            .dependencies
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|dep| edges.contains_key(dep))
+            .collect();
+        edges.insert(input.client_id.as_str(), deps);
+    }
+
+    // Kahn's algorithm: anything left unvisited sits on a cycle.
+    let mut remaining: HashMap<&str, usize> =
+        edges.iter().map(|(id, deps)| (*id, deps.len())).collect();
+    let mut ready: Vec<&str> = remaining
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    while let Some(done) = ready.pop() {
+        remaining.remove(done);
+        for (id, deps) in &edges {
+            if !deps.contains(&done) {
+                continue;
+            }
+            if let Some(count) = remaining.get_mut(id) {
+                *count -= deps.iter().filter(|dep| **dep == done).count();
+                if *count == 0 {
+                    ready.push(id);
+                }
+            }
+        }
+    }
+    if remaining.is_empty() {
+        return Ok(());
+    }
+    let mut cycle: Vec<&str> = remaining.into_keys().collect();
+    cycle.sort_unstable();
+    Err(RpcError::BadRequest(format!(
+        "Dependency cycle in batch: {}",
+        cycle.join(", ")
+    )))
+}
+
+// ---------------------------------------------------------------------------
+// tasks.update
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateParams {
+    pub id: String,
+    pub title: Option<String>,
+    pub objective: Option<String>,
+    pub scope: Option<String>,
+}
+
+/// Edit a task's descriptive fields. Only the fields provided change.
+pub async fn update(state: &AppState, params: UpdateParams) -> Result<serde_json::Value, RpcError> {
+    let mut task = state
+        .task_store
+        .get(&params.id)
+        .await?
+        .ok_or_else(|| RpcError::NotFound(format!("Task {} not found", params.id)))?;
+
+    if let Some(title) = params.title {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(RpcError::BadRequest(
+                "Task title cannot be empty".to_string(),
+            ));
+        }
+        task.title = title.to_string();
+    }
+    if let Some(objective) = params.objective {
+        task.objective = objective;
+    }
+    if let Some(scope) = params.scope {
+        task.scope = Some(scope);
+    }
+    task.updated_at = Utc::now();
+    state.task_store.save(&task).await?;
+
+    state
+        .event_bus
+        .emit(AgentEvent::kanban_changed(
+            "kanban-rpc",
+            task.workspace_id.clone(),
+            KanbanChangedData {
+                entity: "task".to_string(),
+                action: "updated".to_string(),
+                resource_id: Some(task.id.clone()),
+                source: "rpc".to_string(),
+            },
+        ))
+        .await;
+
+    serialize_task_with_evidence(state, &task).await
+}
+
+// ---------------------------------------------------------------------------
+// tasks.setCriterionStatus
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCriterionStatusParams {
+    pub task_id: String,
+    /// Zero-based position in the task's acceptance criteria.
+    pub index: usize,
+    pub status: CriterionStatus,
+}
+
+/// Record whether one acceptance criterion has been verified. Returns the
+/// task with its acceptance checklist, as `tasks.get` does.
+pub async fn set_criterion_status(
+    state: &AppState,
+    params: SetCriterionStatusParams,
+) -> Result<serde_json::Value, RpcError> {
+    let mut task = state
+        .task_store
+        .get(&params.task_id)
+        .await?
+        .ok_or_else(|| RpcError::NotFound(format!("Task {} not found", params.task_id)))?;
+
+    task.set_criterion_status(params.index, params.status)
+        .map_err(RpcError::BadRequest)?;
+    task.updated_at = Utc::now();
+    state.task_store.save(&task).await?;
+
+    state
+        .event_bus
+        .emit(AgentEvent::kanban_changed(
+            "kanban-rpc",
+            task.workspace_id.clone(),
+            KanbanChangedData {
+                entity: "task".to_string(),
+                action: "updated".to_string(),
+                resource_id: Some(task.id.clone()),
+                source: "rpc".to_string(),
+            },
+        ))
+        .await;
+
+    serialize_task_with_checklist(state, &task).await
+}
+
 // ---------------------------------------------------------------------------
 // tasks.delete
 // ---------------------------------------------------------------------------
@@ -224,6 +601,158 @@ pub async fn update_status(
     Ok(UpdateStatusResult { updated: true })
 }
 
+// ---------------------------------------------------------------------------
+// tasks.assign
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignParams {
+    pub id: String,
+    pub agent_id: String,
+    pub caller_agent_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignResult {
+    pub task: serde_json::Value,
+    pub previous_agent_id: Option<String>,
+}
+
+/// Set a task's assignee without spawning an agent or changing its status.
+pub async fn assign(state: &AppState, params: AssignParams) -> Result<AssignResult, RpcError> {
+    let mut task = state
+        .task_store
+        .get(&params.id)
+        .await?
+        .ok_or_else(|| RpcError::NotFound(format!("Task {} not found", params.id)))?;
+    let agent = state
+        .agent_store
+        .get(&params.agent_id)
+        .await?
+        .ok_or_else(|| RpcError::NotFound(format!("Agent {} not found", params.agent_id)))?;
+    if agent.workspace_id != task.workspace_id {
+        return Err(RpcError::BadRequest(format!(
+            "Agent {} belongs to workspace {}, but task {} belongs to workspace {}",
+            agent.id, agent.workspace_id, task.id, task.workspace_id
+        )));
+    }
+
+    let previous_agent_id = task.assigned_to.replace(agent.id.clone());
+    task.updated_at = Utc::now();
+    state.task_store.save(&task).await?;
+
+    state
+        .event_bus
+        .emit(AgentEvent::task_assigned(
+            agent.id.clone(),
+            task.workspace_id.clone(),
+            TaskAssignedData {
+                task_id: task.id.clone(),
+                task_title: task.title.clone(),
+                caller_agent_id: params.caller_agent_id.clone(),
+                previous_agent_id: previous_agent_id.clone(),
+                provider: None,
+                specialist: None,
+            },
+        ))
+        .await;
+
+    Ok(AssignResult {
+        task: serialize_task_with_evidence(state, &task).await?,
+        previous_agent_id,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// tasks.reopen
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReopenParams {
+    pub task_id: String,
+    pub reason: String,
+    pub agent_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReopenResult {
+    pub task: serde_json::Value,
+    pub previous_status: TaskStatus,
+}
+
+/// Move a completed (or in-review) task back to NEEDS_FIX.
+///
+/// The previous completion summary and verification verdict/report are
+/// cleared so the next run starts from a clean slate, and the reason is kept
+/// in the task audit log.
+pub async fn reopen(state: &AppState, params: ReopenParams) -> Result<ReopenResult, RpcError> {
+    let reason = params.reason.trim();
+    if reason.is_empty() {
+        return Err(RpcError::BadRequest(
+            "A reason is required to reopen a task".to_string(),
+        ));
+    }
+    let mut task = state
+        .task_store
+        .get(&params.task_id)
+        .await?
+        .ok_or_else(|| RpcError::NotFound(format!("Task {} not found", params.task_id)))?;
+    if !matches!(
+        task.status,
+        TaskStatus::Completed | TaskStatus::ReviewRequired
+    ) {
+        return Err(RpcError::BadRequest(format!(
+            "Task {} is {} and cannot be reopened",
+            task.id,
+            task.status.as_str()
+        )));
+    }
+
+    let previous_status = std::mem::replace(&mut task.status, TaskStatus::NeedsFix);
+    task.completion_summary = None;
+    task.verification_verdict = None;
+    task.verification_report = None;
+    task.updated_at = Utc::now();
+    state.task_store.save(&task).await?;
+    state
+        .task_store
+        .append_audit_entry(&TaskAuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            task_id: task.id.clone(),
+            action: TaskAuditAction::Reopen,
+            actor: params.agent_id.clone(),
+            reason: Some(reason.to_string()),
+            previous_status: previous_status.clone(),
+            created_at: task.updated_at,
+        })
+        .await?;
+
+    state
+        .event_bus
+        .emit(AgentEvent::task_status_changed(
+            params.agent_id.clone(),
+            task.workspace_id.clone(),
+            TaskStatusChangedData {
+                task_id: task.id.clone(),
+                task_title: Some(task.title.clone()),
+                old_status: previous_status.clone(),
+                new_status: task.status.clone(),
+                reason: Some(reason.to_string()),
+                summary: None,
+            },
+        ))
+        .await;
+
+    Ok(ReopenResult {
+        task: serialize_task_with_evidence(state, &task).await?,
+        previous_status,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // tasks.findReady
 // ---------------------------------------------------------------------------
@@ -245,6 +774,41 @@ pub async fn find_ready(state: &AppState, params: FindReadyParams) -> Result<Lis
     })
 }
 
+// ---------------------------------------------------------------------------
+// tasks.search
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchParams {
+    #[serde(default = "default_workspace_id")]
+    pub workspace_id: String,
+    pub query: String,
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+}
+
+fn default_search_limit() -> usize {
+    20
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub results: Vec<TaskSearchHit>,
+}
+
+/// Substring search over task titles and objectives.
+pub async fn search(state: &AppState, params: SearchParams) -> Result<SearchResult, RpcError> {
+    if params.query.trim().is_empty() {
+        return Err(RpcError::BadRequest("query must not be empty".to_string()));
+    }
+    let results = state
+        .task_store
+        .search(&params.workspace_id, &params.query, params.limit)
+        .await?;
+    Ok(SearchResult { results })
+}
+
 // ---------------------------------------------------------------------------
 // tasks.listArtifacts
 // ---------------------------------------------------------------------------
@@ -370,6 +934,20 @@ async fn serialize_tasks_with_evidence(
     Ok(serialized)
 }
 
+/// [`serialize_task_with_evidence`] plus an `acceptanceChecklist` pairing
+/// each criterion with its checklist state.
+async fn serialize_task_with_checklist(
+    state: &AppState,
+    task: &Task,
+) -> Result<serde_json::Value, RpcError> {
+    let mut task_value = serialize_task_with_evidence(state, task).await?;
+    task_value["acceptanceChecklist"] =
+        serde_json::to_value(task.acceptance_checklist()).map_err(|error| {
+            RpcError::Internal(format!("Failed to serialize acceptance checklist: {error}"))
+        })?;
+    Ok(task_value)
+}
+
 async fn serialize_task_with_evidence(
     state: &AppState,
     task: &Task,
@@ -571,6 +1149,146 @@ mod tests {
         state
     }
 
+    fn batch_input(client_id: &str, dependencies: &[&str]) -> BatchTaskInput {
+        BatchTaskInput {
+            client_id: client_id.to_string(),
+            title: format!("Task {client_id}"),
+            objective: "Planned from spec".to_string(),
+            session_id: None,
+            scope: None,
+            acceptance_criteria: None,
+            verification_commands: None,
+            test_cases: None,
+            dependencies: Some(dependencies.iter().map(|dep| dep.to_string()).collect()),
+            parallel_group: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_batch_remaps_intra_batch_dependencies() {
+        let state = setup_state().await;
+        let result = create_batch(
+            &state,
+            CreateBatchParams {
+                workspace_id: "default".to_string(),
+                tasks: vec![batch_input("a", &[]), batch_input("b", &["a"])],
+            },
+        )
+        .await
+        .expect("batch should be created");
+
+        let a_id = &result.id_map["a"];
+        let b_id = &result.id_map["b"];
+        assert_ne!(a_id, "a");
+        let b = state
+            .task_store
+            .get(b_id)
+            .await
+            .unwrap()
+            .expect("task b should be stored");
+        assert_eq!(b.dependencies, vec![a_id.clone()]);
+        assert_eq!(
+            state
+                .task_store
+                .list_by_workspace("default")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let err = create_batch(
+            &state,
+            CreateBatchParams {
+                workspace_id: "default".to_string(),
+                tasks: vec![
+                    batch_input("x", &["z"]),
+                    batch_input("y", &["x"]),
+                    batch_input("z", &["y"]),
+                    batch_input("free", &[]),
+                ],
+            },
+        )
+        .await
+        .expect_err("cyclic batch should be rejected");
+        assert!(matches!(err, RpcError::BadRequest(msg) if msg.ends_with("x, y, z")));
+        assert_eq!(
+            state
+                .task_store
+                .list_by_workspace("default")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn list_filters_by_timestamp_window() {
+        let state = setup_state().await;
+        let base = chrono::DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        // (id, created hours after base, updated hours after base)
+        for (id, created, updated) in [("old", 0, 1), ("busy", 2, 9), ("fresh", 6, 7)] {
+            let mut task = Task::new(
+                id.to_string(),
+                format!("Task {id}"),
+                "Seeded".to_string(),
+                "default".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            task.created_at = base + chrono::Duration::hours(created);
+            task.updated_at = base + chrono::Duration::hours(updated);
+            state
+                .task_store
+                .save(&task)
+                .await
+                .expect("task should save");
+        }
+
+        let ids = |result: ListResult| {
+            result
+                .tasks
+                .iter()
+                .map(|task| task["id"].as_str().unwrap_or_default().to_string())
+                .collect::<Vec<_>>()
+        };
+        let params = |value: serde_json::Value| {
+            serde_json::from_value::<ListParams>(value).expect("params should parse")
+        };
+
+        let changed = list(
+            &state,
+            params(serde_json::json!({ "updatedAfter": "2026-03-01T05:00:00Z" })),
+        )
+        .await
+        .expect("tasks should list");
+        assert_eq!(ids(changed), vec!["busy", "fresh"]);
+
+        let window = list(
+            &state,
+            params(serde_json::json!({
+                "updatedAfter": "2026-03-01T00:30:00+00:00",
+                "createdBefore": "2026-03-01T04:00:00Z",
+            })),
+        )
+        .await
+        .expect("tasks should list");
+        assert_eq!(ids(window), vec!["busy", "old"]);
+
+        assert!(serde_json::from_value::<ListParams>(
+            serde_json::json!({ "updatedAfter": "yesterday" })
+        )
+        .is_err());
+    }
+
     #[tokio::test]
     async fn provide_and_list_artifacts_roundtrip() {
         let state = setup_state().await;
@@ -740,6 +1458,7 @@ mod tests {
             &state,
             GetParams {
                 id: task.id.clone(),
+                expand: false,
             },
         )
         .await
@@ -777,6 +1496,7 @@ mod tests {
                 session_id: None,
                 status: None,
                 assigned_to: None,
+                range: TimeRange::default(),
             },
         )
         .await
@@ -839,4 +1559,410 @@ mod tests {
             serde_json::json!([])
         );
     }
+
+    #[tokio::test]
+    async fn assign_and_reassign_updates_stored_assignee() {
+        use crate::models::agent::{Agent, AgentRole};
+        use crate::models::workspace::Workspace;
+
+        let state = setup_state().await;
+        state
+            .workspace_store
+            .save(&Workspace::new(
+                "other".to_string(),
+                "Other".to_string(),
+                None,
+            ))
+            .await
+            .expect("second workspace should save");
+        let created = create(
+            &state,
+            CreateParams {
+                title: "Assignable task".to_string(),
+                objective: "Hand off between crafters".to_string(),
+                workspace_id: "default".to_string(),
+                session_id: None,
+                scope: None,
+                acceptance_criteria: None,
+                verification_commands: None,
+                test_cases: None,
+                dependencies: None,
+                parallel_group: None,
+            },
+        )
+        .await
+        .expect("task should be created");
+        let task_id = created.task["id"].as_str().expect("task id").to_string();
+
+        for (id, workspace_id) in [
+            ("crafter-1", "default"),
+            ("crafter-2", "default"),
+            ("outsider", "other"),
+        ] {
+            let agent = Agent::new(
+                id.to_string(),
+                id.to_string(),
+                AgentRole::Crafter,
+                workspace_id.to_string(),
+                None,
+                None,
+                None,
+            );
+            state
+                .agent_store
+                .save(&agent)
+                .await
+                .expect("agent should save");
+        }
+
+        let first = assign(
+            &state,
+            AssignParams {
+                id: task_id.clone(),
+                agent_id: "crafter-1".to_string(),
+                caller_agent_id: None,
+            },
+        )
+        .await
+        .expect("first assignment should succeed");
+        assert_eq!(first.previous_agent_id, None);
+
+        let second = assign(
+            &state,
+            AssignParams {
+                id: task_id.clone(),
+                agent_id: "crafter-2".to_string(),
+                caller_agent_id: None,
+            },
+        )
+        .await
+        .expect("reassignment should succeed");
+        assert_eq!(second.previous_agent_id.as_deref(), Some("crafter-1"));
+
+        let stored = state
+            .task_store
+            .get(&task_id)
+            .await
+            .expect("task lookup should succeed")
+            .expect("task should exist");
+        assert_eq!(stored.assigned_to.as_deref(), Some("crafter-2"));
+
+        let mismatch = assign(
+            &state,
+            AssignParams {
+                id: task_id.clone(),
+                agent_id: "outsider".to_string(),
+                caller_agent_id: None,
+            },
+        )
+        .await;
+        assert!(matches!(mismatch, Err(RpcError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn reopen_completed_task_clears_verification_and_records_reason() {
+        let state = setup_state().await;
+        let mut task = Task::new(
+            "task-reopen".to_string(),
+            "Reopen me".to_string(),
+            "Ship the fix".to_string(),
+            "default".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        task.status = TaskStatus::Completed;
+        task.completion_summary = Some("All done".to_string());
+        task.verification_verdict = Some(VerificationVerdict::Approved);
+        task.verification_report = Some("Looks good".to_string());
+        state
+            .task_store
+            .save(&task)
+            .await
+            .expect("task should save");
+
+        let result = reopen(
+            &state,
+            ReopenParams {
+                task_id: task.id.clone(),
+                reason: "Regression in the login flow".to_string(),
+                agent_id: "gate-1".to_string(),
+            },
+        )
+        .await
+        .expect("reopen should succeed");
+        assert_eq!(result.previous_status, TaskStatus::Completed);
+        assert_eq!(result.task["status"], "NEEDS_FIX");
+
+        let stored = state
+            .task_store
+            .get(&task.id)
+            .await
+            .expect("task lookup should succeed")
+            .expect("task should exist");
+        assert_eq!(stored.status, TaskStatus::NeedsFix);
+        assert!(stored.completion_summary.is_none());
+        assert!(stored.verification_verdict.is_none());
+        assert!(stored.verification_report.is_none());
+
+        let audit = state
+            .task_store
+            .list_audit_entries(&task.id)
+            .await
+            .expect("audit entries should load");
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].action, TaskAuditAction::Reopen);
+        assert_eq!(audit[0].actor, "gate-1");
+        assert_eq!(
+            audit[0].reason.as_deref(),
+            Some("Regression in the login flow")
+        );
+        assert_eq!(audit[0].previous_status, TaskStatus::Completed);
+
+        let again = reopen(
+            &state,
+            ReopenParams {
+                task_id: task.id.clone(),
+                reason: "Still broken".to_string(),
+                agent_id: "gate-1".to_string(),
+            },
+        )
+        .await;
+        assert!(matches!(again, Err(RpcError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn update_changes_only_provided_fields() {
+        let state = setup_state().await;
+        let mut task = Task::new(
+            "task-edit".to_string(),
+            "Draft title".to_string(),
+            "Ship the fix".to_string(),
+            "default".to_string(),
+            None,
+            Some("backend".to_string()),
+            Some(vec!["Login works".to_string()]),
+            None,
+            None,
+            None,
+            None,
+        );
+        task.updated_at -= chrono::Duration::seconds(60);
+        state
+            .task_store
+            .save(&task)
+            .await
+            .expect("task should save");
+
+        let result = update(
+            &state,
+            UpdateParams {
+                id: task.id.clone(),
+                title: Some("Fix login redirect".to_string()),
+                objective: None,
+                scope: None,
+            },
+        )
+        .await
+        .expect("update should succeed");
+        assert_eq!(result["title"], "Fix login redirect");
+
+        update(
+            &state,
+            UpdateParams {
+                id: task.id.clone(),
+                title: None,
+                objective: None,
+                scope: Some("auth".to_string()),
+            },
+        )
+        .await
+        .expect("update should succeed");
+
+        let stored = state
+            .task_store
+            .get(&task.id)
+            .await
+            .expect("task lookup should succeed")
+            .expect("task should exist");
+        assert_eq!(stored.title, "Fix login redirect");
+        assert_eq!(stored.objective, "Ship the fix");
+        assert_eq!(stored.scope.as_deref(), Some("auth"));
+        assert_eq!(stored.acceptance_criteria, task.acceptance_criteria);
+        assert_eq!(stored.status, task.status);
+        assert!(stored.updated_at > task.updated_at);
+
+        let blank = update(
+            &state,
+            UpdateParams {
+                id: task.id.clone(),
+                title: Some("  ".to_string()),
+                objective: None,
+                scope: None,
+            },
+        )
+        .await;
+        assert!(matches!(blank, Err(RpcError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn get_expand_reports_dependency_satisfaction_and_assignee() {
+        let state = setup_state().await;
+        let task_params = |title: &str, dependencies: Option<Vec<String>>| CreateParams {
+            title: title.to_string(),
+            objective: format!("{title} objective"),
+            workspace_id: "default".to_string(),
+            session_id: None,
+            scope: None,
+            acceptance_criteria: None,
+            verification_commands: None,
+            test_cases: None,
+            dependencies,
+            parallel_group: None,
+        };
+        let prerequisite = create(&state, task_params("Prerequisite", None))
+            .await
+            .expect("prerequisite should be created");
+        let prerequisite_id = prerequisite.task["id"]
+            .as_str()
+            .expect("prerequisite id")
+            .to_string();
+        let dependent = create(
+            &state,
+            task_params("Dependent", Some(vec![prerequisite_id.clone()])),
+        )
+        .await
+        .expect("dependent should be created");
+        let dependent_id = dependent.task["id"]
+            .as_str()
+            .expect("dependent id")
+            .to_string();
+
+        let agent = crate::models::agent::Agent::new(
+            "crafter-1".to_string(),
+            "Crafter One".to_string(),
+            AgentRole::Crafter,
+            "default".to_string(),
+            None,
+            None,
+            None,
+        );
+        state
+            .agent_store
+            .save(&agent)
+            .await
+            .expect("agent should save");
+        assign(
+            &state,
+            AssignParams {
+                id: dependent_id.clone(),
+                agent_id: "crafter-1".to_string(),
+                caller_agent_id: None,
+            },
+        )
+        .await
+        .expect("assignment should succeed");
+
+        let expanded = get(
+            &state,
+            GetParams {
+                id: dependent_id.clone(),
+                expand: true,
+            },
+        )
+        .await
+        .expect("task should load");
+        assert_eq!(expanded["assignedAgent"]["name"], "Crafter One");
+        assert_eq!(expanded["dependencies"][0]["id"], prerequisite_id.as_str());
+        assert_eq!(expanded["dependencies"][0]["title"], "Prerequisite");
+        assert_eq!(expanded["dependencies"][0]["status"], "PENDING");
+        assert_eq!(expanded["dependencies"][0]["satisfied"], false);
+
+        state
+            .task_store
+            .update_status(&prerequisite_id, &TaskStatus::Completed)
+            .await
+            .expect("status should update");
+
+        let expanded = get(
+            &state,
+            GetParams {
+                id: dependent_id,
+                expand: true,
+            },
+        )
+        .await
+        .expect("task should load");
+        assert_eq!(expanded["dependencies"][0]["status"], "COMPLETED");
+        assert_eq!(expanded["dependencies"][0]["satisfied"], true);
+    }
+
+    #[tokio::test]
+    async fn set_criterion_status_toggles_one_checklist_item() {
+        let state = setup_state().await;
+        let params: CreateParams = serde_json::from_value(serde_json::json!({
+            "title": "Login",
+            "objective": "Users can sign in",
+            "acceptanceCriteria": [
+                "form renders",
+                { "text": "bad password rejected", "status": "failed" },
+                "session persists",
+            ],
+        }))
+        .expect("plain strings and items should both parse");
+        let created = create(&state, params)
+            .await
+            .expect("task should be created");
+        let task_id = created.task["id"].as_str().unwrap().to_string();
+
+        let updated = set_criterion_status(
+            &state,
+            SetCriterionStatusParams {
+                task_id: task_id.clone(),
+                index: 0,
+                status: CriterionStatus::Verified,
+            },
+        )
+        .await
+        .expect("criterion status should update");
+        assert_eq!(updated["acceptanceChecklist"][0]["status"], "verified");
+
+        let fetched = get(
+            &state,
+            GetParams {
+                id: task_id.clone(),
+                expand: false,
+            },
+        )
+        .await
+        .expect("task should load");
+        assert_eq!(
+            fetched["acceptanceChecklist"],
+            serde_json::json!([
+                { "text": "form renders", "status": "verified" },
+                { "text": "bad password rejected", "status": "failed" },
+                { "text": "session persists", "status": "pending" },
+            ])
+        );
+        assert_eq!(
+            fetched["acceptanceCriteria"],
+            serde_json::json!(["form renders", "bad password rejected", "session persists"])
+        );
+
+        let out_of_range = set_criterion_status(
+            &state,
+            SetCriterionStatusParams {
+                task_id,
+                index: 3,
+                status: CriterionStatus::Verified,
+            },
+        )
+        .await
+        .expect_err("index past the last criterion should fail");
+        assert!(matches!(out_of_range, RpcError::BadRequest(_)));
+    }
 }
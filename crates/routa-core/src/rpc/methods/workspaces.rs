@@ -3,15 +3,22 @@
 //! Methods:
 //! - `workspaces.list`   — list all workspaces
 //! - `workspaces.get`    — get a workspace by id
-//! - `workspaces.create` — create a new workspace
+//! - `workspaces.create` — create a new workspace, optionally seeded from a template
+//! - `workspaces.rename` — change a workspace's title
 //! - `workspaces.delete` — delete a workspace
+//! - `workspaces.workload` — per-agent task counts, for load-balanced delegation
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::events::{AgentEvent, WorkspaceRenamedData};
+use crate::models::agent::{Agent, AgentRole};
+use crate::models::note::Note;
+use crate::models::task::TaskStatus;
 use crate::models::workspace::Workspace;
 use crate::rpc::error::RpcError;
 use crate::state::AppState;
+use crate::store::{AgentStore, AssigneeWorkload, NoteStore, WorkspaceStore};
 
 // ---------------------------------------------------------------------------
 // workspaces.list
@@ -49,27 +56,178 @@ pub async fn get(state: &AppState, params: GetParams) -> Result<Workspace, RpcEr
 // workspaces.create
 // ---------------------------------------------------------------------------
 
+/// Built-in starter content for `workspaces.create`.
+#[derive(Debug, Clone, Copy)]
+pub struct WorkspaceTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub spec_content: &'static str,
+    /// Seed a ROUTA coordinator agent alongside the spec.
+    pub coordinator: bool,
+}
+
+pub const WORKSPACE_TEMPLATES: &[WorkspaceTemplate] = &[
+    WorkspaceTemplate {
+        name: "feature",
+        description: "Feature delivery: goal/acceptance spec plus a ROUTA coordinator",
+        spec_content: "# Goal\n\nDescribe the feature and who it is for.\n\n\
+            ## Acceptance Criteria\n\n- \n\n\
+            ## Tasks\n\n- \n",
+        coordinator: true,
+    },
+    WorkspaceTemplate {
+        name: "research",
+        description: "Investigation notes without agents",
+        spec_content: "# Question\n\nWhat are we trying to learn?\n\n\
+            ## Findings\n\n- \n\n\
+            ## Next Steps\n\n- \n",
+        coordinator: false,
+    },
+];
+
+pub fn find_template(name: &str) -> Option<&'static WorkspaceTemplate> {
+    WORKSPACE_TEMPLATES
+        .iter()
+        .find(|template| template.name.eq_ignore_ascii_case(name))
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateParams {
     pub title: String,
     pub metadata: Option<HashMap<String, String>>,
+    /// Name of a built-in template (see `WORKSPACE_TEMPLATES`).
+    pub template: Option<String>,
+    /// Spec note content; overrides the template's spec.
+    pub spec_content: Option<String>,
+    /// Whether to seed a ROUTA coordinator; defaults to the template's choice.
+    pub create_coordinator: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateResult {
     pub workspace: Workspace,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_note_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coordinator_agent_id: Option<String>,
 }
 
 pub async fn create(state: &AppState, params: CreateParams) -> Result<CreateResult, RpcError> {
+    let template = params
+        .template
+        .as_deref()
+        .map(|name| {
+            find_template(name).ok_or_else(|| {
+                let known: Vec<&str> = WORKSPACE_TEMPLATES.iter().map(|t| t.name).collect();
+                RpcError::BadRequest(format!(
+                    "Unknown workspace template: {name} (available: {})",
+                    known.join(", ")
+                ))
+            })
+        })
+        .transpose()?;
+
     let ws = Workspace::new(
         uuid::Uuid::new_v4().to_string(),
         params.title,
         params.metadata,
     );
 
-    state.workspace_store.save(&ws).await?;
-    Ok(CreateResult { workspace: ws })
+    let spec_content = params
+        .spec_content
+        .or_else(|| template.map(|t| t.spec_content.to_string()));
+    let spec_note = spec_content.map(|content| {
+        let mut note = Note::new_spec(ws.id.clone());
+        note.content = content;
+        note
+    });
+
+    let coordinator = params
+        .create_coordinator
+        .unwrap_or_else(|| template.is_some_and(|t| t.coordinator))
+        .then(|| {
+            Agent::new(
+                uuid::Uuid::new_v4().to_string(),
+                "routa-coordinator".to_string(),
+                AgentRole::Routa,
+                ws.id.clone(),
+                None,
+                None,
+                None,
+            )
+        });
+
+    let spec_note_id = spec_note.as_ref().map(|note| note.id.clone());
+    let coordinator_agent_id = coordinator.as_ref().map(|agent| agent.id.clone());
+    let saved_ws = ws.clone();
+    let max_note_versions = state.note_store.max_versions();
+    state
+        .db
+        .transaction(move |conn| {
+            WorkspaceStore::save_in(conn, &saved_ws)?;
+            if let Some(note) = &spec_note {
+                NoteStore::save_in(conn, note, max_note_versions)?;
+            }
+            if let Some(agent) = &coordinator {
+                AgentStore::save_in(conn, agent)?;
+            }
+            Ok(())
+        })
+        .await?;
+
+    Ok(CreateResult {
+        workspace: ws,
+        spec_note_id,
+        coordinator_agent_id,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// workspaces.rename
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameParams {
+    pub id: String,
+    pub title: String,
+}
+
+pub async fn rename(state: &AppState, params: RenameParams) -> Result<Workspace, RpcError> {
+    let title = params.title.trim();
+    if title.is_empty() {
+        return Err(RpcError::BadRequest(
+            "Workspace title cannot be empty".to_string(),
+        ));
+    }
+    let previous = get(state, GetParams { id: params.id }).await?;
+    state
+        .workspace_store
+        .update_title(&previous.id, title)
+        .await?;
+    let workspace = get(
+        state,
+        GetParams {
+            id: previous.id.clone(),
+        },
+    )
+    .await?;
+
+    state
+        .event_bus
+        .emit(AgentEvent::workspace_renamed(
+            "rpc",
+            workspace.id.clone(),
+            WorkspaceRenamedData {
+                title: workspace.title.clone(),
+                previous_title: previous.title,
+            },
+        ))
+        .await;
+
+    Ok(workspace)
 }
 
 // ---------------------------------------------------------------------------
@@ -91,3 +249,203 @@ pub async fn delete(state: &AppState, params: DeleteParams) -> Result<DeleteResu
     state.workspace_store.delete(&params.id).await?;
     Ok(DeleteResult { deleted: true })
 }
+
+// ---------------------------------------------------------------------------
+// workspaces.workload
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadParams {
+    pub workspace_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentWorkload {
+    /// `None` for assignees that are no longer registered agents.
+    pub name: Option<String>,
+    pub role: Option<AgentRole>,
+    #[serde(flatten)]
+    pub workload: AssigneeWorkload,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadResult {
+    pub workspace_id: String,
+    /// Every agent in the workspace, idle ones included, least loaded first.
+    pub agents: Vec<AgentWorkload>,
+}
+
+pub async fn workload(
+    state: &AppState,
+    params: WorkloadParams,
+) -> Result<WorkloadResult, RpcError> {
+    let mut workloads: HashMap<String, AssigneeWorkload> = state
+        .task_store
+        .workload(&params.workspace_id)
+        .await?
+        .into_iter()
+        .map(|workload| (workload.agent_id.clone(), workload))
+        .collect();
+
+    let mut agents: Vec<AgentWorkload> = state
+        .agent_store
+        .list_by_workspace(&params.workspace_id)
+        .await?
+        .into_iter()
+        .map(|agent| AgentWorkload {
+            workload: workloads.remove(&agent.id).unwrap_or(AssigneeWorkload {
+                agent_id: agent.id.clone(),
+                counts: Default::default(),
+                total: 0,
+                current_task: None,
+            }),
+            name: Some(agent.name),
+            role: Some(agent.role),
+        })
+        .collect();
+    agents.extend(workloads.into_values().map(|workload| AgentWorkload {
+        name: None,
+        role: None,
+        workload,
+    }));
+    agents.sort_by(|a, b| {
+        open_task_count(&a.workload)
+            .cmp(&open_task_count(&b.workload))
+            .then_with(|| a.workload.agent_id.cmp(&b.workload.agent_id))
+    });
+
+    Ok(WorkloadResult {
+        workspace_id: params.workspace_id,
+        agents,
+    })
+}
+
+/// Tasks still on the agent's plate (not completed or cancelled).
+fn open_task_count(workload: &AssigneeWorkload) -> usize {
+    let done: usize = [TaskStatus::Completed, TaskStatus::Cancelled]
+        .iter()
+        .filter_map(|status| workload.counts.get(status.as_str()))
+        .sum();
+    workload.total - done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::note::{NoteType, SPEC_NOTE_ID};
+    use crate::{AppStateInner, Database};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn create_from_template_seeds_spec_and_coordinator() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+
+        let result = create(
+            &state,
+            CreateParams {
+                title: "Checkout revamp".to_string(),
+                metadata: None,
+                template: Some("feature".to_string()),
+                spec_content: None,
+                create_coordinator: None,
+            },
+        )
+        .await
+        .expect("workspace should be created");
+
+        let workspace_id = result.workspace.id.clone();
+        assert_eq!(result.spec_note_id.as_deref(), Some(SPEC_NOTE_ID));
+        let spec = state
+            .note_store
+            .get(SPEC_NOTE_ID, &workspace_id)
+            .await
+            .expect("spec lookup should succeed")
+            .expect("spec note should exist");
+        assert_eq!(spec.metadata.note_type, NoteType::Spec);
+        assert!(spec.content.contains("## Acceptance Criteria"));
+
+        let coordinator_id = result
+            .coordinator_agent_id
+            .expect("feature template seeds a coordinator");
+        let coordinator = state
+            .agent_store
+            .get(&coordinator_id)
+            .await
+            .expect("agent lookup should succeed")
+            .expect("coordinator should exist");
+        assert_eq!(coordinator.role, AgentRole::Routa);
+        assert_eq!(coordinator.workspace_id, workspace_id);
+
+        let unknown = create(
+            &state,
+            CreateParams {
+                title: "Nope".to_string(),
+                metadata: None,
+                template: Some("does-not-exist".to_string()),
+                spec_content: None,
+                create_coordinator: None,
+            },
+        )
+        .await;
+        assert!(matches!(unknown, Err(RpcError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn rename_changes_only_the_title_and_emits_workspace_updated() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        let mut original = Workspace::new(
+            "ws-rename".to_string(),
+            "Untitled".to_string(),
+            Some(HashMap::from([("repo".to_string(), "routa".to_string())])),
+        );
+        original.updated_at -= chrono::Duration::seconds(60);
+        state
+            .workspace_store
+            .save(&original)
+            .await
+            .expect("workspace should save");
+
+        let (tx, mut events) = tokio::sync::mpsc::unbounded_channel();
+        state
+            .event_bus
+            .on("rename-test", move |event| {
+                let _ = tx.send(event);
+            })
+            .await;
+
+        let renamed = rename(
+            &state,
+            RenameParams {
+                id: original.id.clone(),
+                title: " Checkout revamp ".to_string(),
+            },
+        )
+        .await
+        .expect("rename should succeed");
+        assert_eq!(renamed.title, "Checkout revamp");
+        assert_eq!(renamed.metadata, original.metadata);
+        assert_eq!(renamed.status, original.status);
+        assert!(renamed.updated_at > original.updated_at);
+
+        let event = events.recv().await.expect("rename should emit an event");
+        assert_eq!(event.event_type.as_str(), "WORKSPACE_UPDATED");
+        assert_eq!(event.workspace_id, original.id);
+        assert_eq!(event.data["scope"], "workspace");
+        assert_eq!(event.data["previousTitle"], "Untitled");
+
+        let missing = rename(
+            &state,
+            RenameParams {
+                id: "nope".to_string(),
+                title: "Anything".to_string(),
+            },
+        )
+        .await;
+        assert!(matches!(missing, Err(RpcError::NotFound(_))));
+    }
+}
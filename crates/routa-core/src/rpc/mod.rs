@@ -28,6 +28,6 @@ pub mod methods;
 pub mod router;
 pub mod types;
 
-pub use error::RpcError;
+pub use error::{FieldError, RpcError};
 pub use router::RpcRouter;
 pub use types::{JsonRpcRequest, JsonRpcResponse};
@@ -9,6 +9,11 @@
 //! - A napi-rs / wasm-bindgen function (JS bindgen)
 //! - Stdio (CLI)
 
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
 use crate::state::AppState;
 
 use super::error::RpcError;
@@ -27,16 +32,44 @@ use super::types::*;
 ///
 /// // From a parsed request:
 /// let response = router.dispatch(request).await;
+///
+/// // With an embedder-defined method:
+/// let router = RpcRouter::new(app_state).register("native.ping", |_state, _params| async {
+///     Ok(serde_json::json!({ "pong": true }))
+/// });
 /// ```
 #[derive(Clone)]
 pub struct RpcRouter {
     state: AppState,
+    custom: HashMap<String, RpcHandler>,
 }
 
+type RpcFuture = Pin<Box<dyn Future<Output = Result<serde_json::Value, RpcError>> + Send>>;
+
+/// A method handler registered through [`RpcRouter::register`].
+type RpcHandler = Arc<dyn Fn(AppState, serde_json::Value) -> RpcFuture + Send + Sync>;
+
 impl RpcRouter {
     /// Create a new router backed by the given application state.
     pub fn new(state: AppState) -> Self {
-        Self { state }
+        Self {
+            state,
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Add a handler for `method`, consulted before the built-in methods.
+    ///
+    /// Lets embedders extend the RPC surface under their own namespace
+    /// (e.g. `native.*` in the desktop app) without forking the router.
+    pub fn register<F, Fut>(mut self, method: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(AppState, serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<serde_json::Value, RpcError>> + Send + 'static,
+    {
+        let handler: RpcHandler = Arc::new(move |state, params| Box::pin(handler(state, params)));
+        self.custom.insert(method.into(), handler);
+        self
     }
 
     /// Handle a raw JSON string. Parses the request, dispatches it, and returns
@@ -119,6 +152,10 @@ impl RpcRouter {
         method: &str,
         params: serde_json::Value,
     ) -> Result<serde_json::Value, RpcError> {
+        if let Some(handler) = self.custom.get(method) {
+            return handler(self.state.clone(), params).await;
+        }
+
         match method {
             // ----- Agents -----
             "agents.list" => {
@@ -126,6 +163,11 @@ impl RpcRouter {
                 let r = methods::agents::list(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "agents.tree" => {
+                let p = parse_params(params)?;
+                let r = methods::agents::tree(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
             "agents.get" => {
                 let p = parse_params(params)?;
                 let r = methods::agents::get(&self.state, p).await?;
@@ -146,6 +188,12 @@ impl RpcRouter {
                 let r = methods::agents::update_status(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "agents.redactMessage" => {
+                let p = parse_params(params)?;
+                let r = methods::agents::redact_message(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "agents.reportToParent" => methods::agents::report_to_parent(&self.state, params).await,
 
             // ----- Tasks -----
             "tasks.list" => {
@@ -163,6 +211,16 @@ impl RpcRouter {
                 let r = methods::tasks::create(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "tasks.createBatch" => {
+                let p = parse_params(params)?;
+                let r = methods::tasks::create_batch(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "tasks.update" => {
+                let p = parse_params(params)?;
+                let r = methods::tasks::update(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
             "tasks.delete" => {
                 let p = parse_params(params)?;
                 let r = methods::tasks::delete(&self.state, p).await?;
@@ -173,11 +231,31 @@ impl RpcRouter {
                 let r = methods::tasks::update_status(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "tasks.setCriterionStatus" => {
+                let p = parse_params(params)?;
+                let r = methods::tasks::set_criterion_status(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "tasks.assign" => {
+                let p = parse_params(params)?;
+                let r = methods::tasks::assign(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "tasks.reopen" => {
+                let p = parse_params(params)?;
+                let r = methods::tasks::reopen(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
             "tasks.findReady" => {
                 let p = parse_params(params)?;
                 let r = methods::tasks::find_ready(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "tasks.search" => {
+                let p = parse_params(params)?;
+                let r = methods::tasks::search(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
             "tasks.listArtifacts" => {
                 let p = parse_params(params)?;
                 let r = methods::tasks::list_artifacts(&self.state, p).await?;
@@ -307,16 +385,71 @@ impl RpcRouter {
                 let r = methods::notes::get(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "notes.assignedTo" => {
+                let p = parse_params(params)?;
+                let r = methods::notes::assigned_to(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
             "notes.create" => {
                 let p = parse_params(params)?;
                 let r = methods::notes::create(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "notes.rename" => {
+                let p = parse_params(params)?;
+                let r = methods::notes::rename(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
             "notes.delete" => {
                 let p = parse_params(params)?;
                 let r = methods::notes::delete(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "notes.history" => {
+                let p = parse_params(params)?;
+                let r = methods::notes::history(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "notes.revert" => {
+                let p = parse_params(params)?;
+                let r = methods::notes::revert(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+
+            // ----- Orchestration -----
+            "orchestration.status" => {
+                let p = parse_params(params)?;
+                let r = methods::orchestration::status(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+
+            // ----- Providers -----
+            "providers.list" => {
+                let r = methods::providers::list(&self.state).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "providers.setCredential" => {
+                let p = parse_params(params)?;
+                let r = methods::providers::set_credential(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "providers.getMasked" => {
+                let p = parse_params(params)?;
+                let r = methods::providers::get_masked(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "providers.refreshModels" => {
+                let p = parse_params(params)?;
+                let r = methods::providers::refresh_models(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+
+            // ----- Sessions -----
+            "sessions.setMetadata" => {
+                let p = parse_params(params)?;
+                let r = methods::sessions::set_metadata(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
 
             // ----- Workspaces -----
             "workspaces.list" => {
@@ -333,11 +466,40 @@ impl RpcRouter {
                 let r = methods::workspaces::create(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "workspaces.rename" => {
+                let p = parse_params(params)?;
+                let r = methods::workspaces::rename(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
             "workspaces.delete" => {
                 let p = parse_params(params)?;
                 let r = methods::workspaces::delete(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
+            "workspaces.workload" => {
+                let p = parse_params(params)?;
+                let r = methods::workspaces::workload(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+
+            // ----- Codebases -----
+            "codebases.checkout" => {
+                let p = parse_params(params)?;
+                let r = methods::codebases::checkout(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "codebases.currentBranch" => {
+                let p = parse_params(params)?;
+                let r = methods::codebases::current_branch(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+
+            // ----- Database -----
+            "db.backup" => {
+                let p = parse_params(params)?;
+                let r = methods::db::backup(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
 
             // ----- Skills -----
             "skills.list" => {
@@ -350,7 +512,18 @@ impl RpcRouter {
                 Ok(serde_json::to_value(r).unwrap())
             }
             "skills.reload" => {
-                let r = methods::skills::reload(&self.state).await?;
+                let p = parse_params(params)?;
+                let r = methods::skills::reload(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "skills.topInstalled" => {
+                let p = parse_params(params)?;
+                let r = methods::skills::top_installed(&self.state, p).await?;
+                Ok(serde_json::to_value(r).unwrap())
+            }
+            "skills.use" => {
+                let p = parse_params(params)?;
+                let r = methods::skills::use_skill(&self.state, p).await?;
                 Ok(serde_json::to_value(r).unwrap())
             }
 
@@ -361,59 +534,234 @@ impl RpcRouter {
         }
     }
 
-    /// Return a list of all supported RPC method names.
-    /// Useful for introspection / discovery endpoints.
-    pub fn method_list(&self) -> Vec<&'static str> {
-        vec![
-            "agents.list",
-            "agents.get",
-            "agents.create",
-            "agents.delete",
-            "agents.updateStatus",
-            "tasks.list",
-            "tasks.get",
-            "tasks.create",
-            "tasks.delete",
-            "tasks.updateStatus",
-            "tasks.findReady",
-            "tasks.listArtifacts",
-            "tasks.provideArtifact",
-            "kanban.listBoards",
-            "kanban.createBoard",
-            "kanban.getBoard",
-            "kanban.updateBoard",
-            "kanban.createCard",
-            "kanban.moveCard",
-            "kanban.updateCard",
-            "kanban.deleteCard",
-            "kanban.createColumn",
-            "kanban.deleteColumn",
-            "kanban.searchCards",
-            "kanban.listCardsByColumn",
-            "kanban.listCards",
-            "kanban.boardStatus",
-            "kanban.decomposeTasks",
-            "kanban.listAutomations",
-            "kanban.triggerAutomation",
-            "kanban.createIssueFromCard",
-            "kanban.syncGitHubIssues",
-            "notes.list",
-            "notes.get",
-            "notes.create",
-            "notes.delete",
-            "workspaces.list",
-            "workspaces.get",
-            "workspaces.create",
-            "workspaces.delete",
-            "skills.list",
-            "skills.get",
-            "skills.reload",
-        ]
+    /// Return a list of all supported RPC method names, including
+    /// registered ones. Useful for introspection / discovery endpoints.
+    pub fn method_list(&self) -> Vec<String> {
+        let mut methods: Vec<String> = BUILTIN_METHODS.iter().map(|m| m.to_string()).collect();
+        let mut custom: Vec<String> = self
+            .custom
+            .keys()
+            .filter(|method| !BUILTIN_METHODS.contains(&method.as_str()))
+            .cloned()
+            .collect();
+        custom.sort();
+        methods.extend(custom);
+        methods
     }
 }
 
+/// Methods handled by [`RpcRouter::route`]'s built-in match.
+const BUILTIN_METHODS: &[&str] = &[
+    "agents.list",
+    "agents.tree",
+    "agents.get",
+    "agents.create",
+    "agents.delete",
+    "agents.updateStatus",
+    "agents.redactMessage",
+    "agents.reportToParent",
+    "tasks.list",
+    "tasks.get",
+    "tasks.create",
+    "tasks.createBatch",
+    "tasks.update",
+    "tasks.delete",
+    "tasks.updateStatus",
+    "tasks.setCriterionStatus",
+    "tasks.assign",
+    "tasks.reopen",
+    "tasks.findReady",
+    "tasks.search",
+    "tasks.listArtifacts",
+    "tasks.provideArtifact",
+    "kanban.listBoards",
+    "kanban.createBoard",
+    "kanban.getBoard",
+    "kanban.updateBoard",
+    "kanban.createCard",
+    "kanban.moveCard",
+    "kanban.updateCard",
+    "kanban.deleteCard",
+    "kanban.createColumn",
+    "kanban.deleteColumn",
+    "kanban.searchCards",
+    "kanban.listCardsByColumn",
+    "kanban.listCards",
+    "kanban.boardStatus",
+    "kanban.decomposeTasks",
+    "kanban.listAutomations",
+    "kanban.triggerAutomation",
+    "kanban.createIssueFromCard",
+    "kanban.syncGitHubIssues",
+    "notes.list",
+    "notes.get",
+    "notes.assignedTo",
+    "notes.create",
+    "notes.rename",
+    "notes.delete",
+    "notes.history",
+    "notes.revert",
+    "orchestration.status",
+    "providers.list",
+    "providers.setCredential",
+    "providers.getMasked",
+    "providers.refreshModels",
+    "sessions.setMetadata",
+    "workspaces.list",
+    "workspaces.get",
+    "workspaces.create",
+    "workspaces.rename",
+    "workspaces.delete",
+    "workspaces.workload",
+    "codebases.checkout",
+    "codebases.currentBranch",
+    "db.backup",
+    "skills.list",
+    "skills.get",
+    "skills.reload",
+    "skills.topInstalled",
+    "skills.use",
+];
+
 /// Helper: deserialize `serde_json::Value` into a typed params struct.
 fn parse_params<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> Result<T, RpcError> {
-    serde_json::from_value(value)
-        .map_err(|e| RpcError::InvalidParams(format!("Invalid params: {e}")))
+    serde_path_to_error::deserialize(value).map_err(RpcError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AppStateInner, Database};
+    use std::sync::Arc;
+
+    fn request(method: &str, params: serde_json::Value) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(serde_json::json!(1)),
+            method: method.to_string(),
+            params: Some(params),
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_params_report_the_failing_field() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let router = RpcRouter::new(Arc::new(AppStateInner::new(db)));
+
+        let missing = router
+            .dispatch(request(
+                "tasks.create",
+                serde_json::json!({ "objective": "Ship it" }),
+            ))
+            .await
+            .error
+            .expect("missing title should fail");
+        assert_eq!(missing.code, INVALID_PARAMS);
+        assert_eq!(missing.message, "Invalid params: missing field `title`");
+        assert_eq!(
+            missing.data,
+            Some(serde_json::json!([{ "field": "title", "reason": "is required" }]))
+        );
+
+        let mistyped = router
+            .dispatch(request(
+                "tasks.create",
+                serde_json::json!({
+                    "title": "Fix",
+                    "objective": "Ship it",
+                    "acceptanceCriteria": ["ok", 7],
+                }),
+            ))
+            .await
+            .error
+            .expect("non-string criterion should fail");
+        let fields = mistyped.data.expect("field errors should be attached");
+        assert_eq!(fields[0]["field"], "acceptanceCriteria[1]");
+    }
+
+    #[tokio::test]
+    async fn registered_methods_dispatch_before_builtins() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let router = RpcRouter::new(Arc::new(AppStateInner::new(db)))
+            .register("native.echo", |_state, params| async move {
+                Ok(serde_json::json!({ "echo": params["text"] }))
+            });
+
+        let response = router
+            .dispatch(request("native.echo", serde_json::json!({ "text": "hi" })))
+            .await;
+        assert_eq!(response.result, Some(serde_json::json!({ "echo": "hi" })));
+
+        let missing = router
+            .dispatch(request("native.unknown", serde_json::json!({})))
+            .await
+            .error
+            .expect("unregistered method should fail");
+        assert_eq!(missing.code, METHOD_NOT_FOUND);
+
+        let methods = router.method_list();
+        assert!(methods.iter().any(|m| m == "native.echo"));
+        assert!(methods.iter().any(|m| m == "tasks.create"));
+    }
+
+    #[tokio::test]
+    async fn report_to_parent_parses_free_text_like_the_mcp_tool() {
+        use crate::models::agent::{Agent, AgentRole};
+        use crate::models::task::{Task, TaskStatus};
+
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state = Arc::new(AppStateInner::new(db));
+        state.workspace_store.ensure_default().await.unwrap();
+        let agent = |id: &str, role, parent: Option<&str>| {
+            Agent::new(
+                id.to_string(),
+                id.to_string(),
+                role,
+                "default".to_string(),
+                parent.map(str::to_string),
+                None,
+                None,
+            )
+        };
+        state
+            .agent_store
+            .save(&agent("routa", AgentRole::Routa, None))
+            .await
+            .unwrap();
+        state
+            .agent_store
+            .save(&agent("gate-1", AgentRole::Gate, Some("routa")))
+            .await
+            .unwrap();
+        let task = Task::new(
+            "task-1".to_string(),
+            "Review".to_string(),
+            "Review the change".to_string(),
+            "default".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        state.task_store.save(&task).await.unwrap();
+        let router = RpcRouter::new(state.clone());
+
+        let response = router
+            .dispatch(request(
+                "agents.reportToParent",
+                serde_json::json!({
+                    "agentId": "gate-1",
+                    "taskId": "task-1",
+                    "summary": "Verdict: REJECTED. The migration is missing.",
+                }),
+            ))
+            .await;
+        assert!(response.error.is_none(), "{:?}", response.error);
+
+        let task = state.task_store.get("task-1").await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::NeedsFix);
+    }
 }
@@ -60,6 +60,8 @@ pub const INTERNAL_ERROR: i64 = -32603;
 // Application-defined error codes (server range: -32000 to -32099)
 pub const NOT_FOUND: i64 = -32001;
 pub const BAD_REQUEST: i64 = -32002;
+pub const UNAUTHORIZED: i64 = -32003;
+pub const FORBIDDEN: i64 = -32004;
 
 impl JsonRpcResponse {
     /// Build a success response.
@@ -16,6 +16,8 @@ use chrono::Utc;
 use serde_json::Value;
 use tokio::process::Command;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 use crate::acp::docker::find_available_port;
 
@@ -51,6 +53,12 @@ impl Default for SandboxManager {
 impl SandboxManager {
     /// Create a new `SandboxManager` and spawn the background idle-cleanup task.
     pub fn new() -> Self {
+        Self::with_shutdown(CancellationToken::new())
+    }
+
+    /// Like [`new`](Self::new), stopping the idle-cleanup task once `shutdown`
+    /// is cancelled.
+    pub fn with_shutdown(shutdown: CancellationToken) -> Self {
         let sandboxes = Arc::new(RwLock::new(HashMap::new()));
         let last_active = Arc::new(RwLock::new(HashMap::new()));
         let used_ports = Arc::new(RwLock::new(HashSet::new()));
@@ -63,13 +71,13 @@ impl SandboxManager {
         };
 
         // Spawn background task to terminate idle sandboxes.
-        mgr.spawn_idle_cleanup();
+        mgr.spawn_idle_cleanup(shutdown);
 
         mgr
     }
 
     /// Spawn a Tokio task that periodically terminates idle sandboxes.
-    fn spawn_idle_cleanup(&self) {
+    fn spawn_idle_cleanup(&self, shutdown: CancellationToken) -> JoinHandle<()> {
         let sandboxes = self.sandboxes.clone();
         let last_active = self.last_active.clone();
         let used_ports = self.used_ports.clone();
@@ -78,7 +86,10 @@ impl SandboxManager {
             let mut interval =
                 tokio::time::interval(Duration::from_secs(SANDBOX_CHECK_INTERVAL_SECS));
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
                 let now = Instant::now();
                 let ids: Vec<String> = last_active.read().await.keys().cloned().collect();
 
@@ -109,7 +120,7 @@ impl SandboxManager {
                     }
                 }
             }
-        });
+        })
     }
 
     // ── Public API ───────────────────────────────────────────────────────────
@@ -463,6 +474,20 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn idle_cleanup_stops_on_shutdown() {
+        let shutdown = CancellationToken::new();
+        let mgr = SandboxManager::with_shutdown(shutdown.clone());
+        let monitor = mgr.spawn_idle_cleanup(shutdown.clone());
+        assert!(!monitor.is_finished());
+
+        shutdown.cancel();
+        tokio::time::timeout(Duration::from_secs(1), monitor)
+            .await
+            .expect("idle cleanup should stop after shutdown")
+            .expect("idle cleanup should not panic");
+    }
+
     #[tokio::test]
     async fn list_sandboxes_empty_by_default() {
         let mgr = SandboxManager::new();
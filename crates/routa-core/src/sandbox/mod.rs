@@ -31,7 +31,8 @@ pub use policy::{
     ResolvedSandboxPolicy, ResolvedSandboxWorkspaceConfig, SandboxCapability,
     SandboxCapabilityTier, SandboxEnvFileSource, SandboxEnvMode, SandboxLinkedWorktreeMode,
     SandboxMount, SandboxMountAccess, SandboxNetworkMode, SandboxPermissionConstraints,
-    SandboxPolicyContext, SandboxPolicyInput, SandboxPolicyWorktree, SANDBOX_SCOPE_CONTAINER_ROOT,
+    SandboxPolicyContext, SandboxPolicyError, SandboxPolicyInput, SandboxPolicyWorktree,
+    SANDBOX_SCOPE_CONTAINER_ROOT,
 };
 pub use types::{
     CreateSandboxRequest, ExecuteRequest, ResolvedCreateSandboxRequest, SandboxInfo,
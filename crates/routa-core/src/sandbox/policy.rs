@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
 use super::env::parse_env_file_keys;
+use crate::error::ServerError;
 
 mod permission_constraints;
 pub use permission_constraints::SandboxPermissionConstraints;
@@ -14,6 +15,32 @@ const SANDBOX_EXTRA_READONLY_ROOT: &str = "/workspace-extra/ro";
 const SANDBOX_EXTRA_READWRITE_ROOT: &str = "/workspace-extra/rw";
 const SANDBOX_LINKED_WORKTREE_ROOT: &str = "/workspace-worktrees";
 
+/// Why [`SandboxPolicyInput::resolve`] rejected a policy.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SandboxPolicyError {
+    /// The policy is malformed or inconsistent.
+    #[error("{0}")]
+    Invalid(String),
+    /// The policy reaches outside the workspace it is scoped to.
+    #[error("{0}")]
+    OutsideScope(String),
+}
+
+impl From<String> for SandboxPolicyError {
+    fn from(message: String) -> Self {
+        Self::Invalid(message)
+    }
+}
+
+impl From<SandboxPolicyError> for ServerError {
+    fn from(err: SandboxPolicyError) -> Self {
+        match err {
+            SandboxPolicyError::Invalid(message) => ServerError::BadRequest(message),
+            SandboxPolicyError::OutsideScope(message) => ServerError::Forbidden(message),
+        }
+    }
+}
+
 fn is_false(value: &bool) -> bool {
     !*value
 }
@@ -139,7 +166,7 @@ impl SandboxPolicyInput {
     pub fn resolve(
         &self,
         context: Option<SandboxPolicyContext>,
-    ) -> Result<ResolvedSandboxPolicy, String> {
+    ) -> Result<ResolvedSandboxPolicy, SandboxPolicyError> {
         let derived_root = context
             .as_ref()
             .and_then(|ctx| ctx.workspace_root.as_ref())
@@ -163,11 +190,11 @@ impl SandboxPolicyInput {
 
         let scope_root = derived_root.clone().unwrap_or_else(|| host_workdir.clone());
         if !is_within(&scope_root, &host_workdir) {
-            return Err(format!(
+            return Err(SandboxPolicyError::OutsideScope(format!(
                 "Resolved workdir '{}' escapes scope root '{}'.",
                 host_workdir.display(),
                 scope_root.display()
-            ));
+            )));
         }
 
         let mut notes = Vec::new();
@@ -192,9 +219,9 @@ impl SandboxPolicyInput {
         if !read_write_paths.is_empty()
             && !capability_set.contains(&SandboxCapability::WorkspaceWrite)
         {
-            return Err(
+            return Err(SandboxPolicyError::Invalid(
                 "Sandbox policy readWritePaths require the workspaceWrite capability.".to_string(),
-            );
+            ));
         }
 
         let read_write_set: BTreeSet<PathBuf> = read_write_paths.iter().cloned().collect();
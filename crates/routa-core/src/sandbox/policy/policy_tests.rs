@@ -95,7 +95,8 @@ fn resolve_policy_rejects_workdir_outside_scope_root() {
     let err = policy
         .resolve(Some(context))
         .expect_err("workdir outside root should fail");
-    assert!(err.contains("escapes scope root"));
+    assert!(matches!(err, SandboxPolicyError::OutsideScope(_)));
+    assert!(err.to_string().contains("escapes scope root"));
 }
 
 #[test]
@@ -243,7 +244,9 @@ fn invalid_trusted_workspace_config_fails_resolution() {
     }))
     .expect_err("invalid trusted config should fail");
 
-    assert!(err.contains("Failed to parse trusted workspace sandbox config"));
+    assert!(err
+        .to_string()
+        .contains("Failed to parse trusted workspace sandbox config"));
 }
 
 #[test]
@@ -261,7 +264,7 @@ fn read_write_grants_require_workspace_write_capability() {
     .resolve(None)
     .expect_err("write grants should require explicit capability");
 
-    assert!(err.contains("workspaceWrite capability"));
+    assert!(err.to_string().contains("workspaceWrite capability"));
 }
 
 #[test]
@@ -5,8 +5,9 @@
 //! - Windows: usually fine, but may miss user-installed tools
 //! - Linux: depends on the desktop environment
 //!
-//! This module recovers the user's login-shell PATH so we can find
-//! CLI tools like `opencode`, `claude`, `gemini`, etc.
+//! On Unix this module recovers the user's login-shell PATH; on Windows it
+//! merges PATH with common install locations (Program Files, npm, uv, Scoop).
+//! Either way we can find CLI tools like `git`, `node`, `claude`, etc.
 
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
@@ -25,45 +26,76 @@ pub fn full_path() -> &'static str {
     FULL_PATH.get_or_init(resolve_full_path)
 }
 
-/// Resolve PATH by merging current PATH, login-shell PATH, and well-known dirs.
+/// Resolve PATH by merging the login-shell PATH (Unix), the current PATH and
+/// well-known install directories that exist on disk.
 fn resolve_full_path() -> String {
     let current = std::env::var("PATH").unwrap_or_default();
     let home = dirs::home_dir().unwrap_or_default();
 
+    #[cfg(not(windows))]
+    let (shell_path, candidates) = (resolve_unix_shell_path(), well_known_dirs(&home));
+    #[cfg(windows)]
+    let (shell_path, candidates) = (
+        None::<String>,
+        windows_install_dirs(&home, |key| std::env::var(key).ok()),
+    );
+
+    let result = merge_path(
+        shell_path.as_deref(),
+        &current,
+        &candidates,
+        PATH_SEP,
+        cfg!(windows),
+        |dir| dir.is_dir(),
+    );
+    tracing::info!(
+        "[shell_env] Resolved PATH ({} entries)",
+        result.split(PATH_SEP).count()
+    );
+    tracing::debug!("[shell_env] Full PATH: {}", result);
+    result
+}
+
+/// Merge PATH sources in priority order: `shell_path`, then `current`, then
+/// each of `candidates` that `is_dir` accepts. Empty and duplicate entries are
+/// dropped; `case_insensitive` compares entries the way Windows does.
+fn merge_path(
+    shell_path: Option<&str>,
+    current: &str,
+    candidates: &[PathBuf],
+    sep: char,
+    case_insensitive: bool,
+    is_dir: impl Fn(&Path) -> bool,
+) -> String {
     let mut seen = std::collections::HashSet::new();
     let mut parts: Vec<String> = Vec::new();
 
     let mut add = |p: &str| {
-        if !p.is_empty() && seen.insert(p.to_string()) {
+        let trimmed = p.trim_end_matches(['/', '\\']);
+        let key = if trimmed.is_empty() { p } else { trimmed };
+        let key = if case_insensitive {
+            key.to_lowercase()
+        } else {
+            key.to_string()
+        };
+        if !p.is_empty() && seen.insert(key) {
             parts.push(p.to_string());
         }
     };
 
-    // 1. Try to get the real PATH from the user's login shell (Unix only)
-    #[cfg(not(windows))]
-    if let Some(shell_path) = resolve_unix_shell_path() {
-        for p in shell_path.split(PATH_SEP) {
-            add(p);
-        }
+    for p in shell_path.unwrap_or_default().split(sep) {
+        add(p);
     }
-
-    // 2. Merge current process PATH
-    for p in current.split(PATH_SEP) {
+    for p in current.split(sep) {
         add(p);
     }
-
-    // 3. Add well-known directories
-    for dir in well_known_dirs(&home) {
-        let d = dir.to_string_lossy().to_string();
-        if dir.is_dir() {
-            add(&d);
+    for dir in candidates {
+        if is_dir(dir) {
+            add(&dir.to_string_lossy());
         }
     }
 
-    let result = parts.join(&PATH_SEP.to_string());
-    tracing::info!("[shell_env] Resolved PATH ({} entries)", parts.len());
-    tracing::debug!("[shell_env] Full PATH: {}", result);
-    result
+    parts.join(&sep.to_string())
 }
 
 /// Unix: try running the user's login shell to get $PATH.
@@ -96,8 +128,10 @@ fn resolve_unix_shell_path() -> Option<String> {
     None
 }
 
-/// Well-known directories where user CLI tools may be installed.
+/// Well-known directories where user CLI tools may be installed on Unix.
+#[cfg_attr(windows, allow(dead_code))]
 fn well_known_dirs(home: &Path) -> Vec<PathBuf> {
+    #[allow(unused_mut)]
     let mut dirs = vec![
         home.join(".local").join("bin"),
         home.join(".cargo").join("bin"),
@@ -126,25 +160,43 @@ fn well_known_dirs(home: &Path) -> Vec<PathBuf> {
         dirs.push(PathBuf::from("/home/linuxbrew/.linuxbrew/bin"));
     }
 
-    #[cfg(windows)]
-    {
-        // Common Windows install locations
-        if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
-            let lad = PathBuf::from(&local_app_data);
-            dirs.push(lad.join("Programs"));
-            dirs.push(lad.join("Microsoft").join("WinGet").join("Packages"));
-        }
-        if let Ok(app_data) = std::env::var("APPDATA") {
-            let ad = PathBuf::from(&app_data);
-            dirs.push(ad.join("npm"));
-        }
+    dirs
+}
+
+/// Common Windows install locations for git, node, npm globals and uv.
+/// `env` looks up environment variables so tests can supply their own.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn windows_install_dirs(home: &Path, env: impl Fn(&str) -> Option<String>) -> Vec<PathBuf> {
+    let mut dirs = vec![
+        // uv, pipx and other XDG-style installers
+        home.join(".local").join("bin"),
+        home.join(".cargo").join("bin"),
+        home.join(".bun").join("bin"),
+        home.join(".opencode").join("bin"),
         // Scoop
-        dirs.push(home.join("scoop").join("shims"));
-        // Chocolatey
-        if let Ok(choco) = std::env::var("ChocolateyInstall") {
-            dirs.push(PathBuf::from(choco).join("bin"));
+        home.join("scoop").join("shims"),
+    ];
+
+    for program_files in ["ProgramFiles", "ProgramFiles(x86)", "ProgramW6432"] {
+        if let Some(root) = env(program_files) {
+            let root = PathBuf::from(root);
+            dirs.push(root.join("Git").join("cmd"));
+            dirs.push(root.join("nodejs"));
         }
     }
+    if let Some(local_app_data) = env("LOCALAPPDATA") {
+        let lad = PathBuf::from(local_app_data);
+        dirs.push(lad.join("Programs"));
+        dirs.push(lad.join("Programs").join("Git").join("cmd"));
+        dirs.push(lad.join("Microsoft").join("WindowsApps"));
+        dirs.push(lad.join("Microsoft").join("WinGet").join("Links"));
+    }
+    if let Some(app_data) = env("APPDATA") {
+        dirs.push(PathBuf::from(app_data).join("npm"));
+    }
+    if let Some(choco) = env("ChocolateyInstall") {
+        dirs.push(PathBuf::from(choco).join("bin"));
+    }
 
     dirs
 }
@@ -217,10 +269,84 @@ fn which_in_path_windows(cmd: &str, path: &str, pathext: &str) -> Option<String>
     None
 }
 
-#[cfg(all(test, windows))]
+#[cfg(test)]
 mod tests {
+    #[cfg(windows)]
     use super::which_in_path_windows;
+    use super::{merge_path, windows_install_dirs};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn unix_merge_puts_login_shell_first_and_skips_missing_dirs() {
+        let candidates = vec![
+            PathBuf::from("/opt/homebrew/bin"),
+            PathBuf::from("/home/me/.cargo/bin"),
+            PathBuf::from("/usr/bin"),
+        ];
+        let merged = merge_path(
+            Some("/opt/homebrew/bin:/usr/bin"),
+            "/usr/bin:/bin::/usr/bin/",
+            &candidates,
+            ':',
+            false,
+            |dir| dir != Path::new("/home/me/.cargo/bin"),
+        );
+        assert_eq!(merged, "/opt/homebrew/bin:/usr/bin:/bin");
+    }
 
+    #[test]
+    fn unix_merge_keeps_entries_differing_only_in_case() {
+        let merged = merge_path(None, "/opt/Tools:/opt/tools", &[], ':', false, |_| true);
+        assert_eq!(merged, "/opt/Tools:/opt/tools");
+    }
+
+    #[test]
+    fn windows_merge_appends_install_dirs_case_insensitively() {
+        let env: HashMap<&str, &str> = HashMap::from([
+            ("ProgramFiles", r"C:\Program Files"),
+            ("LOCALAPPDATA", r"C:\Users\me\AppData\Local"),
+            ("APPDATA", r"C:\Users\me\AppData\Roaming"),
+        ]);
+        let home = PathBuf::from(r"C:\Users\me");
+        let candidates = windows_install_dirs(&home, |key| env.get(key).map(|v| v.to_string()));
+
+        let npm = PathBuf::from(r"C:\Users\me\AppData\Roaming").join("npm");
+        let git = PathBuf::from(r"C:\Program Files").join("Git").join("cmd");
+        let node = PathBuf::from(r"C:\Program Files").join("nodejs");
+        let uv = home.join(".local").join("bin");
+        for expected in [&npm, &git, &node, &uv] {
+            assert!(
+                candidates.contains(expected),
+                "missing {}",
+                expected.display()
+            );
+        }
+        assert!(!candidates
+            .iter()
+            .any(|dir| dir.to_string_lossy().contains("(x86)")));
+
+        let installed = [&git, &npm, &uv];
+        let current = format!(
+            r"C:\WINDOWS\system32;{};C:\Windows\System32\",
+            git.to_string_lossy().to_uppercase()
+        );
+        let merged = merge_path(None, &current, &candidates, ';', true, |dir| {
+            installed.contains(&&dir.to_path_buf())
+        });
+        let entries: Vec<&str> = merged.split(';').collect();
+        assert_eq!(entries[0], r"C:\WINDOWS\system32");
+        assert_eq!(entries[1], git.to_string_lossy().to_uppercase());
+        assert_eq!(
+            &entries[2..],
+            [
+                uv.to_string_lossy().as_ref(),
+                npm.to_string_lossy().as_ref(),
+            ]
+        );
+    }
+
+    #[cfg(windows)]
     #[test]
     fn windows_which_prefers_spawnable_extension_before_shim() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -243,6 +369,7 @@ mod tests {
         );
     }
 
+    #[cfg(windows)]
     #[test]
     fn windows_which_keeps_explicit_extension_resolution() {
         let temp = tempfile::tempdir().expect("tempdir");
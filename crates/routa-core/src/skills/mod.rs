@@ -57,6 +57,35 @@ pub struct SkillDefinition {
     pub metadata: HashMap<String, String>,
 }
 
+impl SkillDefinition {
+    /// The markdown section of `content` under the heading named `heading`
+    /// (case-insensitive), including the heading and any nested subsections.
+    pub fn section(&self, heading: &str) -> Option<String> {
+        let wanted = heading.trim().trim_start_matches('#').trim();
+        let mut in_fence = false;
+        let mut level = None;
+        let mut lines = Vec::new();
+        for line in self.content.lines() {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+            }
+            let heading_level = (!in_fence)
+                .then(|| line.chars().take_while(|c| *c == '#').count())
+                .filter(|n| *n > 0 && line[*n..].starts_with(' '));
+            match (level, heading_level) {
+                (None, Some(n)) if line[n..].trim().eq_ignore_ascii_case(wanted) => {
+                    level = Some(n);
+                }
+                (Some(current), Some(n)) if n <= current => break,
+                (None, _) => continue,
+                _ => {}
+            }
+            lines.push(line);
+        }
+        level.map(|_| lines.join("\n").trim_end().to_string())
+    }
+}
+
 /// Well-known directory patterns where skills can be found.
 const SKILL_DIRS: &[&str] = &[
     ".opencode/skills",
@@ -89,6 +118,14 @@ impl SkillRegistry {
 
     /// Discover and load skills from well-known directories.
     pub fn reload(&self, cwd: &str) {
+        self.reload_from(cwd, &[]);
+    }
+
+    /// Like [`reload`](Self::reload), but also scans `paths` — e.g. a project
+    /// checked out outside `cwd`. Each path is scanned both for the well-known
+    /// skill directories and as a skills directory itself. Skills found there
+    /// never replace a same-named skill from `cwd` or the home directory.
+    pub fn reload_from(&self, cwd: &str, paths: &[String]) {
         let mut discovered = HashMap::new();
 
         let cwd_path = Path::new(cwd);
@@ -111,6 +148,25 @@ impl SkillRegistry {
             }
         }
 
+        for path in paths {
+            let root = Path::new(path);
+            if !root.is_dir() {
+                tracing::warn!("Skipping skill path {}: not a directory", path);
+                continue;
+            }
+            let mut extra = HashMap::new();
+            discover_skills_in_dir(root, &mut extra);
+            for dir_pattern in SKILL_DIRS {
+                let skill_dir = root.join(dir_pattern);
+                if skill_dir.is_dir() {
+                    discover_skills_in_dir(&skill_dir, &mut extra);
+                }
+            }
+            for (name, skill) in extra {
+                discovered.entry(name).or_insert(skill);
+            }
+        }
+
         let count = discovered.len();
         if let Ok(mut skills) = self.skills.write() {
             *skills = discovered;
@@ -260,3 +316,73 @@ fn parse_skill_file(path: &Path) -> Option<SkillDefinition> {
         metadata: HashMap::new(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(dir: &Path, name: &str, description: &str) {
+        let skill_dir = dir.join(name);
+        std::fs::create_dir_all(&skill_dir).expect("skill dir");
+        std::fs::write(
+            skill_dir.join(SKILL_FILENAME),
+            format!("---\nname: {name}\ndescription: {description}\n---\n\nDo the thing."),
+        )
+        .expect("write SKILL.md");
+    }
+
+    #[test]
+    fn section_extracts_heading_with_subsections() {
+        let skill = SkillDefinition {
+            name: "review".to_string(),
+            description: "Review code".to_string(),
+            short_description: None,
+            content: "Intro\n\n## Checklist\n- tests\n### Style\n```\n# not a heading\n```\n## Output\nSummary".to_string(),
+            source: "SKILL.md".to_string(),
+            license: None,
+            compatibility: None,
+            metadata: HashMap::new(),
+        };
+
+        assert_eq!(
+            skill.section("checklist").as_deref(),
+            Some("## Checklist\n- tests\n### Style\n```\n# not a heading\n```")
+        );
+        assert_eq!(
+            skill.section("## Output").as_deref(),
+            Some("## Output\nSummary")
+        );
+        assert!(skill.section("Missing").is_none());
+    }
+
+    #[test]
+    fn reload_from_scans_extra_directories_without_overriding() {
+        let cwd = tempfile::tempdir().expect("cwd");
+        write_skill(&cwd.path().join(".agents/skills"), "shared", "from cwd");
+        let extra = tempfile::tempdir().expect("extra");
+        write_skill(extra.path(), "extra-only", "from extra root");
+        write_skill(
+            &extra.path().join(".claude/skills"),
+            "nested",
+            "from extra project",
+        );
+        write_skill(extra.path(), "shared", "from extra");
+
+        let registry = SkillRegistry::new();
+        registry.reload_from(
+            &cwd.path().to_string_lossy(),
+            &[
+                extra.path().to_string_lossy().to_string(),
+                "/definitely/not/a/dir".to_string(),
+            ],
+        );
+
+        let extra_only = registry.get_skill("extra-only").expect("extra skill");
+        assert_eq!(extra_only.description, "from extra root");
+        assert!(registry.get_skill("nested").is_some());
+        assert_eq!(
+            registry.get_skill("shared").expect("shared").description,
+            "from cwd"
+        );
+    }
+}
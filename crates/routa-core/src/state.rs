@@ -2,19 +2,25 @@
 
 use std::sync::Arc;
 
+use tokio_util::sync::CancellationToken;
+
 use crate::acp::{
     docker::{DockerDetector, DockerProcessManager},
     AcpBinaryManager, AcpInstallationState, AcpManager, AcpPaths, AcpRuntimeManager,
-    AcpWarmupService,
+    AcpWarmupService, ProviderModelsCache,
 };
 use crate::db::Database;
+use crate::error::ServerError;
 use crate::events::EventBus;
+use crate::orchestration::{OrchestratorConfig, RoutaOrchestrator};
 use crate::sandbox::SandboxManager;
 use crate::skills::SkillRegistry;
 use crate::store::{
-    AcpSessionStore, AgentStore, ArtifactStore, CodebaseStore, ConversationStore, KanbanStore,
-    NoteStore, ScheduleStore, TaskStore, WorkspaceStore, WorktreeStore,
+    AcpSessionStore, AgentStore, ArtifactStore, CodebaseStore, ConversationStore, DelegationStore,
+    EventStore, KanbanStore, NoteStore, ProviderCredentialStore, ScheduleStore, SkillStore,
+    TaskStore, ToolAuditStore, WorkspaceStore, WorktreeStore,
 };
+use crate::tools::{AgentTools, CompletionReport, ToolResult};
 
 /// Docker state for managing Docker-based agent execution.
 #[derive(Default)]
@@ -35,18 +41,36 @@ pub struct AppStateInner {
     pub kanban_store: KanbanStore,
     pub note_store: NoteStore,
     pub schedule_store: ScheduleStore,
+    pub provider_credential_store: ProviderCredentialStore,
     pub conversation_store: ConversationStore,
+    pub delegation_store: DelegationStore,
     pub acp_session_store: AcpSessionStore,
+    /// Record of every coordination tool call, written best-effort.
+    pub tool_audit_store: ToolAuditStore,
+    pub skill_store: SkillStore,
     pub skill_registry: SkillRegistry,
     pub acp_manager: AcpManager,
     pub event_bus: EventBus,
+    /// Coordination tools over the stores and event bus above. Transports
+    /// execute agent tools through this instance instead of building their own.
+    pub agent_tools: AgentTools,
+    /// The one orchestrator every transport delegates through, so delegations
+    /// restored at startup are the ones later reports complete.
+    pub orchestrator: Arc<RoutaOrchestrator>,
     pub acp_paths: AcpPaths,
     pub acp_binary_manager: AcpBinaryManager,
     pub acp_installation_state: AcpInstallationState,
     pub acp_runtime_manager: AcpRuntimeManager,
     pub acp_warmup_service: AcpWarmupService,
+    /// Model lists per provider, shared by the HTTP API and `providers.*` RPCs.
+    pub provider_models_cache: ProviderModelsCache,
     pub docker_state: DockerState,
     pub sandbox_manager: SandboxManager,
+    /// Provider routing defaults shared by every orchestrator built from this state.
+    pub orchestrator_config: OrchestratorConfig,
+    /// Cancelled by [`shutdown`](Self::shutdown). Every background task
+    /// spawned for this state selects on it and exits once it fires.
+    pub shutdown_token: CancellationToken,
 }
 
 pub type AppState = Arc<AppStateInner>;
@@ -58,29 +82,264 @@ impl AppStateInner {
         let acp_installation_state = AcpInstallationState::new(acp_paths.clone());
         let acp_runtime_manager = AcpRuntimeManager::new(acp_paths.clone());
         let acp_warmup_service = AcpWarmupService::new(acp_paths.clone());
+        let provider_models_cache = ProviderModelsCache::new(&acp_paths);
+        let shutdown_token = CancellationToken::new();
+        let provider_credential_store = ProviderCredentialStore::new(db.clone());
+        let agent_store = AgentStore::new(db.clone());
+        let task_store = TaskStore::new(db.clone());
+        let conversation_store = ConversationStore::new(db.clone());
+        let event_bus = if EventBus::persistence_enabled_from_env() {
+            EventBus::with_persistence(EventStore::new(db.clone()))
+        } else {
+            EventBus::new()
+        }
+        .with_subscription_ttl(EventBus::subscription_ttl_from_env());
+        let acp_manager = AcpManager::new()
+            .with_credential_store(provider_credential_store.clone())
+            .with_event_bus(event_bus.clone());
+        let agent_tools = AgentTools::new(
+            agent_store.clone(),
+            conversation_store.clone(),
+            task_store.clone(),
+            event_bus.clone(),
+        )
+        .with_live_sessions(Arc::new(acp_manager.clone()))
+        .with_session_liveness(Arc::new(acp_manager.clone()));
+        let delegation_store = DelegationStore::new(db.clone());
+        let orchestrator_config = OrchestratorConfig::from_env();
+        let orchestrator = Arc::new(
+            RoutaOrchestrator::new(
+                orchestrator_config.clone(),
+                Arc::new(acp_manager.clone()),
+                agent_store.clone(),
+                task_store.clone(),
+                event_bus.clone(),
+            )
+            .with_delegation_store(delegation_store.clone()),
+        );
         Self {
             workspace_store: WorkspaceStore::new(db.clone()),
             codebase_store: CodebaseStore::new(db.clone()),
             worktree_store: WorktreeStore::new(db.clone()),
-            agent_store: AgentStore::new(db.clone()),
+            agent_store,
             artifact_store: ArtifactStore::new(db.clone()),
-            task_store: TaskStore::new(db.clone()),
+            task_store,
             kanban_store: KanbanStore::new(db.clone()),
-            note_store: NoteStore::new(db.clone()),
+            note_store: NoteStore::new(db.clone())
+                .with_max_versions(NoteStore::max_versions_from_env()),
             schedule_store: ScheduleStore::new(db.clone()),
-            conversation_store: ConversationStore::new(db.clone()),
+            conversation_store,
+            delegation_store,
             acp_session_store: AcpSessionStore::new(db.clone()),
+            tool_audit_store: ToolAuditStore::new(db.clone()),
+            skill_store: SkillStore::new(db.clone()),
             skill_registry: SkillRegistry::new(),
-            acp_manager: AcpManager::new(),
-            event_bus: EventBus::new(),
+            acp_manager,
+            provider_credential_store,
+            event_bus,
+            agent_tools,
+            orchestrator,
             db,
             acp_paths,
             acp_binary_manager,
             acp_installation_state,
             acp_runtime_manager,
             acp_warmup_service,
+            provider_models_cache,
             docker_state: DockerState::default(),
-            sandbox_manager: SandboxManager::new(),
+            sandbox_manager: SandboxManager::with_shutdown(shutdown_token.clone()),
+            orchestrator_config,
+            shutdown_token,
+        }
+    }
+
+    /// Signal every background task spawned for this state to stop.
+    pub fn shutdown(&self) {
+        self.shutdown_token.cancel();
+    }
+
+    /// Submit a child's completion report, then let the orchestrator wake
+    /// the parent that delegated to it.
+    pub async fn report_to_parent(
+        &self,
+        agent_id: &str,
+        report: CompletionReport,
+    ) -> Result<ToolResult, ServerError> {
+        let result = self
+            .agent_tools
+            .report_to_parent(agent_id, report.clone())
+            .await?;
+        if result.success {
+            self.orchestrator
+                .handle_report_submitted(agent_id, &report)
+                .await?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acp::SessionLaunchOptions;
+    use crate::models::agent::{Agent, AgentRole};
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn message_agent_prompts_a_live_acp_session() {
+        let state = AppStateInner::new(Database::open_in_memory().expect("in-memory db"));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        for id in ["routa-1", "crafter-1"] {
+            state
+                .agent_store
+                .save(&Agent::new(
+                    id.to_string(),
+                    id.to_string(),
+                    AgentRole::Crafter,
+                    "default".to_string(),
+                    None,
+                    None,
+                    None,
+                ))
+                .await
+                .expect("agent should save");
         }
+
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let cwd = temp.path().to_string_lossy().to_string();
+        let received = temp.path().join("stdin.log");
+        // Answers `initialize` and `session/new`, then records every prompt.
+        let stub_agent = format!(
+            r#"read _
+echo '{{"jsonrpc":"2.0","id":1,"result":{{"protocolVersion":1}}}}'
+read _
+echo '{{"jsonrpc":"2.0","id":2,"result":{{"sessionId":"stub-session"}}}}'
+cat > '{}'"#,
+            received.display()
+        );
+        state
+            .acp_manager
+            .create_session_from_inline(
+                "session-1".to_string(),
+                cwd.clone(),
+                "default".to_string(),
+                "stub".to_string(),
+                None,
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent],
+                SessionLaunchOptions::default(),
+            )
+            .await
+            .expect("stub session should start");
+        state
+            .acp_manager
+            .set_routa_agent_id("session-1", "crafter-1")
+            .await
+            .expect("session should exist");
+
+        let result = state
+            .agent_tools
+            .message_agent("routa-1", "crafter-1", "rebase onto main")
+            .await
+            .expect("message should send");
+        let data = result.data.expect("success data");
+        assert_eq!(data["liveSession"], true);
+        assert_eq!(data["sessionId"], "session-1");
+
+        let mut prompt = String::new();
+        for _ in 0..100 {
+            prompt = std::fs::read_to_string(&received).unwrap_or_default();
+            if prompt.contains("rebase onto main") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(prompt.contains("rebase onto main"), "{prompt}");
+
+        state.acp_manager.delete_session("session-1").await;
+        let _ = std::fs::remove_dir_all(crate::storage::get_project_storage_dir(&cwd));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn list_agents_reports_killed_session_despite_active_status() {
+        let state = AppStateInner::new(Database::open_in_memory().expect("in-memory db"));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        state
+            .agent_store
+            .save(&Agent::new(
+                "crafter-1".to_string(),
+                "crafter-1".to_string(),
+                AgentRole::Crafter,
+                "default".to_string(),
+                None,
+                None,
+                None,
+            ))
+            .await
+            .expect("agent should save");
+        state
+            .agent_store
+            .update_status("crafter-1", &crate::models::agent::AgentStatus::Active)
+            .await
+            .expect("agent should activate");
+
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let cwd = temp.path().to_string_lossy().to_string();
+        let stub_agent = r#"read _
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1}}'
+read _
+echo '{"jsonrpc":"2.0","id":2,"result":{"sessionId":"stub-session"}}'
+cat > /dev/null"#;
+        state
+            .acp_manager
+            .create_session_from_inline(
+                "session-1".to_string(),
+                cwd.clone(),
+                "default".to_string(),
+                "stub".to_string(),
+                Some("CRAFTER".to_string()),
+                None,
+                None,
+                "sh".to_string(),
+                vec!["-c".to_string(), stub_agent.to_string()],
+                SessionLaunchOptions::default(),
+            )
+            .await
+            .expect("stub session should start");
+        state
+            .orchestrator
+            .register_agent_session("crafter-1", "session-1")
+            .await;
+
+        let live = state
+            .agent_tools
+            .list_agents("default", true)
+            .await
+            .unwrap();
+        assert_eq!(live.data.unwrap()[0]["sessionAlive"], true);
+
+        state.acp_manager.kill_session("session-1").await;
+        let killed = state
+            .agent_tools
+            .list_agents("default", true)
+            .await
+            .unwrap();
+        let agent = &killed.data.unwrap()[0];
+        assert_eq!(agent["sessionAlive"], false);
+        assert_eq!(agent["status"], "ACTIVE");
+
+        state.acp_manager.delete_session("session-1").await;
+        let _ = std::fs::remove_dir_all(crate::storage::get_project_storage_dir(&cwd));
     }
 }
@@ -1,8 +1,11 @@
 //! Store for ACP session persistence.
 //!
 //! Handles loading and saving session history to the SQLite database.
+//!
+//! History lives in `session_messages`, one row per notification, so appending
+//! a message does not rewrite the session's earlier history.
 
-use rusqlite::OptionalExtension;
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
 use crate::db::Database;
@@ -40,8 +43,19 @@ pub struct AcpSessionRow {
     pub created_at: i64,
     pub updated_at: i64,
     pub parent_session_id: Option<String>,
+    /// User-assigned context, e.g. a label or a linked PR.
+    #[serde(default = "empty_metadata")]
+    pub metadata: serde_json::Value,
+}
+
+fn empty_metadata() -> serde_json::Value {
+    serde_json::Value::Object(serde_json::Map::new())
 }
 
+/// `acp_sessions.message_history`-shaped JSON array built from `session_messages`.
+const HISTORY_COLUMN: &str = "(SELECT json_group_array(json(message)) FROM
+    (SELECT message FROM session_messages WHERE session_id = acp_sessions.id ORDER BY seq))";
+
 pub struct AcpSessionStore {
     db: Database,
 }
@@ -56,6 +70,7 @@ pub struct CreateAcpSessionParams<'a> {
     pub custom_command: Option<&'a str>,
     pub custom_args: Option<&'a [String]>,
     pub parent_session_id: Option<&'a str>,
+    pub metadata: Option<&'a serde_json::Value>,
 }
 
 impl AcpSessionStore {
@@ -68,12 +83,12 @@ impl AcpSessionStore {
         let id = session_id.to_string();
         self.db
             .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
+                let mut stmt = conn.prepare(&format!(
                     "SELECT id, name, cwd, branch, workspace_id, routa_agent_id, provider_session_id, provider, role, mode_id,
-                            custom_command, custom_args, first_prompt_sent, message_history,
-                            created_at, updated_at, parent_session_id
+                            custom_command, custom_args, first_prompt_sent, {HISTORY_COLUMN},
+                            created_at, updated_at, parent_session_id, metadata
                      FROM acp_sessions WHERE id = ?1",
-                )?;
+                ))?;
 
                 let row = stmt
                     .query_row([&id], |row| {
@@ -102,6 +117,8 @@ impl AcpSessionStore {
                             created_at: row.get(14)?,
                             updated_at: row.get(15)?,
                             parent_session_id: row.get(16)?,
+                            metadata: serde_json::from_str(&row.get::<_, String>(17)?)
+                                .unwrap_or_else(|_| empty_metadata()),
                         })
                     })
                     .optional()?;
@@ -111,7 +128,7 @@ impl AcpSessionStore {
             .await
     }
 
-    /// Load session history from the database.
+    /// Load session history from the database, oldest message first.
     pub async fn get_history(
         &self,
         session_id: &str,
@@ -119,20 +136,15 @@ impl AcpSessionStore {
         let id = session_id.to_string();
         self.db
             .with_conn_async(move |conn| {
-                let mut stmt =
-                    conn.prepare("SELECT message_history FROM acp_sessions WHERE id = ?1")?;
-
-                let history_json: Option<String> =
-                    stmt.query_row([&id], |row| row.get(0)).optional()?;
-
-                match history_json {
-                    Some(json) => {
-                        let history: Vec<serde_json::Value> =
-                            serde_json::from_str(&json).unwrap_or_default();
-                        Ok(history)
-                    }
-                    None => Ok(vec![]),
-                }
+                let mut stmt = conn.prepare(
+                    "SELECT message FROM session_messages WHERE session_id = ?1 ORDER BY seq ASC",
+                )?;
+                let history = stmt
+                    .query_map([&id], |row| row.get::<_, String>(0))?
+                    .filter_map(|r| r.ok())
+                    .filter_map(|json| serde_json::from_str(&json).ok())
+                    .collect();
+                Ok(history)
             })
             .await
     }
@@ -147,24 +159,23 @@ impl AcpSessionStore {
         let limit = limit.unwrap_or(100);
         self.db
             .with_conn_async(move |conn| {
-                let (sql, params): (&str, Vec<Box<dyn rusqlite::ToSql>>) = match &workspace_filter {
+                let (filter, params): (&str, Vec<Box<dyn rusqlite::ToSql>>) = match &workspace_filter {
                     Some(ws) => (
-                        "SELECT id, name, cwd, branch, workspace_id, routa_agent_id, provider_session_id, provider, role, mode_id,
-                                custom_command, custom_args, first_prompt_sent, message_history,
-                                created_at, updated_at, parent_session_id
-                         FROM acp_sessions WHERE workspace_id = ?1 ORDER BY updated_at DESC LIMIT ?2",
+                        "WHERE workspace_id = ?1 ORDER BY updated_at DESC LIMIT ?2",
                         vec![Box::new(ws.clone()) as Box<dyn rusqlite::ToSql>, Box::new(limit as i64)],
                     ),
                     None => (
-                        "SELECT id, name, cwd, branch, workspace_id, routa_agent_id, provider_session_id, provider, role, mode_id,
-                                custom_command, custom_args, first_prompt_sent, message_history,
-                                created_at, updated_at, parent_session_id
-                         FROM acp_sessions ORDER BY updated_at DESC LIMIT ?1",
+                        "ORDER BY updated_at DESC LIMIT ?1",
                         vec![Box::new(limit as i64) as Box<dyn rusqlite::ToSql>],
                     ),
                 };
 
-                let mut stmt = conn.prepare(sql)?;
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT id, name, cwd, branch, workspace_id, routa_agent_id, provider_session_id, provider, role, mode_id,
+                            custom_command, custom_args, first_prompt_sent, {HISTORY_COLUMN},
+                            created_at, updated_at, parent_session_id, metadata
+                     FROM acp_sessions {filter}",
+                ))?;
                 let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
                 let rows = stmt.query_map(param_refs.as_slice(), |row| {
                     let custom_args_json: String = row.get(11)?;
@@ -192,6 +203,8 @@ impl AcpSessionStore {
                         created_at: row.get(14)?,
                         updated_at: row.get(15)?,
                         parent_session_id: row.get(16)?,
+                        metadata: serde_json::from_str(&row.get::<_, String>(17)?)
+                            .unwrap_or_else(|_| empty_metadata()),
                     })
                 })?;
 
@@ -204,37 +217,32 @@ impl AcpSessionStore {
             .await
     }
 
-    /// Append a notification to session history.
-    pub async fn append_history(
+    /// Append one notification to session history.
+    ///
+    /// A single indexed insert, independent of how long the history already
+    /// is. Does nothing if the session is not persisted yet.
+    pub async fn append_message(
         &self,
         session_id: &str,
-        notification: serde_json::Value,
+        message: &serde_json::Value,
     ) -> Result<(), ServerError> {
         let id = session_id.to_string();
+        let message_json = message.to_string();
         self.db
             .with_conn_async(move |conn| {
-                // Get current history
-                let mut stmt =
-                    conn.prepare("SELECT message_history FROM acp_sessions WHERE id = ?1")?;
-                let history_json: Option<String> =
-                    stmt.query_row([&id], |row| row.get(0)).optional()?;
-
-                let mut history: Vec<serde_json::Value> = match history_json {
-                    Some(json) => serde_json::from_str(&json).unwrap_or_default(),
-                    None => return Ok(()), // Session doesn't exist in DB yet
-                };
-
-                // Append notification
-                history.push(notification);
-
-                // Update database
-                let new_history_json = serde_json::to_string(&history).unwrap_or_default();
                 let now = chrono::Utc::now().timestamp_millis();
-                conn.execute(
-                    "UPDATE acp_sessions SET message_history = ?1, updated_at = ?2 WHERE id = ?3",
-                    rusqlite::params![new_history_json, now, id],
+                let inserted = conn.execute(
+                    "INSERT INTO session_messages (session_id, seq, message, created_at)
+                     SELECT ?1, COALESCE((SELECT MAX(seq) + 1 FROM session_messages WHERE session_id = ?1), 0), ?2, ?3
+                     WHERE EXISTS (SELECT 1 FROM acp_sessions WHERE id = ?1)",
+                    rusqlite::params![id, message_json, now],
                 )?;
-
+                if inserted > 0 {
+                    conn.execute(
+                        "UPDATE acp_sessions SET updated_at = ?1 WHERE id = ?2",
+                        rusqlite::params![now, id],
+                    )?;
+                }
                 Ok(())
             })
             .await
@@ -255,6 +263,7 @@ impl AcpSessionStore {
             custom_command,
             custom_args,
             parent_session_id,
+            metadata,
         } = params;
         let id = id.to_string();
         let cwd = cwd.to_string();
@@ -266,6 +275,7 @@ impl AcpSessionStore {
         let custom_args_json =
             serde_json::to_string(&custom_args.unwrap_or(&[])).unwrap_or_else(|_| "[]".to_string());
         let parent_session_id = parent_session_id.map(str::to_string);
+        let metadata_json = metadata.cloned().unwrap_or_else(empty_metadata).to_string();
 
         self.db
             .with_conn_async(move |conn| {
@@ -273,8 +283,8 @@ impl AcpSessionStore {
                 conn.execute(
                     "INSERT OR IGNORE INTO acp_sessions
                         (id, cwd, branch, workspace_id, provider, role, custom_command, custom_args, parent_session_id,
-                         first_prompt_sent, message_history, created_at, updated_at)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0, '[]', ?10, ?10)",
+                         metadata, first_prompt_sent, message_history, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 0, '[]', ?11, ?11)",
                     rusqlite::params![
                         id,
                         cwd,
@@ -285,6 +295,7 @@ impl AcpSessionStore {
                         custom_command,
                         custom_args_json,
                         parent_session_id,
+                        metadata_json,
                         now
                     ],
                 )?;
@@ -293,6 +304,50 @@ impl AcpSessionStore {
             .await
     }
 
+    /// Insert a complete session row, history included, on an existing
+    /// connection, e.g. inside a [`Database::transaction`].
+    pub fn save_in(conn: &Connection, session: &AcpSessionRow) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO acp_sessions
+                (id, name, cwd, branch, workspace_id, routa_agent_id, provider_session_id, provider, role, mode_id,
+                 custom_command, custom_args, first_prompt_sent, message_history, created_at, updated_at, parent_session_id,
+                 metadata)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, '[]', ?14, ?15, ?16, ?17)",
+            rusqlite::params![
+                session.id,
+                session.name,
+                session.cwd,
+                session.branch,
+                session.workspace_id,
+                session.routa_agent_id,
+                session.provider_session_id,
+                session.provider,
+                session.role,
+                session.mode_id,
+                session.custom_command,
+                serde_json::to_string(&session.custom_args).unwrap_or_else(|_| "[]".to_string()),
+                session.first_prompt_sent,
+                session.created_at,
+                session.updated_at,
+                session.parent_session_id,
+                session.metadata.to_string(),
+            ],
+        )?;
+        let mut insert = conn.prepare(
+            "INSERT INTO session_messages (session_id, seq, message, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for (seq, message) in session.message_history.iter().enumerate() {
+            insert.execute(rusqlite::params![
+                session.id,
+                seq as i64,
+                message.to_string(),
+                session.updated_at
+            ])?;
+        }
+        Ok(())
+    }
+
     /// Rename a session in the database.
     pub async fn rename(&self, session_id: &str, name: &str) -> Result<(), ServerError> {
         let id = session_id.to_string();
@@ -309,6 +364,26 @@ impl AcpSessionStore {
             .await
     }
 
+    /// Replace a session's metadata. Returns `false` if the session does not exist.
+    pub async fn set_metadata(
+        &self,
+        session_id: &str,
+        metadata: &serde_json::Value,
+    ) -> Result<bool, ServerError> {
+        let id = session_id.to_string();
+        let metadata_json = metadata.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                let now = chrono::Utc::now().timestamp_millis();
+                let updated = conn.execute(
+                    "UPDATE acp_sessions SET metadata = ?1, updated_at = ?2 WHERE id = ?3",
+                    rusqlite::params![metadata_json, now, id],
+                )?;
+                Ok(updated > 0)
+            })
+            .await
+    }
+
     /// Persist or update the ROUTA agent mapping for a session.
     pub async fn set_routa_agent_id(
         &self,
@@ -329,6 +404,25 @@ impl AcpSessionStore {
             .await
     }
 
+    /// Ids of sessions mapped to a ROUTA agent, oldest first.
+    pub async fn list_ids_by_routa_agent_id(
+        &self,
+        routa_agent_id: &str,
+    ) -> Result<Vec<String>, ServerError> {
+        let agent_id = routa_agent_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id FROM acp_sessions WHERE routa_agent_id = ?1 ORDER BY created_at ASC",
+                )?;
+                let ids = stmt
+                    .query_map(rusqlite::params![agent_id], |row| row.get(0))?
+                    .collect::<Result<Vec<String>, _>>()?;
+                Ok(ids)
+            })
+            .await
+    }
+
     /// Persist or update the provider-native session id for a session.
     pub async fn set_provider_session_id(
         &self,
@@ -384,20 +478,38 @@ impl AcpSessionStore {
     /// Overwrite the full message history for a session.
     ///
     /// Called after a prompt turn completes to flush the in-memory history
-    /// accumulated by `AcpManager::push_to_history` into the database.
+    /// accumulated by `AcpManager::push_to_history` into the database. When the
+    /// stored history is a prefix of `history` (the usual case), only the new
+    /// tail is inserted; otherwise the stored history is replaced.
     pub async fn save_history(
         &self,
         session_id: &str,
         history: &[serde_json::Value],
     ) -> Result<(), ServerError> {
         let id = session_id.to_string();
-        let history_json = serde_json::to_string(history).unwrap_or_else(|_| "[]".to_string());
+        let messages: Vec<String> = history.iter().map(|m| m.to_string()).collect();
         self.db
-            .with_conn_async(move |conn| {
+            .transaction(move |tx| {
+                let start = stored_prefix_len(tx, &id, &messages)?;
+                if start == 0 {
+                    tx.execute(
+                        "DELETE FROM session_messages WHERE session_id = ?1",
+                        rusqlite::params![id],
+                    )?;
+                }
                 let now = chrono::Utc::now().timestamp_millis();
-                conn.execute(
-                    "UPDATE acp_sessions SET message_history = ?1, updated_at = ?2 WHERE id = ?3",
-                    rusqlite::params![history_json, now, id],
+                {
+                    let mut insert = tx.prepare(
+                        "INSERT INTO session_messages (session_id, seq, message, created_at)
+                         VALUES (?1, ?2, ?3, ?4)",
+                    )?;
+                    for (seq, message) in messages.iter().enumerate().skip(start) {
+                        insert.execute(rusqlite::params![id, seq as i64, message, now])?;
+                    }
+                }
+                tx.execute(
+                    "UPDATE acp_sessions SET updated_at = ?1 WHERE id = ?2",
+                    rusqlite::params![now, id],
                 )?;
                 Ok(())
             })
@@ -405,6 +517,27 @@ impl AcpSessionStore {
     }
 }
 
+/// Number of leading `messages` already stored for `session_id`, or 0 when the
+/// stored history is not a prefix of `messages` and must be rewritten.
+fn stored_prefix_len(
+    conn: &Connection,
+    session_id: &str,
+    messages: &[String],
+) -> Result<usize, rusqlite::Error> {
+    let (count, last): (i64, Option<String>) = conn.query_row(
+        "SELECT COUNT(*),
+                (SELECT message FROM session_messages WHERE session_id = ?1 ORDER BY seq DESC LIMIT 1)
+         FROM session_messages WHERE session_id = ?1",
+        rusqlite::params![session_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let count = count as usize;
+    if count == 0 || count > messages.len() || last.as_deref() != Some(&messages[count - 1]) {
+        return Ok(0);
+    }
+    Ok(count)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,6 +572,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create failed");
@@ -470,6 +604,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create failed");
@@ -501,6 +636,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create failed");
@@ -528,6 +664,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create failed");
@@ -566,6 +703,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create failed");
@@ -591,6 +729,66 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_append_message_keeps_order() {
+        let (store, session_id) = setup().await;
+        store
+            .create(CreateAcpSessionParams {
+                id: &session_id,
+                cwd: "/tmp",
+                branch: None,
+                workspace_id: "default",
+                provider: Some("claude"),
+                role: Some("CRAFTER"),
+                custom_command: None,
+                custom_args: None,
+                parent_session_id: None,
+                metadata: None,
+            })
+            .await
+            .expect("create failed");
+
+        for i in 0..500 {
+            store
+                .append_message(
+                    &session_id,
+                    &serde_json::json!({"sessionId": session_id, "seq": i}),
+                )
+                .await
+                .expect("append_message failed");
+        }
+        store
+            .append_message("missing-session", &serde_json::json!({"seq": 0}))
+            .await
+            .expect("append to unknown session should be a no-op");
+
+        let history = store
+            .get_history(&session_id)
+            .await
+            .expect("get_history failed");
+        assert_eq!(history.len(), 500);
+        assert!(history
+            .iter()
+            .enumerate()
+            .all(|(i, message)| message["seq"].as_u64() == Some(i as u64)));
+
+        let session = store
+            .get(&session_id)
+            .await
+            .expect("get failed")
+            .expect("exists");
+        assert_eq!(session.message_history, history);
+
+        // Flushing a longer in-memory copy only adds the tail.
+        let mut flushed = history.clone();
+        flushed.push(serde_json::json!({"sessionId": session_id, "seq": 500}));
+        store
+            .save_history(&session_id, &flushed)
+            .await
+            .expect("save_history failed");
+        assert_eq!(store.get_history(&session_id).await.unwrap(), flushed);
+    }
+
     #[tokio::test]
     async fn test_parent_session_id() {
         let (store, session_id) = setup().await;
@@ -607,6 +805,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: Some(parent_id),
+                metadata: None,
             })
             .await
             .expect("create failed");
@@ -634,6 +833,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create failed");
@@ -666,6 +866,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create failed");
@@ -702,6 +903,7 @@ mod tests {
                 custom_command: Some("uvx"),
                 custom_args: Some(custom_args.as_slice()),
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create failed");
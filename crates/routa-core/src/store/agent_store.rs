@@ -1,11 +1,47 @@
 use chrono::Utc;
-use rusqlite::OptionalExtension;
+use rusqlite::{Connection, OptionalExtension};
 use std::collections::HashMap;
 
 use crate::db::Database;
 use crate::error::ServerError;
 use crate::models::agent::{Agent, AgentRole, AgentStatus, ModelTier};
 
+/// Column used to order filtered agent listings (always newest first).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AgentOrderBy {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl AgentOrderBy {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "createdAt" | "created_at" => Some(Self::CreatedAt),
+            "updatedAt" | "updated_at" => Some(Self::UpdatedAt),
+            _ => None,
+        }
+    }
+
+    fn column(&self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+/// Optional filters for [`AgentStore::list_filtered`]. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct AgentListFilter {
+    pub workspace_id: Option<String>,
+    pub role: Option<AgentRole>,
+    pub status: Option<AgentStatus>,
+    pub parent_id: Option<String>,
+    pub order_by: AgentOrderBy,
+}
+
 #[derive(Clone)]
 pub struct AgentStore {
     db: Database,
@@ -19,37 +55,40 @@ impl AgentStore {
     pub async fn save(&self, agent: &Agent) -> Result<(), ServerError> {
         let a = agent.clone();
         self.db
-            .with_conn_async(move |conn| {
-                conn.execute(
-                    "INSERT INTO agents (id, name, role, model_tier, workspace_id, parent_id, status, metadata, created_at, updated_at)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
-                     ON CONFLICT(id) DO UPDATE SET
-                       name = excluded.name,
-                       role = excluded.role,
-                       model_tier = excluded.model_tier,
-                       workspace_id = excluded.workspace_id,
-                       parent_id = excluded.parent_id,
-                       status = excluded.status,
-                       metadata = excluded.metadata,
-                       updated_at = excluded.updated_at",
-                    rusqlite::params![
-                        a.id,
-                        a.name,
-                        a.role.as_str(),
-                        a.model_tier.as_str(),
-                        a.workspace_id,
-                        a.parent_id,
-                        a.status.as_str(),
-                        serde_json::to_string(&a.metadata).unwrap_or_default(),
-                        a.created_at.timestamp_millis(),
-                        a.updated_at.timestamp_millis(),
-                    ],
-                )?;
-                Ok(())
-            })
+            .with_conn_async(move |conn| Self::save_in(conn, &a))
             .await
     }
 
+    /// [`save`](Self::save) on an existing connection, e.g. inside a [`Database::transaction`].
+    pub fn save_in(conn: &Connection, a: &Agent) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO agents (id, name, role, model_tier, workspace_id, parent_id, status, metadata, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(id) DO UPDATE SET
+               name = excluded.name,
+               role = excluded.role,
+               model_tier = excluded.model_tier,
+               workspace_id = excluded.workspace_id,
+               parent_id = excluded.parent_id,
+               status = excluded.status,
+               metadata = excluded.metadata,
+               updated_at = excluded.updated_at",
+            rusqlite::params![
+                a.id,
+                a.name,
+                a.role.as_str(),
+                a.model_tier.as_str(),
+                a.workspace_id,
+                a.parent_id,
+                a.status.as_str(),
+                serde_json::to_string(&a.metadata).unwrap_or_default(),
+                a.created_at.timestamp_millis(),
+                a.updated_at.timestamp_millis(),
+            ],
+        )?;
+        Ok(())
+    }
+
     pub async fn get(&self, agent_id: &str) -> Result<Option<Agent>, ServerError> {
         let id = agent_id.to_string();
         self.db
@@ -138,6 +177,51 @@ impl AgentStore {
             .await
     }
 
+    /// List agents matching every set field of `filter`, newest first by the
+    /// requested timestamp column.
+    pub async fn list_filtered(&self, filter: &AgentListFilter) -> Result<Vec<Agent>, ServerError> {
+        let mut clauses = Vec::new();
+        let mut values = Vec::new();
+        if let Some(workspace_id) = &filter.workspace_id {
+            values.push(workspace_id.clone());
+            clauses.push(format!("workspace_id = ?{}", values.len()));
+        }
+        if let Some(role) = &filter.role {
+            values.push(role.as_str().to_string());
+            clauses.push(format!("role = ?{}", values.len()));
+        }
+        if let Some(status) = &filter.status {
+            values.push(status.as_str().to_string());
+            clauses.push(format!("status = ?{}", values.len()));
+        }
+        if let Some(parent_id) = &filter.parent_id {
+            values.push(parent_id.clone());
+            clauses.push(format!("parent_id = ?{}", values.len()));
+        }
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT id, name, role, model_tier, workspace_id, parent_id, status, metadata, created_at, updated_at
+             FROM agents {where_clause} ORDER BY {} DESC",
+            filter.order_by.column()
+        );
+
+        self.db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(&sql)?;
+                let rows = stmt
+                    .query_map(rusqlite::params_from_iter(values.iter()), |row| {
+                        Ok(row_to_agent(row))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+    }
+
     pub async fn delete(&self, agent_id: &str) -> Result<(), ServerError> {
         let id = agent_id.to_string();
         self.db
@@ -192,3 +276,86 @@ fn row_to_agent(row: &Row<'_>) -> Agent {
         updated_at: chrono::DateTime::from_timestamp_millis(updated_ms).unwrap_or_else(Utc::now),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::WorkspaceStore;
+
+    async fn setup() -> AgentStore {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        AgentStore::new(db)
+    }
+
+    fn agent(id: &str, role: AgentRole, parent_id: Option<&str>) -> Agent {
+        Agent::new(
+            id.to_string(),
+            id.to_string(),
+            role,
+            "default".to_string(),
+            parent_id.map(str::to_string),
+            None,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn list_filtered_returns_crafters_under_parent() {
+        let store = setup().await;
+        store
+            .save(&agent("routa-1", AgentRole::Routa, None))
+            .await
+            .expect("save should succeed");
+        store
+            .save(&agent("routa-2", AgentRole::Routa, None))
+            .await
+            .expect("save should succeed");
+
+        let mut first = agent("crafter-1", AgentRole::Crafter, Some("routa-1"));
+        first.created_at = chrono::DateTime::from_timestamp_millis(1_000).unwrap();
+        first.updated_at = chrono::DateTime::from_timestamp_millis(5_000).unwrap();
+        let mut second = agent("crafter-2", AgentRole::Crafter, Some("routa-1"));
+        second.created_at = chrono::DateTime::from_timestamp_millis(2_000).unwrap();
+        second.updated_at = chrono::DateTime::from_timestamp_millis(3_000).unwrap();
+        for a in [
+            first,
+            second,
+            agent("gate-1", AgentRole::Gate, Some("routa-1")),
+            agent("crafter-3", AgentRole::Crafter, Some("routa-2")),
+        ] {
+            store.save(&a).await.expect("save should succeed");
+        }
+
+        let mut filter = AgentListFilter {
+            workspace_id: Some("default".to_string()),
+            role: Some(AgentRole::Crafter),
+            parent_id: Some("routa-1".to_string()),
+            ..Default::default()
+        };
+        let by_created = store
+            .list_filtered(&filter)
+            .await
+            .expect("list_filtered should succeed");
+        let ids: Vec<&str> = by_created.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["crafter-2", "crafter-1"]);
+
+        filter.order_by = AgentOrderBy::UpdatedAt;
+        let by_updated = store
+            .list_filtered(&filter)
+            .await
+            .expect("list_filtered should succeed");
+        let ids: Vec<&str> = by_updated.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["crafter-1", "crafter-2"]);
+
+        filter.status = Some(AgentStatus::Active);
+        assert!(store
+            .list_filtered(&filter)
+            .await
+            .expect("list_filtered should succeed")
+            .is_empty());
+    }
+}
@@ -1,9 +1,21 @@
 use chrono::Utc;
+use rusqlite::OptionalExtension;
 
 use crate::db::Database;
 use crate::error::ServerError;
-use crate::models::message::{Message, MessageRole};
+use crate::models::message::{
+    Message, MessageAuditAction, MessageAuditEntry, MessageRole, REDACTED_MESSAGE_PLACEHOLDER,
+};
 
+/// Aggregate view of an agent's conversation, computed in one query.
+#[derive(Debug, Clone)]
+pub struct ConversationSummary {
+    pub message_count: usize,
+    pub tool_call_count: usize,
+    pub last_assistant_message: Option<Message>,
+}
+
+#[derive(Clone)]
 pub struct ConversationStore {
     db: Database,
 }
@@ -13,13 +25,17 @@ impl ConversationStore {
         Self { db }
     }
 
+    /// Store `message`. A message without a turn gets the agent's next turn
+    /// number (highest so far plus one), assigned inside the insert so
+    /// concurrent appends can't claim the same turn.
     pub async fn append(&self, message: &Message) -> Result<(), ServerError> {
         let m = message.clone();
         self.db
             .with_conn_async(move |conn| {
                 conn.execute(
                     "INSERT INTO messages (id, agent_id, role, content, timestamp, tool_name, tool_args, turn)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, COALESCE(?8,
+                       (SELECT COALESCE(MAX(turn), 0) + 1 FROM messages WHERE agent_id = ?2)))",
                     rusqlite::params![
                         m.id,
                         m.agent_id,
@@ -107,6 +123,165 @@ impl ConversationStore {
             .await
     }
 
+    /// Message count, tool-call count and latest assistant message in a
+    /// single round-trip.
+    pub async fn get_summary(&self, agent_id: &str) -> Result<ConversationSummary, ServerError> {
+        let aid = agent_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.query_row(
+                    "SELECT m.id, m.agent_id, m.role, m.content, m.timestamp, m.tool_name, m.tool_args, m.turn,
+                            c.total, c.tool_calls
+                     FROM (SELECT COUNT(*) AS total, COALESCE(SUM(role = ?2), 0) AS tool_calls
+                           FROM messages WHERE agent_id = ?1) c
+                     LEFT JOIN messages m ON m.id = (
+                         SELECT id FROM messages WHERE agent_id = ?1 AND role = ?3
+                         ORDER BY timestamp DESC LIMIT 1
+                     )",
+                    rusqlite::params![
+                        aid,
+                        MessageRole::Tool.as_str(),
+                        MessageRole::Assistant.as_str()
+                    ],
+                    |row| {
+                        let total: i64 = row.get(8)?;
+                        let tool_calls: i64 = row.get(9)?;
+                        let has_assistant = row.get::<_, Option<String>>(0)?.is_some();
+                        Ok(ConversationSummary {
+                            message_count: total as usize,
+                            tool_call_count: tool_calls as usize,
+                            last_assistant_message: has_assistant.then(|| row_to_message(row)),
+                        })
+                    },
+                )
+            })
+            .await
+    }
+
+    pub async fn get_message(&self, message_id: &str) -> Result<Option<Message>, ServerError> {
+        let id = message_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, agent_id, role, content, timestamp, tool_name, tool_args, turn
+                     FROM messages WHERE id = ?1",
+                )?;
+                stmt.query_row(rusqlite::params![id], |row| Ok(row_to_message(row)))
+                    .optional()
+            })
+            .await
+    }
+
+    /// Replace a message's content (and tool arguments) with a placeholder.
+    ///
+    /// The row is kept so `turn` numbering and ordering stay intact. Returns
+    /// `false` when the message does not exist.
+    pub async fn redact_message(
+        &self,
+        message_id: &str,
+        actor: &str,
+        reason: Option<&str>,
+    ) -> Result<bool, ServerError> {
+        self.moderate(message_id, MessageAuditAction::Redact, actor, reason)
+            .await
+    }
+
+    /// Permanently remove a message. Returns `false` when it does not exist.
+    pub async fn delete_message(
+        &self,
+        message_id: &str,
+        actor: &str,
+        reason: Option<&str>,
+    ) -> Result<bool, ServerError> {
+        self.moderate(message_id, MessageAuditAction::Delete, actor, reason)
+            .await
+    }
+
+    async fn moderate(
+        &self,
+        message_id: &str,
+        action: MessageAuditAction,
+        actor: &str,
+        reason: Option<&str>,
+    ) -> Result<bool, ServerError> {
+        let id = message_id.to_string();
+        let actor = actor.to_string();
+        let reason = reason.map(str::to_string);
+        let now = Utc::now().timestamp_millis();
+        self.db
+            .transaction(move |tx| {
+                let agent_id: Option<String> = tx
+                    .query_row(
+                        "SELECT agent_id FROM messages WHERE id = ?1",
+                        rusqlite::params![id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                let Some(agent_id) = agent_id else {
+                    return Ok(false);
+                };
+                match action {
+                    MessageAuditAction::Redact => tx.execute(
+                        "UPDATE messages SET content = ?1, tool_args = NULL WHERE id = ?2",
+                        rusqlite::params![REDACTED_MESSAGE_PLACEHOLDER, id],
+                    )?,
+                    MessageAuditAction::Delete => {
+                        tx.execute("DELETE FROM messages WHERE id = ?1", rusqlite::params![id])?
+                    }
+                };
+                tx.execute(
+                    "INSERT INTO message_audit (id, message_id, agent_id, action, actor, reason, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    rusqlite::params![
+                        uuid::Uuid::new_v4().to_string(),
+                        id,
+                        agent_id,
+                        action.as_str(),
+                        actor,
+                        reason,
+                        now,
+                    ],
+                )?;
+                Ok(true)
+            })
+            .await
+    }
+
+    /// Moderation history for an agent's conversation, oldest first.
+    pub async fn list_audit_entries(
+        &self,
+        agent_id: &str,
+    ) -> Result<Vec<MessageAuditEntry>, ServerError> {
+        let aid = agent_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, message_id, agent_id, action, actor, reason, created_at
+                     FROM message_audit WHERE agent_id = ?1 ORDER BY created_at ASC",
+                )?;
+                let rows = stmt
+                    .query_map(rusqlite::params![aid], |row| {
+                        let created_ms: i64 = row.get(6).unwrap_or(0);
+                        Ok(MessageAuditEntry {
+                            id: row.get(0)?,
+                            message_id: row.get(1)?,
+                            agent_id: row.get(2)?,
+                            action: MessageAuditAction::from_str(
+                                &row.get::<_, String>(3).unwrap_or_default(),
+                            )
+                            .unwrap_or(MessageAuditAction::Redact),
+                            actor: row.get(4)?,
+                            reason: row.get(5).unwrap_or(None),
+                            created_at: chrono::DateTime::from_timestamp_millis(created_ms)
+                                .unwrap_or_else(Utc::now),
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+    }
+
     pub async fn delete_conversation(&self, agent_id: &str) -> Result<(), ServerError> {
         let aid = agent_id.to_string();
         self.db
@@ -138,3 +313,153 @@ fn row_to_message(row: &Row<'_>) -> Message {
         turn: row.get(7).unwrap_or(None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str, content: &str, turn: i32) -> Message {
+        Message::new(
+            id.to_string(),
+            "agent-1".to_string(),
+            MessageRole::User,
+            content.to_string(),
+            None,
+            None,
+            Some(turn),
+        )
+    }
+
+    #[tokio::test]
+    async fn summary_counts_mixed_roles_in_one_query() {
+        let store = ConversationStore::new(Database::open_in_memory().expect("in-memory db"));
+        let empty = store.get_summary("agent-1").await.unwrap();
+        assert_eq!(empty.message_count, 0);
+        assert!(empty.last_assistant_message.is_none());
+
+        let base = Utc::now();
+        let seeded = [
+            ("m-1", MessageRole::User, "do the thing"),
+            ("m-2", MessageRole::Assistant, "on it"),
+            ("m-3", MessageRole::Tool, "read_file"),
+            ("m-4", MessageRole::Tool, "write_file"),
+            ("m-5", MessageRole::Assistant, "done"),
+            ("m-6", MessageRole::User, "thanks"),
+        ];
+        for (offset, (id, role, content)) in seeded.into_iter().enumerate() {
+            let mut msg = message(id, content, offset as i32);
+            msg.role = role;
+            msg.timestamp = base + chrono::Duration::milliseconds(offset as i64);
+            store.append(&msg).await.unwrap();
+        }
+
+        let summary = store.get_summary("agent-1").await.unwrap();
+        assert_eq!(summary.message_count, 6);
+        assert_eq!(summary.tool_call_count, 2);
+        let last = summary.last_assistant_message.expect("assistant message");
+        assert_eq!(last.id, "m-5");
+        assert_eq!(last.content, "done");
+    }
+
+    #[tokio::test]
+    async fn redact_message_keeps_turns_and_records_audit() {
+        let store = ConversationStore::new(Database::open_in_memory().expect("in-memory db"));
+        store.append(&message("m-1", "hello", 1)).await.unwrap();
+        store
+            .append(&message("m-2", "my token is sk-secret", 2))
+            .await
+            .unwrap();
+        store.append(&message("m-3", "thanks", 3)).await.unwrap();
+
+        assert!(store
+            .redact_message("m-2", "admin", Some("leaked credential"))
+            .await
+            .expect("redact should succeed"));
+        assert!(!store
+            .redact_message("missing", "admin", None)
+            .await
+            .expect("redact of missing message should not error"));
+
+        let conversation = store.get_conversation("agent-1").await.unwrap();
+        assert_eq!(conversation.len(), 3);
+        assert_eq!(conversation[1].content, REDACTED_MESSAGE_PLACEHOLDER);
+        let turns: Vec<Option<i32>> = conversation.iter().map(|m| m.turn).collect();
+        assert_eq!(turns, vec![Some(1), Some(2), Some(3)]);
+
+        assert!(store.delete_message("m-3", "admin", None).await.unwrap());
+        assert_eq!(store.get_message_count("agent-1").await.unwrap(), 2);
+
+        let audit = store.list_audit_entries("agent-1").await.unwrap();
+        let actions: Vec<&MessageAuditAction> = audit.iter().map(|e| &e.action).collect();
+        assert_eq!(
+            actions,
+            vec![&MessageAuditAction::Redact, &MessageAuditAction::Delete]
+        );
+        assert_eq!(audit[0].message_id, "m-2");
+        assert_eq!(audit[0].reason.as_deref(), Some("leaked credential"));
+    }
+
+    #[tokio::test]
+    async fn append_numbers_missing_turns_sequentially() {
+        let store = std::sync::Arc::new(ConversationStore::new(
+            Database::open_in_memory().expect("in-memory db"),
+        ));
+        let untracked = |id: &str, agent_id: &str| {
+            Message::new(
+                id.to_string(),
+                agent_id.to_string(),
+                MessageRole::Assistant,
+                id.to_string(),
+                None,
+                None,
+                None,
+            )
+        };
+
+        store.append(&untracked("a-1", "agent-1")).await.unwrap();
+        store.append(&untracked("a-2", "agent-1")).await.unwrap();
+        store.append(&message("a-3", "explicit", 7)).await.unwrap();
+        store.append(&untracked("a-4", "agent-1")).await.unwrap();
+        store.append(&untracked("b-1", "agent-2")).await.unwrap();
+
+        let turns = |messages: Vec<Message>| {
+            let mut turns: Vec<(String, i32)> = messages
+                .into_iter()
+                .map(|m| (m.id, m.turn.unwrap()))
+                .collect();
+            turns.sort();
+            turns
+        };
+        assert_eq!(
+            turns(store.get_conversation("agent-1").await.unwrap()),
+            vec![
+                ("a-1".to_string(), 1),
+                ("a-2".to_string(), 2),
+                ("a-3".to_string(), 7),
+                ("a-4".to_string(), 8),
+            ]
+        );
+        assert_eq!(
+            turns(store.get_conversation("agent-2").await.unwrap()),
+            vec![("b-1".to_string(), 1)]
+        );
+
+        let appends = (0..10).map(|i| {
+            let store = store.clone();
+            let message = untracked(&format!("c-{i}"), "agent-3");
+            tokio::spawn(async move { store.append(&message).await })
+        });
+        for append in appends.collect::<Vec<_>>() {
+            append.await.unwrap().unwrap();
+        }
+        let mut concurrent: Vec<i32> = store
+            .get_conversation("agent-3")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.turn.unwrap())
+            .collect();
+        concurrent.sort();
+        assert_eq!(concurrent, (1..=10).collect::<Vec<_>>());
+    }
+}
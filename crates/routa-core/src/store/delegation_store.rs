@@ -0,0 +1,125 @@
+use chrono::Utc;
+use rusqlite::Row;
+
+use crate::db::Database;
+use crate::error::ServerError;
+use crate::models::agent::AgentRole;
+use crate::models::delegation::DelegationRecord;
+
+#[derive(Clone)]
+pub struct DelegationStore {
+    db: Database,
+}
+
+impl DelegationStore {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn save(&self, record: &DelegationRecord) -> Result<(), ServerError> {
+        let r = record.clone();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "INSERT INTO delegations (child_agent_id, child_session_id, parent_agent_id, parent_session_id, task_id, role, provider, group_id, completed, created_at, repo_path, worktree_path)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                     ON CONFLICT(child_agent_id) DO UPDATE SET
+                       child_session_id = excluded.child_session_id,
+                       parent_session_id = excluded.parent_session_id,
+                       group_id = excluded.group_id,
+                       completed = excluded.completed",
+                    rusqlite::params![
+                        r.child_agent_id,
+                        r.child_session_id,
+                        r.parent_agent_id,
+                        r.parent_session_id,
+                        r.task_id,
+                        r.role.as_str(),
+                        r.provider,
+                        r.group_id,
+                        r.completed as i64,
+                        r.created_at.timestamp_millis(),
+                        r.repo_path,
+                        r.worktree_path,
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// All live delegations, oldest first.
+    pub async fn list(&self) -> Result<Vec<DelegationRecord>, ServerError> {
+        self.db
+            .with_conn_async(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT child_agent_id, child_session_id, parent_agent_id, parent_session_id, task_id, role, provider, group_id, completed, created_at, repo_path, worktree_path
+                     FROM delegations ORDER BY created_at ASC",
+                )?;
+                let rows = stmt
+                    .query_map([], |row| Ok(row_to_delegation(row)))?
+                    .filter_map(|r| r.ok())
+                    .collect();
+                Ok(rows)
+            })
+            .await
+    }
+
+    pub async fn mark_completed(&self, child_agent_id: &str) -> Result<(), ServerError> {
+        let id = child_agent_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "UPDATE delegations SET completed = 1 WHERE child_agent_id = ?1",
+                    rusqlite::params![id],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    pub async fn delete(&self, child_agent_id: &str) -> Result<(), ServerError> {
+        let id = child_agent_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "DELETE FROM delegations WHERE child_agent_id = ?1",
+                    rusqlite::params![id],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    pub async fn delete_group(&self, group_id: &str) -> Result<(), ServerError> {
+        let id = group_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "DELETE FROM delegations WHERE group_id = ?1",
+                    rusqlite::params![id],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+fn row_to_delegation(row: &Row<'_>) -> DelegationRecord {
+    let created_ms: i64 = row.get(9).unwrap_or(0);
+    DelegationRecord {
+        child_agent_id: row.get(0).unwrap_or_default(),
+        child_session_id: row.get(1).unwrap_or_default(),
+        parent_agent_id: row.get(2).unwrap_or_default(),
+        parent_session_id: row.get(3).unwrap_or_default(),
+        task_id: row.get(4).unwrap_or_default(),
+        role: AgentRole::from_str(&row.get::<_, String>(5).unwrap_or_default())
+            .unwrap_or(AgentRole::Crafter),
+        provider: row.get(6).unwrap_or_default(),
+        group_id: row.get(7).unwrap_or(None),
+        completed: row.get::<_, i64>(8).unwrap_or(0) != 0,
+        created_at: chrono::DateTime::from_timestamp_millis(created_ms).unwrap_or_else(Utc::now),
+        repo_path: row.get(10).unwrap_or(None),
+        worktree_path: row.get(11).unwrap_or(None),
+    }
+}
@@ -0,0 +1,206 @@
+use chrono::{TimeZone, Utc};
+
+use crate::db::Database;
+use crate::error::ServerError;
+use crate::events::{AgentEvent, AgentEventType, EventSubscription};
+
+/// A persisted event queued for a subscriber that has not drained it yet.
+#[derive(Debug, Clone)]
+pub struct PersistedPendingEvent {
+    pub id: String,
+    /// The subscribing agent the event is queued for.
+    pub agent_id: String,
+    pub event: AgentEvent,
+}
+
+/// SQLite backing for `EventBus` subscriptions and undelivered events.
+#[derive(Clone)]
+pub struct EventStore {
+    db: Database,
+}
+
+impl EventStore {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn save_subscription(&self, sub: &EventSubscription) -> Result<(), ServerError> {
+        let sub = sub.clone();
+        let event_types: Vec<&str> = sub.event_types.iter().map(|t| t.as_str()).collect();
+        let event_types = serde_json::to_string(&event_types).unwrap_or_else(|_| "[]".into());
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "INSERT INTO event_subscriptions (id, agent_id, agent_name, event_types, exclude_self, one_shot, wait_group_id, priority, workspace_id, cross_workspace, ttl_secs, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                     ON CONFLICT(id) DO UPDATE SET
+                       event_types = excluded.event_types,
+                       exclude_self = excluded.exclude_self,
+                       one_shot = excluded.one_shot,
+                       wait_group_id = excluded.wait_group_id,
+                       priority = excluded.priority,
+                       workspace_id = excluded.workspace_id,
+                       cross_workspace = excluded.cross_workspace,
+                       ttl_secs = excluded.ttl_secs",
+                    rusqlite::params![
+                        sub.id,
+                        sub.agent_id,
+                        sub.agent_name,
+                        event_types,
+                        sub.exclude_self as i64,
+                        sub.one_shot as i64,
+                        sub.wait_group_id,
+                        sub.priority,
+                        sub.workspace_id,
+                        sub.cross_workspace as i64,
+                        sub.ttl_secs.map(|ttl| ttl as i64),
+                        Utc::now().timestamp_millis(),
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    pub async fn delete_subscription(&self, subscription_id: &str) -> Result<(), ServerError> {
+        let id = subscription_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "DELETE FROM event_subscriptions WHERE id = ?1",
+                    rusqlite::params![id],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    pub async fn list_subscriptions(&self) -> Result<Vec<EventSubscription>, ServerError> {
+        self.db
+            .with_conn_async(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, agent_id, agent_name, event_types, exclude_self, one_shot, wait_group_id, priority, workspace_id, cross_workspace, ttl_secs
+                     FROM event_subscriptions ORDER BY created_at ASC",
+                )?;
+                let rows = stmt.query_map([], |row| {
+                    let event_types: String = row.get(3)?;
+                    let event_types: Vec<String> =
+                        serde_json::from_str(&event_types).unwrap_or_default();
+                    Ok(EventSubscription {
+                        id: row.get(0)?,
+                        agent_id: row.get(1)?,
+                        agent_name: row.get(2)?,
+                        event_types: event_types
+                            .iter()
+                            .filter_map(|t| AgentEventType::from_str(t))
+                            .collect(),
+                        exclude_self: row.get::<_, i64>(4)? != 0,
+                        one_shot: row.get::<_, i64>(5)? != 0,
+                        wait_group_id: row.get(6)?,
+                        priority: row.get(7)?,
+                        workspace_id: row.get(8)?,
+                        cross_workspace: row.get::<_, i64>(9)? != 0,
+                        ttl_secs: row.get::<_, Option<i64>>(10)?.map(|ttl| ttl.max(0) as u64),
+                    })
+                })?;
+                rows.collect()
+            })
+            .await
+    }
+
+    /// Queue `event` for the subscribing agent `agent_id` as row `id`.
+    pub async fn save_pending(
+        &self,
+        id: &str,
+        agent_id: &str,
+        event: &AgentEvent,
+    ) -> Result<(), ServerError> {
+        let id = id.to_string();
+        let agent_id = agent_id.to_string();
+        let event = event.clone();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "INSERT INTO pending_events (id, agent_id, event_type, source_agent_id, workspace_id, data, timestamp, delivered)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0)",
+                    rusqlite::params![
+                        id,
+                        agent_id,
+                        event.event_type.as_str(),
+                        event.agent_id,
+                        event.workspace_id,
+                        event.data.to_string(),
+                        event.timestamp.timestamp_millis(),
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Undelivered events in emission order.
+    pub async fn list_undelivered(&self) -> Result<Vec<PersistedPendingEvent>, ServerError> {
+        self.db
+            .with_conn_async(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, agent_id, event_type, source_agent_id, workspace_id, data, timestamp
+                     FROM pending_events WHERE delivered = 0 ORDER BY timestamp ASC",
+                )?;
+                let rows = stmt.query_map([], |row| {
+                    let event_type: String = row.get(2)?;
+                    let data: String = row.get(5)?;
+                    let timestamp: i64 = row.get(6)?;
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        AgentEventType::from_str(&event_type),
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                        data,
+                        timestamp,
+                    ))
+                })?;
+                let mut events = Vec::new();
+                for row in rows {
+                    let (id, agent_id, event_type, source_agent_id, workspace_id, data, ts) = row?;
+                    let Some(event_type) = event_type else {
+                        continue;
+                    };
+                    events.push(PersistedPendingEvent {
+                        id,
+                        agent_id,
+                        event: AgentEvent {
+                            event_type,
+                            agent_id: source_agent_id,
+                            workspace_id,
+                            data: serde_json::from_str(&data).unwrap_or_default(),
+                            timestamp: Utc
+                                .timestamp_millis_opt(ts)
+                                .single()
+                                .unwrap_or_else(Utc::now),
+                        },
+                    });
+                }
+                Ok(events)
+            })
+            .await
+    }
+
+    pub async fn mark_delivered(&self, ids: &[String]) -> Result<(), ServerError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let ids = ids.to_vec();
+        self.db
+            .transaction(move |tx| {
+                for id in &ids {
+                    tx.execute(
+                        "UPDATE pending_events SET delivered = 1 WHERE id = ?1",
+                        rusqlite::params![id],
+                    )?;
+                }
+                Ok(())
+            })
+            .await
+    }
+}
@@ -3,21 +3,37 @@ pub mod agent_store;
 pub mod artifact_store;
 pub mod codebase_store;
 pub mod conversation_store;
+pub mod delegation_store;
+pub mod event_store;
 pub mod kanban_store;
 pub mod note_store;
+#[cfg(feature = "postgres")]
+mod postgres;
+pub mod provider_credential_store;
 pub mod schedule_store;
+pub mod skill_store;
 pub mod task_store;
+pub mod time_range;
+pub mod tool_audit_store;
 pub mod workspace_store;
 pub mod worktree_store;
 
 pub use acp_session_store::AcpSessionStore;
-pub use agent_store::AgentStore;
+pub use agent_store::{AgentListFilter, AgentOrderBy, AgentStore};
 pub use artifact_store::ArtifactStore;
 pub use codebase_store::CodebaseStore;
 pub use conversation_store::ConversationStore;
+pub use delegation_store::DelegationStore;
+pub use event_store::{EventStore, PersistedPendingEvent};
 pub use kanban_store::KanbanStore;
 pub use note_store::NoteStore;
+pub use provider_credential_store::{
+    CredentialCipher, MachineKeyCipher, MaskedCredential, ProviderCredentialStore,
+};
 pub use schedule_store::ScheduleStore;
-pub use task_store::TaskStore;
+pub use skill_store::SkillStore;
+pub use task_store::{AssigneeWorkload, TaskSearchHit, TaskStore, WorkloadTask};
+pub use time_range::TimeRange;
+pub use tool_audit_store::ToolAuditStore;
 pub use workspace_store::WorkspaceStore;
 pub use worktree_store::WorktreeStore;
@@ -1,61 +1,281 @@
 use chrono::Utc;
-use rusqlite::OptionalExtension;
+use rusqlite::{Connection, OptionalExtension};
 use std::collections::HashMap;
 
 use crate::db::Database;
 use crate::error::ServerError;
-use crate::models::note::{Note, NoteMetadata, NoteType, SPEC_NOTE_ID};
+use crate::models::note::{Note, NoteMetadata, NoteType, NoteVersion};
 use crate::models::task::TaskStatus;
+use crate::store::TimeRange;
+
+/// Content versions kept per note unless configured otherwise.
+pub const DEFAULT_MAX_NOTE_VERSIONS: usize = 50;
+
+/// Environment variable overriding [`DEFAULT_MAX_NOTE_VERSIONS`].
+pub const MAX_NOTE_VERSIONS_ENV: &str = "ROUTA_MAX_NOTE_VERSIONS";
 
 pub struct NoteStore {
     db: Database,
+    max_versions: usize,
 }
 
 impl NoteStore {
     pub fn new(db: Database) -> Self {
-        Self { db }
+        Self {
+            db,
+            max_versions: DEFAULT_MAX_NOTE_VERSIONS,
+        }
+    }
+
+    /// Keep at most `max_versions` content versions per note.
+    pub fn with_max_versions(mut self, max_versions: usize) -> Self {
+        self.max_versions = max_versions.max(1);
+        self
+    }
+
+    /// The version limit from [`MAX_NOTE_VERSIONS_ENV`], or
+    /// [`DEFAULT_MAX_NOTE_VERSIONS`] when unset or invalid.
+    pub fn max_versions_from_env() -> usize {
+        match std::env::var(MAX_NOTE_VERSIONS_ENV) {
+            Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!(
+                    "[NoteStore] Ignoring invalid {}: {}",
+                    MAX_NOTE_VERSIONS_ENV,
+                    raw
+                );
+                DEFAULT_MAX_NOTE_VERSIONS
+            }),
+            Err(_) => DEFAULT_MAX_NOTE_VERSIONS,
+        }
+    }
+
+    /// Content versions kept per note.
+    pub fn max_versions(&self) -> usize {
+        self.max_versions
+    }
+
+    /// Upsert `note` and return the stored row. A workspace holds at most one
+    /// spec and one task note: saving either type under another id updates
+    /// the note at the type's [`NoteType::singleton_id`] instead.
+    pub async fn save(&self, note: &Note) -> Result<Note, ServerError> {
+        self.save_as(note, None).await
+    }
+
+    /// [`save`](Self::save), attributing a content change to `author_agent_id`
+    /// in the note's history.
+    pub async fn save_as(
+        &self,
+        note: &Note,
+        author_agent_id: Option<&str>,
+    ) -> Result<Note, ServerError> {
+        let n = note.clone();
+        let author = author_agent_id.map(str::to_string);
+        let max_versions = self.max_versions;
+        self.db
+            .transaction(move |conn| {
+                Self::save_versioned_in(conn, &n, author.as_deref(), max_versions)
+            })
+            .await
     }
 
-    pub async fn save(&self, note: &Note) -> Result<(), ServerError> {
+    /// Save `note`, unless it is a non-empty general note whose
+    /// [`Note::content_hash`] matches another general note in the same
+    /// workspace. Returns the note that ends up stored: `note` itself, or the
+    /// existing duplicate. Spec and task notes are always saved.
+    pub async fn save_deduplicated(&self, note: &Note) -> Result<Note, ServerError> {
         let n = note.clone();
+        let max_versions = self.max_versions;
+        // One write transaction, so a concurrent save of the same content
+        // cannot slip in between the duplicate check and the insert.
+        self.db
+            .transaction(move |conn| {
+                if n.metadata.note_type == NoteType::General && !n.content.trim().is_empty() {
+                    let hash = Note::content_hash(&n.content);
+                    let mut stmt = conn.prepare(
+                        "SELECT id, content FROM notes
+                         WHERE workspace_id = ?1 AND type = 'general' AND id != ?2
+                         ORDER BY created_at ASC",
+                    )?;
+                    let duplicate_id = stmt
+                        .query_map(rusqlite::params![n.workspace_id, n.id], |row| {
+                            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                        })?
+                        .collect::<Result<Vec<_>, _>>()?
+                        .into_iter()
+                        .find(|(_, content)| Note::content_hash(content) == hash)
+                        .map(|(id, _)| id);
+                    if let Some(id) = duplicate_id {
+                        return Self::get_in(conn, &id, &n.workspace_id);
+                    }
+                }
+                Self::save_versioned_in(conn, &n, None, max_versions)
+            })
+            .await
+    }
+
+    /// [`save`](Self::save) on an existing connection, e.g. inside a
+    /// [`Database::transaction`], keeping at most `max_versions` versions
+    /// (pass the store's [`max_versions`](Self::max_versions)).
+    pub fn save_in(
+        conn: &Connection,
+        n: &Note,
+        max_versions: usize,
+    ) -> Result<(), rusqlite::Error> {
+        Self::save_versioned_in(conn, n, None, max_versions.max(1)).map(|_| ())
+    }
+
+    fn get_in(conn: &Connection, id: &str, workspace_id: &str) -> Result<Note, rusqlite::Error> {
+        conn.query_row(
+            "SELECT id, workspace_id, session_id, title, content, type, task_status,
+             assigned_agent_ids, parent_note_id, linked_task_id, custom_metadata, created_at, updated_at
+             FROM notes WHERE id = ?1 AND workspace_id = ?2",
+            rusqlite::params![id, workspace_id],
+            |row| Ok(row_to_note(row)),
+        )
+    }
+
+    /// Upsert `n` (onto its type's singleton id, if any) and, if its content
+    /// differs from the stored content, record a new version, dropping the
+    /// oldest beyond `max_versions`. Returns the stored note.
+    fn save_versioned_in(
+        conn: &Connection,
+        n: &Note,
+        author_agent_id: Option<&str>,
+        max_versions: usize,
+    ) -> Result<Note, rusqlite::Error> {
+        let singleton;
+        let n = match n.metadata.note_type.singleton_id() {
+            Some(id) if n.id != id => {
+                singleton = Note {
+                    id: id.to_string(),
+                    ..n.clone()
+                };
+                &singleton
+            }
+            _ => n,
+        };
+        let previous: Option<String> = conn
+            .query_row(
+                "SELECT content FROM notes WHERE id = ?1 AND workspace_id = ?2",
+                rusqlite::params![n.id, n.workspace_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        conn.execute(
+            "INSERT INTO notes (id, workspace_id, session_id, title, content, type, task_status,
+             assigned_agent_ids, parent_note_id, linked_task_id, custom_metadata, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT(workspace_id, id) DO UPDATE SET
+               session_id = excluded.session_id,
+               title = excluded.title,
+               content = excluded.content,
+               type = excluded.type,
+               task_status = excluded.task_status,
+               assigned_agent_ids = excluded.assigned_agent_ids,
+               parent_note_id = excluded.parent_note_id,
+               linked_task_id = excluded.linked_task_id,
+               custom_metadata = excluded.custom_metadata,
+               updated_at = excluded.updated_at",
+            rusqlite::params![
+                n.id,
+                n.workspace_id,
+                n.session_id,
+                n.title,
+                n.content,
+                n.metadata.note_type.as_str(),
+                n.metadata.task_status.as_ref().map(|s| s.as_str()),
+                n.metadata.assigned_agent_ids.as_ref().map(|v| serde_json::to_string(v).unwrap_or_default()),
+                n.metadata.parent_note_id,
+                n.metadata.linked_task_id,
+                n.metadata.custom.as_ref().map(|v| serde_json::to_string(v).unwrap_or_default()),
+                n.created_at.timestamp_millis(),
+                n.updated_at.timestamp_millis(),
+            ],
+        )?;
+        if previous.as_deref() == Some(n.content.as_str()) {
+            return Self::get_in(conn, &n.id, &n.workspace_id);
+        }
+        let version: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) + 1 FROM note_versions
+             WHERE workspace_id = ?1 AND note_id = ?2",
+            rusqlite::params![n.workspace_id, n.id],
+            |row| row.get(0),
+        )?;
+        conn.execute(
+            "INSERT INTO note_versions (workspace_id, note_id, version, content, author_agent_id, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                n.workspace_id,
+                n.id,
+                version,
+                n.content,
+                author_agent_id,
+                n.updated_at.timestamp_millis(),
+            ],
+        )?;
+        conn.execute(
+            "DELETE FROM note_versions WHERE workspace_id = ?1 AND note_id = ?2 AND version <= ?3",
+            rusqlite::params![n.workspace_id, n.id, version - max_versions as i64],
+        )?;
+        Self::get_in(conn, &n.id, &n.workspace_id)
+    }
+
+    /// Content versions of a note, oldest first.
+    pub async fn history(
+        &self,
+        note_id: &str,
+        workspace_id: &str,
+    ) -> Result<Vec<NoteVersion>, ServerError> {
+        let nid = note_id.to_string();
+        let ws_id = workspace_id.to_string();
         self.db
             .with_conn_async(move |conn| {
-                conn.execute(
-                    "INSERT INTO notes (id, workspace_id, session_id, title, content, type, task_status,
-                     assigned_agent_ids, parent_note_id, linked_task_id, custom_metadata, created_at, updated_at)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
-                     ON CONFLICT(workspace_id, id) DO UPDATE SET
-                       session_id = excluded.session_id,
-                       title = excluded.title,
-                       content = excluded.content,
-                       type = excluded.type,
-                       task_status = excluded.task_status,
-                       assigned_agent_ids = excluded.assigned_agent_ids,
-                       parent_note_id = excluded.parent_note_id,
-                       linked_task_id = excluded.linked_task_id,
-                       custom_metadata = excluded.custom_metadata,
-                       updated_at = excluded.updated_at",
-                    rusqlite::params![
-                        n.id,
-                        n.workspace_id,
-                        n.session_id,
-                        n.title,
-                        n.content,
-                        n.metadata.note_type.as_str(),
-                        n.metadata.task_status.as_ref().map(|s| s.as_str()),
-                        n.metadata.assigned_agent_ids.as_ref().map(|v| serde_json::to_string(v).unwrap_or_default()),
-                        n.metadata.parent_note_id,
-                        n.metadata.linked_task_id,
-                        n.metadata.custom.as_ref().map(|v| serde_json::to_string(v).unwrap_or_default()),
-                        n.created_at.timestamp_millis(),
-                        n.updated_at.timestamp_millis(),
-                    ],
+                let mut stmt = conn.prepare(
+                    "SELECT version, content, author_agent_id, created_at FROM note_versions
+                     WHERE note_id = ?1 AND workspace_id = ?2 ORDER BY version ASC",
                 )?;
-                Ok(())
+                let versions = stmt
+                    .query_map(rusqlite::params![nid, ws_id], |row| {
+                        Ok(NoteVersion {
+                            version: row.get(0)?,
+                            content: row.get(1)?,
+                            author_agent_id: row.get(2)?,
+                            created_at: chrono::DateTime::from_timestamp_millis(row.get(3)?)
+                                .unwrap_or_else(Utc::now),
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(versions)
             })
             .await
     }
 
+    /// Restore a note's content to `version`, recorded as a new version.
+    /// Returns `None` if the note or version does not exist.
+    pub async fn revert(
+        &self,
+        note_id: &str,
+        workspace_id: &str,
+        version: i64,
+        author_agent_id: Option<&str>,
+    ) -> Result<Option<Note>, ServerError> {
+        let Some(mut note) = self.get(note_id, workspace_id).await? else {
+            return Ok(None);
+        };
+        let Some(target) = self
+            .history(note_id, workspace_id)
+            .await?
+            .into_iter()
+            .find(|v| v.version == version)
+        else {
+            return Ok(None);
+        };
+        note.content = target.content;
+        note.updated_at = Utc::now();
+        self.save_as(&note, author_agent_id).await?;
+        Ok(Some(note))
+    }
+
     pub async fn get(
         &self,
         note_id: &str,
@@ -93,6 +313,35 @@ impl NoteStore {
             .await
     }
 
+    /// Notes in `workspace_id` whose timestamps fall inside `range`, most
+    /// recently updated first.
+    pub async fn list_in_range(
+        &self,
+        workspace_id: &str,
+        range: &TimeRange,
+    ) -> Result<Vec<Note>, ServerError> {
+        let mut values: Vec<rusqlite::types::Value> = vec![workspace_id.to_string().into()];
+        let mut clauses = vec!["workspace_id = ?1".to_string()];
+        range.push_clauses(&mut clauses, &mut values);
+        let sql = format!(
+            "SELECT id, workspace_id, session_id, title, content, type, task_status,
+             assigned_agent_ids, parent_note_id, linked_task_id, custom_metadata, created_at, updated_at
+             FROM notes WHERE {} ORDER BY updated_at DESC",
+            clauses.join(" AND ")
+        );
+        self.db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(&sql)?;
+                let rows = stmt
+                    .query_map(rusqlite::params_from_iter(values.iter()), |row| {
+                        Ok(row_to_note(row))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+    }
+
     pub async fn list_by_type(
         &self,
         workspace_id: &str,
@@ -115,6 +364,33 @@ impl NoteStore {
             .await
     }
 
+    /// Notes in a workspace whose `assigned_agent_ids` include `agent_id`.
+    pub async fn list_assigned_to(
+        &self,
+        agent_id: &str,
+        workspace_id: &str,
+    ) -> Result<Vec<Note>, ServerError> {
+        let agent_id = agent_id.to_string();
+        let ws_id = workspace_id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, workspace_id, session_id, title, content, type, task_status,
+                     assigned_agent_ids, parent_note_id, linked_task_id, custom_metadata, created_at, updated_at
+                     FROM notes
+                     WHERE workspace_id = ?1
+                       AND json_valid(assigned_agent_ids)
+                       AND EXISTS (SELECT 1 FROM json_each(notes.assigned_agent_ids) WHERE value = ?2)
+                     ORDER BY created_at DESC",
+                )?;
+                let rows = stmt
+                    .query_map(rusqlite::params![ws_id, agent_id], |row| Ok(row_to_note(row)))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+    }
+
     pub async fn delete(&self, note_id: &str, workspace_id: &str) -> Result<(), ServerError> {
         let nid = note_id.to_string();
         let ws_id = workspace_id.to_string();
@@ -129,13 +405,29 @@ impl NoteStore {
             .await
     }
 
+    /// Return the workspace spec, creating it with `DEFAULT_SPEC_CONTENT` if
+    /// absent. The insert is `OR IGNORE`, so concurrent callers never
+    /// duplicate or overwrite an existing spec.
     pub async fn ensure_spec(&self, workspace_id: &str) -> Result<Note, ServerError> {
-        if let Some(note) = self.get(SPEC_NOTE_ID, workspace_id).await? {
-            return Ok(note);
-        }
-        let note = Note::new_spec(workspace_id.to_string());
-        self.save(&note).await?;
-        Ok(note)
+        let n = Note::new_spec(workspace_id.to_string());
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "INSERT OR IGNORE INTO notes (id, workspace_id, title, content, type, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    rusqlite::params![
+                        n.id,
+                        n.workspace_id,
+                        n.title,
+                        n.content,
+                        n.metadata.note_type.as_str(),
+                        n.created_at.timestamp_millis(),
+                        n.updated_at.timestamp_millis(),
+                    ],
+                )?;
+                Self::get_in(conn, &n.id, &n.workspace_id)
+            })
+            .await
     }
 }
 
@@ -179,3 +471,249 @@ fn row_to_note(row: &Row<'_>) -> Note {
         updated_at: chrono::DateTime::from_timestamp_millis(updated_ms).unwrap_or_else(Utc::now),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::note::{DEFAULT_SPEC_CONTENT, SPEC_NOTE_ID, TASK_NOTE_ID};
+    use crate::store::WorkspaceStore;
+
+    #[tokio::test]
+    async fn ensure_spec_is_idempotent_under_concurrency() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let store = NoteStore::new(db);
+
+        let (first, second) =
+            tokio::join!(store.ensure_spec("default"), store.ensure_spec("default"));
+        let first = first.expect("first ensure_spec should succeed");
+        let second = second.expect("second ensure_spec should succeed");
+        assert_eq!(first.id, SPEC_NOTE_ID);
+        assert_eq!(second.id, SPEC_NOTE_ID);
+        assert_eq!(first.content, DEFAULT_SPEC_CONTENT);
+        assert!(first.content.contains("## Acceptance Criteria"));
+
+        let notes = store
+            .list_by_workspace("default")
+            .await
+            .expect("notes should list");
+        assert_eq!(notes.len(), 1);
+
+        let mut edited = first.clone();
+        edited.content = "# Goal\n\nShip it".to_string();
+        store.save(&edited).await.expect("spec should save");
+        let again = store
+            .ensure_spec("default")
+            .await
+            .expect("ensure_spec should return existing note");
+        assert_eq!(again.content, "# Goal\n\nShip it");
+    }
+
+    #[tokio::test]
+    async fn save_deduplicated_returns_existing_general_note() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let store = NoteStore::new(db);
+        let general = |id: &str, content: &str| {
+            Note::new(
+                id.to_string(),
+                "Findings".to_string(),
+                content.to_string(),
+                "default".to_string(),
+                None,
+            )
+        };
+
+        let first = store
+            .save_deduplicated(&general("note-1", "API uses cursor pagination."))
+            .await
+            .unwrap();
+        let second = store
+            .save_deduplicated(&general("note-2", "  API uses cursor\npagination. "))
+            .await
+            .unwrap();
+        assert_eq!(first.id, "note-1");
+        assert_eq!(second.id, "note-1");
+        assert_eq!(store.list_by_workspace("default").await.unwrap().len(), 1);
+
+        let task_note = Note::new(
+            "task-1".to_string(),
+            "Findings".to_string(),
+            "API uses cursor pagination.".to_string(),
+            "default".to_string(),
+            Some(NoteMetadata {
+                note_type: NoteType::Task,
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            store.save_deduplicated(&task_note).await.unwrap().id,
+            TASK_NOTE_ID
+        );
+        assert_eq!(store.list_by_workspace("default").await.unwrap().len(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_duplicate_saves_store_one_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routa.db");
+        let db = Database::open_with_pool_size(&path.to_string_lossy(), 4).unwrap();
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let store = std::sync::Arc::new(NoteStore::new(db));
+
+        let mut saves = tokio::task::JoinSet::new();
+        for i in 0..8 {
+            let store = store.clone();
+            saves.spawn(async move {
+                let note = Note::new(
+                    format!("note-{i}"),
+                    "Findings".to_string(),
+                    "API uses cursor pagination.".to_string(),
+                    "default".to_string(),
+                    None,
+                );
+                store.save_deduplicated(&note).await
+            });
+        }
+        let ids: std::collections::HashSet<String> = saves
+            .join_all()
+            .await
+            .into_iter()
+            .map(|note| note.unwrap().id)
+            .collect();
+
+        assert_eq!(ids.len(), 1);
+        assert_eq!(store.list_by_workspace("default").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn revert_restores_an_earlier_version() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let store = NoteStore::new(db).with_max_versions(2);
+
+        let mut spec = store.ensure_spec("default").await.unwrap();
+        spec.content = "# Goal\n\nFirst draft".to_string();
+        store.save_as(&spec, Some("agent-1")).await.unwrap();
+        spec.content = "# Goal\n\nAccidental overwrite".to_string();
+        store.save_as(&spec, Some("agent-2")).await.unwrap();
+        store.save(&spec).await.unwrap();
+
+        let history = store.history(SPEC_NOTE_ID, "default").await.unwrap();
+        let versions: Vec<_> = history.iter().map(|v| v.version).collect();
+        assert_eq!(versions, vec![1, 2], "unchanged content adds no version");
+        assert_eq!(history[0].content, "# Goal\n\nFirst draft");
+        assert_eq!(history[0].author_agent_id.as_deref(), Some("agent-1"));
+
+        let reverted = store
+            .revert(SPEC_NOTE_ID, "default", 1, Some("agent-1"))
+            .await
+            .unwrap()
+            .expect("version 1 should exist");
+        assert_eq!(reverted.content, "# Goal\n\nFirst draft");
+        let stored = store.get(SPEC_NOTE_ID, "default").await.unwrap().unwrap();
+        assert_eq!(stored.content, "# Goal\n\nFirst draft");
+
+        let history = store.history(SPEC_NOTE_ID, "default").await.unwrap();
+        let versions: Vec<_> = history.iter().map(|v| v.version).collect();
+        assert_eq!(versions, vec![2, 3], "history is bounded");
+        assert!(store
+            .revert(SPEC_NOTE_ID, "default", 1, None)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn save_in_keeps_the_store_version_limit() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let store = NoteStore::new(db.clone()).with_max_versions(2);
+
+        let mut spec = store.ensure_spec("default").await.unwrap();
+        let max_versions = store.max_versions();
+        db.transaction(move |conn| {
+            for draft in ["one", "two", "three"] {
+                spec.content = draft.to_string();
+                NoteStore::save_in(conn, &spec, max_versions)?;
+            }
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        let history = store.history(SPEC_NOTE_ID, "default").await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].content, "two");
+    }
+
+    #[tokio::test]
+    async fn list_assigned_to_returns_only_the_agents_notes() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let store = NoteStore::new(db);
+        let assigned = |id: &str, agents: Option<Vec<&str>>| {
+            Note::new(
+                id.to_string(),
+                format!("Note {id}"),
+                String::new(),
+                "default".to_string(),
+                Some(NoteMetadata {
+                    assigned_agent_ids: agents
+                        .map(|agents| agents.into_iter().map(str::to_string).collect()),
+                    ..Default::default()
+                }),
+            )
+        };
+
+        store
+            .save(&assigned("mine", Some(vec!["agent-1", "agent-2"])))
+            .await
+            .unwrap();
+        store
+            .save(&assigned("theirs", Some(vec!["agent-2"])))
+            .await
+            .unwrap();
+        store.save(&assigned("unassigned", None)).await.unwrap();
+
+        let notes = store.list_assigned_to("agent-1", "default").await.unwrap();
+        assert_eq!(
+            notes
+                .iter()
+                .map(|note| note.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["mine"]
+        );
+        assert_eq!(
+            store
+                .list_assigned_to("agent-2", "default")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(store
+            .list_assigned_to("agent-1", "other")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
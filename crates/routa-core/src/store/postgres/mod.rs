@@ -0,0 +1,13 @@
+//! Postgres queries for the stores that support the `postgres` backend.
+//!
+//! Each store keeps its SQLite queries inline and dispatches here when it
+//! runs on a [`PgDatabase`](crate::db::PgDatabase).
+
+pub(crate) mod task;
+pub(crate) mod workspace;
+
+use chrono::{DateTime, Utc};
+
+fn from_millis(ms: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(ms).unwrap_or_else(Utc::now)
+}
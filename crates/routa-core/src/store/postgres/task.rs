@@ -0,0 +1,429 @@
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::Row;
+
+use super::from_millis;
+use crate::db::PgDatabase;
+use crate::error::ServerError;
+use crate::models::task::{
+    Task, TaskAuditAction, TaskAuditEntry, TaskContextSearchSpec, TaskCreationSource, TaskPriority,
+    TaskStatus, VerificationVerdict,
+};
+use crate::store::task_store::{AssigneeWorkload, WorkloadTask};
+use crate::store::TimeRange;
+
+/// The columns [`row_to_task`] reads, in order.
+const COLUMNS: &str = "id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+     assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+     assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+     trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+     github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
+     session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+     verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at,
+     acceptance_criteria_status";
+
+pub(crate) async fn save(pg: &PgDatabase, t: &Task) -> Result<(), ServerError> {
+    pg.client()
+        .await?
+        .execute(
+            "INSERT INTO tasks (id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+                                 assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+                                 assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+                                 trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+                                 github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id,
+                                 creation_source, session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+                                 verification_report, codebase_ids, context_search_spec, worktree_id, version, created_at, updated_at,
+                                 acceptance_criteria_status)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18,
+                     $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34, $35, $36,
+                     $37, $38, $39, $40, $41, $42, 1, $43, $44, $45)
+             ON CONFLICT(id) DO UPDATE SET
+               title = excluded.title,
+               objective = excluded.objective,
+               comment = excluded.comment,
+               scope = excluded.scope,
+               acceptance_criteria = excluded.acceptance_criteria,
+               verification_commands = excluded.verification_commands,
+               test_cases = excluded.test_cases,
+               assigned_to = excluded.assigned_to,
+               status = excluded.status,
+               board_id = excluded.board_id,
+               column_id = excluded.column_id,
+               position = excluded.position,
+               priority = excluded.priority,
+               labels = excluded.labels,
+               assignee = excluded.assignee,
+               assigned_provider = excluded.assigned_provider,
+               assigned_role = excluded.assigned_role,
+               assigned_specialist_id = excluded.assigned_specialist_id,
+               assigned_specialist_name = excluded.assigned_specialist_name,
+               trigger_session_id = excluded.trigger_session_id,
+               github_id = excluded.github_id,
+               github_number = excluded.github_number,
+               github_url = excluded.github_url,
+               github_repo = excluded.github_repo,
+               github_state = excluded.github_state,
+               github_synced_at = excluded.github_synced_at,
+               last_sync_error = excluded.last_sync_error,
+               dependencies = excluded.dependencies,
+               parallel_group = excluded.parallel_group,
+               workspace_id = excluded.workspace_id,
+               session_id = excluded.session_id,
+               creation_source = excluded.creation_source,
+               session_ids = excluded.session_ids,
+               lane_sessions = excluded.lane_sessions,
+               lane_handoffs = excluded.lane_handoffs,
+               completion_summary = excluded.completion_summary,
+               verification_verdict = excluded.verification_verdict,
+               verification_report = excluded.verification_report,
+               codebase_ids = excluded.codebase_ids,
+               context_search_spec = excluded.context_search_spec,
+               worktree_id = excluded.worktree_id,
+               updated_at = excluded.updated_at,
+               acceptance_criteria_status = excluded.acceptance_criteria_status",
+            &[
+                &t.id,
+                &t.title,
+                &t.objective,
+                &t.comment,
+                &t.scope,
+                &t.acceptance_criteria.as_ref().map(to_json),
+                &t.verification_commands.as_ref().map(to_json),
+                &t.test_cases.as_ref().map(to_json),
+                &t.assigned_to,
+                &t.status.as_str(),
+                &t.board_id,
+                &t.column_id,
+                &t.position,
+                &t.priority.as_ref().map(|v| v.as_str()),
+                &to_json(&t.labels),
+                &t.assignee,
+                &t.assigned_provider,
+                &t.assigned_role,
+                &t.assigned_specialist_id,
+                &t.assigned_specialist_name,
+                &t.trigger_session_id,
+                &t.github_id,
+                &t.github_number,
+                &t.github_url,
+                &t.github_repo,
+                &t.github_state,
+                &t.github_synced_at.map(|v| v.timestamp_millis()),
+                &t.last_sync_error,
+                &to_json(&t.dependencies),
+                &t.parallel_group,
+                &t.workspace_id,
+                &t.session_id,
+                &t.creation_source.as_ref().map(|value| value.as_str()),
+                &to_json(&t.session_ids),
+                &to_json(&t.lane_sessions),
+                &to_json(&t.lane_handoffs),
+                &t.completion_summary,
+                &t.verification_verdict.as_ref().map(|v| v.as_str()),
+                &t.verification_report,
+                &to_json(&t.codebase_ids),
+                &t.context_search_spec.as_ref().map(to_json),
+                &t.worktree_id,
+                &t.created_at.timestamp_millis(),
+                &t.updated_at.timestamp_millis(),
+                &to_json(&t.acceptance_criteria_status),
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn get(pg: &PgDatabase, task_id: &str) -> Result<Option<Task>, ServerError> {
+    let row = pg
+        .client()
+        .await?
+        .query_opt(
+            &format!("SELECT {COLUMNS} FROM tasks WHERE id = $1"),
+            &[&task_id],
+        )
+        .await?;
+    Ok(row.as_ref().map(row_to_task))
+}
+
+/// Tasks matching `condition` (with `$n` placeholders bound to `params`),
+/// newest first.
+pub(crate) async fn list_where(
+    pg: &PgDatabase,
+    condition: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<Vec<Task>, ServerError> {
+    let rows = pg
+        .client()
+        .await?
+        .query(
+            &format!("SELECT {COLUMNS} FROM tasks WHERE {condition} ORDER BY created_at DESC"),
+            params,
+        )
+        .await?;
+    Ok(rows.iter().map(row_to_task).collect())
+}
+
+pub(crate) async fn list_in_range(
+    pg: &PgDatabase,
+    workspace_id: &str,
+    range: &TimeRange,
+) -> Result<Vec<Task>, ServerError> {
+    let bounds: Vec<(&str, i64)> = range.bounds().collect();
+    let mut clauses = vec!["workspace_id = $1".to_string()];
+    let mut params: Vec<&(dyn ToSql + Sync)> = vec![&workspace_id];
+    for (condition, bound) in &bounds {
+        params.push(bound);
+        clauses.push(format!("{condition} ${}", params.len()));
+    }
+    let rows = pg
+        .client()
+        .await?
+        .query(
+            &format!(
+                "SELECT {COLUMNS} FROM tasks WHERE {} ORDER BY updated_at DESC",
+                clauses.join(" AND ")
+            ),
+            &params,
+        )
+        .await?;
+    Ok(rows.iter().map(row_to_task).collect())
+}
+
+pub(crate) async fn workload(
+    pg: &PgDatabase,
+    workspace_id: &str,
+) -> Result<Vec<AssigneeWorkload>, ServerError> {
+    let mut workloads: BTreeMap<String, AssigneeWorkload> = BTreeMap::new();
+
+    let counts = pg
+        .client()
+        .await?
+        .query(
+            "SELECT assigned_to, status, COUNT(*) FROM tasks
+             WHERE workspace_id = $1 AND assigned_to IS NOT NULL AND assigned_to != ''
+             GROUP BY assigned_to, status",
+            &[&workspace_id],
+        )
+        .await?;
+    for row in &counts {
+        let agent_id: String = row.try_get(0)?;
+        let count = row.try_get::<_, i64>(2)? as usize;
+        let workload = workloads
+            .entry(agent_id.clone())
+            .or_insert_with(|| AssigneeWorkload {
+                agent_id,
+                counts: BTreeMap::new(),
+                total: 0,
+                current_task: None,
+            });
+        workload.counts.insert(row.try_get(1)?, count);
+        workload.total += count;
+    }
+
+    let in_progress = pg
+        .client()
+        .await?
+        .query(
+            "SELECT assigned_to, id, title FROM tasks
+             WHERE workspace_id = $1 AND assigned_to IS NOT NULL AND status = $2
+             ORDER BY updated_at DESC",
+            &[&workspace_id, &TaskStatus::InProgress.as_str()],
+        )
+        .await?;
+    for row in &in_progress {
+        let agent_id: String = row.try_get(0)?;
+        if let Some(workload) = workloads.get_mut(&agent_id) {
+            if workload.current_task.is_none() {
+                workload.current_task = Some(WorkloadTask {
+                    id: row.try_get(1)?,
+                    title: row.try_get(2)?,
+                });
+            }
+        }
+    }
+
+    Ok(workloads.into_values().collect())
+}
+
+/// Tasks whose title or objective matches the escaped `LIKE` `pattern`,
+/// ignoring case, most recently updated first.
+pub(crate) async fn search_candidates(
+    pg: &PgDatabase,
+    workspace_id: &str,
+    pattern: &str,
+) -> Result<Vec<Task>, ServerError> {
+    let rows = pg
+        .client()
+        .await?
+        .query(
+            &format!(
+                "SELECT {COLUMNS} FROM tasks
+                 WHERE workspace_id = $1
+                   AND (title ILIKE $2 ESCAPE '\\' OR objective ILIKE $2 ESCAPE '\\')
+                 ORDER BY updated_at DESC"
+            ),
+            &[&workspace_id, &pattern],
+        )
+        .await?;
+    Ok(rows.iter().map(row_to_task).collect())
+}
+
+pub(crate) async fn update_status(
+    pg: &PgDatabase,
+    task_id: &str,
+    status: &str,
+    now: i64,
+) -> Result<(), ServerError> {
+    pg.client()
+        .await?
+        .execute(
+            "UPDATE tasks SET status = $1, updated_at = $2 WHERE id = $3",
+            &[&status, &now, &task_id],
+        )
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn delete(pg: &PgDatabase, task_id: &str) -> Result<(), ServerError> {
+    pg.client()
+        .await?
+        .execute("DELETE FROM tasks WHERE id = $1", &[&task_id])
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn append_audit_entry(
+    pg: &PgDatabase,
+    e: &TaskAuditEntry,
+) -> Result<(), ServerError> {
+    pg.client()
+        .await?
+        .execute(
+            "INSERT INTO task_audit (id, task_id, action, actor, reason, previous_status, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            &[
+                &e.id,
+                &e.task_id,
+                &e.action.as_str(),
+                &e.actor,
+                &e.reason,
+                &e.previous_status.as_str(),
+                &e.created_at.timestamp_millis(),
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn list_audit_entries(
+    pg: &PgDatabase,
+    task_id: &str,
+) -> Result<Vec<TaskAuditEntry>, ServerError> {
+    let rows = pg
+        .client()
+        .await?
+        .query(
+            "SELECT id, task_id, action, actor, reason, previous_status, created_at
+             FROM task_audit WHERE task_id = $1 ORDER BY created_at ASC",
+            &[&task_id],
+        )
+        .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(TaskAuditEntry {
+                id: row.try_get(0)?,
+                task_id: row.try_get(1)?,
+                action: TaskAuditAction::from_str(&row.try_get::<_, String>(2).unwrap_or_default())
+                    .unwrap_or(TaskAuditAction::Reopen),
+                actor: row.try_get(3)?,
+                reason: row.try_get(4).unwrap_or(None),
+                previous_status: TaskStatus::from_str(
+                    &row.try_get::<_, String>(5).unwrap_or_default(),
+                )
+                .unwrap_or(TaskStatus::Pending),
+                created_at: from_millis(row.try_get(6).unwrap_or(0)),
+            })
+        })
+        .collect()
+}
+
+fn to_json<T: serde::Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn json_column<T: DeserializeOwned>(row: &Row, idx: usize) -> Option<T> {
+    row.try_get::<_, Option<String>>(idx)
+        .ok()
+        .flatten()
+        .and_then(|s| serde_json::from_str(&s).ok())
+}
+
+/// Mirrors the SQLite `row_to_task`, column for column.
+fn row_to_task(row: &Row) -> Task {
+    let session_id: Option<String> = row.try_get(31).unwrap_or(None);
+    let creation_source = row
+        .try_get::<_, Option<String>>(32)
+        .unwrap_or(None)
+        .and_then(|s| TaskCreationSource::from_str(&s))
+        .or_else(|| session_id.as_ref().map(|_| TaskCreationSource::Session));
+
+    Task {
+        id: row.try_get(0).unwrap_or_default(),
+        title: row.try_get(1).unwrap_or_default(),
+        objective: row.try_get(2).unwrap_or_default(),
+        comment: row.try_get(3).unwrap_or(None),
+        scope: row.try_get(4).unwrap_or(None),
+        acceptance_criteria: json_column(row, 5),
+        acceptance_criteria_status: json_column(row, 44).unwrap_or_default(),
+        verification_commands: json_column(row, 6),
+        test_cases: json_column(row, 7),
+        assigned_to: row.try_get(8).unwrap_or(None),
+        status: TaskStatus::from_str(&row.try_get::<_, String>(9).unwrap_or_default())
+            .unwrap_or(TaskStatus::Pending),
+        board_id: row.try_get(10).unwrap_or(None),
+        column_id: row.try_get(11).unwrap_or(None),
+        position: row.try_get(12).unwrap_or(0),
+        priority: row
+            .try_get::<_, Option<String>>(13)
+            .unwrap_or(None)
+            .and_then(|s| TaskPriority::from_str(&s)),
+        labels: json_column(row, 14).unwrap_or_default(),
+        assignee: row.try_get(15).unwrap_or(None),
+        assigned_provider: row.try_get(16).unwrap_or(None),
+        assigned_role: row.try_get(17).unwrap_or(None),
+        assigned_specialist_id: row.try_get(18).unwrap_or(None),
+        assigned_specialist_name: row.try_get(19).unwrap_or(None),
+        trigger_session_id: row.try_get(20).unwrap_or(None),
+        github_id: row.try_get(21).unwrap_or(None),
+        github_number: row.try_get(22).unwrap_or(None),
+        github_url: row.try_get(23).unwrap_or(None),
+        github_repo: row.try_get(24).unwrap_or(None),
+        github_state: row.try_get(25).unwrap_or(None),
+        github_synced_at: row
+            .try_get::<_, Option<i64>>(26)
+            .unwrap_or(None)
+            .and_then(chrono::DateTime::from_timestamp_millis),
+        last_sync_error: row.try_get(27).unwrap_or(None),
+        dependencies: json_column(row, 28).unwrap_or_default(),
+        parallel_group: row.try_get(29).unwrap_or(None),
+        workspace_id: row.try_get(30).unwrap_or_default(),
+        session_id,
+        creation_source,
+        session_ids: json_column(row, 33).unwrap_or_default(),
+        lane_sessions: json_column(row, 34).unwrap_or_default(),
+        lane_handoffs: json_column(row, 35).unwrap_or_default(),
+        completion_summary: row.try_get(36).unwrap_or(None),
+        verification_verdict: row
+            .try_get::<_, Option<String>>(37)
+            .unwrap_or(None)
+            .and_then(|s| VerificationVerdict::from_str(&s)),
+        verification_report: row.try_get(38).unwrap_or(None),
+        codebase_ids: json_column(row, 39).unwrap_or_default(),
+        context_search_spec: json_column::<TaskContextSearchSpec>(row, 40),
+        worktree_id: row.try_get(41).unwrap_or(None),
+        created_at: from_millis(row.try_get(42).unwrap_or(0)),
+        updated_at: from_millis(row.try_get(43).unwrap_or(0)),
+    }
+}
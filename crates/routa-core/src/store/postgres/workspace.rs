@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use tokio_postgres::Row;
+
+use super::from_millis;
+use crate::db::PgDatabase;
+use crate::error::ServerError;
+use crate::models::workspace::{Workspace, WorkspaceStatus};
+
+const COLUMNS: &str = "id, title, status, metadata, created_at, updated_at";
+
+pub(crate) async fn save(pg: &PgDatabase, ws: &Workspace) -> Result<(), ServerError> {
+    pg.client()
+        .await?
+        .execute(
+            "INSERT INTO workspaces (id, title, status, metadata, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT(id) DO UPDATE SET
+               title = excluded.title,
+               status = excluded.status,
+               metadata = excluded.metadata,
+               updated_at = excluded.updated_at",
+            &[
+                &ws.id,
+                &ws.title,
+                &ws.status.as_str(),
+                &serde_json::to_string(&ws.metadata).unwrap_or_default(),
+                &ws.created_at.timestamp_millis(),
+                &ws.updated_at.timestamp_millis(),
+            ],
+        )
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn get(pg: &PgDatabase, id: &str) -> Result<Option<Workspace>, ServerError> {
+    let row = pg
+        .client()
+        .await?
+        .query_opt(
+            &format!("SELECT {COLUMNS} FROM workspaces WHERE id = $1"),
+            &[&id],
+        )
+        .await?;
+    Ok(row.as_ref().map(row_to_workspace))
+}
+
+/// Every workspace, or only those with `status`, newest first.
+pub(crate) async fn list(
+    pg: &PgDatabase,
+    status: Option<&str>,
+) -> Result<Vec<Workspace>, ServerError> {
+    let rows = match status {
+        Some(status) => {
+            let sql = format!(
+                "SELECT {COLUMNS} FROM workspaces WHERE status = $1 ORDER BY created_at DESC"
+            );
+            pg.client().await?.query(&sql, &[&status]).await?
+        }
+        None => {
+            let sql = format!("SELECT {COLUMNS} FROM workspaces ORDER BY created_at DESC");
+            pg.client().await?.query(&sql, &[]).await?
+        }
+    };
+    Ok(rows.iter().map(row_to_workspace).collect())
+}
+
+pub(crate) async fn update_column(
+    pg: &PgDatabase,
+    column: &str,
+    id: &str,
+    value: &str,
+    now: i64,
+) -> Result<(), ServerError> {
+    pg.client()
+        .await?
+        .execute(
+            &format!("UPDATE workspaces SET {column} = $1, updated_at = $2 WHERE id = $3"),
+            &[&value, &now, &id],
+        )
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn delete(pg: &PgDatabase, id: &str) -> Result<(), ServerError> {
+    pg.client()
+        .await?
+        .execute("DELETE FROM workspaces WHERE id = $1", &[&id])
+        .await?;
+    Ok(())
+}
+
+fn row_to_workspace(row: &Row) -> Workspace {
+    let metadata: HashMap<String, String> = row
+        .try_get::<_, String>(3)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    Workspace {
+        id: row.try_get(0).unwrap_or_default(),
+        title: row.try_get(1).unwrap_or_default(),
+        status: WorkspaceStatus::from_str(&row.try_get::<_, String>(2).unwrap_or_default()),
+        metadata,
+        created_at: from_millis(row.try_get(4).unwrap_or(0)),
+        updated_at: from_millis(row.try_get(5).unwrap_or(0)),
+    }
+}
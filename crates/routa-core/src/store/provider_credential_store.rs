@@ -0,0 +1,470 @@
+//! Encrypted storage for provider credentials (API keys and the like).
+//!
+//! Each credential is an environment variable set on the provider's agent
+//! process when it is spawned. Values are sealed with AES-256-GCM before they
+//! reach the database, bound to their `(provider, key)` row, and are only ever
+//! handed back to API callers masked.
+//!
+//! The encryption key comes from a [`CredentialCipher`]. The default,
+//! [`MachineKeyCipher`], derives it from a per-machine secret: the
+//! `ROUTA_CREDENTIALS_SECRET` environment variable when set, otherwise a random
+//! secret generated on first use at `{data_dir}/routa/credentials.key`
+//! (readable only by the owner on Unix). An OS keychain can be plugged in by
+//! implementing the trait.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use chrono::{DateTime, TimeZone, Utc};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Serialize;
+
+use crate::db::Database;
+use crate::error::ServerError;
+
+/// Overrides the machine secret used by [`MachineKeyCipher::from_machine`].
+pub const CREDENTIALS_SECRET_ENV: &str = "ROUTA_CREDENTIALS_SECRET";
+
+/// Seals and opens credential values. `aad` identifies the credential so a
+/// sealed value can't be moved to another row.
+pub trait CredentialCipher: Send + Sync {
+    fn seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String>;
+    fn open(&self, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// AES-256-GCM keyed by a hash of a machine-local secret.
+pub struct MachineKeyCipher {
+    secret_path: PathBuf,
+    key: OnceLock<Result<LessSafeKey, String>>,
+}
+
+impl MachineKeyCipher {
+    /// Cipher keyed by `$ROUTA_CREDENTIALS_SECRET`, or by the secret file in
+    /// the data directory. The secret is loaded (or created) on first use.
+    pub fn from_machine() -> Self {
+        let secret_path = dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("routa")
+            .join("credentials.key");
+        Self {
+            secret_path,
+            key: OnceLock::new(),
+        }
+    }
+
+    /// Cipher keyed by an explicit secret.
+    pub fn from_secret(secret: &[u8]) -> Self {
+        let cipher = Self {
+            secret_path: PathBuf::new(),
+            key: OnceLock::new(),
+        };
+        let _ = cipher.key.set(derive_key(secret));
+        cipher
+    }
+
+    fn key(&self) -> Result<&LessSafeKey, String> {
+        self.key
+            .get_or_init(|| {
+                let secret = match std::env::var(CREDENTIALS_SECRET_ENV) {
+                    Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+                    _ => load_or_create_secret(&self.secret_path)?,
+                };
+                derive_key(&secret)
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+}
+
+impl CredentialCipher for MachineKeyCipher {
+    fn seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| "Failed to generate a nonce".to_string())?;
+        let mut sealed = plaintext.to_vec();
+        self.key()?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(aad),
+                &mut sealed,
+            )
+            .map_err(|_| "Failed to encrypt credential".to_string())?;
+        let mut out = nonce.to_vec();
+        out.extend_from_slice(&sealed);
+        Ok(out)
+    }
+
+    fn open(&self, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+        if sealed.len() < NONCE_LEN {
+            return Err("Stored credential is truncated".to_string());
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| "Stored credential has a bad nonce".to_string())?;
+        let mut buf = ciphertext.to_vec();
+        let plaintext = self
+            .key()?
+            .open_in_place(nonce, Aad::from(aad), &mut buf)
+            .map_err(|_| {
+                "Failed to decrypt credential (was the machine secret changed?)".to_string()
+            })?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+fn derive_key(secret: &[u8]) -> Result<LessSafeKey, String> {
+    let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+    context.update(b"routa-provider-credentials-v1\0");
+    context.update(secret);
+    let digest = context.finish();
+    UnboundKey::new(&AES_256_GCM, digest.as_ref())
+        .map(LessSafeKey::new)
+        .map_err(|_| "Failed to derive credential key".to_string())
+}
+
+fn load_or_create_secret(path: &PathBuf) -> Result<Vec<u8>, String> {
+    if let Ok(secret) = std::fs::read(path) {
+        if !secret.is_empty() {
+            return Ok(secret);
+        }
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create '{}': {e}", parent.display()))?;
+    }
+    let mut secret = vec![0u8; 32];
+    SystemRandom::new()
+        .fill(&mut secret)
+        .map_err(|_| "Failed to generate a machine secret".to_string())?;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    match options.open(path) {
+        Ok(mut file) => {
+            file.write_all(&secret)
+                .map_err(|e| format!("Failed to write '{}': {e}", path.display()))?;
+            Ok(secret)
+        }
+        // Another process created it first; use theirs.
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            std::fs::read(path).map_err(|e| format!("Failed to read '{}': {e}", path.display()))
+        }
+        Err(e) => Err(format!("Failed to create '{}': {e}", path.display())),
+    }
+}
+
+/// A stored credential as shown to API callers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaskedCredential {
+    pub provider: String,
+    pub key: String,
+    pub masked_value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Hide all but the last four characters, and those too for short values.
+pub fn mask_secret(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < 12 {
+        return "****".to_string();
+    }
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{suffix}")
+}
+
+/// Variables that steer how the agent process is found or loaded. A
+/// credential may not override them.
+const DENIED_ENV_KEYS: &[&str] = &[
+    "PATH",
+    "PATHEXT",
+    "BASH_ENV",
+    "ENV",
+    "NODE_OPTIONS",
+    "NODE_PATH",
+    "PYTHONHOME",
+    "PYTHONPATH",
+    "PYTHONSTARTUP",
+    "PERL5LIB",
+    "PERL5OPT",
+    "RUBYLIB",
+    "RUBYOPT",
+];
+
+/// Prefixes of dynamic loader variables (`LD_PRELOAD`, `DYLD_INSERT_LIBRARIES`, ...).
+const DENIED_ENV_PREFIXES: &[&str] = &["LD_", "DYLD_"];
+
+fn is_denied_env_key(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    DENIED_ENV_KEYS.contains(&key.as_str())
+        || DENIED_ENV_PREFIXES
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+}
+
+fn validate_env_key(key: &str) -> Result<(), ServerError> {
+    let mut chars = key.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(ServerError::BadRequest(format!(
+            "Invalid credential key '{key}': expected an environment variable name"
+        )));
+    }
+    if is_denied_env_key(key) {
+        return Err(ServerError::BadRequest(format!(
+            "Credential key '{key}' would override how the agent process is loaded"
+        )));
+    }
+    Ok(())
+}
+
+fn credential_aad(provider: &str, key: &str) -> Vec<u8> {
+    format!("{provider}\0{key}").into_bytes()
+}
+
+#[derive(Clone)]
+pub struct ProviderCredentialStore {
+    db: Database,
+    cipher: Arc<dyn CredentialCipher>,
+}
+
+impl ProviderCredentialStore {
+    pub fn new(db: Database) -> Self {
+        Self::with_cipher(db, Arc::new(MachineKeyCipher::from_machine()))
+    }
+
+    pub fn with_cipher(db: Database, cipher: Arc<dyn CredentialCipher>) -> Self {
+        Self { db, cipher }
+    }
+
+    /// Store (or replace) `key` for `provider`.
+    pub async fn set(
+        &self,
+        provider: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<MaskedCredential, ServerError> {
+        if provider.trim().is_empty() {
+            return Err(ServerError::BadRequest("Provider is required".to_string()));
+        }
+        validate_env_key(key)?;
+        if value.is_empty() {
+            return Err(ServerError::BadRequest(format!(
+                "Credential '{key}' has an empty value"
+            )));
+        }
+
+        let sealed = self
+            .cipher
+            .seal(value.as_bytes(), &credential_aad(provider, key))
+            .map_err(ServerError::Internal)?;
+        let updated_at = Utc::now();
+        let (p, k) = (provider.to_string(), key.to_string());
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "INSERT INTO provider_credentials (provider, key, ciphertext, updated_at)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT(provider, key) DO UPDATE SET
+                       ciphertext = excluded.ciphertext,
+                       updated_at = excluded.updated_at",
+                    rusqlite::params![p, k, sealed, updated_at.timestamp_millis()],
+                )?;
+                Ok(())
+            })
+            .await?;
+
+        Ok(MaskedCredential {
+            provider: provider.to_string(),
+            key: key.to_string(),
+            masked_value: mask_secret(value),
+            updated_at,
+        })
+    }
+
+    /// Remove `key` for `provider`. Returns whether it existed.
+    pub async fn delete(&self, provider: &str, key: &str) -> Result<bool, ServerError> {
+        let (p, k) = (provider.to_string(), key.to_string());
+        self.db
+            .with_conn_async(move |conn| {
+                let n = conn.execute(
+                    "DELETE FROM provider_credentials WHERE provider = ?1 AND key = ?2",
+                    rusqlite::params![p, k],
+                )?;
+                Ok(n > 0)
+            })
+            .await
+    }
+
+    /// Credentials for `provider`, masked.
+    pub async fn list_masked(&self, provider: &str) -> Result<Vec<MaskedCredential>, ServerError> {
+        let rows = self.load(provider).await?;
+        rows.into_iter()
+            .map(|(key, value, updated_at)| {
+                Ok(MaskedCredential {
+                    provider: provider.to_string(),
+                    key,
+                    masked_value: mask_secret(&value?),
+                    updated_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Decrypted credentials for `provider`, as environment variables for its
+    /// agent process. Never expose the result over the API.
+    pub async fn env_for(&self, provider: &str) -> Result<HashMap<String, String>, ServerError> {
+        let rows = self.load(provider).await?;
+        rows.into_iter()
+            .filter(|(key, _, _)| {
+                let denied = is_denied_env_key(key);
+                if denied {
+                    tracing::warn!(
+                        "Ignoring stored credential '{}' for {}: it overrides a loader variable",
+                        key,
+                        provider
+                    );
+                }
+                !denied
+            })
+            .map(|(key, value, _)| Ok((key, value?)))
+            .collect()
+    }
+
+    #[allow(clippy::type_complexity)]
+    async fn load(
+        &self,
+        provider: &str,
+    ) -> Result<Vec<(String, Result<String, ServerError>, DateTime<Utc>)>, ServerError> {
+        let p = provider.to_string();
+        let rows: Vec<(String, Vec<u8>, i64)> = self
+            .db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT key, ciphertext, updated_at FROM provider_credentials
+                     WHERE provider = ?1 ORDER BY key",
+                )?;
+                let rows = stmt
+                    .query_map(rusqlite::params![p], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(key, sealed, updated_at)| {
+                let value = self
+                    .cipher
+                    .open(&sealed, &credential_aad(provider, &key))
+                    .map_err(ServerError::Internal)
+                    .and_then(|plaintext| {
+                        String::from_utf8(plaintext).map_err(|_| {
+                            ServerError::Internal(format!("Credential '{key}' is not UTF-8"))
+                        })
+                    });
+                let updated_at = Utc
+                    .timestamp_millis_opt(updated_at)
+                    .single()
+                    .unwrap_or_else(Utc::now);
+                (key, value, updated_at)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(db: Database) -> ProviderCredentialStore {
+        ProviderCredentialStore::with_cipher(
+            db,
+            Arc::new(MachineKeyCipher::from_secret(b"test-secret")),
+        )
+    }
+
+    #[tokio::test]
+    async fn credentials_are_encrypted_at_rest_and_masked_on_read() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let store = store(db.clone());
+
+        let masked = store
+            .set("opencode", "OPENAI_API_KEY", "sk-live-1234567890abcd")
+            .await
+            .unwrap();
+        assert_eq!(masked.masked_value, "****abcd");
+
+        let raw: Vec<u8> = db
+            .with_conn(|conn| {
+                conn.query_row(
+                    "SELECT ciphertext FROM provider_credentials WHERE provider = 'opencode'",
+                    [],
+                    |row| row.get(0),
+                )
+            })
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("sk-live"));
+
+        let listed = store.list_masked("opencode").await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].masked_value, "****abcd");
+        assert_eq!(
+            store.env_for("opencode").await.unwrap(),
+            HashMap::from([(
+                "OPENAI_API_KEY".to_string(),
+                "sk-live-1234567890abcd".to_string()
+            )])
+        );
+        assert!(store.env_for("gemini").await.unwrap().is_empty());
+
+        // A different machine secret can't read the value back.
+        let other = ProviderCredentialStore::with_cipher(
+            db,
+            Arc::new(MachineKeyCipher::from_secret(b"other-secret")),
+        );
+        assert!(other.env_for("opencode").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn set_rejects_keys_that_are_not_env_names() {
+        let store = store(Database::open_in_memory().expect("in-memory db should open"));
+        for key in ["", "1KEY", "MY-KEY", "A B"] {
+            let err = store.set("opencode", key, "value").await.unwrap_err();
+            assert!(matches!(err, ServerError::BadRequest(_)), "{key}");
+        }
+        assert_eq!(mask_secret("short"), "****");
+    }
+
+    #[tokio::test]
+    async fn set_rejects_loader_and_path_variables() {
+        let store = store(Database::open_in_memory().expect("in-memory db should open"));
+        for key in [
+            "PATH",
+            "Path",
+            "LD_PRELOAD",
+            "LD_LIBRARY_PATH",
+            "DYLD_INSERT_LIBRARIES",
+            "NODE_OPTIONS",
+            "PYTHONPATH",
+        ] {
+            let err = store.set("opencode", key, "value").await.unwrap_err();
+            assert!(matches!(err, ServerError::BadRequest(_)), "{key}");
+        }
+        store
+            .set("opencode", "OPENAI_API_KEY", "sk-test-value")
+            .await
+            .expect("ordinary keys should be accepted");
+    }
+}
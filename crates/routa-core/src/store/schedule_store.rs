@@ -1,4 +1,12 @@
-use chrono::Utc;
+//! Persistence for cron schedules.
+//!
+//! `cron_expr` is a standard 5-field expression (minute, hour, day of month,
+//! month, day of week), evaluated in UTC. The store validates it and keeps
+//! `next_run_at` in step: on create, whenever the expression changes, and
+//! after each run recorded with [`ScheduleStore::record_run`].
+
+use chrono::{DateTime, Utc};
+use croner::Cron;
 use rusqlite::OptionalExtension;
 use uuid::Uuid;
 
@@ -16,9 +24,24 @@ impl ScheduleStore {
         Self { db }
     }
 
+    /// The first time strictly after `after` that `cron_expr` fires, in UTC.
+    /// Rejects anything but a valid 5-field expression.
+    pub fn compute_next_run(
+        cron_expr: &str,
+        after: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, ServerError> {
+        let cron_expr = cron_expr.trim();
+        let cron = Cron::new(cron_expr)
+            .parse()
+            .map_err(|e| ServerError::BadRequest(format!("Invalid cron '{cron_expr}': {e}")))?;
+        cron.find_next_occurrence(&after, false)
+            .map_err(|e| ServerError::BadRequest(format!("Cron '{cron_expr}' never fires: {e}")))
+    }
+
     pub async fn create(&self, input: CreateScheduleInput) -> Result<Schedule, ServerError> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
+        let computed_next_run = Self::compute_next_run(&input.cron_expr, now)?;
         let s = Schedule {
             id: id.clone(),
             name: input.name,
@@ -28,7 +51,7 @@ impl ScheduleStore {
             workspace_id: input.workspace_id,
             enabled: input.enabled,
             last_run_at: None,
-            next_run_at: input.next_run_at,
+            next_run_at: Some(input.next_run_at.unwrap_or(computed_next_run)),
             last_task_id: None,
             prompt_template: input.prompt_template,
             created_at: now,
@@ -129,6 +152,7 @@ impl ScheduleStore {
         if let Some(v) = input.name {
             s.name = v;
         }
+        let reschedule = input.cron_expr.is_some() || input.last_run_at.is_some();
         if let Some(v) = input.cron_expr {
             s.cron_expr = v;
         }
@@ -143,6 +167,8 @@ impl ScheduleStore {
         }
         if let Some(v) = input.next_run_at {
             s.next_run_at = Some(v);
+        } else if reschedule {
+            s.next_run_at = Some(Self::compute_next_run(&s.cron_expr, Utc::now())?);
         }
         if let Some(v) = input.last_run_at {
             s.last_run_at = Some(v);
@@ -181,6 +207,23 @@ impl ScheduleStore {
         Ok(Some(s))
     }
 
+    /// Record that a schedule just ran, and advance `next_run_at` past now.
+    pub async fn record_run(
+        &self,
+        id: &str,
+        task_id: Option<String>,
+    ) -> Result<Option<Schedule>, ServerError> {
+        self.update(
+            id,
+            UpdateScheduleInput {
+                last_run_at: Some(Utc::now()),
+                last_task_id: task_id,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
     pub async fn delete(&self, id: &str) -> Result<bool, ServerError> {
         let id = id.to_string();
         self.db
@@ -213,3 +256,103 @@ fn row_to_schedule(row: &rusqlite::Row<'_>) -> Schedule {
         updated_at: to_dt(row.get(12).ok()).unwrap_or_else(Utc::now),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    use crate::store::WorkspaceStore;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn compute_next_run_handles_common_expressions() {
+        // 2026-03-04 is a Wednesday.
+        let now = at(2026, 3, 4, 10, 7);
+        assert_eq!(
+            ScheduleStore::compute_next_run("*/5 * * * *", now).unwrap(),
+            at(2026, 3, 4, 10, 10)
+        );
+        assert_eq!(
+            ScheduleStore::compute_next_run("0 9 * * 1", now).unwrap(),
+            at(2026, 3, 9, 9, 0)
+        );
+        // Strictly after: a run due exactly now is not returned again.
+        assert_eq!(
+            ScheduleStore::compute_next_run("*/5 * * * *", at(2026, 3, 4, 10, 10)).unwrap(),
+            at(2026, 3, 4, 10, 15)
+        );
+    }
+
+    #[test]
+    fn compute_next_run_rejects_invalid_expressions() {
+        for expr in ["", "not a cron", "61 * * * *", "0 0 9 * * 1"] {
+            let err = ScheduleStore::compute_next_run(expr, Utc::now()).unwrap_err();
+            assert!(matches!(err, ServerError::BadRequest(_)), "{expr}: {err:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn next_run_at_follows_create_update_and_runs() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let store = ScheduleStore::new(db);
+        let input = |cron_expr: &str| CreateScheduleInput {
+            name: "Nightly".to_string(),
+            cron_expr: cron_expr.to_string(),
+            task_prompt: "Run the suite".to_string(),
+            agent_id: "agent-1".to_string(),
+            workspace_id: "default".to_string(),
+            enabled: true,
+            next_run_at: None,
+            prompt_template: None,
+        };
+
+        assert!(store.create(input("every day")).await.is_err());
+
+        let created = store.create(input("*/5 * * * *")).await.unwrap();
+        let next = created.next_run_at.expect("next run should be computed");
+        assert!(next > created.created_at);
+        assert_eq!(next.timestamp() % 300, 0);
+
+        let updated = store
+            .update(
+                &created.id,
+                UpdateScheduleInput {
+                    cron_expr: Some("0 9 * * 1".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        let next = updated.next_run_at.unwrap();
+        assert_eq!(next.format("%a %H:%M").to_string(), "Mon 09:00");
+
+        let err = store
+            .update(
+                &created.id,
+                UpdateScheduleInput {
+                    cron_expr: Some("0 25 * * *".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServerError::BadRequest(_)));
+
+        let ran = store
+            .record_run(&created.id, Some("task-1".to_string()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ran.last_task_id.as_deref(), Some("task-1"));
+        assert!(ran.next_run_at.unwrap() > ran.last_run_at.unwrap());
+    }
+}
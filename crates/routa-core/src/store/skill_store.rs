@@ -0,0 +1,221 @@
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::db::Database;
+use crate::error::ServerError;
+use crate::models::skill::SkillRecord;
+
+const SKILL_COLUMNS: &str =
+    "id, name, description, source, catalog_type, installs, created_at, updated_at, uses";
+
+pub struct SkillStore {
+    db: Database,
+}
+
+impl SkillStore {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Upsert `skill` and increment its install counter in one statement.
+    /// Returns the new count.
+    pub async fn record_install(&self, skill: &SkillRecord) -> Result<i64, ServerError> {
+        let skill = skill.clone();
+        self.db
+            .with_conn_async(move |conn| {
+                upsert_in(conn, &skill, 1)?;
+                conn.query_row(
+                    "SELECT installs FROM skills WHERE id = ?1",
+                    rusqlite::params![skill.id],
+                    |row| row.get(0),
+                )
+            })
+            .await
+    }
+
+    /// Link `skill` to a workspace. The counter only increments when the
+    /// link is new, so reinstalling into the same workspace is not counted.
+    /// Returns whether the link was created.
+    pub async fn install_for_workspace(
+        &self,
+        workspace_id: &str,
+        skill: &SkillRecord,
+    ) -> Result<bool, ServerError> {
+        let workspace_id = workspace_id.to_string();
+        let skill = skill.clone();
+        self.db
+            .transaction(move |conn| {
+                upsert_in(conn, &skill, 0)?;
+                let linked = conn.execute(
+                    "INSERT OR IGNORE INTO workspace_skills (workspace_id, skill_id, installed_at)
+                     VALUES (?1, ?2, ?3)",
+                    rusqlite::params![workspace_id, skill.id, Utc::now().timestamp_millis()],
+                )? == 1;
+                if linked {
+                    conn.execute(
+                        "UPDATE skills SET installs = installs + 1 WHERE id = ?1",
+                        rusqlite::params![skill.id],
+                    )?;
+                }
+                Ok(linked)
+            })
+            .await
+    }
+
+    /// Increment the use counter for `skill`, creating its row if it was
+    /// only discovered on disk. Returns the new count.
+    pub async fn record_use(&self, skill: &SkillRecord) -> Result<i64, ServerError> {
+        let skill = skill.clone();
+        self.db
+            .with_conn_async(move |conn| {
+                let now = Utc::now().timestamp_millis();
+                conn.query_row(
+                    "INSERT INTO skills (id, name, description, source, catalog_type, uses, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6, ?6)
+                     ON CONFLICT(id) DO UPDATE SET uses = skills.uses + 1
+                     RETURNING uses",
+                    rusqlite::params![
+                        skill.id,
+                        skill.name,
+                        skill.description,
+                        skill.source,
+                        skill.catalog_type,
+                        now,
+                    ],
+                    |row| row.get(0),
+                )
+            })
+            .await
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<SkillRecord>, ServerError> {
+        let id = id.to_string();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.query_row(
+                    &format!("SELECT {SKILL_COLUMNS} FROM skills WHERE id = ?1"),
+                    rusqlite::params![id],
+                    row_to_skill,
+                )
+                .optional()
+            })
+            .await
+    }
+
+    /// Skills ordered by popularity (`installs` desc, then name).
+    pub async fn list_by_installs(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<Vec<SkillRecord>, ServerError> {
+        let limit = limit.map(|l| l as i64).unwrap_or(-1);
+        self.db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {SKILL_COLUMNS} FROM skills ORDER BY installs DESC, name ASC LIMIT ?1"
+                ))?;
+                let rows = stmt
+                    .query_map(rusqlite::params![limit], row_to_skill)?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+    }
+}
+
+fn upsert_in(
+    conn: &Connection,
+    skill: &SkillRecord,
+    increment: i64,
+) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT INTO skills (id, name, description, source, catalog_type, installs, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT(id) DO UPDATE SET
+           source = excluded.source,
+           catalog_type = excluded.catalog_type,
+           description = CASE WHEN excluded.description = '' THEN skills.description ELSE excluded.description END,
+           installs = skills.installs + ?6,
+           updated_at = excluded.updated_at",
+        rusqlite::params![
+            skill.id,
+            skill.name,
+            skill.description,
+            skill.source,
+            skill.catalog_type,
+            increment,
+            skill.created_at.timestamp_millis(),
+            Utc::now().timestamp_millis(),
+        ],
+    )?;
+    Ok(())
+}
+
+fn row_to_skill(row: &rusqlite::Row<'_>) -> Result<SkillRecord, rusqlite::Error> {
+    let created_at: i64 = row.get(6)?;
+    let updated_at: i64 = row.get(7)?;
+    Ok(SkillRecord {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        source: row.get(3)?,
+        catalog_type: row.get(4)?,
+        installs: row.get(5)?,
+        uses: row.get(8)?,
+        created_at: chrono::DateTime::from_timestamp_millis(created_at).unwrap_or_else(Utc::now),
+        updated_at: chrono::DateTime::from_timestamp_millis(updated_at).unwrap_or_else(Utc::now),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::workspace::Workspace;
+    use crate::store::WorkspaceStore;
+
+    #[tokio::test]
+    async fn counts_installs_across_workspaces() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let workspace_store = WorkspaceStore::new(db.clone());
+        for id in ["ws-a", "ws-b"] {
+            workspace_store
+                .save(&Workspace::new(id.to_string(), id.to_string(), None))
+                .await
+                .expect("workspace should be created");
+        }
+        let store = SkillStore::new(db);
+        let skill = SkillRecord::new(
+            "code-review".to_string(),
+            "Review diffs".to_string(),
+            "acme/skills".to_string(),
+            "github".to_string(),
+        );
+
+        assert!(store.install_for_workspace("ws-a", &skill).await.unwrap());
+        assert!(store.install_for_workspace("ws-b", &skill).await.unwrap());
+        assert!(!store.install_for_workspace("ws-a", &skill).await.unwrap());
+
+        let stored = store
+            .get("code-review")
+            .await
+            .unwrap()
+            .expect("skill should exist");
+        assert_eq!(stored.installs, 2);
+        assert_eq!(stored.description, "Review diffs");
+
+        let other = SkillRecord::new(
+            "lint".to_string(),
+            String::new(),
+            "upload".to_string(),
+            "upload".to_string(),
+        );
+        assert_eq!(store.record_install(&other).await.unwrap(), 1);
+        let top = store.list_by_installs(Some(1)).await.unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].id, "code-review");
+
+        assert_eq!(store.record_use(&skill).await.unwrap(), 1);
+        assert_eq!(store.record_use(&skill).await.unwrap(), 2);
+        let used = store.get("code-review").await.unwrap().expect("skill");
+        assert_eq!((used.installs, used.uses), (2, 2));
+    }
+}
@@ -1,22 +1,68 @@
 use chrono::Utc;
-use rusqlite::OptionalExtension;
+use rusqlite::{Connection, OptionalExtension};
 use serde::de::DeserializeOwned;
 
-use crate::db::Database;
+use crate::db::{Database, DbBackend};
 use crate::error::ServerError;
 use crate::models::task::{
-    Task, TaskContextSearchSpec, TaskCreationSource, TaskLaneHandoff, TaskLaneSession,
-    TaskPriority, TaskStatus, VerificationVerdict,
+    CriterionStatus, Task, TaskAuditAction, TaskAuditEntry, TaskContextSearchSpec,
+    TaskCreationSource, TaskLaneHandoff, TaskLaneSession, TaskPriority, TaskStatus,
+    VerificationVerdict,
 };
+#[cfg(feature = "postgres")]
+use crate::store::postgres;
+use crate::store::TimeRange;
+
+/// A task matched by [`TaskStore::search`].
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskSearchHit {
+    pub task: Task,
+    /// Text around the first match, from the objective when it matches there.
+    pub snippet: String,
+    /// Higher is better: title occurrences count double objective ones.
+    pub rank: usize,
+}
+
+/// One agent's share of a workspace's tasks, from [`TaskStore::workload`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssigneeWorkload {
+    pub agent_id: String,
+    /// Task count per status (e.g. `"IN_PROGRESS"`); absent statuses are zero.
+    pub counts: std::collections::BTreeMap<String, usize>,
+    pub total: usize,
+    /// The most recently updated `IN_PROGRESS` task, if any.
+    pub current_task: Option<WorkloadTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadTask {
+    pub id: String,
+    pub title: String,
+}
+
+/// Characters of context kept on each side of a match in a snippet.
+const SNIPPET_CONTEXT: usize = 40;
 
 #[derive(Clone)]
 pub struct TaskStore {
-    db: Database,
+    backend: DbBackend,
 }
 
 impl TaskStore {
     pub fn new(db: Database) -> Self {
-        Self { db }
+        Self::with_backend(db.into())
+    }
+
+    pub fn with_backend(backend: DbBackend) -> Self {
+        Self { backend }
+    }
+
+    /// The underlying SQLite database, for writes that span several stores.
+    pub(crate) fn db(&self) -> Result<&Database, ServerError> {
+        self.backend.sqlite()
     }
 
     pub async fn save(&self, task: &Task) -> Result<(), ServerError> {
@@ -33,178 +79,251 @@ impl TaskStore {
             updated_at = %t.updated_at,
             "task_store.save"
         );
-        self.db
-            .with_conn_async(move |conn| {
-                conn.execute(
-                    "INSERT INTO tasks (id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
-                                         assigned_to, status, board_id, column_id, position, priority, labels, assignee,
-                                         assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
-                                         trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
-                                         github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id,
-                                         creation_source, session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
-                                         verification_report, codebase_ids, context_search_spec, worktree_id, version, created_at, updated_at)
-                                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
-                                         ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36,
-                                         ?37, ?38, ?39, ?40, ?41, ?42, 1, ?43, ?44)
-                     ON CONFLICT(id) DO UPDATE SET
-                       title = excluded.title,
-                       objective = excluded.objective,
-                       comment = excluded.comment,
-                       scope = excluded.scope,
-                       acceptance_criteria = excluded.acceptance_criteria,
-                       verification_commands = excluded.verification_commands,
-                       test_cases = excluded.test_cases,
-                       assigned_to = excluded.assigned_to,
-                       status = excluded.status,
-                                             board_id = excluded.board_id,
-                                             column_id = excluded.column_id,
-                                             position = excluded.position,
-                                             priority = excluded.priority,
-                                             labels = excluded.labels,
-                                             assignee = excluded.assignee,
-                                             assigned_provider = excluded.assigned_provider,
-                                             assigned_role = excluded.assigned_role,
-                                             assigned_specialist_id = excluded.assigned_specialist_id,
-                                             assigned_specialist_name = excluded.assigned_specialist_name,
-                                             trigger_session_id = excluded.trigger_session_id,
-                                             github_id = excluded.github_id,
-                                             github_number = excluded.github_number,
-                                             github_url = excluded.github_url,
-                                             github_repo = excluded.github_repo,
-                                             github_state = excluded.github_state,
-                                             github_synced_at = excluded.github_synced_at,
-                                             last_sync_error = excluded.last_sync_error,
-                       dependencies = excluded.dependencies,
-                       parallel_group = excluded.parallel_group,
-                                             workspace_id = excluded.workspace_id,
-                       session_id = excluded.session_id,
-                       creation_source = excluded.creation_source,
-                       session_ids = excluded.session_ids,
-                       lane_sessions = excluded.lane_sessions,
-                       lane_handoffs = excluded.lane_handoffs,
-                       completion_summary = excluded.completion_summary,
-                       verification_verdict = excluded.verification_verdict,
-                       verification_report = excluded.verification_report,
-                       codebase_ids = excluded.codebase_ids,
-                       context_search_spec = excluded.context_search_spec,
-                       worktree_id = excluded.worktree_id,
-                       updated_at = excluded.updated_at",
-                    rusqlite::params![
-                        t.id,
-                        t.title,
-                        t.objective,
-                        t.comment,
-                        t.scope,
-                        t.acceptance_criteria.map(|v| serde_json::to_string(&v).unwrap_or_default()),
-                        t.verification_commands.map(|v| serde_json::to_string(&v).unwrap_or_default()),
-                        t.test_cases.map(|v| serde_json::to_string(&v).unwrap_or_default()),
-                        t.assigned_to,
-                        t.status.as_str(),
-                        t.board_id,
-                        t.column_id,
-                        t.position,
-                        t.priority.as_ref().map(|v| v.as_str()),
-                        serde_json::to_string(&t.labels).unwrap_or_default(),
-                        t.assignee,
-                        t.assigned_provider,
-                        t.assigned_role,
-                        t.assigned_specialist_id,
-                        t.assigned_specialist_name,
-                        t.trigger_session_id,
-                        t.github_id,
-                        t.github_number,
-                        t.github_url,
-                        t.github_repo,
-                        t.github_state,
-                        t.github_synced_at.map(|v| v.timestamp_millis()),
-                        t.last_sync_error,
-                        serde_json::to_string(&t.dependencies).unwrap_or_default(),
-                        t.parallel_group,
-                        t.workspace_id,
-                        t.session_id,
-                        t.creation_source.as_ref().map(|value| value.as_str()),
-                        serde_json::to_string(&t.session_ids).unwrap_or_default(),
-                        serde_json::to_string(&t.lane_sessions).unwrap_or_default(),
-                        serde_json::to_string(&t.lane_handoffs).unwrap_or_default(),
-                        t.completion_summary,
-                        t.verification_verdict.as_ref().map(|v| v.as_str()),
-                        t.verification_report,
-                        serde_json::to_string(&t.codebase_ids).unwrap_or_default(),
-                        t.context_search_spec
-                            .as_ref()
-                            .map(|value| serde_json::to_string(value).unwrap_or_default()),
-                        t.worktree_id,
-                        t.created_at.timestamp_millis(),
-                        t.updated_at.timestamp_millis(),
-                    ],
-                )?;
-                Ok(())
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| Self::save_in(conn, &t))
+                    .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::task::save(pg, task).await,
+        }
+    }
+
+    /// [`save`](Self::save) on an existing connection, e.g. inside a [`Database::transaction`].
+    pub fn save_in(conn: &Connection, t: &Task) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO tasks (id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+                                 assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+                                 assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+                                 trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+                                 github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id,
+                                 creation_source, session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+                                 verification_report, codebase_ids, context_search_spec, worktree_id, version, created_at, updated_at,
+                                 acceptance_criteria_status)
+                                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                                 ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36,
+                                 ?37, ?38, ?39, ?40, ?41, ?42, 1, ?43, ?44, ?45)
+             ON CONFLICT(id) DO UPDATE SET
+               title = excluded.title,
+               objective = excluded.objective,
+               comment = excluded.comment,
+               scope = excluded.scope,
+               acceptance_criteria = excluded.acceptance_criteria,
+               verification_commands = excluded.verification_commands,
+               test_cases = excluded.test_cases,
+               assigned_to = excluded.assigned_to,
+               status = excluded.status,
+                                     board_id = excluded.board_id,
+                                     column_id = excluded.column_id,
+                                     position = excluded.position,
+                                     priority = excluded.priority,
+                                     labels = excluded.labels,
+                                     assignee = excluded.assignee,
+                                     assigned_provider = excluded.assigned_provider,
+                                     assigned_role = excluded.assigned_role,
+                                     assigned_specialist_id = excluded.assigned_specialist_id,
+                                     assigned_specialist_name = excluded.assigned_specialist_name,
+                                     trigger_session_id = excluded.trigger_session_id,
+                                     github_id = excluded.github_id,
+                                     github_number = excluded.github_number,
+                                     github_url = excluded.github_url,
+                                     github_repo = excluded.github_repo,
+                                     github_state = excluded.github_state,
+                                     github_synced_at = excluded.github_synced_at,
+                                     last_sync_error = excluded.last_sync_error,
+               dependencies = excluded.dependencies,
+               parallel_group = excluded.parallel_group,
+                                     workspace_id = excluded.workspace_id,
+               session_id = excluded.session_id,
+               creation_source = excluded.creation_source,
+               session_ids = excluded.session_ids,
+               lane_sessions = excluded.lane_sessions,
+               lane_handoffs = excluded.lane_handoffs,
+               completion_summary = excluded.completion_summary,
+               verification_verdict = excluded.verification_verdict,
+               verification_report = excluded.verification_report,
+               codebase_ids = excluded.codebase_ids,
+               context_search_spec = excluded.context_search_spec,
+               worktree_id = excluded.worktree_id,
+               updated_at = excluded.updated_at,
+               acceptance_criteria_status = excluded.acceptance_criteria_status",
+            rusqlite::params![
+                t.id,
+                t.title,
+                t.objective,
+                t.comment,
+                t.scope,
+                t.acceptance_criteria.as_ref().map(|v| serde_json::to_string(&v).unwrap_or_default()),
+                t.verification_commands.as_ref().map(|v| serde_json::to_string(&v).unwrap_or_default()),
+                t.test_cases.as_ref().map(|v| serde_json::to_string(&v).unwrap_or_default()),
+                t.assigned_to,
+                t.status.as_str(),
+                t.board_id,
+                t.column_id,
+                t.position,
+                t.priority.as_ref().map(|v| v.as_str()),
+                serde_json::to_string(&t.labels).unwrap_or_default(),
+                t.assignee,
+                t.assigned_provider,
+                t.assigned_role,
+                t.assigned_specialist_id,
+                t.assigned_specialist_name,
+                t.trigger_session_id,
+                t.github_id,
+                t.github_number,
+                t.github_url,
+                t.github_repo,
+                t.github_state,
+                t.github_synced_at.map(|v| v.timestamp_millis()),
+                t.last_sync_error,
+                serde_json::to_string(&t.dependencies).unwrap_or_default(),
+                t.parallel_group,
+                t.workspace_id,
+                t.session_id,
+                t.creation_source.as_ref().map(|value| value.as_str()),
+                serde_json::to_string(&t.session_ids).unwrap_or_default(),
+                serde_json::to_string(&t.lane_sessions).unwrap_or_default(),
+                serde_json::to_string(&t.lane_handoffs).unwrap_or_default(),
+                t.completion_summary,
+                t.verification_verdict.as_ref().map(|v| v.as_str()),
+                t.verification_report,
+                serde_json::to_string(&t.codebase_ids).unwrap_or_default(),
+                t.context_search_spec
+                    .as_ref()
+                    .map(|value| serde_json::to_string(value).unwrap_or_default()),
+                t.worktree_id,
+                t.created_at.timestamp_millis(),
+                t.updated_at.timestamp_millis(),
+                serde_json::to_string(&t.acceptance_criteria_status).unwrap_or_default(),
+            ],
+        )?;
+        Ok(())
     }
 
     pub async fn get(&self, task_id: &str) -> Result<Option<Task>, ServerError> {
         let id = task_id.to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
-                     assigned_to, status, board_id, column_id, position, priority, labels, assignee,
-                     assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
-                     trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
-                     github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
-                     session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
-                     verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at
-                     FROM tasks WHERE id = ?1",
-                )?;
-                stmt.query_row(rusqlite::params![id], |row| Ok(row_to_task(row)))
-                    .optional()
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+                         assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+                         assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+                         trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+                         github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
+                         session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+                         verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at,
+                         acceptance_criteria_status
+                         FROM tasks WHERE id = ?1",
+                    )?;
+                    stmt.query_row(rusqlite::params![id], |row| Ok(row_to_task(row)))
+                        .optional()
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::task::get(pg, task_id).await,
+        }
     }
 
     pub async fn list_by_workspace(&self, workspace_id: &str) -> Result<Vec<Task>, ServerError> {
         let ws_id = workspace_id.to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
-                     assigned_to, status, board_id, column_id, position, priority, labels, assignee,
-                     assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
-                     trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
-                     github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
-                     session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
-                     verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at
-                     FROM tasks WHERE workspace_id = ?1 ORDER BY created_at DESC",
-                )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![ws_id], |row| Ok(row_to_task(row)))?
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(rows)
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+                         assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+                         assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+                         trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+                         github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
+                         session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+                         verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at,
+                         acceptance_criteria_status
+                         FROM tasks WHERE workspace_id = ?1 ORDER BY created_at DESC",
+                    )?;
+                    let rows = stmt
+                        .query_map(rusqlite::params![ws_id], |row| Ok(row_to_task(row)))?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => {
+                postgres::task::list_where(pg, "workspace_id = $1", &[&workspace_id]).await
+            }
+        }
+    }
+
+    /// Tasks in `workspace_id` whose timestamps fall inside `range`, most
+    /// recently updated first.
+    pub async fn list_in_range(
+        &self,
+        workspace_id: &str,
+        range: &TimeRange,
+    ) -> Result<Vec<Task>, ServerError> {
+        let mut values: Vec<rusqlite::types::Value> = vec![workspace_id.to_string().into()];
+        let mut clauses = vec!["workspace_id = ?1".to_string()];
+        range.push_clauses(&mut clauses, &mut values);
+        let sql = format!(
+            "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+             assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+             assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+             trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+             github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
+             session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+             verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at,
+             acceptance_criteria_status
+             FROM tasks WHERE {} ORDER BY updated_at DESC",
+            clauses.join(" AND ")
+        );
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(&sql)?;
+                    let rows = stmt
+                        .query_map(rusqlite::params_from_iter(values.iter()), |row| {
+                            Ok(row_to_task(row))
+                        })?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::task::list_in_range(pg, workspace_id, range).await,
+        }
     }
 
     pub async fn list_by_session(&self, session_id: &str) -> Result<Vec<Task>, ServerError> {
         let sid = session_id.to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
-                     assigned_to, status, board_id, column_id, position, priority, labels, assignee,
-                     assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
-                     trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
-                     github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
-                     session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
-                     verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at
-                     FROM tasks WHERE session_id = ?1 ORDER BY created_at DESC",
-                )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![sid], |row| Ok(row_to_task(row)))?
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(rows)
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+                         assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+                         assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+                         trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+                         github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
+                         session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+                         verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at,
+                         acceptance_criteria_status
+                         FROM tasks WHERE session_id = ?1 ORDER BY created_at DESC",
+                    )?;
+                    let rows = stmt
+                        .query_map(rusqlite::params![sid], |row| Ok(row_to_task(row)))?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => {
+                postgres::task::list_where(pg, "session_id = $1", &[&session_id]).await
+            }
+        }
     }
 
     pub async fn list_by_status(
@@ -214,55 +333,225 @@ impl TaskStore {
     ) -> Result<Vec<Task>, ServerError> {
         let ws_id = workspace_id.to_string();
         let status_str = status.as_str().to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
-                     assigned_to, status, board_id, column_id, position, priority, labels, assignee,
-                     assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
-                     trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
-                     github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
-                     session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
-                     verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at
-                     FROM tasks WHERE workspace_id = ?1 AND status = ?2 ORDER BY created_at DESC",
-                )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![ws_id, status_str], |row| {
-                        Ok(row_to_task(row))
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(rows)
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+                         assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+                         assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+                         trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+                         github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
+                         session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+                         verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at,
+                         acceptance_criteria_status
+                         FROM tasks WHERE workspace_id = ?1 AND status = ?2 ORDER BY created_at DESC",
+                    )?;
+                    let rows = stmt
+                        .query_map(rusqlite::params![ws_id, status_str], |row| {
+                            Ok(row_to_task(row))
+                        })?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => {
+                postgres::task::list_where(
+                    pg,
+                    "workspace_id = $1 AND status = $2",
+                    &[&workspace_id, &status.as_str()],
+                )
+                .await
+            }
+        }
     }
 
     pub async fn list_by_assignee(&self, agent_id: &str) -> Result<Vec<Task>, ServerError> {
         let aid = agent_id.to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
-                     assigned_to, status, board_id, column_id, position, priority, labels, assignee,
-                     assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
-                     trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
-                     github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
-                     session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
-                     verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at
-                     FROM tasks WHERE assigned_to = ?1 ORDER BY created_at DESC",
-                )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![aid], |row| Ok(row_to_task(row)))?
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(rows)
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+                         assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+                         assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+                         trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+                         github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
+                         session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+                         verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at,
+                         acceptance_criteria_status
+                         FROM tasks WHERE assigned_to = ?1 ORDER BY created_at DESC",
+                    )?;
+                    let rows = stmt
+                        .query_map(rusqlite::params![aid], |row| Ok(row_to_task(row)))?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => {
+                postgres::task::list_where(pg, "assigned_to = $1", &[&agent_id]).await
+            }
+        }
+    }
+
+    /// Task counts by status for every agent with tasks assigned in
+    /// `workspace_id`, ordered by agent ID.
+    pub async fn workload(&self, workspace_id: &str) -> Result<Vec<AssigneeWorkload>, ServerError> {
+        let ws_id = workspace_id.to_string();
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut workloads: std::collections::BTreeMap<String, AssigneeWorkload> =
+                        std::collections::BTreeMap::new();
+
+                    let mut stmt = conn.prepare(
+                        "SELECT assigned_to, status, COUNT(*) FROM tasks
+                         WHERE workspace_id = ?1 AND assigned_to IS NOT NULL AND assigned_to != ''
+                         GROUP BY assigned_to, status",
+                    )?;
+                    let counts = stmt.query_map(rusqlite::params![ws_id], |row| {
+                        Ok((
+                            row.get::<_, String>(0)?,
+                            row.get::<_, String>(1)?,
+                            row.get::<_, i64>(2)?,
+                        ))
+                    })?;
+                    for row in counts {
+                        let (agent_id, status, count) = row?;
+                        let workload =
+                            workloads
+                                .entry(agent_id.clone())
+                                .or_insert_with(|| AssigneeWorkload {
+                                    agent_id,
+                                    counts: std::collections::BTreeMap::new(),
+                                    total: 0,
+                                    current_task: None,
+                                });
+                        workload.counts.insert(status, count as usize);
+                        workload.total += count as usize;
+                    }
+
+                    let mut stmt = conn.prepare(
+                        "SELECT assigned_to, id, title FROM tasks
+                         WHERE workspace_id = ?1 AND assigned_to IS NOT NULL AND status = ?2
+                         ORDER BY updated_at DESC",
+                    )?;
+                    let in_progress = stmt.query_map(
+                        rusqlite::params![ws_id, TaskStatus::InProgress.as_str()],
+                        |row| {
+                            Ok((
+                                row.get::<_, String>(0)?,
+                                row.get::<_, String>(1)?,
+                                row.get::<_, String>(2)?,
+                            ))
+                        },
+                    )?;
+                    for row in in_progress {
+                        let (agent_id, id, title) = row?;
+                        if let Some(workload) = workloads.get_mut(&agent_id) {
+                            workload
+                                .current_task
+                                .get_or_insert(WorkloadTask { id, title });
+                        }
+                    }
+
+                    Ok(workloads.into_values().collect())
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::task::workload(pg, workspace_id).await,
+        }
+    }
+
+    /// Case-insensitive substring search over `title` and `objective`, best
+    /// matches first (ties: most recently updated).
+    pub async fn search(
+        &self,
+        workspace_id: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<TaskSearchHit>, ServerError> {
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ws_id = workspace_id.to_string();
+        let pattern = format!(
+            "%{}%",
+            query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        let tasks = match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, objective, comment, scope, acceptance_criteria, verification_commands, test_cases,
+                         assigned_to, status, board_id, column_id, position, priority, labels, assignee,
+                         assigned_provider, assigned_role, assigned_specialist_id, assigned_specialist_name,
+                         trigger_session_id, github_id, github_number, github_url, github_repo, github_state,
+                         github_synced_at, last_sync_error, dependencies, parallel_group, workspace_id, session_id, creation_source,
+                         session_ids, lane_sessions, lane_handoffs, completion_summary, verification_verdict,
+                         verification_report, codebase_ids, context_search_spec, worktree_id, created_at, updated_at,
+                         acceptance_criteria_status
+                         FROM tasks
+                         WHERE workspace_id = ?1
+                           AND (title LIKE ?2 ESCAPE '\\' OR objective LIKE ?2 ESCAPE '\\')
+                         ORDER BY updated_at DESC",
+                    )?;
+                    let rows = stmt
+                        .query_map(rusqlite::params![ws_id, pattern], |row| Ok(row_to_task(row)))?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => {
+                postgres::task::search_candidates(pg, workspace_id, &pattern).await
+            }
+        }?;
+
+        let needle = query.to_lowercase();
+        let mut hits: Vec<TaskSearchHit> = tasks
+            .into_iter()
+            .filter_map(|task| {
+                let title = task.title.to_lowercase();
+                let objective = task.objective.to_lowercase();
+                // LIKE folds ASCII case only; re-check with full case folding.
+                let rank = title.matches(&needle).count() * 2 + objective.matches(&needle).count();
+                if rank == 0 {
+                    return None;
+                }
+                let snippet = if objective.contains(&needle) {
+                    snippet_around(&task.objective, &objective, &needle)
+                } else {
+                    snippet_around(&task.title, &title, &needle)
+                };
+                Some(TaskSearchHit {
+                    task,
+                    snippet,
+                    rank,
+                })
             })
-            .await
+            .collect();
+        // Stable sort keeps the recency order among equal ranks.
+        hits.sort_by_key(|hit| std::cmp::Reverse(hit.rank));
+        hits.truncate(limit);
+        Ok(hits)
     }
 
     pub async fn find_ready_tasks(&self, workspace_id: &str) -> Result<Vec<Task>, ServerError> {
         let all_tasks = self.list_by_workspace(workspace_id).await?;
         let completed_ids: std::collections::HashSet<String> = all_tasks
             .iter()
-            .filter(|t| t.status == TaskStatus::Completed)
+            .filter(|t| t.satisfies_dependency())
             .map(|t| t.id.clone())
             .collect();
 
@@ -283,30 +572,134 @@ impl TaskStore {
         let id = task_id.to_string();
         let status_str = status.as_str().to_string();
         let now = Utc::now().timestamp_millis();
-        self.db
-            .with_conn_async(move |conn| {
-                conn.execute(
-                    "UPDATE tasks SET status = ?1, updated_at = ?2 WHERE id = ?3",
-                    rusqlite::params![status_str, now, id],
-                )?;
-                Ok(())
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    conn.execute(
+                        "UPDATE tasks SET status = ?1, updated_at = ?2 WHERE id = ?3",
+                        rusqlite::params![status_str, now, id],
+                    )?;
+                    Ok(())
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => {
+                postgres::task::update_status(pg, task_id, status.as_str(), now).await
+            }
+        }
     }
 
     pub async fn delete(&self, task_id: &str) -> Result<(), ServerError> {
         let id = task_id.to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                conn.execute("DELETE FROM tasks WHERE id = ?1", rusqlite::params![id])?;
-                Ok(())
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    conn.execute("DELETE FROM tasks WHERE id = ?1", rusqlite::params![id])?;
+                    Ok(())
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::task::delete(pg, task_id).await,
+        }
+    }
+
+    pub async fn append_audit_entry(&self, entry: &TaskAuditEntry) -> Result<(), ServerError> {
+        let e = entry.clone();
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    conn.execute(
+                        "INSERT INTO task_audit (id, task_id, action, actor, reason, previous_status, created_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        rusqlite::params![
+                            e.id,
+                            e.task_id,
+                            e.action.as_str(),
+                            e.actor,
+                            e.reason,
+                            e.previous_status.as_str(),
+                            e.created_at.timestamp_millis(),
+                        ],
+                    )?;
+                    Ok(())
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::task::append_audit_entry(pg, entry).await,
+        }
+    }
+
+    /// Audit history for a task, oldest first.
+    pub async fn list_audit_entries(
+        &self,
+        task_id: &str,
+    ) -> Result<Vec<TaskAuditEntry>, ServerError> {
+        let tid = task_id.to_string();
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, task_id, action, actor, reason, previous_status, created_at
+                         FROM task_audit WHERE task_id = ?1 ORDER BY created_at ASC",
+                    )?;
+                    let rows = stmt
+                        .query_map(rusqlite::params![tid], |row| {
+                            let created_ms: i64 = row.get(6).unwrap_or(0);
+                            Ok(TaskAuditEntry {
+                                id: row.get(0)?,
+                                task_id: row.get(1)?,
+                                action: TaskAuditAction::from_str(
+                                    &row.get::<_, String>(2).unwrap_or_default(),
+                                )
+                                .unwrap_or(TaskAuditAction::Reopen),
+                                actor: row.get(3)?,
+                                reason: row.get(4).unwrap_or(None),
+                                previous_status: TaskStatus::from_str(
+                                    &row.get::<_, String>(5).unwrap_or_default(),
+                                )
+                                .unwrap_or(TaskStatus::Pending),
+                                created_at: chrono::DateTime::from_timestamp_millis(created_ms)
+                                    .unwrap_or_else(Utc::now),
+                            })
+                        })?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::task::list_audit_entries(pg, task_id).await,
+        }
     }
 }
 
 use rusqlite::Row;
 
+/// Up to [`SNIPPET_CONTEXT`] characters either side of the first `needle` in
+/// `lowered` (the lowercased `text`), with ellipses where text was cut.
+fn snippet_around(text: &str, lowered: &str, needle: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    // Lowercasing can change byte lengths, so locate the match by char index.
+    let start = lowered
+        .find(needle)
+        .map(|byte| lowered[..byte].chars().count())
+        .unwrap_or(0)
+        .min(chars.len());
+    let from = start.saturating_sub(SNIPPET_CONTEXT);
+    let to = (start + needle.chars().count() + SNIPPET_CONTEXT).min(chars.len());
+    let mut snippet: String = chars[from..to].iter().collect();
+    if from > 0 {
+        snippet.insert(0, '…');
+    }
+    if to < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
 fn row_to_task(row: &Row<'_>) -> Task {
     let created_ms: i64 = row.get(42).unwrap_or(0);
     let updated_ms: i64 = row.get(43).unwrap_or(0);
@@ -336,6 +729,7 @@ fn row_to_task(row: &Row<'_>) -> Task {
     let session_ids: Vec<String> = parse_json_column(row, 33);
     let lane_sessions: Vec<TaskLaneSession> = parse_json_column(row, 34);
     let lane_handoffs: Vec<TaskLaneHandoff> = parse_json_column(row, 35);
+    let acceptance_criteria_status: Vec<CriterionStatus> = parse_json_column(row, 44);
 
     let session_id = row.get(31).unwrap_or(None);
     let creation_source = row
@@ -351,6 +745,7 @@ fn row_to_task(row: &Row<'_>) -> Task {
         comment: row.get(3).unwrap_or(None),
         scope: row.get(4).unwrap_or(None),
         acceptance_criteria,
+        acceptance_criteria_status,
         verification_commands,
         test_cases,
         assigned_to: row.get(8).unwrap_or(None),
@@ -516,4 +911,111 @@ mod tests {
         assert_eq!(loaded.lane_sessions, task.lane_sessions);
         assert_eq!(loaded.lane_handoffs, task.lane_handoffs);
     }
+
+    #[tokio::test]
+    async fn search_matches_terms_only_in_the_objective() {
+        let store = setup().await;
+        let task = |id: &str, title: &str, objective: &str| {
+            Task::new(
+                id.to_string(),
+                title.to_string(),
+                objective.to_string(),
+                "default".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+        store
+            .save(&task(
+                "t-objective",
+                "Fix login",
+                "The session cookie expires after five minutes because of a Clock-Skew bug in the refresh handler",
+            ))
+            .await
+            .unwrap();
+        store
+            .save(&task("t-title", "Clock-skew dashboard", "Chart drift"))
+            .await
+            .unwrap();
+        store
+            .save(&task("t-other", "Unrelated", "Nothing to see"))
+            .await
+            .unwrap();
+
+        let hits = store.search("default", "clock-skew", 10).await.unwrap();
+        let ids: Vec<&str> = hits.iter().map(|hit| hit.task.id.as_str()).collect();
+        assert_eq!(ids, ["t-title", "t-objective"]);
+        let objective_hit = &hits[1];
+        assert!(objective_hit.rank < hits[0].rank);
+        assert!(objective_hit.snippet.contains("Clock-Skew bug"));
+        assert!(objective_hit.snippet.starts_with('…'));
+
+        assert!(store.search("default", "50%", 10).await.unwrap().is_empty());
+        assert!(store
+            .search("other-ws", "clock", 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn workload_counts_tasks_per_agent_and_status() {
+        let store = setup().await;
+        let seed = [
+            ("t1", "agent-a", TaskStatus::InProgress),
+            ("t2", "agent-a", TaskStatus::Pending),
+            ("t3", "agent-a", TaskStatus::Pending),
+            ("t4", "agent-a", TaskStatus::Completed),
+            ("t5", "agent-b", TaskStatus::Completed),
+            ("t6", "", TaskStatus::Pending),
+        ];
+        for (id, agent_id, status) in seed {
+            let mut task = Task::new(
+                id.to_string(),
+                format!("Task {id}"),
+                "Do it".to_string(),
+                "default".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            task.assigned_to = (!agent_id.is_empty()).then(|| agent_id.to_string());
+            task.status = status;
+            store.save(&task).await.unwrap();
+        }
+
+        let workload = store.workload("default").await.unwrap();
+        assert_eq!(workload.len(), 2);
+
+        let a = &workload[0];
+        assert_eq!(a.agent_id, "agent-a");
+        assert_eq!(a.total, 4);
+        assert_eq!(a.counts["PENDING"], 2);
+        assert_eq!(a.counts["IN_PROGRESS"], 1);
+        assert_eq!(a.counts["COMPLETED"], 1);
+        assert_eq!(
+            a.current_task,
+            Some(WorkloadTask {
+                id: "t1".to_string(),
+                title: "Task t1".to_string()
+            })
+        );
+
+        let b = &workload[1];
+        assert_eq!(b.agent_id, "agent-b");
+        assert_eq!(b.total, 1);
+        assert!(!b.counts.contains_key("PENDING"));
+        assert_eq!(b.current_task, None);
+
+        assert!(store.workload("other").await.unwrap().is_empty());
+    }
 }
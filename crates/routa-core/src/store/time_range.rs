@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Bounds on a row's `created_at`/`updated_at` columns, deserialized from
+/// RFC3339 `createdAfter`/`createdBefore`/`updatedAfter`/`updatedBefore`
+/// params. Bounds are exclusive; unset bounds match everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeRange {
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub updated_after: Option<DateTime<Utc>>,
+    pub updated_before: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    pub fn is_unbounded(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Each set bound as a SQL comparison on an epoch-millis column (e.g.
+    /// `"created_at >"`) and the bound in epoch millis.
+    pub(crate) fn bounds(&self) -> impl Iterator<Item = (&'static str, i64)> {
+        [
+            ("created_at >", self.created_after),
+            ("created_at <", self.created_before),
+            ("updated_at >", self.updated_after),
+            ("updated_at <", self.updated_before),
+        ]
+        .into_iter()
+        .filter_map(|(condition, bound)| Some((condition, bound?.timestamp_millis())))
+    }
+
+    /// Append a SQL condition per set bound, comparing the epoch-millis
+    /// columns against numbered placeholders that continue after `values`.
+    pub(crate) fn push_clauses(
+        &self,
+        clauses: &mut Vec<String>,
+        values: &mut Vec<rusqlite::types::Value>,
+    ) {
+        for (condition, bound) in self.bounds() {
+            values.push(bound.into());
+            clauses.push(format!("{condition} ?{}", values.len()));
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use chrono::Utc;
+use rusqlite::Row;
+
+use crate::db::Database;
+use crate::error::ServerError;
+use crate::models::tool_audit::{ToolAuditEntry, ToolAuditFilter};
+
+/// Rows returned by [`ToolAuditStore::list`] when the filter sets no limit.
+const DEFAULT_LIST_LIMIT: usize = 200;
+
+#[derive(Clone)]
+pub struct ToolAuditStore {
+    db: Database,
+}
+
+impl ToolAuditStore {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub async fn append(&self, entry: &ToolAuditEntry) -> Result<(), ServerError> {
+        let e = entry.clone();
+        self.db
+            .with_conn_async(move |conn| {
+                conn.execute(
+                    "INSERT INTO tool_audit (id, tool_name, args, agent_id, session_id, workspace_id, success, duration_ms, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    rusqlite::params![
+                        e.id,
+                        e.tool_name,
+                        e.args,
+                        e.agent_id,
+                        e.session_id,
+                        e.workspace_id,
+                        e.success as i64,
+                        e.duration_ms,
+                        e.created_at.timestamp_millis(),
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Record `entry` in the background, logging instead of failing so the
+    /// audit trail never holds up the tool call it describes.
+    pub fn append_detached(&self, entry: ToolAuditEntry) {
+        let store = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = store.append(&entry).await {
+                tracing::warn!(
+                    "[ToolAudit] Failed to record call to {}: {}",
+                    entry.tool_name,
+                    e
+                );
+            }
+        });
+    }
+
+    /// Audit rows matching `filter`, newest first.
+    pub async fn list(&self, filter: &ToolAuditFilter) -> Result<Vec<ToolAuditEntry>, ServerError> {
+        let mut clauses = Vec::new();
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+        let text_filters = [
+            ("tool_name", &filter.tool_name),
+            ("agent_id", &filter.agent_id),
+            ("session_id", &filter.session_id),
+            ("workspace_id", &filter.workspace_id),
+        ];
+        for (column, value) in text_filters {
+            if let Some(value) = value {
+                values.push(value.clone().into());
+                clauses.push(format!("{column} = ?{}", values.len()));
+            }
+        }
+        if let Some(success) = filter.success {
+            values.push((success as i64).into());
+            clauses.push(format!("success = ?{}", values.len()));
+        }
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT id, tool_name, args, agent_id, session_id, workspace_id, success, duration_ms, created_at
+             FROM tool_audit {where_clause} ORDER BY created_at DESC LIMIT {}",
+            filter.limit.unwrap_or(DEFAULT_LIST_LIMIT)
+        );
+
+        self.db
+            .with_conn_async(move |conn| {
+                let mut stmt = conn.prepare(&sql)?;
+                let rows = stmt
+                    .query_map(rusqlite::params_from_iter(values.iter()), |row| {
+                        Ok(row_to_entry(row))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+    }
+}
+
+fn row_to_entry(row: &Row<'_>) -> ToolAuditEntry {
+    let created_ms: i64 = row.get(8).unwrap_or(0);
+    ToolAuditEntry {
+        id: row.get(0).unwrap_or_default(),
+        tool_name: row.get(1).unwrap_or_default(),
+        args: row.get(2).unwrap_or_default(),
+        agent_id: row.get(3).unwrap_or(None),
+        session_id: row.get(4).unwrap_or(None),
+        workspace_id: row.get(5).unwrap_or(None),
+        success: row.get::<_, i64>(6).unwrap_or(0) != 0,
+        duration_ms: row.get(7).unwrap_or(0),
+        created_at: chrono::DateTime::from_timestamp_millis(created_ms).unwrap_or_else(Utc::now),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn list_filters_by_tool_and_outcome() {
+        let store = ToolAuditStore::new(Database::open_in_memory().expect("in-memory db"));
+        let calls = [
+            ("create_task", serde_json::json!({ "agentId": "a-1" }), true),
+            (
+                "create_task",
+                serde_json::json!({ "agentId": "a-2" }),
+                false,
+            ),
+            ("list_agents", serde_json::json!({ "agentId": "a-1" }), true),
+        ];
+        for (tool, args, success) in calls {
+            store
+                .append(&ToolAuditEntry::new(tool, &args, success, 3))
+                .await
+                .unwrap();
+        }
+
+        let failed = store
+            .list(&ToolAuditFilter {
+                tool_name: Some("create_task".to_string()),
+                success: Some(false),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].agent_id.as_deref(), Some("a-2"));
+
+        let by_agent = store
+            .list(&ToolAuditFilter {
+                agent_id: Some("a-1".to_string()),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(by_agent.len(), 1);
+    }
+}
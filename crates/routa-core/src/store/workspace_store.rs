@@ -1,73 +1,97 @@
 use chrono::Utc;
-use rusqlite::OptionalExtension;
+use rusqlite::{Connection, OptionalExtension};
 use std::collections::HashMap;
 
-use crate::db::Database;
+use crate::db::{Database, DbBackend};
 use crate::error::ServerError;
 use crate::models::workspace::{Workspace, WorkspaceStatus};
+#[cfg(feature = "postgres")]
+use crate::store::postgres;
 
 pub struct WorkspaceStore {
-    db: Database,
+    backend: DbBackend,
 }
 
 impl WorkspaceStore {
     pub fn new(db: Database) -> Self {
-        Self { db }
+        Self::with_backend(db.into())
+    }
+
+    pub fn with_backend(backend: DbBackend) -> Self {
+        Self { backend }
     }
 
     pub async fn save(&self, workspace: &Workspace) -> Result<(), ServerError> {
-        let ws = workspace.clone();
-        self.db
-            .with_conn_async(move |conn| {
-                conn.execute(
-                    "INSERT INTO workspaces (id, title, status, metadata, created_at, updated_at)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)
-                     ON CONFLICT(id) DO UPDATE SET
-                       title = excluded.title,
-                       status = excluded.status,
-                       metadata = excluded.metadata,
-                       updated_at = excluded.updated_at",
-                    rusqlite::params![
-                        ws.id,
-                        ws.title,
-                        ws.status.as_str(),
-                        serde_json::to_string(&ws.metadata).unwrap_or_default(),
-                        ws.created_at.timestamp_millis(),
-                        ws.updated_at.timestamp_millis(),
-                    ],
-                )?;
-                Ok(())
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                let ws = workspace.clone();
+                db.with_conn_async(move |conn| Self::save_in(conn, &ws))
+                    .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::workspace::save(pg, workspace).await,
+        }
+    }
+
+    /// [`save`](Self::save) on an existing connection, e.g. inside a [`Database::transaction`].
+    pub fn save_in(conn: &Connection, ws: &Workspace) -> Result<(), rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO workspaces (id, title, status, metadata, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+               title = excluded.title,
+               status = excluded.status,
+               metadata = excluded.metadata,
+               updated_at = excluded.updated_at",
+            rusqlite::params![
+                ws.id,
+                ws.title,
+                ws.status.as_str(),
+                serde_json::to_string(&ws.metadata).unwrap_or_default(),
+                ws.created_at.timestamp_millis(),
+                ws.updated_at.timestamp_millis(),
+            ],
+        )?;
+        Ok(())
     }
 
     pub async fn get(&self, id: &str) -> Result<Option<Workspace>, ServerError> {
-        let id = id.to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, status, metadata, created_at, updated_at
-                     FROM workspaces WHERE id = ?1",
-                )?;
-                stmt.query_row(rusqlite::params![id], |row| Ok(row_to_workspace(row)))
-                    .optional()
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                let id = id.to_string();
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, status, metadata, created_at, updated_at
+                         FROM workspaces WHERE id = ?1",
+                    )?;
+                    stmt.query_row(rusqlite::params![id], |row| Ok(row_to_workspace(row)))
+                        .optional()
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::workspace::get(pg, id).await,
+        }
     }
 
     pub async fn list(&self) -> Result<Vec<Workspace>, ServerError> {
-        self.db
-            .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, status, metadata, created_at, updated_at
-                     FROM workspaces ORDER BY created_at DESC",
-                )?;
-                let rows = stmt
-                    .query_map([], |row| Ok(row_to_workspace(row)))?
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(rows)
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, status, metadata, created_at, updated_at
+                         FROM workspaces ORDER BY created_at DESC",
+                    )?;
+                    let rows = stmt
+                        .query_map([], |row| Ok(row_to_workspace(row)))?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::workspace::list(pg, None).await,
+        }
     }
 
     pub async fn list_by_status(
@@ -75,63 +99,81 @@ impl WorkspaceStore {
         status: WorkspaceStatus,
     ) -> Result<Vec<Workspace>, ServerError> {
         let status_str = status.as_str().to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, status, metadata, created_at, updated_at
-                     FROM workspaces WHERE status = ?1 ORDER BY created_at DESC",
-                )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![status_str], |row| {
-                        Ok(row_to_workspace(row))
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(rows)
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                db.with_conn_async(move |conn| {
+                    let mut stmt = conn.prepare(
+                        "SELECT id, title, status, metadata, created_at, updated_at
+                         FROM workspaces WHERE status = ?1 ORDER BY created_at DESC",
+                    )?;
+                    let rows = stmt
+                        .query_map(rusqlite::params![status_str], |row| {
+                            Ok(row_to_workspace(row))
+                        })?
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(rows)
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::workspace::list(pg, Some(&status_str)).await,
+        }
     }
 
     pub async fn update_title(&self, id: &str, title: &str) -> Result<(), ServerError> {
-        let id = id.to_string();
-        let title = title.to_string();
-        let now = Utc::now().timestamp_millis();
-        self.db
-            .with_conn_async(move |conn| {
-                conn.execute(
-                    "UPDATE workspaces SET title = ?1, updated_at = ?2 WHERE id = ?3",
-                    rusqlite::params![title, now, id],
-                )?;
-                Ok(())
-            })
-            .await
+        self.update_column("title", id, title).await
     }
 
     pub async fn update_status(&self, id: &str, status: &str) -> Result<(), ServerError> {
-        let id = id.to_string();
-        let status = status.to_string();
+        self.update_column("status", id, status).await
+    }
+
+    /// Set one text `column` of workspace `id` and bump `updated_at`.
+    async fn update_column(
+        &self,
+        column: &'static str,
+        id: &str,
+        value: &str,
+    ) -> Result<(), ServerError> {
         let now = Utc::now().timestamp_millis();
-        self.db
-            .with_conn_async(move |conn| {
-                conn.execute(
-                    "UPDATE workspaces SET status = ?1, updated_at = ?2 WHERE id = ?3",
-                    rusqlite::params![status, now, id],
-                )?;
-                Ok(())
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                let id = id.to_string();
+                let value = value.to_string();
+                db.with_conn_async(move |conn| {
+                    conn.execute(
+                        &format!(
+                            "UPDATE workspaces SET {column} = ?1, updated_at = ?2 WHERE id = ?3"
+                        ),
+                        rusqlite::params![value, now, id],
+                    )?;
+                    Ok(())
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => {
+                postgres::workspace::update_column(pg, column, id, value, now).await
+            }
+        }
     }
 
     pub async fn delete(&self, id: &str) -> Result<(), ServerError> {
-        let id = id.to_string();
-        self.db
-            .with_conn_async(move |conn| {
-                conn.execute(
-                    "DELETE FROM workspaces WHERE id = ?1",
-                    rusqlite::params![id],
-                )?;
-                Ok(())
-            })
-            .await
+        match &self.backend {
+            DbBackend::Sqlite(db) => {
+                let id = id.to_string();
+                db.with_conn_async(move |conn| {
+                    conn.execute(
+                        "DELETE FROM workspaces WHERE id = ?1",
+                        rusqlite::params![id],
+                    )?;
+                    Ok(())
+                })
+                .await
+            }
+            #[cfg(feature = "postgres")]
+            DbBackend::Postgres(pg) => postgres::workspace::delete(pg, id).await,
+        }
     }
 
     pub async fn ensure_default(&self) -> Result<Workspace, ServerError> {
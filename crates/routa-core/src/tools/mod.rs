@@ -16,13 +16,22 @@
 //!  11. subscribeToEvents - Subscribe to workspace events
 //!  12. unsubscribeFromEvents - Unsubscribe
 
+mod report_parser;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
 use crate::error::ServerError;
-use crate::events::{AgentEvent, AgentEventType, EventBus, EventSubscription};
+use crate::events::{
+    AgentCreatedData, AgentEvent, AgentEventType, EventBus, EventSubscription, MessageSentData,
+    ReportSubmittedData, TaskAssignedData, TaskCompletedData, TaskStatusChangedData,
+};
 use crate::models::agent::{Agent, AgentRole, AgentStatus, ModelTier};
 use crate::models::message::{Message, MessageRole};
-use crate::models::task::{Task, TaskStatus};
+use crate::models::task::{Task, TaskCreationSource, TaskStatus};
 use crate::store::{AgentStore, ConversationStore, TaskStore};
 
 /// Result of a tool operation.
@@ -52,6 +61,94 @@ impl ToolResult {
             error: Some(msg.into()),
         }
     }
+
+    /// Render as an MCP `tools/call` result.
+    ///
+    /// Successful results carry the serialized `ToolResult` as pretty JSON
+    /// text; failures always set `isError: true` with the error message.
+    pub fn to_mcp_content(&self) -> serde_json::Value {
+        if self.success {
+            mcp_text_content(
+                &serde_json::to_string_pretty(self).unwrap_or_default(),
+                false,
+            )
+        } else {
+            mcp_text_content(self.error.as_deref().unwrap_or("Tool call failed"), true)
+        }
+    }
+
+    /// Like [`to_mcp_content`](Self::to_mcp_content), but a successful result
+    /// carries only `data`: strings as-is, anything else as pretty JSON. For
+    /// tools whose MCP payload predates `ToolResult`.
+    pub fn to_mcp_data_content(&self) -> serde_json::Value {
+        if !self.success {
+            return self.to_mcp_content();
+        }
+        match &self.data {
+            Some(serde_json::Value::String(text)) => mcp_text_content(text, false),
+            data => mcp_text_content(
+                &serde_json::to_string_pretty(data.as_ref().unwrap_or(&serde_json::Value::Null))
+                    .unwrap_or_default(),
+                false,
+            ),
+        }
+    }
+}
+
+impl From<ToolResult> for serde_json::Value {
+    fn from(result: ToolResult) -> Self {
+        result.to_mcp_content()
+    }
+}
+
+/// A single-text-block MCP `tools/call` result.
+pub fn mcp_text_content(text: &str, is_error: bool) -> serde_json::Value {
+    serde_json::json!({
+        "isError": is_error,
+        "content": [{ "type": "text", "text": text }]
+    })
+}
+
+/// How `create_agent` treats a name already used in the workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AgentNamePolicy {
+    /// Allow duplicate names.
+    #[default]
+    Allow,
+    /// Refuse to create the agent.
+    Unique,
+    /// Append `-2`, `-3`, ... until the name is free.
+    AutoSuffix,
+}
+
+impl AgentNamePolicy {
+    /// From the `uniqueName` / `autoSuffix` tool arguments; `autoSuffix` wins.
+    pub fn from_flags(unique_name: bool, auto_suffix: bool) -> Self {
+        if auto_suffix {
+            Self::AutoSuffix
+        } else if unique_name {
+            Self::Unique
+        } else {
+            Self::Allow
+        }
+    }
+
+    /// The name to create `name` under, given the agents already in the
+    /// workspace. Errors when the policy is `Unique` and the name is taken.
+    pub fn resolve(self, name: &str, existing: &[Agent]) -> Result<String, String> {
+        let taken = |candidate: &str| existing.iter().any(|agent| agent.name == candidate);
+        match self {
+            Self::Allow => Ok(name.to_string()),
+            _ if !taken(name) => Ok(name.to_string()),
+            Self::Unique => Err(format!(
+                "An agent named '{name}' already exists in this workspace"
+            )),
+            Self::AutoSuffix => Ok((2..)
+                .map(|n| format!("{name}-{n}"))
+                .find(|candidate| !taken(candidate))
+                .expect("unbounded suffix range")),
+        }
+    }
 }
 
 /// Completion report from a child agent.
@@ -67,12 +164,41 @@ pub struct CompletionReport {
     pub files_modified: Option<Vec<String>>,
 }
 
+/// Forwards inter-agent messages into the recipient's live ACP session.
+///
+/// Implemented by [`RoutaOrchestrator`](crate::orchestration::RoutaOrchestrator),
+/// which knows which session each spawned agent runs in.
+pub trait LiveSessionDelivery: Send + Sync {
+    /// Prompt `agent_id`'s live session with `text` without waiting for the
+    /// turn to finish. Returns the session ID, or `None` if the agent has no
+    /// live session.
+    fn deliver<'a>(
+        &'a self,
+        agent_id: &'a str,
+        text: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Option<String>> + Send + 'a>>;
+}
+
+/// Answers whether an agent's ACP session is still running, so listings can
+/// catch agents whose stored status went stale when their process died.
+///
+/// Implemented by [`AcpManager`](crate::acp::AcpManager) and
+/// [`RoutaOrchestrator`](crate::orchestration::RoutaOrchestrator).
+pub trait SessionLiveness: Send + Sync {
+    fn session_alive<'a>(
+        &'a self,
+        agent_id: &'a str,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>>;
+}
+
 /// AgentTools provides coordination tools for multi-agent collaboration.
 pub struct AgentTools {
     agent_store: AgentStore,
     conversation_store: ConversationStore,
     task_store: TaskStore,
     event_bus: EventBus,
+    live_sessions: Option<Arc<dyn LiveSessionDelivery>>,
+    session_liveness: Option<Arc<dyn SessionLiveness>>,
 }
 
 impl AgentTools {
@@ -87,25 +213,52 @@ impl AgentTools {
             conversation_store,
             task_store,
             event_bus,
+            live_sessions: None,
+            session_liveness: None,
         }
     }
 
+    /// Let `message_agent` prompt recipients that have a live session instead
+    /// of only appending to their conversation.
+    pub fn with_live_sessions(mut self, live_sessions: Arc<dyn LiveSessionDelivery>) -> Self {
+        self.live_sessions = Some(live_sessions);
+        self
+    }
+
+    /// Let `list_agents` report whether each agent's session is still running.
+    pub fn with_session_liveness(mut self, session_liveness: Arc<dyn SessionLiveness>) -> Self {
+        self.session_liveness = Some(session_liveness);
+        self
+    }
+
     // ─── Tool 1: List Agents ─────────────────────────────────────────────
 
-    pub async fn list_agents(&self, workspace_id: &str) -> Result<ToolResult, ServerError> {
+    /// With `include_live`, each agent also gets `sessionAlive`; without a
+    /// liveness source every agent reports `false`.
+    pub async fn list_agents(
+        &self,
+        workspace_id: &str,
+        include_live: bool,
+    ) -> Result<ToolResult, ServerError> {
         let agents = self.agent_store.list_by_workspace(workspace_id).await?;
-        let summary: Vec<serde_json::Value> = agents
-            .iter()
-            .map(|a| {
-                serde_json::json!({
-                    "id": a.id,
-                    "name": a.name,
-                    "role": a.role,
-                    "status": a.status,
-                    "parentId": a.parent_id,
-                })
-            })
-            .collect();
+        let mut summary = Vec::with_capacity(agents.len());
+        for a in &agents {
+            let mut entry = serde_json::json!({
+                "id": a.id,
+                "name": a.name,
+                "role": a.role,
+                "status": a.status,
+                "parentId": a.parent_id,
+            });
+            if include_live {
+                let alive = match &self.session_liveness {
+                    Some(liveness) => liveness.session_alive(&a.id).await,
+                    None => false,
+                };
+                entry["sessionAlive"] = serde_json::Value::Bool(alive);
+            }
+            summary.push(entry);
+        }
         Ok(ToolResult::success(summary))
     }
 
@@ -162,6 +315,7 @@ impl AgentTools {
         workspace_id: &str,
         parent_id: Option<&str>,
         model_tier: Option<&str>,
+        name_policy: AgentNamePolicy,
     ) -> Result<ToolResult, ServerError> {
         let role = match AgentRole::from_str(role) {
             Some(r) => r,
@@ -176,9 +330,19 @@ impl AgentTools {
             .and_then(ModelTier::from_str)
             .unwrap_or(ModelTier::Smart);
 
+        let name = if name_policy == AgentNamePolicy::Allow {
+            name.to_string()
+        } else {
+            let existing = self.agent_store.list_by_workspace(workspace_id).await?;
+            match name_policy.resolve(name, &existing) {
+                Ok(name) => name,
+                Err(e) => return Ok(ToolResult::error(e)),
+            }
+        };
+
         let agent = Agent::new(
             uuid::Uuid::new_v4().to_string(),
-            name.to_string(),
+            name,
             role.clone(),
             workspace_id.to_string(),
             parent_id.map(|s| s.to_string()),
@@ -189,13 +353,14 @@ impl AgentTools {
         self.agent_store.save(&agent).await?;
 
         self.event_bus
-            .emit(AgentEvent {
-                event_type: AgentEventType::AgentCreated,
-                agent_id: agent.id.clone(),
-                workspace_id: workspace_id.to_string(),
-                data: serde_json::json!({ "name": agent.name, "role": agent.role }),
-                timestamp: chrono::Utc::now(),
-            })
+            .emit(AgentEvent::agent_created(
+                agent.id.clone(),
+                workspace_id,
+                AgentCreatedData {
+                    name: agent.name.clone(),
+                    role: agent.role.clone(),
+                },
+            ))
             .await;
 
         Ok(ToolResult::success(serde_json::json!({
@@ -250,17 +415,18 @@ impl AgentTools {
         self.conversation_store.append(&message).await?;
 
         self.event_bus
-            .emit(AgentEvent {
-                event_type: AgentEventType::TaskAssigned,
-                agent_id: agent_id.to_string(),
-                workspace_id: agent.workspace_id.clone(),
-                data: serde_json::json!({
-                    "taskId": task_id,
-                    "callerAgentId": caller_agent_id,
-                    "taskTitle": task.title,
-                }),
-                timestamp: chrono::Utc::now(),
-            })
+            .emit(AgentEvent::task_assigned(
+                agent_id,
+                agent.workspace_id.clone(),
+                TaskAssignedData {
+                    task_id: task_id.to_string(),
+                    task_title: task.title.clone(),
+                    caller_agent_id: Some(caller_agent_id.to_string()),
+                    previous_agent_id: None,
+                    provider: None,
+                    specialist: None,
+                },
+            ))
             .await;
 
         Ok(ToolResult::success(serde_json::json!({
@@ -287,33 +453,41 @@ impl AgentTools {
             }
         };
 
+        let content = format!("[From agent {from_agent_id}]: {message}");
         let msg = Message::new(
             uuid::Uuid::new_v4().to_string(),
             to_agent_id.to_string(),
             MessageRole::User,
-            format!("[From agent {from_agent_id}]: {message}"),
+            content.clone(),
             None,
             None,
             None,
         );
         self.conversation_store.append(&msg).await?;
 
+        let session_id = match &self.live_sessions {
+            Some(live_sessions) => live_sessions.deliver(to_agent_id, &content).await,
+            None => None,
+        };
+
         self.event_bus
-            .emit(AgentEvent {
-                event_type: AgentEventType::MessageSent,
-                agent_id: from_agent_id.to_string(),
-                workspace_id: to_agent.workspace_id.clone(),
-                data: serde_json::json!({
-                    "fromAgentId": from_agent_id,
-                    "toAgentId": to_agent_id,
-                    "messagePreview": &message[..message.len().min(200)],
-                }),
-                timestamp: chrono::Utc::now(),
-            })
+            .emit(AgentEvent::message_sent(
+                from_agent_id,
+                to_agent.workspace_id.clone(),
+                MessageSentData {
+                    from_agent_id: from_agent_id.to_string(),
+                    to_agent_id: to_agent_id.to_string(),
+                    message_preview: message[..message.len().min(200)].to_string(),
+                    session_id: session_id.clone(),
+                },
+            ))
             .await;
 
         Ok(ToolResult::success(serde_json::json!({
             "delivered": true,
+            "messageId": msg.id,
+            "liveSession": session_id.is_some(),
+            "sessionId": session_id,
             "toAgentId": to_agent_id,
             "fromAgentId": from_agent_id,
         })))
@@ -341,13 +515,14 @@ impl AgentTools {
         };
 
         // Update task status
+        let task_status = if report.success {
+            TaskStatus::Completed
+        } else {
+            TaskStatus::NeedsFix
+        };
         if let Some(task_id) = &report.task_id {
             if let Some(mut task) = self.task_store.get(task_id).await? {
-                task.status = if report.success {
-                    TaskStatus::Completed
-                } else {
-                    TaskStatus::NeedsFix
-                };
+                task.status = task_status.clone();
                 task.completion_summary = Some(report.summary.clone());
                 task.updated_at = chrono::Utc::now();
                 self.task_store.save(&task).await?;
@@ -386,23 +561,23 @@ impl AgentTools {
         self.conversation_store.append(&msg).await?;
 
         self.event_bus
-            .emit(AgentEvent {
-                event_type: AgentEventType::ReportSubmitted,
-                agent_id: agent_id.to_string(),
-                workspace_id: agent.workspace_id.clone(),
-                data: serde_json::json!({
-                    "parentId": parent_id,
-                    "taskId": report.task_id,
-                    "success": report.success,
-                }),
-                timestamp: chrono::Utc::now(),
-            })
+            .emit(AgentEvent::report_submitted(
+                agent_id,
+                agent.workspace_id.clone(),
+                ReportSubmittedData {
+                    parent_id: parent_id.clone(),
+                    task_id: report.task_id.clone(),
+                    success: report.success,
+                },
+            ))
             .await;
 
         Ok(ToolResult::success(serde_json::json!({
             "reported": true,
             "parentId": parent_id,
             "success": report.success,
+            "taskId": report.task_id,
+            "taskStatus": task_status.as_str(),
         })))
     }
 
@@ -420,8 +595,9 @@ impl AgentTools {
         test_cases: Option<Vec<String>>,
         dependencies: Option<Vec<String>>,
         parallel_group: Option<&str>,
+        creation_source: Option<TaskCreationSource>,
     ) -> Result<ToolResult, ServerError> {
-        let task = Task::new(
+        let mut task = Task::new(
             uuid::Uuid::new_v4().to_string(),
             title.to_string(),
             objective.to_string(),
@@ -434,6 +610,9 @@ impl AgentTools {
             dependencies,
             parallel_group.map(|s| s.to_string()),
         );
+        if creation_source.is_some() {
+            task.creation_source = creation_source;
+        }
 
         self.task_store.save(&task).await?;
 
@@ -441,6 +620,7 @@ impl AgentTools {
             "taskId": task.id,
             "title": task.title,
             "status": task.status,
+            "creationSource": task.creation_source,
         })))
     }
 
@@ -479,6 +659,7 @@ impl AgentTools {
         task_id: &str,
         status: &str,
         agent_id: &str,
+        reason: Option<&str>,
         summary: Option<&str>,
     ) -> Result<ToolResult, ServerError> {
         let new_status = match TaskStatus::from_str(status) {
@@ -505,34 +686,32 @@ impl AgentTools {
 
         // Emit status change event
         self.event_bus
-            .emit(AgentEvent {
-                event_type: AgentEventType::TaskStatusChanged,
-                agent_id: agent_id.to_string(),
-                workspace_id: task.workspace_id.clone(),
-                data: serde_json::json!({
-                    "taskId": task_id,
-                    "oldStatus": old_status,
-                    "newStatus": new_status,
-                    "summary": summary,
-                }),
-                timestamp: chrono::Utc::now(),
-            })
+            .emit(AgentEvent::task_status_changed(
+                agent_id,
+                task.workspace_id.clone(),
+                TaskStatusChangedData {
+                    task_id: task_id.to_string(),
+                    task_title: None,
+                    old_status: old_status.clone(),
+                    new_status: new_status.clone(),
+                    reason: reason.map(str::to_string),
+                    summary: summary.map(str::to_string),
+                },
+            ))
             .await;
 
         // Also emit TASK_COMPLETED if applicable
         if new_status == TaskStatus::Completed {
             self.event_bus
-                .emit(AgentEvent {
-                    event_type: AgentEventType::TaskCompleted,
-                    agent_id: agent_id.to_string(),
-                    workspace_id: task.workspace_id.clone(),
-                    data: serde_json::json!({
-                        "taskId": task_id,
-                        "taskTitle": task.title,
-                        "summary": summary,
-                    }),
-                    timestamp: chrono::Utc::now(),
-                })
+                .emit(AgentEvent::task_completed(
+                    agent_id,
+                    task.workspace_id.clone(),
+                    TaskCompletedData {
+                        task_id: task_id.to_string(),
+                        task_title: task.title.clone(),
+                        summary: summary.map(str::to_string),
+                    },
+                ))
                 .await;
         }
 
@@ -556,6 +735,8 @@ impl AgentTools {
         one_shot: bool,
         wait_group_id: Option<String>,
         priority: i32,
+        cross_workspace: bool,
+        ttl_secs: Option<u64>,
     ) -> Result<ToolResult, ServerError> {
         let valid_types: Vec<AgentEventType> = event_types
             .iter()
@@ -569,6 +750,11 @@ impl AgentTools {
             )));
         }
 
+        let agent = match self.agent_store.get(agent_id).await? {
+            Some(agent) => agent,
+            None => return Ok(ToolResult::error(format!("Agent not found: {agent_id}"))),
+        };
+
         let subscription_id = uuid::Uuid::new_v4().to_string();
         self.event_bus
             .subscribe(EventSubscription {
@@ -580,6 +766,9 @@ impl AgentTools {
                 one_shot,
                 wait_group_id: wait_group_id.clone(),
                 priority,
+                workspace_id: agent.workspace_id,
+                cross_workspace,
+                ttl_secs,
             })
             .await;
 
@@ -660,28 +849,17 @@ impl AgentTools {
             None => return Ok(ToolResult::error(format!("Agent not found: {agent_id}"))),
         };
 
-        let message_count = self.conversation_store.get_message_count(agent_id).await?;
-        let last_messages = self.conversation_store.get_last_n(agent_id, 3).await?;
+        let summary = self.conversation_store.get_summary(agent_id).await?;
         let tasks = self.task_store.list_by_assignee(agent_id).await?;
 
-        let last_response = last_messages
-            .iter()
-            .rfind(|m| m.role == MessageRole::Assistant);
-
-        let all_messages = self.conversation_store.get_conversation(agent_id).await?;
-        let tool_call_count = all_messages
-            .iter()
-            .filter(|m| m.role == MessageRole::Tool)
-            .count();
-
         Ok(ToolResult::success(serde_json::json!({
             "agentId": agent.id,
             "name": agent.name,
             "role": agent.role,
             "status": agent.status,
-            "messageCount": message_count,
-            "toolCallCount": tool_call_count,
-            "lastResponse": last_response.map(|m| serde_json::json!({
+            "messageCount": summary.message_count,
+            "toolCallCount": summary.tool_call_count,
+            "lastResponse": summary.last_assistant_message.map(|m| serde_json::json!({
                 "content": &m.content[..m.content.len().min(500)],
                 "timestamp": m.timestamp.to_rfc3339(),
             })),
@@ -689,6 +867,141 @@ impl AgentTools {
                 .filter(|t| t.status == TaskStatus::InProgress)
                 .map(|t| serde_json::json!({ "id": t.id, "title": t.title }))
                 .collect::<Vec<_>>(),
+            "lastActivity": agent.updated_at,
         })))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::store::WorkspaceStore;
+    use std::sync::Mutex;
+
+    /// Records prompts instead of talking to an ACP process.
+    #[derive(Default)]
+    struct StubSessions {
+        live_agent_id: String,
+        prompts: Mutex<Vec<(String, String)>>,
+    }
+
+    impl LiveSessionDelivery for StubSessions {
+        fn deliver<'a>(
+            &'a self,
+            agent_id: &'a str,
+            text: &'a str,
+        ) -> Pin<Box<dyn Future<Output = Option<String>> + Send + 'a>> {
+            Box::pin(async move {
+                if agent_id != self.live_agent_id {
+                    return None;
+                }
+                let session_id = format!("session-{agent_id}");
+                self.prompts
+                    .lock()
+                    .unwrap()
+                    .push((session_id.clone(), text.to_string()));
+                Some(session_id)
+            })
+        }
+    }
+
+    async fn tools_with_agents(db: &Database, agent_ids: &[&str]) -> AgentTools {
+        WorkspaceStore::new(db.clone())
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+        let agent_store = AgentStore::new(db.clone());
+        for id in agent_ids {
+            agent_store
+                .save(&Agent::new(
+                    id.to_string(),
+                    id.to_string(),
+                    AgentRole::Crafter,
+                    "default".to_string(),
+                    None,
+                    None,
+                    None,
+                ))
+                .await
+                .unwrap();
+        }
+        AgentTools::new(
+            agent_store,
+            ConversationStore::new(db.clone()),
+            TaskStore::new(db.clone()),
+            EventBus::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn message_agent_prompts_live_session() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let sessions = Arc::new(StubSessions {
+            live_agent_id: "crafter-live".to_string(),
+            ..StubSessions::default()
+        });
+        let tools = tools_with_agents(&db, &["routa", "crafter-live", "crafter-idle"])
+            .await
+            .with_live_sessions(sessions.clone());
+
+        let live = tools
+            .message_agent("routa", "crafter-live", "rebase onto main")
+            .await
+            .unwrap();
+        let data = live.data.expect("success data");
+        assert_eq!(data["liveSession"], true);
+        assert_eq!(data["sessionId"], "session-crafter-live");
+        assert_eq!(
+            *sessions.prompts.lock().unwrap(),
+            vec![(
+                "session-crafter-live".to_string(),
+                "[From agent routa]: rebase onto main".to_string()
+            )]
+        );
+
+        let idle = tools
+            .message_agent("routa", "crafter-idle", "hello")
+            .await
+            .unwrap();
+        assert_eq!(idle.data.expect("success data")["liveSession"], false);
+        assert_eq!(sessions.prompts.lock().unwrap().len(), 1);
+
+        let stored = ConversationStore::new(db.clone())
+            .get_conversation("crafter-idle")
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn create_agent_enforces_name_policy() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let tools = tools_with_agents(&db, &[]).await;
+        let create = |policy| tools.create_agent("reviewer", "GATE", "default", None, None, policy);
+
+        assert!(create(AgentNamePolicy::Unique).await.unwrap().success);
+        let duplicate = create(AgentNamePolicy::Unique).await.unwrap();
+        assert!(!duplicate.success);
+        assert_eq!(
+            duplicate.error.as_deref(),
+            Some("An agent named 'reviewer' already exists in this workspace")
+        );
+
+        let suffixed = create(AgentNamePolicy::AutoSuffix).await.unwrap();
+        assert_eq!(suffixed.data.unwrap()["name"], "reviewer-2");
+        let suffixed = create(AgentNamePolicy::AutoSuffix).await.unwrap();
+        assert_eq!(suffixed.data.unwrap()["name"], "reviewer-3");
+
+        assert!(create(AgentNamePolicy::Allow).await.unwrap().success);
+        let names: Vec<String> = AgentStore::new(db.clone())
+            .list_by_workspace("default")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|agent| agent.name)
+            .filter(|name| name == "reviewer")
+            .collect();
+        assert_eq!(names.len(), 2);
+    }
+}
@@ -0,0 +1,330 @@
+//! Free-text fallback for `report_to_parent`.
+//!
+//! Agents are asked to call `report_to_parent` with structured fields, but they
+//! regularly paste the whole report into `summary` (or omit fields entirely).
+//! These helpers recover `summary`, `success` and `filesModified` from the text.
+
+use super::CompletionReport;
+
+/// Verdict keywords that mark a report as failed. Checked before successes so
+/// "NOT APPROVED" wins over "APPROVED".
+const FAILURE_PHRASES: &[&[&str]] = &[
+    &["NOT", "APPROVED"],
+    &["NOT", "VERIFIED"],
+    &["REJECTED"],
+    &["FAILED"],
+    &["FAILURE"],
+    &["BLOCKED"],
+    &["NEEDS_FIX"],
+    &["NEEDS", "FIX"],
+];
+
+const SUCCESS_PHRASES: &[&[&str]] = &[
+    &["APPROVED"],
+    &["VERIFIED"],
+    &["DONE"],
+    &["COMPLETED"],
+    &["COMPLETE"],
+    &["SUCCESS"],
+    &["PASSED"],
+];
+
+const FILES_HEADINGS: &[&str] = &[
+    "files modified",
+    "files changed",
+    "modified files",
+    "changed files",
+    "files touched",
+];
+
+impl CompletionReport {
+    /// Parse a report from free-text agent output.
+    ///
+    /// `success` comes from an explicit `Success: true|false` line when present,
+    /// otherwise from verdict keywords (APPROVED/DONE vs FAILED/REJECTED), and
+    /// defaults to `true` like the structured tool does.
+    pub fn from_text(agent_id: &str, text: &str) -> Self {
+        let files = parse_files_modified(text);
+        Self {
+            agent_id: agent_id.to_string(),
+            task_id: None,
+            summary: parse_summary(text),
+            success: parse_success(text).unwrap_or(true),
+            files_modified: (!files.is_empty()).then_some(files),
+        }
+    }
+
+    /// Build a report from `report_to_parent` tool arguments, filling missing
+    /// fields from the free-text `summary` (or `report`/`text`) argument.
+    pub fn from_tool_args(agent_id: &str, args: &serde_json::Value) -> Self {
+        let str_arg = |key: &str| {
+            args.get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let task_id = str_arg("taskId").map(str::to_string);
+        let summary = str_arg("summary");
+        let success = args.get("success").and_then(|v| v.as_bool());
+        let files_modified = args
+            .get("filesModified")
+            .and_then(|v| v.as_array())
+            .map(|files| {
+                files
+                    .iter()
+                    .filter_map(|f| f.as_str().map(str::to_string))
+                    .collect::<Vec<_>>()
+            });
+
+        if let (Some(summary), Some(success)) = (summary, success) {
+            return Self {
+                agent_id: agent_id.to_string(),
+                task_id,
+                summary: summary.to_string(),
+                success,
+                files_modified,
+            };
+        }
+
+        let text = summary
+            .or_else(|| str_arg("report"))
+            .or_else(|| str_arg("text"))
+            .unwrap_or_default();
+        tracing::info!(
+            "[CompletionReport] Structured report from {} is incomplete; parsing free text",
+            agent_id
+        );
+        let parsed = Self::from_text(agent_id, text);
+        Self {
+            agent_id: agent_id.to_string(),
+            task_id,
+            summary: parsed.summary,
+            success: success.unwrap_or(parsed.success),
+            files_modified: files_modified.or(parsed.files_modified),
+        }
+    }
+}
+
+fn strip_markdown(line: &str) -> &str {
+    line.trim()
+        .trim_start_matches('#')
+        .trim()
+        .trim_matches(|c| c == '*' || c == '_')
+        .trim()
+}
+
+fn bullet_item(line: &str) -> Option<&str> {
+    let line = line.trim();
+    for marker in ["- ", "* ", "+ ", "• "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return Some(rest.trim());
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(rest) = line[digits..]
+            .strip_prefix(". ")
+            .or_else(|| line[digits..].strip_prefix(") "))
+        {
+            return Some(rest.trim());
+        }
+    }
+    None
+}
+
+/// Split `Label: value` lines, tolerating markdown emphasis around the label.
+fn labelled(line: &str) -> Option<(String, &str)> {
+    let (label, value) = line.split_once(':')?;
+    let label = strip_markdown(label).to_ascii_lowercase();
+    let value = value.trim().trim_start_matches(['*', '_']).trim();
+    Some((label, value))
+}
+
+fn parse_summary(text: &str) -> String {
+    for line in text.lines() {
+        if let Some((label, value)) = labelled(line) {
+            if label == "summary" && !value.is_empty() {
+                return value.to_string();
+            }
+        }
+    }
+
+    let mut paragraph: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let is_structure = trimmed.starts_with('#')
+            || bullet_item(trimmed).is_some()
+            || labelled(trimmed).is_some_and(|(label, _)| {
+                label == "success" || FILES_HEADINGS.contains(&label.as_str())
+            });
+        if trimmed.is_empty() || is_structure {
+            if !paragraph.is_empty() {
+                break;
+            }
+            continue;
+        }
+        paragraph.push(strip_markdown(trimmed));
+    }
+
+    if paragraph.is_empty() {
+        text.trim().to_string()
+    } else {
+        paragraph.join(" ")
+    }
+}
+
+fn parse_success(text: &str) -> Option<bool> {
+    for line in text.lines() {
+        if let Some((label, value)) = labelled(line) {
+            if label == "success" {
+                match value.to_ascii_lowercase().as_str() {
+                    "true" | "yes" => return Some(true),
+                    "false" | "no" => return Some(false),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let upper = text.to_ascii_uppercase();
+    let tokens: Vec<&str> = upper
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|t| !t.is_empty())
+        .collect();
+    let contains = |phrase: &[&str]| tokens.windows(phrase.len()).any(|w| w == phrase);
+
+    if text.contains('❌') || FAILURE_PHRASES.iter().any(|p| contains(p)) {
+        Some(false)
+    } else if SUCCESS_PHRASES.iter().any(|p| contains(p)) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+fn parse_files_modified(text: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut lines = text.lines().peekable();
+    while let Some(line) = lines.next() {
+        let Some((label, inline)) = labelled(line) else {
+            continue;
+        };
+        if !FILES_HEADINGS.contains(&label.as_str()) {
+            continue;
+        }
+
+        files.extend(
+            inline
+                .split(',')
+                .filter_map(file_path_from_item)
+                .map(str::to_string),
+        );
+        while let Some(next) = lines.peek() {
+            if next.trim().is_empty() && files.is_empty() {
+                lines.next();
+                continue;
+            }
+            let Some(item) = bullet_item(next) else {
+                break;
+            };
+            if let Some(path) = file_path_from_item(item) {
+                files.push(path.to_string());
+            }
+            lines.next();
+        }
+        break;
+    }
+    files
+}
+
+/// Take the path from an item like "`src/lib.rs` — added parser".
+fn file_path_from_item(item: &str) -> Option<&str> {
+    let path = item
+        .split_whitespace()
+        .next()?
+        .trim_matches(|c| c == '`' || c == '*' || c == '"' || c == '\'')
+        .trim_end_matches([',', ':', ';']);
+    (!path.is_empty() && !path.eq_ignore_ascii_case("none")).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_crafter_markdown_report() {
+        let text = "## Completion Report\n\n\
+            Implemented the retry wrapper around the ACP spawn call and added unit tests.\n\n\
+            **Files modified:**\n\
+            - `crates/routa-core/src/acp/process.rs` — retry loop\n\
+            - `crates/routa-core/src/acp/mod.rs`\n\n\
+            Status: DONE";
+
+        let report = CompletionReport::from_text("crafter-1", text);
+
+        assert_eq!(
+            report.summary,
+            "Implemented the retry wrapper around the ACP spawn call and added unit tests."
+        );
+        assert!(report.success);
+        assert_eq!(
+            report.files_modified,
+            Some(vec![
+                "crates/routa-core/src/acp/process.rs".to_string(),
+                "crates/routa-core/src/acp/mod.rs".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn gate_rejection_is_a_failure() {
+        let text = "Verdict: NOT APPROVED (confidence: high)\n\
+            Summary: AC2 is unverified — `cargo test` fails in tasks::tests.\n\
+            - ❌ AC2: assignee persisted";
+
+        let report = CompletionReport::from_text("gate-1", text);
+
+        assert!(!report.success);
+        assert_eq!(
+            report.summary,
+            "AC2 is unverified — `cargo test` fails in tasks::tests."
+        );
+        assert_eq!(report.files_modified, None);
+    }
+
+    #[test]
+    fn explicit_success_line_and_inline_files() {
+        let text = "Fixed the flaky kanban test.\nSuccess: false\nFiles changed: a.rs, b.rs";
+
+        let report = CompletionReport::from_text("crafter-2", text);
+
+        assert!(!report.success);
+        assert_eq!(report.summary, "Fixed the flaky kanban test.");
+        assert_eq!(
+            report.files_modified,
+            Some(vec!["a.rs".to_string(), "b.rs".to_string()])
+        );
+    }
+
+    #[test]
+    fn tool_args_fall_back_to_text_only_for_missing_fields() {
+        let complete = serde_json::json!({
+            "taskId": "t-1",
+            "summary": "FAILED to reproduce, but done",
+            "success": true,
+        });
+        let report = CompletionReport::from_tool_args("crafter-1", &complete);
+        assert!(report.success);
+        assert_eq!(report.summary, "FAILED to reproduce, but done");
+
+        let partial = serde_json::json!({
+            "taskId": "t-1",
+            "summary": "Tests FAILED on CI.\n\nFiles modified:\n- src/main.rs",
+        });
+        let report = CompletionReport::from_tool_args("crafter-1", &partial);
+        assert_eq!(report.task_id.as_deref(), Some("t-1"));
+        assert!(!report.success);
+        assert_eq!(report.summary, "Tests FAILED on CI.");
+        assert_eq!(report.files_modified, Some(vec!["src/main.rs".to_string()]));
+    }
+}
@@ -15,7 +15,8 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use super::types::TraceRecord;
+use super::types::{TraceEventType, TraceRecord};
+use crate::models::message::{Message, MessageRole};
 use crate::storage::get_traces_dir;
 
 /// Query parameters for filtering traces.
@@ -29,6 +30,8 @@ pub struct TraceQuery {
     pub file: Option<String>,
     /// Filter by event type
     pub event_type: Option<String>,
+    /// Only delegation traces naming this agent as parent or child
+    pub agent_id: Option<String>,
     /// Start date (ISO 8601 or YYYY-MM-DD)
     pub start_date: Option<String>,
     /// End date (ISO 8601 or YYYY-MM-DD)
@@ -197,6 +200,24 @@ impl TraceReader {
         Ok(traces_json)
     }
 
+    /// Rebuild a session's conversation from its trace records, oldest first.
+    ///
+    /// Useful when the conversation store no longer holds the session.
+    /// Messages carry the session ID in `agent_id`, since traces don't record
+    /// which agent they came from.
+    pub async fn reconstruct_conversation(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<Message>, TraceReadError> {
+        let traces = self
+            .query(&TraceQuery {
+                session_id: Some(session_id.to_string()),
+                ..Default::default()
+            })
+            .await?;
+        Ok(conversation_from_traces(traces))
+    }
+
     /// Get trace statistics for a workspace.
     pub async fn stats(&self) -> Result<TraceStats, TraceReadError> {
         let all_base_dirs = self.get_all_trace_base_dirs().await;
@@ -259,6 +280,17 @@ impl TraceReader {
             }
         }
 
+        if let Some(ref agent_id) = query.agent_id {
+            let names_agent = |key: &str| {
+                record.metadata.get(key).and_then(|v| v.as_str()) == Some(agent_id.as_str())
+            };
+            if record.event_type != TraceEventType::Delegation
+                || !(names_agent("parentAgentId") || names_agent("childAgentId"))
+            {
+                return false;
+            }
+        }
+
         if let Some(ref event_type) = query.event_type {
             let record_type = format!("{:?}", record.event_type).to_lowercase();
             let query_lower = event_type.to_lowercase();
@@ -414,6 +446,120 @@ fn to_snake_case(s: &str) -> String {
         .collect()
 }
 
+/// Assemble user, assistant and tool messages from trace records.
+///
+/// Assistant text recorded in consecutive chunks becomes one message, and
+/// each tool result is folded into the message of the call with the same
+/// `tool_call_id`. Providers that only send tool input with a later update
+/// record the call after the fact, so a call arriving after its result fills
+/// in the existing message instead of adding another one.
+fn conversation_from_traces(mut traces: Vec<TraceRecord>) -> Vec<Message> {
+    traces.sort_by_key(|trace| trace.timestamp);
+
+    let mut messages: Vec<Message> = Vec::new();
+    let mut tool_messages: HashMap<String, usize> = HashMap::new();
+    let mut turn = 0;
+    let current_turn = |turn: i32| (turn > 0).then_some(turn);
+
+    for trace in traces {
+        match trace.event_type {
+            TraceEventType::UserMessage | TraceEventType::AgentMessage => {
+                let Some(content) = trace.conversation.as_ref().and_then(|conversation| {
+                    conversation
+                        .full_content
+                        .clone()
+                        .or_else(|| conversation.content_preview.clone())
+                }) else {
+                    continue;
+                };
+                if trace.event_type == TraceEventType::UserMessage {
+                    turn += 1;
+                    let mut message = Message::new(
+                        trace.id,
+                        trace.session_id,
+                        MessageRole::User,
+                        content,
+                        None,
+                        None,
+                        current_turn(turn),
+                    );
+                    message.timestamp = trace.timestamp;
+                    messages.push(message);
+                    continue;
+                }
+                if let Some(last) = messages
+                    .last_mut()
+                    .filter(|last| last.role == MessageRole::Assistant)
+                {
+                    last.content.push_str(&content);
+                    continue;
+                }
+                let mut message = Message::new(
+                    trace.id,
+                    trace.session_id,
+                    MessageRole::Assistant,
+                    content,
+                    None,
+                    None,
+                    current_turn(turn),
+                );
+                message.timestamp = trace.timestamp;
+                messages.push(message);
+            }
+            TraceEventType::ToolCall | TraceEventType::ToolResult => {
+                let Some(tool) = trace.tool else {
+                    continue;
+                };
+                let call_id = tool.tool_call_id.unwrap_or_else(|| trace.id.clone());
+                let input = tool.input.map(|input| input.to_string());
+                let output = tool
+                    .output
+                    .map(|output| match output {
+                        Value::String(text) => text,
+                        other => other.to_string(),
+                    })
+                    .or(tool.status);
+
+                if let Some(&index) = tool_messages.get(&call_id) {
+                    let message = &mut messages[index];
+                    if message.tool_args.is_none() {
+                        message.tool_args = input;
+                    }
+                    if trace.event_type == TraceEventType::ToolResult {
+                        if let Some(output) = output {
+                            message.content = output;
+                        }
+                    }
+                    continue;
+                }
+
+                let content = match trace.event_type {
+                    TraceEventType::ToolResult => output.unwrap_or_default(),
+                    _ => String::new(),
+                };
+                let mut message = Message::new(
+                    call_id.clone(),
+                    trace.session_id,
+                    MessageRole::Tool,
+                    content,
+                    Some(tool.name),
+                    input,
+                    current_turn(turn),
+                );
+                message.timestamp = trace.timestamp;
+                tool_messages.insert(call_id, messages.len());
+                messages.push(message);
+            }
+            TraceEventType::AgentThought
+            | TraceEventType::SessionStart
+            | TraceEventType::SessionEnd
+            | TraceEventType::Delegation => {}
+        }
+    }
+
+    messages
+}
+
 /// Statistics about stored traces.
 #[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct TraceStats {
@@ -438,3 +584,101 @@ pub enum TraceReadError {
     #[error("Invalid date: {0}")]
     InvalidDate(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::{Contributor, TraceConversation, TraceTool, TraceWriter};
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+
+    fn record(
+        event_type: TraceEventType,
+        seconds: i64,
+        content: Option<&str>,
+        tool: Option<TraceTool>,
+    ) -> TraceRecord {
+        let mut record = TraceRecord::new("s1", event_type, Contributor::new("opencode", None));
+        record.timestamp = Utc::now() - Duration::seconds(60 - seconds);
+        if let Some(content) = content {
+            record = record.with_conversation(TraceConversation {
+                turn: None,
+                role: None,
+                content_preview: None,
+                full_content: Some(content.to_string()),
+            });
+        }
+        if let Some(tool) = tool {
+            record = record.with_tool(tool);
+        }
+        record
+    }
+
+    fn tool(name: &str, input: Option<Value>, output: Option<Value>) -> TraceTool {
+        TraceTool {
+            name: name.to_string(),
+            tool_call_id: Some("call-1".to_string()),
+            status: Some("completed".to_string()),
+            input,
+            output,
+        }
+    }
+
+    #[tokio::test]
+    async fn reconstructs_ordered_conversation_from_traces() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = TraceWriter::with_base_dir(dir.path());
+        let records = [
+            record(TraceEventType::UserMessage, 0, Some("Fix the bug"), None),
+            record(TraceEventType::AgentMessage, 1, Some("Looking "), None),
+            record(TraceEventType::AgentMessage, 2, Some("into it"), None),
+            // Result logged before the deferred call record carrying the input.
+            record(
+                TraceEventType::ToolResult,
+                3,
+                None,
+                Some(tool("read", None, Some(json!("fn main() {}")))),
+            ),
+            record(
+                TraceEventType::ToolCall,
+                4,
+                None,
+                Some(tool("read", Some(json!({"path": "src/main.rs"})), None)),
+            ),
+            record(TraceEventType::AgentThought, 5, Some("hmm"), None),
+            record(TraceEventType::AgentMessage, 6, Some("Done"), None),
+        ];
+        for record in records.iter().rev() {
+            writer.append(record).await.unwrap();
+        }
+        let mut other = record(TraceEventType::UserMessage, 0, Some("elsewhere"), None);
+        other.session_id = "s2".to_string();
+        writer.append(&other).await.unwrap();
+
+        let messages = TraceReader::with_base_dir(dir.path())
+            .reconstruct_conversation("s1")
+            .await
+            .unwrap();
+
+        let summary: Vec<_> = messages
+            .iter()
+            .map(|m| (m.role.as_str(), m.content.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("USER", "Fix the bug"),
+                ("ASSISTANT", "Looking into it"),
+                ("TOOL", "fn main() {}"),
+                ("ASSISTANT", "Done"),
+            ]
+        );
+        assert_eq!(messages[2].id, "call-1");
+        assert_eq!(messages[2].tool_name.as_deref(), Some("read"));
+        assert_eq!(
+            messages[2].tool_args.as_deref(),
+            Some(r#"{"path":"src/main.rs"}"#)
+        );
+        assert!(messages.iter().all(|m| m.turn == Some(1)));
+    }
+}
@@ -73,6 +73,9 @@ pub enum TraceEventType {
     SessionStart,
     /// Session ended
     SessionEnd,
+    /// An agent spawned a child agent; recorded on the parent's session with
+    /// `parentAgentId`, `childAgentId`, `childSessionId` and `taskId` metadata
+    Delegation,
 }
 
 /// The model/provider that produced the trace.
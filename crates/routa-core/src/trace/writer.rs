@@ -132,6 +132,17 @@ impl TraceWriter {
         Ok(file_path)
     }
 
+    /// The file this writer is appending to, once a record has been written
+    /// there. `None` before the first write or when writing failed (e.g. the
+    /// trace directory is not writable).
+    pub async fn current_path(&self) -> Option<PathBuf> {
+        let current = self.current_file.lock().await;
+        current
+            .as_ref()
+            .map(|cf| cf.path.clone())
+            .filter(|path| path.is_file())
+    }
+
     /// Get the base directory for traces.
     pub fn base_dir(&self) -> &Path {
         &self.base_dir
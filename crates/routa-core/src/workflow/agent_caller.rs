@@ -3,9 +3,16 @@
 //! Instead of spawning subprocesses like the existing `AcpProcess`,
 //! the workflow engine calls the LLM API directly via HTTP.
 //! This is simpler and doesn't require agents to be installed locally.
+//!
+//! Calls to a provider endpoint go through a [`CircuitBreaker`] and are
+//! retried per [`RetryPolicy`] on transport errors, 429 and 5xx responses.
 
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::circuit_breaker::{CircuitBreaker, RetryPolicy};
 
 /// Configuration for calling an ACP-compatible agent via HTTP API.
 #[derive(Debug, Clone)]
@@ -76,6 +83,8 @@ pub struct UsageInfo {
 /// Calls an ACP agent via HTTP API.
 pub struct AcpAgentCaller {
     client: reqwest::Client,
+    breaker: Arc<CircuitBreaker>,
+    retry: RetryPolicy,
 }
 
 impl Default for AcpAgentCaller {
@@ -91,22 +100,80 @@ impl AcpAgentCaller {
                 .timeout(std::time::Duration::from_secs(300)) // 5 min timeout
                 .build()
                 .unwrap_or_else(|_| reqwest::Client::new()),
+            breaker: CircuitBreaker::shared(),
+            retry: RetryPolicy::default(),
         }
     }
 
+    /// Use `breaker` instead of the process-wide [`CircuitBreaker::shared`].
+    pub fn with_circuit_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Call an agent with the given configuration and user prompt.
+    ///
+    /// Fails fast while the endpoint's circuit is open. Otherwise the call is
+    /// retried on provider faults; the last attempt's outcome is returned.
     pub async fn call(
         &self,
         config: &AgentCallConfig,
         user_prompt: &str,
     ) -> Result<AgentResponse, String> {
-        match config.adapter.as_str() {
-            "claude-code-sdk" | "anthropic" => {
+        let opencode = match config.adapter.as_str() {
+            "claude-code-sdk" | "anthropic" => false,
+            "opencode-sdk" | "opencode" => true,
+            "mock" => return Ok(self.call_mock(config, user_prompt)),
+            other => return Err(format!("Unknown adapter type: '{other}'")),
+        };
+        let endpoint = config.base_url.trim_end_matches('/');
+
+        let mut attempt = 1;
+        loop {
+            self.breaker.acquire(endpoint)?;
+            let result = if opencode {
+                self.call_opencode(config, user_prompt).await
+            } else {
                 self.call_anthropic_compatible(config, user_prompt).await
+            };
+
+            let fault = match &result {
+                Ok((status, _)) => {
+                    *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+                }
+                Err(_) => true,
+            };
+            let result = result.map(|(_, response)| response);
+            if !fault {
+                self.breaker.record_success(endpoint);
+                return result;
             }
-            "opencode-sdk" | "opencode" => self.call_opencode(config, user_prompt).await,
-            "mock" => Ok(self.call_mock(config, user_prompt)),
-            other => Err(format!("Unknown adapter type: '{other}'")),
+            self.breaker.record_failure(endpoint);
+            if attempt >= self.retry.max_attempts {
+                return result;
+            }
+
+            let delay = self.retry.delay(attempt);
+            let reason = match &result {
+                Ok(response) => response.error.clone().unwrap_or_default(),
+                Err(e) => e.clone(),
+            };
+            tracing::warn!(
+                "[AgentCaller] Attempt {}/{} to {} failed ({}); retrying in {}ms (circuit {})",
+                attempt,
+                self.retry.max_attempts,
+                endpoint,
+                reason,
+                delay.as_millis(),
+                self.breaker.state(endpoint).as_str()
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
         }
     }
 
@@ -191,7 +258,7 @@ impl AcpAgentCaller {
         &self,
         config: &AgentCallConfig,
         user_prompt: &str,
-    ) -> Result<AgentResponse, String> {
+    ) -> Result<(StatusCode, AgentResponse), String> {
         let url = format!("{}/v1/messages", config.base_url.trim_end_matches('/'));
 
         let mut body = serde_json::json!({
@@ -241,14 +308,17 @@ impl AcpAgentCaller {
             .map_err(|e| format!("Failed to read response body: {e}"))?;
 
         if !status.is_success() {
-            return Ok(AgentResponse {
-                content: String::new(),
-                model: config.model.clone(),
-                usage: None,
-                success: false,
-                error: Some(format!("API returned {status}: {response_text}")),
-                raw: serde_json::from_str(&response_text).ok(),
-            });
+            return Ok((
+                status,
+                AgentResponse {
+                    content: String::new(),
+                    model: config.model.clone(),
+                    usage: None,
+                    success: false,
+                    error: Some(format!("API returned {status}: {response_text}")),
+                    raw: serde_json::from_str(&response_text).ok(),
+                },
+            ));
         }
 
         let json: serde_json::Value = serde_json::from_str(&response_text)
@@ -285,14 +355,17 @@ impl AcpAgentCaller {
             .unwrap_or(&config.model)
             .to_string();
 
-        Ok(AgentResponse {
-            content,
-            model,
-            usage,
-            success: true,
-            error: None,
-            raw: Some(json),
-        })
+        Ok((
+            status,
+            AgentResponse {
+                content,
+                model,
+                usage,
+                success: true,
+                error: None,
+                raw: Some(json),
+            },
+        ))
     }
 
     /// Call the OpenCode SDK API (BigModel coding endpoint).
@@ -305,7 +378,7 @@ impl AcpAgentCaller {
         &self,
         config: &AgentCallConfig,
         user_prompt: &str,
-    ) -> Result<AgentResponse, String> {
+    ) -> Result<(StatusCode, AgentResponse), String> {
         let url = format!("{}/chat/completions", config.base_url.trim_end_matches('/'));
 
         let mut messages = vec![];
@@ -356,14 +429,17 @@ impl AcpAgentCaller {
             .map_err(|e| format!("Failed to read response body: {e}"))?;
 
         if !status.is_success() {
-            return Ok(AgentResponse {
-                content: String::new(),
-                model: config.model.clone(),
-                usage: None,
-                success: false,
-                error: Some(format!("API returned {status}: {response_text}")),
-                raw: serde_json::from_str(&response_text).ok(),
-            });
+            return Ok((
+                status,
+                AgentResponse {
+                    content: String::new(),
+                    model: config.model.clone(),
+                    usage: None,
+                    success: false,
+                    error: Some(format!("API returned {status}: {response_text}")),
+                    raw: serde_json::from_str(&response_text).ok(),
+                },
+            ));
         }
 
         let json: serde_json::Value = serde_json::from_str(&response_text)
@@ -397,14 +473,17 @@ impl AcpAgentCaller {
             .unwrap_or(&config.model)
             .to_string();
 
-        Ok(AgentResponse {
-            content,
-            model,
-            usage,
-            success: true,
-            error: None,
-            raw: Some(json),
-        })
+        Ok((
+            status,
+            AgentResponse {
+                content,
+                model,
+                usage,
+                success: true,
+                error: None,
+                raw: Some(json),
+            },
+        ))
     }
 }
 
@@ -465,4 +544,37 @@ mod tests {
         let specialist_id = AcpAgentCaller::mock_dispatch_specialist_id(&prompt);
         assert_eq!(specialist_id, None);
     }
+
+    #[tokio::test]
+    async fn open_circuit_short_circuits_calls_to_a_down_provider() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let breaker = Arc::new(CircuitBreaker::new(crate::workflow::CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown: std::time::Duration::from_secs(60),
+        }));
+        let caller = AcpAgentCaller::new()
+            .with_circuit_breaker(breaker.clone())
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 2,
+                base_delay: std::time::Duration::from_millis(1),
+                max_delay: std::time::Duration::from_millis(1),
+            });
+        let config = AgentCallConfig {
+            base_url: base_url.clone(),
+            ..AgentCallConfig::default()
+        };
+
+        let first = caller.call(&config, "hi").await.unwrap_err();
+        assert!(first.contains("HTTP request failed"), "{first}");
+        assert_eq!(
+            breaker.state(&base_url),
+            crate::workflow::BreakerState::Open
+        );
+
+        let second = caller.call(&config, "hi").await.unwrap_err();
+        assert!(second.contains("Circuit open"), "{second}");
+    }
 }
@@ -0,0 +1,278 @@
+//! Circuit breaker and retry policy for provider HTTP endpoints.
+//!
+//! Each endpoint moves through three states:
+//!
+//! ```text
+//! Closed ──N consecutive failures──► Open ──cooldown elapsed──► HalfOpen
+//!    ▲                                 ▲                           │
+//!    └──────── probe succeeds ─────────┼───── probe fails ─────────┘
+//! ```
+//!
+//! While open, calls fail fast without touching the network. Once the
+//! cooldown has passed a single probe call is let through; its outcome
+//! closes or re-opens the circuit. Transitions are logged.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// State of one endpoint's circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half-open",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit (default: 5)
+    pub failure_threshold: u32,
+    /// How long an open circuit rejects calls before probing (default: 30s)
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct EndpointCircuit {
+    state: BreakerState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl Default for EndpointCircuit {
+    fn default() -> Self {
+        Self {
+            state: BreakerState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+        }
+    }
+}
+
+/// Per-endpoint circuit breaker, keyed by endpoint URL.
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    circuits: Mutex<HashMap<String, EndpointCircuit>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Process-wide breaker shared by every [`AcpAgentCaller`](super::AcpAgentCaller)
+    /// built with `new()`, so an outage seen by one workflow protects the rest.
+    pub fn shared() -> Arc<Self> {
+        static SHARED: OnceLock<Arc<CircuitBreaker>> = OnceLock::new();
+        SHARED.get_or_init(|| Arc::new(Self::default())).clone()
+    }
+
+    pub fn state(&self, endpoint: &str) -> BreakerState {
+        self.circuits
+            .lock()
+            .unwrap()
+            .get(endpoint)
+            .map(|circuit| circuit.state)
+            .unwrap_or(BreakerState::Closed)
+    }
+
+    /// Ask to call `endpoint`. Fails fast while the circuit is open, and while
+    /// another caller's half-open probe is still in flight.
+    pub fn acquire(&self, endpoint: &str) -> Result<(), String> {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(endpoint.to_string()).or_default();
+        match circuit.state {
+            BreakerState::Closed => Ok(()),
+            // A probe that never reported back (e.g. its caller was dropped)
+            // stops blocking others once another cooldown has passed.
+            BreakerState::HalfOpen if !self.cooled_down(circuit) => Err(format!(
+                "Circuit half-open for {endpoint}: waiting on probe call"
+            )),
+            BreakerState::HalfOpen => {
+                circuit.opened_at = Some(Instant::now());
+                Ok(())
+            }
+            BreakerState::Open => {
+                let elapsed = circuit
+                    .opened_at
+                    .map(|opened_at| opened_at.elapsed())
+                    .unwrap_or(self.config.cooldown);
+                if elapsed < self.config.cooldown {
+                    let remaining = self.config.cooldown - elapsed;
+                    return Err(format!(
+                        "Circuit open for {endpoint} after {} consecutive failures; retry in {}s",
+                        circuit.consecutive_failures,
+                        remaining.as_secs_f64().ceil() as u64
+                    ));
+                }
+                circuit.state = BreakerState::HalfOpen;
+                circuit.opened_at = Some(Instant::now());
+                tracing::info!(
+                    "[CircuitBreaker] {} half-open: sending probe call",
+                    endpoint
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn cooled_down(&self, circuit: &EndpointCircuit) -> bool {
+        circuit
+            .opened_at
+            .is_none_or(|opened_at| opened_at.elapsed() >= self.config.cooldown)
+    }
+
+    pub fn record_success(&self, endpoint: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(endpoint.to_string()).or_default();
+        if circuit.state != BreakerState::Closed {
+            tracing::info!(
+                "[CircuitBreaker] {} closed: {} call succeeded",
+                endpoint,
+                circuit.state.as_str()
+            );
+        }
+        *circuit = EndpointCircuit::default();
+    }
+
+    pub fn record_failure(&self, endpoint: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(endpoint.to_string()).or_default();
+        circuit.consecutive_failures += 1;
+        let reopen = circuit.state == BreakerState::HalfOpen;
+        if reopen || circuit.consecutive_failures >= self.config.failure_threshold {
+            if circuit.state != BreakerState::Open {
+                tracing::warn!(
+                    "[CircuitBreaker] {} open for {}s after {} consecutive failures{}",
+                    endpoint,
+                    self.config.cooldown.as_secs(),
+                    circuit.consecutive_failures,
+                    if reopen { " (probe failed)" } else { "" }
+                );
+            }
+            circuit.state = BreakerState::Open;
+            circuit.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// Bounded retry with exponential backoff and jitter.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts per call, including the first (default: 3)
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each retry (default: 500ms)
+    pub base_delay: Duration,
+    /// Upper bound on the backoff before jitter (default: 5s)
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retrying after failed attempt number `attempt` (1-based):
+    /// the capped backoff plus up to half of it again as jitter.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        let jitter_range = backoff.as_millis() as u64 / 2;
+        let jitter = if jitter_range == 0 {
+            0
+        } else {
+            (uuid::Uuid::new_v4().as_u128() % u128::from(jitter_range + 1)) as u64
+        };
+        backoff + Duration::from_millis(jitter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_threshold_and_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            cooldown: Duration::from_millis(50),
+        });
+        let endpoint = "https://provider.example";
+
+        for _ in 0..2 {
+            breaker.acquire(endpoint).unwrap();
+            breaker.record_failure(endpoint);
+        }
+        assert_eq!(breaker.state(endpoint), BreakerState::Closed);
+        breaker.acquire(endpoint).unwrap();
+        breaker.record_failure(endpoint);
+        assert_eq!(breaker.state(endpoint), BreakerState::Open);
+        let error = breaker.acquire(endpoint).unwrap_err();
+        assert!(error.contains("Circuit open"), "{error}");
+
+        std::thread::sleep(Duration::from_millis(60));
+        breaker
+            .acquire(endpoint)
+            .expect("probe should be let through");
+        assert_eq!(breaker.state(endpoint), BreakerState::HalfOpen);
+        assert!(
+            breaker.acquire(endpoint).is_err(),
+            "only one probe at a time"
+        );
+
+        breaker.record_failure(endpoint);
+        assert_eq!(breaker.state(endpoint), BreakerState::Open);
+
+        std::thread::sleep(Duration::from_millis(60));
+        breaker.acquire(endpoint).unwrap();
+        breaker.record_success(endpoint);
+        assert_eq!(breaker.state(endpoint), BreakerState::Closed);
+        breaker.acquire(endpoint).unwrap();
+    }
+
+    #[test]
+    fn retry_delay_is_capped_with_bounded_jitter() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+        };
+        for _ in 0..20 {
+            let first = policy.delay(1);
+            assert!(first >= Duration::from_millis(100) && first <= Duration::from_millis(150));
+            let capped = policy.delay(4);
+            assert!(capped >= Duration::from_millis(300) && capped <= Duration::from_millis(450));
+        }
+    }
+}
@@ -7,14 +7,18 @@
 //! 4. Passes output between steps via template substitution
 //! 5. Calls agents via the AcpAgentCaller (HTTP API)
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use serde::Serialize;
+
+use crate::orchestration::SpecialistResolver;
 use crate::workflow::agent_caller::{resolve_env_vars, AcpAgentCaller, AgentCallConfig};
 use crate::workflow::schema::{OnFailure, StepAction, WorkflowDefinition, WorkflowStep};
-use crate::workflow::specialist::{SpecialistDef, SpecialistLoader};
+use crate::workflow::specialist::SpecialistDef;
 
 /// Result of executing a single workflow step.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StepResult {
     pub step_name: String,
     pub output: String,
@@ -26,7 +30,8 @@ pub struct StepResult {
 }
 
 /// Result of executing the entire workflow.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WorkflowResult {
     pub workflow_name: String,
     pub steps: Vec<StepResult>,
@@ -35,10 +40,41 @@ pub struct WorkflowResult {
     pub total_output_tokens: u64,
 }
 
+/// Structured record of a workflow run: the resolved variables, the
+/// outputs made available to later steps, and every step's result.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowRunOutput {
+    pub workflow_name: String,
+    /// `succeeded`, `failed`, or `error` when the run could not complete.
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub variables: BTreeMap<String, String>,
+    /// Step outputs keyed by step name and `output_key`.
+    pub outputs: BTreeMap<String, String>,
+    pub steps: Vec<StepResult>,
+    pub total_input_tokens: u64,
+    pub total_output_tokens: u64,
+}
+
+impl WorkflowRunOutput {
+    /// Write the run as pretty-printed JSON, creating parent directories.
+    pub fn write_to(&self, path: &std::path::Path) -> Result<(), String> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize workflow output: {e}"))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
 /// The workflow executor engine.
 pub struct WorkflowExecutor {
     caller: AcpAgentCaller,
-    specialist_loader: SpecialistLoader,
+    specialists: SpecialistResolver,
     /// Resolved variables (workflow-level + env)
     variables: HashMap<String, String>,
     /// Step outputs indexed by step name
@@ -57,51 +93,26 @@ impl Default for WorkflowExecutor {
 
 impl WorkflowExecutor {
     pub fn new() -> Self {
-        let mut specialist_loader = SpecialistLoader::new();
-        // Load from default directories
-        specialist_loader.load_default_dirs();
-
-        // Also load built-in specialists as fallback
-        for builtin in SpecialistLoader::builtin_specialists() {
-            if specialist_loader.get(&builtin.id).is_none() {
-                specialist_loader
-                    .specialists
-                    .insert(builtin.id.clone(), builtin);
-            }
-        }
-
-        Self {
-            caller: AcpAgentCaller::new(),
-            specialist_loader,
-            variables: HashMap::new(),
-            step_outputs: HashMap::new(),
-            trigger_payload: None,
-            verbose: false,
-        }
+        Self::with_specialists(SpecialistResolver::default())
     }
 
     /// Create an executor with a custom specialist directory.
     pub fn with_specialist_dir(specialist_dir: &str) -> Result<Self, String> {
-        let mut specialist_loader = SpecialistLoader::new();
-        specialist_loader.load_dir(specialist_dir)?;
-
-        // Add built-in specialists as fallback
-        for builtin in SpecialistLoader::builtin_specialists() {
-            if specialist_loader.get(&builtin.id).is_none() {
-                specialist_loader
-                    .specialists
-                    .insert(builtin.id.clone(), builtin);
-            }
-        }
+        SpecialistResolver::with_dir(specialist_dir).map(Self::with_specialists)
+    }
 
-        Ok(Self {
+    /// Create an executor that resolves step specialists with `specialists`,
+    /// the same lookup [`RoutaOrchestrator`](crate::orchestration::RoutaOrchestrator)
+    /// uses for delegation.
+    pub fn with_specialists(specialists: SpecialistResolver) -> Self {
+        Self {
             caller: AcpAgentCaller::new(),
-            specialist_loader,
+            specialists,
             variables: HashMap::new(),
             step_outputs: HashMap::new(),
             trigger_payload: None,
             verbose: false,
-        })
+        }
     }
 
     /// Set verbose mode for detailed output.
@@ -114,6 +125,40 @@ impl WorkflowExecutor {
         self.trigger_payload = Some(payload);
     }
 
+    /// The structured output of the last [`execute`](Self::execute) call.
+    pub fn run_output(&self, result: &WorkflowResult) -> WorkflowRunOutput {
+        WorkflowRunOutput {
+            workflow_name: result.workflow_name.clone(),
+            status: if result.success {
+                "succeeded"
+            } else {
+                "failed"
+            }
+            .to_string(),
+            error: None,
+            variables: self.variables.clone().into_iter().collect(),
+            outputs: self.step_outputs.clone().into_iter().collect(),
+            steps: result.steps.clone(),
+            total_input_tokens: result.total_input_tokens,
+            total_output_tokens: result.total_output_tokens,
+        }
+    }
+
+    /// Output for a run that stopped with an error before producing a
+    /// [`WorkflowResult`]; keeps whatever variables and outputs were resolved.
+    pub fn error_output(&self, workflow: &WorkflowDefinition, error: &str) -> WorkflowRunOutput {
+        WorkflowRunOutput {
+            workflow_name: workflow.name.clone(),
+            status: "error".to_string(),
+            error: Some(error.to_string()),
+            variables: self.variables.clone().into_iter().collect(),
+            outputs: self.step_outputs.clone().into_iter().collect(),
+            steps: Vec::new(),
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+        }
+    }
+
     /// Execute a workflow definition.
     pub async fn execute(
         &mut self,
@@ -336,24 +381,15 @@ impl WorkflowExecutor {
         })
     }
 
-    /// Resolve a specialist by ID (from loader or builtins).
+    /// Resolve a specialist by ID, the same way delegation does.
     fn resolve_specialist(&self, id: &str) -> Result<SpecialistDef, String> {
-        if let Some(spec) = self.specialist_loader.get(id) {
-            return Ok(spec.clone());
-        }
-
-        // Check builtins
-        for builtin in SpecialistLoader::builtin_specialists() {
-            if builtin.id == id {
-                return Ok(builtin);
-            }
-        }
-
-        Err(format!(
-            "Unknown specialist '{}'. Available: {:?}",
-            id,
-            self.specialist_loader.all().keys().collect::<Vec<_>>()
-        ))
+        self.specialists.resolve_def(id).ok_or_else(|| {
+            format!(
+                "Unknown specialist '{}'. Available: {:?}",
+                id,
+                self.specialists.available_ids()
+            )
+        })
     }
 
     /// Build the agent call configuration from step config + specialist defaults.
@@ -557,6 +593,7 @@ fn truncate(s: &str, max: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::orchestration::SpecialistConfig;
 
     #[test]
     fn test_resolve_template_steps() {
@@ -586,4 +623,73 @@ mod tests {
             "Model: GLM-4.7"
         );
     }
+
+    #[tokio::test]
+    async fn run_output_records_every_step() {
+        let workflow = WorkflowDefinition::from_yaml(
+            r#"
+name: capture
+variables:
+  target: docs
+steps:
+  - name: Skipped
+    specialist: crafter
+    if: "false"
+  - name: Broken
+    specialist: no-such-specialist
+    input: "Work on ${variables.target}"
+    on_failure: continue
+"#,
+        )
+        .unwrap();
+        let mut executor = WorkflowExecutor::new();
+        let result = executor.execute(&workflow).await.unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out/run.json");
+        executor.run_output(&result).write_to(&path).unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["status"], "failed");
+        assert_eq!(written["variables"]["target"], "docs");
+        let steps = written["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0]["stepName"], "Skipped");
+        assert_eq!(steps[0]["success"], true);
+        assert_eq!(steps[1]["stepName"], "Broken");
+        assert_eq!(steps[1]["success"], false);
+        assert!(steps[1]["error"]
+            .as_str()
+            .unwrap()
+            .contains("no-such-specialist"));
+    }
+
+    #[test]
+    fn gate_step_uses_the_delegation_prompt() {
+        let workflow = WorkflowDefinition::from_yaml(
+            r#"
+name: review
+steps:
+  - name: Review
+    specialist: gate
+    config:
+      api_key: test-key
+"#,
+        )
+        .unwrap();
+        let executor = WorkflowExecutor::new();
+        let step = &workflow.steps[0];
+
+        let specialist = executor.resolve_specialist(&step.specialist).unwrap();
+        let config = executor.build_call_config(step, &specialist).unwrap();
+
+        let delegated = SpecialistResolver::default().resolve("gate").unwrap();
+        assert_eq!(
+            delegated.system_prompt,
+            SpecialistConfig::gate().system_prompt
+        );
+        assert_eq!(Some(config.system_prompt), delegated.system_prompt_body());
+        assert_eq!(specialist.role_reminder.unwrap(), delegated.role_reminder);
+    }
 }
@@ -0,0 +1,405 @@
+//! Step dependency graph for a workflow definition.
+//!
+//! Steps run in declaration order, except that consecutive steps sharing a
+//! `parallel_group` form one stage. The graph has two kinds of edges:
+//!
+//! - **Sequence** edges from every step in a stage to every step in the next.
+//! - **Uses** edges from a step to each later step whose `input` or `if`
+//!   references its output (`${steps.<name>.output}`, or `${<output_key>}`).
+//!
+//! A step is marked unreachable when it can never see the outputs it needs:
+//! it references a step that doesn't exist or hasn't run yet, its condition
+//! is literally false, or it uses the output of another unreachable step.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use super::schema::WorkflowDefinition;
+
+/// A step in the rendered graph.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowGraphNode {
+    pub name: String,
+    /// Zero-based stage index; steps in one parallel group share a stage.
+    pub stage: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    /// Why the step can never run as written, if it can't.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unreachable: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowEdgeKind {
+    /// `to` runs after `from` completes.
+    Sequence,
+    /// `to` reads the output of `from`.
+    Uses,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct WorkflowGraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: WorkflowEdgeKind,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowGraph {
+    pub name: String,
+    pub nodes: Vec<WorkflowGraphNode>,
+    pub edges: Vec<WorkflowGraphEdge>,
+}
+
+impl WorkflowGraph {
+    /// Build the graph for a workflow. Validates the definition first.
+    pub fn from_definition(workflow: &WorkflowDefinition) -> Result<Self, String> {
+        workflow.validate()?;
+
+        let mut stages: Vec<usize> = Vec::with_capacity(workflow.steps.len());
+        for (index, step) in workflow.steps.iter().enumerate() {
+            let joins_previous = index > 0
+                && step.parallel_group.is_some()
+                && step.parallel_group == workflow.steps[index - 1].parallel_group;
+            let stage = match stages.last() {
+                Some(last) if joins_previous => *last,
+                Some(last) => last + 1,
+                None => 0,
+            };
+            stages.push(stage);
+        }
+
+        // Output name (step name or output_key) → index of the producing step.
+        let mut producers: HashMap<&str, usize> = HashMap::new();
+        for (index, step) in workflow.steps.iter().enumerate() {
+            producers.insert(step.name.as_str(), index);
+            if let Some(key) = step.output_key.as_deref() {
+                producers.entry(key).or_insert(index);
+            }
+        }
+
+        let mut edges = BTreeSet::new();
+        for (index, step) in workflow.steps.iter().enumerate() {
+            let Some(previous_stage) = stages[index].checked_sub(1) else {
+                continue;
+            };
+            for (from, stage) in stages.iter().enumerate() {
+                if *stage == previous_stage {
+                    edges.insert(WorkflowGraphEdge {
+                        from: workflow.steps[from].name.clone(),
+                        to: step.name.clone(),
+                        kind: WorkflowEdgeKind::Sequence,
+                    });
+                }
+            }
+        }
+
+        let mut unreachable: Vec<Option<String>> = vec![None; workflow.steps.len()];
+        for (index, step) in workflow.steps.iter().enumerate() {
+            if let Some(condition) = step.condition.as_deref() {
+                if matches!(condition.trim(), "" | "false") {
+                    unreachable[index] = Some("condition is always false".to_string());
+                }
+            }
+
+            let templates = step.input.iter().chain(step.condition.iter());
+            for reference in templates.flat_map(|template| step_references(template)) {
+                let producer = producers.get(reference.name.as_str()).copied();
+                let reason = match producer {
+                    Some(from) if from != index => {
+                        edges.insert(WorkflowGraphEdge {
+                            from: workflow.steps[from].name.clone(),
+                            to: step.name.clone(),
+                            kind: WorkflowEdgeKind::Uses,
+                        });
+                        let from_name = &workflow.steps[from].name;
+                        if stages[from] >= stages[index] {
+                            Some(format!("uses '{from_name}' before it runs"))
+                        } else if unreachable[from].is_some() {
+                            Some(format!("uses unreachable step '{from_name}'"))
+                        } else {
+                            None
+                        }
+                    }
+                    Some(_) => Some("uses its own output".to_string()),
+                    // Bare `${name}` may be a variable or env var; only the
+                    // explicit `steps.` form must name a step.
+                    None if reference.explicit => {
+                        Some(format!("uses unknown step '{}'", reference.name))
+                    }
+                    None => None,
+                };
+                if unreachable[index].is_none() {
+                    unreachable[index] = reason;
+                }
+            }
+        }
+
+        let nodes = workflow
+            .steps
+            .iter()
+            .zip(stages)
+            .zip(unreachable)
+            .map(|((step, stage), unreachable)| WorkflowGraphNode {
+                name: step.name.clone(),
+                stage,
+                parallel_group: step.parallel_group.clone(),
+                condition: step.condition.clone(),
+                unreachable,
+            })
+            .collect();
+
+        Ok(Self {
+            name: workflow.name.clone(),
+            nodes,
+            edges: edges.into_iter().collect(),
+        })
+    }
+
+    /// Steps whose output `name` uses.
+    fn sources_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.kind == WorkflowEdgeKind::Uses && edge.to == name)
+            .map(|edge| edge.from.as_str())
+    }
+
+    /// Render as Graphviz DOT. Parallel groups become clusters, `uses` edges
+    /// are dashed and unreachable steps are greyed out.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph workflow {\n");
+        out.push_str(&format!("  label=\"{}\";\n", escape_dot(&self.name)));
+        out.push_str("  rankdir=TB;\n");
+        out.push_str("  node [shape=box, style=filled];\n");
+        out.push_str("  edge [fontsize=10];\n");
+
+        let mut index = 0;
+        while index < self.nodes.len() {
+            let node = &self.nodes[index];
+            let stage_len = self.nodes[index..]
+                .iter()
+                .take_while(|other| other.stage == node.stage)
+                .count();
+            let clustered = stage_len > 1;
+            if clustered {
+                out.push_str(&format!("  subgraph \"cluster_{}\" {{\n", node.stage));
+                out.push_str(&format!(
+                    "    label=\"parallel: {}\";\n    style=dashed;\n",
+                    escape_dot(node.parallel_group.as_deref().unwrap_or_default())
+                ));
+            }
+            let indent = if clustered { "    " } else { "  " };
+            for node in &self.nodes[index..index + stage_len] {
+                let mut label = escape_dot(&node.name);
+                if let Some(condition) = node.condition.as_deref() {
+                    label.push_str(&format!("\\nif: {}", escape_dot(condition)));
+                }
+                let (color, shape) = match (&node.unreachable, &node.condition) {
+                    (Some(reason), _) => {
+                        label.push_str(&format!("\\nunreachable: {}", escape_dot(reason)));
+                        ("lightgray", "box")
+                    }
+                    (None, Some(_)) => ("lightyellow", "diamond"),
+                    (None, None) => ("white", "box"),
+                };
+                out.push_str(&format!(
+                    "{indent}\"{}\" [label=\"{label}\", fillcolor={color}, shape={shape}];\n",
+                    escape_dot(&node.name)
+                ));
+            }
+            if clustered {
+                out.push_str("  }\n");
+            }
+            index += stage_len;
+        }
+
+        for edge in &self.edges {
+            let (label, style) = match edge.kind {
+                WorkflowEdgeKind::Sequence => ("then", "solid"),
+                WorkflowEdgeKind::Uses => ("output", "dashed"),
+            };
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [label=\"{label}\", style={style}];\n",
+                escape_dot(&edge.from),
+                escape_dot(&edge.to)
+            ));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render as an indented outline, one line per stage (or per step within
+    /// a parallel group).
+    pub fn to_ascii(&self) -> String {
+        let mut out = format!("{}\n", self.name);
+        let mut index = 0;
+        while index < self.nodes.len() {
+            let node = &self.nodes[index];
+            let stage = &self.nodes[index..]
+                .iter()
+                .take_while(|other| other.stage == node.stage)
+                .collect::<Vec<_>>();
+            if stage.len() == 1 {
+                out.push_str(&format!(
+                    "  {}. {}{}\n",
+                    node.stage + 1,
+                    node.name,
+                    self.ascii_annotations(node)
+                ));
+            } else {
+                out.push_str(&format!(
+                    "  {}. parallel '{}'\n",
+                    node.stage + 1,
+                    node.parallel_group.as_deref().unwrap_or_default()
+                ));
+                for (position, member) in stage.iter().enumerate() {
+                    let branch = if position + 1 == stage.len() {
+                        "└─"
+                    } else {
+                        "├─"
+                    };
+                    out.push_str(&format!(
+                        "     {branch} {}{}\n",
+                        member.name,
+                        self.ascii_annotations(member)
+                    ));
+                }
+            }
+            index += stage.len();
+        }
+        out
+    }
+
+    fn ascii_annotations(&self, node: &WorkflowGraphNode) -> String {
+        let mut annotations = String::new();
+        if let Some(condition) = node.condition.as_deref() {
+            annotations.push_str(&format!(" [if: {condition}]"));
+        }
+        let uses: Vec<&str> = self.sources_of(&node.name).collect();
+        if !uses.is_empty() {
+            annotations.push_str(&format!(" ← uses {}", uses.join(", ")));
+        }
+        if let Some(reason) = node.unreachable.as_deref() {
+            annotations.push_str(&format!(" ⚠ unreachable: {reason}"));
+        }
+        annotations
+    }
+}
+
+struct StepReference {
+    name: String,
+    /// Written as `${steps.<name>.output}` rather than a bare `${<name>}`.
+    explicit: bool,
+}
+
+/// Output references in a template, in the forms the executor resolves.
+fn step_references(template: &str) -> Vec<StepReference> {
+    let explicit_re = regex::Regex::new(r"\$\{steps\.([^.]+)\.output\}").unwrap();
+    let bare_re = regex::Regex::new(r"\$\{([^}.]+)\}").unwrap();
+    let explicit = explicit_re
+        .captures_iter(template)
+        .map(|caps| StepReference {
+            name: caps[1].to_string(),
+            explicit: true,
+        });
+    let bare = bare_re.captures_iter(template).map(|caps| StepReference {
+        name: caps[1].to_string(),
+        explicit: false,
+    });
+    explicit.chain(bare).collect()
+}
+
+fn escape_dot(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(yaml: &str) -> WorkflowGraph {
+        WorkflowGraph::from_definition(&WorkflowDefinition::from_yaml(yaml).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn dot_output_includes_edges_for_step_dependencies() {
+        let graph = graph(
+            r#"
+name: "Review Flow"
+steps:
+  - name: "Plan"
+    specialist: "routa"
+    output_key: "plan"
+  - name: "Lint"
+    specialist: "gate"
+    parallel_group: "checks"
+    input: "${steps.Plan.output}"
+  - name: "Test"
+    specialist: "gate"
+    parallel_group: "checks"
+    input: "${plan}"
+  - name: "Ship"
+    specialist: "crafter"
+    if: "${steps.Test.output}"
+"#,
+        );
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"Plan\" -> \"Lint\" [label=\"output\", style=dashed];"));
+        assert!(dot.contains("\"Plan\" -> \"Test\" [label=\"output\", style=dashed];"));
+        assert!(dot.contains("\"Test\" -> \"Ship\" [label=\"output\", style=dashed];"));
+        assert!(dot.contains("\"Plan\" -> \"Test\" [label=\"then\", style=solid];"));
+        assert!(dot.contains("\"Lint\" -> \"Ship\" [label=\"then\", style=solid];"));
+        assert!(dot.contains("\"Test\" -> \"Ship\" [label=\"then\", style=solid];"));
+        assert!(dot.contains("subgraph \"cluster_1\""));
+        assert!(graph.nodes.iter().all(|node| node.unreachable.is_none()));
+    }
+
+    #[test]
+    fn steps_using_later_or_unknown_outputs_are_unreachable() {
+        let graph = graph(
+            r#"
+name: "Broken"
+steps:
+  - name: "Early"
+    specialist: "crafter"
+    input: "${steps.Late.output}"
+  - name: "Late"
+    specialist: "crafter"
+  - name: "Orphan"
+    specialist: "crafter"
+    input: "${steps.Missing.output} and ${HOME}"
+  - name: "Downstream"
+    specialist: "crafter"
+    input: "${steps.Early.output}"
+"#,
+        );
+
+        let reasons: Vec<Option<&str>> = graph
+            .nodes
+            .iter()
+            .map(|node| node.unreachable.as_deref())
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                Some("uses 'Late' before it runs"),
+                None,
+                Some("uses unknown step 'Missing'"),
+                Some("uses unreachable step 'Early'"),
+            ]
+        );
+        assert!(graph
+            .to_ascii()
+            .contains("1. Early ← uses Late ⚠ unreachable: uses 'Late' before it runs"));
+    }
+}
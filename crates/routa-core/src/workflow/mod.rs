@@ -16,11 +16,15 @@
 //! ```
 
 pub mod agent_caller;
+pub mod circuit_breaker;
 pub mod executor;
+pub mod graph;
 pub mod schema;
 pub mod specialist;
 
 pub use agent_caller::AcpAgentCaller;
+pub use circuit_breaker::{BreakerState, CircuitBreaker, CircuitBreakerConfig, RetryPolicy};
 pub use executor::WorkflowExecutor;
+pub use graph::WorkflowGraph;
 pub use schema::{OnFailure, StepAction, TriggerConfig, WorkflowDefinition, WorkflowStep};
 pub use specialist::{SpecialistDef, SpecialistLoader};
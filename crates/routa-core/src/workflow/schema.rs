@@ -8,7 +8,7 @@
 //! version: "1.0"
 //!
 //! trigger:
-//!   type: manual      # manual | webhook | schedule
+//!   type: manual      # manual | webhook | cron (alias: schedule) | file_watch
 //!
 //! variables:
 //!   model: "GLM-4.7"
@@ -71,7 +71,8 @@ fn default_version() -> String {
 /// Trigger configuration — how/when the workflow runs.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TriggerConfig {
-    /// Trigger type: "manual", "webhook", "schedule"
+    /// Trigger type: "manual", "webhook", "cron" (or its alias "schedule"),
+    /// "file_watch"
     #[serde(rename = "type", default = "default_trigger_type")]
     pub trigger_type: String,
 
@@ -83,9 +84,30 @@ pub struct TriggerConfig {
     #[serde(default)]
     pub event: Option<String>,
 
-    /// For schedule triggers: cron expression
+    /// For cron triggers: cron expression, with an optional leading seconds
+    /// field (e.g. `"0 3 * * 1"` or `"*/30 * * * * *"`)
     #[serde(default)]
     pub cron: Option<String>,
+
+    /// For file_watch triggers: files or directories to watch (recursively).
+    /// Relative paths resolve against the server's working directory.
+    #[serde(default)]
+    pub paths: Vec<String>,
+
+    /// For cron and file_watch triggers: workspace the run's tasks are
+    /// created in (defaults to `default`)
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+}
+
+impl TriggerConfig {
+    pub fn is_cron(&self) -> bool {
+        matches!(self.trigger_type.as_str(), "cron" | "schedule")
+    }
+
+    pub fn is_file_watch(&self) -> bool {
+        self.trigger_type == "file_watch"
+    }
 }
 
 fn default_trigger_type() -> String {
@@ -246,6 +268,24 @@ impl WorkflowDefinition {
             .map_err(|e| format!("Failed to read workflow file '{path}': {e}"))?;
         Self::from_yaml(&content)
     }
+
+    /// Check the structural rules the YAML schema can't express: at least one
+    /// step, and step names that are non-empty and unique.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.steps.is_empty() {
+            return Err(format!("Workflow '{}' has no steps", self.name));
+        }
+        let mut seen = std::collections::HashSet::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            if step.name.trim().is_empty() {
+                return Err(format!("Step {} has an empty name", index + 1));
+            }
+            if !seen.insert(step.name.as_str()) {
+                return Err(format!("Duplicate step name '{}'", step.name));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -310,4 +350,18 @@ steps:
         assert_eq!(wf.steps[0].actions.len(), 2);
         assert!(wf.steps[1].condition.is_some());
     }
+
+    #[test]
+    fn test_validate_rejects_duplicate_step_names() {
+        let yaml = r#"
+name: "Dup"
+steps:
+  - name: "Build"
+    specialist: "crafter"
+  - name: "Build"
+    specialist: "gate"
+"#;
+        let wf = WorkflowDefinition::from_yaml(yaml).unwrap();
+        assert_eq!(wf.validate().unwrap_err(), "Duplicate step name 'Build'");
+    }
 }
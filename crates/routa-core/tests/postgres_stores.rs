@@ -0,0 +1,166 @@
+//! Workspace and task store CRUD against Postgres.
+//!
+//! Needs the `postgres` feature and a database URL in
+//! `ROUTA_TEST_POSTGRES_URL`, e.g.
+//! `ROUTA_TEST_POSTGRES_URL=postgres://postgres@localhost/routa_test cargo test -p routa-core --features postgres --test postgres_stores`.
+//! Without the URL the tests pass without doing anything.
+
+#![cfg(feature = "postgres")]
+
+use routa_core::db::{DbBackend, PgDatabase};
+use routa_core::models::task::{Task, TaskStatus};
+use routa_core::models::workspace::{Workspace, WorkspaceStatus};
+use routa_core::store::{TaskStore, TimeRange, WorkspaceStore};
+
+async fn backend() -> Option<DbBackend> {
+    let Ok(url) = std::env::var("ROUTA_TEST_POSTGRES_URL") else {
+        eprintln!("ROUTA_TEST_POSTGRES_URL not set; skipping");
+        return None;
+    };
+    let db = PgDatabase::connect(&url)
+        .await
+        .expect("connect to ROUTA_TEST_POSTGRES_URL");
+    Some(db.into())
+}
+
+fn unique(prefix: &str) -> String {
+    format!("{prefix}-{}", uuid::Uuid::new_v4())
+}
+
+#[tokio::test]
+async fn workspace_crud_roundtrip() {
+    let Some(backend) = backend().await else {
+        return;
+    };
+    let store = WorkspaceStore::with_backend(backend);
+    let id = unique("ws");
+    let mut metadata = std::collections::HashMap::new();
+    metadata.insert("env".to_string(), "dev".to_string());
+    store
+        .save(&Workspace::new(
+            id.clone(),
+            "Workspace 1".to_string(),
+            Some(metadata),
+        ))
+        .await
+        .expect("save should succeed");
+
+    let loaded = store
+        .get(&id)
+        .await
+        .expect("get should succeed")
+        .expect("workspace should exist");
+    assert_eq!(loaded.title, "Workspace 1");
+    assert_eq!(loaded.status, WorkspaceStatus::Active);
+    assert_eq!(loaded.metadata.get("env").map(String::as_str), Some("dev"));
+    let all = store.list().await.expect("list should succeed");
+    assert!(all.iter().any(|ws| ws.id == id));
+
+    store
+        .update_title(&id, "New Title")
+        .await
+        .expect("update_title should succeed");
+    store
+        .update_status(&id, "archived")
+        .await
+        .expect("update_status should succeed");
+    let archived = store
+        .list_by_status(WorkspaceStatus::Archived)
+        .await
+        .expect("list_by_status should succeed");
+    let ws = archived
+        .iter()
+        .find(|ws| ws.id == id)
+        .expect("archived workspace listed");
+    assert_eq!(ws.title, "New Title");
+
+    let default = store
+        .ensure_default()
+        .await
+        .expect("ensure_default should succeed");
+    assert_eq!(default.id, "default");
+    assert_eq!(
+        store.ensure_default().await.expect("second call").id,
+        "default"
+    );
+
+    store.delete(&id).await.expect("delete should succeed");
+    assert!(store.get(&id).await.expect("get should succeed").is_none());
+}
+
+#[tokio::test]
+async fn task_crud_roundtrip() {
+    let Some(backend) = backend().await else {
+        return;
+    };
+    let workspaces = WorkspaceStore::with_backend(backend.clone());
+    let tasks = TaskStore::with_backend(backend);
+    let ws_id = unique("ws");
+    workspaces
+        .save(&Workspace::new(ws_id.clone(), "Tasks".to_string(), None))
+        .await
+        .expect("save workspace");
+
+    let mut task = Task::new(
+        unique("task"),
+        "Add login form".to_string(),
+        "Users can sign in with email".to_string(),
+        ws_id.clone(),
+        Some("session-1".to_string()),
+        Some("auth".to_string()),
+        Some(vec!["form renders".to_string()]),
+        None,
+        None,
+        None,
+        None,
+    );
+    task.labels = vec!["frontend".to_string()];
+    task.assigned_to = Some("agent-1".to_string());
+    task.status = TaskStatus::InProgress;
+    tasks.save(&task).await.expect("save task");
+
+    let loaded = tasks
+        .get(&task.id)
+        .await
+        .expect("get task")
+        .expect("task exists");
+    assert_eq!(loaded.title, task.title);
+    assert_eq!(loaded.scope.as_deref(), Some("auth"));
+    assert_eq!(loaded.acceptance_criteria, task.acceptance_criteria);
+    assert_eq!(loaded.labels, task.labels);
+    assert_eq!(loaded.session_id.as_deref(), Some("session-1"));
+
+    let listed = tasks.list_by_workspace(&ws_id).await.expect("list tasks");
+    assert_eq!(listed.len(), 1);
+    let before_save = TimeRange {
+        created_before: Some(task.created_at - chrono::Duration::seconds(1)),
+        ..Default::default()
+    };
+    let in_range = tasks
+        .list_in_range(&ws_id, &before_save)
+        .await
+        .expect("list in range");
+    assert!(in_range.is_empty());
+    let hits = tasks.search(&ws_id, "LOGIN", 10).await.expect("search");
+    assert_eq!(hits.len(), 1);
+    let workload = tasks.workload(&ws_id).await.expect("workload");
+    assert_eq!(workload[0].agent_id, "agent-1");
+    assert_eq!(
+        workload[0].current_task.as_ref().map(|t| t.id.as_str()),
+        Some(task.id.as_str())
+    );
+
+    tasks
+        .update_status(&task.id, &TaskStatus::Completed)
+        .await
+        .expect("update status");
+    let completed = tasks
+        .list_by_status(&ws_id, &TaskStatus::Completed)
+        .await
+        .expect("list by status");
+    assert_eq!(completed.len(), 1);
+
+    tasks.delete(&task.id).await.expect("delete task");
+    assert!(tasks.get(&task.id).await.expect("get task").is_none());
+    workspaces.delete(&ws_id).await.expect("delete workspace");
+}
@@ -47,6 +47,22 @@
 //! })).await;
 //! ```
 //!
+//! # Example — embedder-defined methods
+//!
+//! Handlers added with `register` are consulted before the built-in methods,
+//! so transports can expose their own namespace (e.g. `native.*`):
+//!
+//! ```ignore
+//! use routa_rpc::{RpcError, RpcRouter};
+//!
+//! let router = RpcRouter::new(app_state).register("native.openWindow", |_state, params| async move {
+//!     let label = params["label"].as_str().ok_or_else(|| {
+//!         RpcError::InvalidParams("label is required".into())
+//!     })?;
+//!     Ok(serde_json::json!({ "opened": label }))
+//! });
+//! ```
+//!
 //! # Supported Methods
 //!
 //! | Domain       | Method               | Description                    |
@@ -56,26 +72,48 @@
 //! | agents      | `agents.create`      | Create a new agent             |
 //! | agents      | `agents.delete`      | Delete an agent                |
 //! | agents      | `agents.updateStatus`| Update agent status            |
+//! | agents      | `agents.reportToParent` | Report task completion to parent |
 //! | tasks       | `tasks.list`         | List tasks with filters        |
 //! | tasks       | `tasks.get`          | Get task by id                 |
 //! | tasks       | `tasks.create`       | Create a new task              |
+//! | tasks       | `tasks.createBatch`  | Create linked tasks at once    |
+//! | tasks       | `tasks.update`       | Edit title/objective/scope     |
 //! | tasks       | `tasks.delete`       | Delete a task                  |
 //! | tasks       | `tasks.updateStatus` | Update task status             |
+//! | tasks       | `tasks.setCriterionStatus` | Check off one acceptance criterion |
 //! | tasks       | `tasks.findReady`    | Find ready tasks               |
+//! | tasks       | `tasks.search`       | Search task titles/objectives  |
 //! | notes       | `notes.list`         | List notes with filters        |
 //! | notes       | `notes.get`          | Get note by id                 |
+//! | notes       | `notes.assignedTo`   | Notes assigned to an agent     |
 //! | notes       | `notes.create`       | Create or update a note        |
+//! | notes       | `notes.rename`       | Change a note's title          |
 //! | notes       | `notes.delete`       | Delete a note                  |
+//! | notes       | `notes.history`      | List note content versions     |
+//! | notes       | `notes.revert`       | Restore a note version         |
+//! | orchestration | `orchestration.status` | Live delegations and wait groups |
+//! | providers   | `providers.list`     | Builtin, registry and installed providers |
+//! | providers   | `providers.setCredential` | Store an encrypted provider credential |
+//! | providers   | `providers.getMasked` | Stored credentials, masked    |
+//! | providers   | `providers.refreshModels` | Re-list a provider's models, bypassing the cache |
+//! | sessions    | `sessions.setMetadata` | Replace session metadata     |
 //! | workspaces  | `workspaces.list`    | List all workspaces            |
 //! | workspaces  | `workspaces.get`     | Get workspace by id            |
 //! | workspaces  | `workspaces.create`  | Create a new workspace         |
+//! | workspaces  | `workspaces.rename`  | Change a workspace's title     |
 //! | workspaces  | `workspaces.delete`  | Delete a workspace             |
+//! | workspaces  | `workspaces.workload` | Per-agent task counts by status |
+//! | codebases   | `codebases.checkout` | Check out and record a branch  |
+//! | codebases   | `codebases.currentBranch` | Live checked-out branch   |
+//! | db          | `db.backup`          | Snapshot the database into `~/.routa/backups` |
 //! | skills      | `skills.list`        | List discovered skills         |
 //! | skills      | `skills.get`         | Get skill by name              |
 //! | skills      | `skills.reload`      | Re-discover skills             |
+//! | skills      | `skills.topInstalled`| Most installed skills          |
+//! | skills      | `skills.use`         | Skill instructions for an agent's context |
 
 // Re-export the core RPC types and router from routa-core
-pub use routa_core::rpc::error::RpcError;
+pub use routa_core::rpc::error::{FieldError, RpcError};
 pub use routa_core::rpc::router::RpcRouter;
 pub use routa_core::rpc::types::{
     JsonRpcError, JsonRpcRequest, JsonRpcResponse, BAD_REQUEST, INTERNAL_ERROR, INVALID_PARAMS,
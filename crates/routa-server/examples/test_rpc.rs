@@ -12,6 +12,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         port: 0, // random port
         db_path: ":memory:".to_string(),
         static_dir: None,
+        workflows_dir: None,
+        workspace_tokens: None,
+        tls_cert_path: None,
+        tls_key_path: None,
+        max_sessions_per_workspace: None,
+        db_encryption_key: None,
     };
 
     let addr = routa_server::start_server(config).await?;
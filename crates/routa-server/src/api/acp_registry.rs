@@ -14,7 +14,9 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::acp::{get_presets, AcpPaths, DistributionType, RuntimeType, WarmupStatus};
+use crate::acp::{
+    deserialize_agents_lenient, get_presets, AcpPaths, DistributionType, RuntimeType, WarmupStatus,
+};
 use crate::error::ServerError;
 use crate::shell_env;
 use crate::state::AppState;
@@ -44,18 +46,26 @@ struct RegistryQuery {
 pub struct RegistryAgent {
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub version: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub repository: Option<String>,
+    #[serde(default)]
     pub authors: Vec<String>,
+    #[serde(default)]
     pub license: String,
+    #[serde(default)]
     pub icon: Option<String>,
     pub distribution: serde_json::Value,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AcpRegistry {
+    #[serde(default)]
     pub version: String,
+    #[serde(default, deserialize_with = "deserialize_agents_lenient")]
     pub agents: Vec<RegistryAgent>,
 }
 
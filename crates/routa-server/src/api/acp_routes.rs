@@ -10,16 +10,16 @@ use axum::{
 };
 use serde::Deserialize;
 use std::convert::Infallible;
-use std::sync::Arc;
 use tokio_stream::StreamExt as _;
 
+use super::sse;
 use crate::acp;
 use crate::error::ServerError;
 use crate::state::AppState;
 use routa_core::acp::terminal_manager::TerminalManager;
 use routa_core::acp::SessionLaunchOptions;
 use routa_core::models::agent::{Agent, AgentRole};
-use routa_core::orchestration::{OrchestratorConfig, RoutaOrchestrator, SpecialistConfig};
+use routa_core::orchestration::SpecialistConfig;
 use routa_core::storage::{LocalSessionProvider, SessionRecord};
 use routa_core::store::acp_session_store::{AcpSessionRow, CreateAcpSessionParams};
 
@@ -202,14 +202,7 @@ async fn ensure_routa_agent_registration(
         routa_agent_id = Some(agent.id);
     }
 
-    let acp = Arc::new(state.acp_manager.clone());
-    let orchestrator = RoutaOrchestrator::new(
-        OrchestratorConfig::default(),
-        acp,
-        state.agent_store.clone(),
-        state.task_store.clone(),
-        state.event_bus.clone(),
-    );
+    let orchestrator = state.orchestrator.clone();
     let routa_agent_id = routa_agent_id.expect("routa agent id must exist for ROUTA session");
     orchestrator
         .register_agent_session(&routa_agent_id, session_id)
@@ -453,6 +446,10 @@ async fn acp_rpc(
                 .get("worktreeId")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string());
+            let metadata = params
+                .get("metadata")
+                .filter(|value| value.is_object())
+                .cloned();
 
             let mut cwd =
                 resolve_session_cwd(&state, &workspace_id, requested_cwd.as_deref()).await;
@@ -518,14 +515,18 @@ async fn acp_rpc(
                     .map(str::to_string)
                     .or_else(|| specialist.as_ref().and_then(build_specialist_system_prompt)),
                 allowed_native_tools: derive_allowed_native_tools(specialist_id.as_deref()),
+                metadata: metadata.clone(),
                 ..SessionLaunchOptions::default()
             };
             let persisted_custom_provider_launch = custom_provider_launch.clone();
-            let effective_provider = provider.clone().or_else(|| {
-                custom_provider_launch
-                    .as_ref()
-                    .map(|custom| custom.command.clone())
-            });
+            let effective_provider = provider
+                .clone()
+                .or_else(|| {
+                    custom_provider_launch
+                        .as_ref()
+                        .map(|custom| custom.command.clone())
+                })
+                .or_else(|| acp::AcpManager::detect_provider(&cwd).map(str::to_string));
 
             // Spawn agent process, initialize protocol, create agent session
             let create_result = if let Some(custom) = custom_provider_launch {
@@ -594,6 +595,7 @@ async fn acp_rpc(
                                 .as_ref()
                                 .map(|launch| launch.args.as_slice()),
                             parent_session_id: parent_session_id.as_deref(),
+                            metadata: metadata.as_ref(),
                         })
                         .await
                     {
@@ -824,6 +826,9 @@ async fn acp_rpc(
                         .map(str::to_string)
                         .or_else(|| specialist.as_ref().and_then(build_specialist_system_prompt)),
                     allowed_native_tools: derive_allowed_native_tools(specialist_id.as_deref()),
+                    metadata: persisted_session
+                        .as_ref()
+                        .map(|session| session.metadata.clone()),
                     ..SessionLaunchOptions::default()
                 };
 
@@ -891,6 +896,7 @@ async fn acp_rpc(
                                     .as_ref()
                                     .map(|launch| launch.args.as_slice()),
                                 parent_session_id: parent_session_id.as_deref(),
+                                metadata: None,
                             })
                             .await
                         {
@@ -1129,7 +1135,9 @@ async fn acp_rpc(
                                         == Some("turn_complete");
 
                                     yield Ok::<_, Infallible>(
-                                        Event::default().data(rewritten.to_string())
+                                        Event::default()
+                                            .event(sse::SESSION_UPDATE)
+                                            .data(rewritten.to_string())
                                     );
 
                                     if is_turn_complete {
@@ -1140,16 +1148,18 @@ async fn acp_rpc(
                                         break;
                                     }
                                 }
-                                Err(e) => {
+                                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
                                     tracing::warn!(
-                                        "[ACP Route] SSE stream error for session {}: {}",
+                                        "[ACP Route] SSE subscriber for session {} skipped {} updates",
                                         session_id_clone,
-                                        e
+                                        skipped
                                     );
-                                    break;
+                                    yield Ok(sse::lagged(skipped));
                                 }
+                                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                             }
                         }
+                        yield Ok(sse::done());
                         // Persist history and mark first_prompt_sent after turn completes
                         let _ = state_clone.acp_session_store.set_first_prompt_sent(&session_id_clone).await;
                         if let Some(history) = state_clone.acp_manager.get_session_history(&session_id_clone).await {
@@ -1158,29 +1168,36 @@ async fn acp_rpc(
                     })
                 } else {
                     // No broadcast channel - return empty stream with error
-                    Box::pin(tokio_stream::once(Ok::<_, Infallible>(
-                        Event::default().data(
-                            serde_json::json!({
-                                "jsonrpc": "2.0",
-                                "method": "session/update",
-                                "params": {
-                                    "sessionId": session_id,
-                                    "update": {
-                                        "sessionUpdate": "turn_complete",
-                                        "stopReason": "error"
+                    Box::pin(tokio_stream::iter([
+                        Ok::<_, Infallible>(
+                            Event::default().event(sse::SESSION_UPDATE).data(
+                                serde_json::json!({
+                                    "jsonrpc": "2.0",
+                                    "method": "session/update",
+                                    "params": {
+                                        "sessionId": session_id,
+                                        "update": {
+                                            "sessionUpdate": "turn_complete",
+                                            "stopReason": "error"
+                                        }
                                     }
-                                }
-                            })
-                            .to_string(),
+                                })
+                                .to_string(),
+                            ),
                         ),
-                    )))
+                        Ok(sse::done()),
+                    ]))
                 };
 
                 return Ok(AcpResponse::Sse(Sse::new(stream)));
             }
 
             // For ACP providers, use the traditional JSON response
-            match state.acp_manager.prompt(&session_id, &prompt_text).await {
+            match state
+                .acp_manager
+                .prompt_raw(&session_id, &prompt_text)
+                .await
+            {
                 Ok(result) => {
                     // Persist history and mark first_prompt_sent after turn completes
                     let _ = state
@@ -1675,12 +1692,26 @@ async fn acp_rpc(
         }
 
         "session/set_mode" => {
-            let _session_id = params.get("sessionId").and_then(|v| v.as_str());
+            let session_id = params.get("sessionId").and_then(|v| v.as_str());
             let _mode_id = params
                 .get("modeId")
                 .or_else(|| params.get("mode"))
                 .and_then(|v| v.as_str());
 
+            if let Some(session_id) = session_id {
+                let capabilities = state.acp_manager.get_capabilities(session_id).await;
+                if capabilities.is_some_and(|capabilities| !capabilities.supports_set_mode()) {
+                    return Ok(AcpResponse::Json(Json(serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": -32601,
+                            "message": "The session's agent does not support session modes"
+                        }
+                    }))));
+                }
+            }
+
             // Acknowledge (mode switching stub)
             Ok(AcpResponse::Json(Json(serde_json::json!({
                 "jsonrpc": "2.0",
@@ -1845,10 +1876,11 @@ fn sse_event_id_from_rpc_message(message: &serde_json::Value) -> Option<String>
 
 fn sse_event_from_rpc_message(message: serde_json::Value) -> Event {
     let payload = message.to_string();
+    let event = Event::default().event(sse::SESSION_UPDATE);
     if let Some(event_id) = sse_event_id_from_rpc_message(&message) {
-        Event::default().id(event_id).data(payload)
+        event.id(event_id).data(payload)
     } else {
-        Event::default().data(payload)
+        event.data(payload)
     }
 }
 
@@ -1899,20 +1931,14 @@ async fn acp_sse(State(state): State<AppState>, Query(query): Query<AcpSseQuery>
     let heartbeat = tokio_stream::wrappers::IntervalStream::new(tokio::time::interval(
         std::time::Duration::from_secs(15),
     ))
-    .map(|_| Ok(Event::default().comment("heartbeat")));
+    .map(|_| Ok(sse::heartbeat()));
 
     type SseStream =
         std::pin::Pin<Box<dyn tokio_stream::Stream<Item = Result<Event, Infallible>> + Send>>;
 
     // Subscribe to agent notifications for this session
-    let stream: SseStream = if let Some(mut rx) = state.acp_manager.subscribe(&session_id).await {
-        let notifications = async_stream::stream! {
-            while let Ok(msg) = rx.recv().await {
-                yield Ok::<_, Infallible>(
-                    sse_event_from_rpc_message(msg)
-                );
-            }
-        };
+    let stream: SseStream = if let Some(rx) = state.acp_manager.subscribe(&session_id).await {
+        let notifications = sse::broadcast_events(rx, sse_event_from_rpc_message);
         // Merge initial + notifications + heartbeat
         Box::pin(
             initial.chain(replay.chain(tokio_stream::StreamExt::merge(notifications, heartbeat))),
@@ -1976,8 +2002,8 @@ mod tests {
     use super::{
         acp_rpc, consolidate_replay_events, custom_provider_launch_from_row,
         extract_custom_provider_launch, has_explicit_cwd, history_since_event_id,
-        resolve_session_cwd, should_attempt_native_resume, sse_event_id_from_rpc_message,
-        AcpResponse, CustomProviderLaunch,
+        resolve_session_cwd, should_attempt_native_resume, sse, sse_event_from_rpc_message,
+        sse_event_id_from_rpc_message, AcpResponse, CustomProviderLaunch,
     };
     use routa_core::acp::terminal_manager::TerminalManager;
 
@@ -2108,6 +2134,7 @@ mod tests {
             created_at: 1,
             updated_at: 1,
             parent_session_id: None,
+            metadata: serde_json::json!({}),
         };
 
         let launch = custom_provider_launch_from_row(&session).expect("launch should exist");
@@ -2138,6 +2165,7 @@ mod tests {
             created_at: 1,
             updated_at: 1,
             parent_session_id: None,
+            metadata: serde_json::json!({}),
         };
 
         assert!(!should_attempt_native_resume(&codex_session, "codex"));
@@ -2250,6 +2278,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("session should persist");
@@ -2299,6 +2328,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("session should persist");
@@ -2421,4 +2451,52 @@ mod tests {
             Some("Persisted session not found: missing-session")
         );
     }
+
+    #[tokio::test]
+    async fn sse_frames_carry_event_names() {
+        let events = vec![
+            Ok::<_, std::convert::Infallible>(sse_event_from_rpc_message(json!({
+                "jsonrpc": "2.0",
+                "method": "session/update",
+                "params": { "sessionId": "s1", "eventId": "evt-1", "update": {} }
+            }))),
+            Ok(sse::heartbeat()),
+            Ok(sse::done()),
+        ];
+        let response = axum::response::IntoResponse::into_response(axum::response::sse::Sse::new(
+            tokio_stream::iter(events),
+        ));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        let frames: Vec<(Option<&str>, Option<&str>, String)> = body
+            .split("\n\n")
+            .filter(|frame| !frame.trim().is_empty())
+            .map(|frame| {
+                let field = |name: &str| {
+                    frame
+                        .lines()
+                        .find_map(|line| line.strip_prefix(name))
+                        .map(str::trim_start)
+                };
+                let data = frame
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("data:"))
+                    .map(str::trim_start)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                (field("event:"), field("id:"), data)
+            })
+            .collect();
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].0, Some(sse::SESSION_UPDATE));
+        assert_eq!(frames[0].1, Some("evt-1"));
+        let update: serde_json::Value = serde_json::from_str(&frames[0].2).unwrap();
+        assert_eq!(update["params"]["sessionId"], "s1");
+        assert_eq!(frames[1].0, Some(sse::HEARTBEAT));
+        assert_eq!(frames[2].0, Some(sse::DONE));
+    }
 }
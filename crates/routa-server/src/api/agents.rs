@@ -3,18 +3,24 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
+use crate::api::traces::resolve_trace_reader_roots;
 use crate::error::ServerError;
 use crate::models::agent::{Agent, AgentRole, AgentStatus, ModelTier};
 use crate::state::AppState;
+use crate::store::{AgentListFilter, AgentOrderBy};
+use routa_core::trace::{TraceQuery, TraceReader, TraceRecord};
 
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", get(list_agents).post(create_agent))
         .route("/{id}", get(get_agent_by_path).delete(delete_agent))
         .route("/{id}/status", post(update_agent_status))
+        .route("/{id}/trace", get(get_agent_trace))
 }
 
 #[derive(Debug, Deserialize)]
@@ -26,6 +32,7 @@ struct ListAgentsQuery {
     role: Option<String>,
     status: Option<String>,
     parent_id: Option<String>,
+    order_by: Option<String>,
     #[allow(dead_code)]
     summary: Option<String>,
 }
@@ -40,24 +47,46 @@ async fn list_agents(
         return Ok(Json(serde_json::json!(agent)));
     }
 
-    let workspace_id = query.workspace_id.as_deref().unwrap_or("default");
-
-    let agents = if let Some(parent_id) = &query.parent_id {
-        state.agent_store.list_by_parent(parent_id).await?
-    } else if let Some(role_str) = &query.role {
-        let role = AgentRole::from_str(role_str)
-            .ok_or_else(|| ServerError::BadRequest(format!("Invalid role: {role_str}")))?;
-        state.agent_store.list_by_role(workspace_id, &role).await?
-    } else if let Some(status_str) = &query.status {
-        let status = AgentStatus::from_str(status_str)
-            .ok_or_else(|| ServerError::BadRequest(format!("Invalid status: {status_str}")))?;
-        state
-            .agent_store
-            .list_by_status(workspace_id, &status)
-            .await?
-    } else {
-        state.agent_store.list_by_workspace(workspace_id).await?
+    let role = query
+        .role
+        .as_deref()
+        .map(|role_str| {
+            AgentRole::from_str(role_str)
+                .ok_or_else(|| ServerError::BadRequest(format!("Invalid role: {role_str}")))
+        })
+        .transpose()?;
+    let status = query
+        .status
+        .as_deref()
+        .map(|status_str| {
+            AgentStatus::from_str(status_str)
+                .ok_or_else(|| ServerError::BadRequest(format!("Invalid status: {status_str}")))
+        })
+        .transpose()?;
+    let order_by = query
+        .order_by
+        .as_deref()
+        .map(|order_str| {
+            AgentOrderBy::from_str(order_str)
+                .ok_or_else(|| ServerError::BadRequest(format!("Invalid orderBy: {order_str}")))
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    // Parent lookups span the parent's workspace unless one is given explicitly.
+    let workspace_id = match (&query.workspace_id, &query.parent_id) {
+        (Some(workspace_id), _) => Some(workspace_id.clone()),
+        (None, Some(_)) => None,
+        (None, None) => Some("default".to_string()),
+    };
+    let filter = AgentListFilter {
+        workspace_id,
+        role,
+        status,
+        parent_id: query.parent_id.clone(),
+        order_by,
     };
+    let agents = state.agent_store.list_filtered(&filter).await?;
 
     Ok(Json(serde_json::json!({ "agents": agents })))
 }
@@ -145,3 +174,310 @@ async fn update_agent_status(
     state.agent_store.update_status(&id, &status).await?;
     Ok(Json(serde_json::json!({ "updated": true })))
 }
+
+/// A file an agent touched, aggregated across its trace records.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AgentFileTouch {
+    path: String,
+    operations: BTreeSet<String>,
+    touches: usize,
+    last_touched_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AgentTraceResponse {
+    agent_id: String,
+    session_ids: Vec<String>,
+    traces: Vec<TraceRecord>,
+    files: Vec<AgentFileTouch>,
+    count: usize,
+}
+
+/// GET /api/agents/{id}/trace — Trace records for every session the agent ran in.
+async fn get_agent_trace(
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Result<Json<AgentTraceResponse>, ServerError> {
+    let cwd = std::env::current_dir()
+        .map_err(|e| ServerError::Internal(format!("Failed to get cwd: {e}")))?;
+    load_agent_trace(&state, &cwd, |root| TraceReader::new(root), &id)
+        .await
+        .map(Json)
+}
+
+/// Reads each session's traces from the roots [`resolve_trace_reader_roots`]
+/// gives for it, so sessions started in another directory are found.
+async fn load_agent_trace(
+    state: &AppState,
+    fallback_cwd: &Path,
+    reader_for: impl Fn(&Path) -> TraceReader,
+    agent_id: &str,
+) -> Result<AgentTraceResponse, ServerError> {
+    let agent = state
+        .agent_store
+        .get(agent_id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("Agent {agent_id} not found")))?;
+
+    // Delegated agents carry their sessions in metadata; ROUTA coordinators are
+    // linked from the session side.
+    let mut session_ids = agent.session_ids();
+    for session_id in state
+        .acp_session_store
+        .list_ids_by_routa_agent_id(agent_id)
+        .await?
+    {
+        if !session_ids.contains(&session_id) {
+            session_ids.push(session_id);
+        }
+    }
+
+    let mut traces: Vec<TraceRecord> = Vec::new();
+    let mut all_roots: Vec<PathBuf> = vec![fallback_cwd.to_path_buf()];
+    for session_id in &session_ids {
+        for root in resolve_trace_reader_roots(state, session_id, fallback_cwd).await? {
+            let records = reader_for(&root)
+                .query(&TraceQuery {
+                    session_id: Some(session_id.clone()),
+                    ..TraceQuery::default()
+                })
+                .await
+                .map_err(|e| ServerError::Internal(format!("Failed to query traces: {e}")))?;
+            push_unique(&mut traces, records);
+            if !all_roots.contains(&root) {
+                all_roots.push(root);
+            }
+        }
+    }
+    // Delegations the agent took part in, including the one that spawned it
+    for root in &all_roots {
+        let delegations = reader_for(root)
+            .query(&TraceQuery {
+                agent_id: Some(agent_id.to_string()),
+                ..TraceQuery::default()
+            })
+            .await
+            .map_err(|e| ServerError::Internal(format!("Failed to query traces: {e}")))?;
+        push_unique(&mut traces, delegations);
+    }
+    traces.sort_by_key(|trace| std::cmp::Reverse(trace.timestamp));
+
+    let mut files: BTreeMap<String, AgentFileTouch> = BTreeMap::new();
+    for trace in &traces {
+        for file in &trace.files {
+            let touch = files
+                .entry(file.path.clone())
+                .or_insert_with(|| AgentFileTouch {
+                    path: file.path.clone(),
+                    operations: BTreeSet::new(),
+                    touches: 0,
+                    last_touched_at: trace.timestamp,
+                });
+            touch.touches += 1;
+            touch.last_touched_at = touch.last_touched_at.max(trace.timestamp);
+            if let Some(operation) = &file.operation {
+                touch.operations.insert(operation.clone());
+            }
+        }
+    }
+
+    Ok(AgentTraceResponse {
+        agent_id: agent.id,
+        session_ids,
+        count: traces.len(),
+        traces,
+        files: files.into_values().collect(),
+    })
+}
+
+fn push_unique(traces: &mut Vec<TraceRecord>, records: Vec<TraceRecord>) {
+    for record in records {
+        if !traces.iter().any(|trace| trace.id == record.id) {
+            traces.push(record);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routa_core::trace::{Contributor, TraceEventType, TraceFile, TraceWriter};
+    use routa_core::{AppStateInner, Database};
+    use std::sync::Arc;
+
+    fn file_trace(session_id: &str, path: &str, operation: &str) -> TraceRecord {
+        TraceRecord::new(
+            session_id,
+            TraceEventType::ToolResult,
+            Contributor::new("opencode", None),
+        )
+        .with_file(TraceFile {
+            path: path.to_string(),
+            ranges: Vec::new(),
+            operation: Some(operation.to_string()),
+            content_hash: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn agent_trace_spans_all_sessions_of_a_delegated_agent() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+
+        let mut crafter = Agent::new(
+            "crafter-1".to_string(),
+            "crafter-1".to_string(),
+            AgentRole::Crafter,
+            "default".to_string(),
+            Some("routa-1".to_string()),
+            None,
+            None,
+        );
+        crafter.record_session_id("session-a");
+        crafter.record_session_id("session-b");
+        state
+            .agent_store
+            .save(&crafter)
+            .await
+            .expect("agent should save");
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let writer = TraceWriter::with_base_dir(dir.path());
+        for record in [
+            file_trace("session-a", "src/lib.rs", "read"),
+            file_trace("session-a", "src/lib.rs", "write"),
+            file_trace("session-b", "src/main.rs", "create"),
+            file_trace("session-other", "README.md", "write"),
+            TraceRecord::new(
+                "session-routa",
+                TraceEventType::Delegation,
+                Contributor::new("opencode", None),
+            )
+            .with_metadata("parentAgentId", serde_json::json!("routa-1"))
+            .with_metadata("childAgentId", serde_json::json!("crafter-1"))
+            .with_metadata("childSessionId", serde_json::json!("session-a")),
+        ] {
+            writer.append(&record).await.expect("trace should write");
+        }
+
+        let response = load_agent_trace(
+            &state,
+            dir.path(),
+            |_| TraceReader::with_base_dir(dir.path()),
+            "crafter-1",
+        )
+        .await
+        .expect("agent trace should load");
+
+        assert_eq!(response.session_ids, vec!["session-a", "session-b"]);
+        assert_eq!(response.count, 4);
+        assert!(response
+            .traces
+            .iter()
+            .all(|trace| trace.session_id != "session-other"));
+        assert!(response
+            .traces
+            .iter()
+            .any(|trace| trace.event_type == TraceEventType::Delegation
+                && trace.session_id == "session-routa"));
+        let files: Vec<(&str, Vec<&str>, usize)> = response
+            .files
+            .iter()
+            .map(|f| {
+                (
+                    f.path.as_str(),
+                    f.operations.iter().map(String::as_str).collect(),
+                    f.touches,
+                )
+            })
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("src/lib.rs", vec!["read", "write"], 2),
+                ("src/main.rs", vec!["create"], 1),
+            ]
+        );
+
+        let missing = load_agent_trace(
+            &state,
+            dir.path(),
+            |_| TraceReader::with_base_dir(dir.path()),
+            "ghost",
+        )
+        .await;
+        assert!(matches!(missing, Err(ServerError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn agent_trace_reads_sessions_from_their_own_cwd() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+
+        let server_cwd = tempfile::tempdir().expect("temp dir");
+        let session_cwd = tempfile::tempdir().expect("temp dir");
+        state
+            .acp_session_store
+            .create(
+                routa_core::store::acp_session_store::CreateAcpSessionParams {
+                    id: "session-elsewhere",
+                    cwd: &session_cwd.path().to_string_lossy(),
+                    branch: None,
+                    workspace_id: "default",
+                    provider: Some("opencode"),
+                    role: Some("CRAFTER"),
+                    custom_command: None,
+                    custom_args: None,
+                    parent_session_id: None,
+                    metadata: None,
+                },
+            )
+            .await
+            .expect("session should save");
+
+        let mut crafter = Agent::new(
+            "crafter-2".to_string(),
+            "crafter-2".to_string(),
+            AgentRole::Crafter,
+            "default".to_string(),
+            None,
+            None,
+            None,
+        );
+        crafter.record_session_id("session-elsewhere");
+        state
+            .agent_store
+            .save(&crafter)
+            .await
+            .expect("agent should save");
+
+        TraceWriter::with_base_dir(session_cwd.path().join("traces"))
+            .append(&file_trace("session-elsewhere", "src/lib.rs", "write"))
+            .await
+            .expect("trace should write");
+
+        let response = load_agent_trace(
+            &state,
+            server_cwd.path(),
+            |root| TraceReader::with_base_dir(root.join("traces")),
+            "crafter-2",
+        )
+        .await
+        .expect("agent trace should load");
+
+        assert_eq!(response.count, 1);
+        assert_eq!(response.files[0].path, "src/lib.rs");
+    }
+}
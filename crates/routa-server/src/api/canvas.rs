@@ -321,6 +321,7 @@ async fn create_canvas_from_specialist(
             custom_command: None,
             custom_args: None,
             parent_session_id: None,
+            metadata: None,
         })
         .await;
     if let Err(error) = persist_result {
@@ -357,7 +358,7 @@ async fn create_canvas_from_specialist(
             .await
             .unwrap_or_default();
 
-        let prompt_text = extract_text_from_prompt_result(&prompt_result);
+        let prompt_text = prompt_result.text.trim().to_string();
         let history_text = extract_specialist_output_from_history(&history);
         let combined_output = if !prompt_text.trim().is_empty() {
             prompt_text
@@ -834,34 +835,6 @@ fn format_or_list(values: &[String]) -> String {
     }
 }
 
-fn extract_text_from_prompt_result(value: &Value) -> String {
-    fn collect(value: &Value, parts: &mut Vec<String>) {
-        match value {
-            Value::Object(map) => {
-                if let Some(text) = map.get("text").and_then(Value::as_str) {
-                    parts.push(text.to_string());
-                }
-                if let Some(delta) = map.get("delta").and_then(Value::as_str) {
-                    parts.push(delta.to_string());
-                }
-                for nested in map.values() {
-                    collect(nested, parts);
-                }
-            }
-            Value::Array(items) => {
-                for item in items {
-                    collect(item, parts);
-                }
-            }
-            _ => {}
-        }
-    }
-
-    let mut parts = Vec::new();
-    collect(value, &mut parts);
-    parts.join("").trim().to_string()
-}
-
 fn extract_text_from_process_output_line(data: &str) -> Option<String> {
     for marker in ["Agent message (non-delta) received: \"", "delta: \""] {
         if let Some(start) = data.find(marker) {
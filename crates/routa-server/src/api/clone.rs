@@ -3,9 +3,14 @@
 //! POST /api/clone - Clone a GitHub repository
 //! GET  /api/clone - List cloned repositories
 //! PATCH /api/clone - Switch branch
+//!
+//! Private repositories can be cloned by passing a `credential` with the
+//! POST body: either `{"type": "token", "token": "..."}` for HTTPS or
+//! `{"type": "sshKey", "name": "id_ed25519"}` for a key under `~/.ssh`.
 
 use axum::{routing::get, Json, Router};
 use serde::Deserialize;
+use std::process::Command;
 
 use crate::error::ServerError;
 use crate::git;
@@ -85,9 +90,146 @@ fn parse_git_clone_error(stderr: &str, exit_code: Option<i32>) -> String {
     format!("Clone failed with exit code {}", exit_code.unwrap_or(-1))
 }
 
+/// Whether git's stderr describes rejected or missing credentials, as
+/// opposed to a network or repository problem.
+fn is_git_auth_failure(stderr: &str) -> bool {
+    let stderr_lower = stderr.to_lowercase();
+    [
+        "authentication failed",
+        "could not read username",
+        "could not read password",
+        "terminal prompts disabled",
+        "permission denied (publickey)",
+        "the requested url returned error: 401",
+        "the requested url returned error: 403",
+    ]
+    .iter()
+    .any(|pattern| stderr_lower.contains(pattern))
+}
+
+/// Map a failed `git clone` to an API error. The remote refusing our
+/// credentials is a 403 — the caller's own session is fine, so it must not
+/// look like a 401 from this server.
+fn clone_failure_error(stderr: &str, exit_code: Option<i32>, has_auth: bool) -> ServerError {
+    if is_git_auth_failure(stderr) {
+        return ServerError::Forbidden(if has_auth {
+            "Git rejected the supplied credentials.".to_string()
+        } else {
+            parse_git_clone_error(stderr, exit_code)
+        });
+    }
+    ServerError::Internal(parse_git_clone_error(stderr, exit_code))
+}
+
 #[derive(Debug, Deserialize)]
 struct CloneRequest {
     url: Option<String>,
+    credential: Option<CloneCredential>,
+}
+
+/// Credential for cloning a private repository.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum CloneCredential {
+    /// HTTPS access token, answered to git through `GIT_ASKPASS`.
+    Token { token: String },
+    /// Name of a private key in `~/.ssh`, used through `GIT_SSH_COMMAND`.
+    SshKey { name: String },
+}
+
+impl std::fmt::Debug for CloneCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Token { .. } => f.write_str("Token { token: \"****\" }"),
+            Self::SshKey { name } => f.debug_struct("SshKey").field("name", name).finish(),
+        }
+    }
+}
+
+/// Environment that hands a clone credential to git.
+///
+/// The token itself only travels in the child's environment; the askpass
+/// script just echoes it back, so it never lands on disk or in arguments.
+struct CloneAuth {
+    envs: Vec<(String, String)>,
+    /// Holds the askpass script until git is done with it.
+    _askpass_dir: Option<tempfile::TempDir>,
+}
+
+const ASKPASS_SCRIPT: &str = r#"#!/bin/sh
+case "$1" in
+  Username*) echo x-access-token ;;
+  *) printf '%s\n' "$ROUTA_CLONE_TOKEN" ;;
+esac
+"#;
+
+impl CloneAuth {
+    fn prepare(credential: &CloneCredential) -> Result<Self, ServerError> {
+        match credential {
+            CloneCredential::Token { token } => {
+                if token.trim().is_empty() {
+                    return Err(ServerError::BadRequest("Credential token is empty".into()));
+                }
+                let dir = tempfile::tempdir().map_err(|e| {
+                    ServerError::Internal(format!("Failed to prepare git askpass: {e}"))
+                })?;
+                let script = dir.path().join("askpass.sh");
+                std::fs::write(&script, ASKPASS_SCRIPT).map_err(|e| {
+                    ServerError::Internal(format!("Failed to prepare git askpass: {e}"))
+                })?;
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o700))
+                        .map_err(|e| {
+                            ServerError::Internal(format!("Failed to prepare git askpass: {e}"))
+                        })?;
+                }
+                Ok(Self {
+                    envs: vec![
+                        ("GIT_ASKPASS".into(), script.to_string_lossy().to_string()),
+                        ("ROUTA_CLONE_TOKEN".into(), token.clone()),
+                        ("GIT_TERMINAL_PROMPT".into(), "0".into()),
+                        // Skip configured credential helpers so the token is what git uses.
+                        ("GIT_CONFIG_COUNT".into(), "1".into()),
+                        ("GIT_CONFIG_KEY_0".into(), "credential.helper".into()),
+                        ("GIT_CONFIG_VALUE_0".into(), String::new()),
+                    ],
+                    _askpass_dir: Some(dir),
+                })
+            }
+            CloneCredential::SshKey { name } => {
+                if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\', '\'']) {
+                    return Err(ServerError::BadRequest(format!(
+                        "Invalid SSH key name '{name}'"
+                    )));
+                }
+                let key_path = dirs::home_dir()
+                    .ok_or_else(|| ServerError::Internal("Cannot resolve home directory".into()))?
+                    .join(".ssh")
+                    .join(name);
+                if !key_path.is_file() {
+                    return Err(ServerError::BadRequest(format!(
+                        "SSH key '{name}' not found in ~/.ssh"
+                    )));
+                }
+                Ok(Self {
+                    envs: vec![(
+                        "GIT_SSH_COMMAND".into(),
+                        format!(
+                            "ssh -i '{}' -o IdentitiesOnly=yes -o BatchMode=yes",
+                            key_path.display()
+                        ),
+                    )],
+                    _askpass_dir: None,
+                })
+            }
+        }
+    }
+
+    fn apply(&self, command: &mut Command) {
+        command.envs(self.envs.iter().map(|(key, value)| (key, value)));
+    }
 }
 
 async fn clone_repo(
@@ -104,6 +246,13 @@ async fn clone_repo(
         )
     })?;
 
+    let auth = body
+        .credential
+        .as_ref()
+        .map(CloneAuth::prepare)
+        .transpose()?
+        .map(std::sync::Arc::new);
+
     let repo_name = git::repo_to_dir_name(&parsed.owner, &parsed.repo);
     let base_dir = git::get_clone_base_dir();
     std::fs::create_dir_all(&base_dir)
@@ -116,11 +265,14 @@ async fn clone_repo(
         // Already cloned — pull latest
         tokio::task::spawn_blocking({
             let target_str = target_str.clone();
+            let auth = auth.clone();
             move || {
-                let _ = git::git_command()
-                    .args(["pull", "--ff-only"])
-                    .current_dir(&target_str)
-                    .output();
+                let mut command = git::git_command();
+                command.args(["pull", "--ff-only"]).current_dir(&target_str);
+                if let Some(auth) = &auth {
+                    auth.apply(&mut command);
+                }
+                let _ = command.output();
             }
         })
         .await
@@ -144,16 +296,25 @@ async fn clone_repo(
     }
 
     // Clone the repository
-    let clone_url = format!("https://github.com/{}/{}.git", parsed.owner, parsed.repo);
+    let clone_url = match body.credential {
+        Some(CloneCredential::SshKey { .. }) => {
+            format!("git@github.com:{}/{}.git", parsed.owner, parsed.repo)
+        }
+        _ => format!("https://github.com/{}/{}.git", parsed.owner, parsed.repo),
+    };
     let target_dir_str = target_dir.to_string_lossy().to_string();
 
     let output = tokio::task::spawn_blocking({
         let clone_url = clone_url.clone();
         let target = target_dir_str.clone();
+        let auth = auth.clone();
         move || {
-            git::git_command()
-                .args(["clone", "--depth", "1", &clone_url, &target])
-                .output()
+            let mut command = git::git_command();
+            command.args(["clone", "--depth", "1", &clone_url, &target]);
+            if let Some(auth) = &auth {
+                auth.apply(&mut command);
+            }
+            command.output()
         }
     })
     .await
@@ -163,18 +324,23 @@ async fn clone_repo(
     // Check if clone succeeded
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        let error_msg = parse_git_clone_error(&stderr, output.status.code());
-        return Err(ServerError::Internal(error_msg));
+        return Err(clone_failure_error(
+            &stderr,
+            output.status.code(),
+            auth.is_some(),
+        ));
     }
 
     // Fetch all branches
     let _ = tokio::task::spawn_blocking({
         let ts = target_str.clone();
         move || {
-            let _ = git::git_command()
-                .args(["fetch", "--all"])
-                .current_dir(&ts)
-                .output();
+            let mut command = git::git_command();
+            command.args(["fetch", "--all"]).current_dir(&ts);
+            if let Some(auth) = &auth {
+                auth.apply(&mut command);
+            }
+            let _ = command.output();
         }
     })
     .await;
@@ -205,7 +371,10 @@ async fn list_repos() -> Result<Json<serde_json::Value>, ServerError> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_git_clone_error;
+    use super::{
+        clone_failure_error, is_git_auth_failure, parse_git_clone_error, CloneAuth, CloneCredential,
+    };
+    use crate::error::ServerError;
 
     #[test]
     fn parse_git_clone_error_maps_auth_and_network_failures() {
@@ -233,6 +402,84 @@ mod tests {
         let code_only = parse_git_clone_error("", Some(7));
         assert_eq!(code_only, "Clone failed with exit code 7");
     }
+
+    #[test]
+    fn auth_failures_are_told_apart_from_network_failures() {
+        assert!(is_git_auth_failure(
+            "fatal: Authentication failed for 'https://github.com/o/r.git/'"
+        ));
+        assert!(is_git_auth_failure(
+            "git@github.com: Permission denied (publickey)."
+        ));
+        assert!(!is_git_auth_failure(
+            "fatal: unable to access: Could not resolve host: github.com"
+        ));
+    }
+
+    #[test]
+    fn remote_auth_failures_are_forbidden_not_unauthorized() {
+        assert!(matches!(
+            clone_failure_error("fatal: Authentication failed", Some(128), true),
+            ServerError::Forbidden(message) if message == "Git rejected the supplied credentials."
+        ));
+        assert!(matches!(
+            clone_failure_error("Permission denied (publickey).", Some(128), false),
+            ServerError::Forbidden(_)
+        ));
+        assert!(matches!(
+            clone_failure_error("fatal: Could not resolve host: github.com", Some(128), true),
+            ServerError::Internal(_)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn token_credential_is_answered_through_askpass() {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let credential: CloneCredential =
+            serde_json::from_value(serde_json::json!({"type": "token", "token": "s3cret"}))
+                .unwrap();
+        assert!(!format!("{credential:?}").contains("s3cret"));
+        let auth = CloneAuth::prepare(&credential).unwrap();
+
+        // `git credential fill` takes the same route as the HTTPS clone:
+        // helpers first (disabled by the auth env), then GIT_ASKPASS.
+        let home = tempfile::tempdir().unwrap();
+        let mut command = crate::git::git_command();
+        command
+            .args(["credential", "fill"])
+            .env("HOME", home.path())
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        auth.apply(&mut command);
+        let mut child = command.spawn().unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"protocol=https\nhost=github.com\npath=owner/private.git\n\n")
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success(), "{output:?}");
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("username=x-access-token"), "{stdout}");
+        assert!(stdout.contains("password=s3cret"), "{stdout}");
+    }
+
+    #[test]
+    fn ssh_key_names_cannot_leave_the_ssh_directory() {
+        for name in ["../id_rsa", "", ".hidden", "a/b"] {
+            let credential = CloneCredential::SshKey {
+                name: name.to_string(),
+            };
+            assert!(CloneAuth::prepare(&credential).is_err(), "{name}");
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
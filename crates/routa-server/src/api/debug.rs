@@ -1,7 +1,15 @@
-use axum::{routing::get, Json, Router};
+use axum::{
+    extract::{Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
 use serde_json::{json, Value};
 
+use crate::error::ServerError;
+use crate::models::tool_audit::ToolAuditFilter;
 use crate::state::AppState;
+use routa_core::db::backup;
 use routa_core::shell_env;
 
 /// Debug endpoint to check PATH and command resolution
@@ -19,6 +27,69 @@ async fn debug_path() -> Json<Value> {
     }))
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolAuditQuery {
+    tool_name: Option<String>,
+    agent_id: Option<String>,
+    session_id: Option<String>,
+    workspace_id: Option<String>,
+    success: Option<bool>,
+    limit: Option<usize>,
+}
+
+/// GET /api/debug/tools/audit — Recent tool calls, newest first
+async fn tool_audit(
+    State(state): State<AppState>,
+    Query(q): Query<ToolAuditQuery>,
+) -> Result<Json<Value>, ServerError> {
+    let filter = ToolAuditFilter {
+        tool_name: q.tool_name,
+        agent_id: q.agent_id,
+        session_id: q.session_id,
+        workspace_id: q.workspace_id,
+        success: q.success,
+        limit: q.limit,
+    };
+    let entries = state.tool_audit_store.list(&filter).await?;
+    Ok(Json(json!({ "entries": entries })))
+}
+
+/// A backup file, named relative to `~/.routa/backups`.
+#[derive(Debug, Deserialize)]
+struct DatabaseFileRequest {
+    name: String,
+}
+
+/// POST /api/debug/backup — Snapshot the live database to a new file in
+/// the backup directory
+async fn backup_database(
+    State(state): State<AppState>,
+    Json(body): Json<DatabaseFileRequest>,
+) -> Result<Json<Value>, ServerError> {
+    let path = backup::backup_path(&body.name)?;
+    std::fs::create_dir_all(backup::backup_dir())
+        .map_err(|e| ServerError::Internal(format!("Cannot create backup directory: {e}")))?;
+    let info = state.db.backup_to(&path).await?;
+    Ok(Json(json!({ "backup": info })))
+}
+
+/// POST /api/debug/restore — Replace the live database with a backup from
+/// the backup directory, after checking it is a Routa database this build
+/// can migrate
+async fn restore_database(
+    State(state): State<AppState>,
+    Json(body): Json<DatabaseFileRequest>,
+) -> Result<Json<Value>, ServerError> {
+    let path = backup::backup_path(&body.name)?;
+    let info = state.db.restore_from(&path).await?;
+    Ok(Json(json!({ "restored": info })))
+}
+
 pub fn router() -> Router<AppState> {
-    Router::new().route("/path", get(debug_path))
+    Router::new()
+        .route("/path", get(debug_path))
+        .route("/tools/audit", get(tool_audit))
+        .route("/backup", post(backup_database))
+        .route("/restore", post(restore_database))
 }
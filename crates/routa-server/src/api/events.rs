@@ -0,0 +1,96 @@
+//! Live `AgentEvent` stream for CLI and dashboard consumers.
+//!
+//! `GET /api/events/stream?workspaceId=...&types=TASK_ASSIGNED,AGENT_ERROR`
+//! forwards every matching event as one `agent_event` SSE frame containing the
+//! serialized `AgentEvent`. The handler is removed when the client disconnects.
+
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+    Router,
+};
+use routa_core::events::AgentEventType;
+use serde::Deserialize;
+use std::convert::Infallible;
+use tokio::sync::mpsc;
+
+use super::kanban::EventBusSubscriptionGuard;
+use super::sse;
+use crate::error::ServerError;
+use crate::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/stream", get(stream_events))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamQuery {
+    workspace_id: Option<String>,
+    /// Comma-separated event types; all types when omitted.
+    types: Option<String>,
+}
+
+fn parse_event_types(raw: Option<&str>) -> Result<Vec<AgentEventType>, ServerError> {
+    raw.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            AgentEventType::from_str(value)
+                .ok_or_else(|| ServerError::BadRequest(format!("Invalid event type: {value}")))
+        })
+        .collect()
+}
+
+async fn stream_events(
+    State(state): State<AppState>,
+    Query(query): Query<StreamQuery>,
+) -> Result<Sse<impl tokio_stream::Stream<Item = Result<Event, Infallible>>>, ServerError> {
+    let types = parse_event_types(query.types.as_deref())?;
+    let workspace_id = query.workspace_id.unwrap_or_else(|| "*".to_string());
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    let handler_key = format!("events-stream-{}", uuid::Uuid::new_v4());
+
+    state
+        .event_bus
+        .on(&handler_key, move |event| {
+            if workspace_id != "*" && event.workspace_id != workspace_id {
+                return;
+            }
+            if !types.is_empty() && !types.contains(&event.event_type) {
+                return;
+            }
+            if let Ok(payload) = serde_json::to_string(&event) {
+                let _ = tx.send(payload);
+            }
+        })
+        .await;
+
+    let event_bus = state.event_bus.clone();
+    let stream = async_stream::stream! {
+        let _guard = EventBusSubscriptionGuard::new(event_bus, handler_key);
+        yield Ok(Event::default().comment("connected"));
+        while let Some(payload) = rx.recv().await {
+            yield Ok(Event::default().event(sse::AGENT_EVENT).data(payload));
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().event(sse::heartbeat())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_comma_separated_event_types() {
+        assert_eq!(
+            parse_event_types(Some("task_assigned, AGENT_ERROR")).unwrap(),
+            vec![AgentEventType::TaskAssigned, AgentEventType::AgentError]
+        );
+        assert!(parse_event_types(None).unwrap().is_empty());
+        assert!(parse_event_types(Some("NOPE")).is_err());
+    }
+}
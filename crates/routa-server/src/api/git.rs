@@ -95,15 +95,20 @@ async fn resolve_codebase_repo_path(
     Ok(repo_path)
 }
 
-fn validate_git_file_path(path: &str) -> Result<(), String> {
+/// Empty paths are bad requests; paths that leave the repository root are forbidden.
+fn validate_git_file_path(path: &str) -> Result<(), ServerError> {
     let trimmed = path.trim();
     if trimmed.is_empty() {
-        return Err("File path cannot be empty".to_string());
+        return Err(ServerError::BadRequest(
+            "File path cannot be empty".to_string(),
+        ));
     }
 
     let candidate = FilePath::new(trimmed);
     if candidate.is_absolute() {
-        return Err(format!("Absolute file paths are not allowed: {trimmed}"));
+        return Err(ServerError::Forbidden(format!(
+            "Absolute file paths are not allowed: {trimmed}"
+        )));
     }
 
     if candidate.components().any(|component| {
@@ -112,15 +117,15 @@ fn validate_git_file_path(path: &str) -> Result<(), String> {
             Component::ParentDir | Component::RootDir | Component::Prefix(_)
         )
     }) {
-        return Err(format!(
+        return Err(ServerError::Forbidden(format!(
             "File paths must stay within the repository root: {trimmed}"
-        ));
+        )));
     }
 
     Ok(())
 }
 
-fn validate_git_file_paths(files: &[String]) -> Result<(), String> {
+fn validate_git_file_paths(files: &[String]) -> Result<(), ServerError> {
     for file in files {
         validate_git_file_path(file)?;
     }
@@ -152,6 +157,8 @@ fn server_error_message(error: ServerError) -> String {
         | ServerError::NotFound(message)
         | ServerError::BadRequest(message)
         | ServerError::Conflict(message)
+        | ServerError::Unauthorized(message)
+        | ServerError::Forbidden(message)
         | ServerError::Internal(message)
         | ServerError::NotImplemented(message) => message,
     }
@@ -566,7 +573,7 @@ async fn get_file_diff(
         .filter(|value| !value.is_empty())
         .ok_or_else(|| ServerError::BadRequest("Missing 'path' query parameter".to_string()))?
         .to_string();
-    validate_git_file_path(&path).map_err(ServerError::BadRequest)?;
+    validate_git_file_path(&path)?;
     let staged = query.staged.unwrap_or(false);
     let repo_path = resolve_codebase_repo_path(&state, &workspace_id, &codebase_id).await?;
     let response_path = path.clone();
@@ -600,7 +607,7 @@ async fn get_commit_diff(
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty());
     if let Some(path_value) = path.as_deref() {
-        validate_git_file_path(path_value).map_err(ServerError::BadRequest)?;
+        validate_git_file_path(path_value)?;
     }
     let repo_path = resolve_codebase_repo_path(&state, &workspace_id, &codebase_id).await?;
     let response_sha = sha.clone();
@@ -838,7 +845,7 @@ async fn export_changes_handler(
         }
     };
     let files = req.files.unwrap_or_default();
-    validate_git_file_paths(&files).map_err(ServerError::BadRequest)?;
+    validate_git_file_paths(&files)?;
     let format = req.format.unwrap_or_else(|| "patch".to_string());
     if format != "patch" && format != "diff" {
         return Ok((
@@ -81,13 +81,14 @@ struct BoardsQuery {
     workspace_id: Option<String>,
 }
 
-struct EventBusSubscriptionGuard {
+/// Removes an `EventBus` handler when an SSE stream is dropped.
+pub(crate) struct EventBusSubscriptionGuard {
     event_bus: EventBus,
     handler_key: String,
 }
 
 impl EventBusSubscriptionGuard {
-    fn new(event_bus: EventBus, handler_key: String) -> Self {
+    pub(crate) fn new(event_bus: EventBus, handler_key: String) -> Self {
         Self {
             event_bus,
             handler_key,
@@ -489,7 +490,7 @@ async fn kanban_events(
                         None => break,
                     }
                 }
-                _ = heartbeat.tick() => yield Ok(Event::default().comment("heartbeat")),
+                _ = heartbeat.tick() => yield Ok(super::sse::heartbeat()),
             }
         }
     };
@@ -990,6 +991,8 @@ async fn rpc_result(
     match code {
         -32001 => Err(ServerError::NotFound(message)),
         -32002 | -32602 => Err(ServerError::BadRequest(message)),
+        -32003 => Err(ServerError::Unauthorized(message)),
+        -32004 => Err(ServerError::Forbidden(message)),
         _ => Err(ServerError::Internal(message)),
     }
 }
@@ -1080,6 +1083,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("session should persist");
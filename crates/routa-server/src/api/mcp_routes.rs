@@ -16,6 +16,7 @@ use axum::{
 };
 use serde::Deserialize;
 
+use crate::models::agent::AgentRole;
 use crate::state::AppState;
 
 #[derive(Debug, Deserialize, Default, Clone)]
@@ -24,6 +25,8 @@ pub(super) struct McpRequestQuery {
     #[serde(rename = "wsId")]
     ws_id: Option<String>,
     mcp_profile: Option<String>,
+    /// Agent role of the client (e.g. `DEVELOPER`); hides tools the role should not use.
+    role: Option<String>,
 }
 
 pub fn router(state: AppState) -> Router<AppState> {
@@ -117,6 +120,16 @@ pub fn build_tool_list_public() -> Vec<serde_json::Value> {
     tool_catalog::build_tool_list_public()
 }
 
+/// [`build_tool_list_public`] without the tools `role` should not use.
+pub fn build_tool_list_for_role(role: Option<AgentRole>) -> Vec<serde_json::Value> {
+    tool_catalog::build_tool_list_for_profile(None, role)
+}
+
+/// Parse a role name from a query string, ignoring case.
+pub(super) fn parse_role(role: Option<&str>) -> Option<AgentRole> {
+    role.and_then(|role| AgentRole::from_str(&role.trim().to_ascii_uppercase()))
+}
+
 pub async fn execute_tool_public(
     state: &AppState,
     name: &str,
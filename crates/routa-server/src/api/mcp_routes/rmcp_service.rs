@@ -15,11 +15,13 @@ use rmcp::{
 };
 use std::sync::Arc;
 
+use crate::models::agent::AgentRole;
 use crate::state::AppState;
 
 use super::tool_catalog;
 use super::{
-    execute_tool_public, inject_workspace_id, normalize_tool_name_public, McpRequestQuery,
+    execute_tool_public, inject_workspace_id, normalize_tool_name_public, parse_role,
+    McpRequestQuery,
 };
 
 pub(super) type SharedMcpHttpService =
@@ -34,6 +36,7 @@ pub(super) struct RoutaMcpHttpServer {
 struct RequestScope {
     workspace_id: String,
     mcp_profile: Option<String>,
+    role: Option<AgentRole>,
 }
 
 impl RequestScope {
@@ -59,6 +62,7 @@ impl RequestScope {
 
         Self {
             workspace_id,
+            role: parse_role(query.role.as_deref()),
             mcp_profile: query.mcp_profile,
         }
     }
@@ -108,10 +112,11 @@ impl ServerHandler for RoutaMcpHttpServer {
         context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
         let scope = RequestScope::from_context(&context);
-        let tools = tool_catalog::build_tool_list_for_profile(scope.mcp_profile.as_deref())
-            .into_iter()
-            .map(tool_from_value)
-            .collect::<Result<Vec<_>, _>>()?;
+        let tools =
+            tool_catalog::build_tool_list_for_profile(scope.mcp_profile.as_deref(), scope.role)
+                .into_iter()
+                .map(tool_from_value)
+                .collect::<Result<Vec<_>, _>>()?;
 
         Ok(ListToolsResult {
             tools,
@@ -138,6 +143,12 @@ impl ServerHandler for RoutaMcpHttpServer {
                 None,
             ));
         }
+        if !tool_catalog::tool_allowed_for_role(&normalized_tool_name, scope.role.as_ref()) {
+            return Err(McpError::invalid_params(
+                format!("Tool not allowed for agent role: {requested_tool_name}"),
+                None,
+            ));
+        }
 
         let mut arguments = request
             .arguments
@@ -181,6 +192,7 @@ fn server_name(profile: Option<&str>) -> &'static str {
     match profile {
         Some("kanban-planning") => "kanban-planning-mcp",
         Some("team-coordination") => "team-coordination-mcp",
+        Some("routa-planning") => "routa-planning-mcp",
         _ => "routa-mcp",
     }
 }
@@ -1,20 +1,26 @@
+use crate::models::agent::AgentRole;
+
 pub(super) fn build_tool_list_public() -> Vec<serde_json::Value> {
     build_tool_list_inner()
 }
 
-pub(super) fn build_tool_list_for_profile(profile: Option<&str>) -> Vec<serde_json::Value> {
-    let tools = build_tool_list_inner();
-    match profile {
-        Some("kanban-planning") => tools
-            .into_iter()
-            .filter(|tool| {
-                tool.get("name")
-                    .and_then(|value| value.as_str())
-                    .is_some_and(|name| tool_allowed_for_profile(name, Some("kanban-planning")))
-            })
-            .collect(),
-        _ => tools,
-    }
+/// Tool list for an MCP client, narrowed by profile and, when the client's
+/// agent role is known, by [`tool_allowed_for_role`].
+pub(super) fn build_tool_list_for_profile(
+    profile: Option<&str>,
+    role: Option<AgentRole>,
+) -> Vec<serde_json::Value> {
+    build_tool_list_inner()
+        .into_iter()
+        .filter(|tool| {
+            tool.get("name")
+                .and_then(|value| value.as_str())
+                .is_some_and(|name| {
+                    tool_allowed_for_profile(name, profile)
+                        && tool_allowed_for_role(name, role.as_ref())
+                })
+        })
+        .collect()
 }
 
 pub(super) fn tool_allowed_for_profile(name: &str, profile: Option<&str>) -> bool {
@@ -31,17 +37,40 @@ pub(super) fn tool_allowed_for_profile(name: &str, profile: Option<&str>) -> boo
                 | "request_previous_lane_handoff"
                 | "submit_lane_handoff"
         ),
+        // Plan-only coordinator runs: everything except spawning and delegating.
+        Some("routa-planning") => !matches!(name, "create_agent" | "delegate_task_to_agent"),
         _ => true,
     }
 }
 
+/// Tools that only make sense for some roles, with the roles that may use
+/// them. Tools not listed here are available to every role.
+const ROLE_RESTRICTED_TOOLS: &[(&str, &[AgentRole])] = &[
+    // Only the coordinator spawns and delegates; DEVELOPER works solo.
+    ("create_agent", &[AgentRole::Routa]),
+    ("delegate_task_to_agent", &[AgentRole::Routa]),
+    // Only delegated agents have a parent to report to.
+    ("report_to_parent", &[AgentRole::Crafter, AgentRole::Gate]),
+];
+
+pub(super) fn tool_allowed_for_role(name: &str, role: Option<&AgentRole>) -> bool {
+    let Some(role) = role else {
+        return true;
+    };
+    ROLE_RESTRICTED_TOOLS
+        .iter()
+        .find(|(tool, _)| *tool == name)
+        .is_none_or(|(_, roles)| roles.contains(role))
+}
+
 fn build_tool_list_inner() -> Vec<serde_json::Value> {
     vec![
         // ── Agent tools ──────────────────────────────────────────────────
         tool_def("list_agents", "List all agents in the workspace", serde_json::json!({
             "type": "object",
             "properties": {
-                "workspaceId": { "type": "string", "description": "Workspace ID (default if omitted)" }
+                "workspaceId": { "type": "string", "description": "Workspace ID (default if omitted)" },
+                "includeLive": { "type": "boolean", "description": "Add sessionAlive: whether each agent's session process is still running (default: false)" }
             }
         })),
         tool_def("create_agent", "Create a new agent (ROUTA=coordinator, CRAFTER=implementor, GATE=verifier, DEVELOPER=solo)", serde_json::json!({
@@ -51,7 +80,9 @@ fn build_tool_list_inner() -> Vec<serde_json::Value> {
                 "role": { "type": "string", "enum": ["ROUTA", "CRAFTER", "GATE", "DEVELOPER"], "description": "Agent role" },
                 "workspaceId": { "type": "string" },
                 "parentId": { "type": "string", "description": "Parent agent ID" },
-                "modelTier": { "type": "string", "enum": ["SMART", "BALANCED", "FAST"], "description": "Model tier (default: SMART)" }
+                "modelTier": { "type": "string", "enum": ["SMART", "BALANCED", "FAST"], "description": "Model tier (default: SMART)" },
+                "uniqueName": { "type": "boolean", "description": "Fail if an agent with this name already exists in the workspace (default: false)" },
+                "autoSuffix": { "type": "boolean", "description": "On a name collision, append -2, -3, ... instead of failing (default: false)" }
             },
             "required": ["name", "role"]
         })),
@@ -107,6 +138,15 @@ fn build_tool_list_inner() -> Vec<serde_json::Value> {
             },
             "required": ["taskId", "status", "agentId"]
         })),
+        tool_def("reopen_task", "Reopen a COMPLETED or REVIEW_REQUIRED task that turned out to be lacking. Sets status to NEEDS_FIX, clears the completion summary and verification verdict, records the reason, and emits TASK_STATUS_CHANGED.", serde_json::json!({
+            "type": "object",
+            "properties": {
+                "taskId": { "type": "string", "description": "Task ID" },
+                "reason": { "type": "string", "description": "Why the task needs more work" },
+                "agentId": { "type": "string", "description": "Agent reopening the task" }
+            },
+            "required": ["taskId", "reason", "agentId"]
+        })),
         tool_def("update_task", "Atomically update structured task fields. Use this for story-readiness fields such as scope, acceptance criteria, verification commands, and test cases. agentId is optional for Kanban sessions.", serde_json::json!({
             "type": "object",
             "properties": {
@@ -161,9 +201,11 @@ fn build_tool_list_inner() -> Vec<serde_json::Value> {
                 "callerSessionId": { "type": "string", "description": "Session ID of the delegator agent (optional)" },
                 "specialist": { "type": "string", "enum": ["CRAFTER", "GATE", "DEVELOPER"], "description": "Specialist type" },
                 "provider": { "type": "string", "description": "ACP provider (claude, auggie, opencode, etc.)" },
+                "modelTier": { "type": "string", "enum": ["FAST", "BALANCED", "SMART"], "description": "Model tier for the child; picks the provider when none is given (default: the specialist's tier)" },
                 "cwd": { "type": "string", "description": "Working directory for the child agent" },
                 "additionalInstructions": { "type": "string", "description": "Extra context or constraints for the child agent" },
-                "waitMode": { "type": "string", "enum": ["immediate", "after_all", "fire_and_forget"], "description": "Wait mode (default: after_all, fire_and_forget behaves like immediate)" }
+                "waitMode": { "type": "string", "enum": ["immediate", "after_all", "fire_and_forget"], "description": "Wait mode (default: after_all, fire_and_forget behaves like immediate)" },
+                "isolate": { "type": "boolean", "description": "Run the child in its own git worktree so parallel agents don't share a working tree (default: false)" }
             },
             "required": ["taskId", "callerAgentId", "specialist"]
         })),
@@ -172,10 +214,11 @@ fn build_tool_list_inner() -> Vec<serde_json::Value> {
             "properties": {
                 "agentId": { "type": "string", "description": "Your agent ID" },
                 "taskId": { "type": "string", "description": "Task ID being reported" },
-                "summary": { "type": "string", "description": "Summary of work done" },
-                "success": { "type": "boolean", "description": "Whether task succeeded" }
+                "summary": { "type": "string", "description": "Summary of work done; may be your full free-text report" },
+                "success": { "type": "boolean", "description": "Whether task succeeded (read from the summary when omitted)" },
+                "filesModified": { "type": "array", "items": { "type": "string" }, "description": "Files you changed" }
             },
-            "required": ["agentId", "taskId", "summary", "success"]
+            "required": ["agentId", "taskId", "summary"]
         })),
         tool_def("send_message_to_agent", "Send message from one agent to another", serde_json::json!({
             "type": "object",
@@ -201,7 +244,8 @@ fn build_tool_list_inner() -> Vec<serde_json::Value> {
                 "title": { "type": "string", "description": "Note title" },
                 "content": { "type": "string", "description": "Note content" },
                 "workspaceId": { "type": "string" },
-                "type": { "type": "string", "enum": ["spec", "task", "general"] }
+                "type": { "type": "string", "enum": ["spec", "task", "general"] },
+                "dedup": { "type": "boolean", "description": "Return the existing general note with the same content instead of creating a duplicate" }
             },
             "required": ["title"]
         })),
@@ -213,6 +257,14 @@ fn build_tool_list_inner() -> Vec<serde_json::Value> {
             },
             "required": ["noteId"]
         })),
+        tool_def("get_my_notes", "Get the notes assigned to the calling agent.", serde_json::json!({
+            "type": "object",
+            "properties": {
+                "agentId": { "type": "string", "description": "Your agent ID" },
+                "workspaceId": { "type": "string" }
+            },
+            "required": ["agentId"]
+        })),
         tool_def("set_note_content", "Set (replace) the content of a note. Spec note is auto-created if missing.", serde_json::json!({
             "type": "object",
             "properties": {
@@ -245,6 +297,14 @@ fn build_tool_list_inner() -> Vec<serde_json::Value> {
             "type": "object",
             "properties": {}
         })),
+        tool_def("use_skill", "Load a discovered skill's instructions so you can follow them. Optionally return only one section.", serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "description": "Skill name (see list_skills)" },
+                "section": { "type": "string", "description": "Heading of the section to return" }
+            },
+            "required": ["name"]
+        })),
         tool_def("list_specialists", "List all available specialist configurations (roles, model tiers, descriptions).", serde_json::json!({
             "type": "object",
             "properties": {}
@@ -269,7 +329,9 @@ fn build_tool_list_inner() -> Vec<serde_json::Value> {
             "properties": {
                 "agentId": { "type": "string", "description": "Your agent ID" },
                 "agentName": { "type": "string", "description": "Your agent name" },
-                "eventTypes": { "type": "array", "items": { "type": "string" }, "description": "Event types to subscribe to" }
+                "eventTypes": { "type": "array", "items": { "type": "string" }, "description": "Event types to subscribe to" },
+                "crossWorkspace": { "type": "boolean", "description": "Also receive events from other workspaces (default: false)" },
+                "ttlSecs": { "type": "integer", "description": "Seconds until the subscription expires; 0 never expires (default: server setting, 1 hour)" }
             },
             "required": ["agentId", "agentName", "eventTypes"]
         })),
@@ -438,6 +500,7 @@ mod tests {
     use std::collections::HashSet;
 
     use super::{build_tool_list_for_profile, tool_allowed_for_profile};
+    use crate::models::agent::AgentRole;
 
     #[test]
     fn kanban_profile_only_allows_kanban_tools() {
@@ -452,9 +515,24 @@ mod tests {
         assert!(tool_allowed_for_profile("list_agents", None));
     }
 
+    #[test]
+    fn planning_profile_withholds_delegation_tools() {
+        let names: HashSet<String> =
+            build_tool_list_for_profile(Some("routa-planning"), Some(AgentRole::Routa))
+                .iter()
+                .filter_map(|tool| tool.get("name").and_then(|v| v.as_str()))
+                .map(str::to_string)
+                .collect();
+
+        assert!(!names.contains("create_agent"));
+        assert!(!names.contains("delegate_task_to_agent"));
+        assert!(names.contains("create_task"));
+        assert!(names.contains("set_note_content"));
+    }
+
     #[test]
     fn build_tool_list_for_kanban_profile_filters_to_allowed_set() {
-        let tools = build_tool_list_for_profile(Some("kanban-planning"));
+        let tools = build_tool_list_for_profile(Some("kanban-planning"), None);
         let names: Vec<&str> = tools
             .iter()
             .filter_map(|tool| tool.get("name").and_then(|v| v.as_str()))
@@ -477,4 +555,28 @@ mod tests {
         assert!(!names.is_empty());
         assert!(names.iter().all(|name| allowed.contains(name)));
     }
+
+    #[test]
+    fn developer_tool_list_omits_delegation_tools() {
+        let names = |role| {
+            build_tool_list_for_profile(None, role)
+                .iter()
+                .filter_map(|tool| tool.get("name").and_then(|v| v.as_str()))
+                .map(str::to_string)
+                .collect::<HashSet<_>>()
+        };
+
+        let developer = names(Some(AgentRole::Developer));
+        assert!(!developer.contains("delegate_task_to_agent"));
+        assert!(!developer.contains("create_agent"));
+        assert!(!developer.contains("report_to_parent"));
+        assert!(developer.contains("create_note"));
+
+        let routa = names(Some(AgentRole::Routa));
+        assert!(routa.contains("delegate_task_to_agent"));
+        assert!(!routa.contains("report_to_parent"));
+
+        assert!(names(Some(AgentRole::Crafter)).contains("report_to_parent"));
+        assert_eq!(names(None).len(), super::build_tool_list_public().len());
+    }
 }
@@ -3,15 +3,30 @@ mod delegation;
 mod events_kanban;
 mod notes_workspace;
 
+use std::time::Instant;
+
+use crate::error::ServerError;
+use crate::models::tool_audit::ToolAuditEntry;
 use crate::rpc::RpcRouter;
 use crate::state::AppState;
+use crate::tools::ToolResult;
 
 pub(super) async fn execute_tool_public(
     state: &AppState,
     name: &str,
     args: &serde_json::Value,
 ) -> serde_json::Value {
-    execute_tool(state, normalize_tool_name(name), args).await
+    let name = normalize_tool_name(name);
+    let started = Instant::now();
+    let result = execute_tool(state, name, args).await;
+    let success = result.get("isError").and_then(|v| v.as_bool()) != Some(true);
+    state.tool_audit_store.append_detached(ToolAuditEntry::new(
+        name,
+        args,
+        success,
+        started.elapsed().as_millis() as i64,
+    ));
+    result
 }
 
 pub(super) fn normalize_tool_name_public(name: &str) -> &str {
@@ -47,10 +62,7 @@ fn normalize_tool_name(name: &str) -> &str {
 }
 
 pub(super) fn tool_result_text(text: &str) -> serde_json::Value {
-    serde_json::json!({
-        "isError": false,
-        "content": [{ "type": "text", "text": text }]
-    })
+    ToolResult::success(text).to_mcp_data_content()
 }
 
 pub(super) async fn rpc_tool_result(
@@ -81,22 +93,86 @@ pub(super) async fn rpc_tool_result(
 }
 
 pub(super) fn tool_result_json(value: &serde_json::Value) -> serde_json::Value {
-    serde_json::json!({
-        "isError": false,
-        "content": [{ "type": "text", "text": serde_json::to_string_pretty(value).unwrap_or_default() }]
-    })
+    ToolResult::success(value).to_mcp_data_content()
 }
 
 pub(super) fn tool_result_error(msg: &str) -> serde_json::Value {
-    serde_json::json!({
-        "isError": true,
-        "content": [{ "type": "text", "text": msg }]
-    })
+    ToolResult::error(msg).to_mcp_content()
+}
+
+/// Adapt a shared [`AgentTools`](crate::tools::AgentTools) call to an MCP
+/// result. Successful calls keep the payload `tool` returned before it was
+/// routed through `AgentTools`; see [`legacy_tool_payload`].
+pub(super) fn agent_tool_result(
+    tool: &str,
+    result: Result<ToolResult, ServerError>,
+) -> serde_json::Value {
+    match result {
+        Ok(ToolResult {
+            success: true,
+            data: Some(data),
+            ..
+        }) => tool_result_json(&legacy_tool_payload(tool, data)),
+        Ok(tool_result) => tool_result.to_mcp_content(),
+        Err(e) => tool_result_error(&e.to_string()),
+    }
+}
+
+/// Reshape `AgentTools` data into the MCP payload `tool` has always returned:
+/// lists are bare arrays, and objects carry `success: true` at the top level
+/// (except the read-only status and summary tools, which never did).
+fn legacy_tool_payload(tool: &str, mut data: serde_json::Value) -> serde_json::Value {
+    if tool == "read_agent_conversation" {
+        return data["messages"].take();
+    }
+    let serde_json::Value::Object(mut fields) = data else {
+        return data;
+    };
+    match tool {
+        "get_agent_status" => {
+            let task_count = fields
+                .get("tasks")
+                .and_then(|tasks| tasks.as_array())
+                .map_or(0, Vec::len);
+            fields.insert("taskCount".to_string(), task_count.into());
+            return serde_json::Value::Object(fields);
+        }
+        "get_agent_summary" => {
+            let active_tasks = fields
+                .get("activeTasks")
+                .and_then(|tasks| tasks.as_array())
+                .map_or(0, Vec::len);
+            let message_count = fields
+                .get("messageCount")
+                .and_then(|count| count.as_u64())
+                .unwrap_or(0);
+            fields.insert("activeTasks".to_string(), active_tasks.into());
+            fields.insert("recentMessages".to_string(), message_count.min(5).into());
+            return serde_json::Value::Object(fields);
+        }
+        "update_task_status" => {
+            if let Some(status) = fields.get("newStatus").cloned() {
+                fields.insert("status".to_string(), status);
+            }
+        }
+        _ => {}
+    }
+    fields.insert("success".to_string(), true.into());
+    serde_json::Value::Object(fields)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::normalize_tool_name_public;
+    use std::sync::Arc;
+
+    use super::{
+        execute_tool_public, normalize_tool_name_public, tool_result_error, tool_result_json,
+        tool_result_text,
+    };
+    use crate::models::task::TaskCreationSource;
+    use crate::models::tool_audit::ToolAuditFilter;
+    use crate::state::{AppState, AppStateInner};
+    use crate::tools::ToolResult;
 
     #[test]
     fn normalize_tool_name_supports_compat_prefixes() {
@@ -110,4 +186,297 @@ mod tests {
         );
         assert_eq!(normalize_tool_name_public("list_tasks"), "list_tasks");
     }
+
+    #[test]
+    fn tool_result_maps_to_mcp_content() {
+        let failed = ToolResult::error("Task not found: t-1").to_mcp_content();
+        assert_eq!(failed["isError"], serde_json::json!(true));
+        assert_eq!(failed["content"][0]["type"], "text");
+        assert_eq!(failed["content"][0]["text"], "Task not found: t-1");
+
+        let ok: serde_json::Value =
+            ToolResult::success(serde_json::json!({ "taskId": "t-1" })).into();
+        assert_eq!(ok["isError"], serde_json::json!(false));
+        let text = ok["content"][0]["text"].as_str().expect("text content");
+        let payload: serde_json::Value = serde_json::from_str(text).expect("json payload");
+        assert_eq!(payload["success"], serde_json::json!(true));
+        assert_eq!(payload["data"]["taskId"], "t-1");
+
+        assert_eq!(
+            tool_result_error("Invalid status: X"),
+            ToolResult::error("Invalid status: X").to_mcp_content()
+        );
+        assert_eq!(tool_result_text("plain")["content"][0]["text"], "plain");
+        let listed = tool_result_json(&serde_json::json!([{ "id": "a-1" }]));
+        assert_eq!(listed["isError"], serde_json::json!(false));
+        let text = listed["content"][0]["text"].as_str().expect("text content");
+        let payload: serde_json::Value = serde_json::from_str(text).expect("json payload");
+        assert_eq!(payload[0]["id"], "a-1");
+    }
+
+    #[tokio::test]
+    async fn create_task_matches_shared_agent_tools() {
+        let db = crate::db::Database::open_in_memory().expect("open in-memory database");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("ensure default workspace");
+
+        let via_mcp = execute_tool_public(
+            &state,
+            "routa-coordination_create_task",
+            &serde_json::json!({
+                "title": "Add login",
+                "objective": "Users can sign in",
+                "scope": "auth",
+                "acceptanceCriteria": ["form renders", "bad password rejected"],
+                "creationSource": "agent",
+            }),
+        )
+        .await;
+        assert_eq!(via_mcp["isError"], serde_json::json!(false));
+        let text = via_mcp["content"][0]["text"]
+            .as_str()
+            .expect("text content");
+        let mut via_mcp: serde_json::Value = serde_json::from_str(text).expect("json payload");
+
+        let direct = state
+            .agent_tools
+            .create_task(
+                "Add login",
+                "Users can sign in",
+                "default",
+                None,
+                Some("auth"),
+                Some(vec![
+                    "form renders".to_string(),
+                    "bad password rejected".to_string(),
+                ]),
+                None,
+                None,
+                None,
+                None,
+                Some(TaskCreationSource::Agent),
+            )
+            .await
+            .expect("create task directly");
+        let mut direct = direct.data.expect("tool result data");
+        direct["success"] = serde_json::json!(true);
+
+        // MCP keeps its original flat `{success, taskId, ...}` payload.
+        let mcp_id = via_mcp["taskId"].take();
+        let direct_id = direct["taskId"].take();
+        assert_eq!(via_mcp, direct);
+
+        let load = |id: serde_json::Value| {
+            let state = state.clone();
+            async move {
+                let mut task = state
+                    .task_store
+                    .get(id.as_str().expect("task id"))
+                    .await
+                    .expect("load task")
+                    .expect("task exists");
+                task.id.clear();
+                task.created_at = chrono::DateTime::<chrono::Utc>::MIN_UTC;
+                task.updated_at = chrono::DateTime::<chrono::Utc>::MIN_UTC;
+                serde_json::to_value(task).expect("serialize task")
+            }
+        };
+        assert_eq!(load(mcp_id).await, load(direct_id).await);
+    }
+
+    #[tokio::test]
+    async fn coordination_tools_keep_their_legacy_payloads() {
+        let db = crate::db::Database::open_in_memory().expect("open in-memory database");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("ensure default workspace");
+        let payload = |result: serde_json::Value| -> serde_json::Value {
+            assert_eq!(result["isError"], serde_json::json!(false), "{result}");
+            let text = result["content"][0]["text"].as_str().expect("text content");
+            serde_json::from_str(text).expect("json payload")
+        };
+
+        let created = payload(
+            execute_tool_public(
+                &state,
+                "create_agent",
+                &serde_json::json!({ "name": "worker", "role": "CRAFTER" }),
+            )
+            .await,
+        );
+        assert_eq!(created["success"], serde_json::json!(true));
+        let agent_id = created["agentId"].as_str().expect("agent id").to_string();
+
+        let agents =
+            payload(execute_tool_public(&state, "list_agents", &serde_json::json!({})).await);
+        assert_eq!(agents.as_array().map(Vec::len), Some(1));
+        let tasks =
+            payload(execute_tool_public(&state, "list_tasks", &serde_json::json!({})).await);
+        assert!(tasks.is_array());
+        let messages = payload(
+            execute_tool_public(
+                &state,
+                "read_agent_conversation",
+                &serde_json::json!({ "agentId": agent_id }),
+            )
+            .await,
+        );
+        assert!(messages.is_array());
+
+        let status = payload(
+            execute_tool_public(
+                &state,
+                "get_agent_status",
+                &serde_json::json!({ "agentId": agent_id }),
+            )
+            .await,
+        );
+        assert_eq!(status["taskCount"], serde_json::json!(0));
+        assert!(status.get("success").is_none());
+        let summary = payload(
+            execute_tool_public(
+                &state,
+                "get_agent_summary",
+                &serde_json::json!({ "agentId": agent_id }),
+            )
+            .await,
+        );
+        assert_eq!(summary["activeTasks"], serde_json::json!(0));
+        assert_eq!(summary["recentMessages"], serde_json::json!(0));
+        assert!(summary.get("lastActivity").is_some());
+
+        let task = payload(
+            execute_tool_public(&state, "create_task", &serde_json::json!({ "title": "T" })).await,
+        );
+        let updated = payload(
+            execute_tool_public(
+                &state,
+                "update_task_status",
+                &serde_json::json!({
+                    "taskId": task["taskId"],
+                    "status": "IN_PROGRESS",
+                    "agentId": agent_id,
+                }),
+            )
+            .await,
+        );
+        assert_eq!(updated["success"], serde_json::json!(true));
+        assert_eq!(updated["status"], "IN_PROGRESS");
+        assert_eq!(updated["taskId"], task["taskId"]);
+    }
+
+    #[tokio::test]
+    async fn create_task_records_tool_audit_row() {
+        let db = crate::db::Database::open_in_memory().expect("open in-memory database");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("ensure default workspace");
+
+        let result = execute_tool_public(
+            &state,
+            "routa-coordination_create_task",
+            &serde_json::json!({
+                "title": "Add login",
+                "objective": "Users can sign in",
+                "agentId": "routa-1",
+                "apiToken": "sk-secret",
+            }),
+        )
+        .await;
+        assert_eq!(result["isError"], serde_json::json!(false));
+
+        // The audit row is written off the tool's path; give it a moment.
+        let filter = ToolAuditFilter {
+            tool_name: Some("create_task".to_string()),
+            ..Default::default()
+        };
+        let mut rows = Vec::new();
+        for _ in 0..50 {
+            rows = state
+                .tool_audit_store
+                .list(&filter)
+                .await
+                .expect("list audit rows");
+            if !rows.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(rows.len(), 1);
+        let row = &rows[0];
+        assert!(row.success);
+        assert_eq!(row.agent_id.as_deref(), Some("routa-1"));
+        assert!(row.args.contains("Add login"));
+        assert!(!row.args.contains("sk-secret"));
+    }
+
+    #[tokio::test]
+    async fn use_skill_returns_discovered_skill_content() {
+        let db = crate::db::Database::open_in_memory().expect("open in-memory database");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let skill_dir = cwd.path().join(".agents/skills/release-notes-test");
+        std::fs::create_dir_all(&skill_dir).expect("skill dir");
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: release-notes-test\ndescription: Draft release notes\n---\n\n\
+             Read the changelog.\n\n## Format\nOne bullet per change.\n\n## Tone\nPlain.",
+        )
+        .expect("write SKILL.md");
+        state.skill_registry.reload(&cwd.path().to_string_lossy());
+
+        let text = |result: serde_json::Value| {
+            assert_eq!(result["isError"], serde_json::json!(false));
+            result["content"][0]["text"]
+                .as_str()
+                .expect("text content")
+                .to_string()
+        };
+        let full = text(
+            execute_tool_public(
+                &state,
+                "use_skill",
+                &serde_json::json!({ "name": "release-notes-test" }),
+            )
+            .await,
+        );
+        assert!(full.starts_with("# Skill: release-notes-test"));
+        assert!(full.contains("Draft release notes"));
+        assert!(full.contains("Read the changelog."));
+        assert!(full.contains("## Tone\nPlain."));
+
+        let section = text(
+            execute_tool_public(
+                &state,
+                "use_skill",
+                &serde_json::json!({ "name": "release-notes-test", "section": "Format" }),
+            )
+            .await,
+        );
+        assert!(section.ends_with("## Format\nOne bullet per change."));
+        assert!(!section.contains("Read the changelog."));
+
+        let used = state
+            .skill_store
+            .get("release-notes-test")
+            .await
+            .expect("load skill")
+            .expect("usage recorded");
+        assert_eq!(used.uses, 2);
+
+        let missing =
+            execute_tool_public(&state, "use_skill", &serde_json::json!({ "name": "nope" })).await;
+        assert_eq!(missing["isError"], serde_json::json!(true));
+    }
 }
@@ -1,6 +1,9 @@
 use crate::state::AppState;
+use crate::tools::AgentNamePolicy;
 
-use super::{rpc_tool_result, tool_result_error, tool_result_json, tool_result_text};
+use super::{
+    agent_tool_result, rpc_tool_result, tool_result_error, tool_result_json, tool_result_text,
+};
 
 pub(super) async fn execute(
     state: &AppState,
@@ -9,203 +12,106 @@ pub(super) async fn execute(
     workspace_id: &str,
 ) -> Option<serde_json::Value> {
     let result = match name {
-        "list_agents" => match state.agent_store.list_by_workspace(workspace_id).await {
-            Ok(agents) => {
-                tool_result_text(&serde_json::to_string_pretty(&agents).unwrap_or_default())
-            }
-            Err(e) => tool_result_error(&e.to_string()),
-        },
+        "list_agents" => agent_tool_result(
+            name,
+            state
+                .agent_tools
+                .list_agents(
+                    workspace_id,
+                    args.get("includeLive")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                )
+                .await,
+        ),
         "create_agent" => {
-            let name_val = args
-                .get("name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unnamed");
-            let role_str = args
-                .get("role")
-                .and_then(|v| v.as_str())
-                .unwrap_or("CRAFTER");
-            let parent_id = args
-                .get("parentId")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-            let role = crate::models::agent::AgentRole::from_str(role_str);
-            match role {
-                Some(r) => {
-                    let agent = crate::models::agent::Agent::new(
-                        uuid::Uuid::new_v4().to_string(),
-                        name_val.to_string(),
-                        r,
-                        workspace_id.to_string(),
-                        parent_id,
-                        None,
-                        None,
-                    );
-                    match state.agent_store.save(&agent).await {
-                        Ok(_) => tool_result_json(&serde_json::json!({
-                            "success": true,
-                            "agentId": agent.id,
-                            "name": agent.name,
-                            "role": role_str
-                        })),
-                        Err(e) => tool_result_error(&e.to_string()),
-                    }
-                }
-                None => tool_result_error(&format!("Invalid role: {role_str}")),
-            }
+            let name_policy = AgentNamePolicy::from_flags(
+                args.get("uniqueName")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+                args.get("autoSuffix")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+            );
+            agent_tool_result(
+                name,
+                state
+                    .agent_tools
+                    .create_agent(
+                        args.get("name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("unnamed"),
+                        args.get("role")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("CRAFTER"),
+                        workspace_id,
+                        args.get("parentId").and_then(|v| v.as_str()),
+                        args.get("modelTier").and_then(|v| v.as_str()),
+                        name_policy,
+                    )
+                    .await,
+            )
         }
         "read_agent_conversation" => {
             let agent_id = args.get("agentId").and_then(|v| v.as_str()).unwrap_or("");
             let limit = args.get("limit").and_then(|v| v.as_i64()).unwrap_or(50) as usize;
-            match state.conversation_store.get_last_n(agent_id, limit).await {
-                Ok(messages) => {
-                    tool_result_text(&serde_json::to_string_pretty(&messages).unwrap_or_default())
-                }
-                Err(e) => tool_result_error(&e.to_string()),
-            }
+            agent_tool_result(
+                name,
+                state
+                    .agent_tools
+                    .read_agent_conversation(agent_id, Some(limit), None, None, true)
+                    .await,
+            )
         }
         "get_agent_status" => {
             let agent_id = args.get("agentId").and_then(|v| v.as_str()).unwrap_or("");
-            match state.agent_store.get(agent_id).await {
-                Ok(Some(agent)) => {
-                    let tasks = state
-                        .task_store
-                        .list_by_assignee(agent_id)
-                        .await
-                        .unwrap_or_default();
-                    let msg_count = state
-                        .conversation_store
-                        .get_message_count(agent_id)
-                        .await
-                        .unwrap_or(0);
-                    tool_result_json(&serde_json::json!({
-                        "agentId": agent.id,
-                        "name": agent.name,
-                        "status": agent.status.as_str(),
-                        "role": agent.role.as_str(),
-                        "messageCount": msg_count,
-                        "taskCount": tasks.len(),
-                        "tasks": tasks.iter().map(|t| serde_json::json!({
-                            "id": t.id,
-                            "title": t.title,
-                            "status": t.status.as_str()
-                        })).collect::<Vec<_>>()
-                    }))
-                }
-                Ok(None) => tool_result_error(&format!("Agent not found: {agent_id}")),
-                Err(e) => tool_result_error(&e.to_string()),
-            }
+            agent_tool_result(name, state.agent_tools.get_agent_status(agent_id).await)
         }
         "get_agent_summary" => {
             let agent_id = args.get("agentId").and_then(|v| v.as_str()).unwrap_or("");
-            match state.agent_store.get(agent_id).await {
-                Ok(Some(agent)) => {
-                    let messages = state
-                        .conversation_store
-                        .get_last_n(agent_id, 5)
-                        .await
-                        .unwrap_or_default();
-                    let tasks = state
-                        .task_store
-                        .list_by_assignee(agent_id)
-                        .await
-                        .unwrap_or_default();
-                    let active_tasks: Vec<_> = tasks
-                        .iter()
-                        .filter(|t| t.status == crate::models::task::TaskStatus::InProgress)
-                        .collect();
-                    tool_result_json(&serde_json::json!({
-                        "agentId": agent.id,
-                        "name": agent.name,
-                        "status": agent.status.as_str(),
-                        "role": agent.role.as_str(),
-                        "activeTasks": active_tasks.len(),
-                        "recentMessages": messages.len(),
-                        "lastActivity": agent.updated_at
-                    }))
-                }
-                Ok(None) => tool_result_error(&format!("Agent not found: {agent_id}")),
-                Err(e) => tool_result_error(&e.to_string()),
-            }
-        }
-        "list_tasks" => match state.task_store.list_by_workspace(workspace_id).await {
-            Ok(tasks) => {
-                tool_result_text(&serde_json::to_string_pretty(&tasks).unwrap_or_default())
-            }
-            Err(e) => tool_result_error(&e.to_string()),
-        },
-        "create_task" => {
-            let title = args
-                .get("title")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Untitled");
-            let objective = args.get("objective").and_then(|v| v.as_str()).unwrap_or("");
-            let session_id = args
-                .get("sessionId")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string());
-            let mut task = crate::models::task::Task::new(
-                uuid::Uuid::new_v4().to_string(),
-                title.to_string(),
-                objective.to_string(),
-                workspace_id.to_string(),
-                session_id,
-                args.get("scope")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_string()),
-                None,
-                None,
-                None,
-                None,
-                None,
-            );
-            if let Some(source) = args
-                .get("creationSource")
-                .and_then(|v| v.as_str())
-                .and_then(crate::models::task::TaskCreationSource::from_str)
-            {
-                task.creation_source = Some(source);
-            }
-            let task_id = task.id.clone();
-            match state.task_store.save(&task).await {
-                Ok(_) => tool_result_json(&serde_json::json!({
-                    "success": true,
-                    "taskId": task_id,
-                    "title": title,
-                    "creationSource": task.creation_source
-                })),
-                Err(e) => tool_result_error(&e.to_string()),
-            }
+            agent_tool_result(name, state.agent_tools.get_agent_summary(agent_id).await)
         }
+        "list_tasks" => agent_tool_result(name, state.agent_tools.list_tasks(workspace_id).await),
+        "create_task" => agent_tool_result(
+            name,
+            state
+                .agent_tools
+                .create_task(
+                    args.get("title")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("Untitled"),
+                    args.get("objective").and_then(|v| v.as_str()).unwrap_or(""),
+                    workspace_id,
+                    args.get("sessionId").and_then(|v| v.as_str()),
+                    args.get("scope").and_then(|v| v.as_str()),
+                    parse_string_array_arg(args, "acceptanceCriteria"),
+                    parse_string_array_arg(args, "verificationCommands"),
+                    parse_string_array_arg(args, "testCases"),
+                    parse_string_array_arg(args, "dependencies"),
+                    args.get("parallelGroup").and_then(|v| v.as_str()),
+                    args.get("creationSource")
+                        .and_then(|v| v.as_str())
+                        .and_then(crate::models::task::TaskCreationSource::from_str),
+                )
+                .await,
+        ),
         "update_task_status" => {
             let task_id = args.get("taskId").and_then(|v| v.as_str()).unwrap_or("");
-            let status_str = args.get("status").and_then(|v| v.as_str()).unwrap_or("");
+            let status = args.get("status").and_then(|v| v.as_str()).unwrap_or("");
             let agent_id = args.get("agentId").and_then(|v| v.as_str()).unwrap_or("");
-            let reason = args.get("reason").and_then(|v| v.as_str());
-            match crate::models::task::TaskStatus::from_str(status_str) {
-                Some(status) => match state.task_store.update_status(task_id, &status).await {
-                    Ok(_) => {
-                        let event = crate::events::AgentEvent {
-                            event_type: crate::events::AgentEventType::TaskStatusChanged,
-                            agent_id: agent_id.to_string(),
-                            workspace_id: workspace_id.to_string(),
-                            data: serde_json::json!({
-                                "taskId": task_id,
-                                "status": status_str,
-                                "reason": reason
-                            }),
-                            timestamp: chrono::Utc::now(),
-                        };
-                        state.event_bus.emit(event).await;
-                        tool_result_json(&serde_json::json!({
-                            "success": true,
-                            "taskId": task_id,
-                            "status": status_str
-                        }))
-                    }
-                    Err(e) => tool_result_error(&e.to_string()),
-                },
-                None => tool_result_error(&format!("Invalid status: {status_str}")),
-            }
+            agent_tool_result(
+                name,
+                state
+                    .agent_tools
+                    .update_task_status(
+                        task_id,
+                        status,
+                        agent_id,
+                        args.get("reason").and_then(|v| v.as_str()),
+                        args.get("summary").and_then(|v| v.as_str()),
+                    )
+                    .await,
+            )
         }
         "update_task" => {
             let task_id = args.get("taskId").and_then(|v| v.as_str()).unwrap_or("");
@@ -229,7 +135,7 @@ pub(super) async fn execute(
                 task.scope = Some(scope.to_string());
             }
             if let Some(values) = parse_string_array_arg(args, "acceptanceCriteria") {
-                task.acceptance_criteria = Some(values);
+                task.replace_acceptance_criteria(values);
             }
             if let Some(values) = parse_string_array_arg(args, "verificationCommands") {
                 task.verification_commands = Some(values);
@@ -250,17 +156,18 @@ pub(super) async fn execute(
             match state.task_store.save(&task).await {
                 Ok(_) => {
                     if task.status != old_status {
-                        let event = crate::events::AgentEvent {
-                            event_type: crate::events::AgentEventType::TaskStatusChanged,
-                            agent_id: agent_id.to_string(),
-                            workspace_id: workspace_id.to_string(),
-                            data: serde_json::json!({
-                                "taskId": task_id,
-                                "oldStatus": old_status.as_str(),
-                                "newStatus": task.status.as_str()
-                            }),
-                            timestamp: chrono::Utc::now(),
-                        };
+                        let event = crate::events::AgentEvent::task_status_changed(
+                            agent_id,
+                            workspace_id,
+                            crate::events::TaskStatusChangedData {
+                                task_id: task_id.to_string(),
+                                task_title: Some(task.title.clone()),
+                                old_status,
+                                new_status: task.status.clone(),
+                                reason: None,
+                                summary: None,
+                            },
+                        );
                         state.event_bus.emit(event).await;
                     }
 
@@ -282,6 +189,25 @@ pub(super) async fn execute(
                 Err(e) => tool_result_error(&e.to_string()),
             }
         }
+        "reopen_task" => match rpc_tool_result(
+            state,
+            "tasks.reopen",
+            serde_json::json!({
+                "taskId": args.get("taskId").and_then(|v| v.as_str()).unwrap_or(""),
+                "reason": args.get("reason").and_then(|v| v.as_str()).unwrap_or(""),
+                "agentId": args.get("agentId").and_then(|v| v.as_str()).unwrap_or(""),
+            }),
+        )
+        .await
+        {
+            Ok(result) => tool_result_json(&serde_json::json!({
+                "success": true,
+                "taskId": result.pointer("/task/id").cloned().unwrap_or_default(),
+                "status": result.pointer("/task/status").cloned().unwrap_or_default(),
+                "previousStatus": result.get("previousStatus").cloned().unwrap_or_default(),
+            })),
+            Err(error) => tool_result_error(&error),
+        },
         "provide_artifact" => match rpc_tool_result(
             state,
             "tasks.provideArtifact",
@@ -1,9 +1,8 @@
-use std::sync::Arc;
-
 use crate::state::AppState;
-use routa_core::orchestration::{DelegateWithSpawnParams, OrchestratorConfig, RoutaOrchestrator};
+use routa_core::models::agent::ModelTier;
+use routa_core::orchestration::DelegateWithSpawnParams;
 
-use super::{tool_result_error, tool_result_json};
+use super::{agent_tool_result, tool_result_error};
 
 pub(super) async fn execute(
     state: &AppState,
@@ -27,6 +26,17 @@ pub(super) async fn execute(
                 .and_then(|v| v.as_str())
                 .filter(|s| !s.is_empty())
                 .map(str::to_string);
+            let model_tier = match args.get("modelTier").and_then(|v| v.as_str()) {
+                None | Some("") => None,
+                Some(tier) => match ModelTier::from_str(&tier.to_ascii_uppercase()) {
+                    Some(tier) => Some(tier),
+                    None => {
+                        return Some(tool_result_error(&format!(
+                            "Unknown model tier: {tier}. Use FAST, BALANCED, or SMART."
+                        )));
+                    }
+                },
+            };
             let caller_session_id = args
                 .get("callerSessionId")
                 .and_then(|v| v.as_str())
@@ -111,13 +121,6 @@ pub(super) async fn execute(
                 cwd = resolve_task_or_workspace_cwd(state, task_id, workspace_id).await;
             }
 
-            let orchestrator = RoutaOrchestrator::new(
-                OrchestratorConfig::default(),
-                Arc::new(state.acp_manager.clone()),
-                state.agent_store.clone(),
-                state.task_store.clone(),
-                state.event_bus.clone(),
-            );
             let params = DelegateWithSpawnParams {
                 task_id: task_id.to_string(),
                 caller_agent_id: caller_agent_id.to_string(),
@@ -125,61 +128,25 @@ pub(super) async fn execute(
                 workspace_id: workspace_id.to_string(),
                 specialist: specialist.to_string(),
                 provider,
+                model_tier,
                 cwd,
                 additional_instructions,
                 wait_mode,
+                isolate: args
+                    .get("isolate")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+                caller_depth: None,
             };
-            let result = match orchestrator.delegate_task_with_spawn(params).await {
-                Ok(tool_result) => tool_result,
-                Err(error) => {
-                    return Some(tool_result_error(&format!(
-                        "Failed to delegate task: {error}"
-                    )))
-                }
-            };
-
-            tool_result_json(&serde_json::to_value(&result).unwrap_or_default())
+            match state.orchestrator.delegate_task_with_spawn(params).await {
+                Ok(tool_result) => tool_result.to_mcp_content(),
+                Err(error) => tool_result_error(&format!("Failed to delegate task: {error}")),
+            }
         }
         "report_to_parent" => {
             let agent_id = args.get("agentId").and_then(|v| v.as_str()).unwrap_or("");
-            let task_id = args.get("taskId").and_then(|v| v.as_str()).unwrap_or("");
-            let summary = args.get("summary").and_then(|v| v.as_str()).unwrap_or("");
-            let success = args
-                .get("success")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(true);
-
-            let new_status = if success {
-                crate::models::task::TaskStatus::Completed
-            } else {
-                crate::models::task::TaskStatus::NeedsFix
-            };
-
-            if let Err(e) = state.task_store.update_status(task_id, &new_status).await {
-                return Some(tool_result_error(&format!(
-                    "Failed to update task status: {e}"
-                )));
-            }
-
-            let event = crate::events::AgentEvent {
-                event_type: crate::events::AgentEventType::ReportSubmitted,
-                agent_id: agent_id.to_string(),
-                workspace_id: workspace_id.to_string(),
-                data: serde_json::json!({
-                    "taskId": task_id,
-                    "summary": summary,
-                    "success": success
-                }),
-                timestamp: chrono::Utc::now(),
-            };
-            state.event_bus.emit(event).await;
-
-            tool_result_json(&serde_json::json!({
-                "success": true,
-                "taskId": task_id,
-                "reported": true,
-                "taskStatus": new_status.as_str()
-            }))
+            let report = crate::tools::CompletionReport::from_tool_args(agent_id, args);
+            agent_tool_result(name, state.report_to_parent(agent_id, report).await)
         }
         "send_message_to_agent" => {
             let from_agent_id = args
@@ -188,40 +155,13 @@ pub(super) async fn execute(
                 .unwrap_or("");
             let to_agent_id = args.get("toAgentId").and_then(|v| v.as_str()).unwrap_or("");
             let message = args.get("message").and_then(|v| v.as_str()).unwrap_or("");
-
-            let msg = crate::models::message::Message::new(
-                uuid::Uuid::new_v4().to_string(),
-                to_agent_id.to_string(),
-                crate::models::message::MessageRole::User,
-                message.to_string(),
-                None,
-                None,
-                None,
-            );
-
-            if let Err(e) = state.conversation_store.append(&msg).await {
-                return Some(tool_result_error(&format!("Failed to send message: {e}")));
-            }
-
-            let event = crate::events::AgentEvent {
-                event_type: crate::events::AgentEventType::MessageSent,
-                agent_id: from_agent_id.to_string(),
-                workspace_id: workspace_id.to_string(),
-                data: serde_json::json!({
-                    "fromAgentId": from_agent_id,
-                    "toAgentId": to_agent_id,
-                    "messageId": msg.id
-                }),
-                timestamp: chrono::Utc::now(),
-            };
-            state.event_bus.emit(event).await;
-
-            tool_result_json(&serde_json::json!({
-                "success": true,
-                "messageId": msg.id,
-                "fromAgentId": from_agent_id,
-                "toAgentId": to_agent_id
-            }))
+            agent_tool_result(
+                name,
+                state
+                    .agent_tools
+                    .message_agent(from_agent_id, to_agent_id, message)
+                    .await,
+            )
         }
         _ => return None,
     };
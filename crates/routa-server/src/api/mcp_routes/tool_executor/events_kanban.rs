@@ -1,6 +1,6 @@
 use crate::state::AppState;
 
-use super::{rpc_tool_result, tool_result_error, tool_result_json};
+use super::{agent_tool_result, rpc_tool_result, tool_result_error, tool_result_json};
 
 fn required_str_arg<'a>(
     args: &'a serde_json::Value,
@@ -24,45 +24,48 @@ pub(super) async fn execute(
         "subscribe_to_events" => {
             let agent_id = args.get("agentId").and_then(|v| v.as_str()).unwrap_or("");
             let agent_name = args.get("agentName").and_then(|v| v.as_str()).unwrap_or("");
-            let event_types: Vec<crate::events::AgentEventType> = args
+            let event_types = args
                 .get("eventTypes")
                 .and_then(|v| v.as_array())
                 .map(|arr| {
                     arr.iter()
                         .filter_map(|v| v.as_str())
-                        .filter_map(crate::events::AgentEventType::from_str)
+                        .map(str::to_string)
                         .collect()
                 })
                 .unwrap_or_default();
-
-            let subscription_id = uuid::Uuid::new_v4().to_string();
-            let subscription = crate::events::EventSubscription {
-                id: subscription_id.clone(),
-                agent_id: agent_id.to_string(),
-                agent_name: agent_name.to_string(),
-                event_types,
-                exclude_self: true,
-                one_shot: false,
-                wait_group_id: None,
-                priority: 0,
-            };
-            state.event_bus.subscribe(subscription).await;
-
-            tool_result_json(&serde_json::json!({
-                "success": true,
-                "subscriptionId": subscription_id
-            }))
+            agent_tool_result(
+                name,
+                state
+                    .agent_tools
+                    .subscribe_to_events(
+                        agent_id,
+                        agent_name,
+                        event_types,
+                        true,
+                        false,
+                        None,
+                        0,
+                        args.get("crossWorkspace")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false),
+                        args.get("ttlSecs").and_then(|v| v.as_u64()),
+                    )
+                    .await,
+            )
         }
         "unsubscribe_from_events" => {
             let subscription_id = args
                 .get("subscriptionId")
                 .and_then(|v| v.as_str())
                 .unwrap_or("");
-            state.event_bus.unsubscribe(subscription_id).await;
-            tool_result_json(&serde_json::json!({
-                "success": true,
-                "subscriptionId": subscription_id
-            }))
+            agent_tool_result(
+                name,
+                state
+                    .agent_tools
+                    .unsubscribe_from_events(subscription_id)
+                    .await,
+            )
         }
         "create_board" => match rpc_tool_result(
             state,
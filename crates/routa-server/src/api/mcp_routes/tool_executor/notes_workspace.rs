@@ -2,7 +2,7 @@ use crate::state::AppState;
 use routa_core::models::read_canvas_sdk_resource;
 use routa_core::models::read_feature_tree_spec_resource;
 
-use super::{tool_result_error, tool_result_json, tool_result_text};
+use super::{rpc_tool_result, tool_result_error, tool_result_json, tool_result_text};
 
 pub(super) async fn execute(
     state: &AppState,
@@ -48,11 +48,18 @@ pub(super) async fn execute(
                     ..Default::default()
                 }),
             );
-            match state.note_store.save(&note).await {
-                Ok(_) => tool_result_json(&serde_json::json!({
+            let dedup = args.get("dedup").and_then(|v| v.as_bool()).unwrap_or(false);
+            let saved = if dedup {
+                state.note_store.save_deduplicated(&note).await
+            } else {
+                state.note_store.save(&note).await
+            };
+            match saved {
+                Ok(saved) => tool_result_json(&serde_json::json!({
                     "success": true,
-                    "noteId": note_id,
-                    "title": title
+                    "noteId": saved.id,
+                    "title": saved.title,
+                    "deduplicated": dedup && saved.id != note_id
                 })),
                 Err(e) => tool_result_error(&e.to_string()),
             }
@@ -67,6 +74,19 @@ pub(super) async fn execute(
                 Err(e) => tool_result_error(&e.to_string()),
             }
         }
+        "get_my_notes" => {
+            let agent_id = args.get("agentId").and_then(|v| v.as_str()).unwrap_or("");
+            match state
+                .note_store
+                .list_assigned_to(agent_id, workspace_id)
+                .await
+            {
+                Ok(notes) => {
+                    tool_result_text(&serde_json::to_string_pretty(&notes).unwrap_or_default())
+                }
+                Err(e) => tool_result_error(&e.to_string()),
+            }
+        }
         "set_note_content" => {
             let note_id = args.get("noteId").and_then(|v| v.as_str()).unwrap_or("");
             let content = args.get("content").and_then(|v| v.as_str()).unwrap_or("");
@@ -183,6 +203,24 @@ pub(super) async fn execute(
             let skills = state.skill_registry.list_skills();
             tool_result_text(&serde_json::to_string_pretty(&skills).unwrap_or_default())
         }
+        "use_skill" => match rpc_tool_result(
+            state,
+            "skills.use",
+            serde_json::json!({
+                "name": args.get("name").and_then(|v| v.as_str()).unwrap_or(""),
+                "section": args.get("section").cloned(),
+            }),
+        )
+        .await
+        {
+            Ok(result) => tool_result_text(
+                result
+                    .get("content")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default(),
+            ),
+            Err(error) => tool_result_error(&error),
+        },
         "list_specialists" => tool_result_json(&serde_json::json!({
             "specialists": [
                 {
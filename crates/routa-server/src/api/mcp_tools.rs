@@ -1,6 +1,6 @@
 //! MCP Tools API - /api/mcp/tools
 //!
-//! GET  /api/mcp/tools - List all MCP tool definitions
+//! GET  /api/mcp/tools - List all MCP tool definitions (`?role=` hides tools the role should not use)
 //! POST /api/mcp/tools - Execute a specific tool by name
 
 use axum::{
@@ -23,9 +23,18 @@ pub fn router() -> Router<AppState> {
     )
 }
 
-async fn list_tools(State(_state): State<AppState>) -> Json<serde_json::Value> {
+#[derive(Debug, Default, Deserialize)]
+struct ListToolsQuery {
+    role: Option<String>,
+}
+
+async fn list_tools(
+    State(_state): State<AppState>,
+    Query(query): Query<ListToolsQuery>,
+) -> Json<serde_json::Value> {
+    let role = super::mcp_routes::parse_role(query.role.as_deref());
     Json(serde_json::json!({
-        "tools": super::mcp_routes::build_tool_list_public()
+        "tools": super::mcp_routes::build_tool_list_for_role(role)
     }))
 }
 
@@ -13,6 +13,7 @@ pub mod clone_local;
 pub mod clone_progress;
 pub mod codebases;
 pub mod debug;
+pub mod events;
 pub mod feature_explorer;
 pub mod files;
 pub mod fitness;
@@ -48,6 +49,7 @@ pub mod skills_clone;
 pub mod skills_upload;
 pub mod spec;
 pub mod specialists;
+pub(crate) mod sse;
 pub mod tasks;
 pub mod tasks_automation;
 pub mod tasks_github;
@@ -55,6 +57,7 @@ pub mod test_mcp;
 pub mod traces;
 pub mod webhooks;
 pub mod workflows;
+pub mod workspace_access;
 pub mod workspaces;
 pub mod worktrees;
 
@@ -115,6 +118,7 @@ pub fn api_router(state: AppState) -> Router<AppState> {
         .nest("/api/system/memory", memory::router())
         .nest("/api/memory", memory::legacy_router())
         .nest("/api/debug", debug::router())
+        .nest("/api/events", events::router())
         .nest("/api/polling", polling::router())
         .nest("/api/workflows", workflows::router())
         .nest("/api", worktrees::router())
@@ -11,6 +11,7 @@ use tokio_stream::StreamExt as _;
 use crate::error::ServerError;
 use crate::models::note::{Note, NoteMetadata, NoteType};
 use crate::state::AppState;
+use crate::store::TimeRange;
 
 pub fn router() -> Router<AppState> {
     Router::new()
@@ -34,6 +35,8 @@ struct ListNotesQuery {
     #[serde(rename = "type")]
     note_type: Option<String>,
     note_id: Option<String>,
+    #[serde(flatten)]
+    range: TimeRange,
 }
 
 async fn list_notes(
@@ -47,7 +50,20 @@ async fn list_notes(
         return Ok(Json(serde_json::json!({ "note": note })));
     }
 
-    let notes = if let Some(type_str) = &query.note_type {
+    let notes = if !query.range.is_unbounded() {
+        let note_type = query.note_type.as_deref().map(NoteType::from_str);
+        state
+            .note_store
+            .list_in_range(workspace_id, &query.range)
+            .await?
+            .into_iter()
+            .filter(|note| {
+                note_type
+                    .as_ref()
+                    .is_none_or(|note_type| &note.metadata.note_type == note_type)
+            })
+            .collect()
+    } else if let Some(type_str) = &query.note_type {
         let note_type = NoteType::from_str(type_str);
         state
             .note_store
@@ -112,7 +128,7 @@ async fn create_or_update_note(
         Some(metadata),
     );
 
-    state.note_store.save(&note).await?;
+    let note = state.note_store.save(&note).await?;
     Ok(Json(serde_json::json!({ "note": note })))
 }
 
@@ -166,7 +182,7 @@ async fn note_events_sse(
     let stream = tokio_stream::wrappers::IntervalStream::new(tokio::time::interval(
         std::time::Duration::from_secs(15),
     ))
-    .map(|_| Ok(Event::default().comment("heartbeat")));
+    .map(|_| Ok(super::sse::heartbeat()));
 
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
@@ -198,7 +198,7 @@ async fn resolve_create_request(
     let policy = match body.policy {
         Some(policy) if !policy.is_empty() => {
             let context = resolve_policy_context(state, &policy).await?;
-            Some(policy.resolve(context).map_err(ServerError::BadRequest)?)
+            Some(policy.resolve(context)?)
         }
         _ => None,
     };
@@ -235,9 +235,7 @@ async fn mutate_sandbox_policy(
         .to_input()
         .apply_permission_constraints(&constraints);
     let context = resolve_policy_context(state, &next_input).await?;
-    let next_policy = next_input
-        .resolve(context)
-        .map_err(ServerError::BadRequest)?;
+    let next_policy = next_input.resolve(context)?;
 
     Ok(ResolvedCreateSandboxRequest {
         lang: sandbox.lang,
@@ -272,7 +270,7 @@ async fn resolve_policy_context(
 
         if let Some(workspace_id) = &context.workspace_id {
             if workspace_id != &codebase.workspace_id {
-                return Err(ServerError::BadRequest(format!(
+                return Err(ServerError::Forbidden(format!(
                     "Codebase {codebase_id} does not belong to workspace {workspace_id}"
                 )));
             }
@@ -341,6 +339,7 @@ mod tests {
     };
 
     use super::resolve_create_request;
+    use crate::error::ServerError;
 
     #[tokio::test]
     async fn resolve_create_request_loads_trusted_workspace_config_from_default_codebase() {
@@ -445,4 +444,71 @@ mod tests {
             "loaded"
         );
     }
+
+    #[tokio::test]
+    async fn resolve_create_request_forbids_policies_outside_their_workspace() {
+        let temp = tempfile::tempdir().expect("tempdir should exist");
+        let repo = temp.path().join("repo");
+        let outside = temp.path().join("outside");
+        std::fs::create_dir_all(&repo).expect("repo directory should exist");
+        std::fs::create_dir_all(&outside).expect("outside directory should exist");
+
+        let db = Database::open_in_memory().expect("db should open");
+        let state = Arc::new(AppStateInner::new(db));
+        for id in ["ws-1", "ws-2"] {
+            state
+                .workspace_store
+                .save(&Workspace::new(id.to_string(), id.to_string(), None))
+                .await
+                .expect("workspace should save");
+        }
+        state
+            .codebase_store
+            .save(&Codebase::new(
+                "cb-1".to_string(),
+                "ws-1".to_string(),
+                repo.to_string_lossy().to_string(),
+                Some("main".to_string()),
+                Some("default".to_string()),
+                true,
+                None,
+                None,
+            ))
+            .await
+            .expect("codebase should save");
+
+        let resolve = |policy: SandboxPolicyInput| {
+            resolve_create_request(
+                &state,
+                CreateSandboxRequest {
+                    lang: "python".to_string(),
+                    policy: Some(policy),
+                },
+            )
+        };
+
+        let escaped = resolve(SandboxPolicyInput {
+            codebase_id: Some("cb-1".to_string()),
+            workdir: Some(outside.to_string_lossy().to_string()),
+            ..Default::default()
+        })
+        .await;
+        assert!(
+            matches!(escaped, Err(ServerError::Forbidden(_))),
+            "{:?}",
+            escaped.err()
+        );
+
+        let foreign = resolve(SandboxPolicyInput {
+            workspace_id: Some("ws-2".to_string()),
+            codebase_id: Some("cb-1".to_string()),
+            ..Default::default()
+        })
+        .await;
+        assert!(
+            matches!(foreign, Err(ServerError::Forbidden(_))),
+            "{:?}",
+            foreign.err()
+        );
+    }
 }
@@ -466,6 +466,7 @@ async fn fork_session(
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: Some(&session_id),
+                metadata: None,
             },
         )
         .await
@@ -1133,7 +1134,9 @@ fn traces_to_transcript_messages(
                     });
                 }
             }
-            TraceEventType::SessionStart | TraceEventType::SessionEnd => {}
+            TraceEventType::SessionStart
+            | TraceEventType::SessionEnd
+            | TraceEventType::Delegation => {}
         }
     }
 
@@ -779,11 +779,13 @@ async fn shared_session_stream(
                 msg = rx.recv() => {
                     match msg {
                         Ok(event) => yield Ok(Event::default().data(event.to_string())),
-                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            yield Ok(super::sse::lagged(skipped));
+                        }
                         Err(broadcast::error::RecvError::Closed) => break,
                     }
                 }
-                _ = heartbeat.tick() => yield Ok(Event::default().comment("heartbeat")),
+                _ = heartbeat.tick() => yield Ok(super::sse::heartbeat()),
             }
         }
     });
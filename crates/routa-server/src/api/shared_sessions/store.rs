@@ -377,7 +377,19 @@ pub fn spawn_host_notification_forwarder(
     mut rx: broadcast::Receiver<Value>,
 ) {
     tokio::spawn(async move {
-        while let Ok(notification) = rx.recv().await {
+        loop {
+            let notification = match rx.recv().await {
+                Ok(notification) => notification,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    shared_session_store().write().await.emit_session_event(
+                        &shared_session_id,
+                        "host_session_lagged",
+                        json!({ "skipped": skipped }),
+                    );
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
             let mut store = shared_session_store().write().await;
             store.expire_sessions();
             let Some(session) = store.sessions.get(&shared_session_id) else {
@@ -13,6 +13,7 @@ use axum::{
     routing::get,
     Json, Router,
 };
+use routa_core::models::skill::SkillRecord;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::PathBuf;
@@ -226,14 +227,14 @@ struct InstallRequest {
 }
 
 async fn install_from_catalog(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(body): Json<InstallRequest>,
 ) -> Result<Json<serde_json::Value>, ServerError> {
     let catalog_type = body.catalog_type.as_deref().unwrap_or("skillssh");
 
     match catalog_type {
-        "skillssh" => install_from_skillssh(&body).await,
-        "github" => install_from_github(&body).await,
+        "skillssh" => install_from_skillssh(&state, &body).await,
+        "github" => install_from_github(&state, &body).await,
         _ => Err(ServerError::BadRequest(format!(
             "Unknown catalog type: {catalog_type}"
         ))),
@@ -242,6 +243,7 @@ async fn install_from_catalog(
 
 /// Install skills from skills.sh — each skill has its own source repo.
 async fn install_from_skillssh(
+    state: &AppState,
     body: &InstallRequest,
 ) -> Result<Json<serde_json::Value>, ServerError> {
     let skills: Vec<SkillInstallItem> = serde_json::from_value(body.skills.clone())
@@ -268,6 +270,7 @@ async fn install_from_skillssh(
     for (repo_source, skill_names) in &by_repo {
         match download_and_install_skills(repo_source, skill_names, None, &dest_base).await {
             Ok((ok, err)) => {
+                record_installs(state, &ok, repo_source, "skillssh").await;
                 installed.extend(ok);
                 errors.extend(err);
             }
@@ -285,6 +288,7 @@ async fn install_from_skillssh(
 
 /// Install skills from a GitHub repo directory catalog.
 async fn install_from_github(
+    state: &AppState,
     body: &InstallRequest,
 ) -> Result<Json<serde_json::Value>, ServerError> {
     let skill_names: Vec<String> = serde_json::from_value(body.skills.clone())
@@ -301,16 +305,40 @@ async fn install_from_github(
     let dest_base = dest_skills_dir();
 
     match download_and_install_skills(repo, &skill_names, Some(catalog_path), &dest_base).await {
-        Ok((installed, errors)) => Ok(Json(serde_json::json!({
+        Ok((installed, errors)) => {
+            record_installs(state, &installed, repo, "github").await;
+            Ok(Json(serde_json::json!({
             "success": !installed.is_empty(),
             "installed": installed,
             "errors": errors,
             "dest": dest_base.to_string_lossy(),
-        }))),
+            })))
+        }
         Err(e) => Err(ServerError::Internal(e)),
     }
 }
 
+/// Bump the install counter for each installed skill. Counter failures are
+/// logged rather than failing an install that already happened on disk.
+pub(crate) async fn record_installs(
+    state: &AppState,
+    names: &[String],
+    source: &str,
+    catalog_type: &str,
+) {
+    for name in names {
+        let record = SkillRecord::new(
+            name.clone(),
+            String::new(),
+            source.to_string(),
+            catalog_type.to_string(),
+        );
+        if let Err(e) = state.skill_store.record_install(&record).await {
+            tracing::warn!("Failed to record install of skill '{}': {}", name, e);
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct SkillInstallItem {
     name: String,
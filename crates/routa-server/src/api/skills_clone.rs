@@ -3,7 +3,11 @@
 //! POST /api/skills/clone - Clone a skill repo and import skills
 //! GET  /api/skills/clone?repoPath=... - Discover skills from a path
 
-use axum::{extract::Query, routing::get, Json, Router};
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
 use serde::Deserialize;
 use std::path::Path;
 
@@ -26,6 +30,7 @@ struct CloneSkillsRequest {
 }
 
 async fn clone_skills(
+    State(state): State<AppState>,
     Json(body): Json<CloneSkillsRequest>,
 ) -> Result<Json<serde_json::Value>, ServerError> {
     let url = body
@@ -96,6 +101,7 @@ async fn clone_skills(
         }
         imported.push(skill.name.clone());
     }
+    super::skills_catalog::record_installs(&state, &imported, url, "github").await;
 
     Ok(Json(serde_json::json!({
         "success": true,
@@ -2,7 +2,7 @@
 //!
 //! POST /api/skills/upload - Upload and extract a skill zip file
 
-use axum::{routing::post, Router};
+use axum::{extract::State, routing::post, Router};
 use axum_extra::extract::Multipart;
 
 use crate::error::ServerError;
@@ -15,6 +15,7 @@ pub fn router() -> Router<AppState> {
 }
 
 async fn upload_skill(
+    State(state): State<AppState>,
     mut multipart: Multipart,
 ) -> Result<axum::Json<serde_json::Value>, ServerError> {
     let mut file_name = String::new();
@@ -67,10 +68,14 @@ async fn upload_skill(
     let _ = std::fs::remove_file(&temp_zip);
 
     match result {
-        Ok(output) if output.status.success() => Ok(axum::Json(serde_json::json!({
-            "success": true,
-            "message": format!("Extracted {} to {}/", file_name, SKILLS_DIR),
-        }))),
+        Ok(output) if output.status.success() => {
+            let skill_names = skill_names_in_zip(&data);
+            super::skills_catalog::record_installs(&state, &skill_names, "upload", "upload").await;
+            Ok(axum::Json(serde_json::json!({
+                "success": true,
+                "message": format!("Extracted {} to {}/", file_name, SKILLS_DIR),
+            })))
+        }
         Ok(output) => Err(ServerError::Internal(format!(
             "Unzip failed: {}",
             String::from_utf8_lossy(&output.stderr)
@@ -78,3 +83,20 @@ async fn upload_skill(
         Err(e) => Err(ServerError::Internal(format!("Unzip command failed: {e}"))),
     }
 }
+
+/// Names of the top-level directories in the archive that contain a `SKILL.md`.
+fn skill_names_in_zip(data: &[u8]) -> Vec<String> {
+    let Ok(archive) = zip::ZipArchive::new(std::io::Cursor::new(data)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter_map(|path| {
+            let (dir, file) = path.split_once('/')?;
+            (file == "SKILL.md").then(|| dir.to_string())
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
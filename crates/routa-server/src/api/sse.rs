@@ -0,0 +1,84 @@
+//! Named SSE events shared by the session and workspace streams.
+//!
+//! Frames carry an `event:` name so `EventSource` clients can register a
+//! listener per kind instead of sniffing every `message` payload.
+
+use std::convert::Infallible;
+
+use axum::response::sse::Event;
+use serde_json::Value;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_stream::Stream;
+
+/// An ACP `session/update` notification.
+pub(crate) const SESSION_UPDATE: &str = "session_update";
+/// A serialized `AgentEvent` from the event bus.
+pub(crate) const AGENT_EVENT: &str = "agent_event";
+/// Periodic keep-alive with an empty payload.
+pub(crate) const HEARTBEAT: &str = "heartbeat";
+/// Sent once when a stream finishes on its own (e.g. a prompt turn completes).
+pub(crate) const DONE: &str = "done";
+/// The subscriber fell behind its broadcast channel; `skipped` notifications
+/// were dropped and will not be delivered.
+pub(crate) const LAGGED: &str = "lagged";
+
+pub(crate) fn heartbeat() -> Event {
+    Event::default().event(HEARTBEAT).data("")
+}
+
+pub(crate) fn done() -> Event {
+    Event::default().event(DONE).data("{}")
+}
+
+pub(crate) fn lagged(skipped: u64) -> Event {
+    Event::default()
+        .event(LAGGED)
+        .data(serde_json::json!({ "skipped": skipped }).to_string())
+}
+
+/// Forward a broadcast receiver as SSE events until the channel closes.
+/// Falling behind yields a [`lagged`] event and keeps streaming.
+pub(crate) fn broadcast_events<F>(
+    mut rx: broadcast::Receiver<Value>,
+    to_event: F,
+) -> impl Stream<Item = Result<Event, Infallible>> + Send
+where
+    F: Fn(Value) -> Event + Send + 'static,
+{
+    async_stream::stream! {
+        loop {
+            match rx.recv().await {
+                Ok(message) => yield Ok(to_event(message)),
+                Err(RecvError::Lagged(skipped)) => yield Ok(lagged(skipped)),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::{IntoResponse, Sse};
+
+    #[tokio::test]
+    async fn overflowing_subscriber_gets_lagged_event_and_keeps_streaming() {
+        let (tx, rx) = broadcast::channel(2);
+        for n in 0..5 {
+            tx.send(serde_json::json!({ "n": n })).unwrap();
+        }
+        drop(tx);
+
+        let stream = broadcast_events(rx, |message| Event::default().data(message.to_string()));
+        let body = Sse::new(stream).into_response().into_body();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        assert_eq!(
+            body,
+            "event: lagged\ndata: {\"skipped\":3}\n\n\
+             data: {\"n\":3}\n\n\
+             data: {\"n\":4}\n\n"
+        );
+    }
+}
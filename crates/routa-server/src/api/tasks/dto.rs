@@ -1,4 +1,5 @@
 use routa_core::models::task::TaskContextSearchSpec;
+use routa_core::store::TimeRange;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::BTreeMap;
 
@@ -125,6 +126,17 @@ pub struct ListTasksQuery {
     pub session_id: Option<String>,
     pub status: Option<String>,
     pub assigned_to: Option<String>,
+    #[serde(flatten)]
+    pub range: TimeRange,
+}
+
+/// Query params for task search
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchTasksQuery {
+    pub workspace_id: Option<String>,
+    pub q: Option<String>,
+    pub limit: Option<usize>,
 }
 
 /// Query params for task file change
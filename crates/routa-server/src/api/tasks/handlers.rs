@@ -4,14 +4,14 @@ use axum::{
     Json, Router,
 };
 use chrono::Utc;
-use routa_core::events::{AgentEvent, AgentEventType};
+use routa_core::events::{AgentEvent, KanbanChangedData};
 use routa_core::kanban::set_task_column;
 use routa_core::models::artifact::{Artifact, ArtifactType};
 
 use super::changes;
 use super::dto::{
-    CreateTaskArtifactRequest, CreateTaskRequest, ListTasksQuery, UpdateStatusRequest,
-    UpdateTaskRequest,
+    CreateTaskArtifactRequest, CreateTaskRequest, ListTasksQuery, SearchTasksQuery,
+    UpdateStatusRequest, UpdateTaskRequest,
 };
 use super::evidence::{
     build_task_run_ledger, ensure_transition_artifacts, serialize_task_with_evidence,
@@ -51,6 +51,7 @@ pub fn router() -> Router<AppState> {
         .route("/{id}/runs", get(list_task_runs))
         .route("/{id}/status", axum::routing::post(update_task_status))
         .route("/ready", get(find_ready_tasks))
+        .route("/search", get(search_tasks))
 }
 
 async fn emit_kanban_workspace_event(
@@ -63,19 +64,16 @@ async fn emit_kanban_workspace_event(
 ) {
     state
         .event_bus
-        .emit(AgentEvent {
-            event_type: AgentEventType::WorkspaceUpdated,
-            agent_id: format!("kanban-{source}"),
-            workspace_id: workspace_id.to_string(),
-            data: serde_json::json!({
-                "scope": "kanban",
-                "entity": entity,
-                "action": action,
-                "resourceId": resource_id,
-                "source": source,
-            }),
-            timestamp: Utc::now(),
-        })
+        .emit(AgentEvent::kanban_changed(
+            format!("kanban-{source}"),
+            workspace_id,
+            KanbanChangedData {
+                entity: entity.to_string(),
+                action: action.to_string(),
+                resource_id: resource_id.map(str::to_string),
+                source: source.to_string(),
+            },
+        ))
         .await;
 }
 
@@ -197,7 +195,33 @@ async fn list_tasks(
 ) -> Result<Json<serde_json::Value>, ServerError> {
     let workspace_id = query.workspace_id.as_deref().unwrap_or("default");
 
-    let tasks = if let Some(session_id) = &query.session_id {
+    let tasks = if !query.range.is_unbounded() {
+        let status = query
+            .status
+            .as_deref()
+            .map(|status_str| {
+                TaskStatus::from_str(status_str)
+                    .ok_or_else(|| ServerError::BadRequest(format!("Invalid status: {status_str}")))
+            })
+            .transpose()?;
+        state
+            .task_store
+            .list_in_range(workspace_id, &query.range)
+            .await?
+            .into_iter()
+            .filter(|task| {
+                query
+                    .session_id
+                    .as_ref()
+                    .is_none_or(|id| task.session_id.as_ref() == Some(id))
+                    && query
+                        .assigned_to
+                        .as_ref()
+                        .is_none_or(|id| task.assigned_to.as_ref() == Some(id))
+                    && status.as_ref().is_none_or(|status| &task.status == status)
+            })
+            .collect()
+    } else if let Some(session_id) = &query.session_id {
         // Filter by session_id takes priority
         state.task_store.list_by_session(session_id).await?
     } else if let Some(assignee) = &query.assigned_to {
@@ -626,6 +650,25 @@ async fn find_ready_tasks(
     Ok(Json(serde_json::json!({ "tasks": serialized_tasks })))
 }
 
+/// GET /api/tasks/search?q= — Substring search over titles and objectives
+async fn search_tasks(
+    State(state): State<AppState>,
+    Query(query): Query<SearchTasksQuery>,
+) -> Result<Json<serde_json::Value>, ServerError> {
+    let workspace_id = query.workspace_id.as_deref().unwrap_or("default");
+    let q = query.q.as_deref().map(str::trim).unwrap_or_default();
+    if q.is_empty() {
+        return Err(ServerError::BadRequest(
+            "Missing 'q' query parameter".to_string(),
+        ));
+    }
+    let results = state
+        .task_store
+        .search(workspace_id, q, query.limit.unwrap_or(20))
+        .await?;
+    Ok(Json(serde_json::json!({ "results": results })))
+}
+
 /// DELETE /api/tasks — Bulk delete all tasks for a workspace
 async fn delete_all_tasks(
     State(state): State<AppState>,
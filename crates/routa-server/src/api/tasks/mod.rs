@@ -12,9 +12,9 @@ pub use handlers::router;
 
 // Re-export commonly used types
 pub use dto::{
-    CreateTaskArtifactRequest, CreateTaskRequest, ListTasksQuery, TaskChangeCommitQuery,
-    TaskChangeFileQuery, TaskChangeStatsQuery, TaskEvidenceSummary, TaskRunLedgerEntry,
-    UpdateStatusRequest, UpdateTaskRequest,
+    CreateTaskArtifactRequest, CreateTaskRequest, ListTasksQuery, SearchTasksQuery,
+    TaskChangeCommitQuery, TaskChangeFileQuery, TaskChangeStatsQuery, TaskEvidenceSummary,
+    TaskRunLedgerEntry, UpdateStatusRequest, UpdateTaskRequest,
 };
 
 pub use evidence::{
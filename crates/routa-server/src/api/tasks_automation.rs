@@ -1,6 +1,6 @@
 use chrono::Utc;
 use reqwest::header::{HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
-use routa_core::events::{AgentEvent, AgentEventType};
+use routa_core::events::{AgentEvent, KanbanChangedData};
 use routa_core::models::kanban::{KanbanAutomationStep, KanbanBoard, KanbanTransport};
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -293,6 +293,7 @@ async fn trigger_assigned_task_acp_agent(
             custom_command: None,
             custom_args: None,
             parent_session_id: None,
+            metadata: None,
         })
         .await
         .map_err(|error| format!("Failed to persist ACP session: {error}"))?;
@@ -937,19 +938,16 @@ async fn wait_for_task_persistence(
 async fn emit_kanban_workspace_event(state: &AppState, workspace_id: &str, task_id: &str) {
     state
         .event_bus
-        .emit(AgentEvent {
-            event_type: AgentEventType::WorkspaceUpdated,
-            agent_id: "kanban-a2a".to_string(),
-            workspace_id: workspace_id.to_string(),
-            data: serde_json::json!({
-                "scope": "kanban",
-                "entity": "task",
-                "action": "updated",
-                "resourceId": task_id,
-                "source": "system",
-            }),
-            timestamp: Utc::now(),
-        })
+        .emit(AgentEvent::kanban_changed(
+            "kanban-a2a",
+            workspace_id,
+            KanbanChangedData {
+                entity: "task".to_string(),
+                action: "updated".to_string(),
+                resource_id: Some(task_id.to_string()),
+                source: "system".to_string(),
+            },
+        ))
         .await;
 }
 
@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::error::ServerError;
+use crate::models::message::Message;
 use crate::state::AppState;
 use routa_core::trace::{TraceQuery, TraceReader, TraceRecord};
 
@@ -18,6 +19,7 @@ pub fn router() -> Router<AppState> {
         .route("/export", post(export_traces))
         .route("/stats", get(get_trace_stats))
         .route("/{id}", get(get_trace_by_id))
+        .route("/{id}/conversation", get(get_trace_conversation))
 }
 
 /// GET /api/traces — Query traces with optional filters.
@@ -27,6 +29,7 @@ pub fn router() -> Router<AppState> {
 /// - workspaceId: Filter by workspace ID
 /// - file: Filter by file path
 /// - eventType: Filter by event type
+/// - agentId: Only delegation traces where the agent is parent or child
 /// - startDate: Start date (YYYY-MM-DD)
 /// - endDate: End date (YYYY-MM-DD)
 /// - limit: Max number of results
@@ -89,6 +92,52 @@ async fn get_trace_by_id(
     }
 }
 
+/// GET /api/traces/:sessionId/conversation — Rebuild a session's conversation
+/// from its traces, for sessions no longer held in the conversation store.
+async fn get_trace_conversation(
+    State(state): State<AppState>,
+    axum::extract::Path(session_id): axum::extract::Path<String>,
+) -> Result<Json<serde_json::Value>, ServerError> {
+    let cwd = std::env::current_dir()
+        .map_err(|e| ServerError::Internal(format!("Failed to get cwd: {e}")))?;
+
+    let messages =
+        load_trace_conversation(&state, &session_id, &cwd, |root| TraceReader::new(root)).await?;
+
+    if messages.is_empty() {
+        return Err(ServerError::NotFound(format!(
+            "No conversation traces for session {session_id}"
+        )));
+    }
+
+    Ok(Json(serde_json::json!({
+        "sessionId": session_id,
+        "messages": messages,
+        "count": messages.len()
+    })))
+}
+
+/// Rebuilds the conversation from the session's own cwd first, then from the
+/// server cwd.
+async fn load_trace_conversation(
+    state: &AppState,
+    session_id: &str,
+    fallback_cwd: &Path,
+    reader_for: impl Fn(&Path) -> TraceReader,
+) -> Result<Vec<Message>, ServerError> {
+    let roots = resolve_trace_reader_roots(state, session_id, fallback_cwd).await?;
+    for root in roots.iter().rev() {
+        let messages = reader_for(root)
+            .reconstruct_conversation(session_id)
+            .await
+            .map_err(|e| ServerError::Internal(format!("Failed to read traces: {e}")))?;
+        if !messages.is_empty() {
+            return Ok(messages);
+        }
+    }
+    Ok(Vec::new())
+}
+
 /// POST /api/traces/export — Export traces in Agent Trace JSON format.
 async fn export_traces(
     State(state): State<AppState>,
@@ -122,6 +171,7 @@ struct TraceQueryParams {
     workspace_id: Option<String>,
     file: Option<String>,
     event_type: Option<String>,
+    agent_id: Option<String>,
     start_date: Option<String>,
     end_date: Option<String>,
     limit: Option<usize>,
@@ -142,6 +192,9 @@ impl TraceQueryParams {
         if overrides.event_type.is_some() {
             self.event_type = overrides.event_type;
         }
+        if overrides.agent_id.is_some() {
+            self.agent_id = overrides.agent_id;
+        }
         if overrides.start_date.is_some() {
             self.start_date = overrides.start_date;
         }
@@ -162,6 +215,7 @@ impl TraceQueryParams {
             workspace_id: self.workspace_id.clone(),
             file: self.file.clone(),
             event_type: self.event_type.clone(),
+            agent_id: self.agent_id.clone(),
             start_date: self.start_date.clone(),
             end_date: self.end_date.clone(),
             limit: self.limit,
@@ -235,7 +289,7 @@ async fn query_traces_with_session_fallback(
         .collect())
 }
 
-async fn resolve_trace_reader_roots(
+pub(crate) async fn resolve_trace_reader_roots(
     state: &AppState,
     session_id: &str,
     fallback_cwd: &Path,
@@ -260,3 +314,68 @@ async fn resolve_trace_reader_roots(
 
     Ok(roots)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use routa_core::store::acp_session_store::CreateAcpSessionParams;
+    use routa_core::trace::{Contributor, TraceConversation, TraceEventType, TraceWriter};
+    use routa_core::{AppStateInner, Database};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn trace_conversation_reads_from_the_session_cwd() {
+        let db = Database::open_in_memory().expect("in-memory db should open");
+        let state: AppState = Arc::new(AppStateInner::new(db));
+        state
+            .workspace_store
+            .ensure_default()
+            .await
+            .expect("default workspace should exist");
+
+        let server_cwd = tempfile::tempdir().expect("temp dir");
+        let session_cwd = tempfile::tempdir().expect("temp dir");
+        state
+            .acp_session_store
+            .create(CreateAcpSessionParams {
+                id: "session-elsewhere",
+                cwd: &session_cwd.path().to_string_lossy(),
+                branch: None,
+                workspace_id: "default",
+                provider: Some("opencode"),
+                role: None,
+                custom_command: None,
+                custom_args: None,
+                parent_session_id: None,
+                metadata: None,
+            })
+            .await
+            .expect("session should save");
+
+        let record = TraceRecord::new(
+            "session-elsewhere",
+            TraceEventType::UserMessage,
+            Contributor::new("opencode", None),
+        )
+        .with_conversation(TraceConversation {
+            turn: Some(1),
+            role: Some("user".to_string()),
+            content_preview: None,
+            full_content: Some("hello".to_string()),
+        });
+        TraceWriter::with_base_dir(session_cwd.path().join("traces"))
+            .append(&record)
+            .await
+            .expect("trace should write");
+
+        let messages =
+            load_trace_conversation(&state, "session-elsewhere", server_cwd.path(), |root| {
+                TraceReader::with_base_dir(root.join("traces"))
+            })
+            .await
+            .expect("conversation should load");
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, "hello");
+    }
+}
@@ -303,25 +303,21 @@ async fn delete_workflow(Path(id): Path<String>) -> Result<Json<serde_json::Valu
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct TriggerWorkflowInput {
-    workspace_id: String,
-    trigger_payload: Option<String>,
+/// Tasks created for one run of a workflow.
+pub(crate) struct WorkflowRun {
+    pub workflow_run_id: String,
+    pub task_ids: Vec<String>,
 }
 
-/// POST /api/workflows/{id}/trigger — start a workflow run inside a workspace.
-async fn trigger_workflow(
-    State(state): State<AppState>,
-    Path(id): Path<String>,
-    Json(body): Json<TriggerWorkflowInput>,
-) -> Result<(axum::http::StatusCode, Json<serde_json::Value>), ServerError> {
-    let workspace_id = require_workspace_id(&body.workspace_id)?;
-    let Some(_) = state.workspace_store.get(&workspace_id).await? else {
-        return Err(ServerError::NotFound("Workspace not found".to_string()));
-    };
-
-    let definition = load_workflow_definition(&id)?;
+/// Create one task per step of `definition`, each parallel group depending on
+/// every task created before it.
+pub(crate) async fn start_workflow_run(
+    state: &AppState,
+    workflow_id: &str,
+    definition: &WorkflowDefinition,
+    workspace_id: &str,
+    trigger_payload: Option<&str>,
+) -> Result<WorkflowRun, ServerError> {
     let workflow_run_id = uuid::Uuid::new_v4().to_string();
     let task_service = TaskApplicationService::new(state.clone());
     let mut task_ids = Vec::new();
@@ -337,12 +333,8 @@ async fn trigger_workflow(
             let plan = task_service
                 .create_task(CreateTaskCommand {
                     title: format!("[{}] {}", definition.name, step.name),
-                    objective: build_step_prompt(
-                        &step,
-                        &definition,
-                        body.trigger_payload.as_deref(),
-                    ),
-                    workspace_id: Some(workspace_id.clone()),
+                    objective: build_step_prompt(&step, definition, trigger_payload),
+                    workspace_id: Some(workspace_id.to_string()),
                     session_id: None,
                     scope: None,
                     acceptance_criteria: None,
@@ -356,7 +348,7 @@ async fn trigger_workflow(
                     priority: None,
                     labels: Some(vec![
                         "workflow".to_string(),
-                        id.clone(),
+                        workflow_id.to_string(),
                         workflow_run_id.clone(),
                     ]),
                     assignee: None,
@@ -381,11 +373,45 @@ async fn trigger_workflow(
         }
     }
 
+    Ok(WorkflowRun {
+        workflow_run_id,
+        task_ids,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TriggerWorkflowInput {
+    workspace_id: String,
+    trigger_payload: Option<String>,
+}
+
+/// POST /api/workflows/{id}/trigger — start a workflow run inside a workspace.
+async fn trigger_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<TriggerWorkflowInput>,
+) -> Result<(axum::http::StatusCode, Json<serde_json::Value>), ServerError> {
+    let workspace_id = require_workspace_id(&body.workspace_id)?;
+    let Some(_) = state.workspace_store.get(&workspace_id).await? else {
+        return Err(ServerError::NotFound("Workspace not found".to_string()));
+    };
+
+    let definition = load_workflow_definition(&id)?;
+    let run = start_workflow_run(
+        &state,
+        &id,
+        &definition,
+        &workspace_id,
+        body.trigger_payload.as_deref(),
+    )
+    .await?;
+
     Ok((
         axum::http::StatusCode::CREATED,
         Json(serde_json::json!({
-            "workflowRunId": workflow_run_id,
-            "taskIds": task_ids,
+            "workflowRunId": run.workflow_run_id,
+            "taskIds": run.task_ids,
         })),
     ))
 }
@@ -0,0 +1,517 @@
+//! Per-token workspace scoping for the agents, tasks, notes, sessions,
+//! workspace, RPC and MCP APIs.
+//!
+//! When [`ServerConfig::workspace_tokens`](crate::ServerConfig) is set, every
+//! request to those APIs must carry `Authorization: Bearer <token>` for a
+//! configured token (401 otherwise). The middleware works out every workspace
+//! a request touches and answers 403 unless the token allows all of them:
+//!
+//! - workspaces named by the `workspaceId` / `wsId` query parameter, the
+//!   `routa-workspace-id` header, a JSON body's `workspaceId`, or the
+//!   `/api/notes/{workspaceId}/…` and `/api/workspaces/{id}/…` paths;
+//! - the workspace of the task, agent or session a path like
+//!   `/api/tasks/{id}` addresses;
+//! - for `/api/rpc` and `/api/mcp`, the `workspaceId` and the tasks, agents
+//!   and sessions named in each call's params or tool arguments. MCP calls
+//!   without a workspace run in `default`, as the MCP server does.
+//!
+//! A request whose workspace can't be determined — no workspace named, or an
+//! unknown resource ID — is rejected with 403. Without the mapping every
+//! request is allowed.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+
+use crate::error::ServerError;
+use crate::state::AppState;
+
+/// Route prefixes whose requests are scoped by workspace.
+const SCOPED_PREFIXES: &[&str] = &[
+    "/api/agents",
+    "/api/tasks",
+    "/api/notes",
+    "/api/sessions",
+    "/api/rpc",
+    "/api/mcp",
+];
+
+/// `/api/tasks/{segment}` routes that are collections, not task IDs.
+const TASK_COLLECTION_ROUTES: &[&str] = &["ready", "search"];
+
+/// Token → workspace IDs that token may access.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceAccess {
+    tokens: HashMap<String, HashSet<String>>,
+}
+
+impl WorkspaceAccess {
+    pub fn new(tokens: HashMap<String, Vec<String>>) -> Self {
+        Self {
+            tokens: tokens
+                .into_iter()
+                .map(|(token, workspaces)| (token, workspaces.into_iter().collect()))
+                .collect(),
+        }
+    }
+
+    fn allowed_workspaces(&self, headers: &HeaderMap) -> Result<&HashSet<String>, ServerError> {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .ok_or_else(|| ServerError::Unauthorized("Missing bearer token".to_string()))?;
+        self.tokens
+            .get(token)
+            .ok_or_else(|| ServerError::Unauthorized("Unknown bearer token".to_string()))
+    }
+}
+
+/// Middleware enforcing `access` on the scoped routes.
+pub async fn enforce(
+    access: Arc<WorkspaceAccess>,
+    state: AppState,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !is_scoped(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let allowed = match access.allowed_workspaces(request.headers()) {
+        Ok(allowed) => allowed,
+        Err(error) => return error.into_response(),
+    };
+    let (targets, request) = match requested_targets(request).await {
+        Ok(found) => found,
+        Err(error) => return error.into_response(),
+    };
+    let workspaces = match targets.workspaces(&state).await {
+        Ok(workspaces) => workspaces,
+        Err(error) => return error.into_response(),
+    };
+    if let Some(denied) = workspaces.iter().find(|id| !allowed.contains(*id)) {
+        return ServerError::Forbidden(format!("Token has no access to workspace {denied}"))
+            .into_response();
+    }
+    next.run(request).await
+}
+
+fn is_scoped(path: &str) -> bool {
+    let under = |prefix: &str| path == prefix || path.starts_with(&format!("{prefix}/"));
+    // `/api/workspaces` itself lists and creates workspaces; only the
+    // routes below a workspace ID are scoped.
+    SCOPED_PREFIXES.iter().any(|prefix| under(prefix))
+        || path
+            .strip_prefix("/api/workspaces/")
+            .is_some_and(|rest| !rest.is_empty())
+}
+
+/// Everything a request reaches that belongs to a workspace.
+#[derive(Debug, Default)]
+struct Targets {
+    workspaces: Vec<String>,
+    tasks: Vec<String>,
+    agents: Vec<String>,
+    sessions: Vec<String>,
+}
+
+impl Targets {
+    /// Record the workspace and resources named in JSON-RPC params or MCP
+    /// tool arguments. `id_kind` says what a bare `id` refers to.
+    fn collect_params(&mut self, params: &Value, id_kind: Option<&str>) {
+        let str_field = |key: &str| {
+            params
+                .get(key)
+                .and_then(Value::as_str)
+                .and_then(|value| non_empty(value.to_string()))
+        };
+        self.workspaces.extend(str_field("workspaceId"));
+        self.tasks.extend(str_field("taskId"));
+        for key in ["agentId", "toAgentId", "fromAgentId", "callerAgentId"] {
+            self.agents.extend(str_field(key));
+        }
+        self.sessions.extend(str_field("sessionId"));
+        if let Some(id) = str_field("id") {
+            match id_kind {
+                Some("tasks") => self.tasks.push(id),
+                Some("agents") => self.agents.push(id),
+                Some("sessions") => self.sessions.push(id),
+                Some("workspaces") => self.workspaces.push(id),
+                _ => {}
+            }
+        }
+    }
+
+    /// The owning workspace of every target. Fails with 403 when nothing
+    /// names a workspace or a resource doesn't exist.
+    async fn workspaces(self, state: &AppState) -> Result<Vec<String>, ServerError> {
+        let unknown = |kind: &str, id: &str| {
+            ServerError::Forbidden(format!("Cannot determine the workspace of {kind} {id}"))
+        };
+        let mut workspaces = self.workspaces;
+        for id in &self.tasks {
+            let task = state.task_store.get(id).await?;
+            workspaces.push(task.ok_or_else(|| unknown("task", id))?.workspace_id);
+        }
+        for id in &self.agents {
+            let agent = state.agent_store.get(id).await?;
+            workspaces.push(agent.ok_or_else(|| unknown("agent", id))?.workspace_id);
+        }
+        for id in &self.sessions {
+            let workspace_id = match state.acp_manager.get_session(id).await {
+                Some(session) => session.workspace_id,
+                None => {
+                    let session = state.acp_session_store.get(id).await?;
+                    session.ok_or_else(|| unknown("session", id))?.workspace_id
+                }
+            };
+            workspaces.push(workspace_id);
+        }
+        if workspaces.is_empty() {
+            return Err(ServerError::Forbidden(
+                "Request does not name a workspace".to_string(),
+            ));
+        }
+        workspaces.sort();
+        workspaces.dedup();
+        Ok(workspaces)
+    }
+}
+
+/// What a request targets. The body is buffered and read as JSON whatever
+/// its `Content-Type` says, since axum's `Json` extractor accepts more than
+/// `application/json` (`application/*+json`, any letter case). A non-empty
+/// body that isn't JSON is rejected, so nothing unchecked reaches a handler.
+async fn requested_targets(request: Request) -> Result<(Targets, Request), ServerError> {
+    let mut targets = Targets::default();
+    let path = request.uri().path().to_string();
+    targets.workspaces = named_workspaces(&request);
+    collect_path(&mut targets, &path);
+
+    let is_mcp = path == "/api/mcp" || path.starts_with("/api/mcp/");
+    let (parts, body) = request.into_parts();
+    let bytes = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| ServerError::BadRequest(format!("Failed to read request body: {e}")))?;
+    if !bytes.iter().all(u8::is_ascii_whitespace) {
+        let body = serde_json::from_slice::<Value>(&bytes)
+            .map_err(|e| ServerError::BadRequest(format!("Request body is not JSON: {e}")))?;
+        collect_body(&mut targets, &path, &body);
+    }
+    let request = Request::from_parts(parts, Body::from(bytes));
+
+    if is_mcp && targets.workspaces.is_empty() {
+        targets.workspaces.push("default".to_string());
+    }
+    Ok((targets, request))
+}
+
+/// Workspaces named by the query string or the `routa-workspace-id` header.
+fn named_workspaces(request: &Request) -> Vec<String> {
+    let query_param = |name: &str| {
+        request.uri().query().and_then(|query| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .and_then(|(_, value)| {
+                    urlencoding::decode(&value.replace('+', " "))
+                        .ok()
+                        .map(|value| value.into_owned())
+                })
+        })
+    };
+    query_param("workspaceId")
+        .into_iter()
+        .chain(query_param("wsId"))
+        .chain(
+            request
+                .headers()
+                .get("routa-workspace-id")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        )
+        .filter_map(non_empty)
+        .collect()
+}
+
+/// Workspaces and resources addressed by the URL path itself.
+fn collect_path(targets: &mut Targets, path: &str) {
+    let first_segment = |prefix: &str| {
+        path.strip_prefix(prefix)
+            .and_then(|rest| rest.split('/').next())
+            .filter(|segment| !segment.is_empty())
+            .and_then(|segment| urlencoding::decode(segment).ok())
+            .map(|segment| segment.into_owned())
+    };
+    if let Some(workspace_id) = path
+        .strip_prefix("/api/notes/")
+        .and_then(|rest| rest.split_once('/'))
+        .map(|(workspace_id, _)| workspace_id.to_string())
+    {
+        targets.workspaces.extend(non_empty(workspace_id));
+    }
+    targets.workspaces.extend(first_segment("/api/workspaces/"));
+    targets.tasks.extend(
+        first_segment("/api/tasks/")
+            .filter(|segment| !TASK_COLLECTION_ROUTES.contains(&segment.as_str())),
+    );
+    targets.agents.extend(first_segment("/api/agents/"));
+    targets.sessions.extend(first_segment("/api/sessions/"));
+}
+
+/// Workspaces and resources named in a JSON body.
+fn collect_body(targets: &mut Targets, path: &str, body: &Value) {
+    let calls: Vec<&Value> = match body {
+        Value::Array(batch) => batch.iter().collect(),
+        call => vec![call],
+    };
+    match path {
+        "/api/rpc" | "/api/rpc/" => {
+            for call in calls {
+                let namespace = call
+                    .get("method")
+                    .and_then(Value::as_str)
+                    .and_then(|method| method.split_once('.'))
+                    .map(|(namespace, _)| namespace);
+                if let Some(params) = call.get("params") {
+                    targets.collect_params(params, namespace);
+                }
+            }
+        }
+        "/api/mcp" | "/api/mcp/" => {
+            for call in calls {
+                if call.get("method").and_then(Value::as_str) == Some("tools/call") {
+                    if let Some(arguments) = call.pointer("/params/arguments") {
+                        targets.collect_params(arguments, None);
+                    }
+                }
+            }
+        }
+        "/api/mcp/tools" | "/api/mcp/tools/" => {
+            targets.collect_params(body, None);
+            if let Some(args) = body.get("args") {
+                targets.collect_params(args, None);
+            }
+        }
+        _ => targets.workspaces.extend(
+            body.get("workspaceId")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .and_then(non_empty),
+        ),
+    }
+}
+
+fn non_empty(workspace_id: String) -> Option<String> {
+    let trimmed = workspace_id.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::{Task, TaskStatus};
+    use crate::models::workspace::Workspace;
+    use crate::state::{AppState, AppStateInner};
+    use crate::Database;
+    use axum::http::StatusCode;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn scoped_token_is_denied_another_workspaces_tasks() {
+        let state: AppState = Arc::new(AppStateInner::new(Database::open_in_memory().unwrap()));
+        for id in ["ws-a", "ws-b"] {
+            state
+                .workspace_store
+                .save(&Workspace::new(id.to_string(), id.to_string(), None))
+                .await
+                .unwrap();
+            let task = Task::new(
+                format!("task-{id}"),
+                "Task".to_string(),
+                "Do it".to_string(),
+                id.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            state.task_store.save(&task).await.unwrap();
+        }
+        let access = Arc::new(WorkspaceAccess::new(HashMap::from([(
+            "token-a".to_string(),
+            vec!["ws-a".to_string()],
+        )])));
+        let access_state = state.clone();
+        let app = axum::Router::new()
+            .nest("/api/tasks", crate::api::tasks::router())
+            .nest("/api/rpc", crate::api::rpc::router())
+            .nest("/api/mcp", crate::api::mcp_routes::router(state.clone()))
+            .nest("/api/mcp/tools", crate::api::mcp_tools::router())
+            .layer(axum::middleware::from_fn(move |request, next| {
+                enforce(access.clone(), access_state.clone(), request, next)
+            }))
+            .with_state(state.clone());
+
+        let get = |uri: &str, token: Option<&str>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some(token) = token {
+                builder = builder.header(header::AUTHORIZATION, format!("Bearer {token}"));
+            }
+            app.clone().oneshot(builder.body(Body::empty()).unwrap())
+        };
+
+        let own = get("/api/tasks?workspaceId=ws-a", Some("token-a"))
+            .await
+            .unwrap();
+        assert_eq!(own.status(), StatusCode::OK);
+        let other = get("/api/tasks?workspaceId=ws-b", Some("token-a"))
+            .await
+            .unwrap();
+        assert_eq!(other.status(), StatusCode::FORBIDDEN);
+        let anonymous = get("/api/tasks?workspaceId=ws-a", None).await.unwrap();
+        assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+        let unnamed = get("/api/tasks", Some("token-a")).await.unwrap();
+        assert_eq!(unnamed.status(), StatusCode::FORBIDDEN);
+
+        let own_by_id = get("/api/tasks/task-ws-a", Some("token-a")).await.unwrap();
+        assert_eq!(own_by_id.status(), StatusCode::OK);
+        let other_by_id = get("/api/tasks/task-ws-b", Some("token-a")).await.unwrap();
+        assert_eq!(other_by_id.status(), StatusCode::FORBIDDEN);
+        let unknown_by_id = get("/api/tasks/task-missing", Some("token-a"))
+            .await
+            .unwrap();
+        assert_eq!(unknown_by_id.status(), StatusCode::FORBIDDEN);
+
+        let post_json = |uri: &str, body: serde_json::Value| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header(header::AUTHORIZATION, "Bearer token-a")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::ACCEPT, "application/json, text/event-stream")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+        };
+        let rpc_get = |id: &str| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tasks.get",
+                "params": { "id": id }
+            })
+        };
+        let own_rpc = post_json("/api/rpc", rpc_get("task-ws-a")).await.unwrap();
+        assert_eq!(own_rpc.status(), StatusCode::OK);
+        let other_rpc = post_json("/api/rpc", rpc_get("task-ws-b")).await.unwrap();
+        assert_eq!(other_rpc.status(), StatusCode::FORBIDDEN);
+
+        let mcp_call = |arguments: serde_json::Value| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": { "name": "update_task_status", "arguments": arguments }
+            })
+        };
+        let other_mcp = post_json(
+            "/api/mcp?wsId=ws-a",
+            mcp_call(serde_json::json!({ "taskId": "task-ws-b", "status": "COMPLETED" })),
+        )
+        .await
+        .unwrap();
+        assert_eq!(other_mcp.status(), StatusCode::FORBIDDEN);
+        // Without a workspace the MCP server runs in `default`.
+        let default_mcp = post_json("/api/mcp", mcp_call(serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(default_mcp.status(), StatusCode::FORBIDDEN);
+        let other_mcp_tool = post_json(
+            "/api/mcp/tools",
+            serde_json::json!({
+                "name": "update_task_status",
+                "args": { "taskId": "task-ws-b", "status": "COMPLETED" },
+                "workspaceId": "ws-a"
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(other_mcp_tool.status(), StatusCode::FORBIDDEN);
+        let ws_b_task = state.task_store.get("task-ws-b").await.unwrap().unwrap();
+        assert_ne!(ws_b_task.status, TaskStatus::Completed);
+
+        let create = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/tasks")
+                    .header(header::AUTHORIZATION, "Bearer token-a")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        serde_json::json!({ "title": "x", "objective": "y", "workspaceId": "ws-b" })
+                            .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(create.status(), StatusCode::FORBIDDEN);
+        let ws_b_tasks = state.task_store.list_by_workspace("ws-b").await.unwrap();
+        assert_eq!(
+            ws_b_tasks.len(),
+            1,
+            "denied create must not reach the handler"
+        );
+
+        // Json also accepts `application/*+json` and any letter case, so
+        // those bodies must be inspected too, even when the query string
+        // names an allowed workspace.
+        let post_as = |content_type: &str, body: String| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/rpc?workspaceId=ws-a")
+                    .header(header::AUTHORIZATION, "Bearer token-a")
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+        };
+        let rpc_list = |workspace_id: &str| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tasks.list",
+                "params": { "workspaceId": workspace_id }
+            })
+            .to_string()
+        };
+        for content_type in ["application/vnd.api+json", "Application/JSON"] {
+            let other = post_as(content_type, rpc_list("ws-b")).await.unwrap();
+            assert_eq!(other.status(), StatusCode::FORBIDDEN, "{content_type}");
+            let own = post_as(content_type, rpc_list("ws-a")).await.unwrap();
+            assert_eq!(own.status(), StatusCode::OK, "{content_type}");
+        }
+        let unparseable = post_as("application/json", "{\"params\":".to_string())
+            .await
+            .unwrap();
+        assert_eq!(unparseable.status(), StatusCode::BAD_REQUEST);
+    }
+}
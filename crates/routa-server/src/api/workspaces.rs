@@ -8,6 +8,7 @@ use std::collections::HashMap;
 
 use crate::api::repo_context::canonical_repo_path_for_response;
 use crate::error::ServerError;
+use crate::models::agent_tree::build_agent_tree;
 use crate::models::codebase::Codebase;
 use crate::models::workspace::{Workspace, WorkspaceStatus};
 use crate::state::AppState;
@@ -22,6 +23,7 @@ pub fn router() -> Router<AppState> {
                 .patch(update_workspace),
         )
         .route("/{id}/archive", post(archive_workspace))
+        .route("/{id}/agents/tree", get(get_agent_tree))
 }
 
 #[derive(Debug, Deserialize)]
@@ -155,3 +157,15 @@ async fn delete_workspace(
     state.workspace_store.delete(&id).await?;
     Ok(Json(serde_json::json!({ "deleted": true })))
 }
+
+/// GET /api/workspaces/{id}/agents/tree — delegation hierarchy rooted at ROUTA agents.
+async fn get_agent_tree(
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Result<Json<serde_json::Value>, ServerError> {
+    let agents = state.agent_store.list_by_workspace(&id).await?;
+    let tasks = state.task_store.list_by_workspace(&id).await?;
+    Ok(Json(
+        serde_json::json!({ "roots": build_agent_tree(agents, &tasks) }),
+    ))
+}
@@ -6,7 +6,7 @@ use serde_json::{json, Value};
 
 use crate::error::ServerError;
 use crate::state::AppState;
-use routa_core::acp::{get_resume_capability, AcpSessionRecord};
+use routa_core::acp::{get_resume_capability, AcpCapabilities, AcpSessionRecord};
 use routa_core::store::acp_session_store::AcpSessionRow;
 
 #[derive(Clone)]
@@ -38,7 +38,14 @@ impl SessionApplicationService {
         let db_session = self.state.acp_session_store.get(session_id).await?;
 
         if let Some(session) = self.state.acp_manager.get_session(session_id).await {
-            let entry = SessionEntry::from_in_memory(session);
+            let mut entry = SessionEntry::from_in_memory(session);
+            entry.capabilities = self.state.acp_manager.get_capabilities(session_id).await;
+            entry.trace_path = self
+                .state
+                .acp_manager
+                .trace_path(session_id)
+                .await
+                .map(|path| path.to_string_lossy().to_string());
             let entry = match db_session.as_ref() {
                 Some(db_session) => entry.merge_db_state(db_session),
                 None => entry,
@@ -165,8 +172,13 @@ struct SessionEntry {
     updated_at: Option<Value>,
     parent_session_id: Option<String>,
     first_prompt_sent: bool,
+    metadata: Value,
     /// Whether there is an active in-memory process for this session.
     is_active: bool,
+    /// What the live agent advertised in `initialize`.
+    capabilities: Option<AcpCapabilities>,
+    /// JSONL file the live session's traces are appended to.
+    trace_path: Option<String>,
 }
 
 impl SessionEntry {
@@ -187,7 +199,10 @@ impl SessionEntry {
             updated_at: None,
             parent_session_id: session.parent_session_id,
             first_prompt_sent: session.first_prompt_sent,
+            metadata: session.metadata,
             is_active: true,
+            capabilities: None,
+            trace_path: None,
         }
     }
 
@@ -208,7 +223,10 @@ impl SessionEntry {
             updated_at: Some(Value::Number(session.updated_at.into())),
             parent_session_id: session.parent_session_id,
             first_prompt_sent: session.first_prompt_sent,
+            metadata: session.metadata,
             is_active: false,
+            capabilities: None,
+            trace_path: None,
         }
     }
 
@@ -234,6 +252,9 @@ impl SessionEntry {
         if self.routa_agent_id.is_none() {
             self.routa_agent_id = db.routa_agent_id.clone();
         }
+        if self.metadata.as_object().is_none_or(|map| map.is_empty()) {
+            self.metadata = db.metadata.clone();
+        }
         self.first_prompt_sent = self.first_prompt_sent || db.first_prompt_sent;
         self.updated_at = Some(Value::Number(db.updated_at.into()));
         self
@@ -304,6 +325,7 @@ impl SessionEntry {
             "updatedAt": self.updated_at,
             "firstPromptSent": self.first_prompt_sent,
             "parentSessionId": self.parent_session_id,
+            "metadata": self.metadata,
             "continuityStatus": self.continuity_status(),
             "resumeCapabilities": resume_cap.and_then(|c| serde_json::to_value(c).ok()),
         })
@@ -327,8 +349,11 @@ impl SessionEntry {
             "updatedAt": self.updated_at,
             "parentSessionId": self.parent_session_id,
             "firstPromptSent": self.first_prompt_sent,
+            "metadata": self.metadata,
             "continuityStatus": self.continuity_status(),
             "resumeCapabilities": resume_cap.and_then(|c| serde_json::to_value(c).ok()),
+            "capabilities": self.capabilities,
+            "tracePath": self.trace_path,
         })
     }
 
@@ -349,6 +374,7 @@ impl SessionEntry {
             "updatedAt": self.updated_at,
             "parentSessionId": self.parent_session_id,
             "firstPromptSent": self.first_prompt_sent,
+            "metadata": self.metadata,
         })
     }
 }
@@ -762,6 +788,7 @@ mod tests {
             parent_session_id: parent_session_id.map(str::to_string),
             specialist_id: None,
             specialist_system_prompt: None,
+            metadata: json!({}),
         }
     }
 
@@ -790,6 +817,7 @@ mod tests {
             created_at,
             updated_at: created_at,
             parent_session_id: parent_session_id.map(str::to_string),
+            metadata: json!({}),
         }
     }
 
@@ -922,6 +950,7 @@ mod tests {
                 custom_command: None,
                 custom_args: None,
                 parent_session_id: None,
+                metadata: None,
             })
             .await
             .expect("create session");
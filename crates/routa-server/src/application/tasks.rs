@@ -189,7 +189,7 @@ impl TaskApplicationService {
             task.scope = Some(value);
         }
         if let Some(value) = command.acceptance_criteria {
-            task.acceptance_criteria = Some(value);
+            task.replace_acceptance_criteria(value);
         }
         if let Some(value) = command.verification_commands {
             task.verification_commands = Some(value);
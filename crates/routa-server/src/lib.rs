@@ -40,16 +40,24 @@ pub use routa_core::{AppState, AppStateInner, Database, ServerError};
 pub mod api;
 mod application;
 pub mod feature_tree;
+pub mod workflow_triggers;
 
 // ── Server bootstrap ────────────────────────────────────────────────────
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
 use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
+/// How often expired event subscriptions are swept while the server runs.
+const SUBSCRIPTION_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
 /// Configuration for the Routa backend server.
 pub struct ServerConfig {
     pub host: String,
@@ -58,6 +66,61 @@ pub struct ServerConfig {
     /// Optional path to static frontend files (Next.js export).
     /// When set, the server serves these files for all non-API routes.
     pub static_dir: Option<String>,
+    /// Optional directory of workflow YAML files whose `cron` and
+    /// `file_watch` triggers are registered at startup.
+    pub workflows_dir: Option<String>,
+    /// Optional bearer token → allowed workspace IDs mapping. When set, the
+    /// agents, tasks, notes and sessions APIs require a listed token and
+    /// reject workspaces it does not cover (see [`api::workspace_access`]).
+    pub workspace_tokens: Option<HashMap<String, Vec<String>>>,
+    /// PEM certificate chain to serve HTTPS with. Requires `tls_key_path`;
+    /// when both are unset the server speaks plain HTTP.
+    pub tls_cert_path: Option<String>,
+    /// PEM private key matching `tls_cert_path`.
+    pub tls_key_path: Option<String>,
+    /// Live ACP sessions allowed per workspace (`0` for no limit). `None`
+    /// keeps `ROUTA_MAX_SESSIONS_PER_WORKSPACE` or the built-in default.
+    pub max_sessions_per_workspace: Option<usize>,
+    /// SQLCipher key for the database file (requires the `sqlcipher`
+    /// feature). `None` keeps `ROUTA_DB_KEY`; changing the key of an existing
+    /// file needs [`db::Database::rekey`] first.
+    pub db_encryption_key: Option<String>,
+}
+
+impl ServerConfig {
+    fn scheme(&self) -> &'static str {
+        if self.tls_cert_path.is_some() {
+            "https"
+        } else {
+            "http"
+        }
+    }
+
+    /// Load the configured certificate and key, or `None` for plain HTTP.
+    fn load_tls(&self) -> Result<Option<RustlsConfig>, String> {
+        let (cert_path, key_path) = match (&self.tls_cert_path, &self.tls_key_path) {
+            (None, None) => return Ok(None),
+            (Some(cert), Some(key)) => (cert, key),
+            _ => return Err("TLS requires both tls_cert_path and tls_key_path".to_string()),
+        };
+
+        let certs = CertificateDer::pem_file_iter(cert_path)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to load TLS certificate {cert_path}: {e}"))?;
+        if certs.is_empty() {
+            return Err(format!("No certificates found in {cert_path}"));
+        }
+        let key = PrivateKeyDer::from_pem_file(key_path)
+            .map_err(|e| format!("Failed to load TLS private key {key_path}: {e}"))?;
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut tls = rustls::ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+            .map_err(|e| format!("Invalid TLS certificate/key pair: {e}"))?;
+        tls.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(Some(RustlsConfig::from_config(Arc::new(tls))))
+    }
 }
 
 impl Default for ServerConfig {
@@ -67,6 +130,12 @@ impl Default for ServerConfig {
             port: 3210,
             db_path: "routa.db".to_string(),
             static_dir: None,
+            workflows_dir: None,
+            workspace_tokens: None,
+            tls_cert_path: None,
+            tls_key_path: None,
+            max_sessions_per_workspace: None,
+            db_encryption_key: None,
         }
     }
 }
@@ -76,7 +145,21 @@ impl Default for ServerConfig {
 /// This is useful when you need to share the state between the HTTP server
 /// and other consumers (e.g. Tauri IPC commands, JSON-RPC router).
 pub async fn create_app_state(db_path: &str) -> Result<state::AppState, String> {
-    let db = db::Database::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+    create_app_state_with_key(db_path, None).await
+}
+
+/// [`create_app_state`] with an explicit database encryption key; `None`
+/// falls back to `ROUTA_DB_KEY`.
+pub async fn create_app_state_with_key(
+    db_path: &str,
+    encryption_key: Option<&str>,
+) -> Result<state::AppState, String> {
+    let db = match encryption_key {
+        Some(key) => db::Database::open_with_key(db_path, Some(key)),
+        None => db::Database::open(db_path),
+    }
+    .map_err(|e| format!("Failed to open database: {e}"))?;
+    db.startup_integrity_check(db_path);
 
     let state: state::AppState = Arc::new(state::AppStateInner::new(db));
 
@@ -87,6 +170,20 @@ pub async fn create_app_state(db_path: &str) -> Result<state::AppState, String>
         .await
         .map_err(|e| format!("Failed to initialize default workspace: {e}"))?;
 
+    // Re-queue events persisted before the last shutdown (ROUTA_PERSIST_EVENTS)
+    state
+        .event_bus
+        .replay_pending()
+        .await
+        .map_err(|e| format!("Failed to replay persisted events: {e}"))?;
+
+    // Reconcile delegations that were in flight when the process last stopped
+    state
+        .orchestrator
+        .restore_delegations()
+        .await
+        .map_err(|e| format!("Failed to restore delegations: {e}"))?;
+
     // Discover skills
     let cwd = std::env::current_dir()
         .map(|p| p.to_string_lossy().to_string())
@@ -234,6 +331,218 @@ fn resolve_static_target(path: &str) -> (String, &'static str) {
     }
 }
 
+/// Corrects the content type `ServeDir` guessed for a file in a Next.js
+/// export: RSC payloads, source maps and WOFF fonts. Responses from the SPA
+/// fallback already carry the right type and are left alone.
+fn static_content_type_override(
+    static_dir: &std::path::Path,
+    path: &str,
+    served_type: &str,
+) -> Option<&'static str> {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())?;
+    let (guessed, corrected) = match extension {
+        "txt" if is_rsc_payload(static_dir, path) => {
+            ("text/plain", "text/x-component; charset=utf-8")
+        }
+        "map" => ("text/plain", "application/json"),
+        "woff" => ("application/font-woff", "font/woff"),
+        _ => return None,
+    };
+    served_type.starts_with(guessed).then_some(corrected)
+}
+
+/// Whether a `.txt` request is a Next.js RSC payload rather than a plain
+/// text file such as `robots.txt`. Exported routes pair each payload with
+/// an `.html` page of the same name.
+fn is_rsc_payload(static_dir: &std::path::Path, path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    path.starts_with("/workspace/")
+        || path.starts_with("/canvas/")
+        || file_name.starts_with("__next.")
+        || static_dir
+            .join(format!(
+                "{}.html",
+                path.trim_start_matches('/').trim_end_matches(".txt")
+            ))
+            .is_file()
+}
+
+/// Served in place of the app shell when `static_dir` has no `index.html`,
+/// usually because the frontend export hasn't been built yet.
+const FRONTEND_NOT_BUILT_HTML: &str = r#"<!doctype html>
+<html lang="en">
+<head><meta charset="utf-8"><title>Routa</title></head>
+<body style="font-family: system-ui, sans-serif; max-width: 40rem; margin: 4rem auto; line-height: 1.5">
+<h1>The Routa frontend hasn't been built</h1>
+<p>The server is running and its API is available under <code>/api</code>,
+but the static directory it was pointed at has no <code>index.html</code>.</p>
+<p>Build the frontend with <code>npm run build:static</code>, then restart the server.</p>
+</body>
+</html>
+"#;
+
+/// Serve a Next.js static export, mapping dynamic routes to their
+/// placeholder pages.
+fn static_frontend(static_dir: &str) -> Router {
+    if !std::path::Path::new(static_dir)
+        .join("index.html")
+        .is_file()
+    {
+        tracing::warn!(
+            "Static directory {} has no index.html; the frontend was probably not built. \
+             Serving a placeholder page until it is (run `npm run build:static`).",
+            static_dir
+        );
+    }
+
+    // For Next.js static export with dynamic routes, we need custom fallback logic.
+    // Next.js generates placeholder files for dynamic routes:
+    // - workspace/__placeholder__.html (for /workspace/[workspaceId])
+    // - workspace/__placeholder__/kanban.html (for /workspace/[workspaceId]/kanban)
+    // - workspace/__placeholder__/sessions/__placeholder__.html
+    //   (for /workspace/[workspaceId]/sessions/[sessionId])
+    //
+    // Additionally, Next.js client navigation requests .txt RSC payload files:
+    // - workspace/default/kanban.txt → workspace/__placeholder__/kanban.txt
+    // - workspace/default/sessions/abc123.txt
+    //   → workspace/__placeholder__/sessions/__placeholder__.txt
+    //
+    // We match the URL pattern and serve the corresponding placeholder file.
+    let fallback_static_dir = static_dir.to_string();
+    let fallback_service = tower::service_fn(move |req: axum::http::Request<axum::body::Body>| {
+        let static_dir = fallback_static_dir.clone();
+        async move {
+            let response = if req.method() == axum::http::Method::GET
+                || req.method() == axum::http::Method::HEAD
+            {
+                serve_static_fallback(&static_dir, req.uri().path()).await
+            } else {
+                not_found(req.uri().path())
+            };
+            Ok::<_, std::convert::Infallible>(response)
+        }
+    });
+
+    let serve_dir = tower_http::services::ServeDir::new(static_dir).fallback(fallback_service);
+    let types_static_dir = std::path::PathBuf::from(static_dir);
+    Router::new()
+        .fallback_service(serve_dir)
+        .layer(axum::middleware::from_fn(
+            move |request: axum::extract::Request, next: axum::middleware::Next| {
+                let static_dir = types_static_dir.clone();
+                async move {
+                    let path = request.uri().path().to_string();
+                    let mut response = next.run(request).await;
+                    let served_type = response
+                        .headers()
+                        .get(axum::http::header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default();
+                    if let Some(content_type) =
+                        static_content_type_override(&static_dir, &path, served_type)
+                    {
+                        response.headers_mut().insert(
+                            axum::http::header::CONTENT_TYPE,
+                            axum::http::HeaderValue::from_static(content_type),
+                        );
+                    }
+                    response
+                }
+            },
+        ))
+}
+
+/// Unmatched requests: JSON for API paths, plain text otherwise.
+fn not_found(path: &str) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    if path == "/api" || path.starts_with("/api/") {
+        ServerError::NotFound(format!("No route for {path}")).into_response()
+    } else {
+        (axum::http::StatusCode::NOT_FOUND, "Not found").into_response()
+    }
+}
+
+/// Files the browser loads directly (scripts, styles, images, RSC payloads);
+/// a missing one is a 404 rather than the app shell.
+fn is_static_asset_path(path: &str) -> bool {
+    path.rsplit('/')
+        .next()
+        .and_then(|name| std::path::Path::new(name).extension())
+        .is_some_and(|ext| ext != "html")
+}
+
+async fn serve_static_fallback(static_dir: &str, path: &str) -> axum::response::Response {
+    if path == "/api" || path.starts_with("/api/") {
+        return not_found(path);
+    }
+
+    let is_rsc_request = path.ends_with(".txt");
+    let (target_file, content_type) = resolve_static_target(path);
+
+    let file_path = std::path::Path::new(static_dir).join(&target_file);
+    tracing::debug!(
+        "SPA fallback: {} -> {} (rsc={})",
+        path,
+        file_path.to_string_lossy(),
+        is_rsc_request
+    );
+
+    let workspace_segments: Vec<&str> = path
+        .trim_start_matches("/workspace/")
+        .trim_end_matches(".txt")
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let should_rewrite_workspace_placeholder = path.starts_with("/workspace/")
+        && !workspace_segments.is_empty()
+        && workspace_segments
+            .get(1)
+            .map(|segment| *segment != "sessions")
+            .unwrap_or(true);
+    let actual_workspace_id = workspace_segments
+        .first()
+        .copied()
+        .unwrap_or("__placeholder__");
+
+    match tokio::fs::read(&file_path).await {
+        Ok(contents) => {
+            let body = if should_rewrite_workspace_placeholder {
+                let rewritten = String::from_utf8_lossy(&contents)
+                    .replace("__placeholder__", actual_workspace_id);
+                axum::body::Body::from(rewritten)
+            } else {
+                axum::body::Body::from(contents)
+            };
+
+            axum::http::Response::builder()
+                .status(axum::http::StatusCode::OK)
+                .header("content-type", content_type)
+                .body(body)
+                .unwrap()
+        }
+        Err(_) if is_static_asset_path(path) => not_found(path),
+        Err(_) => {
+            // Any other client-side route is handled by the app shell
+            let index_path = std::path::Path::new(static_dir).join("index.html");
+            let (status, body) = match tokio::fs::read(&index_path).await {
+                Ok(contents) => (axum::http::StatusCode::OK, axum::body::Body::from(contents)),
+                Err(_) => (
+                    axum::http::StatusCode::SERVICE_UNAVAILABLE,
+                    axum::body::Body::from(FRONTEND_NOT_BUILT_HTML),
+                ),
+            };
+            axum::http::Response::builder()
+                .status(status)
+                .header("content-type", "text/html; charset=utf-8")
+                .body(body)
+                .unwrap()
+        }
+    }
+}
+
 /// Start the embedded Rust backend server.
 ///
 /// Returns the actual address the server is listening on.
@@ -259,10 +568,11 @@ pub async fn start_server(config: ServerConfig) -> Result<SocketAddr, String> {
 
     std::env::set_var(
         "ROUTA_SERVER_URL",
-        format!("http://{}:{}", config.host, config.port),
+        format!("{}://{}:{}", config.scheme(), config.host, config.port),
     );
 
-    let state = create_app_state(&config.db_path).await?;
+    let state =
+        create_app_state_with_key(&config.db_path, config.db_encryption_key.as_deref()).await?;
 
     start_server_with_state(config, state).await
 }
@@ -277,121 +587,71 @@ pub async fn start_server_with_state(
 ) -> Result<SocketAddr, String> {
     std::env::set_var(
         "ROUTA_SERVER_URL",
-        format!("http://{}:{}", config.host, config.port),
+        format!("{}://{}:{}", config.scheme(), config.host, config.port),
     );
 
+    // Fail before anything starts if the configured certificate is unusable
+    let tls = config.load_tls()?;
+
+    if let Some(limit) = config.max_sessions_per_workspace {
+        state.acp_manager.set_max_sessions_per_workspace(limit);
+    }
+
+    if let Some(ref workflows_dir) = config.workflows_dir {
+        let triggers = workflow_triggers::register_workflow_triggers(
+            state.clone(),
+            std::path::Path::new(workflows_dir),
+        );
+        tracing::info!(
+            "Registered {} workflow trigger(s) from {}",
+            triggers.len(),
+            workflows_dir
+        );
+    }
+
+    let shutdown = state.shutdown_token.clone();
+    state
+        .event_bus
+        .spawn_subscription_sweeper(SUBSCRIPTION_SWEEP_INTERVAL, shutdown.clone());
+
     // Build router
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    let mut app = Router::new()
+    let mut app: Router<state::AppState> = Router::new()
         .merge(api::api_router(state.clone()))
-        .route("/api/health", axum::routing::get(health_check))
+        .route("/api/health", axum::routing::get(health_check));
+    if let Some(tokens) = config.workspace_tokens.clone() {
+        let access = Arc::new(api::workspace_access::WorkspaceAccess::new(tokens));
+        let access_state = state.clone();
+        app = app.layer(axum::middleware::from_fn(move |request, next| {
+            api::workspace_access::enforce(access.clone(), access_state.clone(), request, next)
+        }));
+    }
+    let mut app = app
         .layer(cors.clone())
         .layer(TraceLayer::new_for_http())
         .with_state(state);
 
     // Serve static frontend files if configured
-    if let Some(ref static_dir) = config.static_dir {
+    let static_dir = config.static_dir.as_deref().filter(|static_dir| {
         let static_path = std::path::Path::new(static_dir);
         if static_path.exists() && static_path.is_dir() {
-            tracing::info!("Serving static frontend from: {}", static_dir);
-
-            // For Next.js static export with dynamic routes, we need custom fallback logic.
-            // Next.js generates placeholder files for dynamic routes:
-            // - workspace/__placeholder__.html (for /workspace/[workspaceId])
-            // - workspace/__placeholder__/kanban.html (for /workspace/[workspaceId]/kanban)
-            // - workspace/__placeholder__/sessions/__placeholder__.html
-            //   (for /workspace/[workspaceId]/sessions/[sessionId])
-            //
-            // Additionally, Next.js client navigation requests .txt RSC payload files:
-            // - workspace/default/kanban.txt → workspace/__placeholder__/kanban.txt
-            // - workspace/default/sessions/abc123.txt
-            //   → workspace/__placeholder__/sessions/__placeholder__.txt
-            //
-            // We match the URL pattern and serve the corresponding placeholder file.
-            let static_dir_clone = static_dir.clone();
-            let fallback_service =
-                tower::service_fn(move |req: axum::http::Request<axum::body::Body>| {
-                    let static_dir = static_dir_clone.clone();
-                    async move {
-                        let path = req.uri().path();
-                        let is_rsc_request = path.ends_with(".txt");
-                        let (target_file, content_type) = resolve_static_target(path);
-
-                        let file_path = std::path::Path::new(&static_dir).join(&target_file);
-                        tracing::debug!(
-                            "SPA fallback: {} -> {} (rsc={})",
-                            path,
-                            file_path.to_string_lossy(),
-                            is_rsc_request
-                        );
-
-                        let workspace_segments: Vec<&str> = path
-                            .trim_start_matches("/workspace/")
-                            .trim_end_matches(".txt")
-                            .split('/')
-                            .filter(|segment| !segment.is_empty())
-                            .collect();
-                        let should_rewrite_workspace_placeholder = path.starts_with("/workspace/")
-                            && !workspace_segments.is_empty()
-                            && workspace_segments
-                                .get(1)
-                                .map(|segment| *segment != "sessions")
-                                .unwrap_or(true);
-                        let actual_workspace_id = workspace_segments
-                            .first()
-                            .copied()
-                            .unwrap_or("__placeholder__");
-
-                        let response = match tokio::fs::read(&file_path).await {
-                            Ok(contents) => {
-                                let body = if should_rewrite_workspace_placeholder {
-                                    let rewritten = String::from_utf8_lossy(&contents)
-                                        .replace("__placeholder__", actual_workspace_id);
-                                    axum::body::Body::from(rewritten)
-                                } else {
-                                    axum::body::Body::from(contents)
-                                };
-
-                                axum::http::Response::builder()
-                                    .status(axum::http::StatusCode::OK)
-                                    .header("content-type", content_type)
-                                    .body(body)
-                                    .unwrap()
-                            }
-                            Err(_) => {
-                                // If the specific file doesn't exist, fall back to index.html
-                                let index_path =
-                                    std::path::Path::new(&static_dir).join("index.html");
-                                match tokio::fs::read(&index_path).await {
-                                    Ok(contents) => axum::http::Response::builder()
-                                        .status(axum::http::StatusCode::OK)
-                                        .header("content-type", "text/html; charset=utf-8")
-                                        .body(axum::body::Body::from(contents))
-                                        .unwrap(),
-                                    Err(_) => axum::http::Response::builder()
-                                        .status(axum::http::StatusCode::NOT_FOUND)
-                                        .body(axum::body::Body::from("Not found"))
-                                        .unwrap(),
-                                }
-                            }
-                        };
-                        Ok::<_, std::convert::Infallible>(response)
-                    }
-                });
-
-            let serve_dir =
-                tower_http::services::ServeDir::new(static_dir).fallback(fallback_service);
-            app = app.fallback_service(serve_dir);
-        } else {
-            tracing::warn!(
-                "Static directory not found: {}. Frontend won't be served.",
-                static_dir
-            );
+            return true;
         }
+        tracing::warn!(
+            "Static directory not found: {}. Frontend won't be served.",
+            static_dir
+        );
+        false
+    });
+    if let Some(static_dir) = static_dir {
+        tracing::info!("Serving static frontend from: {}", static_dir);
+        app = app.fallback_service(static_frontend(static_dir));
+    } else {
+        app = app.fallback(|uri: axum::http::Uri| async move { not_found(uri.path()) });
     }
 
     // Bind and serve
@@ -407,14 +667,45 @@ pub async fn start_server_with_state(
         .local_addr()
         .map_err(|e| format!("Failed to get local address: {e}"))?;
 
-    tracing::info!("Routa backend server listening on {}", local_addr);
+    tracing::info!(
+        "Routa backend server listening on {}://{}",
+        config.scheme(),
+        local_addr
+    );
 
-    // Spawn the server in a background task
-    tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, app).await {
-            tracing::error!("Server error: {}", e);
+    // Spawn the server in a background task; it drains once the state shuts down
+    match tls {
+        Some(tls) => {
+            let listener = listener
+                .into_std()
+                .map_err(|e| format!("Failed to hand off listener: {e}"))?;
+            let handle = axum_server::Handle::new();
+            let drain = handle.clone();
+            tokio::spawn(async move {
+                shutdown.cancelled().await;
+                drain.graceful_shutdown(None);
+            });
+            tokio::spawn(async move {
+                if let Err(e) = axum_server::from_tcp_rustls(listener, tls)
+                    .handle(handle)
+                    .serve(app.into_make_service())
+                    .await
+                {
+                    tracing::error!("Server error: {}", e);
+                }
+            });
         }
-    });
+        None => {
+            tokio::spawn(async move {
+                if let Err(e) = axum::serve(listener, app)
+                    .with_graceful_shutdown(shutdown.cancelled_owned())
+                    .await
+                {
+                    tracing::error!("Server error: {}", e);
+                }
+            });
+        }
+    }
 
     Ok(local_addr)
 }
@@ -430,7 +721,120 @@ async fn health_check() -> axum::Json<serde_json::Value> {
 
 #[cfg(test)]
 mod tests {
-    use super::resolve_static_target;
+    use super::{resolve_static_target, static_frontend};
+    use tower::ServiceExt;
+
+    async fn get_static(static_dir: &std::path::Path, path: &str) -> (u16, String, String) {
+        let response = static_frontend(static_dir.to_str().unwrap())
+            .oneshot(
+                axum::http::Request::get(path)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            content_type,
+            String::from_utf8_lossy(&body).into_owned(),
+        )
+    }
+
+    fn write_static_file(static_dir: &std::path::Path, relative: &str, contents: &str) {
+        let path = static_dir.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[tokio::test]
+    async fn serves_app_shell_for_client_routes_and_json_for_api() {
+        let dir = tempfile::tempdir().unwrap();
+        write_static_file(dir.path(), "index.html", "<html>home</html>");
+
+        let (status, content_type, body) = get_static(dir.path(), "/settings/agents").await;
+        assert_eq!(status, 200);
+        assert_eq!(content_type, "text/html; charset=utf-8");
+        assert_eq!(body, "<html>home</html>");
+
+        let (status, content_type, body) = get_static(dir.path(), "/api/unknown").await;
+        assert_eq!(status, 404);
+        assert!(
+            content_type.starts_with("application/json"),
+            "{content_type}"
+        );
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert!(body["error"].as_str().unwrap().contains("/api/unknown"));
+
+        let (status, content_type, _) = get_static(dir.path(), "/_next/static/missing.js").await;
+        assert_eq!(status, 404);
+        assert!(!content_type.starts_with("text/html"), "{content_type}");
+    }
+
+    #[tokio::test]
+    async fn serves_placeholder_when_frontend_is_not_built() {
+        let dir = tempfile::tempdir().unwrap();
+
+        for path in ["/", "/settings/agents"] {
+            let (status, content_type, body) = get_static(dir.path(), path).await;
+            assert_eq!(status, 503, "{path}");
+            assert_eq!(content_type, "text/html; charset=utf-8");
+            assert!(
+                body.contains("frontend hasn't been built"),
+                "{path}: {body}"
+            );
+        }
+
+        let (status, content_type, _) = get_static(dir.path(), "/api/unknown").await;
+        assert_eq!(status, 404);
+        assert!(
+            content_type.starts_with("application/json"),
+            "{content_type}"
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_robots_txt_as_plain_text() {
+        let dir = tempfile::tempdir().unwrap();
+        write_static_file(dir.path(), "index.html", "<html>home</html>");
+        write_static_file(dir.path(), "robots.txt", "User-agent: *");
+
+        let (status, content_type, body) = get_static(dir.path(), "/robots.txt").await;
+        assert_eq!(status, 200);
+        assert!(content_type.starts_with("text/plain"), "{content_type}");
+        assert_eq!(body, "User-agent: *");
+    }
+
+    #[tokio::test]
+    async fn serves_rsc_payloads_as_components() {
+        let dir = tempfile::tempdir().unwrap();
+        write_static_file(dir.path(), "index.html", "<html>home</html>");
+        write_static_file(dir.path(), "settings.html", "<html>settings</html>");
+        write_static_file(dir.path(), "settings.txt", "0:settings");
+        write_static_file(
+            dir.path(),
+            "workspace/__placeholder__/kanban.txt",
+            "0:__placeholder__",
+        );
+
+        let (status, content_type, body) =
+            get_static(dir.path(), "/workspace/ws-1/kanban.txt").await;
+        assert_eq!(status, 200);
+        assert_eq!(content_type, "text/x-component; charset=utf-8");
+        assert_eq!(body, "0:ws-1");
+
+        let (_, content_type, _) = get_static(dir.path(), "/settings.txt").await;
+        assert_eq!(content_type, "text/x-component; charset=utf-8");
+    }
 
     #[test]
     fn resolves_workspace_overview_placeholder() {
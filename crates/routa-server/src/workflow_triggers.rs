@@ -0,0 +1,342 @@
+//! Cron and file-watch triggers for workflow YAML files.
+//!
+//! When the server starts with a workflows directory, every workflow in it
+//! whose `trigger.type` is `cron` (or `schedule`) or `file_watch` is
+//! registered here. A fired trigger creates the workflow's tasks exactly like
+//! `POST /api/workflows/{id}/trigger`.
+//!
+//! Triggers are single-flight per workflow: while the tasks of a workflow's
+//! last triggered run are still open, further firings of any of its triggers
+//! are skipped, so a fast schedule or a burst of file changes never stacks up
+//! overlapping runs. Workflows added after startup are picked up on restart.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use croner::Cron;
+use notify::{EventKind, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::api::workflows::start_workflow_run;
+use crate::models::task::TaskStatus;
+use crate::state::AppState;
+use routa_core::workflow::WorkflowDefinition;
+
+/// File changes arriving within this window are folded into one run.
+const FILE_WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Background tasks driving the registered triggers.
+///
+/// Dropping this detaches the tasks; they keep running until the state's
+/// [`shutdown`](crate::AppStateInner::shutdown) or the end of the runtime.
+/// Use [`WorkflowTriggers::shutdown`] to stop just these.
+pub struct WorkflowTriggers {
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl WorkflowTriggers {
+    /// Number of registered triggers.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Stop every trigger.
+    pub fn shutdown(self) {
+        for handle in self.handles {
+            handle.abort();
+        }
+    }
+}
+
+/// A workflow with at least one automatic trigger, shared by its triggers.
+struct TriggeredWorkflow {
+    id: String,
+    definition: WorkflowDefinition,
+    /// Task IDs of the last triggered run. Held locked while a run starts.
+    last_run: Mutex<Vec<String>>,
+}
+
+impl TriggeredWorkflow {
+    async fn fire(&self, state: &AppState, payload: &str) {
+        let mut last_run = self.last_run.lock().await;
+        if has_open_task(state, &last_run).await {
+            tracing::info!(
+                "[WorkflowTriggers] Skipping {}: previous run is still open",
+                self.id
+            );
+            return;
+        }
+
+        let workspace_id = self
+            .definition
+            .trigger
+            .workspace_id
+            .as_deref()
+            .unwrap_or("default");
+        match start_workflow_run(
+            state,
+            &self.id,
+            &self.definition,
+            workspace_id,
+            Some(payload),
+        )
+        .await
+        {
+            Ok(run) => {
+                tracing::info!(
+                    "[WorkflowTriggers] Started {} run {} ({} task(s))",
+                    self.id,
+                    run.workflow_run_id,
+                    run.task_ids.len()
+                );
+                *last_run = run.task_ids;
+            }
+            Err(e) => tracing::error!("[WorkflowTriggers] Failed to start {}: {}", self.id, e),
+        }
+    }
+}
+
+async fn has_open_task(state: &AppState, task_ids: &[String]) -> bool {
+    for task_id in task_ids {
+        if let Ok(Some(task)) = state.task_store.get(task_id).await {
+            if !matches!(task.status, TaskStatus::Completed | TaskStatus::Cancelled) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Register the cron and file_watch triggers of every workflow in `dir`.
+///
+/// Unreadable files, invalid YAML and invalid trigger settings are logged and
+/// skipped.
+pub fn register_workflow_triggers(state: AppState, dir: &Path) -> WorkflowTriggers {
+    let mut handles = Vec::new();
+    for (id, definition) in load_workflows(dir) {
+        let trigger = definition.trigger.clone();
+        if !trigger.is_cron() && !trigger.is_file_watch() {
+            continue;
+        }
+        let workflow = Arc::new(TriggeredWorkflow {
+            id,
+            definition,
+            last_run: Mutex::new(Vec::new()),
+        });
+
+        let handle = if trigger.is_cron() {
+            spawn_cron_trigger(state.clone(), workflow, trigger.cron.as_deref())
+        } else {
+            spawn_file_watch_trigger(state.clone(), workflow, &trigger.paths)
+        };
+        handles.extend(handle);
+    }
+    WorkflowTriggers { handles }
+}
+
+fn load_workflows(dir: &Path) -> Vec<(String, WorkflowDefinition)> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!(
+                "[WorkflowTriggers] Cannot read workflows dir {}: {}",
+                dir.display(),
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    let mut workflows = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if ext != "yaml" && ext != "yml" {
+            continue;
+        }
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_yaml::from_str::<WorkflowDefinition>(&content).map_err(|e| e.to_string())
+            });
+        match parsed {
+            Ok(definition) => workflows.push((id.to_string(), definition)),
+            Err(e) => tracing::warn!("[WorkflowTriggers] Skipping {}: {}", path.display(), e),
+        }
+    }
+    workflows.sort_by(|a, b| a.0.cmp(&b.0));
+    workflows
+}
+
+fn spawn_cron_trigger(
+    state: AppState,
+    workflow: Arc<TriggeredWorkflow>,
+    expression: Option<&str>,
+) -> Option<JoinHandle<()>> {
+    let expression = expression.map(str::trim).unwrap_or_default();
+    let cron = match Cron::new(expression).with_seconds_optional().parse() {
+        Ok(cron) => cron,
+        Err(e) => {
+            tracing::warn!(
+                "[WorkflowTriggers] Invalid cron '{}' for {}: {}",
+                expression,
+                workflow.id,
+                e
+            );
+            return None;
+        }
+    };
+
+    Some(tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            let next = match cron.find_next_occurrence(&now, false) {
+                Ok(next) => next,
+                Err(e) => {
+                    tracing::warn!(
+                        "[WorkflowTriggers] No next occurrence for {}: {}",
+                        workflow.id,
+                        e
+                    );
+                    return;
+                }
+            };
+            tokio::select! {
+                _ = state.shutdown_token.cancelled() => return,
+                _ = tokio::time::sleep((next - now).to_std().unwrap_or_default()) => {}
+            }
+            workflow.fire(&state, &next.to_rfc3339()).await;
+        }
+    }))
+}
+
+fn spawn_file_watch_trigger(
+    state: AppState,
+    workflow: Arc<TriggeredWorkflow>,
+    paths: &[String],
+) -> Option<JoinHandle<()>> {
+    if paths.is_empty() {
+        tracing::warn!(
+            "[WorkflowTriggers] file_watch trigger for {} has no paths",
+            workflow.id
+        );
+        return None;
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<Vec<PathBuf>>();
+    let mut watcher =
+        match notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            if let Ok(event) = result {
+                if !matches!(event.kind, EventKind::Access(_)) {
+                    let _ = tx.send(event.paths);
+                }
+            }
+        }) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                tracing::warn!(
+                    "[WorkflowTriggers] Cannot watch files for {}: {}",
+                    workflow.id,
+                    e
+                );
+                return None;
+            }
+        };
+    let mut watching = 0;
+    for path in paths {
+        if let Err(e) = watcher.watch(Path::new(path), RecursiveMode::Recursive) {
+            tracing::warn!(
+                "[WorkflowTriggers] Cannot watch {} for {}: {}",
+                path,
+                workflow.id,
+                e
+            );
+        } else {
+            watching += 1;
+        }
+    }
+    if watching == 0 {
+        return None;
+    }
+
+    Some(tokio::spawn(async move {
+        // The watcher stops when dropped, so it lives as long as this task.
+        let _watcher = watcher;
+        loop {
+            let mut changed = tokio::select! {
+                _ = state.shutdown_token.cancelled() => return,
+                changed = rx.recv() => match changed {
+                    Some(changed) => changed,
+                    None => return,
+                },
+            };
+            tokio::time::sleep(FILE_WATCH_DEBOUNCE).await;
+            while let Ok(more) = rx.try_recv() {
+                changed.extend(more);
+            }
+            changed.sort();
+            changed.dedup();
+            let payload = changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            workflow.fire(&state, &payload).await;
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AppStateInner, Database};
+
+    #[tokio::test]
+    async fn cron_trigger_fires_once_while_its_run_is_open() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("heartbeat.yaml"),
+            r#"
+name: Heartbeat
+trigger:
+  type: cron
+  cron: "* * * * * *"
+steps:
+  - name: Check
+    specialist: crafter
+    input: "Tick at ${trigger.payload}"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("manual.yaml"),
+            "name: Manual\nsteps:\n  - name: Only\n    specialist: crafter\n",
+        )
+        .unwrap();
+
+        let state: AppState = Arc::new(AppStateInner::new(Database::open_in_memory().unwrap()));
+        state.workspace_store.ensure_default().await.unwrap();
+
+        let triggers = register_workflow_triggers(state.clone(), dir.path());
+        assert_eq!(triggers.len(), 1);
+
+        // The schedule matches every second; later firings find the first
+        // run's task still pending and are skipped.
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+        triggers.shutdown();
+
+        let tasks = state.task_store.list_by_workspace("default").await.unwrap();
+        assert_eq!(tasks.len(), 1, "{tasks:?}");
+        assert_eq!(tasks[0].title, "[Heartbeat] Check");
+        assert!(tasks[0].objective.starts_with("Tick at "));
+    }
+}
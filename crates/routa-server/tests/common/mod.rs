@@ -19,6 +19,12 @@ impl ApiFixture {
             port: 0,
             db_path: db_path.to_string_lossy().to_string(),
             static_dir: None,
+            workflows_dir: None,
+            workspace_tokens: None,
+            tls_cert_path: None,
+            tls_key_path: None,
+            max_sessions_per_workspace: None,
+            db_encryption_key: None,
         };
 
         let addr = start_server(config)
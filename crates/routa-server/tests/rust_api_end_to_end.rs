@@ -189,6 +189,29 @@ async fn api_workspace_and_note_flow() {
     let notes: Value = list_notes.json().await.expect("decode notes list");
     assert!(notes.get("notes").and_then(Value::as_array).is_some());
 
+    for (window, expected) in [
+        ("updatedAfter=2000-01-01T00:00:00Z", 1),
+        ("updatedBefore=2000-01-01T00:00:00Z", 0),
+    ] {
+        let windowed: Value = fixture
+            .client
+            .get(fixture.endpoint(&format!("/api/notes?workspaceId={workspace_id}&{window}")))
+            .send()
+            .await
+            .expect("list notes in window")
+            .json()
+            .await
+            .expect("decode windowed notes list");
+        let ids: Vec<&str> = windowed["notes"]
+            .as_array()
+            .expect("notes array")
+            .iter()
+            .filter_map(|note| note["id"].as_str())
+            .filter(|id| *id == note_id)
+            .collect();
+        assert_eq!(ids.len(), expected, "{window}");
+    }
+
     let get_note = fixture
         .client
         .get(fixture.endpoint(&format!(
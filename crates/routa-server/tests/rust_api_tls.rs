@@ -0,0 +1,78 @@
+use std::fs;
+
+use reqwest::StatusCode;
+use routa_server::{start_server, ServerConfig};
+
+fn tls_config(cert_path: String, key_path: String) -> ServerConfig {
+    ServerConfig {
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        db_path: std::env::temp_dir()
+            .join(format!("routa-server-tls-{}.db", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string(),
+        tls_cert_path: Some(cert_path),
+        tls_key_path: Some(key_path),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn api_serves_https_with_self_signed_certificate() {
+    let temp = tempfile::tempdir().expect("tempdir should exist");
+    let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+        .expect("self-signed certificate");
+    let cert_path = temp.path().join("cert.pem");
+    let key_path = temp.path().join("key.pem");
+    fs::write(&cert_path, certified.cert.pem()).expect("write cert");
+    fs::write(&key_path, certified.key_pair.serialize_pem()).expect("write key");
+
+    let config = tls_config(
+        cert_path.to_string_lossy().to_string(),
+        key_path.to_string_lossy().to_string(),
+    );
+    let db_path = config.db_path.clone();
+    let addr = start_server(config).await.expect("start tls server");
+
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .expect("https client");
+    let resp = client
+        .get(format!("https://localhost:{}/api/health", addr.port()))
+        .send()
+        .await
+        .expect("https request should succeed");
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = resp.json().await.expect("health json");
+    assert_eq!(body["status"], "ok");
+
+    let plaintext = reqwest::get(format!("http://{addr}/api/health")).await;
+    assert!(plaintext.map_or(true, |resp| !resp.status().is_success()));
+
+    let _ = fs::remove_file(db_path);
+}
+
+#[tokio::test]
+async fn start_server_rejects_unreadable_tls_key() {
+    let temp = tempfile::tempdir().expect("tempdir should exist");
+    let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+        .expect("self-signed certificate");
+    let cert_path = temp.path().join("cert.pem");
+    fs::write(&cert_path, certified.cert.pem()).expect("write cert");
+
+    let config = tls_config(
+        cert_path.to_string_lossy().to_string(),
+        temp.path()
+            .join("missing.pem")
+            .to_string_lossy()
+            .to_string(),
+    );
+    let db_path = config.db_path.clone();
+    let err = start_server(config)
+        .await
+        .expect_err("missing key should fail startup");
+    assert!(err.contains("TLS private key"), "unexpected error: {err}");
+
+    let _ = fs::remove_file(db_path);
+}
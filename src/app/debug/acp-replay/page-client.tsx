@@ -56,7 +56,7 @@ export function AcpReplayDebugPageClient() {
       });
 
     const source = new EventSource(resolveApiPath(`/api/acp?${query.toString()}`));
-    source.onmessage = (event) => {
+    const onUpdate = (event: MessageEvent) => {
       try {
         const payload = JSON.parse(event.data) as { params?: ReplayEvent };
         const nextEvent = payload.params;
@@ -67,6 +67,8 @@ export function AcpReplayDebugPageClient() {
         setError(parseError instanceof Error ? parseError.message : parseSseFailedMessage);
       }
     };
+    source.onmessage = onUpdate;
+    source.addEventListener("session_update", onUpdate);
     source.onerror = () => {
       setStatus("error");
       setError(eventSourceDisconnectedMessage);
@@ -530,8 +530,10 @@ export class BrowserAcpClient {
         const lines = buffer.split("\n\n");
         buffer = lines.pop() || ""; // Keep incomplete event in buffer
 
-        for (const line of lines) {
-          if (!line.startsWith("data: ")) continue;
+        for (const frame of lines) {
+          // Frames may carry an `event:` line before the data
+          const line = frame.split("\n").find((field) => field.startsWith("data: "));
+          if (!line) continue;
 
           try {
             const jsonStr = line.slice(6); // Remove "data: " prefix
@@ -701,7 +703,9 @@ export class BrowserAcpClient {
       url.searchParams.delete("probe");
       this.eventSource = new EventSource(url.toString());
 
-      this.eventSource.onmessage = (event) => {
+      // The Rust backend names these frames `session_update`; the Next.js
+      // backend sends them as unnamed `message` events.
+      const onUpdate = (event: MessageEvent) => {
         try {
           if (event.lastEventId) {
             this.lastEventId = event.lastEventId;
@@ -722,6 +726,8 @@ export class BrowserAcpClient {
           console.error("[AcpClient] SSE parse error:", err);
         }
       };
+      this.eventSource.onmessage = onUpdate;
+      this.eventSource.addEventListener("session_update", onUpdate);
 
       this.eventSource.onerror = () => {
         // EventSource auto-reconnects on transient errors, but if the